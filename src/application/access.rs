@@ -0,0 +1,175 @@
+//! 资源归属校验
+//!
+//! 启用用户账号后，小说、音色、会话以及导出和预生成任务按所属用户隔离。
+//! 未启用账号（user_id 为 None）时不限制；没有所属用户的资源（预置音色、启用账号前创建的资源）
+//! 所有用户可读，但只有管理员可以修改或删除。
+//! 无权访问时按资源不存在处理，不暴露其他用户的资源；可读但无权修改时返回 403
+
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::error::ApplicationError;
+use crate::application::ports::{
+    ExportJob, ExportJobManagerPort, NovelRecord, NovelRepositoryPort, PregenerationJob,
+    PregenerationJobManagerPort, Session, SessionManagerPort, UserRepositoryPort, VoiceRecord,
+    VoiceRepositoryPort,
+};
+
+/// 用户能否读取归属于 `owner` 的资源
+pub fn can_access(owner: Option<Uuid>, user_id: Option<Uuid>) -> bool {
+    match (owner, user_id) {
+        (Some(owner), Some(user_id)) => owner == user_id,
+        _ => true,
+    }
+}
+
+/// 用户能否修改或删除归属于 `owner` 的资源（共享资源仅管理员）
+pub fn can_modify(owner: Option<Uuid>, user_id: Option<Uuid>, is_admin: bool) -> bool {
+    match (owner, user_id) {
+        (_, None) => true,
+        (Some(owner), Some(user_id)) => owner == user_id,
+        (None, Some(_)) => is_admin,
+    }
+}
+
+/// 资源归属校验
+#[derive(Clone)]
+pub struct AccessPolicy {
+    novel_repo: Arc<dyn NovelRepositoryPort>,
+    voice_repo: Arc<dyn VoiceRepositoryPort>,
+    session_manager: Arc<dyn SessionManagerPort>,
+    export_jobs: Arc<dyn ExportJobManagerPort>,
+    pregeneration_jobs: Arc<dyn PregenerationJobManagerPort>,
+    /// 启用账号时用于确认当前用户是否为管理员
+    users: Option<Arc<dyn UserRepositoryPort>>,
+}
+
+impl AccessPolicy {
+    pub fn new(
+        novel_repo: Arc<dyn NovelRepositoryPort>,
+        voice_repo: Arc<dyn VoiceRepositoryPort>,
+        session_manager: Arc<dyn SessionManagerPort>,
+        export_jobs: Arc<dyn ExportJobManagerPort>,
+        pregeneration_jobs: Arc<dyn PregenerationJobManagerPort>,
+        users: Option<Arc<dyn UserRepositoryPort>>,
+    ) -> Self {
+        Self {
+            novel_repo,
+            voice_repo,
+            session_manager,
+            export_jobs,
+            pregeneration_jobs,
+            users,
+        }
+    }
+
+    /// 校验用户能否修改归属于 `owner` 的资源（调用前已确认可读）
+    pub async fn check_modify(&self, owner: Option<Uuid>, user_id: Option<Uuid>) -> Result<(), ApplicationError> {
+        let is_admin = match (owner, user_id, &self.users) {
+            (None, Some(user_id), Some(users)) => users.find_by_id(user_id).await?.is_some_and(|user| user.is_admin),
+            _ => false,
+        };
+        if can_modify(owner, user_id, is_admin) {
+            Ok(())
+        } else {
+            Err(ApplicationError::Forbidden(
+                "Shared resources can only be modified by an admin".to_string(),
+            ))
+        }
+    }
+
+    /// 校验小说归属
+    pub async fn check_novel(&self, novel_id: Uuid, user_id: Option<Uuid>) -> Result<NovelRecord, ApplicationError> {
+        self.novel_repo
+            .find_by_id(novel_id)
+            .await?
+            .filter(|novel| can_access(novel.user_id, user_id))
+            .ok_or_else(|| ApplicationError::not_found("Novel", novel_id))
+    }
+
+    /// 校验小说归属并确认可以修改
+    pub async fn check_novel_mut(&self, novel_id: Uuid, user_id: Option<Uuid>) -> Result<NovelRecord, ApplicationError> {
+        let novel = self.check_novel(novel_id, user_id).await?;
+        self.check_modify(novel.user_id, user_id).await?;
+        Ok(novel)
+    }
+
+    /// 校验音色归属
+    pub async fn check_voice(&self, voice_id: Uuid, user_id: Option<Uuid>) -> Result<VoiceRecord, ApplicationError> {
+        self.voice_repo
+            .find_by_id(voice_id)
+            .await?
+            .filter(|voice| can_access(voice.user_id, user_id))
+            .ok_or_else(|| ApplicationError::not_found("Voice", voice_id))
+    }
+
+    /// 校验音色归属并确认可以修改
+    pub async fn check_voice_mut(&self, voice_id: Uuid, user_id: Option<Uuid>) -> Result<VoiceRecord, ApplicationError> {
+        let voice = self.check_voice(voice_id, user_id).await?;
+        self.check_modify(voice.user_id, user_id).await?;
+        Ok(voice)
+    }
+
+    /// 校验会话归属
+    pub fn check_session(&self, session_id: &str, user_id: Option<Uuid>) -> Result<Session, ApplicationError> {
+        self.session_manager
+            .get(session_id)
+            .ok()
+            .filter(|session| can_access(session.user_id, user_id))
+            .ok_or_else(|| ApplicationError::not_found_str("Session", session_id))
+    }
+
+    /// 校验导出任务归属
+    pub fn check_export_job(&self, job_id: &str, user_id: Option<Uuid>) -> Result<ExportJob, ApplicationError> {
+        self.export_jobs
+            .get(job_id)
+            .filter(|job| can_access(job.user_id, user_id))
+            .ok_or_else(|| ApplicationError::not_found_str("ExportJob", job_id))
+    }
+
+    /// 校验预生成任务归属
+    pub fn check_pregeneration_job(
+        &self,
+        job_id: &str,
+        user_id: Option<Uuid>,
+    ) -> Result<PregenerationJob, ApplicationError> {
+        self.pregeneration_jobs
+            .get(job_id)
+            .filter(|job| can_access(job.user_id, user_id))
+            .ok_or_else(|| ApplicationError::not_found_str("PregenerationJob", job_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_can_access() {
+        let alice = Uuid::new_v4();
+        let bob = Uuid::new_v4();
+
+        assert!(can_access(Some(alice), Some(alice)));
+        assert!(!can_access(Some(alice), Some(bob)));
+        // 无所属用户的资源共享
+        assert!(can_access(None, Some(bob)));
+        // 未启用账号时不限制
+        assert!(can_access(Some(alice), None));
+        assert!(can_access(None, None));
+    }
+
+    #[test]
+    fn test_can_modify() {
+        let alice = Uuid::new_v4();
+        let bob = Uuid::new_v4();
+
+        assert!(can_modify(Some(alice), Some(alice), false));
+        assert!(!can_modify(Some(alice), Some(bob), true));
+        // 共享资源只有管理员可以修改
+        assert!(!can_modify(None, Some(bob), false));
+        assert!(can_modify(None, Some(bob), true));
+        // 未启用账号时不限制
+        assert!(can_modify(None, None, false));
+        assert!(can_modify(Some(alice), None, false));
+    }
+}
@@ -0,0 +1,64 @@
+//! 自动前进模式
+//!
+//! 开启后预取按窗口上限向后提交推理；播放进入新片段时，下一片段已就绪则立即推送
+//! `NextSegmentReady`（带可直接播放的音频地址），否则由推理 Worker 在其完成时推送，
+//! 连续播放时客户端无需轮询或逐段请求
+
+use std::sync::Arc;
+
+use crate::application::ports::{NovelRepositoryPort, Session};
+use crate::application::readiness::{SegmentReadiness, SegmentStatusResolver};
+use crate::infrastructure::events::EventPublisher;
+
+/// 片段音频的直接播放地址（GET，按会话参数和角色配音解析）
+pub fn segment_audio_url(session: &Session, segment_index: u32) -> String {
+    format!(
+        "/api/audio/{}/{}/{}?session_id={}",
+        session.novel_id, session.voice_id, segment_index, session.id
+    )
+}
+
+/// 下一片段就绪通知
+pub struct AutoAdvanceNotifier {
+    novel_repo: Arc<dyn NovelRepositoryPort>,
+    segment_status: SegmentStatusResolver,
+    event_publisher: Arc<EventPublisher>,
+}
+
+impl AutoAdvanceNotifier {
+    pub fn new(
+        novel_repo: Arc<dyn NovelRepositoryPort>,
+        segment_status: SegmentStatusResolver,
+        event_publisher: Arc<EventPublisher>,
+    ) -> Self {
+        Self {
+            novel_repo,
+            segment_status,
+            event_publisher,
+        }
+    }
+
+    /// 播放进入 `current_index` 后调用：下一片段已缓存时推送 NextSegmentReady
+    pub async fn on_advance(&self, session: &Session, current_index: u32) {
+        if !session.auto_advance {
+            return;
+        }
+        let next_index = current_index + 1;
+        let segment = match self.novel_repo.find_segment(session.novel_id, next_index as usize).await {
+            Ok(Some(segment)) => segment,
+            Ok(None) => return,
+            Err(e) => {
+                tracing::warn!(session_id = %session.id, error = %e, "Failed to find next segment");
+                return;
+            }
+        };
+        let status = self.segment_status.resolve(session, &segment).await;
+        if status.readiness == SegmentReadiness::Ready {
+            self.event_publisher.publish_next_segment_ready(
+                &session.id,
+                next_index,
+                &segment_audio_url(session, next_index),
+            );
+        }
+    }
+}
@@ -0,0 +1,117 @@
+//! 角色配音解析
+//!
+//! 启用对话说话人识别时，根据小说的角色配音表为片段选择音色。
+//! 推理 Worker、推理提交与音频查询共用同一解析结果，保证缓存 key 一致。
+
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::ports::{
+    NovelRepositoryPort, RepositoryError, VoiceCastingRepositoryPort, VoiceKey, VoiceRepositoryPort,
+};
+use crate::domain::novel::detect_speaker;
+use crate::domain::voice::TtsConfig;
+
+/// 片段音色解析器
+#[derive(Clone)]
+pub struct VoiceCastingResolver {
+    novel_repo: Arc<dyn NovelRepositoryPort>,
+    casting_repo: Arc<dyn VoiceCastingRepositoryPort>,
+    voice_repo: Arc<dyn VoiceRepositoryPort>,
+    /// 是否启用对话说话人识别
+    enabled: bool,
+}
+
+impl VoiceCastingResolver {
+    pub fn new(
+        novel_repo: Arc<dyn NovelRepositoryPort>,
+        casting_repo: Arc<dyn VoiceCastingRepositoryPort>,
+        voice_repo: Arc<dyn VoiceRepositoryPort>,
+        enabled: bool,
+    ) -> Self {
+        Self {
+            novel_repo,
+            casting_repo,
+            voice_repo,
+            enabled,
+        }
+    }
+
+    /// 返回片段实际使用的音色；未识别到已配音的角色时使用会话音色
+    pub async fn resolve(&self, novel_id: Uuid, segment_index: u32, default_voice_id: Uuid) -> Uuid {
+        if !self.enabled {
+            return default_voice_id;
+        }
+
+        match self.find_cast_voice(novel_id, segment_index).await {
+            Ok(Some(voice_id)) => voice_id,
+            Ok(None) => default_voice_id,
+            Err(e) => {
+                tracing::warn!(
+                    novel_id = %novel_id,
+                    segment_index,
+                    error = %e,
+                    "Failed to resolve voice casting, using session voice"
+                );
+                default_voice_id
+            }
+        }
+    }
+
+    /// 返回片段实际使用音色的缓存 key 部分（含音色当前的合成配置）
+    pub async fn resolve_key(&self, novel_id: Uuid, segment_index: u32, default_voice_id: Uuid) -> VoiceKey {
+        let voice_id = self.resolve(novel_id, segment_index, default_voice_id).await;
+        self.voice_key(voice_id).await
+    }
+
+    /// 音色的缓存 key 部分；音色不存在或读取失败时按默认配置计算
+    pub async fn voice_key(&self, voice_id: Uuid) -> VoiceKey {
+        match self.voice_repo.find_by_id(voice_id).await {
+            Ok(Some(voice)) => VoiceKey::new(voice_id, voice.engine.as_deref(), &voice.tts_config),
+            Ok(None) => VoiceKey::new(voice_id, None, &TtsConfig::default()),
+            Err(e) => {
+                tracing::warn!(voice_id = %voice_id, error = %e, "Failed to load voice for cache key");
+                VoiceKey::new(voice_id, None, &TtsConfig::default())
+            }
+        }
+    }
+
+    async fn find_cast_voice(
+        &self,
+        novel_id: Uuid,
+        segment_index: u32,
+    ) -> Result<Option<Uuid>, RepositoryError> {
+        let castings = self.casting_repo.find_by_novel(novel_id).await?;
+        if castings.is_empty() {
+            return Ok(None);
+        }
+
+        // 说话人可能在相邻片段中，取前后各一个片段
+        let indices: Vec<u32> =
+            (segment_index.saturating_sub(1)..=segment_index.saturating_add(1)).collect();
+        let segments = self
+            .novel_repo
+            .find_segments_by_indices(novel_id, &indices)
+            .await?;
+        let content = |index: u32| {
+            segments
+                .iter()
+                .find(|s| s.index == index as usize)
+                .map(|s| s.content.as_str())
+        };
+
+        let Some(current) = content(segment_index) else {
+            return Ok(None);
+        };
+        let previous = segment_index.checked_sub(1).and_then(content);
+        let next = content(segment_index.saturating_add(1));
+
+        let voice_id = detect_speaker(previous, current, next).and_then(|name| {
+            castings
+                .iter()
+                .find(|c| c.character_name == name)
+                .map(|c| c.voice_id)
+        });
+        Ok(voice_id)
+    }
+}
@@ -0,0 +1,21 @@
+//! Cache Commands - 音频缓存管理命令
+
+use uuid::Uuid;
+
+/// 清除音频缓存命令
+///
+/// 至少指定一项；同时指定时分别清除
+#[derive(Debug, Clone)]
+pub struct PurgeAudioCache {
+    pub novel_id: Option<Uuid>,
+    pub voice_id: Option<Uuid>,
+    /// 清除 key 版本低于当前版本的条目（分段规则或合成流程变更后不再命中）
+    pub outdated: bool,
+}
+
+/// 核对存储与数据库记录命令
+///
+/// 删除没有记录的会话音频文件、文件已丢失的音频段落记录、已删除小说的缓存条目，
+/// 以及缓存内部的孤立数据
+#[derive(Debug, Clone, Default)]
+pub struct ReconcileStorage;
@@ -0,0 +1,13 @@
+//! Voice Casting Commands - 角色配音相关命令
+
+use uuid::Uuid;
+
+/// 设置角色配音命令
+///
+/// `voice_id` 为 None 时删除该角色的配音
+#[derive(Debug, Clone)]
+pub struct UpdateVoiceCasting {
+    pub novel_id: Uuid,
+    pub character_name: String,
+    pub voice_id: Option<Uuid>,
+}
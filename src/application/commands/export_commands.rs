@@ -0,0 +1,58 @@
+//! Export Commands - 音频导出
+
+use uuid::Uuid;
+
+use crate::application::ports::{AudioFormat, PlaybackHints};
+use crate::domain::voice::SynthesisParams;
+
+/// 导出章节音频命令
+///
+/// 将章节内已缓存的片段拼接为一个连续文件，用于离线收听
+#[derive(Debug, Clone)]
+pub struct ExportChapterAudio {
+    pub novel_id: Uuid,
+    /// 章节编号（从 1 开始，见 GetNovelChapters）
+    pub chapter: usize,
+    pub voice_id: Uuid,
+    /// 所属会话（用于取会话/片段级合成参数计算缓存 key）
+    pub session_id: Option<String>,
+    pub format: AudioFormat,
+    /// 是否裁剪每个片段首尾的静音
+    pub trim_silence: bool,
+    /// 相邻片段之间的静音（毫秒），None 时使用会话的衔接提示
+    pub gap_ms: Option<u32>,
+    /// 相邻片段的交叉淡化时长（毫秒），None 时使用会话的衔接提示
+    pub crossfade_ms: Option<u32>,
+}
+
+/// 导出章节音频响应
+#[derive(Debug, Clone)]
+pub struct ExportChapterAudioResponse {
+    pub audio_data: Vec<u8>,
+    /// 实际输出格式（编码器不支持请求的格式时为 WAV）
+    pub format: AudioFormat,
+    pub chapter_title: String,
+    pub duration_ms: u64,
+    /// 已拼接的片段数
+    pub exported_segments: usize,
+    /// 尚未合成（无缓存）而被跳过的片段索引
+    pub missing_segments: Vec<usize>,
+}
+
+/// 导出整部有声书命令（后台任务）
+///
+/// 合成缺失的片段后按章节拼接，输出带章节标记和元数据标签的单个文件
+#[derive(Debug, Clone)]
+pub struct ExportAudiobook {
+    pub novel_id: Uuid,
+    pub voice_id: Uuid,
+    /// 合成参数（与播放会话相同的参数可复用已缓存的音频）
+    pub params: SynthesisParams,
+    pub format: AudioFormat,
+    /// 是否裁剪每个片段首尾的静音
+    pub trim_silence: bool,
+    /// 片段衔接（静音与交叉淡化）
+    pub hints: PlaybackHints,
+    /// 发起导出的用户（任务归属该用户）
+    pub user_id: Option<Uuid>,
+}
@@ -0,0 +1,212 @@
+//! Cache Command Handlers - V2 架构
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::application::commands::{PurgeAudioCache, ReconcileStorage};
+use crate::application::error::ApplicationError;
+use crate::application::ports::{
+    AudioCachePort, AudioSegmentRepositoryPort, AudioStoragePort, NovelRepositoryPort, SessionRepositoryPort,
+};
+
+// ============================================================================
+// PurgeAudioCache
+// ============================================================================
+
+/// 清除音频缓存响应
+#[derive(Debug, Clone)]
+pub struct PurgeAudioCacheResponse {
+    /// 删除的缓存条目数
+    pub removed: usize,
+}
+
+/// PurgeAudioCache Handler
+pub struct PurgeAudioCacheHandler {
+    audio_cache: Arc<dyn AudioCachePort>,
+}
+
+impl PurgeAudioCacheHandler {
+    pub fn new(audio_cache: Arc<dyn AudioCachePort>) -> Self {
+        Self { audio_cache }
+    }
+
+    pub async fn handle(&self, command: PurgeAudioCache) -> Result<PurgeAudioCacheResponse, ApplicationError> {
+        if command.novel_id.is_none() && command.voice_id.is_none() && !command.outdated {
+            return Err(ApplicationError::validation("novel_id, voice_id or outdated is required"));
+        }
+
+        let mut removed = 0;
+        if let Some(novel_id) = command.novel_id {
+            removed += self
+                .audio_cache
+                .remove_by_novel(novel_id)
+                .await
+                .map_err(|e| ApplicationError::StorageError(e.to_string()))?;
+        }
+        if let Some(voice_id) = command.voice_id {
+            removed += self
+                .audio_cache
+                .remove_by_voice(voice_id)
+                .await
+                .map_err(|e| ApplicationError::StorageError(e.to_string()))?;
+        }
+        if command.outdated {
+            removed += self
+                .audio_cache
+                .remove_outdated()
+                .await
+                .map_err(|e| ApplicationError::StorageError(e.to_string()))?;
+        }
+
+        tracing::info!(
+            novel_id = ?command.novel_id,
+            voice_id = ?command.voice_id,
+            outdated = command.outdated,
+            removed,
+            "Audio cache purged"
+        );
+
+        Ok(PurgeAudioCacheResponse { removed })
+    }
+}
+
+// ============================================================================
+// ReconcileStorage
+// ============================================================================
+
+/// 核对存储响应
+#[derive(Debug, Clone, Default)]
+pub struct ReconcileStorageResponse {
+    /// 删除的无记录音频文件数
+    pub orphan_audio_files: u64,
+    /// 删除无记录音频文件释放的空间（字节）
+    pub freed_bytes: u64,
+    /// 删除的文件已丢失的音频段落记录数
+    pub missing_audio_records: usize,
+    /// 删除的属于已删除小说的缓存条目数
+    pub orphan_cache_entries: usize,
+    /// 缓存内部清理的孤立数据项数
+    pub cache_inconsistencies: usize,
+}
+
+/// ReconcileStorage Handler
+///
+/// 写入音频文件或缓存时进程崩溃会留下没有记录的文件，这里双向核对并清理
+pub struct ReconcileStorageHandler {
+    audio_cache: Arc<dyn AudioCachePort>,
+    audio_storage: Arc<dyn AudioStoragePort>,
+    session_repo: Arc<dyn SessionRepositoryPort>,
+    audio_segment_repo: Arc<dyn AudioSegmentRepositoryPort>,
+    novel_repo: Arc<dyn NovelRepositoryPort>,
+}
+
+impl ReconcileStorageHandler {
+    pub fn new(
+        audio_cache: Arc<dyn AudioCachePort>,
+        audio_storage: Arc<dyn AudioStoragePort>,
+        session_repo: Arc<dyn SessionRepositoryPort>,
+        audio_segment_repo: Arc<dyn AudioSegmentRepositoryPort>,
+        novel_repo: Arc<dyn NovelRepositoryPort>,
+    ) -> Self {
+        Self {
+            audio_cache,
+            audio_storage,
+            session_repo,
+            audio_segment_repo,
+            novel_repo,
+        }
+    }
+
+    pub async fn handle(&self, _command: ReconcileStorage) -> Result<ReconcileStorageResponse, ApplicationError> {
+        let mut response = ReconcileStorageResponse::default();
+
+        // 会话音频文件 -> 会话/段落记录
+        let files = self
+            .audio_storage
+            .list_audio()
+            .await
+            .map_err(|e| ApplicationError::StorageError(e.to_string()))?;
+        let mut files_by_session: HashMap<Uuid, Vec<_>> = HashMap::new();
+        for file in files {
+            files_by_session.entry(file.session_id).or_default().push(file);
+        }
+
+        let sessions: HashSet<Uuid> = self.session_repo.find_all().await?.into_iter().map(|s| s.id).collect();
+        for (session_id, files) in &files_by_session {
+            if !sessions.contains(session_id) {
+                self.audio_storage
+                    .delete_session_audio(*session_id)
+                    .await
+                    .map_err(|e| ApplicationError::StorageError(e.to_string()))?;
+                response.orphan_audio_files += files.len() as u64;
+                response.freed_bytes += files.iter().map(|f| f.size_bytes).sum::<u64>();
+                continue;
+            }
+
+            let recorded: HashSet<usize> = self
+                .audio_segment_repo
+                .find_by_session(*session_id)
+                .await?
+                .into_iter()
+                .map(|segment| segment.segment_index)
+                .collect();
+            for file in files.iter().filter(|f| !recorded.contains(&f.segment_index)) {
+                self.audio_storage
+                    .delete_audio(file.session_id, file.segment_index)
+                    .await
+                    .map_err(|e| ApplicationError::StorageError(e.to_string()))?;
+                response.orphan_audio_files += 1;
+                response.freed_bytes += file.size_bytes;
+            }
+        }
+
+        // 段落记录 -> 音频文件
+        for session_id in &sessions {
+            let stored: HashSet<usize> = files_by_session
+                .get(session_id)
+                .map(|files| files.iter().map(|f| f.segment_index).collect())
+                .unwrap_or_default();
+            for segment in self.audio_segment_repo.find_by_session(*session_id).await? {
+                if segment.audio_path.is_some() && !stored.contains(&segment.segment_index) {
+                    self.audio_segment_repo.delete(segment.id).await?;
+                    response.missing_audio_records += 1;
+                }
+            }
+        }
+
+        // 缓存引用 -> 小说记录
+        let usage = self
+            .audio_cache
+            .usage_by_novel()
+            .await
+            .map_err(|e| ApplicationError::StorageError(e.to_string()))?;
+        for novel in usage {
+            if self.novel_repo.find_by_id(novel.novel_id).await?.is_none() {
+                response.orphan_cache_entries += self
+                    .audio_cache
+                    .remove_by_novel(novel.novel_id)
+                    .await
+                    .map_err(|e| ApplicationError::StorageError(e.to_string()))?;
+            }
+        }
+
+        response.cache_inconsistencies = self
+            .audio_cache
+            .remove_orphans()
+            .await
+            .map_err(|e| ApplicationError::StorageError(e.to_string()))?;
+
+        tracing::info!(
+            orphan_audio_files = response.orphan_audio_files,
+            freed_bytes = response.freed_bytes,
+            missing_audio_records = response.missing_audio_records,
+            orphan_cache_entries = response.orphan_cache_entries,
+            cache_inconsistencies = response.cache_inconsistencies,
+            "Storage reconciled"
+        );
+
+        Ok(response)
+    }
+}
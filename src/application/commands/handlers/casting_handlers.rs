@@ -0,0 +1,67 @@
+//! Voice Casting Command Handlers - V2 架构
+
+use std::sync::Arc;
+
+use crate::application::commands::UpdateVoiceCasting;
+use crate::application::error::ApplicationError;
+use crate::application::ports::{
+    NovelRepositoryPort, VoiceCastingRepositoryPort, VoiceRepositoryPort,
+};
+
+/// UpdateVoiceCasting Handler
+pub struct UpdateVoiceCastingHandler {
+    casting_repo: Arc<dyn VoiceCastingRepositoryPort>,
+    novel_repo: Arc<dyn NovelRepositoryPort>,
+    voice_repo: Arc<dyn VoiceRepositoryPort>,
+}
+
+impl UpdateVoiceCastingHandler {
+    pub fn new(
+        casting_repo: Arc<dyn VoiceCastingRepositoryPort>,
+        novel_repo: Arc<dyn NovelRepositoryPort>,
+        voice_repo: Arc<dyn VoiceRepositoryPort>,
+    ) -> Self {
+        Self {
+            casting_repo,
+            novel_repo,
+            voice_repo,
+        }
+    }
+
+    pub async fn handle(&self, command: UpdateVoiceCasting) -> Result<(), ApplicationError> {
+        let novel_id = command.novel_id;
+        let character_name = command.character_name.trim();
+        if character_name.is_empty() {
+            return Err(ApplicationError::validation("Character name cannot be empty"));
+        }
+
+        self.novel_repo
+            .find_by_id(novel_id)
+            .await?
+            .ok_or_else(|| ApplicationError::not_found("Novel", novel_id))?;
+
+        let Some(voice_id) = command.voice_id else {
+            self.casting_repo.delete(novel_id, character_name).await?;
+            tracing::info!(novel_id = %novel_id, character = %character_name, "Voice casting cleared");
+            return Ok(());
+        };
+
+        self.voice_repo
+            .find_by_id(voice_id)
+            .await?
+            .ok_or_else(|| ApplicationError::not_found("Voice", voice_id))?;
+
+        self.casting_repo
+            .set(novel_id, character_name, voice_id)
+            .await?;
+
+        tracing::info!(
+            novel_id = %novel_id,
+            character = %character_name,
+            voice_id = %voice_id,
+            "Voice casting updated"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,534 @@
+//! Export Command Handlers - 音频导出
+
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::fs::File;
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+
+use crate::application::casting::VoiceCastingResolver;
+use crate::application::commands::{ExportAudiobook, ExportChapterAudio, ExportChapterAudioResponse};
+use crate::application::error::ApplicationError;
+use crate::application::ports::{
+    generate_cache_key_with_params, AudioCachePort, AudioFormat, AudioMetadata, AudioTranscoderPort,
+    ChapterMark, ConcatConfig, ExportJob, ExportJobManagerPort, ExportJobState, InferenceTask,
+    NovelRecord, NovelRepositoryPort, NovelStatus, PlaybackHints, Session, SessionManagerPort, StreamEnd,
+    TaskError, TaskManagerPort, TaskPriority, TaskState, TextSegmentRecord, TranscodeConfig, TranscodeError,
+    VoiceRepositoryPort,
+};
+use crate::domain::novel::detect_chapters;
+use crate::infrastructure::events::EventPublisher;
+
+/// 裁剪片段首尾静音的阈值（dBFS）
+const EXPORT_SILENCE_THRESHOLD_DB: f32 = -50.0;
+
+/// 有声书导出每批提交的推理任务数，避免占满推理队列影响正在播放的会话
+const AUDIOBOOK_BATCH_SIZE: usize = 16;
+
+/// 等待推理任务完成的轮询间隔
+const TASK_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+fn map_transcode_error(e: TranscodeError) -> ApplicationError {
+    match e {
+        TranscodeError::InvalidInput(msg) => ApplicationError::validation(msg),
+        e => ApplicationError::internal(e.to_string()),
+    }
+}
+
+fn storage_error(e: std::io::Error) -> ApplicationError {
+    ApplicationError::StorageError(e.to_string())
+}
+
+/// 写入流式编码的剩余输出，并回写头部
+async fn finish_file(file: &mut File, end: &StreamEnd) -> Result<(), ApplicationError> {
+    file.write_all(&end.data).await.map_err(storage_error)?;
+    if let Some(header) = &end.header {
+        file.seek(SeekFrom::Start(0)).await.map_err(storage_error)?;
+        file.write_all(header).await.map_err(storage_error)?;
+    }
+    file.flush().await.map_err(storage_error)
+}
+
+/// 导出文件的下载地址
+pub fn export_download_url(job_id: &str) -> String {
+    format!("/api/export/{}/download", job_id)
+}
+
+/// ExportChapterAudio Handler
+///
+/// 只拼接已缓存的片段，不触发推理；未合成的片段在响应中列出
+pub struct ExportChapterAudioHandler {
+    novel_repo: Arc<dyn NovelRepositoryPort>,
+    session_manager: Arc<dyn SessionManagerPort>,
+    audio_cache: Arc<dyn AudioCachePort>,
+    audio_transcoder: Arc<dyn AudioTranscoderPort>,
+    voice_casting: VoiceCastingResolver,
+    /// 输出的码率/采样率/声道数（格式按命令替换）
+    transcode_config: TranscodeConfig,
+}
+
+impl ExportChapterAudioHandler {
+    pub fn new(
+        novel_repo: Arc<dyn NovelRepositoryPort>,
+        session_manager: Arc<dyn SessionManagerPort>,
+        audio_cache: Arc<dyn AudioCachePort>,
+        audio_transcoder: Arc<dyn AudioTranscoderPort>,
+        voice_casting: VoiceCastingResolver,
+        transcode_config: TranscodeConfig,
+    ) -> Self {
+        Self {
+            novel_repo,
+            session_manager,
+            audio_cache,
+            audio_transcoder,
+            voice_casting,
+            transcode_config,
+        }
+    }
+
+    pub async fn handle(
+        &self,
+        cmd: ExportChapterAudio,
+    ) -> Result<ExportChapterAudioResponse, ApplicationError> {
+        // 会话已过期时按默认参数查找
+        let session = cmd
+            .session_id
+            .as_deref()
+            .and_then(|id| self.session_manager.get(id).ok());
+
+        // 未指定时使用会话的衔接提示
+        let session_hints = session.as_ref().map(|s| s.playback_hints).unwrap_or_default();
+        let hints = PlaybackHints {
+            gap_ms: cmd.gap_ms.unwrap_or(session_hints.gap_ms),
+            crossfade_ms: cmd.crossfade_ms.unwrap_or(session_hints.crossfade_ms),
+        };
+        hints.validate().map_err(ApplicationError::validation)?;
+
+        self.novel_repo
+            .find_by_id(cmd.novel_id)
+            .await?
+            .ok_or_else(|| ApplicationError::not_found("Novel", cmd.novel_id))?;
+
+        let segments = self.novel_repo.find_segments_by_novel_id(cmd.novel_id).await?;
+        let chapter = detect_chapters(segments.iter().map(|s| s.content.as_str()))
+            .into_iter()
+            .find(|c| c.number() == cmd.chapter)
+            .ok_or_else(|| ApplicationError::validation(format!("Chapter not found: {}", cmd.chapter)))?;
+
+        let mut clips = Vec::new();
+        let mut missing_segments = Vec::new();
+        for segment in &segments[chapter.start_segment_index()..chapter.end_segment_index()] {
+            let index = segment.index as u32;
+            let voice = self.voice_casting.resolve_key(cmd.novel_id, index, cmd.voice_id).await;
+            let params = session
+                .as_ref()
+                .map(|s| s.params_for(index))
+                .unwrap_or_default();
+            let cache_key = generate_cache_key_with_params(&segment.content, &voice, &params);
+
+            match self.audio_cache.get(&cache_key).await {
+                Ok(Some(audio)) => clips.push(Vec::from(audio)),
+                Ok(None) => missing_segments.push(segment.index),
+                Err(e) => {
+                    tracing::warn!(cache_key = %cache_key, error = %e, "Failed to read cached audio for export");
+                    missing_segments.push(segment.index);
+                }
+            }
+        }
+
+        if clips.is_empty() {
+            return Err(ApplicationError::invalid_state(format!(
+                "No synthesized audio in chapter {}",
+                cmd.chapter
+            )));
+        }
+
+        let config = ConcatConfig {
+            trim_silence_db: cmd.trim_silence.then_some(EXPORT_SILENCE_THRESHOLD_DB),
+            gap_ms: hints.gap_ms,
+            crossfade_ms: hints.crossfade_ms,
+            output: TranscodeConfig {
+                format: cmd.format,
+                ..self.transcode_config.clone()
+            },
+            metadata: AudioMetadata {
+                title: Some(chapter.title().to_string()),
+                ..Default::default()
+            },
+        };
+        let result = self
+            .audio_transcoder
+            .concat(&clips, &config)
+            .await
+            .map_err(map_transcode_error)?;
+
+        tracing::info!(
+            novel_id = %cmd.novel_id,
+            chapter = cmd.chapter,
+            exported = clips.len(),
+            missing = missing_segments.len(),
+            duration_ms = result.duration_ms,
+            "Chapter audio exported"
+        );
+
+        Ok(ExportChapterAudioResponse {
+            audio_data: result.audio_data,
+            format: result.format,
+            chapter_title: chapter.title().to_string(),
+            duration_ms: result.duration_ms,
+            exported_segments: clips.len(),
+            missing_segments,
+        })
+    }
+}
+
+/// ExportAudiobook Handler
+///
+/// `handle` 校验并创建导出任务；`run` 在后台执行：
+/// 1. 以专用会话分批提交缺失片段的推理任务，等待 Worker 合成完成
+/// 2. 按章节拼接为 WAV，记录各章节起始时间
+/// 3. 合并所有章节并编码，写入章节标记和元数据标签
+///
+/// 进度通过全局 WebSocket 事件（ExportProgress / ExportReady / ExportFailed）通知
+pub struct ExportAudiobookHandler {
+    novel_repo: Arc<dyn NovelRepositoryPort>,
+    voice_repo: Arc<dyn VoiceRepositoryPort>,
+    session_manager: Arc<dyn SessionManagerPort>,
+    task_manager: Arc<dyn TaskManagerPort>,
+    audio_cache: Arc<dyn AudioCachePort>,
+    audio_transcoder: Arc<dyn AudioTranscoderPort>,
+    export_jobs: Arc<dyn ExportJobManagerPort>,
+    event_publisher: Arc<EventPublisher>,
+    voice_casting: VoiceCastingResolver,
+    /// 输出的码率/采样率/声道数（格式按命令替换）
+    transcode_config: TranscodeConfig,
+    exports_dir: PathBuf,
+}
+
+impl ExportAudiobookHandler {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        novel_repo: Arc<dyn NovelRepositoryPort>,
+        voice_repo: Arc<dyn VoiceRepositoryPort>,
+        session_manager: Arc<dyn SessionManagerPort>,
+        task_manager: Arc<dyn TaskManagerPort>,
+        audio_cache: Arc<dyn AudioCachePort>,
+        audio_transcoder: Arc<dyn AudioTranscoderPort>,
+        export_jobs: Arc<dyn ExportJobManagerPort>,
+        event_publisher: Arc<EventPublisher>,
+        voice_casting: VoiceCastingResolver,
+        transcode_config: TranscodeConfig,
+        exports_dir: PathBuf,
+    ) -> Self {
+        Self {
+            novel_repo,
+            voice_repo,
+            session_manager,
+            task_manager,
+            audio_cache,
+            audio_transcoder,
+            export_jobs,
+            event_publisher,
+            voice_casting,
+            transcode_config,
+            exports_dir,
+        }
+    }
+
+    /// 创建导出任务（不执行导出）
+    pub async fn handle(&self, cmd: &ExportAudiobook) -> Result<ExportJob, ApplicationError> {
+        cmd.hints.validate().map_err(ApplicationError::validation)?;
+        cmd.params.validate().map_err(ApplicationError::validation)?;
+
+        let novel = self
+            .novel_repo
+            .find_by_id(cmd.novel_id)
+            .await?
+            .ok_or_else(|| ApplicationError::not_found("Novel", cmd.novel_id))?;
+        if novel.status != NovelStatus::Ready {
+            return Err(ApplicationError::invalid_state(format!(
+                "Novel is not ready: {}",
+                novel.status.as_str()
+            )));
+        }
+        self.voice_repo
+            .find_by_id(cmd.voice_id)
+            .await?
+            .ok_or_else(|| ApplicationError::not_found("Voice", cmd.voice_id))?;
+
+        let job = ExportJob::new(cmd.novel_id, cmd.voice_id, cmd.format, novel.total_segments).with_user(cmd.user_id);
+        self.export_jobs.create(job.clone());
+
+        tracing::info!(job_id = %job.job_id, novel_id = %cmd.novel_id, "Audiobook export job created");
+        Ok(job)
+    }
+
+    /// 执行导出任务，结果记录在任务状态中
+    pub async fn run(&self, job_id: String, cmd: ExportAudiobook) {
+        match self.export(&job_id, &cmd).await {
+            Ok((path, format)) => {
+                tracing::info!(job_id = %job_id, path = %path.display(), "Audiobook export completed");
+                self.export_jobs.set_ready(&job_id, path, format);
+                self.event_publisher
+                    .publish_export_ready(&job_id, cmd.novel_id, &export_download_url(&job_id), cmd.user_id);
+            }
+            Err(e) => {
+                tracing::error!(job_id = %job_id, error = %e, "Audiobook export failed");
+                self.export_jobs.set_failed(&job_id, e.to_string());
+                self.event_publisher
+                    .publish_export_failed(&job_id, cmd.novel_id, &e.to_string(), cmd.user_id);
+            }
+        }
+    }
+
+    async fn export(
+        &self,
+        job_id: &str,
+        cmd: &ExportAudiobook,
+    ) -> Result<(PathBuf, AudioFormat), ApplicationError> {
+        let novel = self
+            .novel_repo
+            .find_by_id(cmd.novel_id)
+            .await?
+            .ok_or_else(|| ApplicationError::not_found("Novel", cmd.novel_id))?;
+        let segments = self.novel_repo.find_segments_by_novel_id(cmd.novel_id).await?;
+        if segments.is_empty() {
+            return Err(ApplicationError::invalid_state("Novel has no segments"));
+        }
+
+        // Worker 只处理有效会话的任务，导出使用专用会话
+        let session = Session::new(cmd.novel_id, cmd.voice_id, 0)
+            .with_params(cmd.params.clone())
+            .as_background();
+        let session_id = self
+            .session_manager
+            .create(session.clone())
+            .map_err(|e| ApplicationError::internal(e.to_string()))?;
+
+        let cache_keys = self.synthesize(job_id, &session, &segments).await;
+        self.task_manager.cleanup_session(&session_id);
+        let _ = self.session_manager.close(&session_id);
+
+        self.export_jobs.set_state(job_id, ExportJobState::Merging);
+        self.event_publisher.publish_export_progress(
+            job_id,
+            cmd.novel_id,
+            ExportJobState::Merging.as_str(),
+            segments.len(),
+            segments.len(),
+            cmd.user_id,
+        );
+
+        let cache_keys = cache_keys?;
+
+        // 章节中间文件放在任务的临时目录，结束后删除
+        let parts_dir = self.exports_dir.join(format!("{}.parts", job_id));
+        tokio::fs::create_dir_all(&parts_dir).await.map_err(storage_error)?;
+        let result = self.merge(job_id, cmd, &novel, &segments, &cache_keys, &parts_dir).await;
+        let _ = tokio::fs::remove_dir_all(&parts_dir).await;
+        result
+    }
+
+    /// 分批合成缺失的片段，按片段顺序返回所有片段的缓存 key
+    async fn synthesize(
+        &self,
+        job_id: &str,
+        session: &Session,
+        segments: &[TextSegmentRecord],
+    ) -> Result<Vec<String>, ApplicationError> {
+        let mut all_keys = Vec::with_capacity(segments.len());
+        let owner = self.export_jobs.get(job_id).and_then(|job| job.user_id);
+
+        for batch in segments.chunks(AUDIOBOOK_BATCH_SIZE) {
+            let mut cache_keys = Vec::with_capacity(batch.len());
+            let mut tasks = Vec::new();
+            for segment in batch {
+                let index = segment.index as u32;
+                let voice = self.voice_casting.resolve_key(session.novel_id, index, session.voice_id).await;
+                let params = session.params_for(index);
+                let cache_key = generate_cache_key_with_params(&segment.content, &voice, &params);
+                let cached = self.audio_cache.link(&cache_key, session.novel_id, index, voice.voice_id).await;
+                if !matches!(cached, Ok(true)) {
+                    tasks.push(
+                        InferenceTask::new(
+                            session.id.clone(),
+                            session.novel_id,
+                            session.voice_id,
+                            index,
+                            segment.content.clone(),
+                        )
+                        .with_params(params)
+                        .with_priority(TaskPriority::Background),
+                    );
+                }
+                cache_keys.push(cache_key);
+            }
+
+            // 队列已满时等待播放中的会话的任务消化后再提交
+            let task_ids = loop {
+                match self.task_manager.submit(tasks.clone()) {
+                    Err(TaskError::QueueFull { .. }) => tokio::time::sleep(TASK_POLL_INTERVAL).await,
+                    result => break result?,
+                }
+            };
+            self.wait_for_tasks(&session.id, &task_ids).await?;
+            all_keys.extend(cache_keys);
+
+            self.export_jobs.update_progress(job_id, all_keys.len());
+            self.event_publisher.publish_export_progress(
+                job_id,
+                session.novel_id,
+                ExportJobState::Synthesizing.as_str(),
+                all_keys.len(),
+                segments.len(),
+                owner,
+            );
+        }
+
+        Ok(all_keys)
+    }
+
+    async fn load_clip(&self, cache_key: &str, segment_index: usize) -> Result<Vec<u8>, ApplicationError> {
+        self.audio_cache
+            .get(cache_key)
+            .await
+            .map_err(|e| ApplicationError::internal(e.to_string()))?
+            .map(Vec::from)
+            .ok_or_else(|| ApplicationError::internal(format!("Audio missing for segment {}", segment_index)))
+    }
+
+    /// 等待推理任务全部完成，任一失败时返回错误
+    async fn wait_for_tasks(&self, session_id: &str, task_ids: &[String]) -> Result<(), ApplicationError> {
+        loop {
+            let mut all_ready = true;
+            for task_id in task_ids {
+                let task = self
+                    .task_manager
+                    .get_task(task_id)
+                    .ok_or_else(|| ApplicationError::internal(format!("Task disappeared: {}", task_id)))?;
+                match task.state {
+                    TaskState::Ready => {}
+                    TaskState::Pending | TaskState::Inferring => all_ready = false,
+                    TaskState::Failed | TaskState::Cancelled => {
+                        return Err(ApplicationError::ExternalServiceError(format!(
+                            "Synthesis failed for segment {}: {}",
+                            task.segment_index,
+                            task.error_message.unwrap_or_else(|| task.state.as_str().to_string())
+                        )));
+                    }
+                }
+            }
+            if all_ready {
+                return Ok(());
+            }
+
+            // 保持会话活跃，避免被当作空闲会话清理
+            self.session_manager.touch(session_id);
+            tokio::time::sleep(TASK_POLL_INTERVAL).await;
+        }
+    }
+
+    /// 按章节拼接后合并为单个文件，写入导出目录
+    ///
+    /// 先逐章流式拼接为 WAV 中间文件得到章节时长（章节标记写在输出开头），
+    /// 再依次追加到最终输出；同一时刻只在内存中保留一章的片段
+    async fn merge(
+        &self,
+        job_id: &str,
+        cmd: &ExportAudiobook,
+        novel: &NovelRecord,
+        segments: &[TextSegmentRecord],
+        cache_keys: &[String],
+        parts_dir: &Path,
+    ) -> Result<(PathBuf, AudioFormat), ApplicationError> {
+        let chapter_config = ConcatConfig {
+            trim_silence_db: cmd.trim_silence.then_some(EXPORT_SILENCE_THRESHOLD_DB),
+            gap_ms: cmd.hints.gap_ms,
+            crossfade_ms: cmd.hints.crossfade_ms,
+            output: TranscodeConfig {
+                format: AudioFormat::Wav,
+                ..self.transcode_config.clone()
+            },
+            metadata: AudioMetadata::default(),
+        };
+
+        let mut chapter_files = Vec::new();
+        let mut marks = Vec::new();
+        let mut offset_ms = 0;
+        for chapter in detect_chapters(segments.iter().map(|s| s.content.as_str())) {
+            let path = parts_dir.join(format!("{}.wav", chapter.number()));
+            let mut file = File::create(&path).await.map_err(storage_error)?;
+            let mut stream = self
+                .audio_transcoder
+                .concat_stream(&chapter_config)
+                .map_err(map_transcode_error)?;
+            let range = chapter.start_segment_index()..chapter.end_segment_index();
+            for (index, cache_key) in range.clone().zip(&cache_keys[range]) {
+                let clip = self.load_clip(cache_key, index).await?;
+                let data = stream.push(&clip).map_err(map_transcode_error)?;
+                file.write_all(&data).await.map_err(storage_error)?;
+            }
+
+            match stream.finish() {
+                Ok(end) => {
+                    finish_file(&mut file, &end).await?;
+                    marks.push(ChapterMark {
+                        start_ms: offset_ms,
+                        title: chapter.title().to_string(),
+                    });
+                    // 章节之间同样插入片段间静音
+                    offset_ms += end.duration_ms + cmd.hints.gap_ms as u64;
+                    chapter_files.push(path);
+                }
+                // 整章均为静音时跳过
+                Err(TranscodeError::InvalidInput(msg)) => {
+                    tracing::warn!(job_id = %job_id, chapter = chapter.number(), error = %msg, "Skipping chapter");
+                }
+                Err(e) => return Err(map_transcode_error(e)),
+            }
+        }
+
+        let artist = self
+            .voice_repo
+            .find_by_id(cmd.voice_id)
+            .await?
+            .map(|voice| voice.name);
+        let config = ConcatConfig {
+            trim_silence_db: None,
+            gap_ms: cmd.hints.gap_ms,
+            crossfade_ms: 0,
+            output: TranscodeConfig {
+                format: cmd.format,
+                ..self.transcode_config.clone()
+            },
+            metadata: AudioMetadata {
+                title: Some(novel.title.clone()),
+                album: Some(novel.title.clone()),
+                artist,
+                track_number: None,
+                chapters: marks,
+            },
+        };
+
+        // 输出格式在结束时确定（不支持的格式回退为 WAV），先写入临时文件
+        let partial_path = parts_dir.join("output");
+        let mut file = File::create(&partial_path).await.map_err(storage_error)?;
+        let mut stream = self
+            .audio_transcoder
+            .concat_stream(&config)
+            .map_err(map_transcode_error)?;
+        for chapter_path in &chapter_files {
+            let chapter_audio = tokio::fs::read(chapter_path).await.map_err(storage_error)?;
+            let data = stream.push(&chapter_audio).map_err(map_transcode_error)?;
+            file.write_all(&data).await.map_err(storage_error)?;
+        }
+        let end = stream.finish().map_err(map_transcode_error)?;
+        finish_file(&mut file, &end).await?;
+
+        let path = self.exports_dir.join(format!("{}.{}", job_id, end.format));
+        tokio::fs::rename(&partial_path, &path).await.map_err(storage_error)?;
+
+        Ok((path, end.format))
+    }
+}
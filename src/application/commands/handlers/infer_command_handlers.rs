@@ -2,19 +2,26 @@
 
 use std::sync::Arc;
 
+use crate::application::access::can_access;
+use crate::application::casting::VoiceCastingResolver;
 use crate::application::commands::infer_commands::*;
 use crate::application::error::ApplicationError;
+use crate::application::pagination::Page;
 use crate::application::ports::{
-    generate_cache_key, AudioCachePort, InferenceTask, NovelRepositoryPort, SessionManagerPort,
-    TaskManagerPort, TaskState,
+    generate_cache_key_with_params, AudioCachePort, InferenceTask, NovelRepositoryPort, SessionManagerPort,
+    TaskEventFilter, TaskEventRepositoryPort, TaskManagerPort, TaskPriority, TaskState,
 };
 
+/// 单次查询任务审计日志的条数上限
+const MAX_TASK_EVENTS: usize = 1000;
+
 /// SubmitInfer Handler - 提交推理任务
 pub struct SubmitInferHandler {
     session_manager: Arc<dyn SessionManagerPort>,
     task_manager: Arc<dyn TaskManagerPort>,
     novel_repo: Arc<dyn NovelRepositoryPort>,
     audio_cache: Arc<dyn AudioCachePort>,
+    voice_casting: VoiceCastingResolver,
 }
 
 impl SubmitInferHandler {
@@ -23,16 +30,29 @@ impl SubmitInferHandler {
         task_manager: Arc<dyn TaskManagerPort>,
         novel_repo: Arc<dyn NovelRepositoryPort>,
         audio_cache: Arc<dyn AudioCachePort>,
+        voice_casting: VoiceCastingResolver,
     ) -> Self {
         Self {
             session_manager,
             task_manager,
             novel_repo,
             audio_cache,
+            voice_casting,
         }
     }
 
     pub async fn handle(&self, cmd: SubmitInferCommand) -> Result<SubmitInferResponse, ApplicationError> {
+        for params in cmd.segment_params.values() {
+            params.validate().map_err(ApplicationError::validation)?;
+        }
+
+        // 保存片段参数覆盖（音频查询按会话参数计算缓存 key）
+        for (segment_index, params) in &cmd.segment_params {
+            self.session_manager
+                .set_segment_params(&cmd.session_id, *segment_index, params.clone())
+                .map_err(|_| ApplicationError::not_found_str("Session", &cmd.session_id))?;
+        }
+
         // 获取会话信息
         let session = self
             .session_manager
@@ -52,8 +72,17 @@ impl SubmitInferHandler {
             "Fetched segments for inference"
         );
 
+        // 同一片段已有排队或推理中的任务（如预取提交的）时返回该任务，不重复提交
+        let in_flight: Vec<InferenceTask> = self
+            .task_manager
+            .get_tasks_by_session(&cmd.session_id)
+            .into_iter()
+            .filter(|task| matches!(task.state, TaskState::Pending | TaskState::Inferring))
+            .collect();
+
         let mut tasks_to_submit = Vec::new();
         let mut response_tasks = Vec::new();
+        let mut window_keys = Vec::with_capacity(cmd.segment_indices.len());
 
         for segment_index in cmd.segment_indices.iter().copied() {
             // 验证索引有效
@@ -64,9 +93,18 @@ impl SubmitInferHandler {
                     ApplicationError::validation(format!("Invalid segment index: {}", segment_index))
                 })?;
 
-            // 检查缓存是否已存在
-            let cache_key = generate_cache_key(&segment.content, &session.voice_id);
-            let cache_exists = self.audio_cache.exists(&cache_key).await;
+            // 检查缓存是否已存在（按角色配音解析后的实际音色）
+            let voice = self
+                .voice_casting
+                .resolve_key(session.novel_id, segment_index, session.voice_id)
+                .await;
+            let params = session.params_for(segment_index);
+            let cache_key = generate_cache_key_with_params(&segment.content, &voice, &params);
+            window_keys.push(cache_key.clone());
+            let cache_exists = self
+                .audio_cache
+                .link(&cache_key, session.novel_id, segment_index, voice.voice_id)
+                .await;
             tracing::info!(
                 segment_index = segment_index,
                 cache_key = %cache_key,
@@ -84,6 +122,22 @@ impl SubmitInferHandler {
                 continue;
             }
 
+            let priority = TaskPriority::for_segment(segment_index, session.current_index);
+            let queued = in_flight.iter().find(|task| {
+                task.segment_index == segment_index
+                    && task.voice_id == session.voice_id
+                    && task.params == params
+            });
+            if let Some(task) = queued {
+                self.task_manager.prioritize(&task.task_id, priority);
+                response_tasks.push(TaskInfo {
+                    task_id: task.task_id.clone(),
+                    segment_index,
+                    state: task.state,
+                });
+                continue;
+            }
+
             // 创建推理任务
             let task = InferenceTask::new(
                 cmd.session_id.clone(),
@@ -91,7 +145,9 @@ impl SubmitInferHandler {
                 session.voice_id,
                 segment_index,
                 segment.content.clone(),
-            );
+            )
+            .with_params(params)
+            .with_priority(priority);
 
             tracing::debug!(
                 task_id = %task.task_id,
@@ -108,15 +164,16 @@ impl SubmitInferHandler {
             tasks_to_submit.push(task);
         }
 
+        // 固定窗口内的条目，缓存已满时不淘汰即将播放的片段
+        self.audio_cache.pin(&cmd.session_id, window_keys);
+
         // 批量提交任务
         if !tasks_to_submit.is_empty() {
             tracing::info!(
                 count = tasks_to_submit.len(),
                 "Submitting tasks to queue"
             );
-            self.task_manager
-                .submit(tasks_to_submit)
-                .map_err(|e| ApplicationError::internal(e.to_string()))?;
+            self.task_manager.submit(tasks_to_submit)?;
         }
 
         tracing::debug!(
@@ -127,34 +184,167 @@ impl SubmitInferHandler {
 
         Ok(SubmitInferResponse {
             tasks: response_tasks,
+            queue_depth: self.task_manager.queue_depth(),
         })
     }
 }
 
 /// QueryTaskStatus Handler - 查询任务状态
+///
+/// 启用账号时不返回其他用户会话（或已关闭会话）的任务，按任务不存在处理
 pub struct QueryTaskStatusHandler {
     task_manager: Arc<dyn TaskManagerPort>,
+    session_manager: Arc<dyn SessionManagerPort>,
 }
 
 impl QueryTaskStatusHandler {
-    pub fn new(task_manager: Arc<dyn TaskManagerPort>) -> Self {
-        Self { task_manager }
+    pub fn new(task_manager: Arc<dyn TaskManagerPort>, session_manager: Arc<dyn SessionManagerPort>) -> Self {
+        Self {
+            task_manager,
+            session_manager,
+        }
     }
 
     pub fn handle(&self, cmd: QueryTaskStatusCommand) -> QueryTaskStatusResponse {
+        let visible = |task: &InferenceTask| {
+            cmd.user_id.is_none()
+                || self
+                    .session_manager
+                    .get(&task.session_id)
+                    .is_ok_and(|session| can_access(session.user_id, cmd.user_id))
+        };
         let tasks = cmd
             .task_ids
             .iter()
-            .filter_map(|task_id| {
-                self.task_manager.get_task(task_id).map(|task| TaskStatusInfo {
-                    task_id: task.task_id,
-                    segment_index: task.segment_index,
-                    state: task.state,
-                    error: task.error_message,
-                })
+            .filter_map(|task_id| self.task_manager.get_task(task_id))
+            .filter(|task| visible(task))
+            .map(|task| TaskStatusInfo {
+                task_id: task.task_id,
+                segment_index: task.segment_index,
+                state: task.state,
+                error: task.error_message,
             })
             .collect();
 
         QueryTaskStatusResponse { tasks }
     }
 }
+
+/// ListTasks Handler - 列出任务
+pub struct ListTasksHandler {
+    task_manager: Arc<dyn TaskManagerPort>,
+}
+
+impl ListTasksHandler {
+    pub fn new(task_manager: Arc<dyn TaskManagerPort>) -> Self {
+        Self { task_manager }
+    }
+
+    pub fn handle(&self, cmd: ListTasksCommand) -> Result<Page<TaskDetail>, ApplicationError> {
+        cmd.page.validate()?;
+        let mut tasks: Vec<InferenceTask> = self
+            .task_manager
+            .list_tasks(cmd.state)
+            .into_iter()
+            .filter(|task| cmd.session_id.as_ref().is_none_or(|id| &task.session_id == id))
+            .collect();
+        tasks.sort_by_key(|task| std::cmp::Reverse(task.created_at));
+
+        Ok(cmd.page.paginate(tasks).map(|task| TaskDetail {
+            task_id: task.task_id,
+            session_id: task.session_id,
+            novel_id: task.novel_id,
+            voice_id: task.voice_id,
+            segment_index: task.segment_index,
+            state: task.state,
+            priority: task.priority,
+            error: task.error_message,
+            request_id: task.request_id,
+            created_at: task.created_at,
+            completed_at: task.completed_at,
+        }))
+    }
+}
+
+/// ListTaskEvents Handler - 查询任务审计日志
+pub struct ListTaskEventsHandler {
+    task_event_repo: Arc<dyn TaskEventRepositoryPort>,
+}
+
+impl ListTaskEventsHandler {
+    pub fn new(task_event_repo: Arc<dyn TaskEventRepositoryPort>) -> Self {
+        Self { task_event_repo }
+    }
+
+    pub async fn handle(&self, cmd: ListTaskEventsCommand) -> Result<ListTaskEventsResponse, ApplicationError> {
+        if cmd.limit == 0 || cmd.limit > MAX_TASK_EVENTS {
+            return Err(ApplicationError::validation(format!(
+                "limit must be between 1 and {}",
+                MAX_TASK_EVENTS
+            )));
+        }
+
+        let filter = TaskEventFilter {
+            session_id: cmd.session_id,
+            novel_id: cmd.novel_id,
+            segment_index: cmd.segment_index,
+            task_id: cmd.task_id,
+            event: cmd.event,
+            request_id: cmd.request_id,
+            limit: cmd.limit,
+        };
+        let events = self.task_event_repo.find(&filter).await?;
+        Ok(ListTaskEventsResponse { events })
+    }
+}
+
+/// RetryTask Handler - 失败的任务重新排队
+///
+/// 沿用原任务 ID，会话仍需存在；重试的是正在播放的片段时优先推理
+pub struct RetryTaskHandler {
+    session_manager: Arc<dyn SessionManagerPort>,
+    task_manager: Arc<dyn TaskManagerPort>,
+}
+
+impl RetryTaskHandler {
+    pub fn new(session_manager: Arc<dyn SessionManagerPort>, task_manager: Arc<dyn TaskManagerPort>) -> Self {
+        Self {
+            session_manager,
+            task_manager,
+        }
+    }
+
+    pub fn handle(&self, cmd: RetryTaskCommand) -> Result<RetryTaskResponse, ApplicationError> {
+        let task = self
+            .task_manager
+            .get_task(&cmd.task_id)
+            .ok_or_else(|| ApplicationError::not_found_str("Task", &cmd.task_id))?;
+        if task.state != TaskState::Failed {
+            return Err(ApplicationError::invalid_state(format!(
+                "Only failed tasks can be retried (task is {})",
+                task.state.as_str()
+            )));
+        }
+        let session = self
+            .session_manager
+            .get(&task.session_id)
+            .map_err(|_| ApplicationError::not_found_str("Session", &task.session_id))?;
+
+        let priority = TaskPriority::for_segment(task.segment_index, session.current_index);
+        self.task_manager.retry(&cmd.task_id, priority)?;
+
+        tracing::info!(
+            task_id = %cmd.task_id,
+            session_id = %task.session_id,
+            segment_index = task.segment_index,
+            "Failed task requeued"
+        );
+
+        Ok(RetryTaskResponse {
+            task_id: cmd.task_id,
+            session_id: task.session_id,
+            segment_index: task.segment_index,
+            state: TaskState::Pending,
+        })
+    }
+}
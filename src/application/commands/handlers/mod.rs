@@ -2,12 +2,24 @@
 //!
 //! 所有 CommandHandler 的具体实现
 
+mod cache_handlers;
+mod casting_handlers;
+mod export_handlers;
 mod infer_command_handlers;
 mod novel_handlers;
+mod pregeneration_handlers;
 mod session_command_handlers;
+mod settings_handlers;
+mod user_handlers;
 mod voice_handlers;
 
+pub use cache_handlers::*;
+pub use casting_handlers::*;
+pub use export_handlers::*;
 pub use infer_command_handlers::*;
 pub use novel_handlers::*;
+pub use pregeneration_handlers::*;
 pub use session_command_handlers::*;
+pub use settings_handlers::*;
+pub use user_handlers::*;
 pub use voice_handlers::*;
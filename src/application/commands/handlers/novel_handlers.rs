@@ -6,7 +6,9 @@ use uuid::Uuid;
 
 use crate::application::commands::{CreateNovelFromText, DeleteNovel, ProcessNovelSegments};
 use crate::application::error::ApplicationError;
-use crate::application::ports::{NovelRecord, NovelRepositoryPort, NovelStatus, TextSegmentRecord};
+use crate::application::ports::{
+    AudioCachePort, NovelRecord, NovelRepositoryPort, NovelStatus, TextSegmentRecord,
+};
 use crate::domain::segment_text;
 use crate::domain::SegmentConfig;
 
@@ -44,6 +46,7 @@ impl CreateNovelFromTextHandler {
             raw_text_path: std::path::PathBuf::new(),
             total_segments: 0, // 待处理
             status: NovelStatus::Processing,
+            user_id: command.user_id,
             created_at: now,
             updated_at: now,
         };
@@ -144,11 +147,12 @@ impl ProcessNovelSegmentsHandler {
 /// DeleteNovel Handler
 pub struct DeleteNovelHandler {
     novel_repo: Arc<dyn NovelRepositoryPort>,
+    audio_cache: Arc<dyn AudioCachePort>,
 }
 
 impl DeleteNovelHandler {
-    pub fn new(novel_repo: Arc<dyn NovelRepositoryPort>) -> Self {
-        Self { novel_repo }
+    pub fn new(novel_repo: Arc<dyn NovelRepositoryPort>, audio_cache: Arc<dyn AudioCachePort>) -> Self {
+        Self { novel_repo, audio_cache }
     }
 
     pub async fn handle(&self, command: DeleteNovel) -> Result<(), ApplicationError> {
@@ -163,9 +167,19 @@ impl DeleteNovelHandler {
 
         self.novel_repo.delete(novel_id).await?;
 
+        // 清除已缓存的音频（失败不影响删除结果）
+        let purged = match self.audio_cache.remove_by_novel(novel_id).await {
+            Ok(purged) => purged,
+            Err(e) => {
+                tracing::warn!(novel_id = %novel_id, error = %e, "Failed to purge novel audio cache");
+                0
+            }
+        };
+
         tracing::info!(
             novel_id = %novel_id,
             title = %novel.title,
+            purged_cache_entries = purged,
             "Novel deleted"
         );
 
@@ -0,0 +1,397 @@
+//! Pregeneration Command Handlers - 整书预生成
+
+use chrono::Local;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::application::casting::VoiceCastingResolver;
+use crate::application::commands::{ControlPregeneration, PregenerateNovel, PregenerationAction};
+use crate::application::error::ApplicationError;
+use crate::application::ports::{
+    generate_cache_key_with_params, AudioCachePort, InferenceTask, NovelRepositoryPort, NovelStatus,
+    PregenerationJob, PregenerationJobManagerPort, PregenerationJobState, Session, SessionManagerPort,
+    TaskError, TaskManagerPort, TaskPriority, TaskState, TextSegmentRecord, VoiceRepositoryPort,
+};
+use crate::application::schedule::PregenerationSchedule;
+use crate::infrastructure::events::EventPublisher;
+
+/// 预生成每批提交的推理任务数（小于导出，尽快让出推理队列）
+const PREGENERATION_BATCH_SIZE: usize = 4;
+
+/// 轮询任务状态、暂停状态及其他会话队列的间隔
+const PREGENERATION_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// 发布预生成任务的当前进度
+fn publish_progress(event_publisher: &EventPublisher, job: &PregenerationJob) {
+    event_publisher.publish_pregeneration_progress(job);
+}
+
+// ============================================================================
+// PregenerateNovel
+// ============================================================================
+
+/// PregenerateNovel Handler
+///
+/// `handle` 校验并创建预生成任务；`run` 在后台执行：
+/// 以专用会话逐批提交未缓存片段的推理任务，只在其他会话没有待推理任务时提交下一批，
+/// 保证正在播放的会话优先；配置了预生成时段时，时段外进入 Scheduled 状态等待；
+/// 单个片段失败不中断任务，计入 failed_segments
+///
+/// 进度通过全局 WebSocket 事件（PregenerationProgress）通知
+pub struct PregenerateNovelHandler {
+    novel_repo: Arc<dyn NovelRepositoryPort>,
+    voice_repo: Arc<dyn VoiceRepositoryPort>,
+    session_manager: Arc<dyn SessionManagerPort>,
+    task_manager: Arc<dyn TaskManagerPort>,
+    audio_cache: Arc<dyn AudioCachePort>,
+    pregeneration_jobs: Arc<dyn PregenerationJobManagerPort>,
+    event_publisher: Arc<EventPublisher>,
+    voice_casting: VoiceCastingResolver,
+    schedule: PregenerationSchedule,
+}
+
+impl PregenerateNovelHandler {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        novel_repo: Arc<dyn NovelRepositoryPort>,
+        voice_repo: Arc<dyn VoiceRepositoryPort>,
+        session_manager: Arc<dyn SessionManagerPort>,
+        task_manager: Arc<dyn TaskManagerPort>,
+        audio_cache: Arc<dyn AudioCachePort>,
+        pregeneration_jobs: Arc<dyn PregenerationJobManagerPort>,
+        event_publisher: Arc<EventPublisher>,
+        voice_casting: VoiceCastingResolver,
+        schedule: PregenerationSchedule,
+    ) -> Self {
+        Self {
+            novel_repo,
+            voice_repo,
+            session_manager,
+            task_manager,
+            audio_cache,
+            pregeneration_jobs,
+            event_publisher,
+            voice_casting,
+            schedule,
+        }
+    }
+
+    /// 创建预生成任务（不执行合成）
+    pub async fn handle(&self, cmd: &PregenerateNovel) -> Result<PregenerationJob, ApplicationError> {
+        cmd.params.validate().map_err(ApplicationError::validation)?;
+
+        let novel = self
+            .novel_repo
+            .find_by_id(cmd.novel_id)
+            .await?
+            .ok_or_else(|| ApplicationError::not_found("Novel", cmd.novel_id))?;
+        if novel.status != NovelStatus::Ready {
+            return Err(ApplicationError::invalid_state(format!(
+                "Novel is not ready: {}",
+                novel.status.as_str()
+            )));
+        }
+        self.voice_repo
+            .find_by_id(cmd.voice_id)
+            .await?
+            .ok_or_else(|| ApplicationError::not_found("Voice", cmd.voice_id))?;
+
+        let job = PregenerationJob::new(cmd.novel_id, cmd.voice_id, novel.total_segments).with_user(cmd.user_id);
+        self.pregeneration_jobs.create(job.clone());
+
+        tracing::info!(job_id = %job.job_id, novel_id = %cmd.novel_id, "Pregeneration job created");
+        Ok(job)
+    }
+
+    /// 执行预生成任务，结果记录在任务状态中
+    pub async fn run(&self, job_id: String, cmd: PregenerateNovel) {
+        match self.pregenerate(&job_id, &cmd).await {
+            Ok(state) => {
+                tracing::info!(job_id = %job_id, state = state.as_str(), "Pregeneration finished");
+                self.pregeneration_jobs.set_state(&job_id, state);
+            }
+            Err(e) => {
+                tracing::error!(job_id = %job_id, error = %e, "Pregeneration failed");
+                self.pregeneration_jobs.set_failed(&job_id, e.to_string());
+            }
+        }
+        if let Some(job) = self.pregeneration_jobs.get(&job_id) {
+            publish_progress(&self.event_publisher, &job);
+        }
+    }
+
+    async fn pregenerate(
+        &self,
+        job_id: &str,
+        cmd: &PregenerateNovel,
+    ) -> Result<PregenerationJobState, ApplicationError> {
+        let segments = self.novel_repo.find_segments_by_novel_id(cmd.novel_id).await?;
+        if segments.is_empty() {
+            return Err(ApplicationError::invalid_state("Novel has no segments"));
+        }
+
+        // Worker 只处理有效会话的任务，预生成使用专用会话
+        let session = Session::new(cmd.novel_id, cmd.voice_id, 0)
+            .with_params(cmd.params.clone())
+            .as_background();
+        let session_id = self
+            .session_manager
+            .create(session.clone())
+            .map_err(|e| ApplicationError::internal(e.to_string()))?;
+
+        let result = self.synthesize(job_id, &session, &segments).await;
+        self.task_manager.cancel_all(&session_id);
+        self.task_manager.cleanup_session(&session_id);
+        let _ = self.session_manager.close(&session_id);
+        result
+    }
+
+    /// 逐批合成未缓存的片段，返回结束状态（Completed 或 Cancelled）
+    async fn synthesize(
+        &self,
+        job_id: &str,
+        session: &Session,
+        segments: &[TextSegmentRecord],
+    ) -> Result<PregenerationJobState, ApplicationError> {
+        let mut completed = 0;
+        let mut failed = 0;
+
+        for batch in segments.chunks(PREGENERATION_BATCH_SIZE) {
+            if !self.wait_turn(job_id, &session.id).await {
+                return Ok(PregenerationJobState::Cancelled);
+            }
+
+            let mut tasks = Vec::new();
+            for segment in batch {
+                let index = segment.index as u32;
+                let voice = self.voice_casting.resolve_key(session.novel_id, index, session.voice_id).await;
+                let params = session.params_for(index);
+                let cache_key = generate_cache_key_with_params(&segment.content, &voice, &params);
+                let cached = self.audio_cache.link(&cache_key, session.novel_id, index, voice.voice_id).await;
+                if !matches!(cached, Ok(true)) {
+                    tasks.push(
+                        InferenceTask::new(
+                            session.id.clone(),
+                            session.novel_id,
+                            session.voice_id,
+                            index,
+                            segment.content.clone(),
+                        )
+                        .with_params(params)
+                        .with_priority(TaskPriority::Background),
+                    );
+                }
+            }
+
+            // 队列已满时等待播放中的会话的任务消化后再提交
+            let task_ids = loop {
+                match self.task_manager.submit(tasks.clone()) {
+                    Err(TaskError::QueueFull { .. }) => tokio::time::sleep(PREGENERATION_POLL_INTERVAL).await,
+                    result => break result?,
+                }
+            };
+            match self.wait_for_tasks(job_id, &session.id, &task_ids).await {
+                Some(batch_failed) => failed += batch_failed,
+                None => return Ok(PregenerationJobState::Cancelled),
+            }
+            completed += batch.len();
+
+            self.pregeneration_jobs.update_progress(job_id, completed, failed);
+            if let Some(job) = self.pregeneration_jobs.get(job_id) {
+                publish_progress(&self.event_publisher, &job);
+            }
+        }
+
+        Ok(PregenerationJobState::Completed)
+    }
+
+    /// 等待可以提交下一批：暂停时等待恢复，时段外等待进入时段，其他会话有待推理任务时让出
+    ///
+    /// 返回 false 表示任务已取消
+    async fn wait_turn(&self, job_id: &str, session_id: &str) -> bool {
+        loop {
+            let open = self.schedule.is_open(Local::now().time());
+            match self.pregeneration_jobs.get(job_id).map(|job| job.state) {
+                Some(PregenerationJobState::Running) if !open => {
+                    self.set_scheduled(job_id, PregenerationJobState::Scheduled)
+                }
+                Some(PregenerationJobState::Scheduled) if open => {
+                    self.set_scheduled(job_id, PregenerationJobState::Running)
+                }
+                Some(PregenerationJobState::Running) if !self.others_pending(session_id) => return true,
+                Some(
+                    PregenerationJobState::Running
+                    | PregenerationJobState::Paused
+                    | PregenerationJobState::Scheduled,
+                ) => {}
+                _ => return false,
+            }
+
+            // 保持会话活跃，避免被当作空闲会话清理
+            self.session_manager.touch(session_id);
+            tokio::time::sleep(PREGENERATION_POLL_INTERVAL).await;
+        }
+    }
+
+    /// 按预生成时段暂停或继续，并通知进度
+    fn set_scheduled(&self, job_id: &str, state: PregenerationJobState) {
+        tracing::info!(job_id = %job_id, state = state.as_str(), "Pregeneration schedule window changed");
+        self.pregeneration_jobs.set_state(job_id, state);
+        if let Some(job) = self.pregeneration_jobs.get(job_id) {
+            publish_progress(&self.event_publisher, &job);
+        }
+    }
+
+    /// 其他会话是否有待推理的任务
+    fn others_pending(&self, session_id: &str) -> bool {
+        self.session_manager
+            .list_all()
+            .iter()
+            .filter(|id| id.as_str() != session_id)
+            .any(|id| {
+                self.task_manager
+                    .get_tasks_by_session(id)
+                    .iter()
+                    .any(|task| task.state == TaskState::Pending)
+            })
+    }
+
+    /// 等待本批推理任务结束，返回失败的任务数；任务被取消时取消剩余任务并返回 None
+    async fn wait_for_tasks(&self, job_id: &str, session_id: &str, task_ids: &[String]) -> Option<usize> {
+        loop {
+            let cancelled = self
+                .pregeneration_jobs
+                .get(job_id)
+                .is_none_or(|job| job.state == PregenerationJobState::Cancelled);
+            if cancelled {
+                self.task_manager.cancel_all(session_id);
+                return None;
+            }
+
+            let states: Vec<Option<TaskState>> =
+                task_ids.iter().map(|task_id| self.task_manager.get_state(task_id)).collect();
+            if !states
+                .iter()
+                .any(|state| matches!(state, Some(TaskState::Pending | TaskState::Inferring)))
+            {
+                return Some(
+                    states
+                        .iter()
+                        .filter(|state| !matches!(state, Some(TaskState::Ready)))
+                        .count(),
+                );
+            }
+
+            self.session_manager.touch(session_id);
+            tokio::time::sleep(PREGENERATION_POLL_INTERVAL).await;
+        }
+    }
+}
+
+// ============================================================================
+// ControlPregeneration
+// ============================================================================
+
+/// ControlPregeneration Handler
+///
+/// 只修改任务状态，由后台执行的 PregenerateNovelHandler::run 在批次之间响应
+pub struct ControlPregenerationHandler {
+    pregeneration_jobs: Arc<dyn PregenerationJobManagerPort>,
+    event_publisher: Arc<EventPublisher>,
+}
+
+impl ControlPregenerationHandler {
+    pub fn new(
+        pregeneration_jobs: Arc<dyn PregenerationJobManagerPort>,
+        event_publisher: Arc<EventPublisher>,
+    ) -> Self {
+        Self {
+            pregeneration_jobs,
+            event_publisher,
+        }
+    }
+
+    pub fn handle(&self, cmd: ControlPregeneration) -> Result<PregenerationJob, ApplicationError> {
+        let job = self
+            .pregeneration_jobs
+            .get(&cmd.job_id)
+            .ok_or_else(|| ApplicationError::not_found_str("PregenerationJob", &cmd.job_id))?;
+
+        let state = match (cmd.action, job.state) {
+            (PregenerationAction::Pause, PregenerationJobState::Running | PregenerationJobState::Scheduled) => {
+                PregenerationJobState::Paused
+            }
+            // 恢复后不在时段内时由后台任务重新进入 Scheduled
+            (PregenerationAction::Resume, PregenerationJobState::Paused) => PregenerationJobState::Running,
+            (
+                PregenerationAction::Cancel,
+                PregenerationJobState::Running | PregenerationJobState::Paused | PregenerationJobState::Scheduled,
+            ) => PregenerationJobState::Cancelled,
+            (action, state) => {
+                return Err(ApplicationError::invalid_state(format!(
+                    "Cannot {} pregeneration job in state {}",
+                    action.as_str(),
+                    state.as_str()
+                )))
+            }
+        };
+        self.pregeneration_jobs.set_state(&cmd.job_id, state);
+
+        tracing::info!(job_id = %cmd.job_id, action = cmd.action.as_str(), "Pregeneration job updated");
+
+        let job = self
+            .pregeneration_jobs
+            .get(&cmd.job_id)
+            .ok_or_else(|| ApplicationError::not_found_str("PregenerationJob", &cmd.job_id))?;
+        publish_progress(&self.event_publisher, &job);
+        Ok(job)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::memory::InMemoryPregenerationJobManager;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_control_pregeneration_transitions() {
+        let jobs = Arc::new(InMemoryPregenerationJobManager::new());
+        let handler = ControlPregenerationHandler::new(jobs.clone(), Arc::new(EventPublisher::new()));
+        let job = PregenerationJob::new(Uuid::new_v4(), Uuid::new_v4(), 10);
+        let job_id = job.job_id.clone();
+        jobs.create(job);
+        let control = |action| {
+            handler.handle(ControlPregeneration {
+                job_id: job_id.clone(),
+                action,
+            })
+        };
+
+        // 运行中不能恢复
+        assert!(matches!(control(PregenerationAction::Resume), Err(ApplicationError::InvalidState(_))));
+
+        let job = control(PregenerationAction::Pause).unwrap();
+        assert_eq!(job.state, PregenerationJobState::Paused);
+        assert!(matches!(control(PregenerationAction::Pause), Err(ApplicationError::InvalidState(_))));
+        let job = control(PregenerationAction::Resume).unwrap();
+        assert_eq!(job.state, PregenerationJobState::Running);
+
+        // 时段外等待的任务可以暂停和取消
+        jobs.set_state(&job_id, PregenerationJobState::Scheduled);
+        let job = control(PregenerationAction::Cancel).unwrap();
+        assert_eq!(job.state, PregenerationJobState::Cancelled);
+        assert!(job.completed_at.is_some());
+
+        // 已结束的任务不能再控制
+        for action in [PregenerationAction::Pause, PregenerationAction::Resume, PregenerationAction::Cancel] {
+            assert!(matches!(control(action), Err(ApplicationError::InvalidState(_))));
+        }
+        assert!(handler
+            .handle(ControlPregeneration {
+                job_id: "missing".to_string(),
+                action: PregenerationAction::Cancel,
+            })
+            .is_err());
+    }
+}
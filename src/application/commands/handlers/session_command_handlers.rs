@@ -1,79 +1,329 @@
 //! Session Command Handlers - V2 架构
 
-use std::sync::Arc;
+use chrono::Utc;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
 
+use crate::application::commands::infer_commands::TaskInfo;
 use crate::application::commands::session_commands::*;
+use crate::application::access::can_access;
+use crate::application::auto_advance::AutoAdvanceNotifier;
 use crate::application::error::ApplicationError;
+use crate::application::listening::ListeningTracker;
+use crate::application::prefetch::{BatchPrefetchResult, PrefetchCoordinator};
+use crate::application::readiness::{ReadinessSummary, SegmentReadiness, SegmentStatusResolver};
+use crate::application::snapshot::SessionSnapshot;
 use crate::application::ports::{
-    NovelRepositoryPort, Session, SessionManagerPort, TaskManagerPort, VoiceRepositoryPort,
+    AudioCachePort, NovelRepositoryPort, ReadingProgressRecord, ReadingProgressRepositoryPort, Session,
+    SessionError, SessionManagerPort, SettingsRepositoryPort, TaskManagerPort, TaskState, VoiceHandoff,
+    VoiceRepositoryPort, PLAYBACK_SPEED_RANGE, SETTING_DEFAULT_VOICE_ID,
 };
+use crate::domain::voice::SynthesisParams;
 use crate::infrastructure::events::EventPublisher;
 
+/// 播放会话数限制（0 表示不限制）
+///
+/// 只在创建新会话时检查：复用会话不增加会话数，后台会话（导出/预生成）不计入
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SessionLimits {
+    /// 全局会话上限
+    pub max_active: usize,
+    /// 每个客户端的会话上限
+    pub max_per_client: usize,
+}
+
 /// Play Handler - 创建或复用会话
 pub struct PlayHandler {
     session_manager: Arc<dyn SessionManagerPort>,
     task_manager: Arc<dyn TaskManagerPort>,
     novel_repo: Arc<dyn NovelRepositoryPort>,
     voice_repo: Arc<dyn VoiceRepositoryPort>,
+    settings_repo: Arc<dyn SettingsRepositoryPort>,
+    progress_repo: Arc<dyn ReadingProgressRepositoryPort>,
+    prefetch: Arc<PrefetchCoordinator>,
+    limits: SessionLimits,
+    /// 检查会话数与创建会话在同一把锁内完成，并发的开始播放请求不会同时通过检查
+    create_lock: Mutex<()>,
 }
 
 impl PlayHandler {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         session_manager: Arc<dyn SessionManagerPort>,
         task_manager: Arc<dyn TaskManagerPort>,
         novel_repo: Arc<dyn NovelRepositoryPort>,
         voice_repo: Arc<dyn VoiceRepositoryPort>,
+        settings_repo: Arc<dyn SettingsRepositoryPort>,
+        progress_repo: Arc<dyn ReadingProgressRepositoryPort>,
+        prefetch: Arc<PrefetchCoordinator>,
+        limits: SessionLimits,
     ) -> Self {
         Self {
             session_manager,
             task_manager,
             novel_repo,
             voice_repo,
+            settings_repo,
+            progress_repo,
+            prefetch,
+            limits,
+            create_lock: Mutex::new(()),
+        }
+    }
+
+    /// 解析本次播放使用的音色：优先请求中的 voice_id，否则使用用户的默认音色
+    async fn resolve_voice_id(
+        &self,
+        voice_id: Option<Uuid>,
+        user_id: Option<Uuid>,
+    ) -> Result<Uuid, ApplicationError> {
+        if let Some(voice_id) = voice_id {
+            return Ok(voice_id);
         }
+
+        let value = self
+            .settings_repo
+            .get(user_id, SETTING_DEFAULT_VOICE_ID)
+            .await?
+            .ok_or_else(|| {
+                ApplicationError::validation("voice_id is required (no default voice configured)")
+            })?;
+
+        Uuid::parse_str(&value).map_err(|e| {
+            ApplicationError::internal(format!("Invalid default voice id in settings: {}", e))
+        })
     }
 
     pub async fn handle(&self, cmd: PlayCommand) -> Result<PlayResponse, ApplicationError> {
-        // 验证 novel 存在
+        cmd.params.validate().map_err(ApplicationError::validation)?;
+        cmd.playback_hints.validate().map_err(ApplicationError::validation)?;
+        if let Some(speed) = cmd.playback_speed.filter(|s| !PLAYBACK_SPEED_RANGE.contains(s)) {
+            return Err(ApplicationError::validation(format!(
+                "Invalid playback_speed: {} (allowed: {:?})",
+                speed, PLAYBACK_SPEED_RANGE
+            )));
+        }
+
+        // 验证 novel 存在且属于当前用户
         let novel = self
             .novel_repo
             .find_by_id(cmd.novel_id)
             .await?
+            .filter(|novel| can_access(novel.user_id, cmd.user_id))
             .ok_or_else(|| ApplicationError::not_found("Novel", cmd.novel_id))?;
 
-        // 验证 voice 存在
+        // 验证 voice 存在且当前用户可用
+        let voice_id = self.resolve_voice_id(cmd.voice_id, cmd.user_id).await?;
         self.voice_repo
-            .find_by_id(cmd.voice_id)
+            .find_by_id(voice_id)
             .await?
-            .ok_or_else(|| ApplicationError::not_found("Voice", cmd.voice_id))?;
+            .filter(|voice| can_access(voice.user_id, cmd.user_id))
+            .ok_or_else(|| ApplicationError::not_found("Voice", voice_id))?;
 
         // 验证 start_index 有效
-        if cmd.start_index as usize >= novel.total_segments {
-            return Err(ApplicationError::validation(format!(
-                "Invalid start_index: {} (total segments: {})",
-                cmd.start_index, novel.total_segments
-            )));
+        if let Some(start_index) = cmd.start_index {
+            if start_index as usize >= novel.total_segments {
+                return Err(ApplicationError::validation(format!(
+                    "Invalid start_index: {} (total segments: {})",
+                    start_index, novel.total_segments
+                )));
+            }
         }
 
-        // 创建新会话
-        let session = Session::new(cmd.novel_id, cmd.voice_id, cmd.start_index);
-        let session_id = self
-            .session_manager
-            .create(session)
-            .map_err(|e| ApplicationError::internal(e.to_string()))?;
+        let window = self.prefetch.resolve_window(cmd.window_before, cmd.window_after)?;
+
+        if cmd.reuse {
+            if let Some(session) = self.find_reusable(cmd.novel_id, voice_id, &cmd.params, cmd.user_id) {
+                return self.reuse(session, cmd.start_index, cmd.auto_advance).await;
+            }
+        }
+
+        // 创建新会话：未指定 start_index 时从小说的阅读进度继续
+        let (start_index, offset_ms) = match cmd.start_index {
+            Some(start_index) => (start_index, cmd.offset_ms.unwrap_or(0)),
+            None => self.saved_progress(cmd.user_id, cmd.novel_id, novel.total_segments).await,
+        };
+        let session = Session::new(cmd.novel_id, voice_id, start_index)
+            .with_offset_ms(offset_ms)
+            .with_window(window)
+            .with_params(cmd.params)
+            .with_playback_speed(cmd.playback_speed)
+            .with_playback_hints(cmd.playback_hints)
+            .with_auto_advance(cmd.auto_advance.unwrap_or(false))
+            .with_client(cmd.client.clone())
+            .with_user(cmd.user_id);
+        let session_id = {
+            let _guard = self.create_lock.lock().unwrap();
+            self.check_limits(cmd.client.as_deref())?;
+            self.session_manager
+                .create(session)
+                .map_err(|e| ApplicationError::internal(e.to_string()))?
+        };
 
         tracing::info!(
             session_id = %session_id,
             novel_id = %cmd.novel_id,
-            voice_id = %cmd.voice_id,
-            start_index = cmd.start_index,
+            voice_id = %voice_id,
+            start_index = start_index,
+            resumed = cmd.start_index.is_none(),
+            client = ?cmd.client,
             "Play session created"
         );
+        self.prefetch.refresh(&session_id).await;
 
         Ok(PlayResponse {
             session_id,
             novel_id: cmd.novel_id,
-            voice_id: cmd.voice_id,
-            current_index: cmd.start_index,
+            voice_id,
+            current_index: start_index,
+            offset_ms,
+            window,
+            reused: false,
+            resumed: cmd.start_index.is_none(),
+            auto_advance: cmd.auto_advance.unwrap_or(false),
+        })
+    }
+
+    /// 从快照恢复：与开始播放相同的校验和会话数限制，总是创建新会话
+    pub async fn handle_restore(&self, cmd: RestoreSessionCommand) -> Result<PlayResponse, ApplicationError> {
+        let snapshot = SessionSnapshot::decode(&cmd.token)?;
+        self.handle(PlayCommand {
+            novel_id: snapshot.novel_id,
+            voice_id: Some(snapshot.voice_id),
+            start_index: Some(snapshot.current_index),
+            offset_ms: Some(snapshot.offset_ms),
+            reuse: false,
+            params: snapshot.params,
+            playback_speed: snapshot.playback_speed,
+            playback_hints: snapshot.playback_hints,
+            window_before: Some(snapshot.window_before),
+            window_after: Some(snapshot.window_after),
+            auto_advance: None,
+            client: cmd.client,
+            user_id: cmd.user_id,
+        })
+        .await
+    }
+
+    /// 用户在小说上已保存的阅读进度（没有进度或进度超出片段数时从头开始）
+    async fn saved_progress(&self, user_id: Option<Uuid>, novel_id: Uuid, total_segments: usize) -> (u32, u32) {
+        match self.progress_repo.find(user_id, novel_id).await {
+            Ok(Some(progress)) if (progress.segment_index as usize) < total_segments => {
+                (progress.segment_index, progress.offset_ms)
+            }
+            Ok(_) => (0, 0),
+            Err(e) => {
+                tracing::warn!(novel_id = %novel_id, error = %e, "Failed to load reading progress");
+                (0, 0)
+            }
+        }
+    }
+
+    /// 检查会话数限制：全局和客户端已有的播放会话数达到上限时拒绝创建
+    fn check_limits(&self, client: Option<&str>) -> Result<(), ApplicationError> {
+        let limits = self.limits;
+        if limits.max_active == 0 && limits.max_per_client == 0 {
+            return Ok(());
+        }
+
+        let sessions: Vec<Session> = self
+            .session_manager
+            .list_all()
+            .into_iter()
+            .filter_map(|id| self.session_manager.get(&id).ok())
+            .filter(|session| !session.background)
+            .collect();
+        if limits.max_active > 0 && sessions.len() >= limits.max_active {
+            return Err(ApplicationError::LimitExceeded {
+                scope: "sessions",
+                limit: limits.max_active,
+                current: sessions.len(),
+            });
+        }
+        if let Some(client) = client.filter(|_| limits.max_per_client > 0) {
+            let current = sessions
+                .iter()
+                .filter(|session| session.client.as_deref() == Some(client))
+                .count();
+            if current >= limits.max_per_client {
+                return Err(ApplicationError::LimitExceeded {
+                    scope: "sessions per client",
+                    limit: limits.max_per_client,
+                    current,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// 查找可复用的会话：同一用户、小说、音色和合成参数，取最近活动的一个（后台会话除外）
+    fn find_reusable(
+        &self,
+        novel_id: Uuid,
+        voice_id: Uuid,
+        params: &SynthesisParams,
+        user_id: Option<Uuid>,
+    ) -> Option<Session> {
+        self.session_manager
+            .list_all()
+            .into_iter()
+            .filter_map(|id| self.session_manager.get(&id).ok())
+            .filter(|session| {
+                !session.background
+                    && session.novel_id == novel_id
+                    && session.voice_id == voice_id
+                    && &session.params == params
+                    && session.user_id == user_id
+            })
+            .max_by_key(|session| session.last_activity)
+    }
+
+    /// 复用已有会话：指定了 start_index 则跳转，否则保持已保存的进度；
+    /// 指定了 auto_advance 则切换模式，会话的其他设置不变
+    async fn reuse(
+        &self,
+        session: Session,
+        start_index: Option<u32>,
+        auto_advance: Option<bool>,
+    ) -> Result<PlayResponse, ApplicationError> {
+        if let Some(enabled) = auto_advance.filter(|enabled| *enabled != session.auto_advance) {
+            self.session_manager
+                .set_auto_advance(&session.id, enabled)
+                .map_err(|e| ApplicationError::internal(e.to_string()))?;
+        }
+        let mut current_index = session.current_index;
+        let mut offset_ms = session.offset_ms;
+        match start_index {
+            Some(index) if index != session.current_index => {
+                self.task_manager.cancel_pending(&session.id);
+                self.session_manager
+                    .update_index(&session.id, index)
+                    .map_err(|e| ApplicationError::internal(e.to_string()))?;
+                current_index = index;
+                offset_ms = 0;
+            }
+            _ => self.session_manager.touch(&session.id),
+        }
+
+        tracing::info!(
+            session_id = %session.id,
+            novel_id = %session.novel_id,
+            voice_id = %session.voice_id,
+            current_index = current_index,
+            "Play session reused"
+        );
+        self.prefetch.refresh(&session.id).await;
+
+        Ok(PlayResponse {
+            session_id: session.id,
+            novel_id: session.novel_id,
+            voice_id: session.voice_id,
+            current_index,
+            offset_ms,
+            window: session.window,
+            reused: true,
+            resumed: start_index.is_none(),
+            auto_advance: auto_advance.unwrap_or(session.auto_advance),
         })
     }
 }
@@ -82,16 +332,19 @@ impl PlayHandler {
 pub struct SeekHandler {
     session_manager: Arc<dyn SessionManagerPort>,
     task_manager: Arc<dyn TaskManagerPort>,
+    prefetch: Arc<PrefetchCoordinator>,
 }
 
 impl SeekHandler {
     pub fn new(
         session_manager: Arc<dyn SessionManagerPort>,
         task_manager: Arc<dyn TaskManagerPort>,
+        prefetch: Arc<PrefetchCoordinator>,
     ) -> Self {
         Self {
             session_manager,
             task_manager,
+            prefetch,
         }
     }
 
@@ -109,6 +362,7 @@ impl SeekHandler {
         self.session_manager
             .update_index(&cmd.session_id, cmd.segment_index)
             .map_err(|e| ApplicationError::internal(e.to_string()))?;
+        self.prefetch.refresh(&cmd.session_id).await;
 
         tracing::info!(
             session_id = %cmd.session_id,
@@ -125,11 +379,336 @@ impl SeekHandler {
     }
 }
 
+/// StepSegment Handler - 前进/后退一个片段
+///
+/// 索引在会话管理器中原子地移动，随后按新位置预取；不取消窗口内的 pending 任务
+pub struct StepSegmentHandler {
+    session_manager: Arc<dyn SessionManagerPort>,
+    novel_repo: Arc<dyn NovelRepositoryPort>,
+    prefetch: Arc<PrefetchCoordinator>,
+    segment_status: SegmentStatusResolver,
+}
+
+impl StepSegmentHandler {
+    pub fn new(
+        session_manager: Arc<dyn SessionManagerPort>,
+        novel_repo: Arc<dyn NovelRepositoryPort>,
+        prefetch: Arc<PrefetchCoordinator>,
+        segment_status: SegmentStatusResolver,
+    ) -> Self {
+        Self {
+            session_manager,
+            novel_repo,
+            prefetch,
+            segment_status,
+        }
+    }
+
+    pub async fn handle_next(&self, cmd: NextSegmentCommand) -> Result<StepSegmentResponse, ApplicationError> {
+        self.step(cmd.session_id, 1).await
+    }
+
+    pub async fn handle_prev(&self, cmd: PrevSegmentCommand) -> Result<StepSegmentResponse, ApplicationError> {
+        self.step(cmd.session_id, -1).await
+    }
+
+    async fn step(&self, session_id: String, delta: i32) -> Result<StepSegmentResponse, ApplicationError> {
+        let session = self
+            .session_manager
+            .get(&session_id)
+            .map_err(|_| ApplicationError::not_found_str("Session", &session_id))?;
+        let novel = self
+            .novel_repo
+            .find_by_id(session.novel_id)
+            .await?
+            .ok_or_else(|| ApplicationError::not_found("Novel", session.novel_id))?;
+        if novel.total_segments == 0 {
+            return Err(ApplicationError::invalid_state("Novel has no segments"));
+        }
+
+        let last_index = (novel.total_segments - 1) as u32;
+        let current_index = self
+            .session_manager
+            .step_index(&session_id, delta, last_index)
+            .map_err(|e| match e {
+                SessionError::InvalidOperation(msg) => ApplicationError::invalid_state(msg),
+                _ => ApplicationError::not_found_str("Session", &session_id),
+            })?;
+        let moved = current_index != session.current_index;
+        if moved {
+            self.prefetch.refresh(&session_id).await;
+        }
+
+        let segment = self
+            .novel_repo
+            .find_segment(session.novel_id, current_index as usize)
+            .await?
+            .ok_or_else(|| {
+                ApplicationError::internal(format!(
+                    "Segment not found: {}:{}",
+                    session.novel_id, current_index
+                ))
+            })?;
+        // 在预取之后解析，刚提交的任务显示为 pending
+        let status = self.segment_status.resolve(&session, &segment).await;
+
+        tracing::debug!(
+            session_id = %session_id,
+            current_index = current_index,
+            moved = moved,
+            "Session segment stepped"
+        );
+
+        Ok(StepSegmentResponse {
+            session_id,
+            current_index,
+            moved,
+            total_segments: novel.total_segments,
+            content: segment.content,
+            char_count: segment.char_count,
+            status,
+        })
+    }
+}
+
+/// ReportPosition Handler - 记录客户端上报的播放进度
+///
+/// 播放中定期调用，进度随会话持久化并计入收听统计；同时刷新活动时间，持续播放的会话不会被判定为空闲。
+/// 自动前进模式下进入后面的片段时检查下一片段是否已就绪
+pub struct ReportPositionHandler {
+    session_manager: Arc<dyn SessionManagerPort>,
+    progress_repo: Arc<dyn ReadingProgressRepositoryPort>,
+    prefetch: Arc<PrefetchCoordinator>,
+    listening: Arc<ListeningTracker>,
+    auto_advance: Arc<AutoAdvanceNotifier>,
+}
+
+impl ReportPositionHandler {
+    pub fn new(
+        session_manager: Arc<dyn SessionManagerPort>,
+        progress_repo: Arc<dyn ReadingProgressRepositoryPort>,
+        prefetch: Arc<PrefetchCoordinator>,
+        listening: Arc<ListeningTracker>,
+        auto_advance: Arc<AutoAdvanceNotifier>,
+    ) -> Self {
+        Self {
+            session_manager,
+            progress_repo,
+            prefetch,
+            listening,
+            auto_advance,
+        }
+    }
+
+    pub async fn handle(
+        &self,
+        cmd: ReportPositionCommand,
+    ) -> Result<ReportPositionResponse, ApplicationError> {
+        let session = self
+            .session_manager
+            .get(&cmd.session_id)
+            .map_err(|_| ApplicationError::not_found_str("Session", &cmd.session_id))?;
+        self.session_manager
+            .update_position(&cmd.session_id, cmd.segment_index, cmd.offset_ms)
+            .map_err(|_| ApplicationError::not_found_str("Session", &cmd.session_id))?;
+        // 用户在小说上的阅读进度在会话关闭后保留，下次播放未指定起始片段时从这里继续
+        let progress = ReadingProgressRecord {
+            user_id: session.user_id,
+            novel_id: session.novel_id,
+            segment_index: cmd.segment_index,
+            offset_ms: cmd.offset_ms,
+            updated_at: Utc::now(),
+        };
+        if let Err(e) = self.progress_repo.save(&progress).await {
+            tracing::warn!(session_id = %cmd.session_id, error = %e, "Failed to save reading progress");
+        }
+        // 播放进入下一片段时窗口随之移动
+        if cmd.segment_index != session.current_index {
+            self.prefetch.refresh(&cmd.session_id).await;
+        }
+        if cmd.segment_index > session.current_index {
+            self.auto_advance.on_advance(&session, cmd.segment_index).await;
+        }
+        self.listening
+            .record(&cmd.session_id, session.user_id, session.novel_id, cmd.segment_index, cmd.offset_ms)
+            .await;
+
+        tracing::debug!(
+            session_id = %cmd.session_id,
+            segment_index = cmd.segment_index,
+            offset_ms = cmd.offset_ms,
+            "Session position reported"
+        );
+
+        Ok(ReportPositionResponse {
+            session_id: cmd.session_id,
+            current_index: cmd.segment_index,
+            offset_ms: cmd.offset_ms,
+        })
+    }
+}
+
+/// Heartbeat Handler - 刷新会话活动时间并汇总预取窗口的就绪状态
+///
+/// 窗口内有未提交推理的片段（如缓存被淘汰）时重新预取
+pub struct HeartbeatHandler {
+    session_manager: Arc<dyn SessionManagerPort>,
+    novel_repo: Arc<dyn NovelRepositoryPort>,
+    prefetch: Arc<PrefetchCoordinator>,
+    segment_status: SegmentStatusResolver,
+}
+
+impl HeartbeatHandler {
+    pub fn new(
+        session_manager: Arc<dyn SessionManagerPort>,
+        novel_repo: Arc<dyn NovelRepositoryPort>,
+        prefetch: Arc<PrefetchCoordinator>,
+        segment_status: SegmentStatusResolver,
+    ) -> Self {
+        Self {
+            session_manager,
+            novel_repo,
+            prefetch,
+            segment_status,
+        }
+    }
+
+    pub async fn handle(&self, cmd: HeartbeatCommand) -> Result<HeartbeatResponse, ApplicationError> {
+        let session = self
+            .session_manager
+            .get(&cmd.session_id)
+            .map_err(|_| ApplicationError::not_found_str("Session", &cmd.session_id))?;
+        self.session_manager.touch(&cmd.session_id);
+
+        let novel = self
+            .novel_repo
+            .find_by_id(session.novel_id)
+            .await?
+            .ok_or_else(|| ApplicationError::not_found("Novel", session.novel_id))?;
+        if novel.total_segments == 0 {
+            return Ok(HeartbeatResponse {
+                session_id: cmd.session_id,
+                current_index: session.current_index,
+                offset_ms: session.offset_ms,
+                total_segments: 0,
+                window: None,
+                readiness: ReadinessSummary::default(),
+            });
+        }
+
+        let current = session.current_index.min((novel.total_segments - 1) as u32);
+        let (start, end) = session.window.window_range(current as usize, novel.total_segments);
+        let indices: Vec<u32> = (start as u32..=end as u32).collect();
+        let segments = self
+            .novel_repo
+            .find_segments_by_indices(session.novel_id, &indices)
+            .await?;
+        let mut statuses = self.segment_status.resolve_all(&session, &segments).await;
+        if statuses
+            .iter()
+            .any(|status| status.readiness == SegmentReadiness::Missing)
+        {
+            self.prefetch.refresh(&cmd.session_id).await;
+            statuses = self.segment_status.resolve_all(&session, &segments).await;
+        }
+        let readiness = ReadinessSummary::new(&statuses, current);
+
+        tracing::debug!(
+            session_id = %cmd.session_id,
+            current_index = current,
+            ready_ahead = readiness.ready_ahead,
+            "Session heartbeat"
+        );
+
+        Ok(HeartbeatResponse {
+            session_id: cmd.session_id,
+            current_index: session.current_index,
+            offset_ms: session.offset_ms,
+            total_segments: novel.total_segments,
+            window: Some((start as u32, end as u32)),
+            readiness,
+        })
+    }
+}
+
+/// PrefetchSegments Handler - 批量预取指定片段
+pub struct PrefetchSegmentsHandler {
+    prefetch: Arc<PrefetchCoordinator>,
+}
+
+impl PrefetchSegmentsHandler {
+    pub fn new(prefetch: Arc<PrefetchCoordinator>) -> Self {
+        Self { prefetch }
+    }
+
+    pub async fn handle(&self, cmd: PrefetchSegmentsCommand) -> Result<BatchPrefetchResult, ApplicationError> {
+        let mut indices = cmd.segment_indices;
+        if let Some((start, end)) = cmd.range {
+            if start > end {
+                return Err(ApplicationError::validation(format!(
+                    "Invalid range: start {} is after end {}",
+                    start, end
+                )));
+            }
+            // 超出上限的部分不展开，由批量预取统一校验数量
+            indices.extend((start..=end).take(self.prefetch.max_batch() + 1));
+        }
+        self.prefetch.prefetch_batch(&cmd.session_id, &indices).await
+    }
+}
+
+/// UpdateWindow Handler - 调整会话预取窗口并按新窗口预取
+pub struct UpdateWindowHandler {
+    session_manager: Arc<dyn SessionManagerPort>,
+    prefetch: Arc<PrefetchCoordinator>,
+}
+
+impl UpdateWindowHandler {
+    pub fn new(
+        session_manager: Arc<dyn SessionManagerPort>,
+        prefetch: Arc<PrefetchCoordinator>,
+    ) -> Self {
+        Self {
+            session_manager,
+            prefetch,
+        }
+    }
+
+    pub async fn handle(&self, cmd: UpdateWindowCommand) -> Result<UpdateWindowResponse, ApplicationError> {
+        let session = self
+            .session_manager
+            .get(&cmd.session_id)
+            .map_err(|_| ApplicationError::not_found_str("Session", &cmd.session_id))?;
+        let window = self.prefetch.resolve_window(
+            Some(cmd.before.unwrap_or(session.window.before)),
+            Some(cmd.after.unwrap_or(session.window.after)),
+        )?;
+
+        self.session_manager
+            .update_window(&cmd.session_id, window)
+            .map_err(|e| ApplicationError::internal(e.to_string()))?;
+        self.prefetch.refresh(&cmd.session_id).await;
+
+        tracing::info!(
+            session_id = %cmd.session_id,
+            before = window.before,
+            after = window.after,
+            "Session window updated"
+        );
+
+        Ok(UpdateWindowResponse {
+            session_id: cmd.session_id,
+            window,
+        })
+    }
+}
+
 /// ChangeVoice Handler - 切换音色并取消所有任务
 pub struct ChangeVoiceHandler {
     session_manager: Arc<dyn SessionManagerPort>,
     task_manager: Arc<dyn TaskManagerPort>,
     voice_repo: Arc<dyn VoiceRepositoryPort>,
+    prefetch: Arc<PrefetchCoordinator>,
 }
 
 impl ChangeVoiceHandler {
@@ -137,17 +716,24 @@ impl ChangeVoiceHandler {
         session_manager: Arc<dyn SessionManagerPort>,
         task_manager: Arc<dyn TaskManagerPort>,
         voice_repo: Arc<dyn VoiceRepositoryPort>,
+        prefetch: Arc<PrefetchCoordinator>,
     ) -> Self {
         Self {
             session_manager,
             task_manager,
             voice_repo,
+            prefetch,
         }
     }
 
     pub async fn handle(&self, cmd: ChangeVoiceCommand) -> Result<ChangeVoiceResponse, ApplicationError> {
+        if let Some(params) = &cmd.params {
+            params.validate().map_err(ApplicationError::validation)?;
+        }
+
         // 验证会话存在
-        self.session_manager
+        let session = self
+            .session_manager
             .get(&cmd.session_id)
             .map_err(|_| ApplicationError::not_found_str("Session", &cmd.session_id))?;
 
@@ -160,15 +746,38 @@ impl ChangeVoiceHandler {
         // 取消所有 pending 任务
         let cancelled_count = self.task_manager.cancel_pending(&cmd.session_id);
 
+        // 过渡中再次切换时沿用最初的旧音色（其片段已就绪），切回旧音色则结束过渡
+        let handoff = (cmd.handoff && cmd.voice_id != session.voice_id).then(|| {
+            session
+                .voice_handoff
+                .clone()
+                .filter(|handoff| handoff.voice_id != cmd.voice_id)
+                .unwrap_or(VoiceHandoff {
+                    voice_id: session.voice_id,
+                    params: session.params.clone(),
+                })
+        });
+        let handoff_voice_id = handoff.as_ref().map(|handoff| handoff.voice_id);
+
         // 更新音色
         self.session_manager
             .update_voice(&cmd.session_id, cmd.voice_id)
             .map_err(|e| ApplicationError::internal(e.to_string()))?;
+        self.session_manager
+            .set_voice_handoff(&cmd.session_id, handoff)
+            .map_err(|e| ApplicationError::internal(e.to_string()))?;
+        if let Some(params) = cmd.params {
+            self.session_manager
+                .update_params(&cmd.session_id, params)
+                .map_err(|e| ApplicationError::internal(e.to_string()))?;
+        }
+        self.prefetch.refresh(&cmd.session_id).await;
 
         tracing::info!(
             session_id = %cmd.session_id,
             voice_id = %cmd.voice_id,
             cancelled_count = cancelled_count,
+            handoff_voice_id = ?handoff_voice_id,
             "Session voice changed"
         );
 
@@ -176,6 +785,72 @@ impl ChangeVoiceHandler {
             session_id: cmd.session_id,
             voice_id: cmd.voice_id,
             cancelled_count,
+            handoff_voice_id,
+        })
+    }
+}
+
+/// ClaimSession Handler - 接管其他设备上的会话
+///
+/// 断开原设备的会话 WebSocket（原设备收到 SessionTransferred），返回当前进度和进行中的任务，
+/// 新设备沿用同一会话继续播放，不重复创建会话和推理任务
+pub struct ClaimSessionHandler {
+    session_manager: Arc<dyn SessionManagerPort>,
+    task_manager: Arc<dyn TaskManagerPort>,
+    event_publisher: Arc<EventPublisher>,
+}
+
+impl ClaimSessionHandler {
+    pub fn new(
+        session_manager: Arc<dyn SessionManagerPort>,
+        task_manager: Arc<dyn TaskManagerPort>,
+        event_publisher: Arc<EventPublisher>,
+    ) -> Self {
+        Self {
+            session_manager,
+            task_manager,
+            event_publisher,
+        }
+    }
+
+    pub async fn handle(&self, cmd: ClaimSessionCommand) -> Result<ClaimSessionResponse, ApplicationError> {
+        let session = self
+            .session_manager
+            .get(&cmd.session_id)
+            .map_err(|_| ApplicationError::not_found_str("Session", &cmd.session_id))?;
+        self.session_manager.touch(&cmd.session_id);
+        self.event_publisher.transfer_session(&cmd.session_id);
+
+        let mut tasks: Vec<TaskInfo> = self
+            .task_manager
+            .get_tasks_by_session(&cmd.session_id)
+            .into_iter()
+            .filter(|task| matches!(task.state, TaskState::Pending | TaskState::Inferring))
+            .map(|task| TaskInfo {
+                task_id: task.task_id,
+                segment_index: task.segment_index,
+                state: task.state,
+            })
+            .collect();
+        tasks.sort_by_key(|task| task.segment_index);
+
+        tracing::info!(
+            session_id = %cmd.session_id,
+            current_index = session.current_index,
+            in_flight_tasks = tasks.len(),
+            "Session claimed by another device"
+        );
+
+        Ok(ClaimSessionResponse {
+            session_id: session.id,
+            novel_id: session.novel_id,
+            voice_id: session.voice_id,
+            current_index: session.current_index,
+            offset_ms: session.offset_ms,
+            params: session.params,
+            playback_speed: session.playback_speed,
+            window: session.window,
+            tasks,
         })
     }
 }
@@ -184,6 +859,7 @@ impl ChangeVoiceHandler {
 pub struct CloseSessionHandler {
     session_manager: Arc<dyn SessionManagerPort>,
     task_manager: Arc<dyn TaskManagerPort>,
+    audio_cache: Arc<dyn AudioCachePort>,
     event_publisher: Arc<EventPublisher>,
 }
 
@@ -191,21 +867,24 @@ impl CloseSessionHandler {
     pub fn new(
         session_manager: Arc<dyn SessionManagerPort>,
         task_manager: Arc<dyn TaskManagerPort>,
+        audio_cache: Arc<dyn AudioCachePort>,
         event_publisher: Arc<EventPublisher>,
     ) -> Self {
         Self {
             session_manager,
             task_manager,
+            audio_cache,
             event_publisher,
         }
     }
 
     pub async fn handle(&self, cmd: CloseSessionCommand) -> Result<CloseSessionResponse, ApplicationError> {
-        // 取消所有 pending 任务
-        let cancelled = self.task_manager.cancel_pending(&cmd.session_id);
+        // 取消所有 pending 任务，中止推理中的任务
+        let cancelled = self.task_manager.cancel_all(&cmd.session_id);
 
-        // 清理任务
+        // 清理任务，解除窗口内缓存条目的固定
         self.task_manager.cleanup_session(&cmd.session_id);
+        self.audio_cache.unpin(&cmd.session_id);
 
         // 发布会话关闭事件
         self.event_publisher.publish_session_closed(&cmd.session_id, "client_close");
@@ -229,3 +908,621 @@ impl CloseSessionHandler {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::casting::VoiceCastingResolver;
+    use crate::application::ports::{
+        generate_cache_key_with_params, AudioFormat, CacheMetadata, NovelRecord, NovelStatus, PlaybackHints,
+        TextSegmentRecord, VoiceKey, VoiceRecord,
+    };
+    use crate::application::prefetch::PrefetchSettings;
+    use crate::domain::voice::{TtsConfig, VoiceTags};
+    use crate::infrastructure::events::WsEvent;
+    use crate::infrastructure::memory::TaskQueue;
+    use crate::infrastructure::persistence::sqlite::{
+        create_pool, run_migrations, DatabaseConfig, SqliteListeningStatsRepository, SqliteNovelRepository,
+        SqliteReadingProgressRepository, SqliteSettingsRepository, SqliteVoiceCastingRepository,
+        SqliteVoiceRepository,
+    };
+    use crate::infrastructure::{InMemorySessionManager, InMemoryTaskManager, SledAudioCache};
+    use std::path::PathBuf;
+
+    /// 测试小说的片段数
+    const SEGMENTS: usize = 5;
+
+    struct Fixture {
+        session_manager: Arc<InMemorySessionManager>,
+        task_manager: Arc<InMemoryTaskManager>,
+        novel_repo: Arc<SqliteNovelRepository>,
+        voice_repo: Arc<SqliteVoiceRepository>,
+        settings_repo: Arc<SqliteSettingsRepository>,
+        progress_repo: Arc<SqliteReadingProgressRepository>,
+        audio_cache: Arc<SledAudioCache>,
+        event_publisher: Arc<EventPublisher>,
+        prefetch: Arc<PrefetchCoordinator>,
+        segment_status: SegmentStatusResolver,
+        listening: Arc<ListeningTracker>,
+        /// 两本小说，各有 SEGMENTS 个片段
+        novels: [Uuid; 2],
+        /// 两个共享音色
+        voices: [Uuid; 2],
+        _dir: tempfile::TempDir,
+    }
+
+    /// 已保存两本小说和两个音色的测试环境
+    async fn fixture() -> Fixture {
+        let dir = tempfile::tempdir().unwrap();
+        // 文件数据库允许多个连接，并发请求的查询可以交错执行
+        let pool = create_pool(&DatabaseConfig::new(dir.path().join("rovel.db"))).await.unwrap();
+        run_migrations(&pool).await.unwrap();
+        let novel_repo = Arc::new(SqliteNovelRepository::new(pool.clone()));
+        let voice_repo = Arc::new(SqliteVoiceRepository::new(pool.clone()));
+        let settings_repo = Arc::new(SqliteSettingsRepository::new(pool.clone()));
+        let progress_repo = Arc::new(SqliteReadingProgressRepository::new(pool.clone()));
+        let casting_repo = Arc::new(SqliteVoiceCastingRepository::new(pool.clone()));
+        let listening = Arc::new(ListeningTracker::new(Arc::new(SqliteListeningStatsRepository::new(pool))));
+        let audio_cache = Arc::new(SledAudioCache::open(dir.path().join("cache"), 1024 * 1024).unwrap());
+        let session_manager = Arc::new(InMemorySessionManager::new());
+        let task_manager = Arc::new(InMemoryTaskManager::new(Arc::new(TaskQueue::new(100))));
+
+        let novels = [Uuid::new_v4(), Uuid::new_v4()];
+        for novel_id in novels {
+            novel_repo
+                .save(&NovelRecord {
+                    id: novel_id,
+                    title: "测试".to_string(),
+                    raw_text_path: PathBuf::from("novel.txt"),
+                    total_segments: SEGMENTS,
+                    status: NovelStatus::Ready,
+                    user_id: None,
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                })
+                .await
+                .unwrap();
+            let segments: Vec<TextSegmentRecord> = (0..SEGMENTS)
+                .map(|index| TextSegmentRecord {
+                    id: Uuid::new_v4(),
+                    novel_id,
+                    index,
+                    content: format!("第{}段", index),
+                    char_count: 3,
+                })
+                .collect();
+            novel_repo.save_segments(&segments).await.unwrap();
+        }
+        let voices = [Uuid::new_v4(), Uuid::new_v4()];
+        for voice_id in voices {
+            voice_repo
+                .save(&VoiceRecord {
+                    id: voice_id,
+                    name: "旁白".to_string(),
+                    reference_audio_path: PathBuf::from("voice.wav"),
+                    processed_audio_path: None,
+                    description: None,
+                    prompt_text: None,
+                    engine: None,
+                    tts_config: TtsConfig::default(),
+                    tags: VoiceTags::default(),
+                    user_id: None,
+                    created_at: Utc::now(),
+                })
+                .await
+                .unwrap();
+        }
+
+        let event_publisher = Arc::new(EventPublisher::new());
+        let voice_casting =
+            VoiceCastingResolver::new(novel_repo.clone(), casting_repo, voice_repo.clone(), false);
+        let prefetch = Arc::new(PrefetchCoordinator::new(
+            session_manager.clone(),
+            task_manager.clone(),
+            novel_repo.clone(),
+            audio_cache.clone(),
+            voice_casting.clone(),
+            event_publisher.clone(),
+            PrefetchSettings::default(),
+        ));
+        let segment_status = SegmentStatusResolver::new(task_manager.clone(), audio_cache.clone(), voice_casting);
+
+        Fixture {
+            session_manager,
+            task_manager,
+            novel_repo,
+            voice_repo,
+            settings_repo,
+            progress_repo,
+            audio_cache,
+            event_publisher,
+            prefetch,
+            segment_status,
+            listening,
+            novels,
+            voices,
+            _dir: dir,
+        }
+    }
+
+    impl Fixture {
+        fn play_handler(&self, limits: SessionLimits) -> PlayHandler {
+            PlayHandler::new(
+                self.session_manager.clone(),
+                self.task_manager.clone(),
+                self.novel_repo.clone(),
+                self.voice_repo.clone(),
+                self.settings_repo.clone(),
+                self.progress_repo.clone(),
+                self.prefetch.clone(),
+                limits,
+            )
+        }
+
+        fn step_handler(&self) -> StepSegmentHandler {
+            StepSegmentHandler::new(
+                self.session_manager.clone(),
+                self.novel_repo.clone(),
+                self.prefetch.clone(),
+                self.segment_status.clone(),
+            )
+        }
+
+        fn report_position_handler(&self) -> ReportPositionHandler {
+            ReportPositionHandler::new(
+                self.session_manager.clone(),
+                self.progress_repo.clone(),
+                self.prefetch.clone(),
+                self.listening.clone(),
+                Arc::new(AutoAdvanceNotifier::new(
+                    self.novel_repo.clone(),
+                    self.segment_status.clone(),
+                    self.event_publisher.clone(),
+                )),
+            )
+        }
+
+        fn change_voice_handler(&self) -> ChangeVoiceHandler {
+            ChangeVoiceHandler::new(
+                self.session_manager.clone(),
+                self.task_manager.clone(),
+                self.voice_repo.clone(),
+                self.prefetch.clone(),
+            )
+        }
+
+        fn prefetch_handler(&self) -> PrefetchSegmentsHandler {
+            PrefetchSegmentsHandler::new(self.prefetch.clone())
+        }
+
+        /// 缓存第一本小说在指定音色、默认参数下的片段音频
+        async fn cache_segment(&self, voice: usize, segment_index: u32) {
+            let voice_id = self.voices[voice];
+            let voice = VoiceKey::new(voice_id, None, &TtsConfig::default());
+            let content = format!("第{}段", segment_index);
+            let cache_key = generate_cache_key_with_params(&content, &voice, &SynthesisParams::default());
+            self.audio_cache
+                .put(
+                    &cache_key,
+                    vec![1, 2, 3],
+                    CacheMetadata {
+                        novel_id: self.novels[0],
+                        segment_index,
+                        voice_id,
+                        content_hash: "hash".to_string(),
+                        duration_ms: 100,
+                        sample_rate: None,
+                        format: AudioFormat::Wav,
+                    },
+                )
+                .await
+                .unwrap();
+        }
+    }
+
+    fn play(novel_id: Uuid, voice_id: Uuid) -> PlayCommand {
+        PlayCommand {
+            novel_id,
+            voice_id: Some(voice_id),
+            start_index: None,
+            offset_ms: None,
+            reuse: false,
+            params: SynthesisParams::default(),
+            playback_speed: None,
+            playback_hints: PlaybackHints::default(),
+            window_before: None,
+            window_after: None,
+            auto_advance: None,
+            client: Some("127.0.0.1".to_string()),
+            user_id: None,
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_play_respects_session_limits() {
+        let f = fixture().await;
+        let handler = Arc::new(f.play_handler(SessionLimits {
+            max_active: 10,
+            max_per_client: 3,
+        }));
+
+        let requests: Vec<_> = (0..12)
+            .map(|_| {
+                let handler = handler.clone();
+                let cmd = play(f.novels[0], f.voices[0]);
+                tokio::spawn(async move { handler.handle(cmd).await })
+            })
+            .collect();
+        let mut created = 0;
+        for request in requests {
+            match request.await.unwrap() {
+                Ok(_) => created += 1,
+                Err(ApplicationError::LimitExceeded { limit, .. }) => assert_eq!(limit, 3),
+                Err(e) => panic!("unexpected error: {}", e),
+            }
+        }
+        assert_eq!(created, 3);
+        assert_eq!(f.session_manager.list_all().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_play_reuses_matching_session() {
+        let f = fixture().await;
+        let handler = f.play_handler(SessionLimits::default());
+        let first = handler.handle(play(f.novels[0], f.voices[0])).await.unwrap();
+        assert!(!first.reused);
+
+        // 同一小说、音色和参数：复用并跳转到指定片段
+        let reused = handler
+            .handle(PlayCommand {
+                reuse: true,
+                start_index: Some(3),
+                ..play(f.novels[0], f.voices[0])
+            })
+            .await
+            .unwrap();
+        assert!(reused.reused);
+        assert_eq!(reused.session_id, first.session_id);
+        assert_eq!(reused.current_index, 3);
+        assert_eq!(f.session_manager.get(&first.session_id).unwrap().current_index, 3);
+
+        // 未指定 start_index：保持会话的进度
+        let resumed = handler
+            .handle(PlayCommand {
+                reuse: true,
+                ..play(f.novels[0], f.voices[0])
+            })
+            .await
+            .unwrap();
+        assert_eq!(resumed.session_id, first.session_id);
+        assert_eq!(resumed.current_index, 3);
+        assert_eq!(f.session_manager.list_all().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_play_creates_session_when_novel_or_voice_differs() {
+        let f = fixture().await;
+        let handler = f.play_handler(SessionLimits::default());
+        let first = handler.handle(play(f.novels[0], f.voices[0])).await.unwrap();
+
+        let reuse = |novel_id, voice_id| PlayCommand {
+            reuse: true,
+            ..play(novel_id, voice_id)
+        };
+        let other_voice = handler.handle(reuse(f.novels[0], f.voices[1])).await.unwrap();
+        assert!(!other_voice.reused);
+        assert_ne!(other_voice.session_id, first.session_id);
+
+        let other_novel = handler.handle(reuse(f.novels[1], f.voices[0])).await.unwrap();
+        assert!(!other_novel.reused);
+        assert_ne!(other_novel.session_id, first.session_id);
+
+        // 合成参数不同也不复用
+        let other_params = handler
+            .handle(PlayCommand {
+                params: SynthesisParams {
+                    speed: Some(1.2),
+                    ..SynthesisParams::default()
+                },
+                ..reuse(f.novels[0], f.voices[0])
+            })
+            .await
+            .unwrap();
+        assert!(!other_params.reused);
+
+        // 未要求复用时总是创建新会话
+        let fresh = handler.handle(play(f.novels[0], f.voices[0])).await.unwrap();
+        assert!(!fresh.reused);
+        assert_eq!(f.session_manager.list_all().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_play_resumes_from_saved_progress() {
+        let f = fixture().await;
+        let handler = f.play_handler(SessionLimits::default());
+        let save_progress = |novel_id, segment_index| {
+            let progress_repo = f.progress_repo.clone();
+            async move {
+                progress_repo
+                    .save(&ReadingProgressRecord {
+                        user_id: None,
+                        novel_id,
+                        segment_index,
+                        offset_ms: 1500,
+                        updated_at: Utc::now(),
+                    })
+                    .await
+                    .unwrap();
+            }
+        };
+        save_progress(f.novels[0], 2).await;
+
+        let resumed = handler.handle(play(f.novels[0], f.voices[0])).await.unwrap();
+        assert!(resumed.resumed);
+        assert_eq!((resumed.current_index, resumed.offset_ms), (2, 1500));
+
+        // 指定了 start_index 时不使用保存的进度
+        let explicit = handler
+            .handle(PlayCommand {
+                start_index: Some(4),
+                ..play(f.novels[0], f.voices[0])
+            })
+            .await
+            .unwrap();
+        assert!(!explicit.resumed);
+        assert_eq!((explicit.current_index, explicit.offset_ms), (4, 0));
+
+        // 进度超出片段数（如小说重新分段后）：从头开始
+        save_progress(f.novels[1], SEGMENTS as u32).await;
+        let stale = handler.handle(play(f.novels[1], f.voices[0])).await.unwrap();
+        assert_eq!((stale.current_index, stale.offset_ms), (0, 0));
+    }
+
+    #[tokio::test]
+    async fn test_step_segment_within_bounds() {
+        let f = fixture().await;
+        let handler = f.step_handler();
+        let session_id = f
+            .session_manager
+            .create(Session::new(f.novels[0], f.voices[0], 0))
+            .unwrap();
+        let next = || NextSegmentCommand {
+            session_id: session_id.clone(),
+        };
+        let prev = || PrevSegmentCommand {
+            session_id: session_id.clone(),
+        };
+
+        // 第一个片段：向前不移动
+        let step = handler.handle_prev(prev()).await.unwrap();
+        assert!(!step.moved);
+        assert_eq!(step.current_index, 0);
+
+        let step = handler.handle_next(next()).await.unwrap();
+        assert!(step.moved);
+        assert_eq!((step.current_index, step.content.as_str()), (1, "第1段"));
+        assert_eq!(step.total_segments, SEGMENTS);
+        let step = handler.handle_prev(prev()).await.unwrap();
+        assert!(step.moved);
+        assert_eq!(step.current_index, 0);
+
+        // 最后一个片段：向后不移动
+        let last_index = SEGMENTS as u32 - 1;
+        f.session_manager.update_index(&session_id, last_index).unwrap();
+        let step = handler.handle_next(next()).await.unwrap();
+        assert!(!step.moved);
+        assert_eq!(step.current_index, last_index);
+    }
+
+    #[tokio::test]
+    async fn test_step_segment_rejects_out_of_range_index() {
+        let f = fixture().await;
+        let handler = f.step_handler();
+        let session_id = f
+            .session_manager
+            .create(Session::new(f.novels[0], f.voices[0], SEGMENTS as u32 + 2))
+            .unwrap();
+
+        let err = handler
+            .handle_prev(PrevSegmentCommand {
+                session_id: session_id.clone(),
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ApplicationError::InvalidState(_)));
+        assert_eq!(f.session_manager.get(&session_id).unwrap().current_index, SEGMENTS as u32 + 2);
+
+        let err = handler
+            .handle_next(NextSegmentCommand {
+                session_id: "missing".to_string(),
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ApplicationError::ValidationError(_)));
+    }
+
+    #[tokio::test]
+    async fn test_report_position_saves_progress() {
+        let f = fixture().await;
+        let handler = f.report_position_handler();
+        let session_id = f
+            .session_manager
+            .create(Session::new(f.novels[0], f.voices[0], 0))
+            .unwrap();
+        let before = f.session_manager.get(&session_id).unwrap().last_activity;
+
+        let report = |segment_index, offset_ms| ReportPositionCommand {
+            session_id: session_id.clone(),
+            segment_index,
+            offset_ms,
+        };
+        let response = handler.handle(report(2, 1200)).await.unwrap();
+        assert_eq!((response.current_index, response.offset_ms), (2, 1200));
+
+        let session = f.session_manager.get(&session_id).unwrap();
+        assert_eq!((session.current_index, session.offset_ms), (2, 1200));
+        assert!(session.last_activity >= before);
+        // 阅读进度在会话关闭后保留
+        let progress = f.progress_repo.find(None, f.novels[0]).await.unwrap().unwrap();
+        assert_eq!((progress.segment_index, progress.offset_ms), (2, 1200));
+
+        // 跳转片段时片段内偏移归零
+        f.session_manager.update_index(&session_id, 3).unwrap();
+        assert_eq!(f.session_manager.get(&session_id).unwrap().offset_ms, 0);
+
+        let err = handler
+            .handle(ReportPositionCommand {
+                session_id: "missing".to_string(),
+                segment_index: 0,
+                offset_ms: 0,
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ApplicationError::ValidationError(_)));
+    }
+
+    #[tokio::test]
+    async fn test_auto_advance_publishes_next_segment_ready() {
+        let f = fixture().await;
+        let handler = f.report_position_handler();
+        let session = Session {
+            auto_advance: true,
+            ..Session::new(f.novels[0], f.voices[0], 0)
+        };
+        let session_id = f.session_manager.create(session).unwrap();
+        let mut events = f.event_publisher.register_session(&session_id);
+
+        f.cache_segment(0, 2).await;
+
+        let report = |segment_index| {
+            handler.handle(ReportPositionCommand {
+                session_id: session_id.clone(),
+                segment_index,
+                offset_ms: 0,
+            })
+        };
+        let mut next_ready = || {
+            let mut ready = Vec::new();
+            while let Ok(event) = events.try_recv() {
+                if let WsEvent::NextSegmentReady {
+                    segment_index,
+                    audio_url,
+                    ..
+                } = event
+                {
+                    ready.push((segment_index, audio_url));
+                }
+            }
+            ready
+        };
+
+        // 进入第 1 段：下一段已就绪，立即推送
+        report(1).await.unwrap();
+        let ready = next_ready();
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].0, 2);
+        assert_eq!(
+            ready[0].1,
+            format!("/api/audio/{}/{}/2?session_id={}", f.novels[0], f.voices[0], session_id)
+        );
+
+        // 下一段未缓存或位置未前进：不推送
+        report(2).await.unwrap();
+        report(2).await.unwrap();
+        assert!(next_ready().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_prefetch_segments_batch() {
+        let f = fixture().await;
+        let handler = f.prefetch_handler();
+        let session_id = f
+            .session_manager
+            .create(Session::new(f.novels[0], f.voices[0], 0))
+            .unwrap();
+        f.cache_segment(0, 1).await;
+        let prefetch = |segment_indices: Vec<u32>, range| PrefetchSegmentsCommand {
+            session_id: session_id.clone(),
+            segment_indices,
+            range,
+        };
+
+        // 列表与范围合并去重
+        let result = handler.handle(prefetch(vec![1, 3], Some((0, 3)))).await.unwrap();
+        assert_eq!((result.submitted, result.cached, result.queued), (3, 1, 0));
+        assert_eq!(result.queue_depth, 3);
+        let tasks = f.task_manager.get_tasks_by_session(&session_id);
+        assert!(tasks.iter().all(|task| task.explicit));
+
+        // 已排队的片段不重复提交，显式预取的任务不随窗口移动取消
+        let result = handler.handle(prefetch(vec![0, 4], None)).await.unwrap();
+        assert_eq!((result.submitted, result.queued), (1, 1));
+        assert_eq!(f.task_manager.cancel_outside(&session_id, 4, 4), 0);
+
+        for cmd in [
+            prefetch(Vec::new(), None),
+            prefetch(vec![SEGMENTS as u32], None),
+            prefetch(Vec::new(), Some((3, 1))),
+            prefetch(Vec::new(), Some((0, 1000))),
+        ] {
+            let err = handler.handle(cmd).await.unwrap_err();
+            assert!(matches!(err, ApplicationError::ValidationError(_)));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_change_voice_handoff_until_new_voice_ready() {
+        let f = fixture().await;
+        let handler = f.change_voice_handler();
+        let session_id = f
+            .session_manager
+            .create(Session::new(f.novels[0], f.voices[0], 0))
+            .unwrap();
+        let mut events = f.event_publisher.register_session(&session_id);
+        f.cache_segment(0, 0).await;
+        let change_voice = |voice_id, handoff| ChangeVoiceCommand {
+            session_id: session_id.clone(),
+            voice_id,
+            params: None,
+            handoff,
+        };
+
+        let response = handler.handle(change_voice(f.voices[1], true)).await.unwrap();
+        assert_eq!(response.handoff_voice_id, Some(f.voices[0]));
+        let session = f.session_manager.get(&session_id).unwrap();
+        assert_eq!(session.voice_id, f.voices[1]);
+        assert_eq!(session.handoff_for(0).map(|(voice_id, _)| voice_id), Some(f.voices[0]));
+
+        // 新音色的当前片段合成完成后，下次刷新窗口时结束过渡并通知
+        let task = f
+            .task_manager
+            .get_tasks_by_session(&session_id)
+            .into_iter()
+            .find(|task| task.segment_index == 0)
+            .unwrap();
+        assert_eq!(task.voice_id, f.voices[1]);
+        f.prefetch.refresh(&session_id).await;
+        assert!(f.session_manager.get(&session_id).unwrap().voice_handoff.is_some());
+        f.cache_segment(1, 0).await;
+        f.task_manager.set_state(&task.task_id, TaskState::Ready).unwrap();
+        f.prefetch.refresh(&session_id).await;
+        assert!(f.session_manager.get(&session_id).unwrap().voice_handoff.is_none());
+        let mut completed = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            if let WsEvent::VoiceHandoffCompleted {
+                voice_id,
+                segment_index,
+                ..
+            } = event
+            {
+                completed.push((voice_id, segment_index));
+            }
+        }
+        assert_eq!(completed, vec![(f.voices[1], 0)]);
+
+        // 未要求过渡时直接切换
+        let response = handler.handle(change_voice(f.voices[0], false)).await.unwrap();
+        assert_eq!(response.handoff_voice_id, None);
+        assert!(f.session_manager.get(&session_id).unwrap().voice_handoff.is_none());
+    }
+}
@@ -0,0 +1,60 @@
+//! Settings Command Handlers - V2 架构
+
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::access::can_access;
+use crate::application::commands::UpdateSetting;
+use crate::application::error::ApplicationError;
+use crate::application::ports::{
+    SettingsRepositoryPort, VoiceRepositoryPort, SETTING_DEFAULT_VOICE_ID,
+};
+
+/// 允许写入的设置项
+const KNOWN_SETTINGS: &[&str] = &[SETTING_DEFAULT_VOICE_ID];
+
+/// UpdateSetting Handler
+pub struct UpdateSettingHandler {
+    settings_repo: Arc<dyn SettingsRepositoryPort>,
+    voice_repo: Arc<dyn VoiceRepositoryPort>,
+}
+
+impl UpdateSettingHandler {
+    pub fn new(
+        settings_repo: Arc<dyn SettingsRepositoryPort>,
+        voice_repo: Arc<dyn VoiceRepositoryPort>,
+    ) -> Self {
+        Self {
+            settings_repo,
+            voice_repo,
+        }
+    }
+
+    pub async fn handle(&self, command: UpdateSetting) -> Result<(), ApplicationError> {
+        let key = command.key.as_str();
+        if !KNOWN_SETTINGS.contains(&key) {
+            return Err(ApplicationError::validation(format!("Unknown setting: {}", key)));
+        }
+
+        let Some(value) = command.value else {
+            self.settings_repo.delete(command.user_id, key).await?;
+            tracing::info!(key, "Setting cleared");
+            return Ok(());
+        };
+
+        if key == SETTING_DEFAULT_VOICE_ID {
+            let voice_id = Uuid::parse_str(value.trim())
+                .map_err(|e| ApplicationError::validation(format!("Invalid voice id: {}", e)))?;
+            self.voice_repo
+                .find_by_id(voice_id)
+                .await?
+                .filter(|voice| can_access(voice.user_id, command.user_id))
+                .ok_or_else(|| ApplicationError::not_found("Voice", voice_id))?;
+        }
+
+        self.settings_repo.set(command.user_id, key, value.trim()).await?;
+        tracing::info!(key, value = %value, "Setting updated");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,147 @@
+//! User Command Handlers - 注册与登录
+
+use chrono::Utc;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::commands::{AuthTokenResponse, LoginUser, RegisterUser};
+use crate::application::error::ApplicationError;
+use crate::application::ports::{
+    AuthError, AuthTokenPort, NovelRepositoryPort, PasswordHasherPort, RepositoryError, UserRecord,
+    UserRepositoryPort,
+};
+
+/// 用户名最大长度
+const MAX_USERNAME_LEN: usize = 64;
+
+/// 密码最小长度
+const MIN_PASSWORD_LEN: usize = 8;
+
+fn auth_error(err: AuthError) -> ApplicationError {
+    ApplicationError::internal(err.to_string())
+}
+
+fn issue_token(
+    tokens: &dyn AuthTokenPort,
+    user: &UserRecord,
+) -> Result<AuthTokenResponse, ApplicationError> {
+    let issued = tokens.issue(user.id).map_err(auth_error)?;
+    Ok(AuthTokenResponse {
+        user_id: user.id,
+        username: user.username.clone(),
+        token: issued.token,
+        expires_at: issued.expires_at,
+    })
+}
+
+/// RegisterUser Handler
+pub struct RegisterUserHandler {
+    user_repo: Arc<dyn UserRepositoryPort>,
+    novel_repo: Arc<dyn NovelRepositoryPort>,
+    password_hasher: Arc<dyn PasswordHasherPort>,
+    tokens: Arc<dyn AuthTokenPort>,
+    allow_registration: bool,
+}
+
+impl RegisterUserHandler {
+    /// `allow_registration` 为 false 时只允许注册第一个用户
+    pub fn new(
+        user_repo: Arc<dyn UserRepositoryPort>,
+        novel_repo: Arc<dyn NovelRepositoryPort>,
+        password_hasher: Arc<dyn PasswordHasherPort>,
+        tokens: Arc<dyn AuthTokenPort>,
+        allow_registration: bool,
+    ) -> Self {
+        Self {
+            user_repo,
+            novel_repo,
+            password_hasher,
+            tokens,
+            allow_registration,
+        }
+    }
+
+    pub async fn handle(&self, cmd: RegisterUser) -> Result<AuthTokenResponse, ApplicationError> {
+        let username = cmd.username.trim();
+        if username.is_empty() || username.chars().count() > MAX_USERNAME_LEN {
+            return Err(ApplicationError::validation(format!(
+                "username must be 1-{} characters",
+                MAX_USERNAME_LEN
+            )));
+        }
+        if cmd.password.chars().count() < MIN_PASSWORD_LEN {
+            return Err(ApplicationError::validation(format!(
+                "password must be at least {} characters",
+                MIN_PASSWORD_LEN
+            )));
+        }
+
+        let mut user = UserRecord {
+            id: Uuid::new_v4(),
+            username: username.to_string(),
+            password_hash: self.password_hasher.hash(&cmd.password).map_err(auth_error)?,
+            is_admin: false,
+            created_at: Utc::now(),
+        };
+        // 是否为第一个用户由仓储在插入时原子地判断，并发注册不会产生两个管理员
+        match self.user_repo.create(&user, !self.allow_registration).await {
+            Ok(Some(is_admin)) => user.is_admin = is_admin,
+            Ok(None) => return Err(ApplicationError::Forbidden("registration is closed".to_string())),
+            Err(RepositoryError::Duplicate(_)) => {
+                return Err(ApplicationError::conflict(format!("username already exists: {}", username)));
+            }
+            Err(e) => return Err(e.into()),
+        }
+
+        // 第一个用户接管启用账号前导入的小说（及其阅读进度和收听统计）
+        if user.is_admin {
+            let adopted = self.novel_repo.assign_unowned(user.id).await?;
+            if adopted > 0 {
+                tracing::info!(user_id = %user.id, novels = adopted, "First user adopted existing novels");
+            }
+        }
+
+        tracing::info!(user_id = %user.id, username = %user.username, "User registered");
+        issue_token(self.tokens.as_ref(), &user)
+    }
+}
+
+/// LoginUser Handler
+pub struct LoginHandler {
+    user_repo: Arc<dyn UserRepositoryPort>,
+    password_hasher: Arc<dyn PasswordHasherPort>,
+    tokens: Arc<dyn AuthTokenPort>,
+}
+
+impl LoginHandler {
+    pub fn new(
+        user_repo: Arc<dyn UserRepositoryPort>,
+        password_hasher: Arc<dyn PasswordHasherPort>,
+        tokens: Arc<dyn AuthTokenPort>,
+    ) -> Self {
+        Self {
+            user_repo,
+            password_hasher,
+            tokens,
+        }
+    }
+
+    pub async fn handle(&self, cmd: LoginUser) -> Result<AuthTokenResponse, ApplicationError> {
+        let invalid = || ApplicationError::Unauthorized("invalid username or password".to_string());
+
+        let user = self
+            .user_repo
+            .find_by_username(cmd.username.trim())
+            .await?
+            .ok_or_else(invalid)?;
+        if !self
+            .password_hasher
+            .verify(&cmd.password, &user.password_hash)
+            .map_err(auth_error)?
+        {
+            return Err(invalid());
+        }
+
+        issue_token(self.tokens.as_ref(), &user)
+    }
+}
@@ -1,12 +1,26 @@
 //! Voice Command Handlers - V2 架构
 
 use chrono::Utc;
+use std::path::PathBuf;
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::application::commands::{CreateVoice, DeleteVoice};
+use crate::application::commands::{
+    CreateVoice, DeleteVoice, ImportVoice, NormalizeVoiceAudio, UpdateVoice,
+};
+use crate::application::access::can_access;
 use crate::application::error::ApplicationError;
-use crate::application::ports::{VoiceRecord, VoiceRepositoryPort};
+use crate::application::ports::{
+    AudioCachePort, AudioTranscoderPort, ReferenceNormalizeConfig, SessionManagerPort, TaskManagerPort, TaskState,
+    VoiceRecord, VoiceRepositoryPort,
+};
+use crate::domain::voice::{TtsConfig, VoiceTags};
+use crate::infrastructure::events::EventPublisher;
+
+/// 去除首尾空白，空文本视为未设置（用于 prompt_text、engine）
+fn normalize_optional_text(text: Option<String>) -> Option<String> {
+    text.map(|t| t.trim().to_string()).filter(|t| !t.is_empty())
+}
 
 // ============================================================================
 // CreateVoice
@@ -18,6 +32,10 @@ pub struct CreateVoiceResponse {
     pub id: Uuid,
     pub name: String,
     pub description: Option<String>,
+    pub prompt_text: Option<String>,
+    pub engine: Option<String>,
+    pub tts_config: TtsConfig,
+    pub tags: VoiceTags,
 }
 
 /// CreateVoice Handler
@@ -31,6 +49,11 @@ impl CreateVoiceHandler {
     }
 
     pub async fn handle(&self, command: CreateVoice) -> Result<CreateVoiceResponse, ApplicationError> {
+        command
+            .tts_config
+            .validate()
+            .map_err(ApplicationError::validation)?;
+
         let voice_id = Uuid::new_v4();
         let now = Utc::now();
 
@@ -38,7 +61,13 @@ impl CreateVoiceHandler {
             id: voice_id,
             name: command.name.clone(),
             reference_audio_path: command.reference_audio_path,
+            processed_audio_path: None,
             description: command.description.clone(),
+            prompt_text: normalize_optional_text(command.prompt_text.clone()),
+            engine: normalize_optional_text(command.engine.clone()),
+            tts_config: command.tts_config.clone(),
+            tags: command.tags.clone(),
+            user_id: command.user_id,
             created_at: now,
         };
 
@@ -54,25 +83,349 @@ impl CreateVoiceHandler {
             id: voice_id,
             name: command.name,
             description: command.description,
+            prompt_text: voice.prompt_text,
+            engine: voice.engine,
+            tts_config: command.tts_config,
+            tags: command.tags,
+        })
+    }
+}
+
+// ============================================================================
+// UpdateVoice
+// ============================================================================
+
+/// 更新音色响应
+#[derive(Debug, Clone)]
+pub struct UpdateVoiceResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub prompt_text: Option<String>,
+    pub engine: Option<String>,
+    pub tts_config: TtsConfig,
+    pub tags: VoiceTags,
+    pub created_at: String,
+    /// 是否替换了参考音频
+    pub audio_replaced: bool,
+    /// 需要清理的旧文件（被替换的原始音频、失效的归一化音频）
+    pub stale_files: Vec<PathBuf>,
+}
+
+/// UpdateVoice Handler
+pub struct UpdateVoiceHandler {
+    voice_repo: Arc<dyn VoiceRepositoryPort>,
+    audio_cache: Arc<dyn AudioCachePort>,
+}
+
+impl UpdateVoiceHandler {
+    pub fn new(voice_repo: Arc<dyn VoiceRepositoryPort>, audio_cache: Arc<dyn AudioCachePort>) -> Self {
+        Self {
+            voice_repo,
+            audio_cache,
+        }
+    }
+
+    pub async fn handle(&self, command: UpdateVoice) -> Result<UpdateVoiceResponse, ApplicationError> {
+        let voice_id = command.voice_id;
+
+        let mut voice = self
+            .voice_repo
+            .find_by_id(voice_id)
+            .await?
+            .ok_or_else(|| ApplicationError::not_found("Voice", voice_id))?;
+
+        if let Some(name) = command.name {
+            if name.trim().is_empty() {
+                return Err(ApplicationError::validation("Voice name cannot be empty"));
+            }
+            voice.name = name;
+        }
+
+        if let Some(description) = command.description {
+            voice.description = Some(description);
+        }
+
+        if let Some(prompt_text) = command.prompt_text {
+            voice.prompt_text = normalize_optional_text(Some(prompt_text));
+        }
+
+        let previous_engine = voice.engine.clone();
+        if let Some(engine) = command.engine {
+            voice.engine = normalize_optional_text(Some(engine));
+        }
+
+        let previous_config = voice.tts_config.clone();
+        if let Some(tts_config) = command.tts_config {
+            tts_config.validate().map_err(ApplicationError::validation)?;
+            voice.tts_config = tts_config;
+        }
+
+        if let Some(tags) = command.tags {
+            voice.tags = tags;
+        }
+
+        let mut stale_files = Vec::new();
+        let audio_replaced = command.reference_audio_path.is_some();
+        if let Some(path) = command.reference_audio_path {
+            if path != voice.reference_audio_path {
+                stale_files.push(std::mem::replace(&mut voice.reference_audio_path, path));
+            }
+            // 原始音频变化后归一化结果失效，需重新生成
+            stale_files.extend(voice.processed_audio_path.take());
+        }
+
+        self.voice_repo.save(&voice).await?;
+
+        // 参考音频不在缓存 key 中，替换后必须清除该音色的缓存；
+        // 修改引擎或参数后旧条目不再命中，同样清除以释放空间（失败不影响更新结果）
+        if audio_replaced || voice.engine != previous_engine || voice.tts_config != previous_config {
+            if let Err(e) = self.audio_cache.remove_by_voice(voice_id).await {
+                tracing::warn!(voice_id = %voice_id, error = %e, "Failed to purge voice audio cache");
+            }
+        }
+
+        tracing::info!(
+            voice_id = %voice_id,
+            name = %voice.name,
+            audio_replaced,
+            "Voice updated"
+        );
+
+        Ok(UpdateVoiceResponse {
+            id: voice.id,
+            name: voice.name,
+            description: voice.description,
+            prompt_text: voice.prompt_text,
+            engine: voice.engine,
+            tts_config: voice.tts_config,
+            tags: voice.tags,
+            created_at: voice.created_at.to_rfc3339(),
+            audio_replaced,
+            stale_files,
+        })
+    }
+}
+
+// ============================================================================
+// ImportVoice
+// ============================================================================
+
+/// 导入音色响应
+#[derive(Debug, Clone)]
+pub struct ImportVoiceResponse {
+    pub id: Uuid,
+    pub name: String,
+    /// 是否覆盖了已存在的音色
+    pub replaced: bool,
+    /// 覆盖时需要清理的旧文件
+    pub stale_files: Vec<PathBuf>,
+}
+
+/// ImportVoice Handler
+pub struct ImportVoiceHandler {
+    voice_repo: Arc<dyn VoiceRepositoryPort>,
+    audio_cache: Arc<dyn AudioCachePort>,
+}
+
+impl ImportVoiceHandler {
+    pub fn new(voice_repo: Arc<dyn VoiceRepositoryPort>, audio_cache: Arc<dyn AudioCachePort>) -> Self {
+        Self {
+            voice_repo,
+            audio_cache,
+        }
+    }
+
+    pub async fn handle(&self, command: ImportVoice) -> Result<ImportVoiceResponse, ApplicationError> {
+        let voice_id = command.voice_id;
+
+        if command.name.trim().is_empty() {
+            return Err(ApplicationError::validation("Voice name cannot be empty"));
+        }
+        command
+            .tts_config
+            .validate()
+            .map_err(ApplicationError::validation)?;
+
+        let existing = self.voice_repo.find_by_id(voice_id).await?;
+        if existing.is_some() && !command.overwrite {
+            return Err(ApplicationError::conflict(format!(
+                "Voice already exists: {}",
+                voice_id
+            )));
+        }
+        // 不能覆盖其他用户的音色
+        if existing
+            .as_ref()
+            .is_some_and(|v| !can_access(v.user_id, command.user_id))
+        {
+            return Err(ApplicationError::Forbidden(format!(
+                "Voice belongs to another user: {}",
+                voice_id
+            )));
+        }
+
+        let stale_files: Vec<PathBuf> = existing
+            .iter()
+            .flat_map(|v| {
+                std::iter::once(v.reference_audio_path.clone()).chain(v.processed_audio_path.clone())
+            })
+            .filter(|p| *p != command.reference_audio_path)
+            .collect();
+
+        let voice = VoiceRecord {
+            id: voice_id,
+            name: command.name,
+            reference_audio_path: command.reference_audio_path,
+            processed_audio_path: None,
+            description: command.description,
+            prompt_text: normalize_optional_text(command.prompt_text),
+            engine: normalize_optional_text(command.engine),
+            tts_config: command.tts_config,
+            tags: command.tags,
+            // 覆盖时保留原所属用户（管理员覆盖共享音色后仍为共享音色）
+            user_id: existing.as_ref().map_or(command.user_id, |v| v.user_id),
+            created_at: command.created_at,
+        };
+
+        self.voice_repo.save(&voice).await?;
+
+        // 覆盖后参考音频和参数都可能变化，清除原音色的缓存（失败不影响导入结果）
+        if existing.is_some() {
+            if let Err(e) = self.audio_cache.remove_by_voice(voice_id).await {
+                tracing::warn!(voice_id = %voice_id, error = %e, "Failed to purge voice audio cache");
+            }
+        }
+
+        tracing::info!(
+            voice_id = %voice_id,
+            name = %voice.name,
+            replaced = existing.is_some(),
+            "Voice imported"
+        );
+
+        Ok(ImportVoiceResponse {
+            id: voice_id,
+            name: voice.name,
+            replaced: existing.is_some(),
+            stale_files,
         })
     }
 }
 
+// ============================================================================
+// NormalizeVoiceAudio
+// ============================================================================
+
+/// NormalizeVoiceAudio Handler
+///
+/// 将原始参考音频归一化后写入 `{原文件名}.normalized.wav`
+pub struct NormalizeVoiceAudioHandler {
+    voice_repo: Arc<dyn VoiceRepositoryPort>,
+    audio_transcoder: Arc<dyn AudioTranscoderPort>,
+    /// 为 None 时不做归一化
+    config: Option<ReferenceNormalizeConfig>,
+}
+
+impl NormalizeVoiceAudioHandler {
+    pub fn new(
+        voice_repo: Arc<dyn VoiceRepositoryPort>,
+        audio_transcoder: Arc<dyn AudioTranscoderPort>,
+        config: Option<ReferenceNormalizeConfig>,
+    ) -> Self {
+        Self {
+            voice_repo,
+            audio_transcoder,
+            config,
+        }
+    }
+
+    /// 返回生成的归一化文件路径；未启用时返回 None
+    pub async fn handle(
+        &self,
+        command: NormalizeVoiceAudio,
+    ) -> Result<Option<PathBuf>, ApplicationError> {
+        let Some(config) = &self.config else {
+            return Ok(None);
+        };
+
+        let voice_id = command.voice_id;
+        let mut voice = self
+            .voice_repo
+            .find_by_id(voice_id)
+            .await?
+            .ok_or_else(|| ApplicationError::not_found("Voice", voice_id))?;
+
+        let original = tokio::fs::read(&voice.reference_audio_path)
+            .await
+            .map_err(|e| ApplicationError::StorageError(format!("Failed to read reference audio: {}", e)))?;
+
+        let result = self
+            .audio_transcoder
+            .normalize_reference(&original, config)
+            .await
+            .map_err(|e| ApplicationError::validation(format!("Failed to normalize reference audio: {}", e)))?;
+
+        let processed_path = voice.reference_audio_path.with_extension("normalized.wav");
+        tokio::fs::write(&processed_path, &result.audio_data)
+            .await
+            .map_err(|e| ApplicationError::StorageError(format!("Failed to save normalized audio: {}", e)))?;
+
+        voice.processed_audio_path = Some(processed_path.clone());
+        self.voice_repo.save(&voice).await?;
+
+        tracing::info!(
+            voice_id = %voice_id,
+            duration_ms = result.duration_ms,
+            sample_rate = result.sample_rate,
+            "Voice reference audio normalized"
+        );
+
+        Ok(Some(processed_path))
+    }
+}
+
 // ============================================================================
 // DeleteVoice
 // ============================================================================
 
+/// 删除音色响应
+#[derive(Debug, Clone)]
+pub struct DeleteVoiceResponse {
+    /// 强制删除时被关闭的会话
+    pub closed_sessions: Vec<String>,
+}
+
 /// DeleteVoice Handler
+///
+/// 音色被活跃会话（当前音色或未完成的任务）引用时拒绝删除，
+/// 除非指定 force，此时先关闭这些会话
 pub struct DeleteVoiceHandler {
     voice_repo: Arc<dyn VoiceRepositoryPort>,
+    session_manager: Arc<dyn SessionManagerPort>,
+    task_manager: Arc<dyn TaskManagerPort>,
+    audio_cache: Arc<dyn AudioCachePort>,
+    event_publisher: Arc<EventPublisher>,
 }
 
 impl DeleteVoiceHandler {
-    pub fn new(voice_repo: Arc<dyn VoiceRepositoryPort>) -> Self {
-        Self { voice_repo }
+    pub fn new(
+        voice_repo: Arc<dyn VoiceRepositoryPort>,
+        session_manager: Arc<dyn SessionManagerPort>,
+        task_manager: Arc<dyn TaskManagerPort>,
+        audio_cache: Arc<dyn AudioCachePort>,
+        event_publisher: Arc<EventPublisher>,
+    ) -> Self {
+        Self {
+            voice_repo,
+            session_manager,
+            task_manager,
+            audio_cache,
+            event_publisher,
+        }
     }
 
-    pub async fn handle(&self, command: DeleteVoice) -> Result<(), ApplicationError> {
+    pub async fn handle(&self, command: DeleteVoice) -> Result<DeleteVoiceResponse, ApplicationError> {
         let voice_id = command.voice_id;
 
         // 检查音色是否存在
@@ -82,14 +435,338 @@ impl DeleteVoiceHandler {
             .await?
             .ok_or_else(|| ApplicationError::not_found("Voice", voice_id))?;
 
+        let blocking_sessions = self.sessions_using_voice(voice_id);
+        if !blocking_sessions.is_empty() && !command.force {
+            return Err(ApplicationError::InUse {
+                resource_type: "Voice",
+                id: voice_id,
+                session_ids: blocking_sessions,
+            });
+        }
+
+        for session_id in &blocking_sessions {
+            self.close_session(session_id);
+        }
+
         self.voice_repo.delete(voice_id).await?;
 
+        // 清除已缓存的音频（失败不影响删除结果）
+        let purged = match self.audio_cache.remove_by_voice(voice_id).await {
+            Ok(purged) => purged,
+            Err(e) => {
+                tracing::warn!(voice_id = %voice_id, error = %e, "Failed to purge voice audio cache");
+                0
+            }
+        };
+
         tracing::info!(
             voice_id = %voice_id,
             name = %voice.name,
+            closed_sessions = blocking_sessions.len(),
+            purged_cache_entries = purged,
             "Voice deleted"
         );
 
-        Ok(())
+        Ok(DeleteVoiceResponse {
+            closed_sessions: blocking_sessions,
+        })
+    }
+
+    /// 查找引用该音色的会话：当前音色为该音色，或仍有该音色的未完成任务
+    fn sessions_using_voice(&self, voice_id: Uuid) -> Vec<String> {
+        let mut session_ids: Vec<String> = self
+            .session_manager
+            .list_all()
+            .into_iter()
+            .filter(|session_id| {
+                let current = self
+                    .session_manager
+                    .get(session_id)
+                    .map(|s| s.voice_id == voice_id)
+                    .unwrap_or(false);
+                current
+                    || self
+                        .task_manager
+                        .get_tasks_by_session(session_id)
+                        .iter()
+                        .any(|t| {
+                            t.voice_id == voice_id
+                                && matches!(t.state, TaskState::Pending | TaskState::Inferring)
+                        })
+            })
+            .collect();
+        session_ids.sort();
+        session_ids
+    }
+
+    fn close_session(&self, session_id: &str) {
+        let cancelled = self.task_manager.cancel_all(session_id);
+        self.task_manager.cleanup_session(session_id);
+        self.audio_cache.unpin(session_id);
+        self.event_publisher.publish_session_closed(session_id, "voice_deleted");
+        if let Err(e) = self.session_manager.close(session_id) {
+            tracing::warn!(session_id = %session_id, "Failed to close session: {}", e);
+        }
+        self.event_publisher.unregister_session(session_id);
+
+        tracing::info!(
+            session_id = %session_id,
+            cancelled_tasks = cancelled,
+            "Session closed for voice deletion"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::{AudioFormat, CacheMetadata, InferenceTask, Session};
+    use crate::infrastructure::memory::TaskQueue;
+    use crate::infrastructure::persistence::sqlite::{
+        create_pool, run_migrations, DatabaseConfig, SqliteVoiceRepository,
+    };
+    use crate::infrastructure::persistence::SledAudioCache;
+    use crate::infrastructure::{InMemorySessionManager, InMemoryTaskManager};
+
+    struct Fixture {
+        voice_repo: Arc<SqliteVoiceRepository>,
+        audio_cache: Arc<SledAudioCache>,
+        voice_id: Uuid,
+        _dir: tempfile::TempDir,
+    }
+
+    /// 已保存一个音色、缓存中有一条该音色音频的测试环境
+    async fn fixture() -> Fixture {
+        let pool = create_pool(&DatabaseConfig::in_memory()).await.unwrap();
+        run_migrations(&pool).await.unwrap();
+        let voice_repo = Arc::new(SqliteVoiceRepository::new(pool));
+        let dir = tempfile::tempdir().unwrap();
+        let audio_cache = Arc::new(SledAudioCache::open(dir.path().join("cache"), 1024 * 1024).unwrap());
+
+        let voice_id = Uuid::new_v4();
+        voice_repo
+            .save(&VoiceRecord {
+                id: voice_id,
+                name: "旁白".to_string(),
+                reference_audio_path: PathBuf::from("voice.wav"),
+                processed_audio_path: None,
+                description: None,
+                prompt_text: None,
+                engine: None,
+                tts_config: TtsConfig::default(),
+                tags: VoiceTags::default(),
+                user_id: None,
+                created_at: Utc::now(),
+            })
+            .await
+            .unwrap();
+        audio_cache
+            .put(
+                "cached",
+                vec![1, 2, 3],
+                CacheMetadata {
+                    novel_id: Uuid::new_v4(),
+                    segment_index: 0,
+                    voice_id,
+                    content_hash: "hash".to_string(),
+                    duration_ms: 100,
+                    sample_rate: None,
+                    format: AudioFormat::Wav,
+                },
+            )
+            .await
+            .unwrap();
+
+        Fixture {
+            voice_repo,
+            audio_cache,
+            voice_id,
+            _dir: dir,
+        }
+    }
+
+    fn update(voice_id: Uuid) -> UpdateVoice {
+        UpdateVoice {
+            voice_id,
+            name: None,
+            description: None,
+            prompt_text: None,
+            engine: None,
+            reference_audio_path: None,
+            tts_config: None,
+            tags: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_tts_config_purges_voice_cache() {
+        let f = fixture().await;
+        let handler = UpdateVoiceHandler::new(f.voice_repo.clone(), f.audio_cache.clone());
+
+        // 只改名称：缓存的音频仍然有效
+        handler
+            .handle(UpdateVoice {
+                name: Some("新名称".to_string()),
+                ..update(f.voice_id)
+            })
+            .await
+            .unwrap();
+        assert!(f.audio_cache.exists("cached").await.unwrap());
+
+        let tts_config = TtsConfig {
+            speed: 1.5,
+            volume: 0.8,
+            ..TtsConfig::default()
+        };
+        handler
+            .handle(UpdateVoice {
+                tts_config: Some(tts_config.clone()),
+                ..update(f.voice_id)
+            })
+            .await
+            .unwrap();
+        assert!(!f.audio_cache.exists("cached").await.unwrap());
+        let saved = f.voice_repo.find_by_id(f.voice_id).await.unwrap().unwrap();
+        assert_eq!(saved.tts_config, tts_config);
+    }
+
+    #[tokio::test]
+    async fn test_replace_reference_audio_purges_voice_cache() {
+        let f = fixture().await;
+        let handler = UpdateVoiceHandler::new(f.voice_repo.clone(), f.audio_cache.clone());
+
+        let result = handler
+            .handle(UpdateVoice {
+                reference_audio_path: Some(PathBuf::from("voice.flac")),
+                ..update(f.voice_id)
+            })
+            .await
+            .unwrap();
+        assert!(result.audio_replaced);
+        assert_eq!(result.stale_files, vec![PathBuf::from("voice.wav")]);
+        assert!(!f.audio_cache.exists("cached").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_import_overwrite_purges_voice_cache() {
+        let f = fixture().await;
+        let handler = ImportVoiceHandler::new(f.voice_repo.clone(), f.audio_cache.clone());
+        let import = |overwrite| ImportVoice {
+            voice_id: f.voice_id,
+            name: "导入".to_string(),
+            description: None,
+            prompt_text: None,
+            engine: None,
+            reference_audio_path: PathBuf::from("voice.wav"),
+            tts_config: TtsConfig::default(),
+            tags: VoiceTags::default(),
+            created_at: Utc::now(),
+            overwrite,
+            user_id: None,
+        };
+
+        // 未指定覆盖：冲突，缓存保留
+        let err = handler.handle(import(false)).await.unwrap_err();
+        assert!(matches!(err, ApplicationError::Conflict(_)));
+        assert!(f.audio_cache.exists("cached").await.unwrap());
+
+        let result = handler.handle(import(true)).await.unwrap();
+        assert!(result.replaced);
+        assert!(result.stale_files.is_empty());
+        assert!(!f.audio_cache.exists("cached").await.unwrap());
+        let saved = f.voice_repo.find_by_id(f.voice_id).await.unwrap().unwrap();
+        assert_eq!(saved.name, "导入");
+    }
+
+    /// 删除音色的处理器，以及其使用的会话和任务管理器
+    fn delete_handler(f: &Fixture) -> (DeleteVoiceHandler, Arc<InMemorySessionManager>, Arc<InMemoryTaskManager>) {
+        let session_manager = Arc::new(InMemorySessionManager::new());
+        let task_manager = Arc::new(InMemoryTaskManager::new(Arc::new(TaskQueue::new(10))));
+        let handler = DeleteVoiceHandler::new(
+            f.voice_repo.clone(),
+            session_manager.clone(),
+            task_manager.clone(),
+            f.audio_cache.clone(),
+            Arc::new(EventPublisher::new()),
+        );
+        (handler, session_manager, task_manager)
+    }
+
+    #[tokio::test]
+    async fn test_delete_voice_in_use_blocked() {
+        let f = fixture().await;
+        let (handler, session_manager, task_manager) = delete_handler(&f);
+        let novel_id = Uuid::new_v4();
+        let playing = session_manager.create(Session::new(novel_id, f.voice_id, 0)).unwrap();
+        // 已切换到其他音色，但仍有该音色排队中的任务
+        let queued = session_manager.create(Session::new(novel_id, Uuid::new_v4(), 0)).unwrap();
+        task_manager
+            .submit(vec![InferenceTask::new(queued.clone(), novel_id, f.voice_id, 1, "内容".to_string())])
+            .unwrap();
+        let other = session_manager.create(Session::new(novel_id, Uuid::new_v4(), 0)).unwrap();
+
+        let err = handler
+            .handle(DeleteVoice {
+                voice_id: f.voice_id,
+                force: false,
+            })
+            .await
+            .unwrap_err();
+        let mut expected = vec![playing, queued];
+        expected.sort();
+        match err {
+            ApplicationError::InUse { session_ids, .. } => assert_eq!(session_ids, expected),
+            e => panic!("unexpected error: {}", e),
+        }
+
+        // 未删除任何内容
+        assert!(f.voice_repo.find_by_id(f.voice_id).await.unwrap().is_some());
+        assert!(f.audio_cache.exists("cached").await.unwrap());
+        assert_eq!(session_manager.list_all().len(), 3);
+        assert!(session_manager.get(&other).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_force_delete_voice_closes_sessions_and_purges_cache() {
+        let f = fixture().await;
+        let (handler, session_manager, task_manager) = delete_handler(&f);
+        let novel_id = Uuid::new_v4();
+        let playing = session_manager.create(Session::new(novel_id, f.voice_id, 0)).unwrap();
+        task_manager
+            .submit(vec![InferenceTask::new(playing.clone(), novel_id, f.voice_id, 0, "内容".to_string())])
+            .unwrap();
+        let other = session_manager.create(Session::new(novel_id, Uuid::new_v4(), 0)).unwrap();
+
+        let result = handler
+            .handle(DeleteVoice {
+                voice_id: f.voice_id,
+                force: true,
+            })
+            .await
+            .unwrap();
+        assert_eq!(result.closed_sessions, vec![playing.clone()]);
+
+        assert!(session_manager.get(&playing).is_err());
+        assert!(task_manager.get_tasks_by_session(&playing).is_empty());
+        assert!(session_manager.get(&other).is_ok());
+        assert!(f.voice_repo.find_by_id(f.voice_id).await.unwrap().is_none());
+        assert!(!f.audio_cache.exists("cached").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_delete_unused_voice_purges_cache() {
+        let f = fixture().await;
+        let (handler, _, _) = delete_handler(&f);
+
+        let result = handler
+            .handle(DeleteVoice {
+                voice_id: f.voice_id,
+                force: false,
+            })
+            .await
+            .unwrap();
+        assert!(result.closed_sessions.is_empty());
+        assert!(f.voice_repo.find_by_id(f.voice_id).await.unwrap().is_none());
+        assert!(!f.audio_cache.exists("cached").await.unwrap());
     }
 }
@@ -2,13 +2,22 @@
 //!
 //! 基于 ARCHITECTURE.md V2 设计
 
-use crate::application::ports::TaskState;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::application::pagination::PageRequest;
+use crate::application::ports::{TaskEventKind, TaskEventRecord, TaskPriority, TaskState};
+use crate::domain::voice::SynthesisParams;
 
 /// 提交推理任务命令
+///
+/// `segment_params` 按片段覆盖会话合成参数，覆盖会保存到会话中
 #[derive(Debug, Clone)]
 pub struct SubmitInferCommand {
     pub session_id: String,
     pub segment_indices: Vec<u32>,
+    pub segment_params: HashMap<u32, SynthesisParams>,
 }
 
 /// 任务信息
@@ -23,12 +32,16 @@ pub struct TaskInfo {
 #[derive(Debug, Clone)]
 pub struct SubmitInferResponse {
     pub tasks: Vec<TaskInfo>,
+    /// 提交后排队等待推理的任务数
+    pub queue_depth: usize,
 }
 
 /// 查询任务状态命令
 #[derive(Debug, Clone)]
 pub struct QueryTaskStatusCommand {
     pub task_ids: Vec<String>,
+    /// 当前用户（只返回该用户会话的任务）
+    pub user_id: Option<Uuid>,
 }
 
 /// 任务状态信息
@@ -45,3 +58,63 @@ pub struct TaskStatusInfo {
 pub struct QueryTaskStatusResponse {
     pub tasks: Vec<TaskStatusInfo>,
 }
+
+/// 列出任务命令（管理接口，如查看失败的任务及错误原因）
+#[derive(Debug, Clone)]
+pub struct ListTasksCommand {
+    pub state: Option<TaskState>,
+    pub session_id: Option<String>,
+    /// 分页（按创建时间倒序）
+    pub page: PageRequest,
+}
+
+/// 任务详情
+#[derive(Debug, Clone)]
+pub struct TaskDetail {
+    pub task_id: String,
+    pub session_id: String,
+    pub novel_id: Uuid,
+    pub voice_id: Uuid,
+    pub segment_index: u32,
+    pub state: TaskState,
+    pub priority: TaskPriority,
+    pub error: Option<String>,
+    /// 提交任务的 HTTP 请求 ID
+    pub request_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// 查询任务审计日志命令（条件均可选，同时指定时取交集）
+#[derive(Debug, Clone)]
+pub struct ListTaskEventsCommand {
+    pub session_id: Option<String>,
+    pub novel_id: Option<Uuid>,
+    pub segment_index: Option<u32>,
+    pub task_id: Option<String>,
+    pub event: Option<TaskEventKind>,
+    pub request_id: Option<String>,
+    /// 最多返回的条数（最新的在前）
+    pub limit: usize,
+}
+
+/// 查询任务审计日志响应
+#[derive(Debug, Clone)]
+pub struct ListTaskEventsResponse {
+    pub events: Vec<TaskEventRecord>,
+}
+
+/// 重试失败任务命令
+#[derive(Debug, Clone)]
+pub struct RetryTaskCommand {
+    pub task_id: String,
+}
+
+/// 重试失败任务响应
+#[derive(Debug, Clone)]
+pub struct RetryTaskResponse {
+    pub task_id: String,
+    pub session_id: String,
+    pub segment_index: u32,
+    pub state: TaskState,
+}
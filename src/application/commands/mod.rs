@@ -2,14 +2,26 @@
 //!
 //! CQRS 命令侧：处理所有写操作
 
+mod cache_commands;
+mod casting_commands;
+mod export_commands;
 mod infer_commands;
 mod novel_commands;
+mod pregeneration_commands;
 mod session_commands;
+mod settings_commands;
+mod user_commands;
 mod voice_commands;
 
 pub mod handlers;
 
+pub use cache_commands::*;
+pub use casting_commands::*;
+pub use export_commands::*;
 pub use infer_commands::*;
 pub use novel_commands::*;
+pub use pregeneration_commands::*;
 pub use session_commands::*;
+pub use settings_commands::*;
+pub use user_commands::*;
 pub use voice_commands::*;
@@ -14,7 +14,8 @@ pub struct CreateNovel {
 #[derive(Debug, Clone)]
 pub struct CreateNovelFromText {
     pub title: String,
-    pub text: String,
+    /// 所属用户（未启用账号时为 None）
+    pub user_id: Option<Uuid>,
 }
 
 /// 处理小说分段命令（第二步：异步分段处理）
@@ -0,0 +1,48 @@
+//! Pregeneration Commands - 整书预生成
+
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::domain::voice::SynthesisParams;
+
+/// 整书预生成命令（后台任务）
+///
+/// 以低优先级逐批合成小说所有未缓存的片段，完成后可脱离 TTS 服务离线收听
+#[derive(Debug, Clone)]
+pub struct PregenerateNovel {
+    pub novel_id: Uuid,
+    pub voice_id: Uuid,
+    /// 合成参数（与播放会话相同的参数才能命中缓存）
+    pub params: SynthesisParams,
+    /// 发起预生成的用户（任务归属该用户）
+    pub user_id: Option<Uuid>,
+}
+
+/// 预生成任务控制操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PregenerationAction {
+    /// 暂停（当前批次完成后不再提交）
+    Pause,
+    /// 恢复
+    Resume,
+    /// 取消（同时取消尚未开始的推理任务）
+    Cancel,
+}
+
+impl PregenerationAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PregenerationAction::Pause => "pause",
+            PregenerationAction::Resume => "resume",
+            PregenerationAction::Cancel => "cancel",
+        }
+    }
+}
+
+/// 控制预生成任务命令
+#[derive(Debug, Clone)]
+pub struct ControlPregeneration {
+    pub job_id: String,
+    pub action: PregenerationAction,
+}
@@ -4,12 +4,50 @@
 
 use uuid::Uuid;
 
+use super::infer_commands::TaskInfo;
+use crate::application::ports::{PlaybackHints, WindowConfig};
+use crate::application::readiness::{ReadinessSummary, SegmentStatus};
+use crate::domain::voice::SynthesisParams;
+
 /// 开始播放命令 - 创建或复用会话
+///
+/// `voice_id` 为 None 时使用设置中的默认音色
 #[derive(Debug, Clone)]
 pub struct PlayCommand {
     pub novel_id: Uuid,
-    pub voice_id: Uuid,
-    pub start_index: u32,
+    pub voice_id: Option<Uuid>,
+    /// 起始片段（新会话省略时从小说的阅读进度继续，没有进度为 0；复用会话省略则保持会话的进度）
+    pub start_index: Option<u32>,
+    /// 起始片段内的偏移（毫秒，仅在新会话指定了 start_index 时使用）
+    pub offset_ms: Option<u32>,
+    /// 复用同一小说、音色和合成参数的活动会话，而不是创建新会话
+    pub reuse: bool,
+    /// 会话合成参数（语速/情感/能量）
+    pub params: SynthesisParams,
+    /// 服务端变速播放速度（不影响合成和缓存，获取音频时伸缩）
+    pub playback_speed: Option<f32>,
+    /// 片段衔接提示（合并导出时使用）
+    pub playback_hints: PlaybackHints,
+    /// 预取窗口当前片段之前的片段数（None 使用配置默认值）
+    pub window_before: Option<usize>,
+    /// 预取窗口当前片段之后的片段数（None 使用配置默认值）
+    pub window_after: Option<usize>,
+    /// 自动前进模式（None 时新会话关闭，复用的会话保持原设置）
+    pub auto_advance: Option<bool>,
+    /// 发起请求的客户端（IP），用于按客户端限制会话数
+    pub client: Option<String>,
+    /// 当前用户（新会话归属该用户，只能播放该用户的小说和音色）
+    pub user_id: Option<Uuid>,
+}
+
+/// 从快照恢复会话命令 - 按快照中的播放状态创建新会话
+#[derive(Debug, Clone)]
+pub struct RestoreSessionCommand {
+    pub token: String,
+    /// 发起请求的客户端（IP），用于按客户端限制会话数
+    pub client: Option<String>,
+    /// 当前用户
+    pub user_id: Option<Uuid>,
 }
 
 /// 开始播放响应
@@ -19,6 +57,15 @@ pub struct PlayResponse {
     pub novel_id: Uuid,
     pub voice_id: Uuid,
     pub current_index: u32,
+    /// 当前片段内的播放偏移（复用会话时为已保存的进度）
+    pub offset_ms: u32,
+    pub window: WindowConfig,
+    /// 是否复用了已有会话
+    pub reused: bool,
+    /// 起始位置是否来自已保存的进度（未指定 start_index）
+    pub resumed: bool,
+    /// 是否开启自动前进模式
+    pub auto_advance: bool,
 }
 
 /// Seek 命令 - 跳转位置并取消 pending 任务
@@ -36,11 +83,103 @@ pub struct SeekResponse {
     pub cancelled_count: usize,
 }
 
+/// NextSegment 命令 - 前进到下一个片段
+#[derive(Debug, Clone)]
+pub struct NextSegmentCommand {
+    pub session_id: String,
+}
+
+/// PrevSegment 命令 - 回到上一个片段
+#[derive(Debug, Clone)]
+pub struct PrevSegmentCommand {
+    pub session_id: String,
+}
+
+/// NextSegment / PrevSegment 响应
+#[derive(Debug, Clone)]
+pub struct StepSegmentResponse {
+    pub session_id: String,
+    pub current_index: u32,
+    /// 已在第一个/最后一个片段时为 false（索引不变）
+    pub moved: bool,
+    pub total_segments: usize,
+    /// 当前片段文本
+    pub content: String,
+    pub char_count: usize,
+    /// 当前片段的音频状态
+    pub status: SegmentStatus,
+}
+
+/// 上报播放进度命令 - 记录位置并刷新会话活动时间（不取消任务）
+#[derive(Debug, Clone)]
+pub struct ReportPositionCommand {
+    pub session_id: String,
+    pub segment_index: u32,
+    /// 片段内偏移（毫秒）
+    pub offset_ms: u32,
+}
+
+/// 上报播放进度响应
+#[derive(Debug, Clone)]
+pub struct ReportPositionResponse {
+    pub session_id: String,
+    pub current_index: u32,
+    pub offset_ms: u32,
+}
+
+/// 心跳命令 - 保持会话活动（无法保持 WebSocket 连接的客户端定期调用）
+#[derive(Debug, Clone)]
+pub struct HeartbeatCommand {
+    pub session_id: String,
+}
+
+/// 心跳响应：当前位置和预取窗口的就绪概览
+#[derive(Debug, Clone)]
+pub struct HeartbeatResponse {
+    pub session_id: String,
+    pub current_index: u32,
+    pub offset_ms: u32,
+    pub total_segments: usize,
+    /// 预取窗口（闭区间，小说没有片段时为 None）
+    pub window: Option<(u32, u32)>,
+    pub readiness: ReadinessSummary,
+}
+
+/// 批量预取命令 - 显式提交一组片段的推理（如离线前预取下一章）
+///
+/// `segment_indices` 与 `range`（闭区间）合并后去重
+#[derive(Debug, Clone)]
+pub struct PrefetchSegmentsCommand {
+    pub session_id: String,
+    pub segment_indices: Vec<u32>,
+    pub range: Option<(u32, u32)>,
+}
+
+/// 更新预取窗口命令（未指定的一侧保持不变）
+#[derive(Debug, Clone)]
+pub struct UpdateWindowCommand {
+    pub session_id: String,
+    pub before: Option<usize>,
+    pub after: Option<usize>,
+}
+
+/// 更新预取窗口响应
+#[derive(Debug, Clone)]
+pub struct UpdateWindowResponse {
+    pub session_id: String,
+    pub window: WindowConfig,
+}
+
 /// 切换音色命令 - 取消所有任务
+///
+/// `params` 为 Some 时同时替换会话合成参数；`handoff` 为 true 时新音色的音频就绪前
+/// 继续返回旧音色已就绪的片段
 #[derive(Debug, Clone)]
 pub struct ChangeVoiceCommand {
     pub session_id: String,
     pub voice_id: Uuid,
+    pub params: Option<SynthesisParams>,
+    pub handoff: bool,
 }
 
 /// 切换音色响应
@@ -49,6 +188,29 @@ pub struct ChangeVoiceResponse {
     pub session_id: String,
     pub voice_id: Uuid,
     pub cancelled_count: usize,
+    /// 过渡中的旧音色（新音色的当前片段就绪后通过 VoiceHandoffCompleted 通知结束）
+    pub handoff_voice_id: Option<Uuid>,
+}
+
+/// 接管会话命令 - 从其他设备转移正在播放的会话
+#[derive(Debug, Clone)]
+pub struct ClaimSessionCommand {
+    pub session_id: String,
+}
+
+/// 接管会话响应：继续播放所需的会话状态
+#[derive(Debug, Clone)]
+pub struct ClaimSessionResponse {
+    pub session_id: String,
+    pub novel_id: Uuid,
+    pub voice_id: Uuid,
+    pub current_index: u32,
+    pub offset_ms: u32,
+    pub params: SynthesisParams,
+    pub playback_speed: Option<f32>,
+    pub window: WindowConfig,
+    /// 排队或推理中的任务（新设备继续等待这些任务，无需重新提交）
+    pub tasks: Vec<TaskInfo>,
 }
 
 /// 关闭会话命令
@@ -0,0 +1,14 @@
+//! Settings Commands - 设置相关命令
+
+use uuid::Uuid;
+
+/// 写入设置命令
+///
+/// `value` 为 None 时删除该设置
+#[derive(Debug, Clone)]
+pub struct UpdateSetting {
+    pub key: String,
+    pub value: Option<String>,
+    /// 当前用户（设置按用户区分，未启用账号时为 None）
+    pub user_id: Option<Uuid>,
+}
@@ -0,0 +1,27 @@
+//! User Commands - 用户账号命令
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// 注册用户命令
+#[derive(Debug, Clone)]
+pub struct RegisterUser {
+    pub username: String,
+    pub password: String,
+}
+
+/// 登录命令
+#[derive(Debug, Clone)]
+pub struct LoginUser {
+    pub username: String,
+    pub password: String,
+}
+
+/// 注册/登录结果
+#[derive(Debug, Clone)]
+pub struct AuthTokenResponse {
+    pub user_id: Uuid,
+    pub username: String,
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+}
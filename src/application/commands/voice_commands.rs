@@ -1,18 +1,76 @@
 //! Voice Commands - V2 架构
 
+use chrono::{DateTime, Utc};
 use std::path::PathBuf;
 use uuid::Uuid;
 
+use crate::domain::voice::{TtsConfig, VoiceTags};
+
 /// 创建音色命令
 #[derive(Debug, Clone)]
 pub struct CreateVoice {
     pub name: String,
     pub reference_audio_path: PathBuf,
     pub description: Option<String>,
+    pub prompt_text: Option<String>,
+    pub engine: Option<String>,
+    pub tts_config: TtsConfig,
+    pub tags: VoiceTags,
+    /// 所属用户（未启用账号时为 None）
+    pub user_id: Option<Uuid>,
+}
+
+/// 更新音色命令
+///
+/// 仅更新提供的字段；替换参考音频时旧文件（含归一化文件）由调用方清理
+#[derive(Debug, Clone)]
+pub struct UpdateVoice {
+    pub voice_id: Uuid,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    /// 参考音频文本，空字符串表示清除
+    pub prompt_text: Option<String>,
+    /// TTS 引擎名称，空字符串表示恢复默认引擎
+    pub engine: Option<String>,
+    pub reference_audio_path: Option<PathBuf>,
+    /// 整体替换 TTS 参数
+    pub tts_config: Option<TtsConfig>,
+    /// 整体替换分类标签
+    pub tags: Option<VoiceTags>,
+}
+
+/// 导入音色命令（来自音色包）
+///
+/// 保留原 voice_id 和创建时间，使缓存 key 跨实例保持有效
+#[derive(Debug, Clone)]
+pub struct ImportVoice {
+    pub voice_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub prompt_text: Option<String>,
+    pub engine: Option<String>,
+    pub reference_audio_path: PathBuf,
+    pub tts_config: TtsConfig,
+    pub tags: VoiceTags,
+    pub created_at: DateTime<Utc>,
+    /// 音色已存在时是否覆盖
+    pub overwrite: bool,
+    /// 所属用户（未启用账号时为 None）
+    pub user_id: Option<Uuid>,
+}
+
+/// 归一化音色参考音频命令
+///
+/// 生成处理后的参考音频并记录到音色上，原始文件保持不变
+#[derive(Debug, Clone)]
+pub struct NormalizeVoiceAudio {
+    pub voice_id: Uuid,
 }
 
 /// 删除音色命令
 #[derive(Debug, Clone)]
 pub struct DeleteVoice {
     pub voice_id: Uuid,
+    /// 音色被会话使用时，是否关闭这些会话后强制删除
+    pub force: bool,
 }
@@ -27,6 +27,38 @@ pub enum ApplicationError {
     #[error("Invalid state: {0}")]
     InvalidState(String),
 
+    /// 资源冲突（如 ID 已存在）
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    /// 资源正被会话使用
+    #[error("{resource_type} {id} is in use by sessions: {}", session_ids.join(", "))]
+    InUse {
+        resource_type: &'static str,
+        id: Uuid,
+        session_ids: Vec<String>,
+    },
+
+    /// 超出数量限制（如同时播放的会话数）
+    #[error("Limit exceeded: {scope} allows at most {limit} (currently {current})")]
+    LimitExceeded {
+        scope: &'static str,
+        limit: usize,
+        current: usize,
+    },
+
+    /// 未登录或凭据无效
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    /// 已登录但不允许执行该操作
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
+    /// 推理队列已满，稍后重试
+    #[error("Task queue is full: {depth}/{capacity}")]
+    QueueFull { depth: usize, capacity: usize },
+
     /// 仓储错误
     #[error("Repository error: {0}")]
     RepositoryError(String),
@@ -70,12 +102,29 @@ impl ApplicationError {
         Self::InvalidState(message.into())
     }
 
+    /// 创建资源冲突错误
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Self::Conflict(message.into())
+    }
+
     /// 创建内部错误
     pub fn internal(message: impl Into<String>) -> Self {
         Self::InternalError(message.into())
     }
 }
 
+impl From<crate::application::ports::TaskError> for ApplicationError {
+    fn from(err: crate::application::ports::TaskError) -> Self {
+        use crate::application::ports::TaskError;
+        match err {
+            TaskError::NotFound(id) => Self::not_found_str("Task", &id),
+            TaskError::AlreadyExists(id) => Self::Conflict(format!("Task already exists: {}", id)),
+            TaskError::InvalidStateTransition(msg) => Self::InvalidState(msg),
+            TaskError::QueueFull { depth, capacity } => Self::QueueFull { depth, capacity },
+        }
+    }
+}
+
 impl From<crate::application::ports::RepositoryError> for ApplicationError {
     fn from(err: crate::application::ports::RepositoryError) -> Self {
         Self::RepositoryError(err.to_string())
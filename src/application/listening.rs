@@ -0,0 +1,138 @@
+//! 收听统计
+//!
+//! 由客户端上报的播放进度累计：两次上报之间位置前进时计入经过的时间，
+//! 逐个进入后面的片段时计入听完的片段；间隔过长（暂停/断线）或大幅跳转不计入
+
+use chrono::Local;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use crate::application::ports::{ListeningStatsRecord, ListeningStatsRepositoryPort};
+
+/// 两次上报间隔超过该时长时不计入收听时长
+const MAX_REPORT_GAP: Duration = Duration::from_secs(120);
+
+/// 两次上报之间最多计入的片段数，超过视为跳转
+const MAX_SEGMENT_STEP: u32 = 5;
+
+/// 会话最近一次上报的位置
+#[derive(Debug, Clone, Copy)]
+struct LastReport {
+    user_id: Option<Uuid>,
+    novel_id: Uuid,
+    segment_index: u32,
+    offset_ms: u32,
+    at: Instant,
+}
+
+/// 收听统计记录器
+pub struct ListeningTracker {
+    stats_repo: Arc<dyn ListeningStatsRepositoryPort>,
+    last_reports: Mutex<HashMap<String, LastReport>>,
+}
+
+impl ListeningTracker {
+    pub fn new(stats_repo: Arc<dyn ListeningStatsRepositoryPort>) -> Self {
+        Self {
+            stats_repo,
+            last_reports: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 记录一次进度上报；写入失败只记录日志（统计不影响上报本身）
+    pub async fn record(
+        &self,
+        session_id: &str,
+        user_id: Option<Uuid>,
+        novel_id: Uuid,
+        segment_index: u32,
+        offset_ms: u32,
+    ) {
+        let report = LastReport {
+            user_id,
+            novel_id,
+            segment_index,
+            offset_ms,
+            at: Instant::now(),
+        };
+        let previous = {
+            let mut last_reports = self.last_reports.lock().unwrap();
+            // 顺便清理已停止上报的会话
+            last_reports.retain(|_, last| report.at.duration_since(last.at) <= MAX_REPORT_GAP);
+            last_reports.insert(session_id.to_string(), report)
+        };
+
+        let Some(record) = previous.and_then(|previous| listened(&previous, &report)) else {
+            return;
+        };
+        if let Err(e) = self.stats_repo.add(&record).await {
+            tracing::warn!(session_id = %session_id, error = %e, "Failed to record listening stats");
+        }
+    }
+}
+
+/// 两次上报之间的收听量；没有可计入的内容时返回 None
+fn listened(previous: &LastReport, report: &LastReport) -> Option<ListeningStatsRecord> {
+    // 切换了小说（会话被复用）或间隔过长时只作为新的起点
+    let elapsed = report.at.duration_since(previous.at);
+    if previous.novel_id != report.novel_id || elapsed > MAX_REPORT_GAP {
+        return None;
+    }
+
+    let advanced = report.segment_index > previous.segment_index
+        || (report.segment_index == previous.segment_index && report.offset_ms > previous.offset_ms);
+    if !advanced {
+        return None;
+    }
+    let step = report.segment_index - previous.segment_index;
+    if step > MAX_SEGMENT_STEP {
+        return None;
+    }
+
+    Some(ListeningStatsRecord {
+        user_id: report.user_id,
+        novel_id: report.novel_id,
+        day: Local::now().date_naive(),
+        segments: step,
+        listened_ms: elapsed.as_millis() as u64,
+        max_index: report.segment_index,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(novel_id: Uuid, segment_index: u32, offset_ms: u32, at: Instant) -> LastReport {
+        LastReport {
+            user_id: None,
+            novel_id,
+            segment_index,
+            offset_ms,
+            at,
+        }
+    }
+
+    #[test]
+    fn test_listened_between_reports() {
+        let novel_id = Uuid::new_v4();
+        let start = Instant::now();
+        let later = start + Duration::from_secs(10);
+
+        // 同一片段内前进：只计时长
+        let record = listened(&report(novel_id, 3, 1000, start), &report(novel_id, 3, 9000, later)).unwrap();
+        assert_eq!((record.segments, record.listened_ms, record.max_index), (0, 10_000, 3));
+
+        // 进入下一片段：计入一个片段
+        let record = listened(&report(novel_id, 3, 9000, start), &report(novel_id, 4, 500, later)).unwrap();
+        assert_eq!((record.segments, record.max_index), (1, 4));
+
+        // 后退、大幅跳转、间隔过长都不计入
+        assert!(listened(&report(novel_id, 4, 0, start), &report(novel_id, 3, 0, later)).is_none());
+        assert!(listened(&report(novel_id, 4, 0, start), &report(novel_id, 40, 0, later)).is_none());
+        let stale = start + MAX_REPORT_GAP + Duration::from_secs(1);
+        assert!(listened(&report(novel_id, 4, 0, start), &report(novel_id, 4, 5000, stale)).is_none());
+    }
+}
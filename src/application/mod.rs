@@ -4,67 +4,175 @@
 //! - ports: 六边形架构端口定义（TtsEngine、Repository、SessionManager、TaskManager 等）
 //! - commands: CQRS 命令及处理器
 //! - queries: CQRS 查询及处理器
+//! - access: 资源归属校验（按用户隔离小说、音色、会话）
+//! - auto_advance: 自动前进模式（下一片段就绪推送）
+//! - casting: 角色配音解析（多角色朗读）
+//! - listening: 收听统计（由进度上报累计）
+//! - pagination: 列表分页（offset / limit + 总数）
+//! - prefetch: 滑动窗口预取（按播放位置提交/取消推理任务）
+//! - readiness: 片段就绪状态（推理任务 + 音频缓存）
+//! - request_context: 请求上下文（请求 ID 随推理任务传递）
+//! - schedule: 预生成时段（时段外暂停整书预生成）
+//! - snapshot: 会话快照令牌（保存/分享播放状态）
+//! - tagging: 音频元数据标签解析
 //! - error: 应用层错误定义
 
+pub mod access;
+pub mod auto_advance;
+pub mod casting;
 pub mod commands;
 pub mod error;
+pub mod listening;
+pub mod pagination;
 pub mod ports;
+pub mod prefetch;
 pub mod queries;
+pub mod readiness;
+pub mod request_context;
+pub mod schedule;
+pub mod snapshot;
+pub mod tagging;
 
 // Re-exports
 pub use commands::{
+    // Cache commands
+    PurgeAudioCache,
+    ReconcileStorage,
     // Infer commands
+    ListTaskEventsCommand,
+    ListTaskEventsResponse,
+    ListTasksCommand,
     QueryTaskStatusCommand,
     QueryTaskStatusResponse,
+    RetryTaskCommand,
+    RetryTaskResponse,
     SubmitInferCommand,
     SubmitInferResponse,
+    TaskDetail,
     TaskInfo,
     TaskStatusInfo,
+    // Export commands
+    ExportAudiobook,
+    ExportChapterAudio,
+    ExportChapterAudioResponse,
     // Novel commands
     CreateNovel,
     CreateNovelFromText,
     DeleteNovel,
     ProcessNovelSegments,
+    // Pregeneration commands
+    ControlPregeneration,
+    PregenerateNovel,
+    PregenerationAction,
     // Session commands
     ChangeVoiceCommand,
     ChangeVoiceResponse,
+    ClaimSessionCommand,
+    ClaimSessionResponse,
     CloseSessionCommand,
     CloseSessionResponse,
+    HeartbeatCommand,
+    HeartbeatResponse,
     PlayCommand,
     PlayResponse,
+    PrefetchSegmentsCommand,
+    ReportPositionCommand,
+    ReportPositionResponse,
+    RestoreSessionCommand,
     SeekCommand,
     SeekResponse,
+    NextSegmentCommand,
+    PrevSegmentCommand,
+    StepSegmentResponse,
+    UpdateWindowCommand,
+    UpdateWindowResponse,
+    // Settings commands
+    UpdateSetting,
+    // User commands
+    AuthTokenResponse,
+    LoginUser,
+    RegisterUser,
+    // Voice casting commands
+    UpdateVoiceCasting,
     // Voice commands
     CreateVoice,
     DeleteVoice,
+    ImportVoice,
+    NormalizeVoiceAudio,
+    UpdateVoice,
     // Handlers
     handlers::{
-        ChangeVoiceHandler, CloseSessionHandler, CreateNovelFromTextHandler, CreateVoiceHandler,
-        DeleteNovelHandler, DeleteVoiceHandler, PlayHandler, ProcessNovelSegmentsHandler,
-        QueryTaskStatusHandler, SeekHandler, SubmitInferHandler,
+        ChangeVoiceHandler, ClaimSessionHandler, CloseSessionHandler, ControlPregenerationHandler,
+        CreateNovelFromTextHandler, CreateVoiceHandler, HeartbeatHandler,
+        DeleteNovelHandler, DeleteVoiceHandler, ExportAudiobookHandler, ExportChapterAudioHandler,
+        ImportVoiceHandler, ListTaskEventsHandler, LoginHandler, RegisterUserHandler, ListTasksHandler, NormalizeVoiceAudioHandler,
+        PlayHandler, PrefetchSegmentsHandler, PregenerateNovelHandler,
+        ProcessNovelSegmentsHandler, PurgeAudioCacheHandler, QueryTaskStatusHandler,
+        ReconcileStorageHandler, ReportPositionHandler, RetryTaskHandler, SeekHandler, SessionLimits, StepSegmentHandler, SubmitInferHandler, UpdateSettingHandler,
+        UpdateVoiceCastingHandler, UpdateVoiceHandler, UpdateWindowHandler,
     },
 };
 
+pub use access::{can_access, can_modify, AccessPolicy};
+pub use auto_advance::{segment_audio_url, AutoAdvanceNotifier};
+pub use casting::VoiceCastingResolver;
 pub use error::ApplicationError;
+pub use listening::ListeningTracker;
+pub use pagination::{Page, PageRequest, DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT};
+pub use prefetch::{
+    BatchPrefetchResult, IdleThrottleResult, PrefetchCoordinator, PrefetchResult, PrefetchSettings,
+};
+pub use readiness::{ReadinessSummary, SegmentReadiness, SegmentStatus, SegmentStatusResolver};
+pub use request_context::{current_request_id, with_request_id};
+pub use schedule::{PregenerationSchedule, TimeWindow};
+pub use snapshot::SessionSnapshot;
+pub use tagging::AudioTagResolver;
 
 pub use ports::{
     // Audio cache
     generate_cache_key,
+    generate_cache_key_with_params,
     AudioCachePort,
     CacheEntry,
     CacheError,
     CacheMetadata,
     CacheStats,
+    NovelCacheUsage,
+    VoiceKey,
+    // Partial audio
+    PartialAudioPort,
+    PartialAudioStream,
     // Repositories
     AudioSegmentRecord,
     AudioSegmentRepositoryPort,
+    ListeningStatsRecord,
+    ListeningStatsRepositoryPort,
     NovelRecord,
     NovelRepositoryPort,
     NovelStatus,
+    ReadingProgressRecord,
+    ReadingProgressRepositoryPort,
     RepositoryError,
+    SettingsRepositoryPort,
+    TaskEventFilter,
+    TaskEventKind,
+    TaskEventRecord,
+    TaskEventRepositoryPort,
     TextSegmentRecord,
+    TtsEngineStatusRecord,
+    TtsEngineStatusRepositoryPort,
+    VoiceCastingRecord,
+    VoiceCastingRepositoryPort,
     VoiceRecord,
     VoiceRepositoryPort,
+    UserRecord,
+    UserRepositoryPort,
+    SETTING_DEFAULT_VOICE_ID,
+    // Auth
+    AuthError,
+    AuthTokenPort,
+    IssuedToken,
+    PasswordHasherPort,
     // Session manager
     Session,
     SessionError,
@@ -79,23 +187,50 @@ pub use ports::{
     SegmentedText,
     TextSegmenterPort,
     // TTS engine
+    AudioChunkStream,
     InferRequest,
     InferResponse,
+    InferStream,
     TtsEnginePort,
     TtsError,
 };
 
 pub use queries::{
     // Audio queries
+    AudioData,
+    AudioPeaksResponse,
+    GetAudioPeaks,
     GetAudioQuery,
     GetAudioResponse,
+    // Cache queries
+    GetCacheStats,
+    // Export queries
+    GetExportJob,
     // Novel queries
     GetNovel,
+    GetNovelChapters,
     GetNovelSegments,
     ListNovels,
+    PreviewSegments,
+    // Pregeneration queries
+    GetPregenerationJob,
+    // Listening stats queries
+    GetListeningStats,
+    // Session queries
+    GetSessionSegments,
+    GetSessionSnapshot,
+    ListActiveSessions,
+    ListSessions,
+    // Settings queries
+    GetSettings,
+    // TTS engine queries
+    GetTtsEngineStatus,
+    // Voice casting queries
+    GetVoiceCasting,
     // Voice queries
     GetVoice,
+    ListVoiceTags,
     ListVoices,
     // Handlers
-    handlers::{GetAudioHandler, GetNovelHandler, GetNovelSegmentsHandler, GetVoiceHandler, ListNovelsHandler, ListVoicesHandler},
+    handlers::{CacheStatsView, ChapterResponse, DetectedCharacter, GetAudioHandler, GetAudioPeaksHandler, GetCacheStatsHandler, GetExportJobHandler, GetListeningStatsHandler, ListeningStatsView, NovelCacheUsageView, GetNovelChaptersHandler, GetNovelHandler, GetNovelSegmentsHandler, GetPregenerationJobHandler, GetSessionSegmentsHandler, GetSessionSnapshotHandler, GetSettingsHandler, GetTtsEngineStatusHandler, GetVoiceCastingHandler, GetVoiceHandler, ListActiveSessionsHandler, ListNovelsHandler, ListSessionsHandler, ListVoiceTagsHandler, ListVoicesHandler, PreviewSegmentsHandler, SessionSegmentsView, SessionView, TtsEngineStatusView, VoiceCastingItem, VoiceCastingView},
 };
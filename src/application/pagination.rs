@@ -0,0 +1,118 @@
+//! 分页
+//!
+//! 列表查询（小说、片段、会话、任务）统一按 offset / limit 分页，返回本页条目和符合条件的总数，
+//! 客户端据此渲染翻页控件，无需取回全部数据
+
+use crate::application::error::ApplicationError;
+
+/// 默认每页条数
+pub const DEFAULT_PAGE_LIMIT: usize = 100;
+/// 每页最多条数
+pub const MAX_PAGE_LIMIT: usize = 1000;
+
+/// 分页参数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageRequest {
+    pub offset: usize,
+    pub limit: usize,
+}
+
+impl Default for PageRequest {
+    fn default() -> Self {
+        Self {
+            offset: 0,
+            limit: DEFAULT_PAGE_LIMIT,
+        }
+    }
+}
+
+impl PageRequest {
+    pub fn new(offset: usize, limit: usize) -> Self {
+        Self { offset, limit }
+    }
+
+    /// 校验每页条数
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        if self.limit == 0 || self.limit > MAX_PAGE_LIMIT {
+            return Err(ApplicationError::validation(format!(
+                "limit must be between 1 and {}",
+                MAX_PAGE_LIMIT
+            )));
+        }
+        Ok(())
+    }
+
+    /// 对内存中的完整列表分页（会话、任务等不在数据库中的列表）
+    pub fn paginate<T>(&self, items: Vec<T>) -> Page<T> {
+        let total = items.len();
+        let items = items.into_iter().skip(self.offset).take(self.limit).collect();
+        Page::new(items, total, *self)
+    }
+}
+
+/// 一页结果
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// 符合条件的总数
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+impl<T> Page<T> {
+    pub fn new(items: Vec<T>, total: usize, request: PageRequest) -> Self {
+        Self {
+            items,
+            total,
+            offset: request.offset,
+            limit: request.limit,
+        }
+    }
+
+    /// 下一页的 offset（已是最后一页时为 None）
+    pub fn next_offset(&self) -> Option<usize> {
+        let next = self.offset + self.items.len();
+        (!self.items.is_empty() && next < self.total).then_some(next)
+    }
+
+    /// 转换条目类型
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            total: self.total,
+            offset: self.offset,
+            limit: self.limit,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paginate() {
+        let page = PageRequest::new(0, 2).paginate(vec![1, 2, 3, 4, 5]);
+        assert_eq!(page.items, [1, 2]);
+        assert_eq!(page.total, 5);
+        assert_eq!(page.next_offset(), Some(2));
+
+        let page = PageRequest::new(4, 2).paginate(vec![1, 2, 3, 4, 5]);
+        assert_eq!(page.items, [5]);
+        assert_eq!(page.next_offset(), None);
+
+        // 超出范围时为空页
+        let page = PageRequest::new(10, 2).paginate(vec![1, 2, 3]);
+        assert!(page.items.is_empty());
+        assert_eq!(page.total, 3);
+        assert_eq!(page.next_offset(), None);
+    }
+
+    #[test]
+    fn test_validate_limit() {
+        assert!(PageRequest::default().validate().is_ok());
+        assert!(PageRequest::new(0, 0).validate().is_err());
+        assert!(PageRequest::new(0, MAX_PAGE_LIMIT + 1).validate().is_err());
+    }
+}
@@ -3,9 +3,14 @@
 //! 定义音频缓存的抽象接口，具体实现使用 Sled (LRU 缓存)
 
 use async_trait::async_trait;
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use uuid::Uuid;
 
+use super::audio_transcoder::AudioFormat;
+use crate::domain::voice::{SynthesisParams, TtsConfig};
+
 /// Audio Cache 错误
 #[derive(Debug, Error)]
 pub enum CacheError {
@@ -23,6 +28,9 @@ pub enum CacheError {
 
     #[error("Database error: {0}")]
     DatabaseError(String),
+
+    #[error("Encryption error: {0}")]
+    EncryptionError(String),
 }
 
 /// 缓存元数据
@@ -34,23 +42,41 @@ pub struct CacheMetadata {
     pub content_hash: String,
     pub duration_ms: u64,
     pub sample_rate: Option<u32>,
+    /// 实际存储的音频格式（转码失败或格式不支持时为 WAV）
+    pub format: AudioFormat,
 }
 
 /// 缓存条目
 #[derive(Debug, Clone)]
 pub struct CacheEntry {
-    pub audio_data: Vec<u8>,
+    /// 音频数据（引用计数，克隆和切片不复制）
+    pub audio_data: Bytes,
     pub metadata: CacheMetadata,
+    /// 实际占用的存储大小（启用压缩时为压缩后大小）
     pub size_bytes: u64,
     pub last_accessed: i64,
     pub created_at: i64,
 }
 
+/// 引用缓存条目的片段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SegmentReference {
+    pub novel_id: Uuid,
+    pub segment_index: u32,
+    pub voice_id: Uuid,
+}
+
+/// 波形峰值点数（供播放器绘制波形/进度条）
+pub const WAVEFORM_POINTS: usize = 200;
+
 /// Audio Cache Port
 ///
 /// 基于 content hash + voice_id 的 LRU 缓存
-/// - 缓存 key: md5(segment_content) + voice_id
-/// - 支持通过 novel_id + segment_index + voice_id 查找
+/// - 缓存 key: md5(segment_content) + voice_id + 音色配置指纹（+ 合成参数），内容相同的片段共享同一条目
+/// - 每个片段（novel_id + segment_index + voice_id）记录对条目的引用，支持按片段查找
+/// - 按小说删除时只删除不再被其他片段引用的条目（重新上传的同一部小说、常见短句不重复占用空间）
+/// - 活跃会话窗口内的条目被固定，缓存已满时不淘汰（暂时超出容量）
+/// - 配置了小说配额时，小说写入超出配额先淘汰该小说自己的条目
 #[async_trait]
 pub trait AudioCachePort: Send + Sync {
     /// 存储音频数据
@@ -66,7 +92,10 @@ pub trait AudioCachePort: Send + Sync {
     /// 根据缓存 key 获取音频数据
     ///
     /// 同时更新 last_accessed 时间戳（LRU touch）
-    async fn get(&self, cache_key: &str) -> Result<Option<Vec<u8>>, CacheError>;
+    async fn get(&self, cache_key: &str) -> Result<Option<Bytes>, CacheError>;
+
+    /// 根据缓存 key 获取音频数据及元数据（同样更新 last_accessed）
+    async fn get_entry(&self, cache_key: &str) -> Result<Option<CacheEntry>, CacheError>;
 
     /// 根据 novel_id + segment_index + voice_id 查找缓存 key
     async fn lookup(
@@ -79,14 +108,67 @@ pub trait AudioCachePort: Send + Sync {
     /// 检查缓存是否存在
     async fn exists(&self, cache_key: &str) -> Result<bool, CacheError>;
 
+    /// 缓存存在时记录片段对它的引用并返回 true（缓存命中时调用，替代 `exists`）
+    async fn link(
+        &self,
+        cache_key: &str,
+        novel_id: Uuid,
+        segment_index: u32,
+        voice_id: Uuid,
+    ) -> Result<bool, CacheError>;
+
+    /// 存储音频的波形峰值（随缓存条目一同删除/淘汰）
+    async fn put_peaks(&self, cache_key: &str, peaks: &[f32]) -> Result<(), CacheError>;
+
+    /// 获取波形峰值（未计算过时返回 None）
+    async fn get_peaks(&self, cache_key: &str) -> Result<Option<Vec<f32>>, CacheError>;
+
     /// 删除缓存条目
     async fn remove(&self, cache_key: &str) -> Result<(), CacheError>;
 
+    /// 删除超过保留时长未访问的条目，返回删除数（未配置保留时长时为 0）
+    async fn remove_expired(&self) -> Result<usize, CacheError>;
+
+    /// 解除某部小说所有片段的引用，删除不再被引用的条目，返回删除数
+    async fn remove_by_novel(&self, novel_id: Uuid) -> Result<usize, CacheError>;
+
+    /// 删除某个音色的所有缓存条目，返回删除数
+    async fn remove_by_voice(&self, voice_id: Uuid) -> Result<usize, CacheError>;
+
+    /// 固定会话滑动窗口内的条目（替换该会话之前固定的条目），固定的条目不会被 LRU 淘汰；
+    /// 尚未写入的 key 写入后同样生效
+    fn pin(&self, session_id: &str, cache_keys: Vec<String>);
+
+    /// 解除会话固定的条目（会话关闭时调用）
+    fn unpin(&self, session_id: &str);
+
+    /// 删除孤立数据（指向不存在条目的映射、引用和波形峰值，没有索引的音频文件等），
+    /// 返回删除项数；用于清理写入中途崩溃留下的残留
+    async fn remove_orphans(&self) -> Result<usize, CacheError>;
+
+    /// 删除 key 版本低于 [`CACHE_KEY_VERSION`] 的条目，返回删除数
+    async fn remove_outdated(&self) -> Result<usize, CacheError>;
+
+    /// 将缓冲中的写入落盘（定期及关闭服务时调用，避免非正常退出丢失最近写入的条目）
+    async fn flush(&self) -> Result<(), CacheError>;
+
     /// 获取缓存统计信息
     async fn stats(&self) -> CacheStats;
+
+    /// 按小说统计缓存占用（按占用大小从大到小排列）
+    async fn usage_by_novel(&self) -> Result<Vec<NovelCacheUsage>, CacheError>;
+
+    /// 列出所有缓存 key（供导出）
+    async fn keys(&self) -> Result<Vec<String>, CacheError>;
+
+    /// 读取条目但不更新访问时间和命中统计（供导出）
+    async fn peek(&self, cache_key: &str) -> Result<Option<CacheEntry>, CacheError>;
+
+    /// 引用该条目的所有片段
+    async fn references(&self, cache_key: &str) -> Result<Vec<SegmentReference>, CacheError>;
 }
 
-/// 缓存统计信息
+/// 缓存统计信息（内存/文件缓存的计数自进程启动起累计，sled 缓存跨重启累计）
 #[derive(Debug, Clone, Default)]
 pub struct CacheStats {
     pub total_entries: usize,
@@ -94,13 +176,113 @@ pub struct CacheStats {
     pub max_size_bytes: u64,
     pub hit_count: u64,
     pub miss_count: u64,
+    /// 因容量不足淘汰的条目数
+    pub eviction_count: u64,
+    /// 因超过保留时长删除的条目数
+    pub expired_count: u64,
+    /// 数据文件实际占用的磁盘空间（仅 sled，含日志和尚未回收的旧版本数据）
+    pub disk_size_bytes: Option<u64>,
+}
+
+/// 单部小说的缓存占用（与其他小说共享的条目分别计入）
+#[derive(Debug, Clone)]
+pub struct NovelCacheUsage {
+    pub novel_id: Uuid,
+    pub entries: usize,
+    pub size_bytes: u64,
+}
+
+/// 缓存 key 版本
+///
+/// 分段规则或合成流程变化、同一片段文本不再对应原有音频时递增，旧版本条目不再命中，
+/// 可通过 `remove_outdated` 清除。版本 1 为不带版本后缀的旧格式，
+/// 版本 2 不含音色的合成配置
+pub const CACHE_KEY_VERSION: u32 = 3;
+
+/// 缓存 key 中的音色部分
+///
+/// 除音色 ID 外包含音色合成配置（引擎和 TTS 参数）的指纹，
+/// 修改音色配置后按旧配置合成的音频不再命中
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VoiceKey {
+    pub voice_id: Uuid,
+    /// md5(引擎 + TTS 参数)
+    pub profile: String,
+}
+
+impl VoiceKey {
+    pub fn new(voice_id: Uuid, engine: Option<&str>, tts_config: &TtsConfig) -> Self {
+        let encoded = serde_json::to_string(&(engine, tts_config)).unwrap_or_default();
+        Self {
+            voice_id,
+            profile: format!("{:x}", md5::compute(encoded.as_bytes())),
+        }
+    }
 }
 
 /// 生成缓存 key
 ///
-/// 使用 md5(segment_content) + voice_id 作为缓存 key
-pub fn generate_cache_key(segment_content: &str, voice_id: &Uuid) -> String {
+/// 使用 md5(segment_content) + voice_id + 音色配置指纹 + 版本作为缓存 key
+pub fn generate_cache_key(segment_content: &str, voice: &VoiceKey) -> String {
+    format!("{}:v{}", content_key(segment_content, voice), CACHE_KEY_VERSION)
+}
+
+fn content_key(segment_content: &str, voice: &VoiceKey) -> String {
     let digest = md5::compute(segment_content.as_bytes());
-    let content_hash = format!("{:x}", digest);
-    format!("{}:{}", content_hash, voice_id)
+    format!("{:x}:{}:{}", digest, voice.voice_id, voice.profile)
+}
+
+/// 解析缓存 key 的版本（末段 `v{N}`，没有时为旧格式版本 1）
+pub fn cache_key_version(cache_key: &str) -> u32 {
+    cache_key
+        .rsplit_once(':')
+        .and_then(|(_, last)| last.strip_prefix('v'))
+        .and_then(|version| version.parse().ok())
+        .unwrap_or(1)
+}
+
+/// 生成带合成参数的缓存 key
+///
+/// 默认参数与 [`generate_cache_key`] 相同；
+/// 否则在版本前追加参数的 md5，不同语速/情感的音频分别缓存
+pub fn generate_cache_key_with_params(
+    segment_content: &str,
+    voice: &VoiceKey,
+    params: &SynthesisParams,
+) -> String {
+    if params.is_default() {
+        return generate_cache_key(segment_content, voice);
+    }
+    let encoded = serde_json::to_string(params).unwrap_or_default();
+    format!(
+        "{}:{:x}:v{}",
+        content_key(segment_content, voice),
+        md5::compute(encoded.as_bytes()),
+        CACHE_KEY_VERSION
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_key_includes_voice_profile() {
+        let voice_id = Uuid::new_v4();
+        let config = TtsConfig::default();
+        let key = |voice: &VoiceKey| generate_cache_key_with_params("你好。", voice, &SynthesisParams::default());
+
+        let base = key(&VoiceKey::new(voice_id, None, &config));
+        assert_eq!(base, key(&VoiceKey::new(voice_id, None, &config)));
+        assert_eq!(cache_key_version(&base), CACHE_KEY_VERSION);
+
+        // 切换引擎或修改 TTS 参数后不再命中旧条目
+        assert_ne!(base, key(&VoiceKey::new(voice_id, Some("local"), &config)));
+        let faster = TtsConfig {
+            speed: 1.5,
+            ..config.clone()
+        };
+        assert_ne!(base, key(&VoiceKey::new(voice_id, None, &faster)));
+        assert_ne!(base, key(&VoiceKey::new(Uuid::new_v4(), None, &config)));
+    }
 }
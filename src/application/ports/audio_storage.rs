@@ -55,6 +55,14 @@ pub struct StorageStats {
     pub session_count: u64,
 }
 
+/// 存储中的会话音频文件
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StoredAudioFile {
+    pub session_id: Uuid,
+    pub segment_index: usize,
+    pub size_bytes: u64,
+}
+
 /// GC 结果
 #[derive(Debug, Clone, Default)]
 pub struct GcResult {
@@ -105,6 +113,9 @@ pub trait AudioStoragePort: Send + Sync {
     /// 检查音频是否存在
     async fn audio_exists(&self, session_id: Uuid, segment_index: usize) -> bool;
 
+    /// 列出所有会话音频文件（用于与数据库记录核对）
+    async fn list_audio(&self) -> Result<Vec<StoredAudioFile>, AudioStorageError>;
+
     /// 获取存储统计
     async fn get_stats(&self) -> Result<StorageStats, AudioStorageError>;
 
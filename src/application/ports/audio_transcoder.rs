@@ -4,6 +4,7 @@
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
 use thiserror::Error;
 
 /// 转码错误
@@ -34,6 +35,8 @@ pub enum AudioFormat {
     Wav,
     /// Opus 格式 - 推荐用于 WebRTC/实时通话
     Opus,
+    /// Opus 编码，WebM 容器 - 部分浏览器和 Android 播放器兼容性更好
+    Webm,
     /// MP3 格式 - 通用兼容
     Mp3,
 }
@@ -43,11 +46,35 @@ impl std::fmt::Display for AudioFormat {
         match self {
             AudioFormat::Wav => write!(f, "wav"),
             AudioFormat::Opus => write!(f, "opus"),
+            AudioFormat::Webm => write!(f, "webm"),
             AudioFormat::Mp3 => write!(f, "mp3"),
         }
     }
 }
 
+impl AudioFormat {
+    /// HTTP Content-Type（Opus 使用 OGG 容器）
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            AudioFormat::Wav => "audio/wav",
+            AudioFormat::Opus => "audio/ogg",
+            AudioFormat::Webm => "audio/webm",
+            AudioFormat::Mp3 => "audio/mpeg",
+        }
+    }
+
+    /// 从 MIME 类型解析（用于 Accept 协商）
+    pub fn from_mime(mime: &str) -> Option<Self> {
+        match mime.trim().to_ascii_lowercase().as_str() {
+            "audio/wav" | "audio/wave" | "audio/x-wav" => Some(AudioFormat::Wav),
+            "audio/ogg" | "audio/opus" => Some(AudioFormat::Opus),
+            "audio/webm" => Some(AudioFormat::Webm),
+            "audio/mpeg" | "audio/mp3" => Some(AudioFormat::Mp3),
+            _ => None,
+        }
+    }
+}
+
 impl std::str::FromStr for AudioFormat {
     type Err = TranscodeError;
 
@@ -55,12 +82,26 @@ impl std::str::FromStr for AudioFormat {
         match s.to_lowercase().as_str() {
             "wav" => Ok(AudioFormat::Wav),
             "opus" => Ok(AudioFormat::Opus),
+            "webm" => Ok(AudioFormat::Webm),
             "mp3" => Ok(AudioFormat::Mp3),
             _ => Err(TranscodeError::UnsupportedFormat(s.to_string())),
         }
     }
 }
 
+/// 重采样质量
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ResampleQuality {
+    /// 线性插值：最快，高频有混叠，齿音失真明显
+    Linear,
+    /// Windowed-sinc（16 个过零点）
+    #[default]
+    Medium,
+    /// Windowed-sinc（32 个过零点），过渡带更窄
+    High,
+}
+
 /// 转码配置
 #[derive(Debug, Clone)]
 pub struct TranscodeConfig {
@@ -75,8 +116,14 @@ pub struct TranscodeConfig {
     /// 声道数
     /// 如果为 None，则保持原始声道数
     pub channels: Option<u8>,
+    /// 播放速度（保持音高的时间伸缩，见 PLAYBACK_SPEED_RANGE）
+    /// 如果为 None，则保持原速
+    pub tempo: Option<f32>,
 }
 
+/// 服务端变速播放允许的速度范围
+pub const PLAYBACK_SPEED_RANGE: RangeInclusive<f32> = 0.5..=2.0;
+
 impl Default for TranscodeConfig {
     fn default() -> Self {
         Self {
@@ -84,10 +131,67 @@ impl Default for TranscodeConfig {
             bitrate: Some(32000), // 32kbps，语音足够
             sample_rate: None,    // 保持原始
             channels: Some(1),    // 单声道
+            tempo: None,          // 原速
         }
     }
 }
 
+/// 参考音频归一化配置
+#[derive(Debug, Clone)]
+pub struct ReferenceNormalizeConfig {
+    /// 目标采样率（Hz），通常为 TTS 引擎偏好的采样率
+    pub sample_rate: u32,
+    /// 静音判定阈值（dBFS），低于该电平的首尾片段会被裁剪
+    pub silence_threshold_db: f32,
+    /// 目标响度（RMS dBFS）
+    pub target_loudness_db: f32,
+}
+
+impl Default for ReferenceNormalizeConfig {
+    fn default() -> Self {
+        Self {
+            sample_rate: 24000,
+            silence_threshold_db: -45.0,
+            target_loudness_db: -20.0,
+        }
+    }
+}
+
+/// 章节标记
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChapterMark {
+    /// 章节起始时间（毫秒）
+    pub start_ms: u64,
+    pub title: String,
+}
+
+/// 嵌入输出文件的元数据标签（Opus 使用 Vorbis comment，WebM 使用 Matroska Tags/Chapters；MP3 编码尚未实现）
+#[derive(Debug, Clone, Default)]
+pub struct AudioMetadata {
+    pub title: Option<String>,
+    pub album: Option<String>,
+    pub artist: Option<String>,
+    /// 音轨序号（单个片段输出时为片段序号，从 1 开始）
+    pub track_number: Option<u32>,
+    /// 章节标记（CHAPTERxxx / CHAPTERxxxNAME）
+    pub chapters: Vec<ChapterMark>,
+}
+
+/// 音频拼接配置
+#[derive(Debug, Clone)]
+pub struct ConcatConfig {
+    /// 裁剪每段首尾静音的阈值（dBFS），None 表示不裁剪
+    pub trim_silence_db: Option<f32>,
+    /// 相邻片段之间插入的静音（毫秒），0 表示不插入
+    pub gap_ms: u32,
+    /// 相邻片段的交叉淡化时长（毫秒），0 表示直接拼接；与静音同时设置时下一段在静音末尾淡入
+    pub crossfade_ms: u32,
+    /// 输出格式、采样率、声道数（采样率/声道数为 None 时取第一段的值）
+    pub output: TranscodeConfig,
+    /// 元数据标签
+    pub metadata: AudioMetadata,
+}
+
 /// 转码结果
 #[derive(Debug, Clone)]
 pub struct TranscodeResult {
@@ -107,6 +211,39 @@ pub struct TranscodeResult {
     pub transcoded_size: usize,
 }
 
+/// 流式编码的结束部分
+#[derive(Debug, Clone)]
+pub struct StreamEnd {
+    /// 剩余的输出数据
+    pub data: Vec<u8>,
+    /// 需要回写到输出开头的头部（WAV 头部的长度字段在结束时才确定）；
+    /// 无法回写的输出（如 chunked 响应）可忽略，此时头部长度为占位的最大值
+    pub header: Option<Vec<u8>>,
+    /// 输出格式
+    pub format: AudioFormat,
+    /// 时长（毫秒）
+    pub duration_ms: u64,
+    /// 采样率
+    pub sample_rate: u32,
+    /// 声道数
+    pub channels: u8,
+}
+
+/// 流式拼接编码器
+///
+/// 逐段追加音频并立即返回已编码的输出，内存占用与单段音频相当，
+/// 输出可以直接写入文件或 chunked 响应
+pub trait AudioStreamEncoder: Send {
+    /// 追加一段音频，返回新产生的输出（交叉淡化时末尾一小段延后输出）
+    fn push(&mut self, clip: &[u8]) -> Result<Vec<u8>, TranscodeError>;
+
+    /// 已追加音频的时长（毫秒）
+    fn duration_ms(&self) -> u64;
+
+    /// 结束编码；全部为静音时返回 InvalidInput
+    fn finish(self: Box<Self>) -> Result<StreamEnd, TranscodeError>;
+}
+
 /// Audio Transcoder Port
 ///
 /// 音频转码的抽象接口
@@ -124,8 +261,47 @@ pub trait AudioTranscoderPort: Send + Sync {
         &self,
         wav_data: &[u8],
         config: &TranscodeConfig,
+    ) -> Result<TranscodeResult, TranscodeError> {
+        self.transcode_with_metadata(wav_data, config, &AudioMetadata::default())
+            .await
+    }
+
+    /// 转码音频并在输出中写入元数据标签（输出为 WAV 时忽略标签）
+    async fn transcode_with_metadata(
+        &self,
+        wav_data: &[u8],
+        config: &TranscodeConfig,
+        metadata: &AudioMetadata,
+    ) -> Result<TranscodeResult, TranscodeError>;
+
+    /// 归一化参考音频
+    ///
+    /// 裁剪首尾静音、下混为单声道、重采样并做响度归一化，输出 16 位 WAV。
+    /// 输入可以是任意可解码的格式（WAV/FLAC/OGG/MP3）。
+    async fn normalize_reference(
+        &self,
+        audio_data: &[u8],
+        config: &ReferenceNormalizeConfig,
     ) -> Result<TranscodeResult, TranscodeError>;
 
+    /// 拼接多段音频为一个连续文件
+    ///
+    /// 各段统一到相同的采样率和声道数，按配置裁剪首尾静音、交叉淡化后编码。
+    /// 只含静音的片段被跳过；全部为静音时返回 InvalidInput。
+    async fn concat(
+        &self,
+        clips: &[Vec<u8>],
+        config: &ConcatConfig,
+    ) -> Result<TranscodeResult, TranscodeError>;
+
+    /// 创建流式拼接编码器（处理方式同 concat，逐段追加、按块输出）
+    fn concat_stream(&self, config: &ConcatConfig) -> Result<Box<dyn AudioStreamEncoder>, TranscodeError>;
+
+    /// 计算波形峰值：按时间均分为 `points` 段，取每段各声道的最大幅度（0.0 - 1.0）
+    ///
+    /// 输入可以是任意可解码的格式（WAV/FLAC/OGG/MP3）
+    fn peaks(&self, audio_data: &[u8], points: usize) -> Result<Vec<f32>, TranscodeError>;
+
     /// 获取音频信息（不转码）
     fn get_audio_info(&self, wav_data: &[u8]) -> Result<AudioInfo, TranscodeError>;
 
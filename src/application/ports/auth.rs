@@ -0,0 +1,44 @@
+//! Auth Ports - 用户账号的密码哈希与登录令牌
+//!
+//! 具体实现在 infrastructure/adapters/auth（Argon2、JWT）
+
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+use uuid::Uuid;
+
+/// 认证错误
+#[derive(Debug, Error)]
+pub enum AuthError {
+    /// 令牌无效（签名不符、格式错误或已过期）
+    #[error("Invalid token: {0}")]
+    InvalidToken(String),
+
+    /// 密码哈希计算或解析失败
+    #[error("Password hash error: {0}")]
+    HashError(String),
+}
+
+/// 签发的登录令牌
+#[derive(Debug, Clone)]
+pub struct IssuedToken {
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Password Hasher Port
+pub trait PasswordHasherPort: Send + Sync {
+    /// 计算密码哈希（每次使用新的随机盐）
+    fn hash(&self, password: &str) -> Result<String, AuthError>;
+
+    /// 校验密码是否与哈希匹配
+    fn verify(&self, password: &str, password_hash: &str) -> Result<bool, AuthError>;
+}
+
+/// Auth Token Port
+pub trait AuthTokenPort: Send + Sync {
+    /// 为用户签发登录令牌
+    fn issue(&self, user_id: Uuid) -> Result<IssuedToken, AuthError>;
+
+    /// 校验令牌，返回令牌所属的用户
+    fn verify(&self, token: &str) -> Result<Uuid, AuthError>;
+}
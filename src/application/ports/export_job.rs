@@ -0,0 +1,103 @@
+//! Export Job Port - 有声书导出任务管理
+//!
+//! 定义导出任务状态的抽象接口，具体实现在 infrastructure/memory 层
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+use super::AudioFormat;
+
+/// 导出任务状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportJobState {
+    /// 合成缺失的片段
+    Synthesizing,
+    /// 拼接、编码
+    Merging,
+    /// 导出完成，可下载
+    Ready,
+    /// 导出失败
+    Failed,
+}
+
+impl ExportJobState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExportJobState::Synthesizing => "synthesizing",
+            ExportJobState::Merging => "merging",
+            ExportJobState::Ready => "ready",
+            ExportJobState::Failed => "failed",
+        }
+    }
+}
+
+/// 有声书导出任务
+#[derive(Debug, Clone)]
+pub struct ExportJob {
+    pub job_id: String,
+    pub novel_id: Uuid,
+    pub voice_id: Uuid,
+    /// 输出格式（完成后为实际格式，编码器不支持请求的格式时为 WAV）
+    pub format: AudioFormat,
+    pub state: ExportJobState,
+    /// 已就绪（已缓存）的片段数
+    pub completed_segments: usize,
+    pub total_segments: usize,
+    /// 导出文件路径（Ready 时设置）
+    pub file_path: Option<PathBuf>,
+    pub error_message: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// 所属用户（未启用账号时为 None）
+    pub user_id: Option<Uuid>,
+}
+
+impl ExportJob {
+    pub fn new(novel_id: Uuid, voice_id: Uuid, format: AudioFormat, total_segments: usize) -> Self {
+        Self {
+            job_id: Uuid::new_v4().to_string(),
+            novel_id,
+            voice_id,
+            format,
+            state: ExportJobState::Synthesizing,
+            completed_segments: 0,
+            total_segments,
+            file_path: None,
+            error_message: None,
+            created_at: Utc::now(),
+            completed_at: None,
+            user_id: None,
+        }
+    }
+
+    pub fn with_user(mut self, user_id: Option<Uuid>) -> Self {
+        self.user_id = user_id;
+        self
+    }
+}
+
+/// Export Job Manager Port
+///
+/// 导出任务状态存储在内存中，重启后丢失
+pub trait ExportJobManagerPort: Send + Sync {
+    /// 创建任务
+    fn create(&self, job: ExportJob);
+
+    /// 获取任务
+    fn get(&self, job_id: &str) -> Option<ExportJob>;
+
+    /// 更新已就绪的片段数
+    fn update_progress(&self, job_id: &str, completed_segments: usize);
+
+    /// 设置任务状态
+    fn set_state(&self, job_id: &str, state: ExportJobState);
+
+    /// 标记导出完成
+    fn set_ready(&self, job_id: &str, file_path: PathBuf, format: AudioFormat);
+
+    /// 标记导出失败并记录错误
+    fn set_failed(&self, job_id: &str, error: String);
+}
@@ -3,8 +3,12 @@
 //! 定义应用层与基础设施层的抽象接口
 
 mod audio_cache;
+mod auth;
 mod audio_storage;
 mod audio_transcoder;
+mod export_job;
+mod partial_audio;
+mod pregeneration_job;
 mod repositories;
 mod session_manager;
 mod task_manager;
@@ -12,20 +16,38 @@ mod text_segmenter;
 mod tts_engine;
 
 pub use audio_cache::{
-    generate_cache_key, AudioCachePort, CacheEntry, CacheError, CacheMetadata, CacheStats,
+    cache_key_version, generate_cache_key, generate_cache_key_with_params, AudioCachePort, CacheEntry, CacheError, CacheMetadata, CacheStats,
+    NovelCacheUsage, SegmentReference, VoiceKey, CACHE_KEY_VERSION, WAVEFORM_POINTS,
 };
+pub use auth::{AuthError, AuthTokenPort, IssuedToken, PasswordHasherPort};
 pub use audio_storage::{
-    AudioStorageError, AudioStoragePort, GcConfig, GcResult, StorageStats,
+    AudioStorageError, AudioStoragePort, GcConfig, GcResult, StorageStats, StoredAudioFile,
 };
 pub use repositories::{
-    AudioSegmentRecord, AudioSegmentRepositoryPort, AudioSegmentState, NovelRecord,
-    NovelRepositoryPort, NovelStatus, RepositoryError, SessionRecord, SessionRepositoryPort,
-    SessionState, TextSegmentRecord, VoiceRecord, VoiceRepositoryPort, WindowConfig,
+    AudioSegmentRecord, AudioSegmentRepositoryPort, AudioSegmentState, ListeningStatsRecord,
+    ListeningStatsRepositoryPort, NovelRecord, NovelRepositoryPort, NovelStatus, QueuedTaskRecord,
+    QueuedTaskRepositoryPort, ReadingProgressRecord, ReadingProgressRepositoryPort, RepositoryError, SessionRecord, SessionRepositoryPort,
+    SessionState, SettingsRepositoryPort, TaskEventFilter, TaskEventKind, TaskEventRecord,
+    TaskEventRepositoryPort, TextSegmentRecord, TtsEngineStatusRecord,
+    TtsEngineStatusRepositoryPort, UserRecord, UserRepositoryPort, VoiceCastingRecord,
+    VoiceCastingRepositoryPort, VoiceRecord, VoiceRepositoryPort, WindowConfig,
+    SETTING_DEFAULT_VOICE_ID,
 };
-pub use session_manager::{Session, SessionError, SessionManagerPort};
-pub use task_manager::{InferenceTask, TaskError, TaskManagerPort, TaskState};
+pub use export_job::{ExportJob, ExportJobManagerPort, ExportJobState};
+pub use pregeneration_job::{PregenerationJob, PregenerationJobManagerPort, PregenerationJobState};
+pub use session_manager::{
+    PlaybackHints, Session, SessionError, SessionManagerPort, VoiceHandoff, MAX_CROSSFADE_MS,
+    MAX_GAP_MS,
+};
+pub use task_manager::{InferenceTask, TaskError, TaskManagerPort, TaskPriority, TaskState};
 pub use text_segmenter::{SegmentConfig, SegmentedText, TextSegmenterPort};
-pub use tts_engine::{InferRequest, InferResponse, TtsEnginePort, TtsError};
+pub use partial_audio::{PartialAudioPort, PartialAudioStream};
+pub use tts_engine::{
+    AudioChunkStream, InferRequest, InferResponse, InferStream, MarkupType, TtsEnginePort,
+    TtsError,
+};
 pub use audio_transcoder::{
-    AudioFormat, AudioInfo, AudioTranscoderPort, TranscodeConfig, TranscodeError, TranscodeResult,
+    AudioFormat, AudioInfo, AudioMetadata, AudioStreamEncoder, AudioTranscoderPort, ChapterMark,
+    ConcatConfig, ReferenceNormalizeConfig, ResampleQuality, StreamEnd, TranscodeConfig,
+    TranscodeError, TranscodeResult, PLAYBACK_SPEED_RANGE,
 };
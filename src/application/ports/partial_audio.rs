@@ -0,0 +1,32 @@
+//! Partial Audio Port - 合成中音频的共享缓冲
+//!
+//! 流式推理时 Worker 将已合成的音频分块写入缓冲，
+//! 音频请求在缓存未命中时可订阅缓冲，边合成边播放。
+//! 合成完成后完整音频写入 AudioCache，缓冲随之移除。
+
+use futures_util::stream::BoxStream;
+
+use super::audio_cache::CacheError;
+
+/// 合成中音频流：先产出已缓冲的音频，再依次产出新分块，合成完成后结束
+pub type PartialAudioStream = BoxStream<'static, Result<Vec<u8>, CacheError>>;
+
+/// Partial Audio Port
+///
+/// 以缓存 key 标识，与 AudioCachePort 使用同一 key
+pub trait PartialAudioPort: Send + Sync {
+    /// 开始缓冲（已存在时重置）
+    fn begin(&self, cache_key: &str);
+
+    /// 追加音频分块
+    fn append(&self, cache_key: &str, chunk: &[u8]);
+
+    /// 合成完成，结束所有订阅并移除缓冲
+    fn finish(&self, cache_key: &str);
+
+    /// 合成失败，订阅方收到错误并移除缓冲
+    fn abort(&self, cache_key: &str, error: &str);
+
+    /// 订阅合成中的音频，不存在时返回 None
+    fn subscribe(&self, cache_key: &str) -> Option<PartialAudioStream>;
+}
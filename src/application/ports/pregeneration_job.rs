@@ -0,0 +1,108 @@
+//! Pregeneration Job Port - 整书预生成任务管理
+//!
+//! 定义预生成任务状态的抽象接口，具体实现在 infrastructure/memory 层
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// 预生成任务状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PregenerationJobState {
+    /// 逐批合成中
+    Running,
+    /// 已暂停（当前批次完成后不再提交）
+    Paused,
+    /// 不在预生成时段内，进入时段后自动继续
+    Scheduled,
+    /// 全部片段处理完成（失败的片段计入 failed_segments）
+    Completed,
+    /// 已取消
+    Cancelled,
+    /// 任务失败
+    Failed,
+}
+
+impl PregenerationJobState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PregenerationJobState::Running => "running",
+            PregenerationJobState::Paused => "paused",
+            PregenerationJobState::Scheduled => "scheduled",
+            PregenerationJobState::Completed => "completed",
+            PregenerationJobState::Cancelled => "cancelled",
+            PregenerationJobState::Failed => "failed",
+        }
+    }
+
+    /// 是否已结束
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            PregenerationJobState::Completed | PregenerationJobState::Cancelled | PregenerationJobState::Failed
+        )
+    }
+}
+
+/// 整书预生成任务
+#[derive(Debug, Clone)]
+pub struct PregenerationJob {
+    pub job_id: String,
+    pub novel_id: Uuid,
+    pub voice_id: Uuid,
+    pub state: PregenerationJobState,
+    /// 已处理的片段数（含已缓存和合成失败的片段）
+    pub completed_segments: usize,
+    /// 合成失败的片段数
+    pub failed_segments: usize,
+    pub total_segments: usize,
+    pub error_message: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// 所属用户（未启用账号时为 None）
+    pub user_id: Option<Uuid>,
+}
+
+impl PregenerationJob {
+    pub fn new(novel_id: Uuid, voice_id: Uuid, total_segments: usize) -> Self {
+        Self {
+            job_id: Uuid::new_v4().to_string(),
+            novel_id,
+            voice_id,
+            state: PregenerationJobState::Running,
+            completed_segments: 0,
+            failed_segments: 0,
+            total_segments,
+            error_message: None,
+            created_at: Utc::now(),
+            completed_at: None,
+            user_id: None,
+        }
+    }
+
+    pub fn with_user(mut self, user_id: Option<Uuid>) -> Self {
+        self.user_id = user_id;
+        self
+    }
+}
+
+/// Pregeneration Job Manager Port
+///
+/// 预生成任务状态存储在内存中，重启后丢失（已合成的音频保留在缓存中，重新提交即可继续）
+pub trait PregenerationJobManagerPort: Send + Sync {
+    /// 创建任务
+    fn create(&self, job: PregenerationJob);
+
+    /// 获取任务
+    fn get(&self, job_id: &str) -> Option<PregenerationJob>;
+
+    /// 更新进度
+    fn update_progress(&self, job_id: &str, completed_segments: usize, failed_segments: usize);
+
+    /// 设置任务状态（结束状态记录完成时间）
+    fn set_state(&self, job_id: &str, state: PregenerationJobState);
+
+    /// 标记任务失败并记录错误
+    fn set_failed(&self, job_id: &str, error: String);
+}
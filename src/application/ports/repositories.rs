@@ -4,11 +4,16 @@
 //! 具体实现在 infrastructure 层（如 SQLite）
 
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use thiserror::Error;
 use uuid::Uuid;
 
+use super::session_manager::PlaybackHints;
+use super::task_manager::TaskPriority;
+use crate::domain::voice::{SynthesisParams, TtsConfig, VoiceTags};
+
 /// Repository 错误
 #[derive(Debug, Error)]
 pub enum RepositoryError {
@@ -33,11 +38,12 @@ pub enum RepositoryError {
 // ============================================================================
 
 /// 小说处理状态
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum NovelStatus {
     /// 处理中
     Processing,
     /// 已就绪
+    #[default]
     Ready,
     /// 处理失败
     Failed,
@@ -52,6 +58,7 @@ impl NovelStatus {
         }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Self> {
         match s {
             "processing" => Some(NovelStatus::Processing),
@@ -62,12 +69,6 @@ impl NovelStatus {
     }
 }
 
-impl Default for NovelStatus {
-    fn default() -> Self {
-        NovelStatus::Ready
-    }
-}
-
 /// 小说实体（用于持久化）
 #[derive(Debug, Clone)]
 pub struct NovelRecord {
@@ -76,6 +77,8 @@ pub struct NovelRecord {
     pub raw_text_path: PathBuf,
     pub total_segments: usize,
     pub status: NovelStatus,
+    /// 所属用户（未启用账号时为 None）
+    pub user_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -102,6 +105,17 @@ pub trait NovelRepositoryPort: Send + Sync {
     /// 获取所有小说
     async fn find_all(&self) -> Result<Vec<NovelRecord>, RepositoryError>;
 
+    /// 分页获取小说（按创建时间倒序）；指定用户时只含该用户的和不属于任何用户的小说
+    async fn find_page(
+        &self,
+        user_id: Option<Uuid>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<NovelRecord>, RepositoryError>;
+
+    /// 小说总数（过滤条件同 find_page）
+    async fn count(&self, user_id: Option<Uuid>) -> Result<u64, RepositoryError>;
+
     /// 删除小说
     async fn delete(&self, id: Uuid) -> Result<(), RepositoryError>;
 
@@ -129,6 +143,9 @@ pub trait NovelRepositoryPort: Send + Sync {
         limit: usize,
     ) -> Result<Vec<TextSegmentRecord>, RepositoryError>;
 
+    /// 小说的段落总数
+    async fn count_segments(&self, novel_id: Uuid) -> Result<u64, RepositoryError>;
+
     /// 批量获取指定索引的段落（性能优化）
     async fn find_segments_by_indices(
         &self,
@@ -144,6 +161,9 @@ pub trait NovelRepositoryPort: Send + Sync {
         total_segments: usize,
     ) -> Result<(), RepositoryError>;
 
+    /// 把不属于任何用户的小说（及其阅读进度和收听统计）归到指定用户，返回归属的小说数量
+    async fn assign_unowned(&self, user_id: Uuid) -> Result<u64, RepositoryError>;
+
     /// 批量保存文本段落（性能优化）
     async fn save_segments_batch(&self, segments: &[TextSegmentRecord]) -> Result<(), RepositoryError> {
         // 默认实现：调用 save_segments
@@ -161,7 +181,19 @@ pub struct VoiceRecord {
     pub id: Uuid,
     pub name: String,
     pub reference_audio_path: PathBuf,
+    /// 归一化后的参考音频路径（与原始文件同目录），未处理时为 None
+    pub processed_audio_path: Option<PathBuf>,
     pub description: Option<String>,
+    /// 参考音频对应的文本（部分 TTS 引擎需要）
+    pub prompt_text: Option<String>,
+    /// 使用的 TTS 引擎名称（None 表示默认引擎）
+    pub engine: Option<String>,
+    /// 音色专属的 TTS 合成参数
+    pub tts_config: TtsConfig,
+    /// 分类标签（性别/语言/风格）
+    pub tags: VoiceTags,
+    /// 所属用户（None 为所有用户共享，如内置预置和启用账号前创建的音色）
+    pub user_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -208,6 +240,7 @@ impl SessionState {
         }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Self> {
         match s {
             "idle" => Some(SessionState::Idle),
@@ -220,7 +253,7 @@ impl SessionState {
 }
 
 /// 滑动窗口配置
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct WindowConfig {
     /// 当前位置之前保留的段数
     pub before: usize,
@@ -257,8 +290,24 @@ pub struct SessionRecord {
     pub novel_id: Uuid,
     pub voice_id: Uuid,
     pub current_index: usize,
+    /// 当前片段内的播放偏移（毫秒）
+    pub offset_ms: u32,
     pub state: SessionState,
     pub window_config: WindowConfig,
+    /// 会话合成参数
+    pub params: SynthesisParams,
+    /// 按片段覆盖的合成参数
+    pub segment_params: HashMap<u32, SynthesisParams>,
+    /// 服务端变速播放的默认速度
+    pub playback_speed: Option<f32>,
+    /// 片段衔接提示
+    pub playback_hints: PlaybackHints,
+    /// 创建会话的客户端（IP）
+    pub client: Option<String>,
+    /// 自动前进模式
+    pub auto_advance: bool,
+    /// 所属用户（未启用账号时为 None）
+    pub user_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub last_accessed_at: DateTime<Utc>,
@@ -316,6 +365,7 @@ impl AudioSegmentState {
         }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Self> {
         match s {
             "pending" => Some(AudioSegmentState::Pending),
@@ -389,3 +439,285 @@ pub trait AudioSegmentRepositoryPort: Send + Sync {
     /// 更新最后访问时间
     async fn touch(&self, id: Uuid) -> Result<(), RepositoryError>;
 }
+
+// ============================================================================
+// Settings Repository
+// ============================================================================
+
+/// 设置项: 默认音色 ID（PlayCommand 未指定 voice_id 时使用）
+pub const SETTING_DEFAULT_VOICE_ID: &str = "default_voice_id";
+
+/// Settings Repository Port
+///
+/// 简单的 key/value 设置存储，按用户区分（未启用账号时 user_id 为 None）
+#[async_trait]
+pub trait SettingsRepositoryPort: Send + Sync {
+    /// 获取设置值
+    async fn get(&self, user_id: Option<Uuid>, key: &str) -> Result<Option<String>, RepositoryError>;
+
+    /// 写入设置值（存在则覆盖）
+    async fn set(&self, user_id: Option<Uuid>, key: &str, value: &str) -> Result<(), RepositoryError>;
+
+    /// 删除设置
+    async fn delete(&self, user_id: Option<Uuid>, key: &str) -> Result<(), RepositoryError>;
+
+    /// 删除所有用户中值为 `value` 的设置项（如被删除音色的默认音色设置），返回删除数量
+    async fn delete_by_value(&self, key: &str, value: &str) -> Result<u64, RepositoryError>;
+
+    /// 获取用户的所有设置（按 key 排序）
+    async fn find_all(&self, user_id: Option<Uuid>) -> Result<Vec<(String, String)>, RepositoryError>;
+}
+
+/// 角色配音分配（小说角色名 -> 音色）
+#[derive(Debug, Clone)]
+pub struct VoiceCastingRecord {
+    pub novel_id: Uuid,
+    /// 识别出的角色名
+    pub character_name: String,
+    pub voice_id: Uuid,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Voice Casting Repository Port
+///
+/// 每部小说的角色配音表（删除小说或音色时由对应仓储级联清理）
+#[async_trait]
+pub trait VoiceCastingRepositoryPort: Send + Sync {
+    /// 获取小说的所有角色配音（按角色名排序）
+    async fn find_by_novel(&self, novel_id: Uuid) -> Result<Vec<VoiceCastingRecord>, RepositoryError>;
+
+    /// 设置角色配音（存在则覆盖）
+    async fn set(&self, novel_id: Uuid, character_name: &str, voice_id: Uuid) -> Result<(), RepositoryError>;
+
+    /// 删除角色配音
+    async fn delete(&self, novel_id: Uuid, character_name: &str) -> Result<(), RepositoryError>;
+}
+
+// ============================================================================
+// TTS Engine Status Repository
+// ============================================================================
+
+/// TTS 引擎健康状态
+#[derive(Debug, Clone)]
+pub struct TtsEngineStatusRecord {
+    /// 引擎名称（"default" 为顶层 [tts] 配置）
+    pub engine: String,
+    pub healthy: bool,
+    /// 连续健康检查失败次数
+    pub consecutive_failures: u32,
+    pub last_checked_at: DateTime<Utc>,
+    /// 最近一次健康状态变化的时间
+    pub last_changed_at: DateTime<Utc>,
+}
+
+/// TTS Engine Status Repository Port
+///
+/// 持久化健康检查结果，重启后可据此判断状态变化
+#[async_trait]
+pub trait TtsEngineStatusRepositoryPort: Send + Sync {
+    /// 获取所有引擎状态（按引擎名排序）
+    async fn find_all(&self) -> Result<Vec<TtsEngineStatusRecord>, RepositoryError>;
+
+    /// 写入引擎状态（存在则覆盖）
+    async fn save(&self, record: &TtsEngineStatusRecord) -> Result<(), RepositoryError>;
+}
+
+/// 每日收听统计（按用户 + 小说 + 日期汇总，日期为服务器本地日期）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListeningStatsRecord {
+    /// 收听的用户（未启用账号时为 None）
+    pub user_id: Option<Uuid>,
+    pub novel_id: Uuid,
+    pub day: NaiveDate,
+    /// 听完的片段数
+    pub segments: u32,
+    /// 收听时长（毫秒）
+    pub listened_ms: u64,
+    /// 当天到达的最远片段索引
+    pub max_index: u32,
+}
+
+/// Listening Stats Repository Port
+#[async_trait]
+pub trait ListeningStatsRepositoryPort: Send + Sync {
+    /// 累加收听统计：同一用户同一小说同一天的片段数和时长相加，最远片段取较大值
+    async fn add(&self, record: &ListeningStatsRecord) -> Result<(), RepositoryError>;
+
+    /// 获取用户的统计（可按小说过滤，按日期排序）
+    async fn find(
+        &self,
+        user_id: Option<Uuid>,
+        novel_id: Option<Uuid>,
+    ) -> Result<Vec<ListeningStatsRecord>, RepositoryError>;
+}
+
+/// 小说的阅读进度（每个用户最近一次上报的播放位置，会话关闭后保留）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadingProgressRecord {
+    /// 所属用户（未启用账号时为 None）
+    pub user_id: Option<Uuid>,
+    pub novel_id: Uuid,
+    pub segment_index: u32,
+    /// 片段内偏移（毫秒）
+    pub offset_ms: u32,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Reading Progress Repository Port
+#[async_trait]
+pub trait ReadingProgressRepositoryPort: Send + Sync {
+    /// 写入进度（存在则覆盖）
+    async fn save(&self, record: &ReadingProgressRecord) -> Result<(), RepositoryError>;
+
+    /// 获取用户在小说上的进度
+    async fn find(
+        &self,
+        user_id: Option<Uuid>,
+        novel_id: Uuid,
+    ) -> Result<Option<ReadingProgressRecord>, RepositoryError>;
+}
+
+/// 未完成的推理任务（提交时写入、结束时删除，启动时重新排队）
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueuedTaskRecord {
+    pub task_id: String,
+    pub session_id: String,
+    pub novel_id: Uuid,
+    pub voice_id: Uuid,
+    pub segment_index: u32,
+    pub segment_content: String,
+    pub params: SynthesisParams,
+    pub explicit: bool,
+    pub priority: TaskPriority,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Queued Task Repository Port
+#[async_trait]
+pub trait QueuedTaskRepositoryPort: Send + Sync {
+    /// 写入任务（已存在则覆盖）
+    async fn save_all(&self, tasks: &[QueuedTaskRecord]) -> Result<(), RepositoryError>;
+
+    /// 获取所有任务（按创建时间）
+    async fn find_all(&self) -> Result<Vec<QueuedTaskRecord>, RepositoryError>;
+
+    /// 删除指定任务
+    async fn delete(&self, task_ids: &[String]) -> Result<(), RepositoryError>;
+
+    /// 删除所有任务
+    async fn delete_all(&self) -> Result<(), RepositoryError>;
+}
+
+/// 任务生命周期事件
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskEventKind {
+    /// 提交到队列
+    Submitted,
+    /// 开始推理
+    Started,
+    /// 失败后重新排队
+    Retried,
+    /// 推理失败（附错误）
+    Failed,
+    /// 推理完成（附耗时，命中缓存时无耗时）
+    Completed,
+}
+
+impl TaskEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskEventKind::Submitted => "submitted",
+            TaskEventKind::Started => "started",
+            TaskEventKind::Retried => "retried",
+            TaskEventKind::Failed => "failed",
+            TaskEventKind::Completed => "completed",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "submitted" => Some(TaskEventKind::Submitted),
+            "started" => Some(TaskEventKind::Started),
+            "retried" => Some(TaskEventKind::Retried),
+            "failed" => Some(TaskEventKind::Failed),
+            "completed" => Some(TaskEventKind::Completed),
+            _ => None,
+        }
+    }
+}
+
+/// 任务审计日志条目（只追加）
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskEventRecord {
+    pub task_id: String,
+    pub session_id: String,
+    pub novel_id: Uuid,
+    pub voice_id: Uuid,
+    pub segment_index: u32,
+    pub event: TaskEventKind,
+    pub error: Option<String>,
+    pub duration_ms: Option<u64>,
+    /// 提交任务的 HTTP 请求 ID
+    pub request_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// 任务审计日志查询条件（均为可选，同时指定时取交集）
+#[derive(Debug, Clone, Default)]
+pub struct TaskEventFilter {
+    pub session_id: Option<String>,
+    pub novel_id: Option<Uuid>,
+    pub segment_index: Option<u32>,
+    pub task_id: Option<String>,
+    pub event: Option<TaskEventKind>,
+    pub request_id: Option<String>,
+    /// 返回最近的条数
+    pub limit: usize,
+}
+
+/// Task Event Repository Port
+#[async_trait]
+pub trait TaskEventRepositoryPort: Send + Sync {
+    /// 追加事件
+    async fn append(&self, events: &[TaskEventRecord]) -> Result<(), RepositoryError>;
+
+    /// 按条件查询事件（最新的在前）
+    async fn find(&self, filter: &TaskEventFilter) -> Result<Vec<TaskEventRecord>, RepositoryError>;
+
+    /// 删除指定时间之前的事件，返回删除数
+    async fn delete_before(&self, before: DateTime<Utc>) -> Result<u64, RepositoryError>;
+}
+
+// ============================================================================
+// User Repository
+// ============================================================================
+
+/// 用户账号
+#[derive(Debug, Clone)]
+pub struct UserRecord {
+    pub id: Uuid,
+    pub username: String,
+    /// 密码哈希（PHC 字符串）
+    pub password_hash: String,
+    /// 可访问管理接口（第一个注册的用户）
+    pub is_admin: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// User Repository Port
+#[async_trait]
+pub trait UserRepositoryPort: Send + Sync {
+    /// 创建用户，用户名已存在时返回 Duplicate
+    ///
+    /// 没有任何用户时新用户成为管理员（忽略 `user.is_admin`），判断和插入在同一条语句中完成，
+    /// 并发注册时只有一个用户成为管理员；`first_only` 为 true 且已有用户时不插入，返回 None。
+    /// 成功时返回新用户是否为管理员
+    async fn create(&self, user: &UserRecord, first_only: bool) -> Result<Option<bool>, RepositoryError>;
+
+    /// 根据 ID 查找用户
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<UserRecord>, RepositoryError>;
+
+    /// 根据用户名查找用户
+    async fn find_by_username(&self, username: &str) -> Result<Option<UserRecord>, RepositoryError>;
+}
@@ -3,9 +3,14 @@
 //! 定义会话管理的抽象接口，具体实现在 infrastructure/memory 层
 
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use thiserror::Error;
 use uuid::Uuid;
 
+use super::repositories::WindowConfig;
+use crate::domain::voice::SynthesisParams;
+
 /// Session Manager 错误
 #[derive(Debug, Error)]
 pub enum SessionError {
@@ -22,6 +27,46 @@ pub enum SessionError {
     InvalidOperation(String),
 }
 
+/// 相邻片段间静音时长上限（毫秒）
+pub const MAX_GAP_MS: u32 = 5000;
+
+/// 交叉淡化时长上限（毫秒），过长会吞掉句子开头
+pub const MAX_CROSSFADE_MS: u32 = 500;
+
+/// 片段衔接提示：合并输出（导出、HLS）时相邻片段之间的处理
+///
+/// 同时设置时，下一段在静音末尾淡入
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlaybackHints {
+    /// 相邻片段之间插入的静音（毫秒）
+    #[serde(default)]
+    pub gap_ms: u32,
+    /// 相邻片段的交叉淡化时长（毫秒）
+    #[serde(default)]
+    pub crossfade_ms: u32,
+}
+
+impl PlaybackHints {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.gap_ms > MAX_GAP_MS {
+            return Err(format!("gap_ms must be at most {}", MAX_GAP_MS));
+        }
+        if self.crossfade_ms > MAX_CROSSFADE_MS {
+            return Err(format!("crossfade_ms must be at most {}", MAX_CROSSFADE_MS));
+        }
+        Ok(())
+    }
+}
+
+/// 切换音色的过渡：新音色的音频就绪前，继续播放旧音色已就绪的片段
+#[derive(Debug, Clone, PartialEq)]
+pub struct VoiceHandoff {
+    /// 切换前的音色
+    pub voice_id: Uuid,
+    /// 切换前的会话合成参数
+    pub params: SynthesisParams,
+}
+
 /// 会话状态（in-memory）
 #[derive(Debug, Clone)]
 pub struct Session {
@@ -29,6 +74,28 @@ pub struct Session {
     pub novel_id: Uuid,
     pub voice_id: Uuid,
     pub current_index: u32,
+    /// 当前片段内的播放偏移（毫秒，客户端上报）
+    pub offset_ms: u32,
+    /// 会话合成参数
+    pub params: SynthesisParams,
+    /// 按片段覆盖的合成参数
+    pub segment_params: HashMap<u32, SynthesisParams>,
+    /// 服务端变速播放的默认速度（获取音频时未指定速度则使用）
+    pub playback_speed: Option<f32>,
+    /// 片段衔接提示（合并导出时未指定则使用）
+    pub playback_hints: PlaybackHints,
+    /// 预取窗口（当前片段前后的片段数）
+    pub window: WindowConfig,
+    /// 后台会话（导出/预生成专用）：不参与播放复用，也不持久化
+    pub background: bool,
+    /// 创建会话的客户端（IP），用于按客户端限制会话数
+    pub client: Option<String>,
+    /// 所属用户（未启用账号时为 None）
+    pub user_id: Option<Uuid>,
+    /// 自动前进模式：预取到窗口上限，下一片段就绪时推送 NextSegmentReady
+    pub auto_advance: bool,
+    /// 切换音色的过渡（仅在内存中，不持久化）
+    pub voice_handoff: Option<VoiceHandoff>,
     pub created_at: DateTime<Utc>,
     pub last_activity: DateTime<Utc>,
 }
@@ -41,15 +108,89 @@ impl Session {
             novel_id,
             voice_id,
             current_index: start_index,
+            offset_ms: 0,
+            params: SynthesisParams::default(),
+            segment_params: HashMap::new(),
+            playback_speed: None,
+            playback_hints: PlaybackHints::default(),
+            window: WindowConfig::default(),
+            background: false,
+            client: None,
+            user_id: None,
+            auto_advance: false,
+            voice_handoff: None,
             created_at: now,
             last_activity: now,
         }
     }
+
+    pub fn with_offset_ms(mut self, offset_ms: u32) -> Self {
+        self.offset_ms = offset_ms;
+        self
+    }
+
+    pub fn with_params(mut self, params: SynthesisParams) -> Self {
+        self.params = params;
+        self
+    }
+
+    pub fn with_playback_speed(mut self, playback_speed: Option<f32>) -> Self {
+        self.playback_speed = playback_speed;
+        self
+    }
+
+    pub fn with_playback_hints(mut self, playback_hints: PlaybackHints) -> Self {
+        self.playback_hints = playback_hints;
+        self
+    }
+
+    pub fn with_window(mut self, window: WindowConfig) -> Self {
+        self.window = window;
+        self
+    }
+
+    pub fn with_client(mut self, client: Option<String>) -> Self {
+        self.client = client;
+        self
+    }
+
+    pub fn with_user(mut self, user_id: Option<Uuid>) -> Self {
+        self.user_id = user_id;
+        self
+    }
+
+    pub fn with_auto_advance(mut self, auto_advance: bool) -> Self {
+        self.auto_advance = auto_advance;
+        self
+    }
+
+    pub fn as_background(mut self) -> Self {
+        self.background = true;
+        self
+    }
+
+    /// 片段实际使用的合成参数（会话参数 + 片段覆盖）
+    pub fn params_for(&self, segment_index: u32) -> SynthesisParams {
+        match self.segment_params.get(&segment_index) {
+            Some(overrides) => self.params.merged(overrides),
+            None => self.params.clone(),
+        }
+    }
+
+    /// 过渡中的旧音色及其在片段上实际使用的参数
+    pub fn handoff_for(&self, segment_index: u32) -> Option<(Uuid, SynthesisParams)> {
+        let handoff = self.voice_handoff.as_ref()?;
+        let params = match self.segment_params.get(&segment_index) {
+            Some(overrides) => handoff.params.merged(overrides),
+            None => handoff.params.clone(),
+        };
+        Some((handoff.voice_id, params))
+    }
 }
 
 /// Session Manager Port
 ///
-/// 管理播放会话的生命周期，所有状态存储在内存中
+/// 管理播放会话的生命周期，会话状态以内存为准（实现可以写穿到数据库以便重启后恢复）
 pub trait SessionManagerPort: Send + Sync {
     /// 创建新会话
     fn create(&self, session: Session) -> Result<String, SessionError>;
@@ -57,12 +198,39 @@ pub trait SessionManagerPort: Send + Sync {
     /// 获取会话
     fn get(&self, id: &str) -> Result<Session, SessionError>;
 
-    /// 更新当前播放索引
+    /// 更新当前播放索引（片段内偏移归零）
     fn update_index(&self, id: &str, index: u32) -> Result<(), SessionError>;
 
+    /// 前后移动当前播放索引（限制在 0..=last_index，片段内偏移归零），返回新的索引；
+    /// 当前索引已超出 last_index 时返回 InvalidOperation，不修改会话
+    fn step_index(&self, id: &str, delta: i32, last_index: u32) -> Result<u32, SessionError>;
+
+    /// 更新播放进度（片段索引 + 片段内偏移）
+    fn update_position(&self, id: &str, index: u32, offset_ms: u32) -> Result<(), SessionError>;
+
     /// 更新音色
     fn update_voice(&self, id: &str, voice_id: Uuid) -> Result<(), SessionError>;
 
+    /// 更新预取窗口
+    fn update_window(&self, id: &str, window: WindowConfig) -> Result<(), SessionError>;
+
+    /// 开启/关闭自动前进模式
+    fn set_auto_advance(&self, id: &str, enabled: bool) -> Result<(), SessionError>;
+
+    /// 开始/结束切换音色的过渡（None 表示结束）
+    fn set_voice_handoff(&self, id: &str, handoff: Option<VoiceHandoff>) -> Result<(), SessionError>;
+
+    /// 更新会话合成参数
+    fn update_params(&self, id: &str, params: SynthesisParams) -> Result<(), SessionError>;
+
+    /// 设置片段的合成参数覆盖（默认参数表示移除覆盖）
+    fn set_segment_params(
+        &self,
+        id: &str,
+        segment_index: u32,
+        params: SynthesisParams,
+    ) -> Result<(), SessionError>;
+
     /// 检查会话是否有效
     fn is_valid(&self, id: &str) -> bool;
 
@@ -5,8 +5,12 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
+use crate::application::request_context::current_request_id;
+use crate::domain::voice::SynthesisParams;
+
 /// Task Manager 错误
 #[derive(Debug, Error)]
 pub enum TaskError {
@@ -18,6 +22,10 @@ pub enum TaskError {
 
     #[error("Invalid state transition: {0}")]
     InvalidStateTransition(String),
+
+    /// 推理队列已满，整批任务未提交
+    #[error("Task queue is full: {depth}/{capacity}")]
+    QueueFull { depth: usize, capacity: usize },
 }
 
 /// 任务状态
@@ -47,6 +55,7 @@ impl TaskState {
         }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Self> {
         match s {
             "pending" => Some(TaskState::Pending),
@@ -59,6 +68,48 @@ impl TaskState {
     }
 }
 
+/// 任务优先级：排队中优先级高的任务先推理，同优先级先进先出
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskPriority {
+    /// 导出、预生成等后台任务
+    Background,
+    /// 预取的片段
+    #[default]
+    Prefetch,
+    /// 会话正在播放的片段
+    Current,
+}
+
+impl TaskPriority {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskPriority::Background => "background",
+            TaskPriority::Prefetch => "prefetch",
+            TaskPriority::Current => "current",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "background" => Some(TaskPriority::Background),
+            "prefetch" => Some(TaskPriority::Prefetch),
+            "current" => Some(TaskPriority::Current),
+            _ => None,
+        }
+    }
+
+    /// 会话片段的优先级：正在播放的片段最高
+    pub fn for_segment(segment_index: u32, current_index: u32) -> Self {
+        if segment_index == current_index {
+            TaskPriority::Current
+        } else {
+            TaskPriority::Prefetch
+        }
+    }
+}
+
 /// 推理任务
 #[derive(Debug, Clone)]
 pub struct InferenceTask {
@@ -68,13 +119,23 @@ pub struct InferenceTask {
     pub voice_id: Uuid,
     pub segment_index: u32,
     pub segment_content: String,
+    /// 合成参数（会话参数与片段覆盖合并后）
+    pub params: SynthesisParams,
+    /// 客户端显式批量预取的任务：预取窗口移动时不取消
+    pub explicit: bool,
+    pub priority: TaskPriority,
+    /// 取消后 Worker 中止进行中的推理请求（克隆的任务共享同一令牌）
+    pub cancel_token: CancellationToken,
     pub state: TaskState,
+    /// 提交任务的 HTTP 请求 ID（用于串联请求、任务和 Worker 日志；后台提交时为 None）
+    pub request_id: Option<String>,
     pub created_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
     pub error_message: Option<String>,
 }
 
 impl InferenceTask {
+    /// 创建任务，记录当前请求的 ID
     pub fn new(
         session_id: String,
         novel_id: Uuid,
@@ -89,24 +150,58 @@ impl InferenceTask {
             voice_id,
             segment_index,
             segment_content,
+            params: SynthesisParams::default(),
+            explicit: false,
+            priority: TaskPriority::default(),
+            cancel_token: CancellationToken::new(),
             state: TaskState::Pending,
+            request_id: current_request_id(),
             created_at: Utc::now(),
             completed_at: None,
             error_message: None,
         }
     }
+
+    pub fn with_params(mut self, params: SynthesisParams) -> Self {
+        self.params = params;
+        self
+    }
+
+    pub fn as_explicit(mut self) -> Self {
+        self.explicit = true;
+        self
+    }
+
+    pub fn with_priority(mut self, priority: TaskPriority) -> Self {
+        self.priority = priority;
+        self
+    }
 }
 
 /// Task Manager Port
 ///
-/// 管理推理任务的生命周期，所有状态存储在内存中
+/// 管理推理任务的生命周期，状态以内存为准（可写穿到数据库，重启后恢复未完成的任务）
 pub trait TaskManagerPort: Send + Sync {
-    /// 提交任务到队列
+    /// 提交任务到队列；队列容纳不下整批任务时返回 [`TaskError::QueueFull`]，不提交其中任何任务
     fn submit(&self, tasks: Vec<InferenceTask>) -> Result<Vec<String>, TaskError>;
 
     /// 取消会话的所有 pending 任务，返回取消数量
     fn cancel_pending(&self, session_id: &str) -> usize;
 
+    /// 取消会话的所有 pending 任务并中止推理中的任务（会话关闭时），返回取消数量
+    fn cancel_all(&self, session_id: &str) -> usize;
+
+    /// 取消会话中片段索引不在 [start, end] 内的 pending 和推理中的任务（显式预取的任务除外），
+    /// 返回取消数量
+    fn cancel_outside(&self, session_id: &str, start: u32, end: u32) -> usize;
+
+    /// 取消会话中所有 pending 和推理中的窗口预取任务（显式预取的任务除外，会话空闲时），
+    /// 返回取消数量
+    fn cancel_window(&self, session_id: &str) -> usize;
+
+    /// 提升 pending 任务的优先级（不降低），返回是否调整
+    fn prioritize(&self, task_id: &str, priority: TaskPriority) -> bool;
+
     /// 检查任务是否已取消
     fn is_cancelled(&self, task_id: &str) -> bool;
 
@@ -125,6 +220,15 @@ pub trait TaskManagerPort: Send + Sync {
     /// 获取会话的所有任务
     fn get_tasks_by_session(&self, session_id: &str) -> Vec<InferenceTask>;
 
+    /// 获取所有任务（可按状态过滤）
+    fn list_tasks(&self, state: Option<TaskState>) -> Vec<InferenceTask>;
+
+    /// 失败的任务重新排队（清除错误，按指定优先级入队）
+    fn retry(&self, task_id: &str, priority: TaskPriority) -> Result<(), TaskError>;
+
     /// 清理会话的所有任务
     fn cleanup_session(&self, session_id: &str);
+
+    /// 排队等待推理的任务数
+    fn queue_depth(&self) -> usize;
 }
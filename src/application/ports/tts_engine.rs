@@ -3,8 +3,12 @@
 //! 定义 TTS 推理的抽象接口，具体实现在 infrastructure/adapters 层
 
 use async_trait::async_trait;
+use futures_util::stream::{self, BoxStream, StreamExt};
+use std::path::PathBuf;
 use thiserror::Error;
 
+use crate::domain::voice::{SynthesisParams, TtsConfig};
+
 /// TTS 错误
 #[derive(Debug, Error)]
 pub enum TtsError {
@@ -24,6 +28,16 @@ pub enum TtsError {
     VoiceNotFound(String),
 }
 
+/// 推理文本的标记类型
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MarkupType {
+    /// 纯文本
+    #[default]
+    PlainText,
+    /// SSML 文档（`<speak>...</speak>`）
+    Ssml,
+}
+
 /// TTS 推理请求
 #[derive(Debug, Clone)]
 pub struct InferRequest {
@@ -31,8 +45,24 @@ pub struct InferRequest {
     pub text: String,
     /// 参考音频的 URL 或路径（TTS 服务会自行下载/读取并缓存）
     pub voice_ref: String,
+    /// 参考音频的本地文件路径（上传模式下由客户端读取并随请求发送）
+    pub voice_ref_path: Option<PathBuf>,
     /// 音色 ID（用于日志和追踪）
     pub voice_id: String,
+    /// 参考音频对应的文本（需要 prompt text 的引擎使用）
+    pub prompt_text: Option<String>,
+    /// 使用的 TTS 引擎名称（None 表示默认引擎）
+    pub engine: Option<String>,
+    /// 音色的合成参数
+    pub tts_config: TtsConfig,
+    /// 播放时的合成参数（语速/情感/能量），优先于 tts_config
+    pub params: SynthesisParams,
+    /// 随机种子：相同文本、音色与种子应得到相同音频（缓存丢失后可重新生成一致的音频）
+    pub seed: Option<u64>,
+    /// 延迟敏感（当前播放片段），允许对冲到备用后端
+    pub latency_sensitive: bool,
+    /// text 的标记类型
+    pub markup: MarkupType,
 }
 
 /// TTS 推理响应
@@ -48,6 +78,33 @@ pub struct InferResponse {
     pub sample_rate: Option<u32>,
 }
 
+/// 音频分块流
+pub type AudioChunkStream = BoxStream<'static, Result<Vec<u8>, TtsError>>;
+
+/// 流式 TTS 推理响应
+///
+/// 按合成进度依次产出音频分块，所有分块按顺序拼接即为完整音频（WAV/PCM）
+pub struct InferStream {
+    /// TTS 服务会话 ID（用于追踪）
+    pub session_id: String,
+    /// 采样率
+    pub sample_rate: Option<u32>,
+    /// 音频分块
+    pub chunks: AudioChunkStream,
+}
+
+impl InferStream {
+    /// 将完整推理结果包装为单分块流
+    pub fn from_response(response: InferResponse) -> Self {
+        let audio_data = response.audio_data;
+        Self {
+            session_id: response.session_id,
+            sample_rate: response.sample_rate,
+            chunks: stream::once(async move { Ok(audio_data) }).boxed(),
+        }
+    }
+}
+
 /// TTS Engine Port
 ///
 /// 外部 TTS 服务的抽象接口
@@ -58,6 +115,26 @@ pub trait TtsEnginePort: Send + Sync {
     /// 发送文本和参考音频到外部 TTS 服务，返回合成的音频数据
     async fn infer(&self, request: InferRequest) -> Result<InferResponse, TtsError>;
 
+    /// 执行流式 TTS 推理
+    ///
+    /// 合成过程中即返回音频分块，长片段无需等待合成完成即可开始播放。
+    /// 默认实现等待整段推理完成后作为单个分块返回
+    async fn infer_stream(&self, request: InferRequest) -> Result<InferStream, TtsError> {
+        self.infer(request).await.map(InferStream::from_response)
+    }
+
+    /// 批量执行 TTS 推理
+    ///
+    /// 响应与请求一一对应；任一请求失败则整批失败。
+    /// 默认实现逐个调用 infer，支持批量的服务可覆盖以减少请求开销
+    async fn infer_batch(&self, requests: Vec<InferRequest>) -> Result<Vec<InferResponse>, TtsError> {
+        let mut responses = Vec::with_capacity(requests.len());
+        for request in requests {
+            responses.push(self.infer(request).await?);
+        }
+        Ok(responses)
+    }
+
     /// 检查 TTS 服务是否可用
     async fn health_check(&self) -> bool {
         true // 默认实现
@@ -0,0 +1,554 @@
+//! 滑动窗口预取
+//!
+//! 播放、跳转、切换音色和上报进度后按会话当前位置计算窗口（[`WindowConfig::window_range`]），
+//! 为窗口内未缓存的片段提交推理任务，并取消已移出窗口的任务（推理中的请求随之中止）。
+//! 窗口大小可按会话设置（不超过配置的上限），自动前进模式的会话向后预取到上限；
+//! 客户端可通过批量预取显式请求窗口外的片段（如离线前预取下一章），这些任务不随窗口移动取消。
+//! 切换音色的过渡期间同时固定旧音色在窗口内的缓存，新音色的当前片段就绪后结束过渡并推送
+//! `VoiceHandoffCompleted`。
+//! 会话长时间没有活动（位置上报、WS 消息等）时暂停其窗口预取，取消窗口内未完成的任务，
+//! 避免后台标签页让 TTS 一直合成没人听的音频；会话再次活动时恢复
+
+use chrono::Utc;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+use crate::application::casting::VoiceCastingResolver;
+use crate::application::error::ApplicationError;
+use crate::application::ports::{
+    generate_cache_key_with_params, AudioCachePort, InferenceTask, NovelRepositoryPort,
+    SessionManagerPort, TaskManagerPort, TaskPriority, TaskState, WindowConfig,
+};
+use crate::infrastructure::events::EventPublisher;
+
+/// 预取设置
+#[derive(Debug, Clone, Copy)]
+pub struct PrefetchSettings {
+    /// 是否按播放位置自动提交推理
+    pub enabled: bool,
+    /// 会话未指定时使用的窗口
+    pub default_window: WindowConfig,
+    /// 会话可设置的窗口上限
+    pub max_window: WindowConfig,
+    /// 单次批量预取的片段数上限
+    pub max_batch: usize,
+    /// 会话无活动多少秒后暂停窗口预取（0 表示不暂停）
+    pub idle_pause_secs: u64,
+}
+
+impl Default for PrefetchSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            default_window: WindowConfig::default(),
+            max_window: WindowConfig::default(),
+            max_batch: 200,
+            idle_pause_secs: 600,
+        }
+    }
+}
+
+/// 一次预取的结果
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PrefetchResult {
+    /// 新提交的任务数
+    pub submitted: usize,
+    /// 因移出窗口取消的任务数
+    pub cancelled: usize,
+}
+
+/// 一次批量预取的结果
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BatchPrefetchResult {
+    /// 新提交的任务数
+    pub submitted: usize,
+    /// 已缓存的片段数
+    pub cached: usize,
+    /// 已在排队或推理中的片段数
+    pub queued: usize,
+    /// 提交后排队等待推理的任务数（所有会话）
+    pub queue_depth: usize,
+}
+
+/// 一次空闲检查的结果
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IdleThrottleResult {
+    /// 新暂停预取的会话数
+    pub paused: usize,
+    /// 恢复预取的会话数
+    pub resumed: usize,
+    /// 取消的任务数
+    pub cancelled: usize,
+}
+
+/// 预取协调器
+pub struct PrefetchCoordinator {
+    session_manager: Arc<dyn SessionManagerPort>,
+    task_manager: Arc<dyn TaskManagerPort>,
+    novel_repo: Arc<dyn NovelRepositoryPort>,
+    audio_cache: Arc<dyn AudioCachePort>,
+    voice_casting: VoiceCastingResolver,
+    event_publisher: Arc<EventPublisher>,
+    settings: PrefetchSettings,
+    /// 因空闲暂停了预取的会话
+    paused: Mutex<HashSet<String>>,
+}
+
+impl PrefetchCoordinator {
+    pub fn new(
+        session_manager: Arc<dyn SessionManagerPort>,
+        task_manager: Arc<dyn TaskManagerPort>,
+        novel_repo: Arc<dyn NovelRepositoryPort>,
+        audio_cache: Arc<dyn AudioCachePort>,
+        voice_casting: VoiceCastingResolver,
+        event_publisher: Arc<EventPublisher>,
+        settings: PrefetchSettings,
+    ) -> Self {
+        Self {
+            session_manager,
+            task_manager,
+            novel_repo,
+            audio_cache,
+            voice_casting,
+            event_publisher,
+            settings,
+            paused: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// 单次批量预取的片段数上限
+    pub fn max_batch(&self) -> usize {
+        self.settings.max_batch
+    }
+
+    /// 解析会话窗口：未指定的一侧使用默认值，超出上限时返回校验错误
+    pub fn resolve_window(
+        &self,
+        before: Option<usize>,
+        after: Option<usize>,
+    ) -> Result<WindowConfig, ApplicationError> {
+        let default = self.settings.default_window;
+        let max = self.settings.max_window;
+        let window = WindowConfig::new(
+            before.unwrap_or(default.before),
+            after.unwrap_or(default.after),
+        );
+        if window.before > max.before {
+            return Err(ApplicationError::validation(format!(
+                "window before must be at most {}",
+                max.before
+            )));
+        }
+        if window.after > max.after {
+            return Err(ApplicationError::validation(format!(
+                "window after must be at most {}",
+                max.after
+            )));
+        }
+        Ok(window)
+    }
+
+    /// 按会话当前位置更新窗口；失败只记录日志（预取不影响触发它的请求）
+    pub async fn refresh(&self, session_id: &str) {
+        if !self.settings.enabled {
+            return;
+        }
+        match self.prefetch(session_id).await {
+            Ok(result) if result != PrefetchResult::default() => tracing::debug!(
+                session_id = %session_id,
+                submitted = result.submitted,
+                cancelled = result.cancelled,
+                "Prefetch window updated"
+            ),
+            Ok(_) => {}
+            Err(e) => tracing::warn!(session_id = %session_id, error = %e, "Prefetch failed"),
+        }
+    }
+
+    /// 暂停空闲会话的窗口预取（取消窗口内未完成的任务，显式预取的除外），
+    /// 恢复已暂停但重新活动的会话；导出、预生成等后台会话不参与
+    pub async fn throttle_idle(&self) -> IdleThrottleResult {
+        let mut result = IdleThrottleResult::default();
+        if !self.settings.enabled || self.settings.idle_pause_secs == 0 {
+            return result;
+        }
+
+        let idle_after = chrono::Duration::seconds(self.settings.idle_pause_secs as i64);
+        let now = Utc::now();
+        let mut resumed = Vec::new();
+        for session_id in self.session_manager.list_all() {
+            let Ok(session) = self.session_manager.get(&session_id) else {
+                continue;
+            };
+            if session.background {
+                continue;
+            }
+            let idle = now - session.last_activity >= idle_after;
+            let mut paused = self.paused.lock().unwrap();
+            if idle && paused.insert(session_id.clone()) {
+                drop(paused);
+                let cancelled = self.task_manager.cancel_window(&session_id);
+                result.paused += 1;
+                result.cancelled += cancelled;
+                tracing::info!(session_id = %session_id, cancelled, "Prefetch paused for idle session");
+            } else if !idle && paused.contains(&session_id) {
+                resumed.push(session_id);
+            }
+        }
+
+        // 仅有 WS 活动、没有位置上报的会话在这里恢复
+        for session_id in resumed {
+            self.refresh(&session_id).await;
+            result.resumed += 1;
+        }
+        // 已关闭的会话
+        self.paused
+            .lock()
+            .unwrap()
+            .retain(|session_id| self.session_manager.is_valid(session_id));
+        result
+    }
+
+    /// 提交窗口内缺失的片段，取消窗口外的 pending 任务（会话暂停了预取时恢复）
+    pub async fn prefetch(&self, session_id: &str) -> Result<PrefetchResult, ApplicationError> {
+        if !self.settings.enabled {
+            return Ok(PrefetchResult::default());
+        }
+        if self.paused.lock().unwrap().remove(session_id) {
+            tracing::info!(session_id = %session_id, "Prefetch resumed");
+        }
+
+        let session = self
+            .session_manager
+            .get(session_id)
+            .map_err(|_| ApplicationError::not_found_str("Session", session_id))?;
+        let novel = self
+            .novel_repo
+            .find_by_id(session.novel_id)
+            .await?
+            .ok_or_else(|| ApplicationError::not_found("Novel", session.novel_id))?;
+        if novel.total_segments == 0 {
+            return Ok(PrefetchResult::default());
+        }
+
+        let current = (session.current_index as usize).min(novel.total_segments - 1);
+        let window = if session.auto_advance {
+            WindowConfig::new(
+                session.window.before,
+                session.window.after.max(self.settings.max_window.after),
+            )
+        } else {
+            session.window
+        };
+        let (start, end) = window.window_range(current, novel.total_segments);
+        let cancelled = self
+            .task_manager
+            .cancel_outside(session_id, start as u32, end as u32);
+
+        // 当前片段优先级最高；同优先级先进先出：先是后面的片段，然后是前面的片段
+        let indices: Vec<u32> = (current..=end)
+            .chain((start..current).rev())
+            .map(|index| index as u32)
+            .collect();
+        let in_flight: Vec<InferenceTask> = self
+            .task_manager
+            .get_tasks_by_session(session_id)
+            .into_iter()
+            .filter(|task| matches!(task.state, TaskState::Pending | TaskState::Inferring))
+            .collect();
+        let segments = self
+            .novel_repo
+            .find_segments_by_indices(session.novel_id, &indices)
+            .await?;
+
+        let mut window_keys = Vec::with_capacity(indices.len());
+        let mut handoff_keys = Vec::new();
+        let mut handoff_completed = false;
+        let mut tasks = Vec::new();
+        for segment_index in indices {
+            let Some(segment) = segments.iter().find(|s| s.index == segment_index as usize) else {
+                continue;
+            };
+            let voice = self
+                .voice_casting
+                .resolve_key(session.novel_id, segment_index, session.voice_id)
+                .await;
+            let params = session.params_for(segment_index);
+            let cache_key = generate_cache_key_with_params(&segment.content, &voice, &params);
+            window_keys.push(cache_key.clone());
+            if let Some((old_voice_id, old_params)) = session.handoff_for(segment_index) {
+                let old_voice = self
+                    .voice_casting
+                    .resolve_key(session.novel_id, segment_index, old_voice_id)
+                    .await;
+                handoff_keys.push(generate_cache_key_with_params(
+                    &segment.content,
+                    &old_voice,
+                    &old_params,
+                ));
+            }
+
+            let priority = TaskPriority::for_segment(segment_index, current as u32);
+            let queued = in_flight.iter().find(|task| {
+                task.segment_index == segment_index
+                    && task.voice_id == session.voice_id
+                    && task.params == params
+            });
+            if let Some(task) = queued {
+                // 跳转到已排队的片段时提前推理
+                self.task_manager.prioritize(&task.task_id, priority);
+                continue;
+            }
+            let cached = self
+                .audio_cache
+                .link(&cache_key, session.novel_id, segment_index, voice.voice_id)
+                .await
+                .unwrap_or(false);
+            if cached && segment_index == current as u32 && session.voice_handoff.is_some() {
+                handoff_completed = true;
+            }
+            if !cached {
+                tasks.push(
+                    InferenceTask::new(
+                        session_id.to_string(),
+                        session.novel_id,
+                        session.voice_id,
+                        segment_index,
+                        segment.content.clone(),
+                    )
+                    .with_params(params)
+                    .with_priority(priority),
+                );
+            }
+        }
+
+        // 新音色赶上当前片段后结束过渡，否则旧音色的条目也需固定
+        if handoff_completed {
+            if self.session_manager.set_voice_handoff(session_id, None).is_ok() {
+                self.event_publisher
+                    .publish_voice_handoff_completed(session_id, session.voice_id, current as u32);
+            }
+        } else {
+            window_keys.extend(handoff_keys);
+        }
+
+        // 固定窗口内的条目，缓存已满时不淘汰即将播放的片段
+        self.audio_cache.pin(session_id, window_keys);
+
+        let submitted = tasks.len();
+        if !tasks.is_empty() {
+            self.task_manager.submit(tasks)?;
+        }
+
+        Ok(PrefetchResult { submitted, cancelled })
+    }
+
+    /// 批量预取指定片段（不受预取开关影响）：提交未缓存且未排队的片段，任务不随窗口移动取消
+    pub async fn prefetch_batch(
+        &self,
+        session_id: &str,
+        segment_indices: &[u32],
+    ) -> Result<BatchPrefetchResult, ApplicationError> {
+        let mut indices = segment_indices.to_vec();
+        indices.sort_unstable();
+        indices.dedup();
+        if indices.is_empty() {
+            return Err(ApplicationError::validation("No segments to prefetch"));
+        }
+        if indices.len() > self.settings.max_batch {
+            return Err(ApplicationError::validation(format!(
+                "Too many segments: at most {} per request",
+                self.settings.max_batch
+            )));
+        }
+
+        let session = self
+            .session_manager
+            .get(session_id)
+            .map_err(|_| ApplicationError::not_found_str("Session", session_id))?;
+        let novel = self
+            .novel_repo
+            .find_by_id(session.novel_id)
+            .await?
+            .ok_or_else(|| ApplicationError::not_found("Novel", session.novel_id))?;
+        if let Some(index) = indices.iter().find(|index| **index as usize >= novel.total_segments) {
+            return Err(ApplicationError::validation(format!(
+                "Invalid segment index: {} (total segments: {})",
+                index, novel.total_segments
+            )));
+        }
+
+        let in_flight: Vec<InferenceTask> = self
+            .task_manager
+            .get_tasks_by_session(session_id)
+            .into_iter()
+            .filter(|task| matches!(task.state, TaskState::Pending | TaskState::Inferring))
+            .collect();
+        let segments = self
+            .novel_repo
+            .find_segments_by_indices(session.novel_id, &indices)
+            .await?;
+
+        let mut result = BatchPrefetchResult::default();
+        let mut tasks = Vec::new();
+        for segment in &segments {
+            let segment_index = segment.index as u32;
+            let voice = self
+                .voice_casting
+                .resolve_key(session.novel_id, segment_index, session.voice_id)
+                .await;
+            let params = session.params_for(segment_index);
+            let cache_key = generate_cache_key_with_params(&segment.content, &voice, &params);
+
+            let queued = in_flight.iter().any(|task| {
+                task.segment_index == segment_index
+                    && task.voice_id == session.voice_id
+                    && task.params == params
+            });
+            if queued {
+                result.queued += 1;
+                continue;
+            }
+            let cached = self
+                .audio_cache
+                .link(&cache_key, session.novel_id, segment_index, voice.voice_id)
+                .await
+                .unwrap_or(false);
+            if cached {
+                result.cached += 1;
+                continue;
+            }
+            tasks.push(
+                InferenceTask::new(
+                    session_id.to_string(),
+                    session.novel_id,
+                    session.voice_id,
+                    segment_index,
+                    segment.content.clone(),
+                )
+                .with_params(params)
+                .as_explicit(),
+            );
+        }
+
+        result.submitted = tasks.len();
+        if !tasks.is_empty() {
+            self.task_manager.submit(tasks)?;
+        }
+        result.queue_depth = self.task_manager.queue_depth();
+
+        tracing::info!(
+            session_id = %session_id,
+            requested = indices.len(),
+            submitted = result.submitted,
+            cached = result.cached,
+            queued = result.queued,
+            "Batch prefetch submitted"
+        );
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::{NovelRecord, NovelStatus, Session, TextSegmentRecord};
+    use crate::infrastructure::memory::TaskQueue;
+    use crate::infrastructure::persistence::sqlite::{
+        create_pool, run_migrations, DatabaseConfig, SqliteNovelRepository, SqliteVoiceCastingRepository,
+        SqliteVoiceRepository,
+    };
+    use crate::infrastructure::{InMemorySessionManager, InMemoryTaskManager, SledAudioCache};
+    use std::path::PathBuf;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_throttle_idle_pauses_and_resumes_window_prefetch() {
+        let pool = create_pool(&DatabaseConfig::in_memory()).await.unwrap();
+        run_migrations(&pool).await.unwrap();
+        let novel_repo = Arc::new(SqliteNovelRepository::new(pool.clone()));
+        let voice_casting = VoiceCastingResolver::new(
+            novel_repo.clone(),
+            Arc::new(SqliteVoiceCastingRepository::new(pool.clone())),
+            Arc::new(SqliteVoiceRepository::new(pool)),
+            false,
+        );
+        let dir = tempfile::tempdir().unwrap();
+        let session_manager = Arc::new(InMemorySessionManager::new());
+        let task_manager = Arc::new(InMemoryTaskManager::new(Arc::new(TaskQueue::new(100))));
+        let prefetch = PrefetchCoordinator::new(
+            session_manager.clone(),
+            task_manager.clone(),
+            novel_repo.clone(),
+            Arc::new(SledAudioCache::open(dir.path().join("cache"), 1024 * 1024).unwrap()),
+            voice_casting,
+            Arc::new(EventPublisher::new()),
+            PrefetchSettings::default(),
+        );
+
+        let novel_id = Uuid::new_v4();
+        novel_repo
+            .save(&NovelRecord {
+                id: novel_id,
+                title: "测试".to_string(),
+                raw_text_path: PathBuf::from("novel.txt"),
+                total_segments: 10,
+                status: NovelStatus::Ready,
+                user_id: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            })
+            .await
+            .unwrap();
+        let segments: Vec<TextSegmentRecord> = (0..10)
+            .map(|index| TextSegmentRecord {
+                id: Uuid::new_v4(),
+                novel_id,
+                index,
+                content: format!("第{}段", index),
+                char_count: 3,
+            })
+            .collect();
+        novel_repo.save_segments(&segments).await.unwrap();
+
+        let idle_pause_secs = PrefetchSettings::default().idle_pause_secs as i64;
+        let idle_since = Utc::now() - chrono::Duration::seconds(idle_pause_secs);
+        let session_id = session_manager
+            .create(Session {
+                last_activity: idle_since,
+                ..Session::new(novel_id, Uuid::new_v4(), 0)
+            })
+            .unwrap();
+        // 后台会话不参与
+        let background_id = session_manager
+            .create(Session {
+                last_activity: idle_since,
+                ..Session::new(novel_id, Uuid::new_v4(), 0).as_background()
+            })
+            .unwrap();
+        for id in [&session_id, &background_id] {
+            assert_eq!(prefetch.prefetch(id).await.unwrap().submitted, 4);
+        }
+        prefetch.prefetch_batch(&session_id, &[8]).await.unwrap();
+
+        // 窗口任务取消，显式预取的任务保留
+        let result = prefetch.throttle_idle().await;
+        assert_eq!((result.paused, result.cancelled, result.resumed), (1, 4, 0));
+        let active = |id: &str| {
+            task_manager
+                .get_tasks_by_session(id)
+                .into_iter()
+                .filter(|task| task.state == TaskState::Pending)
+                .count()
+        };
+        assert_eq!(active(&session_id), 1);
+        assert_eq!(active(&background_id), 4);
+        assert_eq!(prefetch.throttle_idle().await, IdleThrottleResult::default());
+
+        // 重新活动后恢复
+        session_manager.touch(&session_id);
+        let result = prefetch.throttle_idle().await;
+        assert_eq!((result.paused, result.resumed), (0, 1));
+        assert_eq!(active(&session_id), 5);
+    }
+}
@@ -2,19 +2,63 @@
 //!
 //! 基于 ARCHITECTURE.md V2 设计
 
+use bytes::Bytes;
+use std::fmt;
 use uuid::Uuid;
 
+use crate::application::ports::{AudioFormat, PartialAudioStream};
+
 /// 获取音频查询
 #[derive(Debug, Clone)]
 pub struct GetAudioQuery {
     pub novel_id: Uuid,
     pub segment_index: u32,
     pub voice_id: Uuid,
+    /// 所属会话（用于取会话/片段级合成参数计算缓存 key）
+    pub session_id: Option<String>,
+    /// 期望的输出格式（None 表示按缓存格式返回）
+    pub format: Option<AudioFormat>,
+    /// 服务端变速播放速度（None 时使用会话的设置）
+    pub playback_speed: Option<f32>,
+}
+
+/// 音频数据
+pub enum AudioData {
+    /// 已缓存的完整音频（直接引用缓存数据，不复制）
+    Complete(Bytes),
+    /// 合成中的音频（流式推理），边合成边返回
+    Streaming(PartialAudioStream),
+}
+
+impl fmt::Debug for AudioData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Complete(data) => f.debug_tuple("Complete").field(&data.len()).finish(),
+            Self::Streaming(_) => f.write_str("Streaming"),
+        }
+    }
 }
 
 /// 获取音频响应
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct GetAudioResponse {
-    pub audio_data: Vec<u8>,
+    pub audio: AudioData,
     pub content_type: String,
 }
+
+/// 获取片段波形峰值查询（参数同 GetAudioQuery）
+#[derive(Debug, Clone)]
+pub struct GetAudioPeaks {
+    pub novel_id: Uuid,
+    pub segment_index: u32,
+    pub voice_id: Uuid,
+    pub session_id: Option<String>,
+}
+
+/// 波形峰值响应
+#[derive(Debug, Clone)]
+pub struct AudioPeaksResponse {
+    /// 均分时间的峰值（0.0 - 1.0），点数为 WAVEFORM_POINTS
+    pub peaks: Vec<f32>,
+    pub duration_ms: u64,
+}
@@ -0,0 +1,8 @@
+//! Cache Queries - 音频缓存统计
+
+/// 获取音频缓存统计查询
+#[derive(Debug, Clone)]
+pub struct GetCacheStats {
+    /// 返回缓存占用最大的前 N 部小说
+    pub top_novels: usize,
+}
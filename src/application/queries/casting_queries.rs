@@ -0,0 +1,9 @@
+//! Voice Casting Queries - V2 架构
+
+use uuid::Uuid;
+
+/// 获取小说角色配音查询
+#[derive(Debug, Clone)]
+pub struct GetVoiceCasting {
+    pub novel_id: Uuid,
+}
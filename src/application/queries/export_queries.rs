@@ -0,0 +1,7 @@
+//! Export Queries - 导出任务查询
+
+/// 获取有声书导出任务查询
+#[derive(Debug, Clone)]
+pub struct GetExportJob {
+    pub job_id: String,
+}
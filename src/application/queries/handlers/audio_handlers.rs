@@ -1,48 +1,323 @@
 //! Audio Query Handlers - V2 架构
 
-use std::sync::Arc;
+use bytes::Bytes;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
 
+use crate::application::casting::VoiceCastingResolver;
 use crate::application::error::ApplicationError;
-use crate::application::ports::{generate_cache_key, AudioCachePort, NovelRepositoryPort};
-use crate::application::queries::audio_queries::{GetAudioQuery, GetAudioResponse};
+use crate::application::tagging::AudioTagResolver;
+use crate::application::ports::{
+    generate_cache_key_with_params, AudioCachePort, AudioFormat, AudioMetadata, AudioTranscoderPort,
+    CacheEntry,
+    NovelRepositoryPort, PartialAudioPort, Session, SessionManagerPort, TranscodeConfig,
+    PLAYBACK_SPEED_RANGE, WAVEFORM_POINTS,
+};
+use crate::application::queries::audio_queries::{
+    AudioData, AudioPeaksResponse, GetAudioPeaks, GetAudioQuery, GetAudioResponse,
+};
+
+/// 按需转码结果缓存的条目数
+const TRANSCODED_CACHE_CAPACITY: usize = 32;
+
+/// 转码结果条目：(缓存 key, 格式, 播放速度, 音频数据)
+type TranscodedEntry = (String, AudioFormat, Option<f32>, Bytes);
+
+/// 按需转码结果的小型 LRU 缓存（按缓存 key + 格式 + 播放速度）
+#[derive(Default)]
+struct TranscodedCache {
+    entries: Mutex<VecDeque<TranscodedEntry>>,
+}
+
+impl TranscodedCache {
+    fn get(&self, cache_key: &str, format: AudioFormat, tempo: Option<f32>) -> Option<Bytes> {
+        let mut entries = self.entries.lock().unwrap();
+        let index = entries
+            .iter()
+            .position(|(key, f, t, _)| key == cache_key && *f == format && *t == tempo)?;
+        let entry = entries.remove(index)?;
+        let data = entry.3.clone();
+        entries.push_back(entry);
+        Some(data)
+    }
+
+    fn insert(&self, cache_key: &str, format: AudioFormat, tempo: Option<f32>, data: Bytes) {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= TRANSCODED_CACHE_CAPACITY {
+            entries.pop_front();
+        }
+        entries.push_back((cache_key.to_string(), format, tempo, data));
+    }
+}
+
+/// 片段音频的缓存 key 解析
+#[derive(Clone)]
+struct SegmentCacheKeys {
+    novel_repo: Arc<dyn NovelRepositoryPort>,
+    session_manager: Arc<dyn SessionManagerPort>,
+    voice_casting: VoiceCastingResolver,
+}
+
+impl SegmentCacheKeys {
+    /// 计算缓存 key（按角色配音解析后的实际音色和会话/片段参数），同时返回会话
+    ///
+    /// 会话已过期时按默认参数计算
+    async fn resolve(
+        &self,
+        novel_id: Uuid,
+        segment_index: u32,
+        voice_id: Uuid,
+        session_id: Option<&str>,
+    ) -> Result<(String, Option<Session>), ApplicationError> {
+        let segment = self
+            .novel_repo
+            .find_segment(novel_id, segment_index as usize)
+            .await?
+            .ok_or_else(|| {
+                ApplicationError::validation(format!("Segment not found: {}:{}", novel_id, segment_index))
+            })?;
+
+        let voice = self.voice_casting.resolve_key(novel_id, segment_index, voice_id).await;
+        let session = session_id.and_then(|id| self.session_manager.get(id).ok());
+        let params = session
+            .as_ref()
+            .map(|session| session.params_for(segment_index))
+            .unwrap_or_default();
+
+        Ok((generate_cache_key_with_params(&segment.content, &voice, &params), session))
+    }
+
+    /// 切换音色过渡中旧音色的缓存 key（请求的是会话当前音色时）
+    async fn resolve_handoff(
+        &self,
+        novel_id: Uuid,
+        segment_index: u32,
+        voice_id: Uuid,
+        session: &Session,
+    ) -> Result<Option<String>, ApplicationError> {
+        if session.novel_id != novel_id || session.voice_id != voice_id {
+            return Ok(None);
+        }
+        let Some((old_voice_id, params)) = session.handoff_for(segment_index) else {
+            return Ok(None);
+        };
+        let Some(segment) = self.novel_repo.find_segment(novel_id, segment_index as usize).await? else {
+            return Ok(None);
+        };
+        let old_voice = self.voice_casting.resolve_key(novel_id, segment_index, old_voice_id).await;
+        Ok(Some(generate_cache_key_with_params(&segment.content, &old_voice, &params)))
+    }
+}
 
 /// GetAudio Handler - 获取音频数据
+///
+/// 请求的格式与缓存格式不同或指定了播放速度时，从缓存的 WAV 按需转码；
+/// 会话处于切换音色的过渡中且新音色尚未合成时，返回旧音色已就绪的音频
 pub struct GetAudioHandler {
     audio_cache: Arc<dyn AudioCachePort>,
-    novel_repo: Arc<dyn NovelRepositoryPort>,
+    partial_audio: Arc<dyn PartialAudioPort>,
+    cache_keys: SegmentCacheKeys,
+    audio_transcoder: Arc<dyn AudioTranscoderPort>,
+    audio_tags: AudioTagResolver,
+    /// 按需转码的参数（格式按请求替换）
+    transcode_config: TranscodeConfig,
+    transcoded: TranscodedCache,
 }
 
 impl GetAudioHandler {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         audio_cache: Arc<dyn AudioCachePort>,
+        partial_audio: Arc<dyn PartialAudioPort>,
         novel_repo: Arc<dyn NovelRepositoryPort>,
+        session_manager: Arc<dyn SessionManagerPort>,
+        voice_casting: VoiceCastingResolver,
+        audio_transcoder: Arc<dyn AudioTranscoderPort>,
+        audio_tags: AudioTagResolver,
+        transcode_config: TranscodeConfig,
     ) -> Self {
         Self {
             audio_cache,
-            novel_repo,
+            partial_audio,
+            cache_keys: SegmentCacheKeys {
+                novel_repo,
+                session_manager,
+                voice_casting,
+            },
+            audio_transcoder,
+            audio_tags,
+            transcode_config,
+            transcoded: TranscodedCache::default(),
         }
     }
 
     pub async fn handle(&self, query: GetAudioQuery) -> Result<GetAudioResponse, ApplicationError> {
-        // 获取片段内容
-        let segment = self
-            .novel_repo
-            .find_segment(query.novel_id, query.segment_index as usize)
-            .await?
-            .ok_or_else(|| {
-                ApplicationError::validation(format!(
-                    "Segment not found: {}:{}",
-                    query.novel_id, query.segment_index
-                ))
-            })?;
+        if let Some(speed) = query.playback_speed.filter(|s| !PLAYBACK_SPEED_RANGE.contains(s)) {
+            return Err(ApplicationError::validation(format!(
+                "Invalid speed: {} (allowed: {:?})",
+                speed, PLAYBACK_SPEED_RANGE
+            )));
+        }
 
-        // 计算缓存 key
-        let cache_key = generate_cache_key(&segment.content, &query.voice_id);
+        let (cache_key, session) = self
+            .cache_keys
+            .resolve(
+                query.novel_id,
+                query.segment_index,
+                query.voice_id,
+                query.session_id.as_deref(),
+            )
+            .await?;
+
+        // 原速不伸缩
+        let tempo = query
+            .playback_speed
+            .or_else(|| session.as_ref().and_then(|session| session.playback_speed))
+            .filter(|&speed| speed != 1.0);
 
         // 从缓存获取音频
-        let audio_data = self
+        if let Some(entry) = self.cached_audio(&cache_key).await? {
+            return Ok(self.negotiate(&cache_key, entry, query.format, tempo).await);
+        }
+
+        // 流式推理中：返回已合成部分并持续输出后续分块（始终为 WAV、原速）
+        if let Some(stream) = self.partial_audio.subscribe(&cache_key) {
+            return Ok(GetAudioResponse {
+                audio: AudioData::Streaming(stream),
+                content_type: "audio/wav".to_string(),
+            });
+        }
+
+        // 查询缓存与订阅之间合成可能恰好完成，再查一次缓存
+        if let Some(entry) = self.cached_audio(&cache_key).await? {
+            return Ok(self.negotiate(&cache_key, entry, query.format, tempo).await);
+        }
+
+        // 切换音色过渡中：新音色尚未合成，返回旧音色已就绪的音频
+        if let Some(session) = &session {
+            let handoff_key = self
+                .cache_keys
+                .resolve_handoff(query.novel_id, query.segment_index, query.voice_id, session)
+                .await?;
+            if let Some(handoff_key) = handoff_key {
+                if let Some(entry) = self.cached_audio(&handoff_key).await? {
+                    return Ok(self.negotiate(&handoff_key, entry, query.format, tempo).await);
+                }
+            }
+        }
+
+        Err(ApplicationError::validation(format!(
+            "Audio not found: novel={}, segment={}, voice={}",
+            query.novel_id, query.segment_index, query.voice_id
+        )))
+    }
+
+    /// 按请求格式和播放速度返回缓存音频
+    ///
+    /// 仅 WAV 可按需转码；格式不支持时保持缓存格式，转码失败时按缓存格式原速返回
+    /// （Content-Type 反映实际格式）
+    async fn negotiate(
+        &self,
+        cache_key: &str,
+        entry: CacheEntry,
+        format: Option<AudioFormat>,
+        tempo: Option<f32>,
+    ) -> GetAudioResponse {
+        let stored = entry.metadata.format;
+        if stored != AudioFormat::Wav {
+            return complete(entry.audio_data, stored);
+        }
+        let target = format
+            .filter(|&target| self.audio_transcoder.supports_format(target))
+            .unwrap_or(stored);
+        if target == stored && tempo.is_none() {
+            return complete(entry.audio_data, stored);
+        }
+
+        if let Some(data) = self.transcoded.get(cache_key, target, tempo) {
+            return complete(data, target);
+        }
+
+        let config = TranscodeConfig {
+            format: target,
+            tempo,
+            ..self.transcode_config.clone()
+        };
+        // WAV 输出不写标签，无需查询
+        let metadata = if target == AudioFormat::Wav {
+            AudioMetadata::default()
+        } else {
+            let meta = &entry.metadata;
+            self.audio_tags.resolve(meta.novel_id, meta.segment_index, meta.voice_id).await
+        };
+        match self
+            .audio_transcoder
+            .transcode_with_metadata(&entry.audio_data, &config, &metadata)
+            .await
+        {
+            Ok(result) => {
+                let audio_data = Bytes::from(result.audio_data);
+                self.transcoded.insert(cache_key, result.format, tempo, audio_data.clone());
+                complete(audio_data, result.format)
+            }
+            Err(e) => {
+                tracing::warn!(cache_key = %cache_key, format = %target, error = %e, "On-demand transcode failed");
+                complete(entry.audio_data, stored)
+            }
+        }
+    }
+
+    async fn cached_audio(&self, cache_key: &str) -> Result<Option<CacheEntry>, ApplicationError> {
+        self.audio_cache
+            .get_entry(cache_key)
+            .await
+            .map_err(|e| ApplicationError::internal(e.to_string()))
+    }
+}
+
+/// GetAudioPeaks Handler - 获取片段波形峰值
+///
+/// 峰值在合成完成时计算；缺失时（较早的缓存）从缓存的 WAV 补算
+pub struct GetAudioPeaksHandler {
+    audio_cache: Arc<dyn AudioCachePort>,
+    cache_keys: SegmentCacheKeys,
+    audio_transcoder: Arc<dyn AudioTranscoderPort>,
+}
+
+impl GetAudioPeaksHandler {
+    pub fn new(
+        audio_cache: Arc<dyn AudioCachePort>,
+        novel_repo: Arc<dyn NovelRepositoryPort>,
+        session_manager: Arc<dyn SessionManagerPort>,
+        voice_casting: VoiceCastingResolver,
+        audio_transcoder: Arc<dyn AudioTranscoderPort>,
+    ) -> Self {
+        Self {
+            audio_cache,
+            cache_keys: SegmentCacheKeys {
+                novel_repo,
+                session_manager,
+                voice_casting,
+            },
+            audio_transcoder,
+        }
+    }
+
+    pub async fn handle(&self, query: GetAudioPeaks) -> Result<AudioPeaksResponse, ApplicationError> {
+        let (cache_key, _) = self
+            .cache_keys
+            .resolve(
+                query.novel_id,
+                query.segment_index,
+                query.voice_id,
+                query.session_id.as_deref(),
+            )
+            .await?;
+
+        let entry = self
             .audio_cache
-            .get(&cache_key)
+            .get_entry(&cache_key)
             .await
             .map_err(|e| ApplicationError::internal(e.to_string()))?
             .ok_or_else(|| {
@@ -52,9 +327,48 @@ impl GetAudioHandler {
                 ))
             })?;
 
-        Ok(GetAudioResponse {
-            audio_data,
-            content_type: "audio/wav".to_string(),
+        let peaks = match self
+            .audio_cache
+            .get_peaks(&cache_key)
+            .await
+            .map_err(|e| ApplicationError::internal(e.to_string()))?
+        {
+            Some(peaks) => peaks,
+            None => self.backfill(&cache_key, &entry)?,
+        };
+
+        Ok(AudioPeaksResponse {
+            peaks,
+            duration_ms: entry.metadata.duration_ms,
         })
     }
+
+    fn backfill(&self, cache_key: &str, entry: &CacheEntry) -> Result<Vec<f32>, ApplicationError> {
+        if entry.metadata.format != AudioFormat::Wav {
+            return Err(ApplicationError::invalid_state(format!(
+                "Waveform not available for cached {} audio",
+                entry.metadata.format
+            )));
+        }
+        let peaks = self
+            .audio_transcoder
+            .peaks(&entry.audio_data, WAVEFORM_POINTS)
+            .map_err(|e| ApplicationError::internal(e.to_string()))?;
+
+        let audio_cache = self.audio_cache.clone();
+        let (cache_key, stored) = (cache_key.to_string(), peaks.clone());
+        tokio::spawn(async move {
+            if let Err(e) = audio_cache.put_peaks(&cache_key, &stored).await {
+                tracing::warn!(cache_key = %cache_key, error = %e, "Failed to cache waveform peaks");
+            }
+        });
+        Ok(peaks)
+    }
+}
+
+fn complete(audio_data: Bytes, format: AudioFormat) -> GetAudioResponse {
+    GetAudioResponse {
+        audio: AudioData::Complete(audio_data),
+        content_type: format.content_type().to_string(),
+    }
 }
@@ -0,0 +1,156 @@
+//! Cache Query Handlers - 音频缓存统计
+
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::error::ApplicationError;
+use crate::application::ports::{AudioCachePort, CacheStats, NovelRepositoryPort};
+use crate::application::queries::GetCacheStats;
+
+/// 单部小说的缓存占用视图
+#[derive(Debug, Clone)]
+pub struct NovelCacheUsageView {
+    pub novel_id: Uuid,
+    /// None 表示小说已删除（缓存尚未清除）
+    pub title: Option<String>,
+    pub entries: usize,
+    pub size_bytes: u64,
+}
+
+/// 音频缓存统计视图
+#[derive(Debug, Clone)]
+pub struct CacheStatsView {
+    pub stats: CacheStats,
+    /// 命中率（尚无读取时为 None）
+    pub hit_rate: Option<f64>,
+    /// 空间放大率：磁盘占用 / 条目总大小（后端不报告磁盘占用或缓存为空时为 None）
+    pub space_amplification: Option<f64>,
+    pub top_novels: Vec<NovelCacheUsageView>,
+}
+
+/// GetCacheStats Handler
+pub struct GetCacheStatsHandler {
+    audio_cache: Arc<dyn AudioCachePort>,
+    novel_repo: Arc<dyn NovelRepositoryPort>,
+}
+
+impl GetCacheStatsHandler {
+    pub fn new(audio_cache: Arc<dyn AudioCachePort>, novel_repo: Arc<dyn NovelRepositoryPort>) -> Self {
+        Self {
+            audio_cache,
+            novel_repo,
+        }
+    }
+
+    pub async fn handle(&self, query: GetCacheStats) -> Result<CacheStatsView, ApplicationError> {
+        let stats = self.audio_cache.stats().await;
+        let lookups = stats.hit_count + stats.miss_count;
+        let hit_rate = (lookups > 0).then(|| stats.hit_count as f64 / lookups as f64);
+        let space_amplification = stats
+            .disk_size_bytes
+            .filter(|_| stats.total_size_bytes > 0)
+            .map(|disk| disk as f64 / stats.total_size_bytes as f64);
+
+        let usage = self
+            .audio_cache
+            .usage_by_novel()
+            .await
+            .map_err(|e| ApplicationError::StorageError(e.to_string()))?;
+
+        let mut top_novels = Vec::with_capacity(query.top_novels.min(usage.len()));
+        for novel in usage.into_iter().take(query.top_novels) {
+            let title = self
+                .novel_repo
+                .find_by_id(novel.novel_id)
+                .await?
+                .map(|record| record.title);
+            top_novels.push(NovelCacheUsageView {
+                novel_id: novel.novel_id,
+                title,
+                entries: novel.entries,
+                size_bytes: novel.size_bytes,
+            });
+        }
+
+        Ok(CacheStatsView {
+            stats,
+            hit_rate,
+            space_amplification,
+            top_novels,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::{AudioFormat, CacheMetadata, NovelRecord, NovelStatus};
+    use crate::infrastructure::persistence::sqlite::{
+        create_pool, run_migrations, DatabaseConfig, SqliteNovelRepository,
+    };
+    use crate::infrastructure::SledAudioCache;
+    use chrono::Utc;
+    use std::path::PathBuf;
+
+    fn metadata(novel_id: Uuid) -> CacheMetadata {
+        CacheMetadata {
+            novel_id,
+            segment_index: 0,
+            voice_id: Uuid::new_v4(),
+            content_hash: "hash".to_string(),
+            duration_ms: 100,
+            sample_rate: None,
+            format: AudioFormat::Wav,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cache_stats_top_novels() {
+        let pool = create_pool(&DatabaseConfig::in_memory()).await.unwrap();
+        run_migrations(&pool).await.unwrap();
+        let novel_repo = Arc::new(SqliteNovelRepository::new(pool));
+        let dir = tempfile::tempdir().unwrap();
+        let audio_cache = Arc::new(SledAudioCache::open(dir.path().join("cache"), 1024 * 1024).unwrap());
+        let handler = GetCacheStatsHandler::new(audio_cache.clone(), novel_repo.clone());
+
+        let view = handler.handle(GetCacheStats { top_novels: 5 }).await.unwrap();
+        assert_eq!(view.hit_rate, None);
+        assert_eq!(view.space_amplification, None);
+        assert!(view.top_novels.is_empty());
+
+        let novel_id = Uuid::new_v4();
+        novel_repo
+            .save(&NovelRecord {
+                id: novel_id,
+                title: "测试".to_string(),
+                raw_text_path: PathBuf::from("novel.txt"),
+                total_segments: 1,
+                status: NovelStatus::Ready,
+                user_id: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            })
+            .await
+            .unwrap();
+        // 已删除的小说（缓存尚未清除）占用更多
+        let deleted_id = Uuid::new_v4();
+        audio_cache.put("a", vec![1, 2], metadata(novel_id)).await.unwrap();
+        audio_cache.put("b", vec![1, 2, 3, 4], metadata(deleted_id)).await.unwrap();
+        audio_cache.put("c", vec![1, 2, 3, 4], metadata(Uuid::new_v4())).await.unwrap();
+        audio_cache.get("a").await.unwrap();
+        audio_cache.get("missing").await.unwrap();
+        audio_cache.get("missing").await.unwrap();
+
+        let view = handler.handle(GetCacheStats { top_novels: 2 }).await.unwrap();
+        assert_eq!(view.stats.total_entries, 3);
+        assert_eq!(view.hit_rate, Some(1.0 / 3.0));
+        let disk_size_bytes = view.stats.disk_size_bytes.unwrap();
+        assert_eq!(view.space_amplification, Some(disk_size_bytes as f64 / 10.0));
+        assert_eq!(view.top_novels.len(), 2);
+        assert!(view.top_novels.iter().all(|n| n.size_bytes == 4 && n.title.is_none()));
+
+        let view = handler.handle(GetCacheStats { top_novels: 3 }).await.unwrap();
+        let last = &view.top_novels[2];
+        assert_eq!((last.novel_id, last.title.as_deref(), last.entries), (novel_id, Some("测试"), 1));
+    }
+}
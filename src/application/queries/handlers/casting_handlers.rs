@@ -0,0 +1,100 @@
+//! Voice Casting Query Handlers - V2 架构
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::error::ApplicationError;
+use crate::application::ports::{NovelRepositoryPort, VoiceCastingRepositoryPort};
+use crate::application::queries::GetVoiceCasting;
+use crate::domain::novel::detect_speaker;
+
+/// 角色配音条目
+#[derive(Debug, Clone)]
+pub struct VoiceCastingItem {
+    pub character_name: String,
+    pub voice_id: Uuid,
+    pub updated_at: String,
+}
+
+/// 小说中识别出的角色
+#[derive(Debug, Clone)]
+pub struct DetectedCharacter {
+    pub name: String,
+    /// 识别为该角色的对话片段数
+    pub dialogue_count: usize,
+}
+
+/// 角色配音视图
+#[derive(Debug, Clone)]
+pub struct VoiceCastingView {
+    pub castings: Vec<VoiceCastingItem>,
+    /// 按对话片段数降序
+    pub detected_characters: Vec<DetectedCharacter>,
+}
+
+/// GetVoiceCasting Handler
+pub struct GetVoiceCastingHandler {
+    casting_repo: Arc<dyn VoiceCastingRepositoryPort>,
+    novel_repo: Arc<dyn NovelRepositoryPort>,
+}
+
+impl GetVoiceCastingHandler {
+    pub fn new(
+        casting_repo: Arc<dyn VoiceCastingRepositoryPort>,
+        novel_repo: Arc<dyn NovelRepositoryPort>,
+    ) -> Self {
+        Self {
+            casting_repo,
+            novel_repo,
+        }
+    }
+
+    pub async fn handle(&self, query: GetVoiceCasting) -> Result<VoiceCastingView, ApplicationError> {
+        let novel_id = query.novel_id;
+        self.novel_repo
+            .find_by_id(novel_id)
+            .await?
+            .ok_or_else(|| ApplicationError::not_found("Novel", novel_id))?;
+
+        let castings = self
+            .casting_repo
+            .find_by_novel(novel_id)
+            .await?
+            .into_iter()
+            .map(|c| VoiceCastingItem {
+                character_name: c.character_name,
+                voice_id: c.voice_id,
+                updated_at: c.updated_at.to_rfc3339(),
+            })
+            .collect();
+
+        let segments = self.novel_repo.find_segments_by_novel_id(novel_id).await?;
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for (i, segment) in segments.iter().enumerate() {
+            let previous = i.checked_sub(1).map(|p| segments[p].content.as_str());
+            let next = segments.get(i + 1).map(|s| s.content.as_str());
+            if let Some(name) = detect_speaker(previous, &segment.content, next) {
+                *counts.entry(name).or_default() += 1;
+            }
+        }
+
+        let mut detected_characters: Vec<DetectedCharacter> = counts
+            .into_iter()
+            .map(|(name, dialogue_count)| DetectedCharacter {
+                name,
+                dialogue_count,
+            })
+            .collect();
+        detected_characters.sort_by(|a, b| {
+            b.dialogue_count
+                .cmp(&a.dialogue_count)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        Ok(VoiceCastingView {
+            castings,
+            detected_characters,
+        })
+    }
+}
@@ -0,0 +1,24 @@
+//! Export Query Handlers - 导出任务查询
+
+use std::sync::Arc;
+
+use crate::application::error::ApplicationError;
+use crate::application::ports::{ExportJob, ExportJobManagerPort};
+use crate::application::queries::GetExportJob;
+
+/// GetExportJob Handler
+pub struct GetExportJobHandler {
+    export_jobs: Arc<dyn ExportJobManagerPort>,
+}
+
+impl GetExportJobHandler {
+    pub fn new(export_jobs: Arc<dyn ExportJobManagerPort>) -> Self {
+        Self { export_jobs }
+    }
+
+    pub fn handle(&self, query: GetExportJob) -> Result<ExportJob, ApplicationError> {
+        self.export_jobs
+            .get(&query.job_id)
+            .ok_or_else(|| ApplicationError::not_found_str("ExportJob", &query.job_id))
+    }
+}
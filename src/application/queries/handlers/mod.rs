@@ -3,9 +3,25 @@
 //! 所有 QueryHandler 的具体实现
 
 mod audio_handlers;
+mod cache_handlers;
+mod casting_handlers;
+mod export_handlers;
 mod novel_handlers;
+mod pregeneration_handlers;
+mod session_handlers;
+mod settings_handlers;
+mod stats_handlers;
+mod tts_handlers;
 mod voice_handlers;
 
 pub use audio_handlers::*;
+pub use cache_handlers::*;
+pub use casting_handlers::*;
+pub use export_handlers::*;
 pub use novel_handlers::*;
+pub use pregeneration_handlers::*;
+pub use session_handlers::*;
+pub use settings_handlers::*;
+pub use stats_handlers::*;
+pub use tts_handlers::*;
 pub use voice_handlers::*;
@@ -4,8 +4,13 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::application::error::ApplicationError;
+use crate::application::pagination::Page;
 use crate::application::ports::{NovelRecord, NovelRepositoryPort, TextSegmentRecord};
-use crate::application::queries::{GetNovel, GetNovelSegments, ListNovels};
+use crate::application::queries::{
+    GetNovel, GetNovelChapters, GetNovelSegments, ListNovels, PreviewSegments,
+};
+use crate::domain::novel::{detect_chapters, Chapter};
+use crate::domain::{segment_text, SegmentConfig};
 
 // ============================================================================
 // Response DTOs
@@ -51,6 +56,27 @@ impl From<TextSegmentRecord> for TextSegmentResponse {
     }
 }
 
+/// 章节响应
+#[derive(Debug, Clone)]
+pub struct ChapterResponse {
+    pub number: usize,
+    pub title: String,
+    pub start_segment_index: usize,
+    /// 结束片段索引（不包含）
+    pub end_segment_index: usize,
+}
+
+impl From<Chapter> for ChapterResponse {
+    fn from(chapter: Chapter) -> Self {
+        Self {
+            number: chapter.number(),
+            title: chapter.title().to_string(),
+            start_segment_index: chapter.start_segment_index(),
+            end_segment_index: chapter.end_segment_index(),
+        }
+    }
+}
+
 // ============================================================================
 // Handlers
 // ============================================================================
@@ -86,9 +112,16 @@ impl ListNovelsHandler {
         Self { novel_repo }
     }
 
-    pub async fn handle(&self, _query: ListNovels) -> Result<Vec<NovelResponse>, ApplicationError> {
-        let novels = self.novel_repo.find_all().await?;
-        Ok(novels.into_iter().map(NovelResponse::from).collect())
+    /// 按创建时间从新到旧分页
+    pub async fn handle(&self, query: ListNovels) -> Result<Page<NovelResponse>, ApplicationError> {
+        query.page.validate()?;
+        let total = self.novel_repo.count(query.user_id).await?;
+        let novels = self
+            .novel_repo
+            .find_page(query.user_id, query.page.offset, query.page.limit)
+            .await?;
+
+        Ok(Page::new(novels, total as usize, query.page).map(NovelResponse::from))
     }
 }
 
@@ -105,22 +138,114 @@ impl GetNovelSegmentsHandler {
     pub async fn handle(
         &self,
         query: GetNovelSegments,
-    ) -> Result<Vec<TextSegmentResponse>, ApplicationError> {
+    ) -> Result<Page<TextSegmentResponse>, ApplicationError> {
+        query.page.validate()?;
         // 验证小说存在
         self.novel_repo
             .find_by_id(query.novel_id)
             .await?
             .ok_or_else(|| ApplicationError::not_found("Novel", query.novel_id))?;
 
-        // 分页查询
-        let offset = query.start_index.unwrap_or(0);
-        let limit = query.limit.unwrap_or(100);
-
+        let total = self.novel_repo.count_segments(query.novel_id).await?;
         let segments = self
             .novel_repo
-            .find_segments_paginated(query.novel_id, offset, limit)
+            .find_segments_paginated(query.novel_id, query.page.offset, query.page.limit)
             .await?;
 
-        Ok(segments.into_iter().map(TextSegmentResponse::from).collect())
+        Ok(Page::new(segments, total as usize, query.page).map(TextSegmentResponse::from))
+    }
+}
+
+/// GetNovelChapters Handler
+pub struct GetNovelChaptersHandler {
+    novel_repo: Arc<dyn NovelRepositoryPort>,
+}
+
+impl GetNovelChaptersHandler {
+    pub fn new(novel_repo: Arc<dyn NovelRepositoryPort>) -> Self {
+        Self { novel_repo }
+    }
+
+    pub async fn handle(
+        &self,
+        query: GetNovelChapters,
+    ) -> Result<Vec<ChapterResponse>, ApplicationError> {
+        self.novel_repo
+            .find_by_id(query.novel_id)
+            .await?
+            .ok_or_else(|| ApplicationError::not_found("Novel", query.novel_id))?;
+
+        let segments = self.novel_repo.find_segments_by_novel_id(query.novel_id).await?;
+        let chapters = detect_chapters(segments.iter().map(|s| s.content.as_str()));
+
+        Ok(chapters.into_iter().map(ChapterResponse::from).collect())
+    }
+}
+
+/// 分段预览的文本长度上限（字符数）：预览用于调整参数，取一部分文本即可
+const MAX_PREVIEW_CHARS: usize = 100_000;
+
+/// PreviewSegments Handler - 仅执行分段，不写入数据库
+#[derive(Default)]
+pub struct PreviewSegmentsHandler;
+
+impl PreviewSegmentsHandler {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn handle(
+        &self,
+        query: PreviewSegments,
+    ) -> Result<Vec<TextSegmentResponse>, ApplicationError> {
+        if query.text.trim().is_empty() {
+            return Err(ApplicationError::validation("Text cannot be empty"));
+        }
+        if query.text.chars().nth(MAX_PREVIEW_CHARS).is_some() {
+            return Err(ApplicationError::validation(format!(
+                "Text is too long for preview (maximum {} characters)",
+                MAX_PREVIEW_CHARS
+            )));
+        }
+
+        let mut config = SegmentConfig::default();
+        if let Some(min_chars) = query.min_chars {
+            config.min_chars = min_chars;
+        }
+
+        let segments = segment_text(&query.text, &config)
+            .into_iter()
+            .enumerate()
+            .map(|(index, content)| TextSegmentResponse {
+                index,
+                char_count: content.chars().count(),
+                content,
+            })
+            .collect();
+
+        Ok(segments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preview(text: String) -> Result<Vec<TextSegmentResponse>, ApplicationError> {
+        PreviewSegmentsHandler::new().handle(PreviewSegments { text, min_chars: None })
+    }
+
+    #[test]
+    fn test_preview_segments_text_length() {
+        let segments = preview("第一句话。第二句话。".to_string()).unwrap();
+        assert!(!segments.is_empty());
+        assert_eq!(segments[0].index, 0);
+
+        assert!(matches!(preview("  ".to_string()), Err(ApplicationError::ValidationError(_))));
+
+        // 上限按字符数计算
+        let text = "字".repeat(MAX_PREVIEW_CHARS);
+        assert!(preview(text.clone()).is_ok());
+        assert!(matches!(preview(text + "字"), Err(ApplicationError::ValidationError(_))));
     }
 }
@@ -0,0 +1,24 @@
+//! Pregeneration Query Handlers - 预生成任务查询
+
+use std::sync::Arc;
+
+use crate::application::error::ApplicationError;
+use crate::application::ports::{PregenerationJob, PregenerationJobManagerPort};
+use crate::application::queries::GetPregenerationJob;
+
+/// GetPregenerationJob Handler
+pub struct GetPregenerationJobHandler {
+    pregeneration_jobs: Arc<dyn PregenerationJobManagerPort>,
+}
+
+impl GetPregenerationJobHandler {
+    pub fn new(pregeneration_jobs: Arc<dyn PregenerationJobManagerPort>) -> Self {
+        Self { pregeneration_jobs }
+    }
+
+    pub fn handle(&self, query: GetPregenerationJob) -> Result<PregenerationJob, ApplicationError> {
+        self.pregeneration_jobs
+            .get(&query.job_id)
+            .ok_or_else(|| ApplicationError::not_found_str("PregenerationJob", &query.job_id))
+    }
+}
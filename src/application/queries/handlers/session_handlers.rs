@@ -1,347 +1,464 @@
-//! Session Query Handlers
+//! Session Query Handlers - 播放会话查询
+//!
+//! 会话状态以内存为准（SessionManager），片段就绪状态见 [`SegmentStatusResolver`]
 
-use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use std::cmp::Reverse;
 use std::sync::Arc;
 use uuid::Uuid;
 
+use crate::application::access::can_access;
 use crate::application::error::ApplicationError;
+use crate::application::pagination::Page;
 use crate::application::ports::{
-    AudioSegmentRecord, AudioSegmentRepositoryPort, AudioStoragePort, SessionRecord,
-    SessionRepositoryPort,
+    NovelRepositoryPort, Session, SessionManagerPort, SessionState, TaskManagerPort, TaskState,
+    WindowConfig,
 };
-use crate::application::queries::{GetPlaybackSession, ListActiveSessions, Query, QueryHandler};
+use crate::application::queries::{
+    GetSessionSegments, GetSessionSnapshot, ListActiveSessions, ListSessions,
+};
+use crate::application::readiness::{SegmentStatus, SegmentStatusResolver};
+use crate::application::snapshot::SessionSnapshot;
+
+/// 最近多少秒内有活动（播放、跳转、上报进度等）的会话视为播放中
+const SESSION_ACTIVE_SECS: i64 = 60;
 
-// ============================================================================
-// Response DTOs
-// ============================================================================
+/// 一次查询的片段数上限
+const MAX_SEGMENT_RANGE: u32 = 200;
 
-/// 会话详情响应
+/// 会话视图
 #[derive(Debug, Clone)]
-pub struct SessionResponse {
-    pub id: Uuid,
+pub struct SessionView {
+    pub id: String,
     pub novel_id: Uuid,
     pub voice_id: Uuid,
-    pub current_index: usize,
-    pub state: String,
-    pub window_before: usize,
-    pub window_after: usize,
-    pub created_at: String,
-    pub updated_at: String,
+    /// 会话所属用户（未启用账号时为 None）
+    pub user_id: Option<Uuid>,
+    pub current_index: u32,
+    pub offset_ms: u32,
+    /// Playing：最近有活动或有进行中的推理任务；否则为 Idle
+    pub state: SessionState,
+    /// 导出/预生成专用的后台会话
+    pub background: bool,
+    /// 自动前进模式
+    pub auto_advance: bool,
+    pub window: WindowConfig,
+    pub pending_tasks: usize,
+    pub inferring_tasks: usize,
+    pub created_at: DateTime<Utc>,
+    pub last_activity: DateTime<Utc>,
 }
 
-impl From<SessionRecord> for SessionResponse {
-    fn from(record: SessionRecord) -> Self {
-        Self {
-            id: record.id,
-            novel_id: record.novel_id,
-            voice_id: record.voice_id,
-            current_index: record.current_index,
-            state: record.state.as_str().to_string(),
-            window_before: record.window_config.before,
-            window_after: record.window_config.after,
-            created_at: record.created_at.to_rfc3339(),
-            updated_at: record.updated_at.to_rfc3339(),
-        }
-    }
+/// 会话片段状态
+#[derive(Debug, Clone)]
+pub struct SessionSegmentsView {
+    pub session_id: String,
+    pub current_index: u32,
+    pub start: u32,
+    pub end: u32,
+    pub segments: Vec<SegmentStatus>,
 }
 
-/// 音频段落状态响应
-#[derive(Debug, Clone)]
-pub struct AudioSegmentStatusResponse {
-    pub segment_index: usize,
-    pub state: String,
-    pub duration_ms: Option<u32>,
-    pub error_message: Option<String>,
+/// ListSessions Handler
+pub struct ListSessionsHandler {
+    session_manager: Arc<dyn SessionManagerPort>,
+    task_manager: Arc<dyn TaskManagerPort>,
 }
 
-impl From<AudioSegmentRecord> for AudioSegmentStatusResponse {
-    fn from(record: AudioSegmentRecord) -> Self {
+impl ListSessionsHandler {
+    pub fn new(
+        session_manager: Arc<dyn SessionManagerPort>,
+        task_manager: Arc<dyn TaskManagerPort>,
+    ) -> Self {
         Self {
-            segment_index: record.segment_index,
-            state: record.state.as_str().to_string(),
-            duration_ms: record.duration_ms,
-            error_message: record.error_message,
+            session_manager,
+            task_manager,
         }
     }
-}
 
-/// 会话段落响应
-#[derive(Debug, Clone)]
-pub struct SessionSegmentsResponse {
-    pub session_id: Uuid,
-    pub segments: Vec<AudioSegmentStatusResponse>,
-}
-
-// ============================================================================
-// GetPlaybackSession Query
-// ============================================================================
-
-impl Query for GetPlaybackSession {}
-
-/// GetPlaybackSession Handler
-pub struct GetSessionHandler<S: SessionRepositoryPort> {
-    session_repo: Arc<S>,
-}
-
-impl<S: SessionRepositoryPort> GetSessionHandler<S> {
-    pub fn new(session_repo: Arc<S>) -> Self {
-        Self { session_repo }
+    /// 按最近活动时间从新到旧分页
+    pub fn handle(&self, query: ListSessions) -> Result<Page<SessionView>, ApplicationError> {
+        query.page.validate()?;
+        let now = Utc::now();
+        let mut sessions: Vec<SessionView> = self
+            .session_manager
+            .list_all()
+            .into_iter()
+            .filter_map(|id| self.session_manager.get(&id).ok())
+            .filter(|session| query.novel_id.is_none_or(|novel_id| session.novel_id == novel_id))
+            .filter(|session| can_access(session.user_id, query.user_id))
+            .map(|session| self.view(session, now))
+            .filter(|view| query.state.is_none_or(|state| view.state == state))
+            .collect();
+        sessions.sort_by_key(|session| Reverse(session.last_activity));
+        Ok(query.page.paginate(sessions))
     }
-}
-
-#[async_trait]
-impl<S: SessionRepositoryPort + 'static> QueryHandler<GetPlaybackSession> for GetSessionHandler<S> {
-    type Output = SessionResponse;
-    type Error = ApplicationError;
-
-    async fn handle(&self, query: GetPlaybackSession) -> Result<Self::Output, Self::Error> {
-        let session_id = *query.session_id.as_uuid();
-
-        let session = self
-            .session_repo
-            .find_by_id(session_id)
-            .await?
-            .ok_or_else(|| ApplicationError::not_found("Session", session_id))?;
-
-        Ok(SessionResponse::from(session))
-    }
-}
-
-// ============================================================================
-// ListSessions Query
-// ============================================================================
-
-/// 列出所有会话查询
-#[derive(Debug, Clone)]
-pub struct ListSessions;
-
-impl Query for ListSessions {}
-
-/// ListSessions Handler
-pub struct ListSessionsHandler<S: SessionRepositoryPort> {
-    session_repo: Arc<S>,
-}
-
-impl<S: SessionRepositoryPort> ListSessionsHandler<S> {
-    pub fn new(session_repo: Arc<S>) -> Self {
-        Self { session_repo }
-    }
-}
 
-#[async_trait]
-impl<S: SessionRepositoryPort + 'static> QueryHandler<ListSessions> for ListSessionsHandler<S> {
-    type Output = Vec<SessionResponse>;
-    type Error = ApplicationError;
-
-    async fn handle(&self, _query: ListSessions) -> Result<Self::Output, Self::Error> {
-        let sessions = self.session_repo.find_all().await?;
-        Ok(sessions.into_iter().map(SessionResponse::from).collect())
+    fn view(&self, session: Session, now: DateTime<Utc>) -> SessionView {
+        let tasks = self.task_manager.get_tasks_by_session(&session.id);
+        let count = |state: TaskState| tasks.iter().filter(|task| task.state == state).count();
+        let pending_tasks = count(TaskState::Pending);
+        let inferring_tasks = count(TaskState::Inferring);
+
+        let recently_active = now - session.last_activity <= Duration::seconds(SESSION_ACTIVE_SECS);
+        let state = if recently_active || pending_tasks + inferring_tasks > 0 {
+            SessionState::Playing
+        } else {
+            SessionState::Idle
+        };
+
+        SessionView {
+            id: session.id,
+            novel_id: session.novel_id,
+            voice_id: session.voice_id,
+            user_id: session.user_id,
+            current_index: session.current_index,
+            offset_ms: session.offset_ms,
+            state,
+            background: session.background,
+            auto_advance: session.auto_advance,
+            window: session.window,
+            pending_tasks,
+            inferring_tasks,
+            created_at: session.created_at,
+            last_activity: session.last_activity,
+        }
     }
 }
 
-// ============================================================================
-// ListActiveSessions Query
-// ============================================================================
-
-impl Query for ListActiveSessions {}
-
 /// ListActiveSessions Handler
-pub struct ListActiveSessionsHandler<S: SessionRepositoryPort> {
-    session_repo: Arc<S>,
+pub struct ListActiveSessionsHandler {
+    sessions: ListSessionsHandler,
 }
 
-impl<S: SessionRepositoryPort> ListActiveSessionsHandler<S> {
-    pub fn new(session_repo: Arc<S>) -> Self {
-        Self { session_repo }
+impl ListActiveSessionsHandler {
+    pub fn new(
+        session_manager: Arc<dyn SessionManagerPort>,
+        task_manager: Arc<dyn TaskManagerPort>,
+    ) -> Self {
+        Self {
+            sessions: ListSessionsHandler::new(session_manager, task_manager),
+        }
     }
-}
 
-#[async_trait]
-impl<S: SessionRepositoryPort + 'static> QueryHandler<ListActiveSessions>
-    for ListActiveSessionsHandler<S>
-{
-    type Output = Vec<SessionResponse>;
-    type Error = ApplicationError;
-
-    async fn handle(&self, _query: ListActiveSessions) -> Result<Self::Output, Self::Error> {
-        let sessions = self.session_repo.find_active().await?;
-        Ok(sessions.into_iter().map(SessionResponse::from).collect())
+    pub fn handle(&self, query: ListActiveSessions) -> Result<Page<SessionView>, ApplicationError> {
+        self.sessions.handle(ListSessions {
+            novel_id: query.novel_id,
+            state: Some(SessionState::Playing),
+            user_id: query.user_id,
+            page: query.page,
+        })
     }
 }
 
-// ============================================================================
-// GetSessionSegments Query
-// ============================================================================
-
-/// 获取会话段落状态查询
-#[derive(Debug, Clone)]
-pub struct GetSessionSegments {
-    pub session_id: Uuid,
-}
-
-impl Query for GetSessionSegments {}
-
 /// GetSessionSegments Handler
-pub struct GetSessionSegmentsHandler<S, A>
-where
-    S: SessionRepositoryPort,
-    A: AudioSegmentRepositoryPort,
-{
-    session_repo: Arc<S>,
-    audio_segment_repo: Arc<A>,
+pub struct GetSessionSegmentsHandler {
+    session_manager: Arc<dyn SessionManagerPort>,
+    novel_repo: Arc<dyn NovelRepositoryPort>,
+    segment_status: SegmentStatusResolver,
 }
 
-impl<S, A> GetSessionSegmentsHandler<S, A>
-where
-    S: SessionRepositoryPort,
-    A: AudioSegmentRepositoryPort,
-{
-    pub fn new(session_repo: Arc<S>, audio_segment_repo: Arc<A>) -> Self {
+impl GetSessionSegmentsHandler {
+    pub fn new(
+        session_manager: Arc<dyn SessionManagerPort>,
+        novel_repo: Arc<dyn NovelRepositoryPort>,
+        segment_status: SegmentStatusResolver,
+    ) -> Self {
         Self {
-            session_repo,
-            audio_segment_repo,
+            session_manager,
+            novel_repo,
+            segment_status,
         }
     }
-}
 
-#[async_trait]
-impl<S, A> QueryHandler<GetSessionSegments> for GetSessionSegmentsHandler<S, A>
-where
-    S: SessionRepositoryPort + 'static,
-    A: AudioSegmentRepositoryPort + 'static,
-{
-    type Output = SessionSegmentsResponse;
-    type Error = ApplicationError;
-
-    async fn handle(&self, query: GetSessionSegments) -> Result<Self::Output, Self::Error> {
-        // 验证会话存在
-        self.session_repo
-            .find_by_id(query.session_id)
+    pub async fn handle(
+        &self,
+        query: GetSessionSegments,
+    ) -> Result<SessionSegmentsView, ApplicationError> {
+        let session = self
+            .session_manager
+            .get(&query.session_id)
+            .map_err(|_| ApplicationError::not_found_str("Session", &query.session_id))?;
+        let novel = self
+            .novel_repo
+            .find_by_id(session.novel_id)
             .await?
-            .ok_or_else(|| ApplicationError::not_found("Session", query.session_id))?;
+            .ok_or_else(|| ApplicationError::not_found("Novel", session.novel_id))?;
+        if novel.total_segments == 0 {
+            return Ok(SessionSegmentsView {
+                session_id: session.id,
+                current_index: session.current_index,
+                start: 0,
+                end: 0,
+                segments: Vec::new(),
+            });
+        }
 
-        let segments = self
-            .audio_segment_repo
-            .find_by_session(query.session_id)
-            .await?;
+        let last = (novel.total_segments - 1) as u32;
+        let current = session.current_index.min(last);
+        let (window_start, window_end) =
+            session.window.window_range(current as usize, novel.total_segments);
+        let start = query.start.unwrap_or(window_start as u32).min(last);
+        let end = query.end.unwrap_or(window_end as u32).min(last);
+        if start > end {
+            return Err(ApplicationError::validation(format!(
+                "Invalid range: start {} is after end {}",
+                start, end
+            )));
+        }
+        if end - start >= MAX_SEGMENT_RANGE {
+            return Err(ApplicationError::validation(format!(
+                "Range too large: at most {} segments per request",
+                MAX_SEGMENT_RANGE
+            )));
+        }
 
-        Ok(SessionSegmentsResponse {
-            session_id: query.session_id,
-            segments: segments
-                .into_iter()
-                .map(AudioSegmentStatusResponse::from)
-                .collect(),
+        let indices: Vec<u32> = (start..=end).collect();
+        let text_segments = self
+            .novel_repo
+            .find_segments_by_indices(session.novel_id, &indices)
+            .await?;
+        let segments = self.segment_status.resolve_all(&session, &text_segments).await;
+
+        Ok(SessionSegmentsView {
+            session_id: session.id,
+            current_index: session.current_index,
+            start,
+            end,
+            segments,
         })
     }
 }
 
-// ============================================================================
-// GetAudio Query
-// ============================================================================
-
-/// 获取音频查询
-#[derive(Debug, Clone)]
-pub struct GetAudio {
-    pub session_id: Uuid,
-    pub segment_index: usize,
+/// GetSessionSnapshot Handler - 把会话当前的播放状态编码为令牌
+pub struct GetSessionSnapshotHandler {
+    session_manager: Arc<dyn SessionManagerPort>,
 }
 
-impl Query for GetAudio {}
-
-/// 音频响应
-#[derive(Debug)]
-pub enum AudioResponse {
-    /// 音频已就绪
-    Ready {
-        data: Vec<u8>,
-        duration_ms: Option<u32>,
-    },
-    /// 正在推理中
-    Inferring,
-    /// 推理失败
-    Failed { error_message: String },
-    /// 未找到
-    NotFound,
-}
-
-/// GetAudio Handler
-pub struct GetAudioHandler<S, A, AS>
-where
-    S: SessionRepositoryPort,
-    A: AudioSegmentRepositoryPort,
-    AS: AudioStoragePort,
-{
-    session_repo: Arc<S>,
-    audio_segment_repo: Arc<A>,
-    audio_storage: Arc<AS>,
-}
+impl GetSessionSnapshotHandler {
+    pub fn new(session_manager: Arc<dyn SessionManagerPort>) -> Self {
+        Self { session_manager }
+    }
 
-impl<S, A, AS> GetAudioHandler<S, A, AS>
-where
-    S: SessionRepositoryPort,
-    A: AudioSegmentRepositoryPort,
-    AS: AudioStoragePort,
-{
-    pub fn new(session_repo: Arc<S>, audio_segment_repo: Arc<A>, audio_storage: Arc<AS>) -> Self {
-        Self {
-            session_repo,
-            audio_segment_repo,
-            audio_storage,
+    pub fn handle(&self, query: GetSessionSnapshot) -> Result<SessionSnapshot, ApplicationError> {
+        let session = self
+            .session_manager
+            .get(&query.session_id)
+            .map_err(|_| ApplicationError::not_found_str("Session", &query.session_id))?;
+        if session.background {
+            return Err(ApplicationError::invalid_state(
+                "Background sessions cannot be snapshotted",
+            ));
         }
+        Ok(SessionSnapshot::from_session(&session))
     }
 }
 
-#[async_trait]
-impl<S, A, AS> QueryHandler<GetAudio> for GetAudioHandler<S, A, AS>
-where
-    S: SessionRepositoryPort + 'static,
-    A: AudioSegmentRepositoryPort + 'static,
-    AS: AudioStoragePort + 'static,
-{
-    type Output = AudioResponse;
-    type Error = ApplicationError;
-
-    async fn handle(&self, query: GetAudio) -> Result<Self::Output, Self::Error> {
-        use crate::application::ports::AudioSegmentState;
-
-        // 验证会话存在
-        self.session_repo
-            .find_by_id(query.session_id)
-            .await?
-            .ok_or_else(|| ApplicationError::not_found("Session", query.session_id))?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::casting::VoiceCastingResolver;
+    use crate::application::pagination::PageRequest;
+    use crate::application::ports::{
+        generate_cache_key_with_params, AudioCachePort, AudioFormat, CacheMetadata, InferenceTask,
+        NovelRecord, NovelStatus, TextSegmentRecord,
+    };
+    use crate::application::readiness::SegmentReadiness;
+    use crate::domain::voice::SynthesisParams;
+    use crate::infrastructure::memory::TaskQueue;
+    use crate::infrastructure::persistence::sqlite::{
+        create_pool, run_migrations, DatabaseConfig, SqliteNovelRepository, SqliteVoiceCastingRepository,
+        SqliteVoiceRepository,
+    };
+    use crate::infrastructure::{InMemorySessionManager, InMemoryTaskManager, SledAudioCache};
+    use std::path::PathBuf;
+
+    fn session_at(novel_id: Uuid, current_index: u32, idle_secs: i64) -> Session {
+        Session {
+            last_activity: Utc::now() - Duration::seconds(idle_secs),
+            ..Session::new(novel_id, Uuid::new_v4(), current_index)
+        }
+    }
 
-        // 查找音频段落
-        let segment = self
-            .audio_segment_repo
-            .find_by_session_and_index(query.session_id, query.segment_index)
-            .await?;
+    #[test]
+    fn test_list_sessions_filters_and_state() {
+        let session_manager = Arc::new(InMemorySessionManager::new());
+        let task_manager = Arc::new(InMemoryTaskManager::new(Arc::new(TaskQueue::new(10))));
+        let handler = ListSessionsHandler::new(session_manager.clone(), task_manager.clone());
+        let active_handler = ListActiveSessionsHandler::new(session_manager.clone(), task_manager.clone());
+
+        let novel_id = Uuid::new_v4();
+        let playing = session_manager.create(session_at(novel_id, 0, 0)).unwrap();
+        let idle = session_manager.create(session_at(novel_id, 0, 600)).unwrap();
+        // 长时间无活动但仍有推理任务：视为播放中
+        let busy = session_manager.create(session_at(novel_id, 0, 300)).unwrap();
+        task_manager
+            .submit(vec![InferenceTask::new(busy.clone(), novel_id, Uuid::new_v4(), 0, "第0段".to_string())])
+            .unwrap();
+        let other_user = session_manager
+            .create(Session {
+                user_id: Some(Uuid::new_v4()),
+                ..session_at(Uuid::new_v4(), 0, 0)
+            })
+            .unwrap();
+
+        let ids = |page: Page<SessionView>| page.items.into_iter().map(|view| view.id).collect::<Vec<_>>();
+
+        // 按最近活动从新到旧
+        let all = handler
+            .handle(ListSessions {
+                novel_id: Some(novel_id),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(all.items[2].state, SessionState::Idle);
+        assert_eq!(all.items[1].pending_tasks, 1);
+        assert_eq!(ids(all), vec![playing.clone(), busy.clone(), idle.clone()]);
+
+        let idle_only = handler
+            .handle(ListSessions {
+                state: Some(SessionState::Idle),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(ids(idle_only), vec![idle]);
+
+        // 只列出当前用户可访问的会话
+        let active = active_handler
+            .handle(ListActiveSessions {
+                user_id: Some(Uuid::new_v4()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(active.total, 2);
+        assert!(!ids(active).contains(&other_user));
+
+        let page = handler
+            .handle(ListSessions {
+                page: PageRequest { offset: 1, limit: 2 },
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!((page.total, page.items.len()), (4, 2));
+    }
 
-        match segment {
-            None => Ok(AudioResponse::NotFound),
-            Some(seg) => match seg.state {
-                AudioSegmentState::Ready => {
-                    // 读取音频数据
-                    let data = self
-                        .audio_storage
-                        .read_audio(query.session_id, query.segment_index)
-                        .await
-                        .map_err(|e| ApplicationError::StorageError(e.to_string()))?;
-
-                    // 更新访问时间
-                    let _ = self.audio_segment_repo.touch(seg.id).await;
-
-                    Ok(AudioResponse::Ready {
-                        data,
-                        duration_ms: seg.duration_ms,
-                    })
-                }
-                AudioSegmentState::Inferring | AudioSegmentState::Pending => {
-                    Ok(AudioResponse::Inferring)
-                }
-                AudioSegmentState::Failed => Ok(AudioResponse::Failed {
-                    error_message: seg.error_message.unwrap_or_else(|| "Unknown error".to_string()),
-                }),
-            },
-        }
+    #[tokio::test]
+    async fn test_session_segments_readiness() {
+        let pool = create_pool(&DatabaseConfig::in_memory()).await.unwrap();
+        run_migrations(&pool).await.unwrap();
+        let novel_repo = Arc::new(SqliteNovelRepository::new(pool.clone()));
+        let voice_casting = VoiceCastingResolver::new(
+            novel_repo.clone(),
+            Arc::new(SqliteVoiceCastingRepository::new(pool.clone())),
+            Arc::new(SqliteVoiceRepository::new(pool)),
+            false,
+        );
+        let dir = tempfile::tempdir().unwrap();
+        let audio_cache = Arc::new(SledAudioCache::open(dir.path().join("cache"), 1024 * 1024).unwrap());
+        let session_manager = Arc::new(InMemorySessionManager::new());
+        let task_manager = Arc::new(InMemoryTaskManager::new(Arc::new(TaskQueue::new(10))));
+        let handler = GetSessionSegmentsHandler::new(
+            session_manager.clone(),
+            novel_repo.clone(),
+            SegmentStatusResolver::new(task_manager.clone(), audio_cache.clone(), voice_casting.clone()),
+        );
+
+        let novel_id = Uuid::new_v4();
+        novel_repo
+            .save(&NovelRecord {
+                id: novel_id,
+                title: "测试".to_string(),
+                raw_text_path: PathBuf::from("novel.txt"),
+                total_segments: 10,
+                status: NovelStatus::Ready,
+                user_id: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            })
+            .await
+            .unwrap();
+        let segments: Vec<TextSegmentRecord> = (0..10)
+            .map(|index| TextSegmentRecord {
+                id: Uuid::new_v4(),
+                novel_id,
+                index,
+                content: format!("第{}段", index),
+                char_count: 3,
+            })
+            .collect();
+        novel_repo.save_segments(&segments).await.unwrap();
+
+        let session = session_at(novel_id, 1, 0);
+        let session_id = session_manager.create(session.clone()).unwrap();
+        task_manager
+            .submit(vec![InferenceTask::new(
+                session_id.clone(),
+                novel_id,
+                session.voice_id,
+                2,
+                "第2段".to_string(),
+            )])
+            .unwrap();
+        let voice = voice_casting.voice_key(session.voice_id).await;
+        let cache_key = generate_cache_key_with_params("第3段", &voice, &SynthesisParams::default());
+        audio_cache
+            .put(
+                &cache_key,
+                vec![1, 2, 3],
+                CacheMetadata {
+                    novel_id,
+                    segment_index: 3,
+                    voice_id: session.voice_id,
+                    content_hash: "hash".to_string(),
+                    duration_ms: 100,
+                    sample_rate: None,
+                    format: AudioFormat::Wav,
+                },
+            )
+            .await
+            .unwrap();
+
+        // 未指定范围：当前预取窗口（前 2 后 3）
+        let view = handler
+            .handle(GetSessionSegments {
+                session_id: session_id.clone(),
+                start: None,
+                end: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!((view.start, view.end), (0, 4));
+        let readiness: Vec<_> = view.segments.iter().map(|status| status.readiness).collect();
+        assert_eq!(
+            readiness,
+            vec![
+                SegmentReadiness::Missing,
+                SegmentReadiness::Missing,
+                SegmentReadiness::Pending,
+                SegmentReadiness::Ready,
+                SegmentReadiness::Missing,
+            ]
+        );
+
+        // 范围超出片段数时截断
+        let view = handler
+            .handle(GetSessionSegments {
+                session_id: session_id.clone(),
+                start: Some(8),
+                end: Some(20),
+            })
+            .await
+            .unwrap();
+        assert_eq!((view.start, view.end, view.segments.len()), (8, 9, 2));
+
+        let err = handler
+            .handle(GetSessionSegments {
+                session_id,
+                start: Some(5),
+                end: Some(3),
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ApplicationError::ValidationError(_)));
     }
 }
@@ -0,0 +1,27 @@
+//! Settings Query Handlers - V2 架构
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::application::error::ApplicationError;
+use crate::application::ports::SettingsRepositoryPort;
+use crate::application::queries::GetSettings;
+
+/// GetSettings Handler
+pub struct GetSettingsHandler {
+    settings_repo: Arc<dyn SettingsRepositoryPort>,
+}
+
+impl GetSettingsHandler {
+    pub fn new(settings_repo: Arc<dyn SettingsRepositoryPort>) -> Self {
+        Self { settings_repo }
+    }
+
+    pub async fn handle(
+        &self,
+        query: GetSettings,
+    ) -> Result<BTreeMap<String, String>, ApplicationError> {
+        let settings = self.settings_repo.find_all(query.user_id).await?;
+        Ok(settings.into_iter().collect())
+    }
+}
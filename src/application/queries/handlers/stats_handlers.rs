@@ -0,0 +1,135 @@
+//! Stats Query Handlers - 收听统计查询
+
+use chrono::{Local, NaiveDate};
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::error::ApplicationError;
+use crate::application::ports::{ListeningStatsRepositoryPort, NovelRepositoryPort};
+use crate::application::queries::GetListeningStats;
+
+/// 最多统计的天数
+pub const MAX_LISTENING_STATS_DAYS: u32 = 366;
+
+/// 单日收听量
+#[derive(Debug, Clone)]
+pub struct DailyListeningView {
+    pub day: NaiveDate,
+    pub listened_ms: u64,
+    pub segments: u32,
+}
+
+/// 单部小说的收听量和完成度
+#[derive(Debug, Clone)]
+pub struct NovelListeningView {
+    pub novel_id: Uuid,
+    /// 小说已删除时为 None
+    pub title: Option<String>,
+    /// 统计范围内的收听时长和片段数
+    pub listened_ms: u64,
+    pub segments: u32,
+    /// 到达过的最远片段索引（不限统计范围）
+    pub max_index: u32,
+    pub total_segments: usize,
+    /// 完成度（0.0 - 1.0，按最远片段计算）
+    pub completion: f32,
+}
+
+/// 收听统计
+#[derive(Debug, Clone)]
+pub struct ListeningStatsView {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub listened_ms: u64,
+    pub segments: u32,
+    /// 范围内每一天（没有收听的日期为 0）
+    pub days: Vec<DailyListeningView>,
+    /// 按范围内收听时长从多到少排列
+    pub novels: Vec<NovelListeningView>,
+}
+
+/// GetListeningStats Handler
+pub struct GetListeningStatsHandler {
+    stats_repo: Arc<dyn ListeningStatsRepositoryPort>,
+    novel_repo: Arc<dyn NovelRepositoryPort>,
+}
+
+impl GetListeningStatsHandler {
+    pub fn new(
+        stats_repo: Arc<dyn ListeningStatsRepositoryPort>,
+        novel_repo: Arc<dyn NovelRepositoryPort>,
+    ) -> Self {
+        Self {
+            stats_repo,
+            novel_repo,
+        }
+    }
+
+    pub async fn handle(&self, query: GetListeningStats) -> Result<ListeningStatsView, ApplicationError> {
+        if query.days == 0 || query.days > MAX_LISTENING_STATS_DAYS {
+            return Err(ApplicationError::validation(format!(
+                "days must be between 1 and {}",
+                MAX_LISTENING_STATS_DAYS
+            )));
+        }
+
+        let to = Local::now().date_naive();
+        let from = to - chrono::Duration::days(query.days as i64 - 1);
+        let records = self.stats_repo.find(query.user_id, query.novel_id).await?;
+
+        let mut days: Vec<DailyListeningView> = from
+            .iter_days()
+            .take(query.days as usize)
+            .map(|day| DailyListeningView {
+                day,
+                listened_ms: 0,
+                segments: 0,
+            })
+            .collect();
+        // (范围内时长, 范围内片段数, 最远片段)
+        let mut by_novel: HashMap<Uuid, (u64, u32, u32)> = HashMap::new();
+        for record in &records {
+            let novel = by_novel.entry(record.novel_id).or_default();
+            novel.2 = novel.2.max(record.max_index);
+            if record.day < from || record.day > to {
+                continue;
+            }
+            novel.0 += record.listened_ms;
+            novel.1 += record.segments;
+            let day = &mut days[(record.day - from).num_days() as usize];
+            day.listened_ms += record.listened_ms;
+            day.segments += record.segments;
+        }
+
+        let mut novels = Vec::with_capacity(by_novel.len());
+        for (novel_id, (listened_ms, segments, max_index)) in by_novel {
+            let novel = self.novel_repo.find_by_id(novel_id).await?;
+            let total_segments = novel.as_ref().map(|n| n.total_segments).unwrap_or(0);
+            let completion = if total_segments == 0 {
+                0.0
+            } else {
+                ((max_index as usize + 1) as f32 / total_segments as f32).min(1.0)
+            };
+            novels.push(NovelListeningView {
+                novel_id,
+                title: novel.map(|n| n.title),
+                listened_ms,
+                segments,
+                max_index,
+                total_segments,
+                completion,
+            });
+        }
+        novels.sort_by(|a, b| b.listened_ms.cmp(&a.listened_ms).then(a.novel_id.cmp(&b.novel_id)));
+
+        Ok(ListeningStatsView {
+            from,
+            to,
+            listened_ms: days.iter().map(|day| day.listened_ms).sum(),
+            segments: days.iter().map(|day| day.segments).sum(),
+            days,
+            novels,
+        })
+    }
+}
@@ -0,0 +1,64 @@
+//! TTS Engine Query Handlers - V2 架构
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::application::error::ApplicationError;
+use crate::application::ports::TtsEngineStatusRepositoryPort;
+use crate::application::queries::GetTtsEngineStatus;
+
+/// TTS 引擎健康状态视图
+#[derive(Debug, Clone)]
+pub struct TtsEngineStatusView {
+    pub engine: String,
+    /// None 表示尚未完成首次健康检查
+    pub healthy: Option<bool>,
+    pub consecutive_failures: u32,
+    pub last_checked_at: Option<String>,
+    pub last_changed_at: Option<String>,
+}
+
+/// GetTtsEngineStatus Handler
+pub struct GetTtsEngineStatusHandler {
+    status_repo: Arc<dyn TtsEngineStatusRepositoryPort>,
+}
+
+impl GetTtsEngineStatusHandler {
+    pub fn new(status_repo: Arc<dyn TtsEngineStatusRepositoryPort>) -> Self {
+        Self { status_repo }
+    }
+
+    pub async fn handle(
+        &self,
+        query: GetTtsEngineStatus,
+    ) -> Result<Vec<TtsEngineStatusView>, ApplicationError> {
+        let mut records: HashMap<_, _> = self
+            .status_repo
+            .find_all()
+            .await?
+            .into_iter()
+            .map(|r| (r.engine.clone(), r))
+            .collect();
+
+        Ok(query
+            .engines
+            .into_iter()
+            .map(|engine| match records.remove(&engine) {
+                Some(record) => TtsEngineStatusView {
+                    engine,
+                    healthy: Some(record.healthy),
+                    consecutive_failures: record.consecutive_failures,
+                    last_checked_at: Some(record.last_checked_at.to_rfc3339()),
+                    last_changed_at: Some(record.last_changed_at.to_rfc3339()),
+                },
+                None => TtsEngineStatusView {
+                    engine,
+                    healthy: None,
+                    consecutive_failures: 0,
+                    last_checked_at: None,
+                    last_changed_at: None,
+                },
+            })
+            .collect())
+    }
+}
@@ -1,11 +1,14 @@
 //! Voice Query Handlers - V2 架构
 
+use std::collections::BTreeSet;
 use std::sync::Arc;
 use uuid::Uuid;
 
+use crate::application::access::can_access;
 use crate::application::error::ApplicationError;
 use crate::application::ports::{VoiceRecord, VoiceRepositoryPort};
-use crate::application::queries::{GetVoice, ListVoices};
+use crate::application::queries::{GetVoice, ListVoiceTags, ListVoices};
+use crate::domain::voice::{TtsConfig, VoiceTags};
 
 // ============================================================================
 // Response DTOs
@@ -17,15 +20,31 @@ pub struct VoiceResponse {
     pub id: Uuid,
     pub name: String,
     pub description: Option<String>,
+    pub prompt_text: Option<String>,
+    pub engine: Option<String>,
+    pub tts_config: TtsConfig,
+    pub tags: VoiceTags,
     pub created_at: String,
 }
 
+/// 音色标签分面（各类别下已使用的标签值，去重排序）
+#[derive(Debug, Clone, Default)]
+pub struct VoiceTagFacets {
+    pub genders: Vec<String>,
+    pub languages: Vec<String>,
+    pub styles: Vec<String>,
+}
+
 impl From<VoiceRecord> for VoiceResponse {
     fn from(record: VoiceRecord) -> Self {
         Self {
             id: record.id,
             name: record.name,
             description: record.description,
+            prompt_text: record.prompt_text,
+            engine: record.engine,
+            tts_config: record.tts_config,
+            tags: record.tags,
             created_at: record.created_at.to_rfc3339(),
         }
     }
@@ -66,8 +85,51 @@ impl ListVoicesHandler {
         Self { voice_repo }
     }
 
-    pub async fn handle(&self, _query: ListVoices) -> Result<Vec<VoiceResponse>, ApplicationError> {
+    pub async fn handle(&self, query: ListVoices) -> Result<Vec<VoiceResponse>, ApplicationError> {
+        let filter = VoiceTags {
+            gender: query.gender.as_deref().and_then(VoiceTags::normalize_value),
+            language: query.language.as_deref().and_then(VoiceTags::normalize_value),
+            style: query.style.as_deref().and_then(VoiceTags::normalize_value),
+        };
+
+        let voices = self.voice_repo.find_all().await?;
+        Ok(voices
+            .into_iter()
+            .filter(|v| v.tags.matches(&filter) && can_access(v.user_id, query.user_id))
+            .map(VoiceResponse::from)
+            .collect())
+    }
+}
+
+/// ListVoiceTags Handler
+pub struct ListVoiceTagsHandler {
+    voice_repo: Arc<dyn VoiceRepositoryPort>,
+}
+
+impl ListVoiceTagsHandler {
+    pub fn new(voice_repo: Arc<dyn VoiceRepositoryPort>) -> Self {
+        Self { voice_repo }
+    }
+
+    pub async fn handle(&self, query: ListVoiceTags) -> Result<VoiceTagFacets, ApplicationError> {
         let voices = self.voice_repo.find_all().await?;
-        Ok(voices.into_iter().map(VoiceResponse::from).collect())
+        let voices: Vec<_> = voices
+            .into_iter()
+            .filter(|v| can_access(v.user_id, query.user_id))
+            .collect();
+
+        let collect = |f: fn(&VoiceTags) -> &Option<String>| {
+            let values: BTreeSet<String> = voices
+                .iter()
+                .filter_map(|v| f(&v.tags).clone())
+                .collect();
+            values.into_iter().collect()
+        };
+
+        Ok(VoiceTagFacets {
+            genders: collect(|t| &t.gender),
+            languages: collect(|t| &t.language),
+            styles: collect(|t| &t.style),
+        })
     }
 }
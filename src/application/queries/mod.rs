@@ -3,11 +3,27 @@
 //! CQRS 查询侧：处理所有读操作
 
 mod audio_queries;
+mod cache_queries;
+mod casting_queries;
+mod export_queries;
 mod novel_queries;
+mod pregeneration_queries;
+mod session_queries;
+mod settings_queries;
+mod stats_queries;
+mod tts_queries;
 mod voice_queries;
 
 pub mod handlers;
 
 pub use audio_queries::*;
+pub use cache_queries::*;
+pub use casting_queries::*;
+pub use export_queries::*;
 pub use novel_queries::*;
+pub use pregeneration_queries::*;
+pub use session_queries::*;
+pub use settings_queries::*;
+pub use stats_queries::*;
+pub use tts_queries::*;
 pub use voice_queries::*;
@@ -2,20 +2,39 @@
 
 use uuid::Uuid;
 
+use crate::application::pagination::PageRequest;
+
 /// 获取小说详情查询
 #[derive(Debug, Clone)]
 pub struct GetNovel {
     pub novel_id: Uuid,
 }
 
-/// 列出所有小说查询
-#[derive(Debug, Clone)]
-pub struct ListNovels;
+/// 列出小说查询
+///
+/// `user_id` 为 None 时列出所有小说，否则只列出该用户的小说（及无所属用户的小说）
+#[derive(Debug, Clone, Default)]
+pub struct ListNovels {
+    pub user_id: Option<Uuid>,
+    pub page: PageRequest,
+}
 
-/// 获取小说片段查询
+/// 获取小说片段查询（按片段索引分页）
 #[derive(Debug, Clone)]
 pub struct GetNovelSegments {
     pub novel_id: Uuid,
-    pub start_index: Option<usize>,
-    pub limit: Option<usize>,
+    pub page: PageRequest,
+}
+
+/// 获取小说章节查询（按标题片段识别）
+#[derive(Debug, Clone)]
+pub struct GetNovelChapters {
+    pub novel_id: Uuid,
+}
+
+/// 分段预览查询（不持久化，用于调试 min_chars）
+#[derive(Debug, Clone)]
+pub struct PreviewSegments {
+    pub text: String,
+    pub min_chars: Option<usize>,
 }
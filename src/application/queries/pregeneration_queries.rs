@@ -0,0 +1,7 @@
+//! Pregeneration Queries - 预生成任务查询
+
+/// 获取整书预生成任务查询
+#[derive(Debug, Clone)]
+pub struct GetPregenerationJob {
+    pub job_id: String,
+}
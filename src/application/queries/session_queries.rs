@@ -0,0 +1,42 @@
+//! Session Queries - 播放会话查询
+
+use uuid::Uuid;
+
+use crate::application::pagination::PageRequest;
+use crate::application::ports::SessionState;
+
+/// 列出会话查询
+///
+/// 各过滤字段为 None 时不参与过滤
+#[derive(Debug, Clone, Default)]
+pub struct ListSessions {
+    pub novel_id: Option<Uuid>,
+    pub state: Option<SessionState>,
+    /// 当前用户（只列出该用户的会话）
+    pub user_id: Option<Uuid>,
+    pub page: PageRequest,
+}
+
+/// 列出活动会话查询（最近有活动或有进行中的推理任务）
+#[derive(Debug, Clone, Default)]
+pub struct ListActiveSessions {
+    pub novel_id: Option<Uuid>,
+    pub user_id: Option<Uuid>,
+    pub page: PageRequest,
+}
+
+/// 获取会话片段就绪状态查询
+///
+/// 未指定范围时使用会话当前的预取窗口
+#[derive(Debug, Clone)]
+pub struct GetSessionSegments {
+    pub session_id: String,
+    pub start: Option<u32>,
+    pub end: Option<u32>,
+}
+
+/// 获取会话快照查询
+#[derive(Debug, Clone)]
+pub struct GetSessionSnapshot {
+    pub session_id: String,
+}
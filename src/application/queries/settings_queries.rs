@@ -0,0 +1,10 @@
+//! Settings Queries - V2 架构
+
+use uuid::Uuid;
+
+/// 获取当前用户所有设置查询
+#[derive(Debug, Clone)]
+pub struct GetSettings {
+    /// 当前用户（未启用账号时为 None）
+    pub user_id: Option<Uuid>,
+}
@@ -0,0 +1,14 @@
+//! Stats Queries - 收听统计查询
+
+use uuid::Uuid;
+
+/// 获取收听统计查询
+///
+/// 按天统计当前用户最近 `days` 天（含今天）；`novel_id` 为 None 时统计所有小说
+#[derive(Debug, Clone)]
+pub struct GetListeningStats {
+    /// 当前用户（未启用账号时为 None）
+    pub user_id: Option<Uuid>,
+    pub novel_id: Option<Uuid>,
+    pub days: u32,
+}
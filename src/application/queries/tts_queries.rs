@@ -0,0 +1,8 @@
+//! TTS Engine Queries - V2 架构
+
+/// 获取 TTS 引擎健康状态查询
+#[derive(Debug, Clone)]
+pub struct GetTtsEngineStatus {
+    /// 当前配置的引擎名称（未配置的历史记录不返回）
+    pub engines: Vec<String>,
+}
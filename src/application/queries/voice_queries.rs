@@ -8,6 +8,21 @@ pub struct GetVoice {
     pub voice_id: Uuid,
 }
 
-/// 列出所有音色查询
+/// 列出音色查询
+///
+/// 各过滤字段为 None 时不参与过滤（忽略大小写匹配）
+#[derive(Debug, Clone, Default)]
+pub struct ListVoices {
+    pub gender: Option<String>,
+    pub language: Option<String>,
+    pub style: Option<String>,
+    /// 当前用户（只列出该用户的音色和共享音色）
+    pub user_id: Option<Uuid>,
+}
+
+/// 列出已使用的音色标签（用于分类浏览）
 #[derive(Debug, Clone)]
-pub struct ListVoices;
+pub struct ListVoiceTags {
+    /// 当前用户（只统计该用户的音色和共享音色）
+    pub user_id: Option<Uuid>,
+}
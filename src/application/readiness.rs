@@ -0,0 +1,197 @@
+//! 片段就绪状态
+//!
+//! 由会话的推理任务和音频缓存得出：缓存 key 按角色配音解析后的实际音色和会话/片段参数计算，
+//! 与预取和推理 Worker 一致
+
+use std::sync::Arc;
+
+use crate::application::casting::VoiceCastingResolver;
+use crate::application::ports::{
+    generate_cache_key_with_params, AudioCachePort, InferenceTask, Session, TaskManagerPort,
+    TaskState, TextSegmentRecord,
+};
+
+/// 片段就绪状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SegmentReadiness {
+    /// 音频已缓存
+    Ready,
+    /// 排队等待推理
+    Pending,
+    /// 推理中
+    Inferring,
+    /// 推理失败
+    Failed,
+    /// 未缓存且没有推理任务
+    Missing,
+}
+
+impl SegmentReadiness {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SegmentReadiness::Ready => "ready",
+            SegmentReadiness::Pending => "pending",
+            SegmentReadiness::Inferring => "inferring",
+            SegmentReadiness::Failed => "failed",
+            SegmentReadiness::Missing => "missing",
+        }
+    }
+}
+
+/// 片段状态
+#[derive(Debug, Clone)]
+pub struct SegmentStatus {
+    pub index: u32,
+    pub readiness: SegmentReadiness,
+    /// 该片段最近的推理任务
+    pub task_id: Option<String>,
+    pub error_message: Option<String>,
+}
+
+/// 一组片段的就绪状态汇总（心跳等只需要概览的场景）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReadinessSummary {
+    pub ready: usize,
+    pub pending: usize,
+    pub inferring: usize,
+    pub failed: usize,
+    pub missing: usize,
+    /// 从当前片段起连续就绪的片段数（客户端可不经请求连续播放的片段数）
+    pub ready_ahead: usize,
+}
+
+impl ReadinessSummary {
+    pub fn new(statuses: &[SegmentStatus], current_index: u32) -> Self {
+        let mut summary = Self::default();
+        for status in statuses {
+            match status.readiness {
+                SegmentReadiness::Ready => summary.ready += 1,
+                SegmentReadiness::Pending => summary.pending += 1,
+                SegmentReadiness::Inferring => summary.inferring += 1,
+                SegmentReadiness::Failed => summary.failed += 1,
+                SegmentReadiness::Missing => summary.missing += 1,
+            }
+        }
+
+        let mut ahead: Vec<&SegmentStatus> =
+            statuses.iter().filter(|status| status.index >= current_index).collect();
+        ahead.sort_by_key(|status| status.index);
+        summary.ready_ahead = ahead
+            .iter()
+            .enumerate()
+            .take_while(|(offset, status)| {
+                status.index == current_index + *offset as u32
+                    && status.readiness == SegmentReadiness::Ready
+            })
+            .count();
+        summary
+    }
+}
+
+/// 片段状态解析器
+#[derive(Clone)]
+pub struct SegmentStatusResolver {
+    task_manager: Arc<dyn TaskManagerPort>,
+    audio_cache: Arc<dyn AudioCachePort>,
+    voice_casting: VoiceCastingResolver,
+}
+
+impl SegmentStatusResolver {
+    pub fn new(
+        task_manager: Arc<dyn TaskManagerPort>,
+        audio_cache: Arc<dyn AudioCachePort>,
+        voice_casting: VoiceCastingResolver,
+    ) -> Self {
+        Self {
+            task_manager,
+            audio_cache,
+            voice_casting,
+        }
+    }
+
+    /// 解析会话中多个片段的状态（按传入顺序）
+    pub async fn resolve_all(&self, session: &Session, segments: &[TextSegmentRecord]) -> Vec<SegmentStatus> {
+        let tasks = self.task_manager.get_tasks_by_session(&session.id);
+        let mut statuses = Vec::with_capacity(segments.len());
+        for segment in segments {
+            statuses.push(self.status(session, segment, &tasks).await);
+        }
+        statuses
+    }
+
+    /// 解析会话中单个片段的状态
+    pub async fn resolve(&self, session: &Session, segment: &TextSegmentRecord) -> SegmentStatus {
+        let tasks = self.task_manager.get_tasks_by_session(&session.id);
+        self.status(session, segment, &tasks).await
+    }
+
+    async fn status(
+        &self,
+        session: &Session,
+        segment: &TextSegmentRecord,
+        tasks: &[InferenceTask],
+    ) -> SegmentStatus {
+        let index = segment.index as u32;
+        let params = session.params_for(index);
+        let voice = self
+            .voice_casting
+            .resolve_key(session.novel_id, index, session.voice_id)
+            .await;
+        let cache_key = generate_cache_key_with_params(&segment.content, &voice, &params);
+        let cached = self.audio_cache.exists(&cache_key).await.unwrap_or(false);
+
+        // 同一片段可能有多个任务（跳转后重新提交），取最近创建的
+        let task = tasks
+            .iter()
+            .filter(|task| task.segment_index == index)
+            .max_by_key(|task| task.created_at);
+        let readiness = if cached {
+            SegmentReadiness::Ready
+        } else {
+            match task.map(|task| task.state) {
+                Some(TaskState::Pending) => SegmentReadiness::Pending,
+                Some(TaskState::Inferring) => SegmentReadiness::Inferring,
+                Some(TaskState::Failed) => SegmentReadiness::Failed,
+                _ => SegmentReadiness::Missing,
+            }
+        };
+
+        SegmentStatus {
+            index,
+            readiness,
+            task_id: task.map(|task| task.task_id.clone()),
+            error_message: task.and_then(|task| task.error_message.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(index: u32, readiness: SegmentReadiness) -> SegmentStatus {
+        SegmentStatus {
+            index,
+            readiness,
+            task_id: None,
+            error_message: None,
+        }
+    }
+
+    #[test]
+    fn test_summary_counts_ready_run_from_current() {
+        let statuses = vec![
+            status(3, SegmentReadiness::Missing),
+            status(4, SegmentReadiness::Ready),
+            status(5, SegmentReadiness::Ready),
+            status(6, SegmentReadiness::Inferring),
+            status(7, SegmentReadiness::Ready),
+        ];
+        let summary = ReadinessSummary::new(&statuses, 4);
+        assert_eq!((summary.ready, summary.inferring, summary.missing), (3, 1, 1));
+        assert_eq!(summary.ready_ahead, 2);
+
+        assert_eq!(ReadinessSummary::new(&statuses, 3).ready_ahead, 0);
+        assert_eq!(ReadinessSummary::new(&[], 0), ReadinessSummary::default());
+    }
+}
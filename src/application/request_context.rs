@@ -0,0 +1,41 @@
+//! 请求上下文
+//!
+//! HTTP 中间件为每个请求分配请求 ID（X-Request-Id），处理请求期间保存在任务本地变量中：
+//! 期间创建的推理任务记录该 ID，Worker 推理、审计日志和 WebSocket 任务事件随之携带，
+//! 一个失败的片段可以从 HTTP 请求一路追踪到 TTS 客户端日志。
+//! `tokio::spawn` 的后台任务不继承请求 ID，需要时用 [`with_request_id`] 传递
+
+use std::future::Future;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// 当前请求的 ID（不在请求处理中时为 None）
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(|request_id| request_id.clone()).ok()
+}
+
+/// 在指定请求 ID 下执行（为 None 时直接执行）
+pub async fn with_request_id<F: Future>(request_id: Option<String>, future: F) -> F::Output {
+    match request_id {
+        Some(request_id) => REQUEST_ID.scope(request_id, future).await,
+        None => future.await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_request_id_scope() {
+        assert_eq!(current_request_id(), None);
+
+        let inner = with_request_id(Some("req-1".to_string()), async { current_request_id() }).await;
+        assert_eq!(inner.as_deref(), Some("req-1"));
+
+        let inner = with_request_id(None, async { current_request_id() }).await;
+        assert_eq!(inner, None);
+    }
+}
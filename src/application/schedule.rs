@@ -0,0 +1,92 @@
+//! 预生成时段
+//!
+//! 整书预生成只在配置的时段（本地时间，如 `02:00-07:00`，多个时段以逗号分隔）内提交推理，
+//! 时段外暂停，夜间批量合成不与白天的收听争用 TTS 后端；结束时间早于开始时间的时段跨越午夜
+
+use chrono::NaiveTime;
+
+/// 一个每日时段 [start, end)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeWindow {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl TimeWindow {
+    /// 解析 `HH:MM-HH:MM`
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let invalid = || format!("Invalid time window '{}', expected HH:MM-HH:MM", s.trim());
+        let (start, end) = s.trim().split_once('-').ok_or_else(invalid)?;
+        let parse_time =
+            |value: &str| NaiveTime::parse_from_str(value.trim(), "%H:%M").map_err(|_| invalid());
+        let window = Self {
+            start: parse_time(start)?,
+            end: parse_time(end)?,
+        };
+        if window.start == window.end {
+            return Err(format!("Empty time window '{}'", s.trim()));
+        }
+        Ok(window)
+    }
+
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start < self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+/// 预生成时段（为空时不限制）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PregenerationSchedule {
+    windows: Vec<TimeWindow>,
+}
+
+impl PregenerationSchedule {
+    /// 解析逗号分隔的时段列表，空字符串表示不限制
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let windows = s
+            .split(',')
+            .filter(|window| !window.trim().is_empty())
+            .map(TimeWindow::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { windows })
+    }
+
+    /// 是否限制了时段
+    pub fn is_restricted(&self) -> bool {
+        !self.windows.is_empty()
+    }
+
+    /// 该时间是否允许预生成
+    pub fn is_open(&self, time: NaiveTime) -> bool {
+        !self.is_restricted() || self.windows.iter().any(|window| window.contains(time))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_schedule_windows() {
+        let schedule = PregenerationSchedule::parse("02:00-07:00, 23:30-00:30").unwrap();
+        assert!(schedule.is_open(at(2, 0)));
+        assert!(schedule.is_open(at(6, 59)));
+        assert!(!schedule.is_open(at(7, 0)));
+        assert!(!schedule.is_open(at(12, 0)));
+        // 跨越午夜
+        assert!(schedule.is_open(at(23, 45)));
+        assert!(schedule.is_open(at(0, 15)));
+
+        assert!(PregenerationSchedule::parse("").unwrap().is_open(at(12, 0)));
+        assert!(PregenerationSchedule::parse("2am-7am").is_err());
+        assert!(PregenerationSchedule::parse("02:00-02:00").is_err());
+    }
+}
@@ -0,0 +1,92 @@
+//! 会话快照
+//!
+//! 把会话的播放状态编码为可分享的令牌（版本前缀 + base64url 编码的 JSON），不依赖服务端保存的状态，
+//! 之后或在另一个实例上恢复为新会话；小说和音色按 ID 匹配，恢复时仍需存在
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::application::error::ApplicationError;
+use crate::application::ports::{PlaybackHints, Session};
+use crate::domain::voice::SynthesisParams;
+
+/// 令牌前缀（格式变化时递增）
+const TOKEN_PREFIX: &str = "rs1.";
+
+/// 会话播放状态快照
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionSnapshot {
+    pub novel_id: Uuid,
+    pub voice_id: Uuid,
+    pub current_index: u32,
+    #[serde(default)]
+    pub offset_ms: u32,
+    pub window_before: usize,
+    pub window_after: usize,
+    #[serde(default)]
+    pub params: SynthesisParams,
+    #[serde(default)]
+    pub playback_speed: Option<f32>,
+    #[serde(default)]
+    pub playback_hints: PlaybackHints,
+}
+
+impl SessionSnapshot {
+    pub fn from_session(session: &Session) -> Self {
+        Self {
+            novel_id: session.novel_id,
+            voice_id: session.voice_id,
+            current_index: session.current_index,
+            offset_ms: session.offset_ms,
+            window_before: session.window.before,
+            window_after: session.window.after,
+            params: session.params.clone(),
+            playback_speed: session.playback_speed,
+            playback_hints: session.playback_hints,
+        }
+    }
+
+    /// 编码为令牌
+    pub fn encode(&self) -> String {
+        let json = serde_json::to_vec(self).expect("snapshot serialization cannot fail");
+        format!(
+            "{}{}",
+            TOKEN_PREFIX,
+            base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(json)
+        )
+    }
+
+    /// 解析令牌，格式不正确时返回校验错误
+    pub fn decode(token: &str) -> Result<Self, ApplicationError> {
+        let invalid = || ApplicationError::validation("Invalid snapshot token");
+        let encoded = token.trim().strip_prefix(TOKEN_PREFIX).ok_or_else(invalid)?;
+        let json = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(encoded)
+            .map_err(|_| invalid())?;
+        serde_json::from_slice(&json).map_err(|_| invalid())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::WindowConfig;
+
+    #[test]
+    fn test_token_round_trip() {
+        let session = Session::new(Uuid::new_v4(), Uuid::new_v4(), 412)
+            .with_offset_ms(3500)
+            .with_window(WindowConfig::new(1, 8))
+            .with_playback_speed(Some(1.25))
+            .with_params(SynthesisParams { speed: Some(1.1), ..Default::default() });
+
+        let snapshot = SessionSnapshot::from_session(&session);
+        let token = snapshot.encode();
+        assert!(token.starts_with(TOKEN_PREFIX));
+        assert_eq!(SessionSnapshot::decode(&token).unwrap(), snapshot);
+
+        assert!(SessionSnapshot::decode("rs1.not-base64!").is_err());
+        assert!(SessionSnapshot::decode(&token.replacen(TOKEN_PREFIX, "rs0.", 1)).is_err());
+    }
+}
@@ -0,0 +1,67 @@
+//! 音频元数据标签
+//!
+//! 单个片段转码为 Opus 时写入的标签，使导出/流式输出的文件在外部播放器中可识别：
+//! - ALBUM: 小说标题
+//! - TITLE: 片段所在章节标题
+//! - ARTIST: 音色名称
+//! - TRACKNUMBER: 片段序号（从 1 开始）
+
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::ports::{AudioMetadata, NovelRepositoryPort, VoiceRepositoryPort};
+use crate::domain::novel::detect_chapters;
+
+/// 片段标签解析器
+#[derive(Clone)]
+pub struct AudioTagResolver {
+    novel_repo: Arc<dyn NovelRepositoryPort>,
+    voice_repo: Arc<dyn VoiceRepositoryPort>,
+}
+
+impl AudioTagResolver {
+    pub fn new(novel_repo: Arc<dyn NovelRepositoryPort>, voice_repo: Arc<dyn VoiceRepositoryPort>) -> Self {
+        Self {
+            novel_repo,
+            voice_repo,
+        }
+    }
+
+    /// 解析片段标签；查询失败的字段留空（标签缺失不影响转码）
+    pub async fn resolve(&self, novel_id: Uuid, segment_index: u32, voice_id: Uuid) -> AudioMetadata {
+        let album = match self.novel_repo.find_by_id(novel_id).await {
+            Ok(novel) => novel.map(|novel| novel.title),
+            Err(e) => {
+                tracing::warn!(novel_id = %novel_id, error = %e, "Failed to load novel for audio tags");
+                None
+            }
+        };
+
+        let title = match self.novel_repo.find_segments_by_novel_id(novel_id).await {
+            Ok(segments) => detect_chapters(segments.iter().map(|s| s.content.as_str()))
+                .into_iter()
+                .find(|c| (c.start_segment_index()..c.end_segment_index()).contains(&(segment_index as usize)))
+                .map(|c| c.title().to_string()),
+            Err(e) => {
+                tracing::warn!(novel_id = %novel_id, error = %e, "Failed to load chapters for audio tags");
+                None
+            }
+        };
+
+        let artist = match self.voice_repo.find_by_id(voice_id).await {
+            Ok(voice) => voice.map(|voice| voice.name),
+            Err(e) => {
+                tracing::warn!(voice_id = %voice_id, error = %e, "Failed to load voice for audio tags");
+                None
+            }
+        };
+
+        AudioMetadata {
+            title,
+            album,
+            artist,
+            track_number: Some(segment_index + 1),
+            chapters: Vec::new(),
+        }
+    }
+}
@@ -11,7 +11,8 @@ use config::{Config, ConfigError as ConfigCrateError, Environment, File};
 use std::path::Path;
 use thiserror::Error;
 
-use super::types::AppConfig;
+use super::types::{AppConfig, AudioCacheBackend, CorsConfig, QueueBackend, TtsEngineKind};
+use crate::application::PregenerationSchedule;
 
 /// 配置加载错误
 #[derive(Debug, Error)]
@@ -66,20 +67,79 @@ pub fn load_config_from_path(config_path: Option<&Path>) -> Result<AppConfig, Co
     builder = builder
         .set_default("server.host", "0.0.0.0")?
         .set_default("server.port", 5060)?
+        .set_default("server.trust_forwarded_for", false)?
+        .set_default("server.cors.allow_credentials", false)?
+        .set_default("server.cors.max_age_secs", 3600)?
+        .set_default("server.tls.enabled", false)?
+        .set_default("server.tls.reload_interval_secs", 0)?
+        .set_default("server.compression.enabled", true)?
+        .set_default("server.compression.gzip", true)?
+        .set_default("server.compression.brotli", true)?
+        .set_default("server.compression.min_size_bytes", 1024)?
+        .set_default("tts.engine", "http")?
         .set_default("tts.url", "http://localhost:8000")?
         .set_default("tts.timeout_secs", 120)?
         .set_default("tts.max_retries", 0)?
+        .set_default("tts.requests_per_minute", 0)?
+        .set_default("tts.max_concurrent_requests", 0)?
+        .set_default("tts.balance", "round_robin")?
+        .set_default("tts.failure_threshold", 3)?
+        .set_default("tts.cooldown_secs", 30)?
+        .set_default("tts.hedge_delay_ms", 2000)?
+        .set_default("tts.health_check_interval_secs", 30)?
+        .set_default("tts.streaming", false)?
+        .set_default("tts.batch", false)?
+        .set_default("tts.reference_mode", "url")?
+        .set_default("tts.ssml", false)?
+        .set_default("tts.ssml_paragraph_break_ms", 500)?
+        .set_default("tts.batch_size", 1)?
+        .set_default("tts.validate_audio", true)?
+        .set_default("tts.shutdown_drain_secs", 30)?
+        .set_default("tts.speaker_detection", false)?
+        .set_default("tts.warmup", false)?
+        .set_default("tts.warmup_text", "你好。")?
+        .set_default("tts.warmup_timeout_secs", 300)?
         .set_default("database.path", "data/rovel.db")?
         .set_default("database.max_connections", 5)?
         .set_default("storage.audio_dir", "data/audio")?
         .set_default("storage.novels_dir", "data/novels")?
         .set_default("storage.voices_dir", "data/voices")?
+        .set_default("storage.presets_dir", "data/presets")?
+        .set_default("storage.exports_dir", "data/exports")?
         .set_default("storage.max_size_bytes", 0)?
         .set_default("storage.max_upload_size", 10 * 1024 * 1024)?
+        .set_default("storage.audio_cache_backend", "sled")?
+        .set_default("storage.compress_audio_cache", false)?
+        .set_default("storage.memory_cache_bytes", 64_u64 * 1024 * 1024)?
+        .set_default("storage.novel_cache_quota_bytes", 0)?
+        .set_default("storage.cache_flush_interval_secs", 60)?
+        .set_default("storage.store_session_audio", true)?
+        .set_default("prefetch.enabled", true)?
+        .set_default("prefetch.before", 2)?
+        .set_default("prefetch.after", 3)?
+        .set_default("prefetch.max_before", 10)?
+        .set_default("prefetch.max_after", 20)?
+        .set_default("prefetch.max_batch", 200)?
+        .set_default("prefetch.idle_pause_secs", 600)?
+        .set_default("sessions.max_active", 0)?
+        .set_default("sessions.max_per_client", 0)?
+        .set_default("pregeneration.schedule", "")?
+        .set_default("queue.backend", "memory")?
+        .set_default("queue.capacity", 1000)?
+        .set_default("queue.key_prefix", "rovel:tasks")?
+        .set_default("queue.group", "rovel-workers")?
+        .set_default("queue.read_ahead", 4)?
+        .set_default("queue.claim_idle_secs", 600)?
+        .set_default("auth.enabled", false)?
+        .set_default("auth.token_ttl_secs", 30_u64 * 24 * 60 * 60)?
+        .set_default("auth.allow_registration", true)?
         .set_default("gc.enabled", true)?
         .set_default("gc.interval_secs", 3600)?
         .set_default("gc.session_expire_secs", 86400)?
+        .set_default("gc.session_sweep_interval_secs", 60)?
         .set_default("gc.max_storage_bytes", 10_u64 * 1024 * 1024 * 1024)?
+        .set_default("gc.window_evict_delay_secs", 300)?
+        .set_default("gc.cache_ttl_days", 0)?
         .set_default("log.level", "info")?
         .set_default("log.json", false)?;
 
@@ -128,12 +188,69 @@ fn validate_config(config: &AppConfig) -> Result<(), ConfigError> {
         ));
     }
 
-    // 验证 TTS URL
-    if config.tts.url.is_empty() {
+    validate_cors(&config.server.cors)?;
+    if config.server.tls.enabled
+        && (config.server.tls.cert_path.is_none() || config.server.tls.key_path.is_none())
+    {
+        return Err(ConfigError::ValidationError(
+            "server.tls.cert_path and server.tls.key_path are required when TLS is enabled".to_string(),
+        ));
+    }
+
+    // 验证 TTS URL（fake 引擎不需要）
+    if config.tts.engine == TtsEngineKind::Http
+        && config.tts.backend_urls().iter().any(|url| url.is_empty())
+    {
         return Err(ConfigError::ValidationError(
             "TTS URL cannot be empty".to_string(),
         ));
     }
+    if config.tts.batch_size == 0 {
+        return Err(ConfigError::ValidationError(
+            "TTS batch size cannot be 0".to_string(),
+        ));
+    }
+    if config.tts.hedge_url.as_deref() == Some("")
+        || config.tts.engines.values().any(|e| e.hedge_url.as_deref() == Some(""))
+    {
+        return Err(ConfigError::ValidationError(
+            "TTS hedge URL cannot be empty".to_string(),
+        ));
+    }
+    let uses_local = config.tts.engine == TtsEngineKind::Local
+        || config.tts.engines.values().any(|e| e.engine == TtsEngineKind::Local);
+    if uses_local && config.tts.local.model_path.is_none() {
+        return Err(ConfigError::ValidationError(
+            "tts.local.model_path is required for the local TTS engine".to_string(),
+        ));
+    }
+    if config.tts.warmup && config.tts.warmup_text.trim().is_empty() {
+        return Err(ConfigError::ValidationError(
+            "TTS warmup text cannot be empty when warmup is enabled".to_string(),
+        ));
+    }
+    if config.tts.failure_threshold == 0 {
+        return Err(ConfigError::ValidationError(
+            "TTS failure threshold cannot be 0".to_string(),
+        ));
+    }
+
+    // 验证命名 TTS 引擎
+    for (name, engine) in &config.tts.engines {
+        if name == "default" {
+            return Err(ConfigError::ValidationError(
+                "TTS engine name 'default' is reserved for the top-level [tts] config".to_string(),
+            ));
+        }
+        if engine.engine == TtsEngineKind::Http
+            && engine.backend_urls().iter().any(|url| url.is_empty())
+        {
+            return Err(ConfigError::ValidationError(format!(
+                "TTS engine '{}' URL cannot be empty",
+                name
+            )));
+        }
+    }
 
     // 验证数据库路径
     if config.database.path.is_empty() {
@@ -142,6 +259,76 @@ fn validate_config(config: &AppConfig) -> Result<(), ConfigError> {
         ));
     }
 
+    // 验证参考音频归一化配置
+    if config.audio.reference.enabled && config.audio.reference.sample_rate == 0 {
+        return Err(ConfigError::ValidationError(
+            "Reference audio sample rate cannot be 0 when normalization is enabled".to_string(),
+        ));
+    }
+
+    // 验证预取配置
+    if config.prefetch.before > config.prefetch.max_before
+        || config.prefetch.after > config.prefetch.max_after
+    {
+        return Err(ConfigError::ValidationError(
+            "Prefetch window cannot exceed max_before / max_after".to_string(),
+        ));
+    }
+    if config.prefetch.max_batch == 0 {
+        return Err(ConfigError::ValidationError(
+            "Prefetch max_batch must be greater than 0".to_string(),
+        ));
+    }
+
+    // 验证预生成时段
+    if let Err(e) = PregenerationSchedule::parse(&config.pregeneration.schedule) {
+        return Err(ConfigError::ValidationError(format!("Invalid pregeneration.schedule: {}", e)));
+    }
+
+    // 验证推理队列配置
+    if config.queue.capacity == 0 {
+        return Err(ConfigError::ValidationError(
+            "queue.capacity must be greater than 0".to_string(),
+        ));
+    }
+    if config.queue.backend == QueueBackend::Redis {
+        if config.queue.redis_url.as_deref().is_none_or(|url| url.trim().is_empty()) {
+            return Err(ConfigError::ValidationError(
+                "queue.redis_url is required for the Redis queue".to_string(),
+            ));
+        }
+        if config.queue.read_ahead == 0 {
+            return Err(ConfigError::ValidationError(
+                "queue.read_ahead must be greater than 0".to_string(),
+            ));
+        }
+        // 其他实例推理的片段要能被提交任务的实例读取（sled 缓存由单个进程独占）
+        if config.storage.audio_cache_backend != AudioCacheBackend::File {
+            return Err(ConfigError::ValidationError(
+                "The Redis queue requires storage.audio_cache_backend = \"file\"".to_string(),
+            ));
+        }
+    }
+
+    // 验证用户账号配置
+    if config.auth.enabled {
+        if config.auth.jwt_secret.as_deref().is_none_or(|secret| secret.trim().is_empty()) {
+            return Err(ConfigError::ValidationError(
+                "auth.jwt_secret is required when auth is enabled".to_string(),
+            ));
+        }
+        if config.auth.token_ttl_secs == 0 {
+            return Err(ConfigError::ValidationError(
+                "auth.token_ttl_secs must be greater than 0".to_string(),
+            ));
+        }
+    }
+    if config.auth.admin_token.as_deref().is_some_and(|token| token.trim().is_empty()) {
+        return Err(ConfigError::ValidationError(
+            "auth.admin_token cannot be empty".to_string(),
+        ));
+    }
+
     // 验证 GC 配置
     if config.gc.enabled && config.gc.interval_secs == 0 {
         return Err(ConfigError::ValidationError(
@@ -152,20 +339,144 @@ fn validate_config(config: &AppConfig) -> Result<(), ConfigError> {
     Ok(())
 }
 
+/// 验证跨域配置：各项可解析，携带凭据时来源不能为 "*"（浏览器会拒绝）
+fn validate_cors(cors: &CorsConfig) -> Result<(), ConfigError> {
+    let invalid = |field: &str, value: &str| {
+        ConfigError::ValidationError(format!("Invalid server.cors.{} entry: {:?}", field, value))
+    };
+
+    if cors.allowed_origins.is_empty() {
+        return Err(ConfigError::ValidationError(
+            "server.cors.allowed_origins cannot be empty".to_string(),
+        ));
+    }
+    for origin in cors.allowed_origins.iter().filter(|origin| *origin != "*") {
+        http::HeaderValue::from_str(origin).map_err(|_| invalid("allowed_origins", origin))?;
+    }
+    for method in cors.allowed_methods.iter().filter(|method| *method != "*") {
+        http::Method::from_bytes(method.as_bytes()).map_err(|_| invalid("allowed_methods", method))?;
+    }
+    for header in cors.allowed_headers.iter().filter(|header| *header != "*") {
+        http::HeaderName::from_bytes(header.as_bytes()).map_err(|_| invalid("allowed_headers", header))?;
+    }
+    if cors.allow_credentials && cors.any_origin() {
+        return Err(ConfigError::ValidationError(
+            "server.cors.allowed_origins must list explicit origins when allow_credentials is enabled".to_string(),
+        ));
+    }
+    Ok(())
+}
+
 /// 打印配置信息（用于启动时日志）
 pub fn print_config(config: &AppConfig) {
     tracing::info!("=== Application Configuration ===");
     tracing::info!("Server: {}:{}", config.server.host, config.server.port);
     tracing::info!("Public Base URL: {}", config.server.public_base_url());
-    tracing::info!("TTS URL: {}", config.tts.url);
+    if config.server.compression.enabled {
+        tracing::info!(
+            "Compression: gzip {}, brotli {}, min {} bytes",
+            config.server.compression.gzip,
+            config.server.compression.brotli,
+            config.server.compression.min_size_bytes
+        );
+    }
+    if config.server.tls.enabled {
+        if let (Some(cert_path), Some(key_path)) = (&config.server.tls.cert_path, &config.server.tls.key_path) {
+            tracing::info!("TLS: cert {}, key {}", cert_path.display(), key_path.display());
+        }
+    }
+    tracing::info!(
+        "CORS: origins [{}], credentials {}",
+        config.server.cors.allowed_origins.join(", "),
+        config.server.cors.allow_credentials
+    );
+    tracing::info!("TTS Engine: {:?}", config.tts.engine);
+    tracing::info!("TTS URL: {}", config.tts.backend_urls().join(", "));
+    tracing::info!("TTS Balance: {:?}", config.tts.balance);
+    if let Some(hedge_url) = &config.tts.hedge_url {
+        tracing::info!("TTS Hedge: {} (after {}ms)", hedge_url, config.tts.hedge_delay_ms);
+    }
     tracing::info!("TTS Timeout: {}s", config.tts.timeout_secs);
+    if !config.tts.rate_limit().is_unlimited() {
+        tracing::info!(
+            "TTS Rate Limit: {} requests/min, {} concurrent (0 = unlimited)",
+            config.tts.requests_per_minute,
+            config.tts.max_concurrent_requests
+        );
+    }
+    tracing::info!("TTS Health Check Interval: {}s", config.tts.health_check_interval_secs);
+    tracing::info!("TTS Streaming: {}", config.tts.streaming);
+    tracing::info!("TTS Batch Size: {}", config.tts.batch_size);
+    tracing::info!("TTS Validate Audio: {}", config.tts.validate_audio);
+    tracing::info!("TTS Shutdown Drain: {}s", config.tts.shutdown_drain_secs);
+    if let Some(seed) = config.tts.seed {
+        tracing::info!("TTS Seed: {}", seed);
+    }
+    tracing::info!("TTS Reference Mode: {:?}", config.tts.reference_mode);
+    tracing::info!("TTS SSML: {}", config.tts.ssml);
+    tracing::info!(
+        "TTS API Key: {}",
+        if config.tts.api_key.is_some() { "configured" } else { "none" }
+    );
+    tracing::info!("Speaker Detection: {}", config.tts.speaker_detection);
+    if config.tts.warmup {
+        tracing::info!("TTS Warmup: enabled (timeout {}s)", config.tts.warmup_timeout_secs);
+    }
+    for (name, engine) in &config.tts.engines {
+        tracing::info!(
+            "TTS Engine [{}]: {:?} {}",
+            name,
+            engine.engine,
+            engine.backend_urls().join(", ")
+        );
+    }
     tracing::info!("Database: {}", config.database.path);
     tracing::info!("Database Max Connections: {}", config.database.max_connections);
     tracing::info!("Audio Directory: {:?}", config.storage.audio_dir);
+    if config.prefetch.enabled {
+        tracing::info!(
+            "Prefetch Window: -{} / +{} segments",
+            config.prefetch.before,
+            config.prefetch.after
+        );
+        if config.prefetch.idle_pause_secs > 0 {
+            tracing::info!("Prefetch Idle Pause: {}s", config.prefetch.idle_pause_secs);
+        }
+    }
+    if config.sessions.max_active > 0 || config.sessions.max_per_client > 0 {
+        tracing::info!(
+            "Session Limits: {} total / {} per client (0 = unlimited)",
+            config.sessions.max_active,
+            config.sessions.max_per_client
+        );
+    }
+    if !config.pregeneration.schedule.trim().is_empty() {
+        tracing::info!("Pregeneration Schedule: {}", config.pregeneration.schedule);
+    }
+    tracing::info!("Task Queue: {:?} (capacity {})", config.queue.backend, config.queue.capacity);
+    if config.auth.enabled {
+        tracing::info!(
+            "Auth Enabled: token TTL {}s, registration {}",
+            config.auth.token_ttl_secs,
+            if config.auth.allow_registration { "open" } else { "closed" }
+        );
+    }
+    tracing::info!(
+        "Admin API: {}",
+        match (config.auth.admin_token.is_some(), config.auth.enabled) {
+            (true, true) => "admin token or admin users",
+            (true, false) => "admin token",
+            (false, true) => "admin users",
+            (false, false) => "disabled",
+        }
+    );
     tracing::info!("GC Enabled: {}", config.gc.enabled);
     if config.gc.enabled {
         tracing::info!("GC Interval: {}s", config.gc.interval_secs);
         tracing::info!("Session Expire: {}s", config.gc.session_expire_secs);
+        if config.gc.cache_ttl_days > 0 {
+            tracing::info!("Cache TTL: {} days", config.gc.cache_ttl_days);
+        }
     }
     tracing::info!("Log Level: {}", config.log.level);
     tracing::info!("=================================");
@@ -196,6 +507,31 @@ mod tests {
         assert!(validate_config(&config).is_err());
     }
 
+    #[test]
+    fn test_validation_of_cors() {
+        let mut config = AppConfig::default();
+        config.server.cors.allow_credentials = true;
+        assert!(validate_config(&config).is_err());
+
+        config.server.cors.allowed_origins = vec!["https://reader.example.com".to_string()];
+        assert!(validate_config(&config).is_ok());
+
+        config.server.cors.allowed_methods = vec!["GET".to_string(), "bad method".to_string()];
+        assert!(validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_validation_error_for_tls_without_cert() {
+        let mut config = AppConfig::default();
+        config.server.tls.enabled = true;
+        config.server.tls.cert_path = Some("certs/fullchain.pem".into());
+        assert!(validate_config(&config).is_err());
+
+        config.server.tls.key_path = Some("certs/privkey.pem".into());
+        assert!(validate_config(&config).is_ok());
+        assert_eq!(config.server.public_base_url(), "https://localhost:5060");
+    }
+
     #[test]
     fn test_validation_error_for_empty_tts_url() {
         let mut config = AppConfig::default();
@@ -209,4 +545,17 @@ mod tests {
         config.database.path = String::new();
         assert!(validate_config(&config).is_err());
     }
+
+    #[test]
+    fn test_validation_of_redis_queue() {
+        let mut config = AppConfig::default();
+        config.queue.backend = QueueBackend::Redis;
+        assert!(validate_config(&config).is_err());
+
+        config.queue.redis_url = Some("redis://127.0.0.1:6379/0".to_string());
+        assert!(validate_config(&config).is_err());
+
+        config.storage.audio_cache_backend = AudioCacheBackend::File;
+        assert!(validate_config(&config).is_ok());
+    }
 }
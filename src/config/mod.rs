@@ -10,6 +10,7 @@ mod types;
 
 pub use loader::{load_config, print_config, ConfigError};
 pub use types::{
-    AppConfig, AudioConfig, DatabaseConfig, GcConfig, LogConfig, ServerConfig, StaticFilesConfig,
-    StorageConfig, TtsConfig,
+    AppConfig, AudioCacheBackend, AudioConfig, CompressionConfig, CorsConfig, DatabaseConfig, FakeTtsConfig, GcConfig, LocalTtsConfig, LogConfig,
+    PrefetchConfig, QueueBackend, QueueConfig, ReferenceAudioConfig, ServerConfig, StaticFilesConfig, StorageConfig, TlsConfig, TtsConfig,
+    TtsEngineConfig, TtsEngineKind,
 };
@@ -2,13 +2,19 @@
 //!
 //! 定义所有配置结构体
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
+use uuid::Uuid;
 
-use crate::application::ports::AudioFormat;
+use crate::application::ports::{
+    AudioFormat, ReferenceNormalizeConfig, ResampleQuality, TranscodeConfig, WindowConfig,
+};
+use crate::application::{PrefetchSettings, PregenerationSchedule, SessionLimits};
+use crate::infrastructure::adapters::{BalanceStrategy, RateLimitConfig, ReferenceMode};
 
 /// 应用主配置
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AppConfig {
     /// 服务器配置
     #[serde(default)]
@@ -30,6 +36,26 @@ pub struct AppConfig {
     #[serde(default)]
     pub storage: StorageConfig,
 
+    /// 播放窗口预取配置
+    #[serde(default)]
+    pub prefetch: PrefetchConfig,
+
+    /// 播放会话配置
+    #[serde(default)]
+    pub sessions: SessionsConfig,
+
+    /// 整书预生成配置
+    #[serde(default)]
+    pub pregeneration: PregenerationConfig,
+
+    /// 推理队列配置
+    #[serde(default)]
+    pub queue: QueueConfig,
+
+    /// 用户账号配置
+    #[serde(default)]
+    pub auth: AuthConfig,
+
     /// GC 配置
     #[serde(default)]
     pub gc: GcConfig,
@@ -39,22 +65,35 @@ pub struct AppConfig {
     pub log: LogConfig,
 }
 
-impl Default for AppConfig {
-    fn default() -> Self {
-        Self {
-            server: ServerConfig::default(),
-            tts: TtsConfig::default(),
-            audio: AudioConfig::default(),
-            database: DatabaseConfig::default(),
-            storage: StorageConfig::default(),
-            gc: GcConfig::default(),
-            log: LogConfig::default(),
+/// 脱敏后替换密钥的占位符
+const REDACTED: &str = "***";
+
+impl AppConfig {
+    /// 去掉密钥后的配置副本（供管理接口查看运行配置）
+    ///
+    /// 已设置的密钥替换为 `***`，未设置的保持为空，便于区分
+    pub fn redacted(&self) -> AppConfig {
+        fn redact(secret: &mut Option<String>) {
+            if secret.is_some() {
+                *secret = Some(REDACTED.to_string());
+            }
         }
+
+        let mut config = self.clone();
+        redact(&mut config.tts.api_key);
+        for engine in config.tts.engines.values_mut() {
+            redact(&mut engine.api_key);
+        }
+        redact(&mut config.storage.cache_encryption_key);
+        redact(&mut config.auth.jwt_secret);
+        redact(&mut config.auth.admin_token);
+        redact(&mut config.queue.redis_url);
+        config
     }
 }
 
 /// 服务器配置
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServerConfig {
     /// 监听地址
     #[serde(default = "default_host")]
@@ -69,13 +108,165 @@ pub struct ServerConfig {
     #[serde(default)]
     pub base_url: Option<String>,
 
+    /// 使用 X-Forwarded-For 中的第一个地址作为客户端 IP（仅在反向代理后开启）
+    #[serde(default)]
+    pub trust_forwarded_for: bool,
+
     /// 静态文件服务配置
     #[serde(default)]
     pub static_files: StaticFilesConfig,
+
+    /// 跨域（CORS）配置
+    #[serde(default)]
+    pub cors: CorsConfig,
+
+    /// HTTPS 配置
+    #[serde(default)]
+    pub tls: TlsConfig,
+
+    /// 响应压缩配置
+    #[serde(default)]
+    pub compression: CompressionConfig,
+}
+
+/// 响应压缩配置
+///
+/// 只压缩 JSON 和文本类响应（API、网页资源），音频等已压缩的内容原样返回
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompressionConfig {
+    /// 是否启用响应压缩
+    #[serde(default = "default_compression_enabled")]
+    pub enabled: bool,
+
+    /// 启用 gzip
+    #[serde(default = "default_compression_enabled")]
+    pub gzip: bool,
+
+    /// 启用 brotli
+    #[serde(default = "default_compression_enabled")]
+    pub brotli: bool,
+
+    /// 小于该大小（字节）的响应不压缩
+    #[serde(default = "default_compression_min_size")]
+    pub min_size_bytes: u16,
+}
+
+fn default_compression_enabled() -> bool {
+    true
+}
+
+fn default_compression_min_size() -> u16 {
+    1024
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_compression_enabled(),
+            gzip: default_compression_enabled(),
+            brotli: default_compression_enabled(),
+            min_size_bytes: default_compression_min_size(),
+        }
+    }
+}
+
+/// HTTPS 配置
+///
+/// 启用后直接以 HTTPS（含 WSS）提供服务。证书由外部 ACME 客户端（certbot、acme.sh 等）签发和续期，
+/// 按 `reload_interval_secs` 定期重新读取证书文件，续期后无需重启
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TlsConfig {
+    /// 是否启用 HTTPS
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// 证书链文件（PEM）
+    #[serde(default)]
+    pub cert_path: Option<PathBuf>,
+
+    /// 私钥文件（PEM）
+    #[serde(default)]
+    pub key_path: Option<PathBuf>,
+
+    /// 重新读取证书文件的间隔（秒），0 表示不重新读取
+    #[serde(default)]
+    pub reload_interval_secs: u64,
+}
+
+/// 跨域（CORS）配置
+///
+/// 默认允许任意来源，不携带凭据（登录令牌通过 Authorization 请求头传递，不依赖 Cookie）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorsConfig {
+    /// 允许的来源（如 "https://reader.example.com"），"*" 表示任意来源
+    #[serde(default = "default_cors_wildcard")]
+    pub allowed_origins: Vec<String>,
+
+    /// 允许的请求方法，"*" 表示任意方法
+    #[serde(default = "default_cors_wildcard")]
+    pub allowed_methods: Vec<String>,
+
+    /// 允许的请求头，"*" 表示任意请求头
+    #[serde(default = "default_cors_allowed_headers")]
+    pub allowed_headers: Vec<String>,
+
+    /// 允许携带凭据（Cookie 等）；开启时来源必须逐个列出
+    #[serde(default)]
+    pub allow_credentials: bool,
+
+    /// 预检请求结果的缓存时长（秒）
+    #[serde(default = "default_cors_max_age_secs")]
+    pub max_age_secs: u64,
+}
+
+fn default_cors_wildcard() -> Vec<String> {
+    vec!["*".to_string()]
+}
+
+fn default_cors_allowed_headers() -> Vec<String> {
+    vec![
+        "authorization".to_string(),
+        "content-type".to_string(),
+        "x-request-id".to_string(),
+        "x-admin-token".to_string(),
+    ]
+}
+
+fn default_cors_max_age_secs() -> u64 {
+    3600
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            allowed_origins: default_cors_wildcard(),
+            allowed_methods: default_cors_wildcard(),
+            allowed_headers: default_cors_allowed_headers(),
+            allow_credentials: false,
+            max_age_secs: default_cors_max_age_secs(),
+        }
+    }
+}
+
+impl CorsConfig {
+    /// 是否允许任意来源
+    pub fn any_origin(&self) -> bool {
+        self.allowed_origins.iter().any(|origin| origin == "*")
+    }
+
+    /// 是否允许任意请求方法
+    pub fn any_method(&self) -> bool {
+        self.allowed_methods.iter().any(|method| method == "*")
+    }
+
+    /// 是否允许任意请求头
+    pub fn any_header(&self) -> bool {
+        self.allowed_headers.iter().any(|header| header == "*")
+    }
 }
 
 /// 静态文件服务配置
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StaticFilesConfig {
     /// 是否启用静态文件服务
     #[serde(default = "default_static_enabled")]
@@ -126,7 +317,11 @@ impl Default for ServerConfig {
             host: default_host(),
             port: default_port(),
             base_url: None,
+            trust_forwarded_for: false,
             static_files: StaticFilesConfig::default(),
+            cors: CorsConfig::default(),
+            tls: TlsConfig::default(),
+            compression: CompressionConfig::default(),
         }
     }
 }
@@ -145,18 +340,44 @@ impl ServerConfig {
             } else {
                 &self.host
             };
-            format!("http://{}:{}", host, self.port)
+            let scheme = if self.tls.enabled { "https" } else { "http" };
+            format!("{}://{}:{}", scheme, host, self.port)
         })
     }
 }
 
 /// TTS 引擎配置
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TtsConfig {
     /// TTS 服务基础 URL
     #[serde(default = "default_tts_url")]
     pub url: String,
 
+    /// 多个 TTS 服务地址（非空时替代 url，请求在这些后端间负载均衡）
+    #[serde(default)]
+    pub urls: Vec<String>,
+
+    /// 多后端负载均衡策略
+    #[serde(default)]
+    pub balance: BalanceStrategy,
+
+    /// 后端连续失败多少次后暂停调度
+    #[serde(default = "default_tts_failure_threshold")]
+    pub failure_threshold: u32,
+
+    /// 不健康后端的暂停时间（秒）
+    #[serde(default = "default_tts_cooldown")]
+    pub cooldown_secs: u64,
+
+    /// 备用 TTS 服务地址：当前播放片段的请求超过 hedge_delay_ms 未完成时，
+    /// 向该地址发送同一请求，采用先返回的结果
+    #[serde(default)]
+    pub hedge_url: Option<String>,
+
+    /// 对冲请求的等待时间（毫秒）
+    #[serde(default = "default_tts_hedge_delay")]
+    pub hedge_delay_ms: u64,
+
     /// 请求超时时间（秒）
     #[serde(default = "default_tts_timeout")]
     pub timeout_secs: u64,
@@ -164,6 +385,270 @@ pub struct TtsConfig {
     /// 最大重试次数
     #[serde(default)]
     pub max_retries: u32,
+
+    /// 每分钟最多请求数（0 表示不限制）
+    #[serde(default)]
+    pub requests_per_minute: u32,
+
+    /// 同时进行的最多请求数（0 表示不限制）
+    #[serde(default)]
+    pub max_concurrent_requests: usize,
+
+    /// 健康检查间隔（秒），0 表示不检查
+    #[serde(default = "default_tts_health_check_interval")]
+    pub health_check_interval_secs: u64,
+
+    /// 流式推理：合成中的音频即可播放，无需等待整段合成完成
+    #[serde(default)]
+    pub streaming: bool,
+
+    /// 服务端是否支持批量推理接口（/api/tts/infer_batch）
+    #[serde(default)]
+    pub batch: bool,
+
+    /// 参考音频传递方式：url（TTS 服务回调下载）、base64、multipart（随请求上传）
+    #[serde(default)]
+    pub reference_mode: ReferenceMode,
+
+    /// TTS 服务 API Key（默认以 Authorization: Bearer 发送）
+    #[serde(default)]
+    pub api_key: Option<String>,
+
+    /// 发送 API Key 的 header 名（如 X-API-Key），未设置时使用 Bearer
+    #[serde(default)]
+    pub api_key_header: Option<String>,
+
+    /// 引擎支持 SSML：推理文本转换为 SSML（段落停顿、对话强调）
+    #[serde(default)]
+    pub ssml: bool,
+
+    /// SSML 段落停顿时长（毫秒）
+    #[serde(default = "default_tts_ssml_paragraph_break")]
+    pub ssml_paragraph_break_ms: u32,
+
+    /// 批量推理的最大片段数：同一会话、同一音色的相邻片段合并为一次请求（1 表示不批量）
+    #[serde(default = "default_tts_batch_size")]
+    pub batch_size: usize,
+
+    /// 校验 TTS 返回的音频（WAV 头、时长与文本长度），不合格时任务失败
+    #[serde(default = "default_tts_validate_audio")]
+    pub validate_audio: bool,
+
+    /// 关闭服务时等待进行中的推理完成的最长时间（秒），剩余任务下次启动时重新排队
+    #[serde(default = "default_tts_shutdown_drain")]
+    pub shutdown_drain_secs: u64,
+
+    /// 固定随机种子：支持的 TTS 服务对相同文本与音色生成相同音频，
+    /// 缓存丢失后重新合成不会出现音色漂移
+    #[serde(default)]
+    pub seed: Option<u64>,
+
+    /// 对话说话人识别：对话片段按小说的角色配音表选择音色
+    #[serde(default)]
+    pub speaker_detection: bool,
+
+    /// 启动预热：对每个音色执行一次短文本推理，完成后才报告就绪（/api/ready）
+    #[serde(default)]
+    pub warmup: bool,
+
+    /// 预热使用的文本
+    #[serde(default = "default_tts_warmup_text")]
+    pub warmup_text: String,
+
+    /// 预热总超时（秒），超时后不再等待，直接报告就绪
+    #[serde(default = "default_tts_warmup_timeout")]
+    pub warmup_timeout_secs: u64,
+
+    /// 默认引擎类型：http（默认）、fake、local
+    #[serde(default)]
+    pub engine: TtsEngineKind,
+
+    /// Fake 引擎配置（engine = "fake" 时使用）
+    #[serde(default)]
+    pub fake: FakeTtsConfig,
+
+    /// 本地引擎配置（engine = "local" 时使用）
+    #[serde(default)]
+    pub local: LocalTtsConfig,
+
+    /// 额外的命名 TTS 引擎（音色通过 engine 字段选择，未指定时使用顶层配置）
+    #[serde(default)]
+    pub engines: BTreeMap<String, TtsEngineConfig>,
+}
+
+/// TTS 引擎类型
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TtsEngineKind {
+    /// 调用外部 HTTP TTS 服务
+    #[default]
+    Http,
+    /// 返回固定音频，不调用 TTS 服务（演示/测试部署）
+    Fake,
+    /// 进程内本地模型（需以 `local-tts` feature 编译）
+    Local,
+}
+
+/// 本地 TTS 引擎配置（`[tts.local]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalTtsConfig {
+    /// Piper 模型文件（.onnx，同目录需有 .onnx.json 配置，仅支持 text 音素的模型）
+    #[serde(default)]
+    pub model_path: Option<PathBuf>,
+
+    /// onnxruntime 动态库路径，未设置时按 `ORT_DYLIB_PATH` 或系统库搜索路径查找
+    #[serde(default)]
+    pub onnxruntime_path: Option<PathBuf>,
+
+    /// 最大并发推理数
+    #[serde(default = "default_local_tts_max_concurrent")]
+    pub max_concurrent: usize,
+}
+
+impl Default for LocalTtsConfig {
+    fn default() -> Self {
+        Self {
+            model_path: None,
+            onnxruntime_path: None,
+            max_concurrent: default_local_tts_max_concurrent(),
+        }
+    }
+}
+
+fn default_local_tts_max_concurrent() -> usize {
+    1
+}
+
+/// Fake TTS 引擎配置（`[tts.fake]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FakeTtsConfig {
+    /// 固定返回的音频文件（未设置时返回静音）
+    #[serde(default)]
+    pub audio_path: Option<PathBuf>,
+
+    /// 音频时长（毫秒）
+    #[serde(default = "default_fake_tts_duration")]
+    pub duration_ms: u64,
+
+    /// 采样率
+    #[serde(default = "default_fake_tts_sample_rate")]
+    pub sample_rate: u32,
+}
+
+impl Default for FakeTtsConfig {
+    fn default() -> Self {
+        Self {
+            audio_path: None,
+            duration_ms: default_fake_tts_duration(),
+            sample_rate: default_fake_tts_sample_rate(),
+        }
+    }
+}
+
+fn default_fake_tts_duration() -> u64 {
+    5000
+}
+
+fn default_fake_tts_sample_rate() -> u32 {
+    22050
+}
+
+/// 命名 TTS 引擎配置（`[tts.engines.<name>]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TtsEngineConfig {
+    /// 引擎类型：http（默认）、fake、local
+    #[serde(default)]
+    pub engine: TtsEngineKind,
+
+    /// TTS 服务基础 URL
+    #[serde(default)]
+    pub url: String,
+
+    /// 多个 TTS 服务地址（非空时替代 url）
+    #[serde(default)]
+    pub urls: Vec<String>,
+
+    /// 多后端负载均衡策略
+    #[serde(default)]
+    pub balance: BalanceStrategy,
+
+    /// 备用 TTS 服务地址（对冲请求，等待时间使用 [tts] hedge_delay_ms）
+    #[serde(default)]
+    pub hedge_url: Option<String>,
+
+    /// 请求超时时间（秒）
+    #[serde(default = "default_tts_timeout")]
+    pub timeout_secs: u64,
+
+    /// 最大重试次数
+    #[serde(default)]
+    pub max_retries: u32,
+
+    /// 该引擎每分钟最多请求数（0 表示不限制）
+    #[serde(default)]
+    pub requests_per_minute: u32,
+
+    /// 该引擎同时进行的最多请求数（0 表示不限制）
+    #[serde(default)]
+    pub max_concurrent_requests: usize,
+
+    /// 服务端是否支持批量推理接口
+    #[serde(default)]
+    pub batch: bool,
+
+    /// 参考音频传递方式
+    #[serde(default)]
+    pub reference_mode: ReferenceMode,
+
+    /// 该引擎的 API Key（未设置时使用 [tts] api_key）
+    #[serde(default)]
+    pub api_key: Option<String>,
+
+    /// 发送 API Key 的 header 名（未设置时使用 [tts] api_key_header）
+    #[serde(default)]
+    pub api_key_header: Option<String>,
+
+    /// 引擎支持 SSML（停顿时长使用 [tts] ssml_paragraph_break_ms）
+    #[serde(default)]
+    pub ssml: bool,
+}
+
+impl TtsConfig {
+    /// 默认引擎的所有后端地址
+    pub fn backend_urls(&self) -> Vec<String> {
+        resolve_backend_urls(&self.url, &self.urls)
+    }
+
+    /// 默认引擎的限流配置
+    pub fn rate_limit(&self) -> RateLimitConfig {
+        RateLimitConfig {
+            requests_per_minute: self.requests_per_minute,
+            max_concurrent: self.max_concurrent_requests,
+        }
+    }
+}
+
+impl TtsEngineConfig {
+    /// 该引擎的所有后端地址
+    pub fn backend_urls(&self) -> Vec<String> {
+        resolve_backend_urls(&self.url, &self.urls)
+    }
+
+    /// 该引擎的限流配置
+    pub fn rate_limit(&self) -> RateLimitConfig {
+        RateLimitConfig {
+            requests_per_minute: self.requests_per_minute,
+            max_concurrent: self.max_concurrent_requests,
+        }
+    }
+}
+
+fn resolve_backend_urls(url: &str, urls: &[String]) -> Vec<String> {
+    if urls.is_empty() {
+        vec![url.to_string()]
+    } else {
+        urls.to_vec()
+    }
 }
 
 fn default_tts_url() -> String {
@@ -174,18 +659,86 @@ fn default_tts_timeout() -> u64 {
     120
 }
 
+fn default_tts_hedge_delay() -> u64 {
+    2000
+}
+
+fn default_tts_health_check_interval() -> u64 {
+    30
+}
+
+fn default_tts_batch_size() -> usize {
+    1
+}
+
+fn default_tts_ssml_paragraph_break() -> u32 {
+    500
+}
+
+fn default_tts_validate_audio() -> bool {
+    true
+}
+
+fn default_tts_shutdown_drain() -> u64 {
+    30
+}
+
+fn default_tts_warmup_text() -> String {
+    "你好。".to_string()
+}
+
+fn default_tts_warmup_timeout() -> u64 {
+    300
+}
+
+fn default_tts_failure_threshold() -> u32 {
+    3
+}
+
+fn default_tts_cooldown() -> u64 {
+    30
+}
+
 impl Default for TtsConfig {
     fn default() -> Self {
         Self {
             url: default_tts_url(),
+            urls: Vec::new(),
+            balance: BalanceStrategy::default(),
+            failure_threshold: default_tts_failure_threshold(),
+            cooldown_secs: default_tts_cooldown(),
+            hedge_url: None,
+            hedge_delay_ms: default_tts_hedge_delay(),
             timeout_secs: default_tts_timeout(),
             max_retries: 0,
+            requests_per_minute: 0,
+            max_concurrent_requests: 0,
+            health_check_interval_secs: default_tts_health_check_interval(),
+            streaming: false,
+            batch: false,
+            reference_mode: ReferenceMode::Url,
+            api_key: None,
+            api_key_header: None,
+            ssml: false,
+            ssml_paragraph_break_ms: default_tts_ssml_paragraph_break(),
+            batch_size: default_tts_batch_size(),
+            validate_audio: default_tts_validate_audio(),
+            shutdown_drain_secs: default_tts_shutdown_drain(),
+            seed: None,
+            speaker_detection: false,
+            warmup: false,
+            warmup_text: default_tts_warmup_text(),
+            warmup_timeout_secs: default_tts_warmup_timeout(),
+            engine: TtsEngineKind::Http,
+            fake: FakeTtsConfig::default(),
+            local: LocalTtsConfig::default(),
+            engines: BTreeMap::new(),
         }
     }
 }
 
 /// 音频配置
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AudioConfig {
     /// 输出格式
     /// 可选: wav, opus, mp3
@@ -210,6 +763,75 @@ pub struct AudioConfig {
     /// 0 表示保持原始声道数，1 表示单声道，2 表示立体声
     #[serde(default = "default_channels")]
     pub channels: u8,
+
+    /// 重采样质量：linear / medium / high
+    #[serde(default)]
+    pub resample_quality: ResampleQuality,
+
+    /// 音色参考音频预处理配置
+    #[serde(default)]
+    pub reference: ReferenceAudioConfig,
+}
+
+/// 音色参考音频预处理配置
+///
+/// 上传后对参考音频做静音裁剪、下混、重采样和响度归一化，
+/// 处理结果与原始文件并存，TTS 服务优先使用处理后的版本
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReferenceAudioConfig {
+    /// 是否启用归一化
+    #[serde(default = "default_reference_enabled")]
+    pub enabled: bool,
+
+    /// 目标采样率（Hz），应与 TTS 引擎偏好的采样率一致
+    #[serde(default = "default_reference_sample_rate")]
+    pub sample_rate: u32,
+
+    /// 静音判定阈值（dBFS）
+    #[serde(default = "default_reference_silence_db")]
+    pub silence_threshold_db: f32,
+
+    /// 目标响度（RMS dBFS）
+    #[serde(default = "default_reference_loudness_db")]
+    pub target_loudness_db: f32,
+}
+
+fn default_reference_enabled() -> bool {
+    true
+}
+
+fn default_reference_sample_rate() -> u32 {
+    24000
+}
+
+fn default_reference_silence_db() -> f32 {
+    -45.0
+}
+
+fn default_reference_loudness_db() -> f32 {
+    -20.0
+}
+
+impl Default for ReferenceAudioConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_reference_enabled(),
+            sample_rate: default_reference_sample_rate(),
+            silence_threshold_db: default_reference_silence_db(),
+            target_loudness_db: default_reference_loudness_db(),
+        }
+    }
+}
+
+impl ReferenceAudioConfig {
+    /// 转换为归一化参数，未启用时返回 None
+    pub fn normalize_config(&self) -> Option<ReferenceNormalizeConfig> {
+        self.enabled.then_some(ReferenceNormalizeConfig {
+            sample_rate: self.sample_rate,
+            silence_threshold_db: self.silence_threshold_db,
+            target_loudness_db: self.target_loudness_db,
+        })
+    }
 }
 
 fn default_transcode_enabled() -> bool {
@@ -224,6 +846,19 @@ fn default_channels() -> u8 {
     1 // 单声道
 }
 
+impl AudioConfig {
+    /// 推理结果写入缓存前的转码参数
+    pub fn transcode_config(&self) -> TranscodeConfig {
+        TranscodeConfig {
+            format: self.output_format,
+            bitrate: Some(self.bitrate),
+            sample_rate: (self.sample_rate > 0).then_some(self.sample_rate),
+            channels: (self.channels > 0).then_some(self.channels),
+            tempo: None,
+        }
+    }
+}
+
 impl Default for AudioConfig {
     fn default() -> Self {
         Self {
@@ -232,12 +867,14 @@ impl Default for AudioConfig {
             bitrate: default_bitrate(),
             sample_rate: 0,
             channels: default_channels(),
+            resample_quality: ResampleQuality::default(),
+            reference: ReferenceAudioConfig::default(),
         }
     }
 }
 
 /// 数据库配置
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DatabaseConfig {
     /// 数据库文件路径
     #[serde(default = "default_db_path")]
@@ -273,7 +910,7 @@ impl DatabaseConfig {
 }
 
 /// 存储配置
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StorageConfig {
     /// 音频存储目录
     #[serde(default = "default_audio_dir")]
@@ -287,6 +924,14 @@ pub struct StorageConfig {
     #[serde(default = "default_voices_dir")]
     pub voices_dir: PathBuf,
 
+    /// 内置音色预置目录（包含 presets.toml 时启动注册）
+    #[serde(default = "default_presets_dir")]
+    pub presets_dir: PathBuf,
+
+    /// 有声书导出文件目录
+    #[serde(default = "default_exports_dir")]
+    pub exports_dir: PathBuf,
+
     /// 最大存储空间（字节），0 表示不限制
     #[serde(default)]
     pub max_size_bytes: u64,
@@ -294,6 +939,65 @@ pub struct StorageConfig {
     /// 上传文件最大大小（字节），默认 10MB
     #[serde(default = "default_max_upload_size")]
     pub max_upload_size: u64,
+
+    /// 音频缓存后端
+    #[serde(default)]
+    pub audio_cache_backend: AudioCacheBackend,
+
+    /// 是否以 zstd 压缩音频缓存（只影响新写入的条目，仅 sled 后端）
+    #[serde(default)]
+    pub compress_audio_cache: bool,
+
+    /// 音频缓存内存层容量（字节），0 表示不启用，默认 64MB
+    #[serde(default = "default_memory_cache_bytes")]
+    pub memory_cache_bytes: u64,
+
+    /// 单部小说的音频缓存配额（字节），0 表示不限制
+    #[serde(default)]
+    pub novel_cache_quota_bytes: u64,
+
+    /// 按小说单独指定的缓存配额（novel_id -> 字节，0 表示不限制）
+    #[serde(default)]
+    pub novel_cache_quotas: BTreeMap<Uuid, u64>,
+
+    /// 音频缓存加密密钥（base64 编码的 32 字节 AES-256 密钥），优先于密钥文件
+    #[serde(default)]
+    pub cache_encryption_key: Option<String>,
+
+    /// 音频缓存加密密钥文件（32 字节原始密钥或其 base64 编码）
+    #[serde(default)]
+    pub cache_encryption_key_file: Option<PathBuf>,
+
+    /// 音频缓存定期落盘及检查磁盘占用的间隔（秒），0 表示只在关闭服务时落盘
+    #[serde(default = "default_cache_flush_interval")]
+    pub cache_flush_interval_secs: u64,
+
+    /// 推理完成后是否把会话音频写入会话存储目录并记录音频段落
+    #[serde(default = "default_store_session_audio")]
+    pub store_session_audio: bool,
+}
+
+/// 音频缓存后端
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AudioCacheBackend {
+    /// 音频存为 sled 中的 blob（`{audio_dir}/cache.sled`）
+    #[default]
+    Sled,
+    /// 音频存为独立文件，SQLite 索引（`{audio_dir}/cache/`）
+    File,
+}
+
+fn default_store_session_audio() -> bool {
+    true
+}
+
+fn default_cache_flush_interval() -> u64 {
+    60 // 1 分钟
+}
+
+fn default_memory_cache_bytes() -> u64 {
+    64 * 1024 * 1024 // 64 MB
 }
 
 fn default_audio_dir() -> PathBuf {
@@ -304,6 +1008,14 @@ fn default_novels_dir() -> PathBuf {
     PathBuf::from("data/novels")
 }
 
+fn default_presets_dir() -> PathBuf {
+    PathBuf::from("data/presets")
+}
+
+fn default_exports_dir() -> PathBuf {
+    PathBuf::from("data/exports")
+}
+
 fn default_voices_dir() -> PathBuf {
     PathBuf::from("data/voices")
 }
@@ -318,14 +1030,283 @@ impl Default for StorageConfig {
             audio_dir: default_audio_dir(),
             novels_dir: default_novels_dir(),
             voices_dir: default_voices_dir(),
+            presets_dir: default_presets_dir(),
+            exports_dir: default_exports_dir(),
             max_size_bytes: 0,
             max_upload_size: default_max_upload_size(),
+            audio_cache_backend: AudioCacheBackend::default(),
+            compress_audio_cache: false,
+            memory_cache_bytes: default_memory_cache_bytes(),
+            novel_cache_quota_bytes: 0,
+            novel_cache_quotas: BTreeMap::new(),
+            cache_encryption_key: None,
+            cache_encryption_key_file: None,
+            cache_flush_interval_secs: default_cache_flush_interval(),
+            store_session_audio: default_store_session_audio(),
+        }
+    }
+}
+
+/// 播放窗口预取配置
+///
+/// 播放、跳转和上报进度后为窗口 [当前 - before, 当前 + after] 内未缓存的片段提交推理，
+/// 取消移出窗口的排队任务；before/after 为默认窗口，会话可在上限内单独设置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrefetchConfig {
+    /// 是否启用预取（关闭时只合成客户端显式提交的片段）
+    #[serde(default = "default_prefetch_enabled")]
+    pub enabled: bool,
+
+    /// 当前片段之前保留的片段数
+    #[serde(default = "default_prefetch_before")]
+    pub before: usize,
+
+    /// 当前片段之后预取的片段数
+    #[serde(default = "default_prefetch_after")]
+    pub after: usize,
+
+    /// 会话可设置的 before 上限
+    #[serde(default = "default_prefetch_max_before")]
+    pub max_before: usize,
+
+    /// 会话可设置的 after 上限
+    #[serde(default = "default_prefetch_max_after")]
+    pub max_after: usize,
+
+    /// 单次批量预取的片段数上限
+    #[serde(default = "default_prefetch_max_batch")]
+    pub max_batch: usize,
+
+    /// 会话无活动（位置上报、WS 消息）多少秒后暂停窗口预取，再次活动时恢复（0 表示不暂停）
+    #[serde(default = "default_prefetch_idle_pause_secs")]
+    pub idle_pause_secs: u64,
+}
+
+fn default_prefetch_enabled() -> bool {
+    true
+}
+
+fn default_prefetch_before() -> usize {
+    2
+}
+
+fn default_prefetch_after() -> usize {
+    3
+}
+
+fn default_prefetch_max_before() -> usize {
+    10
+}
+
+fn default_prefetch_max_after() -> usize {
+    20
+}
+
+fn default_prefetch_max_batch() -> usize {
+    200
+}
+
+fn default_prefetch_idle_pause_secs() -> u64 {
+    600
+}
+
+impl Default for PrefetchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_prefetch_enabled(),
+            before: default_prefetch_before(),
+            after: default_prefetch_after(),
+            max_before: default_prefetch_max_before(),
+            max_after: default_prefetch_max_after(),
+            max_batch: default_prefetch_max_batch(),
+            idle_pause_secs: default_prefetch_idle_pause_secs(),
+        }
+    }
+}
+
+impl PrefetchConfig {
+    pub fn settings(&self) -> PrefetchSettings {
+        PrefetchSettings {
+            enabled: self.enabled,
+            default_window: WindowConfig::new(self.before, self.after),
+            max_window: WindowConfig::new(self.max_before, self.max_after),
+            max_batch: self.max_batch,
+            idle_pause_secs: self.idle_pause_secs,
+        }
+    }
+}
+
+/// 播放会话配置
+///
+/// 限制同时存在的播放会话数（0 表示不限制），保护单个 TTS 后端不被过多会话的预取拖垮；
+/// 导出/预生成使用的后台会话和复用的会话不计入
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionsConfig {
+    /// 全局会话上限
+    #[serde(default)]
+    pub max_active: usize,
+
+    /// 每个客户端 IP 的会话上限
+    #[serde(default)]
+    pub max_per_client: usize,
+}
+
+impl SessionsConfig {
+    pub fn limits(&self) -> SessionLimits {
+        SessionLimits {
+            max_active: self.max_active,
+            max_per_client: self.max_per_client,
+        }
+    }
+}
+
+/// 整书预生成配置
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PregenerationConfig {
+    /// 允许预生成的时段（本地时间，如 "02:00-07:00"，逗号分隔多个时段，空表示不限制）
+    #[serde(default)]
+    pub schedule: String,
+}
+
+impl PregenerationConfig {
+    /// 解析后的时段（加载配置时已校验）
+    pub fn schedule(&self) -> PregenerationSchedule {
+        PregenerationSchedule::parse(&self.schedule).unwrap_or_default()
+    }
+}
+
+/// 推理队列后端
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueueBackend {
+    /// 进程内队列
+    #[default]
+    Memory,
+    /// Redis Streams，多个实例共享同一队列（需以 `redis-queue` feature 编译）
+    Redis,
+}
+
+/// 推理队列配置
+///
+/// 共享 Redis 队列的实例需使用同一数据库、存储目录和文件音频缓存，
+/// 其他实例推理的片段才能被提交任务的实例读取
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueConfig {
+    /// 队列后端：memory（默认）、redis
+    #[serde(default)]
+    pub backend: QueueBackend,
+
+    /// 排队任务数上限（Redis 队列为所有实例合计），超出时提交返回 HTTP 429
+    #[serde(default = "default_queue_capacity")]
+    pub capacity: usize,
+
+    /// Redis 地址（如 redis://127.0.0.1:6379/0），Redis 队列必须设置
+    #[serde(default)]
+    pub redis_url: Option<String>,
+
+    /// Redis 键前缀（各优先级的 Stream 和任务结束标记）
+    #[serde(default = "default_queue_key_prefix")]
+    pub key_prefix: String,
+
+    /// 消费组（共享队列的实例使用同一消费组）
+    #[serde(default = "default_queue_group")]
+    pub group: String,
+
+    /// 每个实例预先取出的任务数，其余留在 Redis 中由空闲的实例取走
+    #[serde(default = "default_queue_read_ahead")]
+    pub read_ahead: usize,
+
+    /// 已取出的任务超过该时长（秒）未完成时由其他实例接管（实例崩溃后）
+    #[serde(default = "default_queue_claim_idle")]
+    pub claim_idle_secs: u64,
+}
+
+fn default_queue_capacity() -> usize {
+    1000
+}
+
+fn default_queue_key_prefix() -> String {
+    "rovel:tasks".to_string()
+}
+
+fn default_queue_group() -> String {
+    "rovel-workers".to_string()
+}
+
+fn default_queue_read_ahead() -> usize {
+    4
+}
+
+fn default_queue_claim_idle() -> u64 {
+    600 // 10 分钟
+}
+
+impl Default for QueueConfig {
+    fn default() -> Self {
+        Self {
+            backend: QueueBackend::default(),
+            capacity: default_queue_capacity(),
+            redis_url: None,
+            key_prefix: default_queue_key_prefix(),
+            group: default_queue_group(),
+            read_ahead: default_queue_read_ahead(),
+            claim_idle_secs: default_queue_claim_idle(),
+        }
+    }
+}
+
+/// 用户账号配置
+///
+/// 启用后除健康检查、注册和登录外的接口都需要登录令牌，
+/// 小说、音色和会话按用户隔离（阅读进度随小说隔离）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthConfig {
+    /// 是否启用用户账号
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// 签发登录令牌（JWT, HS256）的密钥，启用账号时必须设置
+    #[serde(default)]
+    pub jwt_secret: Option<String>,
+
+    /// 登录令牌有效期（秒），默认 30 天
+    #[serde(default = "default_token_ttl")]
+    pub token_ttl_secs: u64,
+
+    /// 是否允许注册新用户（关闭后只有第一个用户可以注册）
+    #[serde(default = "default_allow_registration")]
+    pub allow_registration: bool,
+
+    /// 管理接口令牌（请求头 X-Admin-Token），与是否启用账号无关
+    ///
+    /// 设置后携带该令牌即可访问 /api/admin（供运维脚本使用）；启用账号时管理员用户
+    /// 仍可凭登录令牌访问，未启用账号且未设置时管理接口关闭
+    #[serde(default)]
+    pub admin_token: Option<String>,
+}
+
+fn default_token_ttl() -> u64 {
+    30 * 24 * 60 * 60 // 30 天
+}
+
+fn default_allow_registration() -> bool {
+    true
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            jwt_secret: None,
+            token_ttl_secs: default_token_ttl(),
+            allow_registration: default_allow_registration(),
+            admin_token: None,
         }
     }
 }
 
 /// GC（垃圾回收）配置
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GcConfig {
     /// 是否启用自动 GC
     #[serde(default = "default_gc_enabled")]
@@ -339,9 +1320,21 @@ pub struct GcConfig {
     #[serde(default = "default_session_expire")]
     pub session_expire_secs: u64,
 
+    /// 空闲会话检查间隔（秒，0 表示只在 GC 时检查）
+    #[serde(default = "default_session_sweep_interval")]
+    pub session_sweep_interval_secs: u64,
+
     /// 最大存储空间（字节）
     #[serde(default = "default_max_storage")]
     pub max_storage_bytes: u64,
+
+    /// 窗口外音频保留时间（秒），超过后清理
+    #[serde(default = "default_window_evict_delay")]
+    pub window_evict_delay_secs: u64,
+
+    /// 音频缓存条目自最后访问起的保留天数（0 表示只按大小淘汰）
+    #[serde(default)]
+    pub cache_ttl_days: u64,
 }
 
 fn default_gc_enabled() -> bool {
@@ -356,23 +1349,34 @@ fn default_session_expire() -> u64 {
     86400 // 24 小时
 }
 
+fn default_session_sweep_interval() -> u64 {
+    60
+}
+
 fn default_max_storage() -> u64 {
     10 * 1024 * 1024 * 1024 // 10 GB
 }
 
+fn default_window_evict_delay() -> u64 {
+    300 // 5 分钟
+}
+
 impl Default for GcConfig {
     fn default() -> Self {
         Self {
             enabled: default_gc_enabled(),
             interval_secs: default_gc_interval(),
             session_expire_secs: default_session_expire(),
+            session_sweep_interval_secs: default_session_sweep_interval(),
             max_storage_bytes: default_max_storage(),
+            window_evict_delay_secs: default_window_evict_delay(),
+            cache_ttl_days: 0,
         }
     }
 }
 
 /// 日志配置
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LogConfig {
     /// 日志级别
     #[serde(default = "default_log_level")]
@@ -420,4 +1424,25 @@ mod tests {
         let config = DatabaseConfig::default();
         assert_eq!(config.database_url(), "sqlite:data/rovel.db?mode=rwc");
     }
+
+    #[test]
+    fn test_redacted_config_hides_secrets() {
+        let mut config = AppConfig::default();
+        config.auth.jwt_secret = Some("jwt-secret".to_string());
+        config.auth.admin_token = Some("admin-token".to_string());
+        config.tts.engines.insert(
+            "backup".to_string(),
+            serde_json::from_value(serde_json::json!({ "api_key": "engine-key" })).unwrap(),
+        );
+
+        let redacted = config.redacted();
+        assert_eq!(redacted.auth.jwt_secret.as_deref(), Some("***"));
+        assert_eq!(redacted.auth.admin_token.as_deref(), Some("***"));
+        assert_eq!(redacted.tts.engines["backup"].api_key.as_deref(), Some("***"));
+        // 未设置的密钥保持为空
+        assert_eq!(redacted.tts.api_key, None);
+
+        let json = serde_json::to_string(&redacted).unwrap();
+        assert!(!json.contains("jwt-secret") && !json.contains("engine-key"));
+    }
 }
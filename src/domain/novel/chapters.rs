@@ -0,0 +1,133 @@
+//! 章节识别
+//!
+//! 文本分段按行切分，章节标题行（`第一章 陨落的天才`、`Chapter 3`、`楔子` 等）
+//! 总是独立成为一个片段，因此按片段识别标题即可划分章节：
+//! - 每个标题片段开始一个新章节，直到下一个标题为止
+//! - 第一个标题之前的片段归入“前言”
+//! - 没有识别到标题时整部小说为一个章节
+
+use super::Chapter;
+
+/// 标题最大字符数，超过的视为正文
+const MAX_HEADING_CHARS: usize = 40;
+
+/// 无章节序号的特殊标题
+const SPECIAL_HEADINGS: &[&str] = &["序章", "序言", "楔子", "引子", "尾声", "后记", "番外"];
+
+/// 章节单位
+const CHAPTER_UNITS: &[char] = &['章', '回', '节', '卷', '部', '集'];
+
+/// 前言（第一个标题前的片段）标题
+const PROLOGUE_TITLE: &str = "前言";
+
+/// 无章节时的标题
+const WHOLE_TEXT_TITLE: &str = "全文";
+
+#[inline]
+fn is_chinese_numeral(ch: char) -> bool {
+    matches!(
+        ch,
+        '零' | '〇' | '一' | '二' | '两' | '三' | '四' | '五' | '六' | '七' | '八' | '九' | '十' | '百' | '千' | '万'
+    )
+}
+
+/// 片段是否为章节标题
+pub fn is_chapter_heading(text: &str) -> bool {
+    let text = text.trim();
+    if text.is_empty() || text.chars().count() > MAX_HEADING_CHARS {
+        return false;
+    }
+
+    // 第X章 / 第X回 / ...
+    if let Some(rest) = text.strip_prefix('第') {
+        let number_len: usize = rest
+            .chars()
+            .take_while(|&c| c.is_ascii_digit() || c.is_numeric() || is_chinese_numeral(c))
+            .map(char::len_utf8)
+            .sum();
+        if number_len > 0 && rest[number_len..].starts_with(CHAPTER_UNITS) {
+            return true;
+        }
+    }
+
+    // Chapter 3 / CHAPTER III
+    let lower = text.to_lowercase();
+    if let Some(rest) = lower.strip_prefix("chapter") {
+        if rest.starts_with(|c: char| c.is_whitespace()) {
+            return true;
+        }
+    }
+
+    SPECIAL_HEADINGS.iter().any(|heading| {
+        text.strip_prefix(heading)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(|c: char| c.is_whitespace() || c == '：' || c == ':'))
+    })
+}
+
+/// 按片段内容划分章节（编号从 1 开始）
+pub fn detect_chapters<'a>(segments: impl IntoIterator<Item = &'a str>) -> Vec<Chapter> {
+    // (标题, 起始片段索引)
+    let mut starts: Vec<(String, usize)> = Vec::new();
+    let mut total = 0;
+    for (index, content) in segments.into_iter().enumerate() {
+        if is_chapter_heading(content) {
+            if index > 0 && starts.is_empty() {
+                starts.push((PROLOGUE_TITLE.to_string(), 0));
+            }
+            starts.push((content.trim().to_string(), index));
+        }
+        total = index + 1;
+    }
+
+    if total == 0 {
+        return Vec::new();
+    }
+    if starts.is_empty() {
+        starts.push((WHOLE_TEXT_TITLE.to_string(), 0));
+    }
+
+    let ends: Vec<usize> = starts.iter().skip(1).map(|(_, start)| *start).chain([total]).collect();
+    starts
+        .into_iter()
+        .zip(ends)
+        .enumerate()
+        .filter_map(|(i, ((title, start), end))| Chapter::new(i + 1, title, start, end).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_chapter_heading() {
+        assert!(is_chapter_heading("第001章 陨落的天才"));
+        assert!(is_chapter_heading("第一百二十回 大结局"));
+        assert!(is_chapter_heading("第三卷"));
+        assert!(is_chapter_heading("Chapter 3: The Return"));
+        assert!(is_chapter_heading("楔子"));
+        assert!(is_chapter_heading("番外：往事"));
+        assert!(!is_chapter_heading("第一次见面时，他什么也没说。"));
+        assert!(!is_chapter_heading("尾声渐渐消失在风里。"));
+        assert!(!is_chapter_heading("“斗之力，三段！”"));
+    }
+
+    #[test]
+    fn test_detect_chapters() {
+        let segments = ["序", "第一章 开始", "正文一", "正文二", "第二章 结束", "正文三"];
+        let chapters = detect_chapters(segments);
+
+        assert_eq!(chapters.len(), 3);
+        assert_eq!(chapters[0].title(), "前言");
+        assert_eq!((chapters[0].start_segment_index(), chapters[0].end_segment_index()), (0, 1));
+        assert_eq!(chapters[1].number(), 2);
+        assert_eq!(chapters[1].title(), "第一章 开始");
+        assert_eq!((chapters[1].start_segment_index(), chapters[1].end_segment_index()), (1, 4));
+        assert_eq!((chapters[2].start_segment_index(), chapters[2].end_segment_index()), (4, 6));
+
+        let chapters = detect_chapters(["没有标题的正文", "继续"]);
+        assert_eq!(chapters.len(), 1);
+        assert_eq!(chapters[0].title(), "全文");
+        assert!(detect_chapters([]).is_empty());
+    }
+}
@@ -3,14 +3,22 @@
 //! 职责:
 //! - 小说聚合管理
 //! - 文本片段实体
-//! - 章节和段落管理
+//! - 章节和段落管理（按标题片段识别章节）
+//! - 对话说话人识别
+//! - SSML 韵律标记生成
 
 mod aggregate;
+mod chapters;
 mod entities;
 mod errors;
+mod speaker;
+mod ssml;
 mod value_objects;
 
 pub use aggregate::Novel;
+pub use chapters::{detect_chapters, is_chapter_heading};
 pub use entities::{Chapter, TextSegment};
 pub use errors::NovelError;
+pub use speaker::{detect_speaker, is_dialogue};
+pub use ssml::{to_ssml, SsmlOptions};
 pub use value_objects::{NovelId, RawTextPath, Title};
@@ -0,0 +1,153 @@
+//! 对话说话人识别
+//!
+//! 基于中文小说常见的引语格式做启发式识别，用于多角色配音：
+//! - `张三说：“……”`（引语前）
+//! - `“……”张三笑道。`（引语后）
+//!
+//! 文本分段后引语与说话人可能分属相邻片段，因此同时参考前后片段。
+
+/// 说话动词（按长度降序匹配，避免“冷笑道”被识别为“冷笑” + “道”）
+const SPEECH_VERBS: &[&str] = &[
+    "笑着说道", "笑着说", "低声道", "冷笑道", "说道", "笑道", "问道", "答道", "喊道", "叫道",
+    "怒道", "叹道", "说", "道", "问", "喊", "叫", "答",
+];
+
+/// 角色名长度范围（字符数），排除“她”“他”等代词
+const MIN_NAME_CHARS: usize = 2;
+const MAX_NAME_CHARS: usize = 4;
+
+#[inline]
+pub(super) fn is_open_quote(ch: char) -> bool {
+    matches!(ch, '\u{201C}' | '「' | '『')
+}
+
+#[inline]
+pub(super) fn is_close_quote(ch: char) -> bool {
+    matches!(ch, '\u{201D}' | '」' | '』')
+}
+
+#[inline]
+fn is_quote(ch: char) -> bool {
+    is_open_quote(ch) || is_close_quote(ch) || ch == '"'
+}
+
+/// 片段是否包含引语
+pub fn is_dialogue(text: &str) -> bool {
+    text.chars().any(is_quote)
+}
+
+/// 识别片段中引语的说话人
+///
+/// `previous` / `next` 为相邻片段，用于处理说话人与引语被分到不同片段的情况。
+/// 非对话片段返回 None。
+pub fn detect_speaker(previous: Option<&str>, current: &str, next: Option<&str>) -> Option<String> {
+    if !is_dialogue(current) {
+        return None;
+    }
+
+    // 引语前：张三说：“……”
+    if let Some(pos) = current.find(|c: char| is_open_quote(c) || c == '"') {
+        if let Some(name) = speaker_before(&current[..pos]) {
+            return Some(name);
+        }
+    }
+
+    // 引语后：“……”张三说。
+    if let Some(pos) = current.rfind(|c: char| is_close_quote(c) || c == '"') {
+        let after = &current[pos..];
+        let after = after.trim_start_matches(|c: char| is_quote(c));
+        if let Some(name) = speaker_after(after) {
+            return Some(name);
+        }
+    }
+
+    // 前一片段以 “张三说：” 结尾
+    if let Some(name) = previous.filter(|p| !ends_inside_quote(p)).and_then(speaker_before) {
+        return Some(name);
+    }
+
+    // 后一片段以 “张三说” 开头
+    next.filter(|n| !n.trim_start().starts_with(is_quote))
+        .and_then(speaker_after)
+}
+
+/// 前一片段若以未闭合的引语结尾，说明仍在对话中，不能作为说话人提示
+fn ends_inside_quote(text: &str) -> bool {
+    let open = text.rfind(|c: char| is_open_quote(c));
+    let close = text.rfind(|c: char| is_close_quote(c));
+    match (open, close) {
+        (Some(o), Some(c)) => o > c,
+        (Some(_), None) => true,
+        _ => false,
+    }
+}
+
+/// 从 “……张三说：” 形式的文本末尾提取说话人
+fn speaker_before(text: &str) -> Option<String> {
+    let text = text.trim_end_matches(|c: char| {
+        c.is_whitespace() || matches!(c, '：' | ':' | '，' | ',')
+    });
+    let head = strip_verb_suffix(text)?;
+    let name: Vec<char> = head
+        .chars()
+        .rev()
+        .take_while(|c| c.is_alphanumeric())
+        .collect();
+    to_name(name.into_iter().rev())
+}
+
+/// 从 “张三说。……” 形式的文本开头提取说话人
+fn speaker_after(text: &str) -> Option<String> {
+    let run: String = text
+        .trim_start_matches(|c: char| c.is_whitespace() || matches!(c, '，' | ','))
+        .chars()
+        .take_while(|c| c.is_alphanumeric())
+        .collect();
+    let head = strip_verb_suffix(&run)?;
+    to_name(head.chars())
+}
+
+fn strip_verb_suffix(text: &str) -> Option<&str> {
+    SPEECH_VERBS.iter().find_map(|verb| text.strip_suffix(verb))
+}
+
+fn to_name(chars: impl Iterator<Item = char>) -> Option<String> {
+    let name: String = chars.collect();
+    let len = name.chars().count();
+    (MIN_NAME_CHARS..=MAX_NAME_CHARS)
+        .contains(&len)
+        .then_some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_speaker_in_segment() {
+        assert_eq!(
+            detect_speaker(None, "张三说：\u{201C}你好。\u{201D}", None).as_deref(),
+            Some("张三")
+        );
+        assert_eq!(
+            detect_speaker(None, "\u{201C}走吧。\u{201D}李四冷笑道。", None).as_deref(),
+            Some("李四")
+        );
+        // 代词无法配音，不识别
+        assert_eq!(detect_speaker(None, "她说：\u{201C}好。\u{201D}", None), None);
+        // 叙述片段
+        assert_eq!(detect_speaker(None, "张三走进房间。", None), None);
+    }
+
+    #[test]
+    fn test_detect_speaker_from_neighbours() {
+        assert_eq!(
+            detect_speaker(Some("王五问道："), "\u{201C}你去哪？\u{201D}", None).as_deref(),
+            Some("王五")
+        );
+        assert_eq!(
+            detect_speaker(None, "\u{201C}我不知道。\u{201D}", Some("赵六答。")).as_deref(),
+            Some("赵六")
+        );
+    }
+}
@@ -0,0 +1,120 @@
+//! SSML 韵律标记生成
+//!
+//! 为支持 SSML 的 TTS 引擎生成韵律标记，改善长篇叙述的节奏：
+//! - 段落（换行）处插入停顿
+//! - 片段以句末标点结束时在末尾插入停顿（分段后无法区分段落与句子，统一按段落处理）
+//! - 对话引语加强调
+//!
+//! 文本中的 XML 特殊字符会被转义。
+
+use super::speaker::{is_close_quote, is_open_quote};
+
+/// SSML 生成选项
+#[derive(Debug, Clone)]
+pub struct SsmlOptions {
+    /// 段落停顿时长（毫秒），0 表示不插入停顿
+    pub paragraph_break_ms: u32,
+    /// 对话引语是否加强调
+    pub emphasize_dialogue: bool,
+}
+
+impl Default for SsmlOptions {
+    fn default() -> Self {
+        Self {
+            paragraph_break_ms: 500,
+            emphasize_dialogue: true,
+        }
+    }
+}
+
+#[inline]
+fn is_sentence_end(ch: char) -> bool {
+    matches!(ch, '。' | '？' | '！' | '…' | '.' | '?' | '!')
+}
+
+/// 将片段文本转换为 SSML 文档
+pub fn to_ssml(text: &str, options: &SsmlOptions) -> String {
+    let pause = (options.paragraph_break_ms > 0)
+        .then(|| format!("<break time=\"{}ms\"/>", options.paragraph_break_ms));
+    let mut ssml = String::with_capacity(text.len() * 2 + 32);
+    ssml.push_str("<speak>");
+
+    let mut in_quote = false;
+    let mut paragraph_pending = false;
+    for ch in text.trim().chars() {
+        if ch == '\n' || ch == '\r' {
+            paragraph_pending = true;
+            continue;
+        }
+        if paragraph_pending {
+            if let Some(pause) = &pause {
+                ssml.push_str(pause);
+            }
+            paragraph_pending = false;
+        }
+
+        let opens = is_open_quote(ch) || (ch == '"' && !in_quote);
+        let closes = is_close_quote(ch) || (ch == '"' && in_quote);
+        if options.emphasize_dialogue && opens && !in_quote {
+            ssml.push_str("<emphasis level=\"moderate\">");
+            in_quote = true;
+        }
+        push_escaped(&mut ssml, ch);
+        if options.emphasize_dialogue && closes && in_quote {
+            ssml.push_str("</emphasis>");
+            in_quote = false;
+        }
+    }
+    // 引语跨片段时在片段末尾闭合
+    if in_quote {
+        ssml.push_str("</emphasis>");
+    }
+
+    let ends_sentence = text
+        .trim_end()
+        .trim_end_matches(|c: char| is_close_quote(c) || c == '"')
+        .ends_with(is_sentence_end);
+    if let Some(pause) = pause.filter(|_| ends_sentence) {
+        ssml.push_str(&pause);
+    }
+
+    ssml.push_str("</speak>");
+    ssml
+}
+
+fn push_escaped(out: &mut String, ch: char) {
+    match ch {
+        '&' => out.push_str("&amp;"),
+        '<' => out.push_str("&lt;"),
+        '>' => out.push_str("&gt;"),
+        '"' => out.push_str("&quot;"),
+        '\'' => out.push_str("&apos;"),
+        _ => out.push(ch),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dialogue_emphasis_and_pause() {
+        let ssml = to_ssml("张三说：\u{201C}你好。\u{201D}", &SsmlOptions::default());
+        assert_eq!(
+            ssml,
+            "<speak>张三说：<emphasis level=\"moderate\">\u{201C}你好。\u{201D}</emphasis><break time=\"500ms\"/></speak>"
+        );
+    }
+
+    #[test]
+    fn test_paragraphs_and_escaping() {
+        let options = SsmlOptions {
+            paragraph_break_ms: 300,
+            emphasize_dialogue: false,
+        };
+        assert_eq!(
+            to_ssml("A & B\n\nC <D>，", &options),
+            "<speak>A &amp; B<break time=\"300ms\"/>C &lt;D&gt;，</speak>"
+        );
+    }
+}
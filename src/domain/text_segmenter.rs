@@ -65,13 +65,9 @@ fn split_by_delimiters(text: &str, config: &SegmentConfig) -> Vec<String> {
         current.push(ch);
         char_count += 1;
 
-        let should_split = if is_strong_delimiter(ch) {
-            true // 强分隔符总是分割
-        } else if is_weak_delimiter(ch) && char_count >= config.min_chars {
-            true // 弱分隔符在满足 min_chars 时分割
-        } else {
-            false
-        };
+        // 强分隔符总是分割；弱分隔符在满足 min_chars 时分割
+        let should_split = is_strong_delimiter(ch)
+            || (is_weak_delimiter(ch) && char_count >= config.min_chars);
 
         if should_split {
             let trimmed = current.trim().to_string();
@@ -161,11 +157,6 @@ pub fn segment_text(text: &str, config: &SegmentConfig) -> Vec<String> {
     segments
 }
 
-/// 使用默认配置分段（便捷方法）
-pub fn segment_text_default(text: &str) -> Vec<String> {
-    segment_text(text, &SegmentConfig::default())
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -231,7 +222,7 @@ mod tests {
 
     #[test]
     fn test_default_config() {
-        let segments = segment_text_default("测试内容。");
+        let segments = segment_text("测试内容。", &SegmentConfig::default());
         assert_eq!(segments.len(), 1);
         assert_eq!(segments[0], "测试内容。");
     }
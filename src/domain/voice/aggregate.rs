@@ -114,6 +114,14 @@ mod tests {
             speed: 3.0, // 超出范围
             pitch: 0,
             volume: 1.0,
+            temperature: None,
+            top_p: None,
+        };
+        assert!(config.validate().is_err());
+
+        let config = TtsConfig {
+            top_p: Some(1.5), // 超出范围
+            ..TtsConfig::default()
         };
         assert!(config.validate().is_err());
     }
@@ -11,4 +11,6 @@ mod value_objects;
 
 pub use aggregate::Voice;
 pub use errors::VoiceError;
-pub use value_objects::{AudioFormat, AudioRef, TtsConfig, VoiceId, VoiceName};
+pub use value_objects::{
+    AudioFormat, AudioRef, SynthesisParams, TtsConfig, VoiceId, VoiceName, VoiceTags,
+};
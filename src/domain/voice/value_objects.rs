@@ -128,14 +128,21 @@ impl AudioRef {
 }
 
 /// TTS 配置参数
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// temperature / top_p 为 None 时使用 TTS 服务自身的默认值
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TtsConfig {
     /// 语速 (0.5 - 2.0)
     pub speed: f32,
     /// 音调 (-12 - 12)
     pub pitch: i8,
     /// 音量 (0.0 - 1.0)
+    #[serde(default = "default_volume")]
     pub volume: f32,
+    /// 采样温度 (0.0 - 2.0)
+    pub temperature: Option<f32>,
+    /// 核采样阈值 (0.0 - 1.0)
+    pub top_p: Option<f32>,
 }
 
 impl Default for TtsConfig {
@@ -143,11 +150,17 @@ impl Default for TtsConfig {
         Self {
             speed: 1.0,
             pitch: 0,
-            volume: 1.0,
+            volume: default_volume(),
+            temperature: None,
+            top_p: None,
         }
     }
 }
 
+fn default_volume() -> f32 {
+    1.0
+}
+
 impl TtsConfig {
     pub fn validate(&self) -> Result<(), &'static str> {
         if !(0.5..=2.0).contains(&self.speed) {
@@ -159,6 +172,140 @@ impl TtsConfig {
         if !(0.0..=1.0).contains(&self.volume) {
             return Err("音量必须在 0.0 到 1.0 之间");
         }
+        if self.temperature.is_some_and(|t| !(0.0..=2.0).contains(&t)) {
+            return Err("temperature 必须在 0.0 到 2.0 之间");
+        }
+        if self.top_p.is_some_and(|p| !(0.0..=1.0).contains(&p)) {
+            return Err("top_p 必须在 0.0 到 1.0 之间");
+        }
+        Ok(())
+    }
+}
+
+/// 播放时的合成参数（会话级设置，可按片段覆盖）
+///
+/// 未设置的字段使用音色的 TtsConfig 或 TTS 服务默认值
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SynthesisParams {
+    /// 语速 (0.5 - 2.0)，覆盖音色的 speed
+    pub speed: Option<f32>,
+    /// 情感（如 happy / sad / angry，由 TTS 服务解释）
+    pub emotion: Option<String>,
+    /// 能量/音量强度 (0.0 - 2.0)
+    pub energy: Option<f32>,
+}
+
+impl SynthesisParams {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// 以 `overrides` 中已设置的字段覆盖当前参数
+    pub fn merged(&self, overrides: &SynthesisParams) -> SynthesisParams {
+        SynthesisParams {
+            speed: overrides.speed.or(self.speed),
+            emotion: overrides.emotion.clone().or_else(|| self.emotion.clone()),
+            energy: overrides.energy.or(self.energy),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self.speed.is_some_and(|s| !(0.5..=2.0).contains(&s)) {
+            return Err("语速必须在 0.5 到 2.0 之间");
+        }
+        if self.energy.is_some_and(|e| !(0.0..=2.0).contains(&e)) {
+            return Err("energy 必须在 0.0 到 2.0 之间");
+        }
+        if self.emotion.as_deref().is_some_and(|e| e.trim().is_empty()) {
+            return Err("emotion 不能为空字符串");
+        }
         Ok(())
     }
 }
+
+/// 音色分类标签
+///
+/// 用于在音色较多时按类别浏览，标签值为自由文本，比较时忽略大小写
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VoiceTags {
+    /// 性别（如 male / female）
+    pub gender: Option<String>,
+    /// 语言（如 zh / en）
+    pub language: Option<String>,
+    /// 风格（如 narration / gentle）
+    pub style: Option<String>,
+}
+
+impl VoiceTags {
+    /// 规范化标签值：去除首尾空白，空字符串视为未设置
+    pub fn normalize_value(value: &str) -> Option<String> {
+        let value = value.trim();
+        (!value.is_empty()).then(|| value.to_string())
+    }
+
+    /// 判断是否满足过滤条件（过滤条件中未设置的字段不参与匹配）
+    pub fn matches(&self, filter: &VoiceTags) -> bool {
+        fn field_matches(value: &Option<String>, expected: &Option<String>) -> bool {
+            match expected {
+                None => true,
+                Some(expected) => value
+                    .as_deref()
+                    .is_some_and(|v| v.eq_ignore_ascii_case(expected)),
+            }
+        }
+
+        field_matches(&self.gender, &filter.gender)
+            && field_matches(&self.language, &filter.language)
+            && field_matches(&self.style, &filter.style)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_synthesis_params_merge() {
+        let session = SynthesisParams {
+            speed: Some(1.2),
+            emotion: Some("calm".to_string()),
+            energy: None,
+        };
+        let segment = SynthesisParams {
+            emotion: Some("angry".to_string()),
+            energy: Some(1.5),
+            ..Default::default()
+        };
+
+        let merged = session.merged(&segment);
+        assert_eq!(merged.speed, Some(1.2));
+        assert_eq!(merged.emotion.as_deref(), Some("angry"));
+        assert_eq!(merged.energy, Some(1.5));
+        assert!(SynthesisParams::default().is_default());
+        assert!(SynthesisParams { speed: Some(3.0), ..Default::default() }.validate().is_err());
+    }
+
+    #[test]
+    fn test_voice_tags_matches() {
+        let tags = VoiceTags {
+            gender: Some("Female".to_string()),
+            language: Some("zh".to_string()),
+            style: None,
+        };
+
+        assert!(tags.matches(&VoiceTags::default()));
+        assert!(tags.matches(&VoiceTags {
+            gender: Some("female".to_string()),
+            ..Default::default()
+        }));
+        assert!(!tags.matches(&VoiceTags {
+            language: Some("en".to_string()),
+            ..Default::default()
+        }));
+        assert!(!tags.matches(&VoiceTags {
+            style: Some("narration".to_string()),
+            ..Default::default()
+        }));
+    }
+}
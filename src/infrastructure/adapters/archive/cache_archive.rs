@@ -0,0 +1,255 @@
+//! Cache Archive - 音频缓存导出格式
+//!
+//! 一个 tar 归档，按顺序包含:
+//! - `manifest.json`: 格式版本与导出时间
+//! - `entries/{n}.json`: 第 n 个条目的缓存 key、元数据、片段引用和波形峰值
+//! - `entries/{n}.audio`: 第 n 个条目的音频数据（紧跟在对应元数据之后）
+//!
+//! 缓存 key 超出 tar 文件名长度限制，因此写在元数据中；
+//! 条目逐个编码/解码，导出和导入都无需把整个缓存放入内存
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::tar::{encode_entry, end_of_archive, ArchiveError, TarDecoder};
+use crate::application::ports::{AudioFormat, CacheEntry, CacheMetadata, SegmentReference};
+
+/// 当前缓存归档格式版本
+pub const CACHE_ARCHIVE_VERSION: u32 = 1;
+
+/// 归档元数据文件名
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// 缓存归档元数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheArchiveManifest {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+}
+
+/// 单个缓存条目的元数据（保留原缓存 key，导入后与源实例的 key 一致）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheArchiveEntry {
+    pub cache_key: String,
+    pub novel_id: Uuid,
+    pub segment_index: u32,
+    pub voice_id: Uuid,
+    pub content_hash: String,
+    pub duration_ms: u64,
+    pub sample_rate: Option<u32>,
+    pub format: AudioFormat,
+    pub created_at: i64,
+    /// 引用该条目的片段
+    #[serde(default)]
+    pub references: Vec<SegmentReference>,
+    #[serde(default)]
+    pub peaks: Option<Vec<f32>>,
+}
+
+impl CacheArchiveEntry {
+    pub fn new(
+        cache_key: &str,
+        entry: &CacheEntry,
+        references: Vec<SegmentReference>,
+        peaks: Option<Vec<f32>>,
+    ) -> Self {
+        let metadata = &entry.metadata;
+        Self {
+            cache_key: cache_key.to_string(),
+            novel_id: metadata.novel_id,
+            segment_index: metadata.segment_index,
+            voice_id: metadata.voice_id,
+            content_hash: metadata.content_hash.clone(),
+            duration_ms: metadata.duration_ms,
+            sample_rate: metadata.sample_rate,
+            format: metadata.format,
+            created_at: entry.created_at,
+            references,
+            peaks,
+        }
+    }
+
+    /// 写入缓存时使用的元数据
+    pub fn metadata(&self) -> CacheMetadata {
+        CacheMetadata {
+            novel_id: self.novel_id,
+            segment_index: self.segment_index,
+            voice_id: self.voice_id,
+            content_hash: self.content_hash.clone(),
+            duration_ms: self.duration_ms,
+            sample_rate: self.sample_rate,
+            format: self.format,
+        }
+    }
+}
+
+/// 缓存归档写入器（逐条目输出 tar 数据块）
+#[derive(Debug, Default)]
+pub struct CacheArchiveWriter {
+    next_index: usize,
+}
+
+impl CacheArchiveWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 归档开头的元数据块
+    pub fn begin(&self) -> Result<Vec<u8>, ArchiveError> {
+        let manifest = CacheArchiveManifest {
+            version: CACHE_ARCHIVE_VERSION,
+            exported_at: Utc::now(),
+        };
+        let data = serde_json::to_vec_pretty(&manifest)
+            .map_err(|e| ArchiveError::InvalidArchive(e.to_string()))?;
+        encode_entry(MANIFEST_FILE, &data, manifest.exported_at.timestamp().max(0) as u64)
+    }
+
+    /// 编码一个缓存条目（元数据 + 音频）
+    pub fn encode(&mut self, entry: &CacheArchiveEntry, audio_data: &[u8]) -> Result<Vec<u8>, ArchiveError> {
+        let data = serde_json::to_vec(entry).map_err(|e| ArchiveError::InvalidArchive(e.to_string()))?;
+        let mtime = entry.created_at.max(0) as u64;
+
+        let mut block = encode_entry(&format!("entries/{:08}.json", self.next_index), &data, mtime)?;
+        block.extend(encode_entry(
+            &format!("entries/{:08}.audio", self.next_index),
+            audio_data,
+            mtime,
+        )?);
+        self.next_index += 1;
+        Ok(block)
+    }
+
+    /// 归档结束标记
+    pub fn end(self) -> Vec<u8> {
+        end_of_archive()
+    }
+}
+
+/// 缓存归档增量读取器
+#[derive(Debug, Default)]
+pub struct CacheArchiveReader {
+    decoder: TarDecoder,
+    manifest: Option<CacheArchiveManifest>,
+    /// 已读取元数据、等待音频的条目 (文件名前缀, 条目)
+    pending: Option<(String, CacheArchiveEntry)>,
+}
+
+impl CacheArchiveReader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 输入一段数据，返回其中已完整的条目 (元数据, 音频)
+    pub fn push(&mut self, chunk: &[u8]) -> Result<Vec<(CacheArchiveEntry, Vec<u8>)>, ArchiveError> {
+        let mut entries = Vec::new();
+        for (name, data) in self.decoder.push(chunk)? {
+            if name == MANIFEST_FILE {
+                let manifest: CacheArchiveManifest = serde_json::from_slice(&data)
+                    .map_err(|e| ArchiveError::InvalidArchive(format!("Invalid manifest: {}", e)))?;
+                if manifest.version != CACHE_ARCHIVE_VERSION {
+                    return Err(ArchiveError::InvalidArchive(format!(
+                        "Unsupported cache archive version: {}",
+                        manifest.version
+                    )));
+                }
+                self.manifest = Some(manifest);
+                continue;
+            }
+            if self.manifest.is_none() {
+                return Err(ArchiveError::MissingEntry(MANIFEST_FILE.to_string()));
+            }
+
+            if let Some(stem) = name.strip_suffix(".json") {
+                let entry: CacheArchiveEntry = serde_json::from_slice(&data)
+                    .map_err(|e| ArchiveError::InvalidArchive(format!("Invalid entry {}: {}", name, e)))?;
+                self.pending = Some((stem.to_string(), entry));
+            } else if let Some(stem) = name.strip_suffix(".audio") {
+                match self.pending.take() {
+                    Some((pending, entry)) if pending == stem => entries.push((entry, data)),
+                    _ => return Err(ArchiveError::MissingEntry(format!("{}.json", stem))),
+                }
+            }
+        }
+        Ok(entries)
+    }
+
+    /// 归档元数据（读到之前为 None）
+    pub fn manifest(&self) -> Option<&CacheArchiveManifest> {
+        self.manifest.as_ref()
+    }
+
+    /// 输入结束，检查归档是否完整
+    pub fn finish(self) -> Result<(), ArchiveError> {
+        self.decoder.finish()?;
+        if self.manifest.is_none() {
+            return Err(ArchiveError::MissingEntry(MANIFEST_FILE.to_string()));
+        }
+        match self.pending {
+            Some((stem, _)) => Err(ArchiveError::MissingEntry(format!("{}.audio", stem))),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(cache_key: &str) -> CacheArchiveEntry {
+        let novel_id = Uuid::new_v4();
+        let voice_id = Uuid::new_v4();
+        CacheArchiveEntry {
+            cache_key: cache_key.to_string(),
+            novel_id,
+            segment_index: 3,
+            voice_id,
+            content_hash: "hash".to_string(),
+            duration_ms: 1200,
+            sample_rate: Some(24000),
+            format: AudioFormat::Opus,
+            created_at: 1_700_000_000,
+            references: vec![SegmentReference {
+                novel_id,
+                segment_index: 3,
+                voice_id,
+            }],
+            peaks: Some(vec![0.5, 1.0]),
+        }
+    }
+
+    #[test]
+    fn test_cache_archive_roundtrip() {
+        // 缓存 key 长于 tar 文件名限制
+        let long_key = format!("{:x}:{}:{}", md5::compute("text"), Uuid::new_v4(), "f".repeat(32));
+
+        let mut writer = CacheArchiveWriter::new();
+        let mut archive = writer.begin().unwrap();
+        archive.extend(writer.encode(&entry(&long_key), &[1, 2, 3]).unwrap());
+        archive.extend(writer.encode(&entry("short"), &vec![9; 2000]).unwrap());
+        archive.extend(writer.end());
+
+        let mut reader = CacheArchiveReader::new();
+        let mut entries = Vec::new();
+        for chunk in archive.chunks(1000) {
+            entries.extend(reader.push(chunk).unwrap());
+        }
+        assert_eq!(reader.manifest().unwrap().version, CACHE_ARCHIVE_VERSION);
+        reader.finish().unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].0.cache_key, long_key);
+        assert_eq!(entries[0].0.format, AudioFormat::Opus);
+        assert_eq!(entries[0].0.references.len(), 1);
+        assert_eq!(entries[0].0.peaks, Some(vec![0.5, 1.0]));
+        assert_eq!(entries[0].1, vec![1, 2, 3]);
+        assert_eq!(entries[1].1, vec![9; 2000]);
+
+        // 缺少元数据的归档被拒绝
+        let mut writer = CacheArchiveWriter::new();
+        let mut archive = writer.encode(&entry("a"), &[1]).unwrap();
+        archive.extend(writer.end());
+        assert!(CacheArchiveReader::new().push(&archive).is_err());
+    }
+}
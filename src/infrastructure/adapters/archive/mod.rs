@@ -0,0 +1,13 @@
+//! Archive Adapter
+//!
+//! 简单的 tar 打包/解包实现，以及基于 tar 的音色包、音频缓存导出格式
+
+mod cache_archive;
+mod tar;
+mod voice_bundle;
+
+pub use self::tar::{read_tar, ArchiveError, TarDecoder, TarWriter};
+pub use cache_archive::{
+    CacheArchiveEntry, CacheArchiveManifest, CacheArchiveReader, CacheArchiveWriter, CACHE_ARCHIVE_VERSION,
+};
+pub use voice_bundle::{VoiceBundle, VoiceBundleManifest, VoiceBundleReader, VOICE_BUNDLE_VERSION};
@@ -0,0 +1,277 @@
+//! Tar - 最小化的 ustar 读写实现
+//!
+//! 仅支持普通文件条目，满足音色包等内部归档需求，
+//! 生成的文件可被标准 tar 工具解开。
+//! 大归档（如音频缓存）可逐条目编码/增量解码，无需整体放入内存。
+
+use thiserror::Error;
+
+/// tar 块大小
+const BLOCK_SIZE: usize = 512;
+
+/// 归档错误
+#[derive(Debug, Error)]
+pub enum ArchiveError {
+    #[error("Invalid archive: {0}")]
+    InvalidArchive(String),
+
+    #[error("Entry name too long: {0}")]
+    NameTooLong(String),
+
+    #[error("Missing entry: {0}")]
+    MissingEntry(String),
+}
+
+/// tar 写入器（内存中构建）
+#[derive(Debug, Default)]
+pub struct TarWriter {
+    buffer: Vec<u8>,
+}
+
+impl TarWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 追加一个普通文件条目
+    pub fn append(&mut self, name: &str, data: &[u8], mtime: u64) -> Result<(), ArchiveError> {
+        self.buffer.extend(encode_entry(name, data, mtime)?);
+        Ok(())
+    }
+
+    /// 写入结束标记并返回归档数据
+    pub fn finish(mut self) -> Vec<u8> {
+        self.buffer.extend(end_of_archive());
+        self.buffer
+    }
+}
+
+/// 编码一个普通文件条目（头部 + 数据 + 填充）
+pub fn encode_entry(name: &str, data: &[u8], mtime: u64) -> Result<Vec<u8>, ArchiveError> {
+    if name.is_empty() || name.len() > 100 {
+        return Err(ArchiveError::NameTooLong(name.to_string()));
+    }
+
+    let mut header = [0u8; BLOCK_SIZE];
+    header[..name.len()].copy_from_slice(name.as_bytes());
+    write_octal(&mut header[100..108], 0o644);
+    write_octal(&mut header[108..116], 0);
+    write_octal(&mut header[116..124], 0);
+    write_octal(&mut header[124..136], data.len() as u64);
+    write_octal(&mut header[136..148], mtime);
+    header[156] = b'0';
+    header[257..263].copy_from_slice(b"ustar\0");
+    header[263..265].copy_from_slice(b"00");
+
+    // 计算校验和时校验和字段按空格计算
+    header[148..156].fill(b' ');
+    let checksum: u32 = header.iter().map(|&b| b as u32).sum();
+    write_octal(&mut header[148..155], checksum as u64);
+    header[155] = b' ';
+
+    let padding = (BLOCK_SIZE - data.len() % BLOCK_SIZE) % BLOCK_SIZE;
+    let mut entry = Vec::with_capacity(BLOCK_SIZE + data.len() + padding);
+    entry.extend_from_slice(&header);
+    entry.extend_from_slice(data);
+    entry.extend(std::iter::repeat_n(0u8, padding));
+    Ok(entry)
+}
+
+/// 归档结束标记（两个全零块）
+pub fn end_of_archive() -> Vec<u8> {
+    vec![0u8; BLOCK_SIZE * 2]
+}
+
+/// 增量 tar 解码器：按到达顺序输入数据块，每当条目完整时输出
+///
+/// 只缓存当前未完成的条目，内存占用与单个条目大小相当
+#[derive(Debug, Default)]
+pub struct TarDecoder {
+    buffer: Vec<u8>,
+    finished: bool,
+}
+
+impl TarDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 输入一段数据，返回其中已完整的普通文件条目 (文件名, 数据)
+    pub fn push(&mut self, chunk: &[u8]) -> Result<Vec<(String, Vec<u8>)>, ArchiveError> {
+        if self.finished {
+            return Ok(Vec::new());
+        }
+        self.buffer.extend_from_slice(chunk);
+
+        let mut entries = Vec::new();
+        let mut offset = 0;
+        while let Some(parsed) = parse_entry(&self.buffer[offset..], false)? {
+            match parsed {
+                ParsedEntry::End => {
+                    self.finished = true;
+                    break;
+                }
+                ParsedEntry::File { name, data, consumed } => {
+                    entries.extend(data.map(|data| (name, data)));
+                    offset += consumed;
+                }
+            }
+        }
+        self.buffer.drain(..offset);
+        Ok(entries)
+    }
+
+    /// 输入结束，检查归档是否完整
+    pub fn finish(self) -> Result<(), ArchiveError> {
+        if self.finished || self.buffer.iter().all(|&b| b == 0) {
+            Ok(())
+        } else {
+            Err(ArchiveError::InvalidArchive("Truncated archive".to_string()))
+        }
+    }
+}
+
+/// 解析结果
+enum ParsedEntry {
+    /// 结束标记
+    End,
+    /// 条目（非普通文件时 data 为 None），consumed 为占用的字节数
+    File {
+        name: String,
+        data: Option<Vec<u8>>,
+        consumed: usize,
+    },
+}
+
+/// 从数据开头解析一个条目，数据不足时返回 None
+///
+/// `complete` 表示数据已完整（容忍末尾缺失的填充），否则需等待填充全部到达
+fn parse_entry(data: &[u8], complete: bool) -> Result<Option<ParsedEntry>, ArchiveError> {
+    if data.len() < BLOCK_SIZE {
+        return Ok(None);
+    }
+    let header = &data[..BLOCK_SIZE];
+
+    // 全零块表示归档结束
+    if header.iter().all(|&b| b == 0) {
+        return Ok(Some(ParsedEntry::End));
+    }
+
+    let expected = parse_octal(&header[148..156])?;
+    let actual: u64 = header
+        .iter()
+        .enumerate()
+        .map(|(i, &b)| if (148..156).contains(&i) { b' ' as u64 } else { b as u64 })
+        .sum();
+    if expected != actual {
+        return Err(ArchiveError::InvalidArchive("Header checksum mismatch".to_string()));
+    }
+
+    let name_end = header[..100].iter().position(|&b| b == 0).unwrap_or(100);
+    let name = String::from_utf8_lossy(&header[..name_end]).to_string();
+    let size = parse_octal(&header[124..136])? as usize;
+    let type_flag = header[156];
+
+    let consumed = BLOCK_SIZE + size.div_ceil(BLOCK_SIZE) * BLOCK_SIZE;
+    let required = if complete { BLOCK_SIZE + size } else { consumed };
+    if data.len() < required {
+        return Ok(None);
+    }
+
+    // 只保留普通文件，忽略目录等其他类型
+    let file = (type_flag == b'0' || type_flag == 0).then(|| data[BLOCK_SIZE..BLOCK_SIZE + size].to_vec());
+    Ok(Some(ParsedEntry::File {
+        name,
+        data: file,
+        consumed: consumed.min(data.len()),
+    }))
+}
+
+/// 读取 tar 中的所有普通文件条目，返回 (文件名, 数据)
+pub fn read_tar(data: &[u8]) -> Result<Vec<(String, Vec<u8>)>, ArchiveError> {
+    let mut entries = Vec::new();
+    let mut offset = 0;
+
+    while offset + BLOCK_SIZE <= data.len() {
+        match parse_entry(&data[offset..], true)? {
+            Some(ParsedEntry::End) => break,
+            Some(ParsedEntry::File { name, data, consumed }) => {
+                entries.extend(data.map(|data| (name, data)));
+                offset += consumed;
+            }
+            None => return Err(ArchiveError::InvalidArchive("Truncated entry".to_string())),
+        }
+    }
+
+    Ok(entries)
+}
+
+/// 写入以 NUL 结尾的八进制数字段
+fn write_octal(field: &mut [u8], value: u64) {
+    let digits = field.len() - 1;
+    let text = format!("{:0width$o}", value, width = digits);
+    field[..digits].copy_from_slice(&text.as_bytes()[text.len() - digits..]);
+    field[digits] = 0;
+}
+
+/// 解析八进制数字段（忽略首尾的空格和 NUL）
+fn parse_octal(field: &[u8]) -> Result<u64, ArchiveError> {
+    let text = String::from_utf8_lossy(field);
+    let text = text.trim_matches(|c: char| c == '\0' || c == ' ');
+    if text.is_empty() {
+        return Ok(0);
+    }
+    u64::from_str_radix(text, 8)
+        .map_err(|e| ArchiveError::InvalidArchive(format!("Invalid octal field: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tar_roundtrip() {
+        let mut writer = TarWriter::new();
+        writer.append("voice.json", b"{\"name\":\"test\"}", 0).unwrap();
+        writer.append("reference.wav", &vec![7u8; 1000], 0).unwrap();
+        let archive = writer.finish();
+        assert_eq!(archive.len() % BLOCK_SIZE, 0);
+
+        let entries = read_tar(&archive).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].0, "voice.json");
+        assert_eq!(entries[1].0, "reference.wav");
+        assert_eq!(entries[1].1.len(), 1000);
+    }
+
+    #[test]
+    fn test_decoder_accepts_arbitrary_chunks() {
+        let mut writer = TarWriter::new();
+        writer.append("a.json", b"{}", 0).unwrap();
+        writer.append("a.audio", &vec![3u8; 1500], 0).unwrap();
+        let archive = writer.finish();
+
+        let mut decoder = TarDecoder::new();
+        let mut entries = Vec::new();
+        for chunk in archive.chunks(700) {
+            entries.extend(decoder.push(chunk).unwrap());
+        }
+        decoder.finish().unwrap();
+        assert_eq!(entries, read_tar(&archive).unwrap());
+
+        // 截断的归档
+        let mut decoder = TarDecoder::new();
+        decoder.push(&archive[..1000]).unwrap();
+        assert!(decoder.finish().is_err());
+    }
+
+    #[test]
+    fn test_read_tar_rejects_corrupted_header() {
+        let mut writer = TarWriter::new();
+        writer.append("a.txt", b"hello", 0).unwrap();
+        let mut archive = writer.finish();
+        archive[0] = b'b';
+
+        assert!(read_tar(&archive).is_err());
+    }
+}
@@ -0,0 +1,176 @@
+//! Voice Bundle - 音色包格式
+//!
+//! 一个 tar 归档，包含:
+//! - `voice.json`: 音色元数据（保留原 UUID，使缓存 key 跨实例有效）
+//! - `reference.{ext}`: 原始参考音频
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::tar::{read_tar, ArchiveError, TarDecoder, TarWriter};
+use crate::domain::voice::{TtsConfig, VoiceTags};
+
+/// 当前音色包格式版本
+pub const VOICE_BUNDLE_VERSION: u32 = 1;
+
+/// 元数据文件名
+const MANIFEST_FILE: &str = "voice.json";
+
+/// 音色包元数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoiceBundleManifest {
+    pub version: u32,
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    /// 参考音频对应的文本（旧版本音色包中不存在）
+    #[serde(default)]
+    pub prompt_text: Option<String>,
+    /// TTS 引擎名称（旧版本音色包中不存在）
+    #[serde(default)]
+    pub engine: Option<String>,
+    pub tts_config: TtsConfig,
+    #[serde(default)]
+    pub tags: VoiceTags,
+    pub created_at: DateTime<Utc>,
+    /// 归档内参考音频文件名
+    pub audio_file: String,
+}
+
+/// 音色包
+#[derive(Debug, Clone)]
+pub struct VoiceBundle {
+    pub manifest: VoiceBundleManifest,
+    pub audio_data: Vec<u8>,
+}
+
+impl VoiceBundle {
+    /// 参考音频扩展名
+    pub fn audio_ext(&self) -> Option<String> {
+        std::path::Path::new(&self.manifest.audio_file)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase())
+    }
+
+    /// 打包为 tar
+    pub fn to_tar(&self) -> Result<Vec<u8>, ArchiveError> {
+        let manifest = serde_json::to_vec_pretty(&self.manifest)
+            .map_err(|e| ArchiveError::InvalidArchive(e.to_string()))?;
+        let mtime = self.manifest.created_at.timestamp().max(0) as u64;
+
+        let mut writer = TarWriter::new();
+        writer.append(MANIFEST_FILE, &manifest, mtime)?;
+        writer.append(&self.manifest.audio_file, &self.audio_data, mtime)?;
+        Ok(writer.finish())
+    }
+
+    /// 从 tar 解包
+    pub fn from_tar(data: &[u8]) -> Result<Self, ArchiveError> {
+        Self::from_entries(read_tar(data)?)
+    }
+
+    /// 从归档条目 (文件名, 数据) 组装
+    fn from_entries(mut entries: Vec<(String, Vec<u8>)>) -> Result<Self, ArchiveError> {
+        let manifest_index = entries
+            .iter()
+            .position(|(name, _)| name == MANIFEST_FILE)
+            .ok_or_else(|| ArchiveError::MissingEntry(MANIFEST_FILE.to_string()))?;
+        let (_, manifest_data) = entries.swap_remove(manifest_index);
+
+        let manifest: VoiceBundleManifest = serde_json::from_slice(&manifest_data)
+            .map_err(|e| ArchiveError::InvalidArchive(format!("Invalid manifest: {}", e)))?;
+        if manifest.version != VOICE_BUNDLE_VERSION {
+            return Err(ArchiveError::InvalidArchive(format!(
+                "Unsupported bundle version: {}",
+                manifest.version
+            )));
+        }
+
+        let (_, audio_data) = entries
+            .into_iter()
+            .find(|(name, _)| *name == manifest.audio_file)
+            .ok_or_else(|| ArchiveError::MissingEntry(manifest.audio_file.clone()))?;
+
+        Ok(Self {
+            manifest,
+            audio_data,
+        })
+    }
+}
+
+/// 音色包增量读取器：按块输入归档数据（如逐块读取上传到磁盘的文件），不需要先将整个归档读入内存
+#[derive(Debug, Default)]
+pub struct VoiceBundleReader {
+    decoder: TarDecoder,
+    entries: Vec<(String, Vec<u8>)>,
+}
+
+impl VoiceBundleReader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 输入一段数据
+    pub fn push(&mut self, chunk: &[u8]) -> Result<(), ArchiveError> {
+        self.entries.extend(self.decoder.push(chunk)?);
+        Ok(())
+    }
+
+    /// 输入结束，检查归档完整并返回音色包
+    pub fn finish(self) -> Result<VoiceBundle, ArchiveError> {
+        self.decoder.finish()?;
+        VoiceBundle::from_entries(self.entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_voice_bundle_roundtrip() {
+        let bundle = VoiceBundle {
+            manifest: VoiceBundleManifest {
+                version: VOICE_BUNDLE_VERSION,
+                id: Uuid::new_v4(),
+                name: "旁白".to_string(),
+                description: Some("温和的男声".to_string()),
+                prompt_text: Some("今天天气很好".to_string()),
+                engine: None,
+                tts_config: TtsConfig::default(),
+                tags: VoiceTags {
+                    gender: Some("male".to_string()),
+                    ..Default::default()
+                },
+                created_at: Utc::now(),
+                audio_file: "reference.wav".to_string(),
+            },
+            audio_data: vec![1, 2, 3, 4],
+        };
+
+        let archive = bundle.to_tar().unwrap();
+        let restored = VoiceBundle::from_tar(&archive).unwrap();
+
+        assert_eq!(restored.manifest.id, bundle.manifest.id);
+        assert_eq!(restored.manifest.name, "旁白");
+        assert_eq!(restored.manifest.tags, bundle.manifest.tags);
+        assert_eq!(restored.audio_data, vec![1, 2, 3, 4]);
+        assert_eq!(restored.audio_ext().as_deref(), Some("wav"));
+
+        // 按任意大小分块读取，结果相同
+        let mut reader = VoiceBundleReader::new();
+        for chunk in archive.chunks(100) {
+            reader.push(chunk).unwrap();
+        }
+        let streamed = reader.finish().unwrap();
+        assert_eq!(streamed.manifest.id, bundle.manifest.id);
+        assert_eq!(streamed.audio_data, vec![1, 2, 3, 4]);
+
+        // 截断的归档
+        let mut reader = VoiceBundleReader::new();
+        reader.push(&archive[..700]).unwrap();
+        assert!(reader.finish().is_err());
+    }
+}
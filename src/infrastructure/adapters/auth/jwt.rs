@@ -0,0 +1,95 @@
+//! JWT Token Service
+//!
+//! 实现 AuthTokenPort trait：HS256 签名，sub 为用户 ID，exp 为过期时间
+
+use chrono::{Duration, Utc};
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::application::ports::{AuthError, AuthTokenPort, IssuedToken};
+
+/// 令牌声明
+#[derive(Debug, Serialize, Deserialize)]
+struct Claims {
+    /// 用户 ID
+    sub: String,
+    /// 签发时间（Unix 秒）
+    iat: i64,
+    /// 过期时间（Unix 秒）
+    exp: i64,
+}
+
+/// JWT 登录令牌服务
+pub struct JwtTokenService {
+    encoding_key: EncodingKey,
+    decoding_key: DecodingKey,
+    validation: Validation,
+    ttl: Duration,
+}
+
+impl JwtTokenService {
+    /// 使用共享密钥创建，ttl 为令牌有效期
+    pub fn new(secret: &str, ttl: std::time::Duration) -> Self {
+        Self {
+            encoding_key: EncodingKey::from_secret(secret.as_bytes()),
+            decoding_key: DecodingKey::from_secret(secret.as_bytes()),
+            validation: Validation::new(Algorithm::HS256),
+            ttl: Duration::from_std(ttl).unwrap_or(Duration::MAX),
+        }
+    }
+}
+
+impl AuthTokenPort for JwtTokenService {
+    fn issue(&self, user_id: Uuid) -> Result<IssuedToken, AuthError> {
+        let now = Utc::now();
+        let expires_at = now + self.ttl;
+        let claims = Claims {
+            sub: user_id.to_string(),
+            iat: now.timestamp(),
+            exp: expires_at.timestamp(),
+        };
+        let token = encode(&Header::new(Algorithm::HS256), &claims, &self.encoding_key)
+            .map_err(|e| AuthError::InvalidToken(e.to_string()))?;
+        Ok(IssuedToken { token, expires_at })
+    }
+
+    fn verify(&self, token: &str) -> Result<Uuid, AuthError> {
+        let data = decode::<Claims>(token, &self.decoding_key, &self.validation)
+            .map_err(|e| AuthError::InvalidToken(e.to_string()))?;
+        Uuid::parse_str(&data.claims.sub).map_err(|e| AuthError::InvalidToken(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TTL: std::time::Duration = std::time::Duration::from_secs(3600);
+
+    #[test]
+    fn test_issue_and_verify() {
+        let service = JwtTokenService::new("secret", TTL);
+        let user_id = Uuid::new_v4();
+        let issued = service.issue(user_id).unwrap();
+        assert!(issued.expires_at > Utc::now());
+        assert_eq!(service.verify(&issued.token).unwrap(), user_id);
+
+        // 其他密钥签发的令牌和被篡改的令牌无效
+        let other = JwtTokenService::new("other secret", TTL);
+        assert!(other.verify(&issued.token).is_err());
+        assert!(service.verify(&format!("{}x", issued.token)).is_err());
+    }
+
+    #[test]
+    fn test_expired_token_rejected() {
+        let service = JwtTokenService::new("secret", TTL);
+        let claims = Claims {
+            sub: Uuid::new_v4().to_string(),
+            iat: Utc::now().timestamp() - 7200,
+            exp: Utc::now().timestamp() - 3600,
+        };
+        let token = encode(&Header::new(Algorithm::HS256), &claims, &service.encoding_key).unwrap();
+        assert!(service.verify(&token).is_err());
+    }
+}
@@ -0,0 +1,9 @@
+//! Auth Adapter - 密码哈希（Argon2）、登录令牌（JWT）与参考音频链接签名实现
+
+mod jwt;
+mod password;
+mod voice_audio;
+
+pub use jwt::JwtTokenService;
+pub use password::Argon2PasswordHasher;
+pub use voice_audio::{voice_audio_url, VoiceAudioSigner};
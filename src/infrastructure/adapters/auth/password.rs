@@ -0,0 +1,55 @@
+//! Argon2 Password Hasher
+//!
+//! 实现 PasswordHasherPort trait，哈希以 PHC 字符串保存（包含算法参数和盐）
+
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use uuid::Uuid;
+
+use crate::application::ports::{AuthError, PasswordHasherPort};
+
+/// Argon2id 密码哈希（默认参数）
+#[derive(Default)]
+pub struct Argon2PasswordHasher {
+    argon2: Argon2<'static>,
+}
+
+impl Argon2PasswordHasher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl PasswordHasherPort for Argon2PasswordHasher {
+    fn hash(&self, password: &str) -> Result<String, AuthError> {
+        // v4 UUID 的 16 字节来自系统随机数，作为盐足够
+        let salt = SaltString::encode_b64(Uuid::new_v4().as_bytes())
+            .map_err(|e| AuthError::HashError(e.to_string()))?;
+        self.argon2
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|e| AuthError::HashError(e.to_string()))
+    }
+
+    fn verify(&self, password: &str, password_hash: &str) -> Result<bool, AuthError> {
+        let parsed = PasswordHash::new(password_hash).map_err(|e| AuthError::HashError(e.to_string()))?;
+        Ok(self.argon2.verify_password(password.as_bytes(), &parsed).is_ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_and_verify() {
+        let hasher = Argon2PasswordHasher::new();
+        let hash = hasher.hash("correct horse").unwrap();
+        assert!(hash.starts_with("$argon2id$"));
+        assert_ne!(hash, hasher.hash("correct horse").unwrap());
+
+        assert!(hasher.verify("correct horse", &hash).unwrap());
+        assert!(!hasher.verify("wrong horse", &hash).unwrap());
+        assert!(hasher.verify("correct horse", "not a hash").is_err());
+    }
+}
@@ -0,0 +1,81 @@
+//! 参考音频下载链接签名
+//!
+//! TTS 服务按 Worker 提供的 URL 下载参考音频，不携带登录令牌。启用用户账号时链接附带
+//! `?sig=`（HS256(voice_id)），没有有效签名的请求按音色不存在处理；签名不随时间变化，
+//! TTS 服务仍可按 URL 缓存参考音频
+
+use jsonwebtoken::{crypto, Algorithm, DecodingKey, EncodingKey};
+use std::fmt;
+use uuid::Uuid;
+
+/// 参考音频下载链接签名器
+#[derive(Clone)]
+pub struct VoiceAudioSigner {
+    encoding_key: EncodingKey,
+    decoding_key: DecodingKey,
+}
+
+impl VoiceAudioSigner {
+    /// 使用共享密钥创建（与登录令牌共用 auth.jwt_secret）
+    pub fn new(secret: &str) -> Self {
+        Self {
+            encoding_key: EncodingKey::from_secret(secret.as_bytes()),
+            decoding_key: DecodingKey::from_secret(secret.as_bytes()),
+        }
+    }
+
+    /// 音色的签名（base64url）
+    pub fn sign(&self, voice_id: Uuid) -> String {
+        crypto::sign(&signed_message(voice_id), &self.encoding_key, Algorithm::HS256).unwrap_or_default()
+    }
+
+    /// 校验签名
+    pub fn verify(&self, voice_id: Uuid, signature: &str) -> bool {
+        crypto::verify(signature, &signed_message(voice_id), &self.decoding_key, Algorithm::HS256)
+            .unwrap_or(false)
+    }
+}
+
+impl fmt::Debug for VoiceAudioSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VoiceAudioSigner").finish_non_exhaustive()
+    }
+}
+
+/// 签名内容带前缀，与登录令牌的签名区分
+fn signed_message(voice_id: Uuid) -> Vec<u8> {
+    format!("voice-audio:{}", voice_id).into_bytes()
+}
+
+/// 参考音频下载链接 `{base_url}/api/voice/audio/{voice_id}`，`signer` 为 None（未启用账号）时不带签名
+pub fn voice_audio_url(base_url: &str, voice_id: Uuid, signer: Option<&VoiceAudioSigner>) -> String {
+    match signer {
+        Some(signer) => format!("{}/api/voice/audio/{}?sig={}", base_url, voice_id, signer.sign(voice_id)),
+        None => format!("{}/api/voice/audio/{}", base_url, voice_id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_voice_audio_signature() {
+        let signer = VoiceAudioSigner::new("secret");
+        let voice_id = Uuid::new_v4();
+        let signature = signer.sign(voice_id);
+
+        assert_eq!(signature, signer.sign(voice_id));
+        assert!(signer.verify(voice_id, &signature));
+        assert!(!signer.verify(Uuid::new_v4(), &signature));
+        assert!(!signer.verify(voice_id, ""));
+        assert!(!VoiceAudioSigner::new("other").verify(voice_id, &signature));
+
+        let url = voice_audio_url("http://rovel", voice_id, Some(&signer));
+        assert_eq!(url, format!("http://rovel/api/voice/audio/{}?sig={}", voice_id, signature));
+        assert_eq!(
+            voice_audio_url("http://rovel", voice_id, None),
+            format!("http://rovel/api/voice/audio/{}", voice_id)
+        );
+    }
+}
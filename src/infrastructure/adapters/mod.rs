@@ -2,10 +2,18 @@
 //!
 //! 六边形架构的适配器实现
 
+pub mod archive;
+pub mod auth;
+#[cfg(feature = "redis-queue")]
+pub mod queue;
 pub mod tts;
 pub mod storage;
 pub mod transcoder;
 
+pub use archive::*;
+pub use auth::*;
+#[cfg(feature = "redis-queue")]
+pub use queue::*;
 pub use tts::*;
 pub use storage::*;
 pub use transcoder::*;
@@ -0,0 +1,5 @@
+//! Queue Adapter - 多个实例共享的推理队列（Redis Streams，需以 `redis-queue` feature 编译）
+
+mod redis_streams;
+
+pub use redis_streams::{RedisQueueConfig, RedisTaskManager, SharedSessions};
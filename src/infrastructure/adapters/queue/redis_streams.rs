@@ -0,0 +1,921 @@
+//! Redis Streams Task Queue - 多个实例共享的推理队列
+//!
+//! RedisTaskManager 包装本实例的任务管理器：提交的任务先进入本地发件队列，由后台任务按优先级
+//! 写入 Redis Stream（`{prefix}:current` / `:prefetch` / `:background`）。所有实例以同一消费组
+//! 读取，本实例 Worker 的收件队列不足 `read_ahead` 时才按优先级取任务，其余留在 Redis 中
+//! 由空闲的实例取走；容量按所有实例排队中和推理中的条目合计。
+//!
+//! 任务结束（完成、失败、取消）后确认并删除取出的条目，写入结束标记（`{prefix}:finished:{task_id}`）：
+//! 提交任务的实例据此更新任务状态并通知客户端，推理其他实例任务的实例据此跳过或中止已取消的任务，
+//! 同一任务因提升优先级重复入队的条目也据此跳过。实例崩溃后其取出未完成的条目超过
+//! `claim_idle` 由其他实例接管。
+//!
+//! 推理其他实例的任务需要同样的小说、音色和音频缓存：共享队列的实例需使用同一数据库、
+//! 存储目录和文件音频缓存；会话和 WebSocket 连接仍在提交任务的实例上，
+//! Worker 通过 SharedSessions 把取出任务所属的会话视为有效
+
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use redis::aio::ConnectionManager;
+use redis::streams::{StreamAutoClaimOptions, StreamAutoClaimReply, StreamId, StreamReadOptions, StreamReadReply};
+use redis::{AsyncCommands, RedisResult};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use crate::application::ports::{
+    InferenceTask, Session, SessionError, SessionManagerPort, TaskError, TaskManagerPort, TaskPriority,
+    TaskState, VoiceHandoff, WindowConfig,
+};
+use crate::domain::voice::SynthesisParams;
+use crate::infrastructure::events::EventPublisher;
+use crate::infrastructure::memory::TaskQueue;
+
+/// 结束标记的保留时长（秒）
+const FINISHED_TTL_SECS: u64 = 24 * 60 * 60;
+
+/// 没有可取的任务时轮询 Redis 的间隔
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// 同步队列长度、结束标记及接管超时条目的间隔
+const SYNC_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 每次查询结束标记的任务数
+const MARKER_BATCH: usize = 100;
+
+/// 出队顺序
+const PRIORITIES: [TaskPriority; 3] = [TaskPriority::Current, TaskPriority::Prefetch, TaskPriority::Background];
+
+/// Redis 队列配置
+#[derive(Debug, Clone)]
+pub struct RedisQueueConfig {
+    /// Redis 地址
+    pub url: String,
+    /// 键前缀
+    pub key_prefix: String,
+    /// 消费组
+    pub group: String,
+    /// 排队任务数上限（所有实例合计）
+    pub capacity: usize,
+    /// 本实例预先取出的任务数
+    pub read_ahead: usize,
+    /// 取出后超过该时长未完成的条目由其他实例接管
+    pub claim_idle: Duration,
+}
+
+impl RedisQueueConfig {
+    fn stream_key(&self, priority: TaskPriority) -> String {
+        format!("{}:{}", self.key_prefix, priority.as_str())
+    }
+
+    fn finished_key(&self, task_id: &str) -> String {
+        format!("{}:finished:{}", self.key_prefix, task_id)
+    }
+}
+
+/// Stream 条目中的任务
+#[derive(Debug, Serialize, Deserialize)]
+struct QueuedTask {
+    task_id: String,
+    session_id: String,
+    novel_id: Uuid,
+    voice_id: Uuid,
+    segment_index: u32,
+    segment_content: String,
+    params: SynthesisParams,
+    explicit: bool,
+    priority: TaskPriority,
+    request_id: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<&InferenceTask> for QueuedTask {
+    fn from(task: &InferenceTask) -> Self {
+        Self {
+            task_id: task.task_id.clone(),
+            session_id: task.session_id.clone(),
+            novel_id: task.novel_id,
+            voice_id: task.voice_id,
+            segment_index: task.segment_index,
+            segment_content: task.segment_content.clone(),
+            params: task.params.clone(),
+            explicit: task.explicit,
+            priority: task.priority,
+            request_id: task.request_id.clone(),
+            created_at: task.created_at,
+        }
+    }
+}
+
+impl From<QueuedTask> for InferenceTask {
+    fn from(queued: QueuedTask) -> Self {
+        InferenceTask {
+            task_id: queued.task_id,
+            session_id: queued.session_id,
+            novel_id: queued.novel_id,
+            voice_id: queued.voice_id,
+            segment_index: queued.segment_index,
+            segment_content: queued.segment_content,
+            params: queued.params,
+            explicit: queued.explicit,
+            priority: queued.priority,
+            cancel_token: CancellationToken::new(),
+            state: TaskState::Pending,
+            request_id: queued.request_id,
+            created_at: queued.created_at,
+            completed_at: None,
+            error_message: None,
+        }
+    }
+}
+
+/// 任务结束标记
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct FinishedMarker {
+    state: TaskState,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// 本实例取出的 Stream 条目
+#[derive(Debug, Clone, PartialEq)]
+struct EntryRef {
+    stream: String,
+    id: String,
+}
+
+/// 交给后台任务执行的 Redis 写入
+enum Command {
+    /// 写入结束标记，确认并删除取出的条目
+    Finish {
+        task_id: String,
+        marker: FinishedMarker,
+        entries: Vec<EntryRef>,
+    },
+    /// 删除结束标记（失败的任务重试）
+    Reopen(String),
+    /// 之前的写入全部完成后通知
+    Flush(oneshot::Sender<()>),
+}
+
+/// 任务管理器与后台任务共享的状态
+#[derive(Default)]
+struct SharedState {
+    /// 其他实例提交、本实例取出的任务
+    foreign: DashMap<String, InferenceTask>,
+    /// task_id -> 本实例取出的条目（任务结束后确认）
+    consumed: DashMap<String, Vec<EntryRef>>,
+    /// Redis 中的条目数（所有实例排队中和推理中的任务），同步时更新
+    depth: AtomicUsize,
+}
+
+impl SharedState {
+    /// 会话是否有本实例取出、尚未结束的其他实例任务
+    fn has_foreign_session(&self, session_id: &str) -> bool {
+        self.foreign
+            .iter()
+            .any(|task| task.session_id == session_id && matches!(task.state, TaskState::Pending | TaskState::Inferring))
+    }
+}
+
+/// 共享 Redis 队列的任务管理器
+pub struct RedisTaskManager {
+    tasks: Arc<dyn TaskManagerPort>,
+    /// 本实例提交、等待写入 Redis 的任务（`tasks` 入队的队列）
+    outbox: Arc<TaskQueue>,
+    shared: Arc<SharedState>,
+    capacity: usize,
+    commands: mpsc::UnboundedSender<Command>,
+}
+
+impl RedisTaskManager {
+    /// 连接 Redis、创建消费组并启动后台任务（需在 Tokio 运行时中调用）
+    ///
+    /// `tasks` 是本实例的任务管理器，其提交的任务进入 `outbox`；
+    /// Worker 消费 `inbox`，其中是本实例从 Redis 取出的任务
+    pub async fn connect(
+        config: RedisQueueConfig,
+        tasks: Arc<dyn TaskManagerPort>,
+        outbox: Arc<TaskQueue>,
+        inbox: Arc<TaskQueue>,
+        event_publisher: Arc<EventPublisher>,
+    ) -> RedisResult<Self> {
+        let client = redis::Client::open(config.url.as_str())?;
+        let mut conn = client.get_connection_manager().await?;
+        for priority in PRIORITIES {
+            let created: RedisResult<()> = conn
+                .xgroup_create_mkstream(config.stream_key(priority), &config.group, "0")
+                .await;
+            match created {
+                Ok(()) => {}
+                // 其他实例已创建
+                Err(e) if e.code() == Some("BUSYGROUP") => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        let (manager, commands) = Self::new(tasks.clone(), outbox.clone(), config.capacity);
+        let bridge = Bridge {
+            conn,
+            consumer: format!("rovel-{}", Uuid::new_v4()),
+            config,
+            tasks,
+            outbox,
+            inbox,
+            shared: manager.shared.clone(),
+            event_publisher,
+            unpublished: Vec::new(),
+        };
+        tracing::info!(consumer = %bridge.consumer, group = %bridge.config.group, "Joined Redis task queue");
+        tokio::spawn(bridge.run(commands));
+        Ok(manager)
+    }
+
+    fn new(
+        tasks: Arc<dyn TaskManagerPort>,
+        outbox: Arc<TaskQueue>,
+        capacity: usize,
+    ) -> (Self, mpsc::UnboundedReceiver<Command>) {
+        let (commands, receiver) = mpsc::unbounded_channel();
+        let manager = Self {
+            tasks,
+            outbox,
+            shared: Arc::new(SharedState::default()),
+            capacity,
+            commands,
+        };
+        (manager, receiver)
+    }
+
+    /// Worker 使用的会话管理器：本实例取出的其他实例任务所属的会话视为有效
+    pub fn sessions(&self, sessions: Arc<dyn SessionManagerPort>) -> SharedSessions {
+        SharedSessions {
+            sessions,
+            shared: self.shared.clone(),
+        }
+    }
+
+    /// 等待已提交的 Redis 写入全部完成（关闭服务前调用）
+    pub async fn flush(&self) {
+        let (done, wait) = oneshot::channel();
+        if self.commands.send(Command::Flush(done)).is_ok() {
+            let _ = wait.await;
+        }
+    }
+
+    fn send(&self, command: Command) {
+        if self.commands.send(command).is_err() {
+            tracing::warn!("Redis queue writer stopped, change not applied");
+        }
+    }
+
+    /// 任务结束：写入结束标记并确认本实例取出的条目
+    fn finish(&self, task_id: &str, state: TaskState, error: Option<String>) {
+        let entries = self
+            .shared
+            .consumed
+            .remove(task_id)
+            .map(|(_, entries)| entries)
+            .unwrap_or_default();
+        self.send(Command::Finish {
+            task_id: task_id.to_string(),
+            marker: FinishedMarker { state, error },
+            entries,
+        });
+    }
+
+    /// 取消后为会话中被取消的任务写入结束标记（其他实例取出后跳过或中止）
+    fn cancelled(&self, session_id: &str, cancel: impl FnOnce() -> usize) -> usize {
+        let active: Vec<String> = self
+            .tasks
+            .get_tasks_by_session(session_id)
+            .into_iter()
+            .filter(|task| matches!(task.state, TaskState::Pending | TaskState::Inferring))
+            .map(|task| task.task_id)
+            .collect();
+        let cancelled_count = cancel();
+        if cancelled_count > 0 {
+            for task_id in active {
+                if self.tasks.get_state(&task_id) == Some(TaskState::Cancelled) {
+                    self.finish(&task_id, TaskState::Cancelled, None);
+                }
+            }
+        }
+        cancelled_count
+    }
+}
+
+fn is_finished(state: TaskState) -> bool {
+    matches!(state, TaskState::Ready | TaskState::Failed | TaskState::Cancelled)
+}
+
+impl TaskManagerPort for RedisTaskManager {
+    fn submit(&self, tasks: Vec<InferenceTask>) -> Result<Vec<String>, TaskError> {
+        let depth = self.queue_depth();
+        if depth + tasks.len() > self.capacity {
+            let error = TaskError::QueueFull {
+                depth,
+                capacity: self.capacity,
+            };
+            tracing::warn!(count = tasks.len(), error = %error, "Tasks rejected");
+            return Err(error);
+        }
+        self.tasks.submit(tasks)
+    }
+
+    fn cancel_pending(&self, session_id: &str) -> usize {
+        self.cancelled(session_id, || self.tasks.cancel_pending(session_id))
+    }
+
+    fn cancel_all(&self, session_id: &str) -> usize {
+        self.cancelled(session_id, || self.tasks.cancel_all(session_id))
+    }
+
+    fn cancel_outside(&self, session_id: &str, start: u32, end: u32) -> usize {
+        self.cancelled(session_id, || self.tasks.cancel_outside(session_id, start, end))
+    }
+
+    fn cancel_window(&self, session_id: &str) -> usize {
+        self.cancelled(session_id, || self.tasks.cancel_window(session_id))
+    }
+
+    fn prioritize(&self, task_id: &str, priority: TaskPriority) -> bool {
+        self.tasks.prioritize(task_id, priority)
+    }
+
+    fn is_cancelled(&self, task_id: &str) -> bool {
+        match self.shared.foreign.get(task_id) {
+            Some(task) => task.state == TaskState::Cancelled,
+            None => self.tasks.is_cancelled(task_id),
+        }
+    }
+
+    fn get_state(&self, task_id: &str) -> Option<TaskState> {
+        match self.shared.foreign.get(task_id) {
+            Some(task) => Some(task.state),
+            None => self.tasks.get_state(task_id),
+        }
+    }
+
+    fn set_state(&self, task_id: &str, state: TaskState) -> Result<(), TaskError> {
+        if let Some(mut task) = self.shared.foreign.get_mut(task_id) {
+            task.state = state;
+            drop(task);
+            if is_finished(state) {
+                self.shared.foreign.remove(task_id);
+                self.finish(task_id, state, None);
+            }
+            return Ok(());
+        }
+
+        self.tasks.set_state(task_id, state)?;
+        if is_finished(state) {
+            self.finish(task_id, state, None);
+        }
+        Ok(())
+    }
+
+    fn set_failed(&self, task_id: &str, error: String) -> Result<(), TaskError> {
+        if self.shared.foreign.remove(task_id).is_none() {
+            self.tasks.set_failed(task_id, error.clone())?;
+        }
+        self.finish(task_id, TaskState::Failed, Some(error));
+        Ok(())
+    }
+
+    fn get_task(&self, task_id: &str) -> Option<InferenceTask> {
+        match self.shared.foreign.get(task_id) {
+            Some(task) => Some(task.clone()),
+            None => self.tasks.get_task(task_id),
+        }
+    }
+
+    fn get_tasks_by_session(&self, session_id: &str) -> Vec<InferenceTask> {
+        self.tasks.get_tasks_by_session(session_id)
+    }
+
+    fn list_tasks(&self, state: Option<TaskState>) -> Vec<InferenceTask> {
+        let mut tasks = self.tasks.list_tasks(state);
+        tasks.extend(
+            self.shared
+                .foreign
+                .iter()
+                .filter(|task| state.is_none_or(|state| task.state == state))
+                .map(|task| task.clone()),
+        );
+        tasks
+    }
+
+    fn retry(&self, task_id: &str, priority: TaskPriority) -> Result<(), TaskError> {
+        // 先删除结束标记，重新写入 Redis 的条目才不会被跳过
+        if self.tasks.get_state(task_id) == Some(TaskState::Failed) {
+            self.send(Command::Reopen(task_id.to_string()));
+        }
+        self.tasks.retry(task_id, priority)
+    }
+
+    fn cleanup_session(&self, session_id: &str) {
+        for task in self.tasks.get_tasks_by_session(session_id) {
+            if !is_finished(task.state) {
+                self.finish(&task.task_id, TaskState::Cancelled, None);
+            }
+        }
+        self.tasks.cleanup_session(session_id);
+    }
+
+    fn queue_depth(&self) -> usize {
+        self.shared.depth.load(Ordering::Acquire) + self.outbox.len()
+    }
+}
+
+/// Worker 使用的会话管理器
+///
+/// 其他实例提交的任务所属的会话不在本实例中，取出后视为有效直到任务结束；其余操作交给本实例的会话管理器
+pub struct SharedSessions {
+    sessions: Arc<dyn SessionManagerPort>,
+    shared: Arc<SharedState>,
+}
+
+impl SessionManagerPort for SharedSessions {
+    fn create(&self, session: Session) -> Result<String, SessionError> {
+        self.sessions.create(session)
+    }
+
+    fn get(&self, id: &str) -> Result<Session, SessionError> {
+        self.sessions.get(id)
+    }
+
+    fn update_index(&self, id: &str, index: u32) -> Result<(), SessionError> {
+        self.sessions.update_index(id, index)
+    }
+
+    fn step_index(&self, id: &str, delta: i32, last_index: u32) -> Result<u32, SessionError> {
+        self.sessions.step_index(id, delta, last_index)
+    }
+
+    fn update_position(&self, id: &str, index: u32, offset_ms: u32) -> Result<(), SessionError> {
+        self.sessions.update_position(id, index, offset_ms)
+    }
+
+    fn update_voice(&self, id: &str, voice_id: Uuid) -> Result<(), SessionError> {
+        self.sessions.update_voice(id, voice_id)
+    }
+
+    fn update_window(&self, id: &str, window: WindowConfig) -> Result<(), SessionError> {
+        self.sessions.update_window(id, window)
+    }
+
+    fn set_auto_advance(&self, id: &str, enabled: bool) -> Result<(), SessionError> {
+        self.sessions.set_auto_advance(id, enabled)
+    }
+
+    fn set_voice_handoff(&self, id: &str, handoff: Option<VoiceHandoff>) -> Result<(), SessionError> {
+        self.sessions.set_voice_handoff(id, handoff)
+    }
+
+    fn update_params(&self, id: &str, params: SynthesisParams) -> Result<(), SessionError> {
+        self.sessions.update_params(id, params)
+    }
+
+    fn set_segment_params(&self, id: &str, segment_index: u32, params: SynthesisParams) -> Result<(), SessionError> {
+        self.sessions.set_segment_params(id, segment_index, params)
+    }
+
+    fn is_valid(&self, id: &str) -> bool {
+        self.sessions.is_valid(id) || self.shared.has_foreign_session(id)
+    }
+
+    fn close(&self, id: &str) -> Result<(), SessionError> {
+        self.sessions.close(id)
+    }
+
+    fn touch(&self, id: &str) {
+        self.sessions.touch(id)
+    }
+
+    fn get_expired_sessions(&self, idle_timeout_secs: u64) -> Vec<String> {
+        self.sessions.get_expired_sessions(idle_timeout_secs)
+    }
+
+    fn list_all(&self) -> Vec<String> {
+        self.sessions.list_all()
+    }
+}
+
+/// 在本地队列与 Redis 之间转发任务的后台任务
+struct Bridge {
+    conn: ConnectionManager,
+    consumer: String,
+    config: RedisQueueConfig,
+    /// 本实例的任务管理器
+    tasks: Arc<dyn TaskManagerPort>,
+    outbox: Arc<TaskQueue>,
+    /// Worker 消费的队列
+    inbox: Arc<TaskQueue>,
+    shared: Arc<SharedState>,
+    event_publisher: Arc<EventPublisher>,
+    /// 写入 Redis 失败、下次同步时重试的任务
+    unpublished: Vec<String>,
+}
+
+impl Bridge {
+    async fn run(mut self, mut commands: mpsc::UnboundedReceiver<Command>) {
+        let mut sync = tokio::time::interval(SYNC_INTERVAL);
+        let mut poll = tokio::time::interval(POLL_INTERVAL);
+        let outbox = self.outbox.clone();
+        loop {
+            // 先执行写入：重试任务删除结束标记后才写入新的条目
+            tokio::select! {
+                biased;
+                command = commands.recv() => match command {
+                    Some(command) => self.execute(command).await,
+                    None => break,
+                },
+                Some(task_id) = outbox.pop() => self.publish(task_id).await,
+                _ = sync.tick() => self.sync().await,
+                _ = poll.tick() => self.consume().await,
+            }
+        }
+    }
+
+    async fn execute(&mut self, command: Command) {
+        match command {
+            Command::Finish { task_id, marker, entries } => {
+                let marker = serde_json::to_string(&marker).expect("marker serializes");
+                let written: RedisResult<()> = self
+                    .conn
+                    .set_ex(self.config.finished_key(&task_id), marker, FINISHED_TTL_SECS)
+                    .await;
+                if let Err(e) = written {
+                    tracing::warn!(task_id = %task_id, error = %e, "Failed to write task finished marker");
+                }
+                self.ack(&entries).await;
+            }
+            Command::Reopen(task_id) => {
+                let deleted: RedisResult<()> = self.conn.del(self.config.finished_key(&task_id)).await;
+                if let Err(e) = deleted {
+                    tracing::warn!(task_id = %task_id, error = %e, "Failed to delete task finished marker");
+                }
+            }
+            Command::Flush(done) => {
+                let _ = done.send(());
+            }
+        }
+    }
+
+    /// 确认并删除条目（Stream 长度即为未完成的任务数）
+    async fn ack(&mut self, entries: &[EntryRef]) {
+        for entry in entries {
+            let acked: RedisResult<()> = self.conn.xack(&entry.stream, &self.config.group, &[&entry.id]).await;
+            let deleted: RedisResult<()> = self.conn.xdel(&entry.stream, &[&entry.id]).await;
+            if let Err(e) = acked.and(deleted) {
+                tracing::warn!(stream = %entry.stream, entry_id = %entry.id, error = %e, "Failed to ack task entry");
+            }
+        }
+    }
+
+    /// 把本实例提交的任务写入对应优先级的 Stream
+    async fn publish(&mut self, task_id: String) {
+        let Some(task) = self.tasks.get_task(&task_id) else {
+            return;
+        };
+        // 写入前已取消
+        if task.state != TaskState::Pending {
+            return;
+        }
+        let payload = serde_json::to_string(&QueuedTask::from(&task)).expect("task serializes");
+        let added: RedisResult<String> = self
+            .conn
+            .xadd(self.config.stream_key(task.priority), "*", &[("task", payload)])
+            .await;
+        if let Err(e) = added {
+            tracing::warn!(task_id = %task_id, error = %e, "Failed to publish task to Redis");
+            self.unpublished.push(task_id);
+        }
+    }
+
+    /// Worker 收件队列有空位时按优先级取任务
+    async fn consume(&mut self) {
+        if self.inbox.is_closed() {
+            return;
+        }
+        let mut room = self.config.read_ahead.saturating_sub(self.inbox.len());
+        for priority in PRIORITIES {
+            if room == 0 {
+                break;
+            }
+            let stream = self.config.stream_key(priority);
+            let options = StreamReadOptions::default()
+                .group(&self.config.group, &self.consumer)
+                .count(room);
+            let reply: RedisResult<Option<StreamReadReply>> =
+                self.conn.xread_options(&[&stream], &[">"], &options).await;
+            let entries = match reply {
+                Ok(reply) => reply.into_iter().flat_map(|reply| reply.keys).flat_map(|key| key.ids),
+                Err(e) => {
+                    tracing::warn!(stream = %stream, error = %e, "Failed to read Redis task queue");
+                    return;
+                }
+            };
+            for entry in entries.collect::<Vec<_>>() {
+                if self.accept(&stream, entry).await {
+                    room = room.saturating_sub(1);
+                }
+            }
+        }
+    }
+
+    /// 取出的条目放入 Worker 收件队列，返回是否放入；无需推理的条目直接确认
+    async fn accept(&mut self, stream: &str, entry: StreamId) -> bool {
+        let entry_ref = EntryRef {
+            stream: stream.to_string(),
+            id: entry.id.clone(),
+        };
+        let queued = entry
+            .get::<String>("task")
+            .and_then(|payload| serde_json::from_str::<QueuedTask>(&payload).ok());
+        let Some(queued) = queued else {
+            tracing::warn!(stream = %stream, entry_id = %entry.id, "Invalid task entry, dropping");
+            self.ack(&[entry_ref]).await;
+            return false;
+        };
+
+        let task_id = queued.task_id.clone();
+        let session_id = queued.session_id.clone();
+        let priority = queued.priority;
+        let state = self
+            .tasks
+            .get_state(&task_id)
+            .or_else(|| self.shared.foreign.get(&task_id).map(|task| task.state));
+        match state {
+            // 本实例提交的任务，或已取出的其他实例任务提升了优先级
+            Some(TaskState::Pending) => {}
+            Some(_) => {
+                self.ack(&[entry_ref]).await;
+                return false;
+            }
+            None => {
+                if self.finished_marker(&task_id).await.is_some() {
+                    self.ack(&[entry_ref]).await;
+                    return false;
+                }
+                self.shared.foreign.insert(task_id.clone(), queued.into());
+            }
+        }
+
+        // 收件队列已关闭（服务正在关闭）：不确认，由其他实例接管
+        if !self.inbox.push(&task_id, &session_id, priority) {
+            self.shared
+                .foreign
+                .remove_if(&task_id, |_, task| task.state == TaskState::Pending);
+            return false;
+        }
+        self.shared.consumed.entry(task_id).or_default().push(entry_ref);
+        true
+    }
+
+    async fn finished_marker(&mut self, task_id: &str) -> Option<FinishedMarker> {
+        let marker: RedisResult<Option<String>> = self.conn.get(self.config.finished_key(task_id)).await;
+        marker
+            .ok()
+            .flatten()
+            .and_then(|marker| serde_json::from_str(&marker).ok())
+    }
+
+    async fn sync(&mut self) {
+        for task_id in std::mem::take(&mut self.unpublished) {
+            self.publish(task_id).await;
+        }
+        self.update_depth().await;
+        self.apply_markers().await;
+        self.claim_stale().await;
+    }
+
+    async fn update_depth(&mut self) {
+        let mut depth = 0;
+        for priority in PRIORITIES {
+            match self.conn.xlen::<_, usize>(self.config.stream_key(priority)).await {
+                Ok(len) => depth += len,
+                Err(e) => {
+                    tracing::warn!(error = %e, "Failed to read Redis task queue length");
+                    return;
+                }
+            }
+        }
+        self.shared.depth.store(depth, Ordering::Release);
+    }
+
+    /// 按结束标记更新任务：其他实例完成了本实例提交的任务，或本实例取出的任务已被取消
+    async fn apply_markers(&mut self) {
+        let mut task_ids: Vec<String> = self
+            .tasks
+            .list_tasks(Some(TaskState::Pending))
+            .into_iter()
+            .map(|task| task.task_id)
+            .collect();
+        task_ids.extend(self.shared.foreign.iter().map(|task| task.task_id.clone()));
+
+        for batch in task_ids.chunks(MARKER_BATCH) {
+            let keys: Vec<String> = batch.iter().map(|task_id| self.config.finished_key(task_id)).collect();
+            let markers: Vec<Option<String>> = match self.conn.mget(&keys).await {
+                Ok(markers) => markers,
+                Err(e) => {
+                    tracing::warn!(error = %e, "Failed to read task finished markers");
+                    return;
+                }
+            };
+            for (task_id, marker) in batch.iter().zip(markers) {
+                let Some(marker) = marker.and_then(|marker| serde_json::from_str::<FinishedMarker>(&marker).ok())
+                else {
+                    continue;
+                };
+                self.apply_marker(task_id, marker).await;
+            }
+        }
+    }
+
+    async fn apply_marker(&mut self, task_id: &str, marker: FinishedMarker) {
+        let entries = self
+            .shared
+            .consumed
+            .remove(task_id)
+            .map(|(_, entries)| entries)
+            .unwrap_or_default();
+        self.ack(&entries).await;
+
+        // 其他实例的任务已结束：中止推理，Worker 不再写入结果
+        if let Some((_, task)) = self.shared.foreign.remove(task_id) {
+            task.cancel_token.cancel();
+            tracing::debug!(task_id = %task_id, state = ?marker.state, "Shared task finished elsewhere");
+            return;
+        }
+
+        let Some(task) = self.tasks.get_task(task_id) else {
+            return;
+        };
+        match marker.state {
+            TaskState::Ready => {
+                if self.tasks.set_state(task_id, TaskState::Ready).is_ok() {
+                    self.event_publisher.publish_task_ready(&task);
+                }
+            }
+            TaskState::Failed => {
+                let error = marker.error.unwrap_or_default();
+                if self.tasks.set_failed(task_id, error.clone()).is_ok() {
+                    self.event_publisher.publish_task_failed(&task, &error);
+                }
+            }
+            TaskState::Cancelled => {
+                let _ = self.tasks.set_state(task_id, TaskState::Cancelled);
+            }
+            TaskState::Pending | TaskState::Inferring => {}
+        }
+        tracing::debug!(task_id = %task_id, state = ?marker.state, "Task finished by another instance");
+    }
+
+    /// 接管其他实例取出后超时未完成的条目（实例崩溃后）
+    async fn claim_stale(&mut self) {
+        if self.inbox.is_closed() {
+            return;
+        }
+        let min_idle = self.config.claim_idle.as_millis() as usize;
+        for priority in PRIORITIES {
+            let room = self.config.read_ahead.saturating_sub(self.inbox.len());
+            if room == 0 {
+                return;
+            }
+            let stream = self.config.stream_key(priority);
+            let reply: RedisResult<StreamAutoClaimReply> = self
+                .conn
+                .xautoclaim_options(
+                    &stream,
+                    &self.config.group,
+                    &self.consumer,
+                    min_idle,
+                    "0-0",
+                    StreamAutoClaimOptions::default().count(room),
+                )
+                .await;
+            match reply {
+                Ok(reply) => {
+                    for entry in reply.claimed {
+                        tracing::info!(stream = %stream, entry_id = %entry.id, "Claimed stale task entry");
+                        self.accept(&stream, entry).await;
+                    }
+                }
+                Err(e) => tracing::warn!(stream = %stream, error = %e, "Failed to claim stale task entries"),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::memory::InMemoryTaskManager;
+
+    fn task(session_id: &str, segment_index: u32) -> InferenceTask {
+        InferenceTask::new(
+            session_id.to_string(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            segment_index,
+            format!("Content {}", segment_index),
+        )
+    }
+
+    fn manager(capacity: usize) -> (RedisTaskManager, Arc<TaskQueue>, mpsc::UnboundedReceiver<Command>) {
+        let outbox = Arc::new(TaskQueue::new(capacity));
+        let tasks = Arc::new(InMemoryTaskManager::new(outbox.clone()));
+        let (manager, commands) = RedisTaskManager::new(tasks, outbox.clone(), capacity);
+        (manager, outbox, commands)
+    }
+
+    fn finished(commands: &mut mpsc::UnboundedReceiver<Command>) -> Vec<(String, FinishedMarker, Vec<EntryRef>)> {
+        std::iter::from_fn(|| commands.try_recv().ok())
+            .filter_map(|command| match command {
+                Command::Finish { task_id, marker, entries } => Some((task_id, marker, entries)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_queued_task_roundtrip() {
+        let task = task("session-1", 3).as_explicit().with_priority(TaskPriority::Current);
+        let payload = serde_json::to_string(&QueuedTask::from(&task)).unwrap();
+        let restored: InferenceTask = serde_json::from_str::<QueuedTask>(&payload).unwrap().into();
+
+        assert_eq!(restored.task_id, task.task_id);
+        assert_eq!(restored.session_id, "session-1");
+        assert_eq!((restored.segment_index, restored.explicit), (3, true));
+        assert_eq!(restored.priority, TaskPriority::Current);
+        assert_eq!(restored.state, TaskState::Pending);
+    }
+
+    #[tokio::test]
+    async fn test_capacity_counts_shared_queue() {
+        let (manager, _outbox, _commands) = manager(3);
+        manager.shared.depth.store(2, Ordering::Release);
+
+        let error = manager.submit(vec![task("s1", 0), task("s1", 1)]).unwrap_err();
+        assert!(matches!(error, TaskError::QueueFull { depth: 2, capacity: 3 }));
+        assert!(manager.get_tasks_by_session("s1").is_empty());
+
+        manager.submit(vec![task("s1", 0)]).unwrap();
+        assert_eq!(manager.queue_depth(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_writes_finished_markers() {
+        let (manager, outbox, mut commands) = manager(10);
+        let task_ids = manager.submit(vec![task("s1", 0), task("s1", 1)]).unwrap();
+        while outbox.try_pop().is_some() {}
+        let entry = EntryRef {
+            stream: "rovel:tasks:prefetch".to_string(),
+            id: "1-0".to_string(),
+        };
+        manager.shared.consumed.insert(task_ids[0].clone(), vec![entry.clone()]);
+
+        assert_eq!(manager.cancel_pending("s1"), 2);
+        let mut finished = finished(&mut commands);
+        finished.sort_by_key(|(task_id, _, _)| task_ids.iter().position(|id| id == task_id));
+        assert_eq!(finished.len(), 2);
+        assert_eq!(finished[0].1.state, TaskState::Cancelled);
+        // 本实例取出的条目随之确认
+        assert_eq!(finished[0].2, [entry]);
+        assert!(finished[1].2.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_foreign_task_lifecycle() {
+        let (manager, _outbox, mut commands) = manager(10);
+        let sessions = manager.sessions(Arc::new(crate::infrastructure::InMemorySessionManager::new()));
+        let foreign = task("remote-session", 5);
+        let task_id = foreign.task_id.clone();
+        manager.shared.foreign.insert(task_id.clone(), foreign);
+
+        // Worker 通过包装的任务管理器和会话管理器处理其他实例的任务
+        assert!(sessions.is_valid("remote-session"));
+        assert_eq!(manager.get_state(&task_id), Some(TaskState::Pending));
+        manager.set_state(&task_id, TaskState::Inferring).unwrap();
+        assert!(!manager.is_cancelled(&task_id));
+
+        manager.set_failed(&task_id, "TTS error: timeout".to_string()).unwrap();
+        assert!(manager.get_task(&task_id).is_none());
+        assert!(!sessions.is_valid("remote-session"));
+        let finished = finished(&mut commands);
+        assert_eq!(finished.len(), 1);
+        assert_eq!(
+            finished[0].1,
+            FinishedMarker {
+                state: TaskState::Failed,
+                error: Some("TTS error: timeout".to_string()),
+            }
+        );
+    }
+}
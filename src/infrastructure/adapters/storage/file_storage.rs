@@ -1,14 +1,17 @@
 //! File Storage - 文件系统音频存储实现
 //!
 //! 实现 AudioStoragePort trait
+//!
+//! 每个会话一个目录（目录名为会话 ID），根目录下的其他内容（如音频缓存）不计入统计、不参与清理
 
 use async_trait::async_trait;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 use tokio::fs;
 use uuid::Uuid;
 
 use crate::application::ports::{
-    AudioStorageError, AudioStoragePort, GcConfig, GcResult, StorageStats,
+    AudioStorageError, AudioStoragePort, GcConfig, GcResult, StorageStats, StoredAudioFile,
 };
 
 /// 文件系统音频存储
@@ -34,6 +37,47 @@ impl FileAudioStorage {
     pub fn base_dir(&self) -> &Path {
         &self.base_dir
     }
+
+    /// 所有会话目录（目录名可解析为会话 ID）
+    async fn session_dirs(&self) -> Result<Vec<PathBuf>, AudioStorageError> {
+        let mut dirs = Vec::new();
+        let mut entries = fs::read_dir(&self.base_dir)
+            .await
+            .map_err(|e| AudioStorageError::IoError(e.to_string()))?;
+
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| AudioStorageError::IoError(e.to_string()))?
+        {
+            let path = entry.path();
+            let is_session = entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| Uuid::parse_str(name).is_ok());
+            if is_session && path.is_dir() {
+                dirs.push(path);
+            }
+        }
+        Ok(dirs)
+    }
+
+    /// 会话目录下的音频文件（路径、大小、最后写入时间）
+    async fn session_files(dir: &Path) -> Vec<(PathBuf, u64, SystemTime)> {
+        let mut files = Vec::new();
+        if let Ok(mut entries) = fs::read_dir(dir).await {
+            while let Ok(Some(entry)) = entries.next_entry().await {
+                if entry.path().extension().is_none_or(|ext| ext != "wav") {
+                    continue;
+                }
+                if let Ok(metadata) = entry.metadata().await {
+                    let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                    files.push((entry.path(), metadata.len(), modified));
+                }
+            }
+        }
+        files
+    }
 }
 
 #[async_trait]
@@ -133,7 +177,7 @@ impl AudioStoragePort for FileAudioStorage {
             .await
             .map_err(|e| AudioStorageError::IoError(e.to_string()))?
         {
-            if entry.path().extension().map_or(false, |ext| ext == "wav") {
+            if entry.path().extension().is_some_and(|ext| ext == "wav") {
                 fs::remove_file(entry.path())
                     .await
                     .map_err(|e| AudioStorageError::IoError(e.to_string()))?;
@@ -157,65 +201,122 @@ impl AudioStoragePort for FileAudioStorage {
         self.get_audio_path(session_id, segment_index).exists()
     }
 
+    async fn list_audio(&self) -> Result<Vec<StoredAudioFile>, AudioStorageError> {
+        let mut files = Vec::new();
+        for dir in self.session_dirs().await? {
+            let Some(session_id) = dir
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| Uuid::parse_str(name).ok())
+            else {
+                continue;
+            };
+            for (path, size_bytes, _) in Self::session_files(&dir).await {
+                // 文件名: segment_{index}.wav
+                let segment_index = path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .and_then(|stem| stem.strip_prefix("segment_"))
+                    .and_then(|index| index.parse().ok());
+                if let Some(segment_index) = segment_index {
+                    files.push(StoredAudioFile {
+                        session_id,
+                        segment_index,
+                        size_bytes,
+                    });
+                }
+            }
+        }
+        Ok(files)
+    }
+
     async fn get_stats(&self) -> Result<StorageStats, AudioStorageError> {
         let mut stats = StorageStats::default();
 
-        let mut entries = fs::read_dir(&self.base_dir)
-            .await
-            .map_err(|e| AudioStorageError::IoError(e.to_string()))?;
+        for dir in self.session_dirs().await? {
+            stats.session_count += 1;
 
-        while let Some(entry) = entries
-            .next_entry()
-            .await
-            .map_err(|e| AudioStorageError::IoError(e.to_string()))?
-        {
-            let path = entry.path();
-            if path.is_dir() {
-                stats.session_count += 1;
-
-                // 统计该会话下的文件
-                if let Ok(mut session_entries) = fs::read_dir(&path).await {
-                    while let Ok(Some(file_entry)) = session_entries.next_entry().await {
-                        if file_entry
-                            .path()
-                            .extension()
-                            .map_or(false, |ext| ext == "wav")
-                        {
-                            stats.file_count += 1;
-                            if let Ok(metadata) = file_entry.metadata().await {
-                                stats.used_bytes += metadata.len();
-                            }
-                        }
-                    }
-                }
+            // 统计该会话下的文件
+            for (_, size, _) in Self::session_files(&dir).await {
+                stats.file_count += 1;
+                stats.used_bytes += size;
             }
         }
 
         Ok(stats)
     }
 
-    async fn gc(&self, _config: &GcConfig) -> Result<GcResult, AudioStorageError> {
-        // GC 逻辑需要配合 Repository 使用
-        // 这里只是基础实现，实际 GC 由 GcService 协调
-        Ok(GcResult::default())
+    /// 删除超过会话过期时间没有写入的会话目录，再按 LRU 清理到存储上限
+    ///
+    /// 用于清理数据库中已没有记录的会话目录；有记录的会话由 GcService 按记录清理
+    async fn gc(&self, config: &GcConfig) -> Result<GcResult, AudioStorageError> {
+        let mut result = GcResult::default();
+        let cutoff = SystemTime::now() - Duration::from_secs(config.session_expire_secs);
+
+        for dir in self.session_dirs().await? {
+            let files = Self::session_files(&dir).await;
+            if files.iter().any(|(_, _, modified)| *modified >= cutoff) {
+                continue;
+            }
+            fs::remove_dir_all(&dir)
+                .await
+                .map_err(|e| AudioStorageError::IoError(e.to_string()))?;
+            result.deleted_files += files.len() as u64;
+            result.freed_bytes += files.iter().map(|(_, size, _)| size).sum::<u64>();
+            result.cleaned_sessions += 1;
+        }
+
+        if config.max_storage_bytes > 0 {
+            let evicted = self.evict_to_size(config.max_storage_bytes).await?;
+            result.deleted_files += evicted.deleted_files;
+            result.freed_bytes += evicted.freed_bytes;
+            result.cleaned_sessions += evicted.cleaned_sessions;
+        }
+
+        Ok(result)
     }
 
+    /// 按最后写入时间从旧到新删除音频文件，直到占用不超过目标大小（清空的会话目录一并删除）
     async fn evict_to_size(&self, target_bytes: u64) -> Result<GcResult, AudioStorageError> {
-        let stats = self.get_stats().await?;
+        let mut result = GcResult::default();
+        let dirs = self.session_dirs().await?;
 
-        if stats.used_bytes <= target_bytes {
-            return Ok(GcResult::default());
+        let mut files = Vec::new();
+        for dir in &dirs {
+            files.extend(Self::session_files(dir).await);
+        }
+        let mut used_bytes: u64 = files.iter().map(|(_, size, _)| size).sum();
+        if used_bytes <= target_bytes {
+            return Ok(result);
         }
 
-        // LRU 清理需要配合 Repository 的 last_accessed_at 信息
-        // 这里只是基础框架
-        tracing::warn!(
-            "Storage exceeds limit: used={} bytes, target={} bytes",
-            stats.used_bytes,
-            target_bytes
+        tracing::info!(
+            used_bytes,
+            target_bytes,
+            "Storage exceeds limit, evicting oldest audio files"
         );
 
-        Ok(GcResult::default())
+        files.sort_by_key(|(_, _, modified)| *modified);
+        for (path, size, _) in files {
+            if used_bytes <= target_bytes {
+                break;
+            }
+            fs::remove_file(&path)
+                .await
+                .map_err(|e| AudioStorageError::IoError(e.to_string()))?;
+            used_bytes -= size;
+            result.deleted_files += 1;
+            result.freed_bytes += size;
+        }
+
+        // remove_dir 只删除空目录
+        for dir in dirs {
+            if fs::remove_dir(&dir).await.is_ok() {
+                result.cleaned_sessions += 1;
+            }
+        }
+
+        Ok(result)
     }
 }
 
@@ -279,4 +380,35 @@ mod tests {
             assert!(!storage.audio_exists(session_id, i).await);
         }
     }
+
+    #[tokio::test]
+    async fn test_evict_to_size() {
+        let temp_dir = tempdir().unwrap();
+        let storage = FileAudioStorage::new(temp_dir.path()).await.unwrap();
+
+        // 根目录下的非会话内容不参与统计和清理
+        std::fs::write(temp_dir.path().join("cache.sled"), b"not a session").unwrap();
+
+        let (old_session, new_session) = (Uuid::new_v4(), Uuid::new_v4());
+        storage.save_audio(old_session, 0, &[0; 10]).await.unwrap();
+        storage.save_audio(old_session, 1, &[0; 10]).await.unwrap();
+        let old = SystemTime::now() - Duration::from_secs(3600);
+        for index in 0..2 {
+            let file = std::fs::File::options()
+                .write(true)
+                .open(storage.get_audio_path(old_session, index))
+                .unwrap();
+            file.set_modified(old).unwrap();
+        }
+        storage.save_audio(new_session, 0, &[0; 10]).await.unwrap();
+
+        let stats = storage.get_stats().await.unwrap();
+        assert_eq!((stats.session_count, stats.file_count, stats.used_bytes), (2, 3, 30));
+
+        let result = storage.evict_to_size(15).await.unwrap();
+        assert_eq!((result.deleted_files, result.freed_bytes, result.cleaned_sessions), (2, 20, 1));
+        assert!(!storage.get_session_dir(old_session).exists());
+        assert!(storage.audio_exists(new_session, 0).await);
+        assert!(temp_dir.path().join("cache.sled").exists());
+    }
 }
@@ -2,6 +2,10 @@
 //!
 //! 音频转码适配器实现
 
+mod resampler;
+mod stream;
+mod time_stretch;
 mod wav_transcoder;
+mod webm;
 
 pub use wav_transcoder::WavTranscoder;
@@ -0,0 +1,141 @@
+//! Windowed-sinc 重采样
+//!
+//! 以 Blackman 窗截断的 sinc 核做带限插值；降采样时按目标奈奎斯特频率降低截止频率，
+//! 避免高频（齿音）混叠。核函数预先制表，推理时查表线性插值，不逐点计算三角函数。
+
+use std::f64::consts::PI;
+
+/// 每个过零点区间的制表精度
+const TABLE_RESOLUTION: usize = 512;
+
+/// 截止频率相对奈奎斯特频率的比例，为过渡带留出余量
+const ROLLOFF: f64 = 0.95;
+
+/// Windowed-sinc 重采样器
+pub(super) struct SincResampler {
+    /// 核的单侧过零点数
+    zero_crossings: usize,
+    /// 核函数表，覆盖 [0, zero_crossings]
+    table: Vec<f32>,
+}
+
+impl SincResampler {
+    pub(super) fn new(zero_crossings: usize) -> Self {
+        let len = zero_crossings * TABLE_RESOLUTION + 1;
+        let table = (0..len)
+            .map(|i| {
+                let x = i as f64 / TABLE_RESOLUTION as f64;
+                (sinc(x) * blackman(x / zero_crossings as f64)) as f32
+            })
+            .collect();
+        Self {
+            zero_crossings,
+            table,
+        }
+    }
+
+    /// 核函数值，x 以过零点为单位
+    fn kernel(&self, x: f64) -> f32 {
+        let pos = x.abs() * TABLE_RESOLUTION as f64;
+        let index = pos as usize;
+        if index + 1 >= self.table.len() {
+            return 0.0;
+        }
+        let frac = (pos - index as f64) as f32;
+        self.table[index] + (self.table[index + 1] - self.table[index]) * frac
+    }
+
+    /// 重采样交错排列的多声道样本
+    pub(super) fn process(
+        &self,
+        samples: &[f32],
+        from_rate: u32,
+        to_rate: u32,
+        channels: u8,
+    ) -> Vec<f32> {
+        if from_rate == to_rate || samples.is_empty() {
+            return samples.to_vec();
+        }
+
+        let channel_count = channels.max(1) as usize;
+        let frame_count = samples.len() / channel_count;
+        let ratio = to_rate as f64 / from_rate as f64;
+        // 截止频率（相对输入奈奎斯特频率）：降采样时取目标奈奎斯特频率
+        let cutoff = ratio.min(1.0) * ROLLOFF;
+        // 核在输入样本上的单侧宽度
+        let half_width = self.zero_crossings as f64 / cutoff;
+
+        let new_frame_count = (frame_count as f64 * ratio) as usize;
+        let mut output = Vec::with_capacity(new_frame_count * channel_count);
+        let mut acc = vec![0f32; channel_count];
+
+        for i in 0..new_frame_count {
+            let center = i as f64 / ratio;
+            let first = (center - half_width).ceil().max(0.0) as usize;
+            let last = ((center + half_width).floor() as usize).min(frame_count - 1);
+
+            acc.iter_mut().for_each(|a| *a = 0.0);
+            for j in first..=last {
+                let weight = self.kernel((center - j as f64) * cutoff);
+                let frame = &samples[j * channel_count..(j + 1) * channel_count];
+                for (a, &s) in acc.iter_mut().zip(frame) {
+                    *a += s * weight;
+                }
+            }
+            output.extend(acc.iter().map(|a| a * cutoff as f32));
+        }
+
+        output
+    }
+}
+
+fn sinc(x: f64) -> f64 {
+    if x == 0.0 {
+        1.0
+    } else {
+        (PI * x).sin() / (PI * x)
+    }
+}
+
+/// Blackman 窗，x ∈ [-1, 1]
+fn blackman(x: f64) -> f64 {
+    if x.abs() >= 1.0 {
+        return 0.0;
+    }
+    0.42 + 0.5 * (PI * x).cos() + 0.08 * (2.0 * PI * x).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(freq: f32, rate: u32, secs: f32) -> Vec<f32> {
+        (0..(rate as f32 * secs) as usize)
+            .map(|i| (i as f32 / rate as f32 * freq * std::f32::consts::TAU).sin() * 0.5)
+            .collect()
+    }
+
+    fn rms(samples: &[f32]) -> f32 {
+        // 忽略首尾过渡
+        let body = &samples[samples.len() / 10..samples.len() * 9 / 10];
+        (body.iter().map(|s| s * s).sum::<f32>() / body.len() as f32).sqrt()
+    }
+
+    #[test]
+    fn test_passband_kept_and_aliasing_suppressed() {
+        let resampler = SincResampler::new(16);
+
+        // 1kHz 在 16kHz 的通带内，幅度应保持（0.5 / √2）
+        let passband = resampler.process(&sine(1000.0, 48000, 0.5), 48000, 16000, 1);
+        assert_eq!(passband.len(), 8000);
+        assert!((rms(&passband) - 0.354).abs() < 0.02);
+
+        // 12kHz 高于 16kHz 的奈奎斯特频率，应被滤除而不是混叠到 4kHz
+        let aliased = resampler.process(&sine(12000.0, 48000, 0.5), 48000, 16000, 1);
+        assert!(rms(&aliased) < 0.01);
+
+        // 升采样保持幅度
+        let upsampled = resampler.process(&sine(1000.0, 16000, 0.5), 16000, 48000, 1);
+        assert!((rms(&upsampled) - 0.354).abs() < 0.02);
+    }
+}
@@ -0,0 +1,371 @@
+//! 流式编码
+//!
+//! 编码器按块接收交错排列的 f32 PCM，立即返回已编码的字节，不保留完整的样本和输出：
+//! - WAV：先输出长度待定的头部，结束时给出最终头部，可回写的输出（文件、内存）再覆盖
+//! - Opus：按 20ms 帧编码写入 Ogg 包，只缓存不足一帧的样本和未满的页
+//! - WebM：同样的 Opus 帧写入 Matroska 簇（见 webm.rs）
+
+use ogg::{PacketWriteEndInfo, PacketWriter};
+use opus::{Application, Channels, Encoder};
+
+use crate::application::ports::TranscodeError;
+
+/// WAV 头部长度
+const WAV_HEADER_LEN: usize = 44;
+
+/// 流式 PCM 编码器
+pub(super) trait PcmEncoder: Send {
+    /// 编码一块样本，返回新产生的输出
+    fn push(&mut self, samples: &[f32]) -> Result<Vec<u8>, TranscodeError>;
+
+    /// 结束编码，返回剩余的输出
+    fn finish(&mut self) -> Result<Vec<u8>, TranscodeError>;
+
+    /// 结束后需要回写到输出开头的头部（None 表示无需回写）
+    fn final_header(&self) -> Option<Vec<u8>>;
+}
+
+/// 一次性编码全部样本（回写头部）
+pub(super) fn encode_all(
+    encoder: &mut dyn PcmEncoder,
+    samples: &[f32],
+) -> Result<Vec<u8>, TranscodeError> {
+    let mut output = encoder.push(samples)?;
+    output.extend(encoder.finish()?);
+    if let Some(header) = encoder.final_header() {
+        output[..header.len()].copy_from_slice(&header);
+    }
+    Ok(output)
+}
+
+#[inline]
+pub(super) fn to_i16(sample: f32) -> i16 {
+    (sample.clamp(-1.0, 1.0) * 32767.0) as i16
+}
+
+/// 16 位 PCM WAV 头部
+fn wav_header(sample_rate: u32, channels: u8, data_size: u32) -> Vec<u8> {
+    let bits_per_sample: u16 = 16;
+    let num_channels = channels as u16;
+    let byte_rate = sample_rate * num_channels as u32 * (bits_per_sample / 8) as u32;
+    let block_align = num_channels * (bits_per_sample / 8);
+
+    let mut header = Vec::with_capacity(WAV_HEADER_LEN);
+
+    // RIFF header
+    header.extend_from_slice(b"RIFF");
+    header.extend_from_slice(&data_size.saturating_add(36).to_le_bytes());
+    header.extend_from_slice(b"WAVE");
+
+    // fmt chunk
+    header.extend_from_slice(b"fmt ");
+    header.extend_from_slice(&16u32.to_le_bytes()); // chunk size
+    header.extend_from_slice(&1u16.to_le_bytes()); // PCM format
+    header.extend_from_slice(&num_channels.to_le_bytes());
+    header.extend_from_slice(&sample_rate.to_le_bytes());
+    header.extend_from_slice(&byte_rate.to_le_bytes());
+    header.extend_from_slice(&block_align.to_le_bytes());
+    header.extend_from_slice(&bits_per_sample.to_le_bytes());
+
+    // data chunk
+    header.extend_from_slice(b"data");
+    header.extend_from_slice(&data_size.to_le_bytes());
+
+    header
+}
+
+/// 流式 WAV 编码器（16 位 PCM）
+///
+/// 头部先以最大长度占位（流式播放时读到结尾为止），结束后由 final_header 给出实际长度
+pub(super) struct WavStreamEncoder {
+    sample_rate: u32,
+    channels: u8,
+    data_size: u64,
+    header_written: bool,
+}
+
+impl WavStreamEncoder {
+    pub(super) fn new(sample_rate: u32, channels: u8) -> Self {
+        Self {
+            sample_rate,
+            channels,
+            data_size: 0,
+            header_written: false,
+        }
+    }
+
+    fn take_header(&mut self) -> Vec<u8> {
+        if std::mem::replace(&mut self.header_written, true) {
+            return Vec::new();
+        }
+        wav_header(self.sample_rate, self.channels, u32::MAX - 36)
+    }
+}
+
+impl PcmEncoder for WavStreamEncoder {
+    fn push(&mut self, samples: &[f32]) -> Result<Vec<u8>, TranscodeError> {
+        let mut output = self.take_header();
+        output.reserve(samples.len() * 2);
+        for &sample in samples {
+            output.extend_from_slice(&to_i16(sample).to_le_bytes());
+        }
+        self.data_size += samples.len() as u64 * 2;
+        Ok(output)
+    }
+
+    fn finish(&mut self) -> Result<Vec<u8>, TranscodeError> {
+        Ok(self.take_header())
+    }
+
+    fn final_header(&self) -> Option<Vec<u8>> {
+        let data_size = u32::try_from(self.data_size).unwrap_or(u32::MAX - 36);
+        Some(wav_header(self.sample_rate, self.channels, data_size))
+    }
+}
+
+/// Opus 帧长（毫秒）
+pub(super) const OPUS_FRAME_MS: usize = 20;
+
+/// Opus 最大包大小
+const OPUS_MAX_PACKET: usize = 4000;
+
+/// Opus 帧编码（与容器无关）
+///
+/// 按 20ms 帧编码，只缓存不足一帧的样本；结束时补齐最后一帧并追加静音帧刷新编码器延迟
+pub(super) struct OpusFrames {
+    encoder: Encoder,
+    sample_rate: u32,
+    channels: u8,
+    /// 编码器延迟（输入采样率下的样本数）
+    pre_skip: u16,
+    /// 不足一帧的样本
+    pending: Vec<i16>,
+    samples_per_frame: usize,
+    /// 刷新编码器延迟所需的静音帧数
+    flush_frames: usize,
+    packet: Vec<u8>,
+}
+
+impl OpusFrames {
+    /// `sample_rate` 必须为 Opus 支持的采样率（8/12/16/24/48 kHz），`channels` 为 1 或 2
+    pub(super) fn new(sample_rate: u32, channels: u8, bitrate: u32) -> Result<Self, TranscodeError> {
+        let opus_channels = match channels {
+            1 => Channels::Mono,
+            2 => Channels::Stereo,
+            n => {
+                return Err(TranscodeError::UnsupportedFormat(format!(
+                    "Opus supports mono or stereo, got {} channels",
+                    n
+                )))
+            }
+        };
+
+        // 创建 Opus 编码器 (Application::Voip 优化语音)
+        let mut encoder = Encoder::new(sample_rate, opus_channels, Application::Voip)
+            .map_err(|e| TranscodeError::EncodingError(format!("Failed to create Opus encoder: {}", e)))?;
+        encoder
+            .set_bitrate(opus::Bitrate::Bits(bitrate as i32))
+            .map_err(|e| TranscodeError::EncodingError(format!("Failed to set bitrate: {}", e)))?;
+
+        // 编码器延迟 (lookahead)，通常为 ~312 samples @ 48kHz
+        let pre_skip = encoder.get_lookahead().map(|l| l as u16).unwrap_or(312);
+        let samples_per_frame = sample_rate as usize * OPUS_FRAME_MS / 1000 * channels as usize;
+
+        Ok(Self {
+            encoder,
+            sample_rate,
+            channels,
+            pre_skip,
+            pending: Vec::with_capacity(samples_per_frame),
+            samples_per_frame,
+            flush_frames: (pre_skip as usize).div_ceil(samples_per_frame).max(1),
+            packet: vec![0u8; OPUS_MAX_PACKET],
+        })
+    }
+
+    /// Opus Head 包（Ogg 的首个包，WebM 的 CodecPrivate）
+    pub(super) fn head(&self) -> Vec<u8> {
+        opus_head(self.channels, self.sample_rate, self.pre_skip)
+    }
+
+    /// 编码器延迟（纳秒）
+    pub(super) fn pre_skip_ns(&self) -> u64 {
+        self.pre_skip as u64 * 1_000_000_000 / self.sample_rate as u64
+    }
+
+    /// 编码器延迟（48kHz 下的样本数）
+    fn pre_skip_48k(&self) -> u64 {
+        self.pre_skip as u64 * 48000 / self.sample_rate as u64
+    }
+
+    /// 每帧对应的 48kHz 样本数
+    fn frame_granule(&self) -> u64 {
+        (48000 * OPUS_FRAME_MS / 1000) as u64
+    }
+
+    fn encode_frame(&mut self, frame: &[i16]) -> Result<Vec<u8>, TranscodeError> {
+        let encoded_len = self
+            .encoder
+            .encode(frame, &mut self.packet)
+            .map_err(|e| TranscodeError::EncodingError(format!("Opus encode failed: {}", e)))?;
+        Ok(self.packet[..encoded_len].to_vec())
+    }
+
+    /// 编码一块样本，返回新完成的包
+    pub(super) fn push(&mut self, samples: &[f32]) -> Result<Vec<Vec<u8>>, TranscodeError> {
+        let mut packets = Vec::new();
+        let mut pending = std::mem::take(&mut self.pending);
+        for &sample in samples {
+            pending.push(to_i16(sample));
+            if pending.len() == self.samples_per_frame {
+                packets.push(self.encode_frame(&pending)?);
+                pending.clear();
+            }
+        }
+        self.pending = pending;
+        Ok(packets)
+    }
+
+    /// 结束编码，返回剩余的包（最后一帧用零填充，之后为刷新延迟的静音帧）
+    pub(super) fn finish(&mut self) -> Result<Vec<Vec<u8>>, TranscodeError> {
+        let mut packets = Vec::new();
+        if !self.pending.is_empty() {
+            let mut frame = std::mem::take(&mut self.pending);
+            frame.resize(self.samples_per_frame, 0);
+            packets.push(self.encode_frame(&frame)?);
+        }
+
+        let silence = vec![0i16; self.samples_per_frame];
+        for _ in 0..self.flush_frames {
+            packets.push(self.encode_frame(&silence)?);
+        }
+        Ok(packets)
+    }
+}
+
+/// 流式 Opus 编码器（Ogg 容器，RFC 7845）
+pub(super) struct OpusStreamEncoder {
+    frames: OpusFrames,
+    writer: PacketWriter<'static, Vec<u8>>,
+    granule_pos: u64,
+}
+
+impl OpusStreamEncoder {
+    /// 创建编码器并写入 OpusHead / OpusTags
+    ///
+    /// `sample_rate` 必须为 Opus 支持的采样率（8/12/16/24/48 kHz），`channels` 为 1 或 2
+    pub(super) fn new(
+        sample_rate: u32,
+        channels: u8,
+        bitrate: u32,
+        tags: Vec<u8>,
+    ) -> Result<Self, TranscodeError> {
+        let frames = OpusFrames::new(sample_rate, channels, bitrate)?;
+
+        let mut writer = PacketWriter::new(Vec::new());
+        writer
+            .write_packet(frames.head(), 0, PacketWriteEndInfo::EndPage, 0)
+            .map_err(|e| TranscodeError::EncodingError(format!("Failed to write Opus head: {}", e)))?;
+        writer
+            .write_packet(tags, 0, PacketWriteEndInfo::EndPage, 0)
+            .map_err(|e| TranscodeError::EncodingError(format!("Failed to write Opus tags: {}", e)))?;
+
+        // RFC 7845: granule position 与 pre-skip 均为 48kHz 下的样本数
+        let granule_pos = frames.pre_skip_48k();
+        Ok(Self {
+            frames,
+            writer,
+            granule_pos,
+        })
+    }
+
+    fn write_packets(&mut self, packets: Vec<Vec<u8>>, last: bool) -> Result<Vec<u8>, TranscodeError> {
+        let count = packets.len();
+        for (i, packet) in packets.into_iter().enumerate() {
+            let end_info = if last && i + 1 == count {
+                PacketWriteEndInfo::EndStream
+            } else {
+                PacketWriteEndInfo::NormalPacket
+            };
+            self.granule_pos += self.frames.frame_granule();
+            self.writer
+                .write_packet(packet, 0, end_info, self.granule_pos)
+                .map_err(|e| TranscodeError::EncodingError(format!("Failed to write Opus packet: {}", e)))?;
+        }
+        // 取出已写完的 Ogg 页
+        Ok(std::mem::take(self.writer.inner_mut()))
+    }
+}
+
+impl PcmEncoder for OpusStreamEncoder {
+    fn push(&mut self, samples: &[f32]) -> Result<Vec<u8>, TranscodeError> {
+        let packets = self.frames.push(samples)?;
+        self.write_packets(packets, false)
+    }
+
+    fn finish(&mut self) -> Result<Vec<u8>, TranscodeError> {
+        let packets = self.frames.finish()?;
+        self.write_packets(packets, true)
+    }
+
+    fn final_header(&self) -> Option<Vec<u8>> {
+        None
+    }
+}
+
+/// 创建 Opus Head 包 (RFC 7845)
+fn opus_head(channels: u8, sample_rate: u32, pre_skip: u16) -> Vec<u8> {
+    let mut head = Vec::with_capacity(19);
+    head.extend_from_slice(b"OpusHead"); // Magic signature
+    head.push(1); // Version
+    head.push(channels); // Channel count
+    head.extend_from_slice(&pre_skip.to_le_bytes()); // Pre-skip (encoder delay)
+    head.extend_from_slice(&sample_rate.to_le_bytes()); // Input sample rate
+    head.extend_from_slice(&0i16.to_le_bytes()); // Output gain
+    head.push(0); // Channel mapping family
+    head
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(rate: u32, secs: f32) -> Vec<f32> {
+        (0..(rate as f32 * secs) as usize)
+            .map(|i| (i as f32 / rate as f32 * 440.0 * std::f32::consts::TAU).sin() * 0.5)
+            .collect()
+    }
+
+    #[test]
+    fn test_streamed_output_matches_single_push() {
+        let samples = sine(16000, 1.0);
+
+        // WAV：分块输出 + 回写头部与一次性编码一致
+        let mut encoder = WavStreamEncoder::new(16000, 1);
+        let whole = encode_all(&mut encoder, &samples).unwrap();
+        assert_eq!(whole.len(), WAV_HEADER_LEN + samples.len() * 2);
+        assert_eq!(&whole[40..44], &(samples.len() as u32 * 2).to_le_bytes());
+
+        let mut encoder = WavStreamEncoder::new(16000, 1);
+        let mut streamed = Vec::new();
+        for chunk in samples.chunks(1234) {
+            streamed.extend(encoder.push(chunk).unwrap());
+        }
+        streamed.extend(encoder.finish().unwrap());
+        assert_eq!(&streamed[40..44], &(u32::MAX - 36).to_le_bytes());
+        streamed[..WAV_HEADER_LEN].copy_from_slice(&encoder.final_header().unwrap());
+        assert_eq!(streamed, whole);
+
+        // Opus：分块与一次性编码输出相同
+        let mut encoder = OpusStreamEncoder::new(16000, 1, 32000, b"OpusTags".to_vec()).unwrap();
+        let whole = encode_all(&mut encoder, &samples).unwrap();
+        let mut encoder = OpusStreamEncoder::new(16000, 1, 32000, b"OpusTags".to_vec()).unwrap();
+        let mut streamed = Vec::new();
+        for chunk in samples.chunks(777) {
+            streamed.extend(encoder.push(chunk).unwrap());
+        }
+        streamed.extend(encoder.finish().unwrap());
+        assert_eq!(&streamed[0..4], b"OggS");
+        assert_eq!(streamed, whole);
+    }
+}
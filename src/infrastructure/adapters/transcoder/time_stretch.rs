@@ -0,0 +1,119 @@
+//! WSOLA 时间伸缩
+//!
+//! 波形相似叠加（Waveform Similarity Overlap-Add）：按目标速度在输入中等距取帧，
+//! 在容差范围内搜索与上一帧自然延续最相似的位置，再以 Hann 窗叠加输出。
+//! 改变时长而不改变音高，适合语音的 1.25× / 1.5× 播放。
+
+use std::f32::consts::TAU;
+
+/// 帧长（毫秒），相邻帧重叠一半
+const FRAME_MS: u32 = 30;
+
+/// 相似位置搜索容差（毫秒）
+const SEEK_MS: u32 = 8;
+
+/// 按速度伸缩交错排列的多声道样本（speed > 1 加快，< 1 放慢）
+pub(super) fn stretch(samples: &[f32], sample_rate: u32, channels: u8, speed: f32) -> Vec<f32> {
+    let channel_count = channels.max(1) as usize;
+    let frame_count = samples.len() / channel_count;
+    let window = (sample_rate * FRAME_MS / 1000) as usize;
+    if (speed - 1.0).abs() < f32::EPSILON || speed <= 0.0 || frame_count < window * 2 {
+        return samples.to_vec();
+    }
+
+    let hop = window / 2;
+    let overlap = window - hop;
+    let seek = (sample_rate * SEEK_MS / 1000) as usize;
+    let analysis_hop = hop as f64 * speed as f64;
+    let output_frames = (frame_count as f64 / speed as f64) as usize;
+
+    // 相似度按下混后的单声道计算；末尾补零，保证搜索和取帧不越界
+    let padded = frame_count + window + seek;
+    let mut guide: Vec<f32> = samples
+        .chunks_exact(channel_count)
+        .map(|frame| frame.iter().sum::<f32>() / channel_count as f32)
+        .collect();
+    guide.resize(padded, 0.0);
+    let mut input = samples[..frame_count * channel_count].to_vec();
+    input.resize(padded * channel_count, 0.0);
+
+    let hann: Vec<f32> = (0..window)
+        .map(|n| 0.5 - 0.5 * (TAU * n as f32 / window as f32).cos())
+        .collect();
+    let mut output = vec![0f32; (output_frames + window) * channel_count];
+    let mut weights = vec![0f32; output_frames + window];
+
+    let mut previous: Option<usize> = None;
+    let mut k = 0;
+    while k * hop < output_frames {
+        let nominal = (k as f64 * analysis_hop) as usize;
+        let position = match previous {
+            Some(previous) => best_position(&guide, previous + hop, nominal, seek, overlap),
+            None => nominal,
+        };
+
+        let out_start = k * hop;
+        for (n, &w) in hann.iter().enumerate() {
+            weights[out_start + n] += w;
+            let src = (position + n) * channel_count;
+            let dst = (out_start + n) * channel_count;
+            for ch in 0..channel_count {
+                output[dst + ch] += input[src + ch] * w;
+            }
+        }
+
+        previous = Some(position);
+        k += 1;
+    }
+
+    output.truncate(output_frames * channel_count);
+    for (frame, &weight) in output.chunks_exact_mut(channel_count).zip(&weights) {
+        if weight > 1e-6 {
+            frame.iter_mut().for_each(|s| *s /= weight);
+        }
+    }
+    output
+}
+
+/// 在 nominal ± seek 内寻找与自然延续（natural 起的 len 个样本）归一化互相关最大的位置
+fn best_position(guide: &[f32], natural: usize, nominal: usize, seek: usize, len: usize) -> usize {
+    let template = &guide[natural..natural + len];
+    let mut best = (nominal, f32::MIN);
+    for candidate in nominal.saturating_sub(seek)..=nominal + seek {
+        let (corr, energy) = template
+            .iter()
+            .zip(&guide[candidate..candidate + len])
+            .fold((0.0f32, 0.0f32), |(corr, energy), (&a, &b)| (corr + a * b, energy + b * b));
+        let score = corr / (energy.sqrt() + 1e-9);
+        if score > best.1 {
+            best = (candidate, score);
+        }
+    }
+    best.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stretch_keeps_pitch() {
+        let rate = 16000;
+        let sine: Vec<f32> = (0..rate)
+            .map(|i| (i as f32 / rate as f32 * 200.0 * TAU).sin() * 0.5)
+            .collect();
+
+        // 1 秒 200Hz 正弦以 1.5× 播放：时长约 2/3 秒，频率不变
+        let output = stretch(&sine, rate, 1, 1.5);
+        assert_eq!(output.len(), 10666);
+        let body = &output[1000..9000];
+        let crossings = body.windows(2).filter(|w| w[0] < 0.0 && w[1] >= 0.0).count();
+        let freq = crossings as f32 * rate as f32 / body.len() as f32;
+        assert!((freq - 200.0).abs() < 10.0, "frequency {}", freq);
+        let rms = (body.iter().map(|s| s * s).sum::<f32>() / body.len() as f32).sqrt();
+        assert!((rms - 0.354).abs() < 0.03, "rms {}", rms);
+
+        // 原速直接返回
+        assert_eq!(stretch(&sine, rate, 1, 1.0), sine);
+    }
+}
@@ -3,12 +3,16 @@
 //! 支持：
 //! - WAV 解析和信息提取
 //! - WAV pass-through（不转码）
-//! - WAV → Opus (OGG 容器) 编码
+//! - 按目标采样率/声道数重采样（windowed-sinc，质量可配置）、下混
+//! - WAV → Opus (OGG / WebM 容器) 编码
+//! - 参考音频归一化（裁剪静音、下混、重采样、响度归一化）
+//! - 多段音频拼接（裁剪静音、交叉淡化，可逐段流式输出），Opus/WebM 输出可写入标题/章节标签
 
 use async_trait::async_trait;
-use ogg::writing::PacketWriter;
-use opus::{Application, Channels, Encoder};
+use std::f32::consts::FRAC_PI_2;
 use std::io::Cursor;
+use std::ops::Range;
+use std::sync::Arc;
 use symphonia::core::audio::SampleBuffer;
 use symphonia::core::codecs::DecoderOptions;
 use symphonia::core::formats::FormatOptions;
@@ -16,22 +20,54 @@ use symphonia::core::io::MediaSourceStream;
 use symphonia::core::meta::MetadataOptions;
 use symphonia::core::probe::Hint;
 
+use super::resampler::SincResampler;
+use super::stream::{encode_all, OpusStreamEncoder, PcmEncoder, WavStreamEncoder};
+use super::time_stretch;
+use super::webm::WebmStreamEncoder;
 use crate::application::ports::{
-    AudioFormat, AudioInfo, AudioTranscoderPort, TranscodeConfig, TranscodeError, TranscodeResult,
+    AudioFormat, AudioInfo, AudioMetadata, AudioStreamEncoder, AudioTranscoderPort, ConcatConfig,
+    ReferenceNormalizeConfig, ResampleQuality, StreamEnd, TranscodeConfig, TranscodeError,
+    TranscodeResult,
 };
 
+/// 静音检测窗口长度（毫秒）
+const SILENCE_WINDOW_MS: u32 = 10;
+
+/// 裁剪静音后首尾保留的余量（毫秒）
+const TRIM_PADDING_MS: u32 = 100;
+
+/// 响度归一化后的峰值上限（约 -0.2 dBFS）
+const PEAK_CEILING: f32 = 0.977;
+
 /// WAV 转码器
 ///
 /// 基于 symphonia 实现的音频转码器
 /// 当前主要用于 WAV 解析，后续可扩展支持更多格式
+#[derive(Clone)]
 pub struct WavTranscoder {
     /// 是否启用转码（如果为 false，总是返回原始 WAV）
     enabled: bool,
+    /// sinc 重采样器（None 表示线性插值）
+    resampler: Option<Arc<SincResampler>>,
 }
 
 impl WavTranscoder {
     pub fn new(enabled: bool) -> Self {
-        Self { enabled }
+        Self {
+            enabled,
+            resampler: None,
+        }
+        .with_resample_quality(ResampleQuality::default())
+    }
+
+    /// 设置重采样质量
+    pub fn with_resample_quality(mut self, quality: ResampleQuality) -> Self {
+        self.resampler = match quality {
+            ResampleQuality::Linear => None,
+            ResampleQuality::Medium => Some(Arc::new(SincResampler::new(16))),
+            ResampleQuality::High => Some(Arc::new(SincResampler::new(32))),
+        };
+        self
     }
 
     /// 解析 WAV 文件头
@@ -105,7 +141,7 @@ impl WavTranscoder {
 
             pos += 8 + chunk_size;
             // 对齐到偶数字节
-            if chunk_size % 2 != 0 {
+            if !chunk_size.is_multiple_of(2) {
                 pos += 1;
             }
         }
@@ -129,11 +165,15 @@ impl WavTranscoder {
 
     /// 使用 symphonia 解码 WAV 获取 PCM 数据
     fn decode_wav_to_pcm(&self, data: &[u8]) -> Result<DecodedAudio, TranscodeError> {
-        let cursor = Cursor::new(data.to_vec());
-        let mss = MediaSourceStream::new(Box::new(cursor), Default::default());
-
         let mut hint = Hint::new();
         hint.with_extension("wav");
+        self.decode_to_pcm(data, hint)
+    }
+
+    /// 使用 symphonia 解码任意已启用格式（WAV/FLAC/OGG/MP3）获取 PCM 数据
+    fn decode_to_pcm(&self, data: &[u8], hint: Hint) -> Result<DecodedAudio, TranscodeError> {
+        let cursor = Cursor::new(data.to_vec());
+        let mss = MediaSourceStream::new(Box::new(cursor), Default::default());
 
         let format_opts = FormatOptions::default();
         let metadata_opts = MetadataOptions::default();
@@ -220,52 +260,7 @@ impl WavTranscoder {
 
     /// 将 PCM f32 样本编码为 WAV
     fn encode_wav(&self, pcm: &DecodedAudio) -> Result<Vec<u8>, TranscodeError> {
-        let bits_per_sample: u16 = 16;
-        let num_channels = pcm.channels as u16;
-        let sample_rate = pcm.sample_rate;
-        let byte_rate = sample_rate * num_channels as u32 * (bits_per_sample / 8) as u32;
-        let block_align = num_channels * (bits_per_sample / 8);
-
-        // 转换 f32 样本到 i16
-        let pcm_data: Vec<i16> = pcm
-            .samples
-            .iter()
-            .map(|&s| {
-                let clamped = s.clamp(-1.0, 1.0);
-                (clamped * 32767.0) as i16
-            })
-            .collect();
-
-        let data_size = pcm_data.len() * 2;
-        let file_size = 36 + data_size;
-
-        let mut wav = Vec::with_capacity(44 + data_size);
-
-        // RIFF header
-        wav.extend_from_slice(b"RIFF");
-        wav.extend_from_slice(&(file_size as u32).to_le_bytes());
-        wav.extend_from_slice(b"WAVE");
-
-        // fmt chunk
-        wav.extend_from_slice(b"fmt ");
-        wav.extend_from_slice(&16u32.to_le_bytes()); // chunk size
-        wav.extend_from_slice(&1u16.to_le_bytes()); // PCM format
-        wav.extend_from_slice(&num_channels.to_le_bytes());
-        wav.extend_from_slice(&sample_rate.to_le_bytes());
-        wav.extend_from_slice(&byte_rate.to_le_bytes());
-        wav.extend_from_slice(&block_align.to_le_bytes());
-        wav.extend_from_slice(&bits_per_sample.to_le_bytes());
-
-        // data chunk
-        wav.extend_from_slice(b"data");
-        wav.extend_from_slice(&(data_size as u32).to_le_bytes());
-
-        // PCM data
-        for sample in pcm_data {
-            wav.extend_from_slice(&sample.to_le_bytes());
-        }
-
-        Ok(wav)
+        encode_all(&mut WavStreamEncoder::new(pcm.sample_rate, pcm.channels), &pcm.samples)
     }
 
     /// 将 PCM f32 样本编码为 Opus (OGG 容器)
@@ -273,146 +268,40 @@ impl WavTranscoder {
         &self,
         pcm: &DecodedAudio,
         bitrate: u32,
+        metadata: &AudioMetadata,
     ) -> Result<Vec<u8>, TranscodeError> {
         // Opus 支持的采样率: 8000, 12000, 16000, 24000, 48000
         // 为了兼容性，如果不在列表中需要重采样
-        let target_sample_rate = self.get_opus_compatible_sample_rate(pcm.sample_rate);
-        
-        // 重采样（如果需要）
-        let (samples, sample_rate) = if target_sample_rate != pcm.sample_rate {
-            let resampled = self.resample(&pcm.samples, pcm.sample_rate, target_sample_rate, pcm.channels)?;
-            (resampled, target_sample_rate)
+        let sample_rate = self.get_opus_compatible_sample_rate(pcm.sample_rate);
+        let mut encoder = OpusStreamEncoder::new(
+            sample_rate,
+            pcm.channels,
+            bitrate,
+            self.create_opus_tags(metadata),
+        )?;
+        if sample_rate != pcm.sample_rate {
+            let resampled = self.resample(&pcm.samples, pcm.sample_rate, sample_rate, pcm.channels)?;
+            encode_all(&mut encoder, &resampled)
         } else {
-            (pcm.samples.clone(), pcm.sample_rate)
-        };
+            encode_all(&mut encoder, &pcm.samples)
+        }
+    }
 
-        // Opus 仅支持单声道或立体声
-        let channels = if pcm.channels == 1 {
-            Channels::Mono
+    /// 将 PCM f32 样本编码为 Opus (WebM 容器)
+    fn encode_webm(
+        &self,
+        pcm: &DecodedAudio,
+        bitrate: u32,
+        metadata: &AudioMetadata,
+    ) -> Result<Vec<u8>, TranscodeError> {
+        let sample_rate = self.get_opus_compatible_sample_rate(pcm.sample_rate);
+        let mut encoder = WebmStreamEncoder::new(sample_rate, pcm.channels, bitrate, metadata.clone())?;
+        if sample_rate != pcm.sample_rate {
+            let resampled = self.resample(&pcm.samples, pcm.sample_rate, sample_rate, pcm.channels)?;
+            encode_all(&mut encoder, &resampled)
         } else {
-            Channels::Stereo
-        };
-        let channel_count = if pcm.channels == 1 { 1 } else { 2 };
-
-        // 创建 Opus 编码器 (Application::Voip 优化语音)
-        let mut encoder = Encoder::new(sample_rate, channels, Application::Voip)
-            .map_err(|e| TranscodeError::EncodingError(format!("Failed to create Opus encoder: {}", e)))?;
-
-        // 设置比特率
-        encoder
-            .set_bitrate(opus::Bitrate::Bits(bitrate as i32))
-            .map_err(|e| TranscodeError::EncodingError(format!("Failed to set bitrate: {}", e)))?;
-
-        // 获取编码器延迟 (lookahead) 作为 pre-skip
-        // Opus 编码器通常有 ~312 samples @ 48kHz 的延迟
-        let pre_skip = encoder.get_lookahead()
-            .map(|l| l as u16)
-            .unwrap_or(312); // 默认值
-
-        // 转换 f32 到 i16
-        let pcm_i16: Vec<i16> = samples
-            .iter()
-            .map(|&s| {
-                let clamped = s.clamp(-1.0, 1.0);
-                (clamped * 32767.0) as i16
-            })
-            .collect();
-
-        // Opus frame size: 支持 2.5, 5, 10, 20, 40, 60 ms
-        // 使用 20ms frame (sample_rate * 0.02)
-        let frame_size = (sample_rate as usize * 20) / 1000;
-        let samples_per_frame = frame_size * channel_count;
-
-        // 创建 OGG writer
-        let mut ogg_data = Vec::new();
-        {
-            let mut packet_writer = PacketWriter::new(&mut ogg_data);
-            
-            // 写入 Opus Head 包 (RFC 7845)
-            let opus_head = self.create_opus_head(channel_count as u8, sample_rate, pre_skip);
-            packet_writer
-                .write_packet(opus_head, 0, ogg::PacketWriteEndInfo::EndPage, 0)
-                .map_err(|e| TranscodeError::EncodingError(format!("Failed to write Opus head: {}", e)))?;
-
-            // 写入 Opus Tags 包
-            let opus_tags = self.create_opus_tags();
-            packet_writer
-                .write_packet(opus_tags, 0, ogg::PacketWriteEndInfo::EndPage, 0)
-                .map_err(|e| TranscodeError::EncodingError(format!("Failed to write Opus tags: {}", e)))?;
-
-            // 编码音频数据
-            let mut output_buf = vec![0u8; 4000]; // Opus 最大包大小
-            
-            // RFC 7845: granule position 必须是 48kHz 采样率下的样本数
-            // 需要将实际采样率的帧大小转换为 48kHz
-            let granule_scale = 48000.0 / sample_rate as f64;
-            let frame_granule = (frame_size as f64 * granule_scale) as u64;
-            
-            // pre_skip 也是 48kHz 下的样本数
-            let pre_skip_48k = (pre_skip as f64 * granule_scale) as u64;
-            let mut granule_pos: u64 = pre_skip_48k;
-            
-            // 收集所有 chunks（包括不完整的最后一帧）
-            let chunks: Vec<_> = pcm_i16.chunks(samples_per_frame).collect();
-            
-            // 计算需要刷新的额外帧数（编码器延迟）
-            // pre_skip 样本被缓存在编码器中，需要额外的帧来刷新
-            let flush_frames = (pre_skip as usize + samples_per_frame - 1) / samples_per_frame;
-
-            for chunk in chunks.into_iter() {
-                // 如果最后一帧不完整，用零填充
-                let frame = if chunk.len() < samples_per_frame {
-                    let mut padded = chunk.to_vec();
-                    padded.resize(samples_per_frame, 0);
-                    padded
-                } else {
-                    chunk.to_vec()
-                };
-
-                let encoded_len = encoder
-                    .encode(&frame, &mut output_buf)
-                    .map_err(|e| TranscodeError::EncodingError(format!("Opus encode failed: {}", e)))?;
-
-                granule_pos += frame_granule;
-                
-                packet_writer
-                    .write_packet(
-                        output_buf[..encoded_len].to_vec(),
-                        0,
-                        ogg::PacketWriteEndInfo::NormalPacket,
-                        granule_pos,
-                    )
-                    .map_err(|e| TranscodeError::EncodingError(format!("Failed to write Opus packet: {}", e)))?;
-            }
-            
-            // 刷新编码器：发送额外的静音帧来获取编码器缓冲区中剩余的样本
-            let silence_frame = vec![0i16; samples_per_frame];
-            for flush_idx in 0..flush_frames {
-                let encoded_len = encoder
-                    .encode(&silence_frame, &mut output_buf)
-                    .map_err(|e| TranscodeError::EncodingError(format!("Opus flush encode failed: {}", e)))?;
-
-                granule_pos += frame_granule;
-                
-                let is_last = flush_idx == flush_frames - 1;
-                let end_info = if is_last {
-                    ogg::PacketWriteEndInfo::EndStream
-                } else {
-                    ogg::PacketWriteEndInfo::NormalPacket
-                };
-
-                packet_writer
-                    .write_packet(
-                        output_buf[..encoded_len].to_vec(),
-                        0,
-                        end_info,
-                        granule_pos,
-                    )
-                    .map_err(|e| TranscodeError::EncodingError(format!("Failed to write Opus flush packet: {}", e)))?;
-            }
+            encode_all(&mut encoder, &pcm.samples)
         }
-
-        Ok(ogg_data)
     }
 
     /// 获取 Opus 兼容的采样率
@@ -428,7 +317,7 @@ impl WavTranscoder {
         }
     }
 
-    /// 简单线性重采样
+    /// 重采样（按配置的质量选择 sinc 或线性插值）
     fn resample(
         &self,
         samples: &[f32],
@@ -439,6 +328,9 @@ impl WavTranscoder {
         if from_rate == to_rate {
             return Ok(samples.to_vec());
         }
+        if let Some(resampler) = &self.resampler {
+            return Ok(resampler.process(samples, from_rate, to_rate, channels));
+        }
 
         let ratio = to_rate as f64 / from_rate as f64;
         let channel_count = channels as usize;
@@ -467,31 +359,284 @@ impl WavTranscoder {
         Ok(resampled)
     }
 
-    /// 创建 Opus Head 包 (RFC 7845)
-    fn create_opus_head(&self, channels: u8, sample_rate: u32, pre_skip: u16) -> Vec<u8> {
-        let mut head = Vec::with_capacity(19);
-        head.extend_from_slice(b"OpusHead");  // Magic signature
-        head.push(1);                          // Version
-        head.push(channels);                   // Channel count
-        head.extend_from_slice(&pre_skip.to_le_bytes()); // Pre-skip (encoder delay)
-        head.extend_from_slice(&sample_rate.to_le_bytes()); // Input sample rate
-        head.extend_from_slice(&0i16.to_le_bytes()); // Output gain
-        head.push(0);                          // Channel mapping family
-        head
+    /// 转换到目标声道数（单声道下混 / 单声道复制为立体声）、播放速度和采样率
+    fn convert(
+        &self,
+        audio: DecodedAudio,
+        config: &TranscodeConfig,
+    ) -> Result<DecodedAudio, TranscodeError> {
+        let (samples, channels) = match config.channels {
+            Some(1) if audio.channels > 1 => (self.downmix_to_mono(&audio.samples, audio.channels), 1),
+            Some(2) if audio.channels == 1 => (
+                audio.samples.iter().flat_map(|&s| [s, s]).collect(),
+                2,
+            ),
+            _ => (audio.samples, audio.channels),
+        };
+
+        let samples = match config.tempo {
+            Some(tempo) => time_stretch::stretch(&samples, audio.sample_rate, channels, tempo),
+            None => samples,
+        };
+
+        let sample_rate = config.sample_rate.unwrap_or(audio.sample_rate);
+        let samples = self.resample(&samples, audio.sample_rate, sample_rate, channels)?;
+        let duration_ms = samples.len() as u64 * 1000 / (sample_rate as u64 * channels as u64);
+
+        Ok(DecodedAudio {
+            samples,
+            sample_rate,
+            channels,
+            duration_ms,
+        })
+    }
+
+    /// 按目标格式编码 PCM
+    fn encode(
+        &self,
+        decoded: DecodedAudio,
+        config: &TranscodeConfig,
+        metadata: &AudioMetadata,
+        original_size: usize,
+    ) -> Result<TranscodeResult, TranscodeError> {
+        match config.format {
+            AudioFormat::Wav => {
+                let output = self.encode_wav(&decoded)?;
+                Ok(TranscodeResult {
+                    audio_data: output.clone(),
+                    format: AudioFormat::Wav,
+                    duration_ms: decoded.duration_ms,
+                    sample_rate: decoded.sample_rate,
+                    channels: decoded.channels,
+                    original_size,
+                    transcoded_size: output.len(),
+                })
+            }
+            AudioFormat::Opus => {
+                let bitrate = config.bitrate.unwrap_or(32000);
+                let opus_data = self.encode_opus(&decoded, bitrate, metadata)?;
+                
+                tracing::debug!(
+                    original_size = original_size,
+                    opus_size = opus_data.len(),
+                    bitrate = bitrate,
+                    "Encoded to Opus"
+                );
+
+                Ok(TranscodeResult {
+                    audio_data: opus_data.clone(),
+                    format: AudioFormat::Opus,
+                    duration_ms: decoded.duration_ms,
+                    sample_rate: decoded.sample_rate,
+                    channels: decoded.channels,
+                    original_size,
+                    transcoded_size: opus_data.len(),
+                })
+            }
+            AudioFormat::Webm => {
+                let bitrate = config.bitrate.unwrap_or(32000);
+                let webm_data = self.encode_webm(&decoded, bitrate, metadata)?;
+                Ok(TranscodeResult {
+                    transcoded_size: webm_data.len(),
+                    audio_data: webm_data,
+                    format: AudioFormat::Webm,
+                    duration_ms: decoded.duration_ms,
+                    sample_rate: decoded.sample_rate,
+                    channels: decoded.channels,
+                    original_size,
+                })
+            }
+            AudioFormat::Mp3 => {
+                // TODO: 实现 MP3 编码
+                // 需要添加 mp3lame-encoder 或类似 crate
+                tracing::warn!(
+                    "MP3 encoding not yet implemented, returning WAV. \
+                     To enable MP3, add an MP3 encoder crate dependency."
+                );
+                let output = self.encode_wav(&decoded)?;
+                Ok(TranscodeResult {
+                    transcoded_size: output.len(),
+                    audio_data: output,
+                    format: AudioFormat::Wav, // 实际返回 WAV
+                    duration_ms: decoded.duration_ms,
+                    sample_rate: decoded.sample_rate,
+                    channels: decoded.channels,
+                    original_size,
+                })
+            }
+        }
+    }
+
+    /// 下混为单声道（各声道取平均）
+    fn downmix_to_mono(&self, samples: &[f32], channels: u8) -> Vec<f32> {
+        if channels <= 1 {
+            return samples.to_vec();
+        }
+        let channel_count = channels as usize;
+        samples
+            .chunks_exact(channel_count)
+            .map(|frame| frame.iter().sum::<f32>() / channel_count as f32)
+            .collect()
+    }
+
+    /// 裁剪首尾低于阈值的静音（单声道）
+    fn trim_silence(
+        &self,
+        samples: &[f32],
+        sample_rate: u32,
+        threshold_db: f32,
+    ) -> Result<Vec<f32>, TranscodeError> {
+        let range = self
+            .audible_range(samples, sample_rate, 1, threshold_db)
+            .ok_or_else(|| {
+                TranscodeError::InvalidInput("Reference audio contains only silence".to_string())
+            })?;
+        Ok(samples[range].to_vec())
+    }
+
+    /// 按窗口计算 RMS 电平，返回去掉首尾静音后的样本范围（含余量，按帧对齐）
+    ///
+    /// 全部为静音时返回 None
+    fn audible_range(
+        &self,
+        samples: &[f32],
+        sample_rate: u32,
+        channels: u8,
+        threshold_db: f32,
+    ) -> Option<Range<usize>> {
+        let channel_count = channels.max(1) as usize;
+        let window = ((sample_rate * SILENCE_WINDOW_MS / 1000) as usize).max(1) * channel_count;
+        let threshold = db_to_linear(threshold_db);
+
+        let loud_windows: Vec<usize> = samples
+            .chunks(window)
+            .enumerate()
+            .filter(|(_, chunk)| rms(chunk) >= threshold)
+            .map(|(i, _)| i)
+            .collect();
+
+        let (first, last) = (*loud_windows.first()?, *loud_windows.last()?);
+
+        let padding = (sample_rate * TRIM_PADDING_MS / 1000) as usize * channel_count;
+        let start = (first * window).saturating_sub(padding);
+        let end = ((last + 1) * window + padding).min(samples.len());
+
+        Some(start..end)
+    }
+
+    /// 响度归一化到目标 RMS 电平，同时限制峰值避免削波
+    fn normalize_loudness(&self, samples: &mut [f32], target_db: f32) {
+        let current = rms(samples);
+        if current <= f32::EPSILON {
+            return;
+        }
+
+        let peak = samples.iter().fold(0.0f32, |acc, &s| acc.max(s.abs()));
+        let mut gain = db_to_linear(target_db) / current;
+        if peak * gain > PEAK_CEILING {
+            gain = PEAK_CEILING / peak;
+        }
+
+        for sample in samples.iter_mut() {
+            *sample *= gain;
+        }
     }
 
     /// 创建 Opus Tags 包
-    fn create_opus_tags(&self) -> Vec<u8> {
+    ///
+    /// 章节使用 Vorbis comment 章节扩展：`CHAPTER001=00:00:00.000`、`CHAPTER001NAME=标题`
+    fn create_opus_tags(&self, metadata: &AudioMetadata) -> Vec<u8> {
+        let mut comments = Vec::new();
+        for (key, value) in [
+            ("TITLE", &metadata.title),
+            ("ALBUM", &metadata.album),
+            ("ARTIST", &metadata.artist),
+        ] {
+            if let Some(value) = value {
+                comments.push(format!("{}={}", key, value));
+            }
+        }
+        if let Some(track_number) = metadata.track_number {
+            comments.push(format!("TRACKNUMBER={}", track_number));
+        }
+        for (i, chapter) in metadata.chapters.iter().enumerate() {
+            let ms = chapter.start_ms;
+            comments.push(format!(
+                "CHAPTER{:03}={:02}:{:02}:{:02}.{:03}",
+                i + 1,
+                ms / 3_600_000,
+                ms / 60_000 % 60,
+                ms / 1000 % 60,
+                ms % 1000
+            ));
+            comments.push(format!("CHAPTER{:03}NAME={}", i + 1, chapter.title));
+        }
+
         let vendor = "rovel";
         let mut tags = Vec::new();
         tags.extend_from_slice(b"OpusTags");
         tags.extend_from_slice(&(vendor.len() as u32).to_le_bytes());
         tags.extend_from_slice(vendor.as_bytes());
-        tags.extend_from_slice(&0u32.to_le_bytes()); // No user comments
+        tags.extend_from_slice(&(comments.len() as u32).to_le_bytes());
+        for comment in comments {
+            tags.extend_from_slice(&(comment.len() as u32).to_le_bytes());
+            tags.extend_from_slice(comment.as_bytes());
+        }
         tags
     }
 }
 
+/// 计算样本的 RMS（线性值）
+fn rms(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let sum: f64 = samples.iter().map(|&s| (s as f64) * (s as f64)).sum();
+    (sum / samples.len() as f64).sqrt() as f32
+}
+
+/// 按时间均分为 `points` 段，取每段的最大幅度（保留 3 位小数）
+///
+/// 帧数少于点数时相邻的点取同一帧
+fn compute_peaks(samples: &[f32], channels: u8, points: usize) -> Vec<f32> {
+    let channel_count = channels.max(1) as usize;
+    let frame_count = samples.len() / channel_count;
+    if frame_count == 0 {
+        return vec![0.0; points];
+    }
+
+    (0..points)
+        .map(|i| {
+            let start = i * frame_count / points;
+            let end = ((i + 1) * frame_count / points).max(start + 1);
+            let peak = samples[start * channel_count..end * channel_count]
+                .iter()
+                .fold(0.0f32, |acc, &s| acc.max(s.abs()))
+                .min(1.0);
+            (peak * 1000.0).round() / 1000.0
+        })
+        .collect()
+}
+
+/// dBFS 转线性幅度
+/// 交叉淡化拼接：`next` 的开头与 `output` 的末尾重叠至多 `fade_frames` 帧（等功率淡化）
+fn append_crossfade(output: &mut Vec<f32>, next: &[f32], channels: usize, fade_frames: usize) {
+    let frames = fade_frames
+        .min(output.len() / channels)
+        .min(next.len() / channels);
+    let overlap = frames * channels;
+    let start = output.len() - overlap;
+    for (i, (out, &sample)) in output[start..].iter_mut().zip(&next[..overlap]).enumerate() {
+        let t = ((i / channels) as f32 + 0.5) / frames as f32 * FRAC_PI_2;
+        *out = *out * t.cos() + sample * t.sin();
+    }
+    output.extend_from_slice(&next[overlap..]);
+}
+
+fn db_to_linear(db: f32) -> f32 {
+    10f32.powf(db / 20.0)
+}
+
 #[derive(Debug)]
 struct WavHeader {
     fmt: FmtChunk,
@@ -523,16 +668,22 @@ struct DecodedAudio {
 
 #[async_trait]
 impl AudioTranscoderPort for WavTranscoder {
-    async fn transcode(
+    async fn transcode_with_metadata(
         &self,
         wav_data: &[u8],
         config: &TranscodeConfig,
+        metadata: &AudioMetadata,
     ) -> Result<TranscodeResult, TranscodeError> {
         let original_size = wav_data.len();
-
-        // 如果未启用转码或目标格式是 WAV，直接返回
-        if !self.enabled || config.format == AudioFormat::Wav {
-            let info = self.get_audio_info(wav_data)?;
+        let info = self.get_audio_info(wav_data)?;
+
+        // 未启用转码，或目标为 WAV 且采样率/声道数/速度已满足要求时，直接返回
+        let needs_resample = config.sample_rate.is_some_and(|rate| rate != info.sample_rate);
+        let needs_remix = config.channels.is_some_and(|channels| channels != info.channels);
+        let needs_stretch = config.tempo.is_some_and(|tempo| tempo != 1.0);
+        if !self.enabled
+            || (config.format == AudioFormat::Wav && !needs_resample && !needs_remix && !needs_stretch)
+        {
             return Ok(TranscodeResult {
                 audio_data: wav_data.to_vec(),
                 format: AudioFormat::Wav,
@@ -544,72 +695,113 @@ impl AudioTranscoderPort for WavTranscoder {
             });
         }
 
-        // 解码 WAV
-        let decoded = self.decode_wav_to_pcm(wav_data)?;
+        // 解码 WAV 并转换到目标采样率/声道数
+        let decoded = self.convert(self.decode_wav_to_pcm(wav_data)?, config)?;
 
-        // 根据目标格式进行编码
-        match config.format {
-            AudioFormat::Wav => {
-                // 如果需要重采样或改变声道，处理后重新编码为 WAV
-                let output = self.encode_wav(&decoded)?;
-                Ok(TranscodeResult {
-                    audio_data: output.clone(),
-                    format: AudioFormat::Wav,
-                    duration_ms: decoded.duration_ms,
-                    sample_rate: decoded.sample_rate,
-                    channels: decoded.channels,
-                    original_size,
-                    transcoded_size: output.len(),
-                })
-            }
-            AudioFormat::Opus => {
-                let bitrate = config.bitrate.unwrap_or(32000);
-                let opus_data = self.encode_opus(&decoded, bitrate)?;
-                
-                tracing::debug!(
-                    original_size = original_size,
-                    opus_size = opus_data.len(),
-                    bitrate = bitrate,
-                    "Encoded to Opus"
-                );
+        self.encode(decoded, config, metadata, original_size)
+    }
 
-                Ok(TranscodeResult {
-                    audio_data: opus_data.clone(),
-                    format: AudioFormat::Opus,
-                    duration_ms: decoded.duration_ms,
-                    sample_rate: decoded.sample_rate,
-                    channels: decoded.channels,
-                    original_size,
-                    transcoded_size: opus_data.len(),
-                })
-            }
-            AudioFormat::Mp3 => {
-                // TODO: 实现 MP3 编码
-                // 需要添加 mp3lame-encoder 或类似 crate
-                tracing::warn!(
-                    "MP3 encoding not yet implemented, returning original WAV. \
-                     To enable MP3, add an MP3 encoder crate dependency."
-                );
-                let info = self.get_audio_info(wav_data)?;
-                Ok(TranscodeResult {
-                    audio_data: wav_data.to_vec(),
-                    format: AudioFormat::Wav, // 实际返回 WAV
-                    duration_ms: info.duration_ms,
-                    sample_rate: info.sample_rate,
-                    channels: info.channels,
-                    original_size,
-                    transcoded_size: original_size,
-                })
-            }
+    async fn normalize_reference(
+        &self,
+        audio_data: &[u8],
+        config: &ReferenceNormalizeConfig,
+    ) -> Result<TranscodeResult, TranscodeError> {
+        let original_size = audio_data.len();
+        let decoded = self.decode_to_pcm(audio_data, Hint::new())?;
+
+        let mono = self.downmix_to_mono(&decoded.samples, decoded.channels);
+        let trimmed =
+            self.trim_silence(&mono, decoded.sample_rate, config.silence_threshold_db)?;
+        let mut samples = self.resample(&trimmed, decoded.sample_rate, config.sample_rate, 1)?;
+        self.normalize_loudness(&mut samples, config.target_loudness_db);
+
+        let duration_ms = samples.len() as u64 * 1000 / config.sample_rate as u64;
+        let output = self.encode_wav(&DecodedAudio {
+            samples,
+            sample_rate: config.sample_rate,
+            channels: 1,
+            duration_ms,
+        })?;
+
+        tracing::debug!(
+            original_ms = decoded.duration_ms,
+            normalized_ms = duration_ms,
+            original_rate = decoded.sample_rate,
+            original_channels = decoded.channels,
+            "Reference audio normalized"
+        );
+
+        Ok(TranscodeResult {
+            transcoded_size: output.len(),
+            audio_data: output,
+            format: AudioFormat::Wav,
+            duration_ms,
+            sample_rate: config.sample_rate,
+            channels: 1,
+            original_size,
+        })
+    }
+
+    async fn concat(
+        &self,
+        clips: &[Vec<u8>],
+        config: &ConcatConfig,
+    ) -> Result<TranscodeResult, TranscodeError> {
+        let original_size = clips.iter().map(Vec::len).sum();
+        let mut stream = self.concat_stream(config)?;
+        let mut audio_data = Vec::new();
+        for clip in clips {
+            audio_data.extend(stream.push(clip)?);
+        }
+        let end = stream.finish()?;
+        audio_data.extend(end.data);
+        if let Some(header) = end.header {
+            audio_data[..header.len()].copy_from_slice(&header);
         }
+
+        tracing::debug!(
+            clips = clips.len(),
+            duration_ms = end.duration_ms,
+            format = %end.format,
+            "Audio clips concatenated"
+        );
+
+        Ok(TranscodeResult {
+            transcoded_size: audio_data.len(),
+            audio_data,
+            format: end.format,
+            duration_ms: end.duration_ms,
+            sample_rate: end.sample_rate,
+            channels: end.channels,
+            original_size,
+        })
+    }
+
+    fn concat_stream(&self, config: &ConcatConfig) -> Result<Box<dyn AudioStreamEncoder>, TranscodeError> {
+        Ok(Box::new(ConcatStream {
+            transcoder: self.clone(),
+            config: config.clone(),
+            output: None,
+            tail: Vec::new(),
+            emitted: 0,
+        }))
+    }
+
+    fn peaks(&self, audio_data: &[u8], points: usize) -> Result<Vec<f32>, TranscodeError> {
+        let decoded = self.decode_to_pcm(audio_data, Hint::new())?;
+        Ok(compute_peaks(&decoded.samples, decoded.channels, points))
     }
 
     fn get_audio_info(&self, wav_data: &[u8]) -> Result<AudioInfo, TranscodeError> {
         let header = self.parse_wav_header(wav_data)?;
+        // 流式/截断的 WAV 头部声明的长度可能大于实际数据
+        let data_size = header
+            .data_size
+            .min(wav_data.len().saturating_sub(header.data_start));
 
         // 计算时长
         let samples_per_channel = if header.fmt.bits_per_sample > 0 && header.fmt.num_channels > 0 {
-            header.data_size
+            data_size
                 / (header.fmt.bits_per_sample as usize / 8)
                 / header.fmt.num_channels as usize
         } else {
@@ -627,7 +819,7 @@ impl AudioTranscoderPort for WavTranscoder {
             sample_rate: header.fmt.sample_rate,
             channels: header.fmt.num_channels as u8,
             bits_per_sample: header.fmt.bits_per_sample,
-            data_size: header.data_size,
+            data_size,
         })
     }
 
@@ -635,11 +827,161 @@ impl AudioTranscoderPort for WavTranscoder {
         match format {
             AudioFormat::Wav => true,
             AudioFormat::Opus => true,
+            AudioFormat::Webm => true,
             AudioFormat::Mp3 => false, // TODO: 实现后改为 true
         }
     }
 }
 
+/// 流式拼接的输出编码器（采样率/声道数由第一段有声音频确定）
+struct StreamOutput {
+    encoder: Box<dyn PcmEncoder>,
+    format: AudioFormat,
+    sample_rate: u32,
+    channels: u8,
+}
+
+/// 流式拼接：逐段解码、转换、裁剪静音后编码输出
+///
+/// 只保留交叉淡化所需的末尾样本，不持有已输出的音频
+struct ConcatStream {
+    transcoder: WavTranscoder,
+    config: ConcatConfig,
+    output: Option<StreamOutput>,
+    /// 尚未输出的末尾样本（等待与下一段交叉淡化）
+    tail: Vec<f32>,
+    /// 已输出的样本数
+    emitted: u64,
+}
+
+impl ConcatStream {
+    fn create_output(&self, sample_rate: u32, channels: u8) -> Result<StreamOutput, TranscodeError> {
+        let (encoder, format): (Box<dyn PcmEncoder>, _) = match self.config.output.format {
+            AudioFormat::Opus => (
+                Box::new(OpusStreamEncoder::new(
+                    sample_rate,
+                    channels,
+                    self.config.output.bitrate.unwrap_or(32000),
+                    self.transcoder.create_opus_tags(&self.config.metadata),
+                )?),
+                AudioFormat::Opus,
+            ),
+            AudioFormat::Webm => (
+                Box::new(WebmStreamEncoder::new(
+                    sample_rate,
+                    channels,
+                    self.config.output.bitrate.unwrap_or(32000),
+                    self.config.metadata.clone(),
+                )?),
+                AudioFormat::Webm,
+            ),
+            AudioFormat::Wav => (Box::new(WavStreamEncoder::new(sample_rate, channels)), AudioFormat::Wav),
+            AudioFormat::Mp3 => {
+                tracing::warn!("MP3 encoding not yet implemented, returning WAV");
+                (Box::new(WavStreamEncoder::new(sample_rate, channels)), AudioFormat::Wav)
+            }
+        };
+        Ok(StreamOutput {
+            encoder,
+            format,
+            sample_rate,
+            channels,
+        })
+    }
+}
+
+impl AudioStreamEncoder for ConcatStream {
+    fn push(&mut self, clip: &[u8]) -> Result<Vec<u8>, TranscodeError> {
+        let decoded = self.transcoder.decode_to_pcm(clip, Hint::new())?;
+        // 统一到输出（未指定时为第一段）的采样率和声道数，Opus 输出取兼容的采样率
+        let (sample_rate, channels) = match &self.output {
+            Some(out) => (out.sample_rate, out.channels),
+            None => {
+                let sample_rate = self.config.output.sample_rate.unwrap_or(decoded.sample_rate);
+                let sample_rate = match self.config.output.format {
+                    AudioFormat::Opus | AudioFormat::Webm => {
+                        self.transcoder.get_opus_compatible_sample_rate(sample_rate)
+                    }
+                    _ => sample_rate,
+                };
+                (sample_rate, self.config.output.channels.unwrap_or(decoded.channels))
+            }
+        };
+        let decoded = self.transcoder.convert(
+            decoded,
+            &TranscodeConfig {
+                sample_rate: Some(sample_rate),
+                channels: Some(channels),
+                ..self.config.output.clone()
+            },
+        )?;
+        if decoded.channels != channels {
+            return Err(TranscodeError::InvalidInput(format!(
+                "Cannot convert {} channels to {}",
+                decoded.channels, channels
+            )));
+        }
+
+        let samples = match self.config.trim_silence_db {
+            Some(threshold) => {
+                match self.transcoder.audible_range(&decoded.samples, sample_rate, channels, threshold) {
+                    Some(range) => decoded.samples[range].to_vec(),
+                    None => return Ok(Vec::new()),
+                }
+            }
+            None => decoded.samples,
+        };
+
+        if self.output.is_none() {
+            self.output = Some(self.create_output(sample_rate, channels)?);
+        }
+        let out = self.output.as_mut().unwrap();
+
+        // 非第一段时先插入片段间静音
+        let channel_count = channels as usize;
+        if self.config.gap_ms > 0 && (self.emitted > 0 || !self.tail.is_empty()) {
+            let gap_frames = (sample_rate as u64 * self.config.gap_ms as u64 / 1000) as usize;
+            self.tail.resize(self.tail.len() + gap_frames * channel_count, 0.0);
+        }
+
+        // 末尾 fade_frames 帧留待与下一段交叉淡化
+        let fade_frames = (sample_rate as u64 * self.config.crossfade_ms as u64 / 1000) as usize;
+        append_crossfade(&mut self.tail, &samples, channel_count, fade_frames);
+        let keep = (fade_frames * channel_count).min(self.tail.len());
+        let ready: Vec<f32> = self.tail.drain(..self.tail.len() - keep).collect();
+        self.emitted += ready.len() as u64;
+        out.encoder.push(&ready)
+    }
+
+    fn duration_ms(&self) -> u64 {
+        match &self.output {
+            Some(out) => {
+                (self.emitted + self.tail.len() as u64) * 1000
+                    / (out.sample_rate as u64 * out.channels as u64)
+            }
+            None => 0,
+        }
+    }
+
+    fn finish(self: Box<Self>) -> Result<StreamEnd, TranscodeError> {
+        let duration_ms = self.duration_ms();
+        let mut out = self.output.ok_or_else(|| {
+            TranscodeError::InvalidInput("No audible audio to concatenate".to_string())
+        })?;
+        let mut data = out.encoder.push(&self.tail)?;
+        data.extend(out.encoder.finish()?);
+
+        Ok(StreamEnd {
+            data,
+            header: out.encoder.final_header(),
+            format: out.format,
+            duration_ms,
+            sample_rate: out.sample_rate,
+            channels: out.channels,
+        })
+    }
+}
+
 impl Default for WavTranscoder {
     fn default() -> Self {
         Self::new(true)
@@ -718,11 +1060,31 @@ mod tests {
         assert_eq!(result.audio_data.len(), wav.len());
     }
 
+    #[tokio::test]
+    async fn test_transcode_applies_sample_rate_and_channels() {
+        let transcoder = WavTranscoder::new(true);
+        let wav = create_test_wav();
+
+        let config = TranscodeConfig {
+            format: AudioFormat::Wav,
+            sample_rate: Some(24000),
+            channels: Some(2),
+            ..Default::default()
+        };
+
+        let result = transcoder.transcode(&wav, &config).await.unwrap();
+        let info = transcoder.get_audio_info(&result.audio_data).unwrap();
+        assert_eq!((info.sample_rate, info.channels), (24000, 2));
+        assert_eq!((result.sample_rate, result.channels), (24000, 2));
+        assert!(info.duration_ms >= 990 && info.duration_ms <= 1010);
+    }
+
     #[test]
     fn test_supports_format() {
         let transcoder = WavTranscoder::new(true);
         assert!(transcoder.supports_format(AudioFormat::Wav));
         assert!(transcoder.supports_format(AudioFormat::Opus));
+        assert!(transcoder.supports_format(AudioFormat::Webm));
         // MP3 暂未实现
         assert!(!transcoder.supports_format(AudioFormat::Mp3));
     }
@@ -745,4 +1107,162 @@ mod tests {
         // 验证 OGG 头
         assert_eq!(&result.audio_data[0..4], b"OggS");
     }
+
+    #[tokio::test]
+    async fn test_normalize_reference() {
+        let transcoder = WavTranscoder::new(false);
+
+        // 48kHz 立体声：0.5s 静音 + 1s 正弦波 + 0.5s 静音
+        let source_rate = 48000u32;
+        let mut samples = Vec::new();
+        for i in 0..(source_rate * 2) {
+            let value = if (source_rate / 2..source_rate * 3 / 2).contains(&i) {
+                let t = i as f32 / source_rate as f32;
+                (t * 440.0 * std::f32::consts::TAU).sin() * 0.05
+            } else {
+                0.0
+            };
+            samples.push(value);
+            samples.push(value);
+        }
+        let wav = transcoder
+            .encode_wav(&DecodedAudio {
+                samples,
+                sample_rate: source_rate,
+                channels: 2,
+                duration_ms: 2000,
+            })
+            .unwrap();
+
+        let config = ReferenceNormalizeConfig::default();
+        let result = transcoder.normalize_reference(&wav, &config).await.unwrap();
+
+        assert_eq!(result.channels, 1);
+        assert_eq!(result.sample_rate, 24000);
+        // 静音被裁剪，仅保留约 1s 语音 + 首尾余量
+        assert!(result.duration_ms >= 1000 && result.duration_ms <= 1300);
+
+        let info = transcoder.get_audio_info(&result.audio_data).unwrap();
+        assert_eq!(info.sample_rate, 24000);
+        assert_eq!(info.channels, 1);
+    }
+
+    #[tokio::test]
+    async fn test_concat_trims_silence_and_crossfades() {
+        let transcoder = WavTranscoder::new(true);
+
+        // 16kHz 单声道：0.5s 静音 + 1s 正弦波 + 0.5s 静音
+        let samples = (0..32000)
+            .map(|i| {
+                if (8000..24000).contains(&i) {
+                    (i as f32 / 16000.0 * 440.0 * std::f32::consts::TAU).sin() * 0.3
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        let clip = transcoder
+            .encode_wav(&DecodedAudio {
+                samples,
+                sample_rate: 16000,
+                channels: 1,
+                duration_ms: 2000,
+            })
+            .unwrap();
+
+        let mut config = ConcatConfig {
+            trim_silence_db: Some(-45.0),
+            gap_ms: 0,
+            crossfade_ms: 50,
+            output: TranscodeConfig {
+                format: AudioFormat::Wav,
+                ..Default::default()
+            },
+            metadata: AudioMetadata::default(),
+        };
+        let clips = vec![clip.clone(), create_test_wav(), clip];
+        let result = transcoder.concat(&clips, &config).await.unwrap();
+
+        // 每段保留约 1s 语音 + 首尾余量，静音片段被跳过，重叠 50ms
+        assert_eq!((result.sample_rate, result.channels), (16000, 1));
+        assert!(result.duration_ms >= 2300 && result.duration_ms <= 2450);
+
+        // 片段间插入 200ms 静音（静音片段不计入间隔）
+        config.gap_ms = 200;
+        let gapped = transcoder.concat(&clips, &config).await.unwrap();
+        assert_eq!(gapped.duration_ms, result.duration_ms + 200);
+
+        let silent = transcoder.concat(&[create_test_wav()], &config).await;
+        assert!(silent.is_err());
+    }
+
+    #[test]
+    fn test_decode_mp3() {
+        // MPEG-1 Layer III 单声道帧：128kbps、44.1kHz，每帧 417 字节、1152 个采样；
+        // 边信息全零，解码为静音
+        let mut frame = vec![0u8; 417];
+        frame[..4].copy_from_slice(&[0xFF, 0xFB, 0x90, 0xC4]);
+        let mp3 = frame.repeat(20);
+
+        let transcoder = WavTranscoder::new(true);
+        let decoded = transcoder.decode_to_pcm(&mp3, Hint::new()).unwrap();
+        assert_eq!(decoded.sample_rate, 44100);
+        assert_eq!(decoded.channels, 1);
+        assert!(decoded.samples.len() >= 19 * 1152);
+        assert!(decoded.samples.iter().all(|s| *s == 0.0));
+    }
+
+    #[tokio::test]
+    async fn test_normalize_reference_rejects_silence() {
+        let transcoder = WavTranscoder::new(false);
+        let wav = create_test_wav();
+
+        let result = transcoder
+            .normalize_reference(&wav, &ReferenceNormalizeConfig::default())
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_opus_tags_include_chapters() {
+        use crate::application::ports::ChapterMark;
+
+        let transcoder = WavTranscoder::new(true);
+        let metadata = AudioMetadata {
+            title: Some("斗破苍穹".to_string()),
+            album: None,
+            artist: None,
+            track_number: Some(12),
+            chapters: vec![
+                ChapterMark { start_ms: 0, title: "第一章 陨落的天才".to_string() },
+                ChapterMark { start_ms: 3_723_456, title: "第二章 斗气大陆".to_string() },
+            ],
+        };
+        let tags = String::from_utf8_lossy(&transcoder.create_opus_tags(&metadata)).into_owned();
+
+        assert!(tags.starts_with("OpusTags"));
+        assert!(tags.contains("TITLE=斗破苍穹"));
+        assert!(!tags.contains("ALBUM="));
+        assert!(tags.contains("TRACKNUMBER=12"));
+        assert!(tags.contains("CHAPTER001=00:00:00.000"));
+        assert!(tags.contains("CHAPTER001NAME=第一章 陨落的天才"));
+        assert!(tags.contains("CHAPTER002=01:02:03.456"));
+    }
+
+    #[test]
+    fn test_compute_peaks() {
+        // 立体声：前半段右声道 0.5，后半段左声道 -1.0
+        let mut samples = Vec::new();
+        for _ in 0..100 {
+            samples.extend([0.0, 0.5]);
+        }
+        for _ in 0..100 {
+            samples.extend([-1.0, 0.25]);
+        }
+
+        assert_eq!(compute_peaks(&samples, 2, 4), vec![0.5, 0.5, 1.0, 1.0]);
+        // 帧数少于点数
+        assert_eq!(compute_peaks(&[0.2, -0.3], 1, 4), vec![0.2, 0.2, 0.3, 0.3]);
+        assert_eq!(compute_peaks(&[], 1, 2), vec![0.0, 0.0]);
+    }
 }
@@ -0,0 +1,380 @@
+//! WebM 容器（Matroska 子集）
+//!
+//! 部分浏览器和 Android 播放器对 WebM 音频的支持比 Ogg 更可靠。输出结构：
+//! - EBML 头部（DocType = webm）
+//! - Segment：Info（时长）、Tracks（A_OPUS，CodecPrivate 为 OpusHead）、Tags、Chapters
+//! - 每秒一个 Cluster，帧以 SimpleBlock 写入
+//!
+//! 流式输出时 Segment 长度为“未知”、时长为 0；结束后由 final_header 给出实际值（长度不变，可直接回写）
+
+use crate::application::ports::{AudioMetadata, TranscodeError};
+
+use super::stream::{OpusFrames, PcmEncoder, OPUS_FRAME_MS};
+
+// EBML / Matroska 元素 ID
+const EBML: u32 = 0x1A45DFA3;
+const EBML_VERSION: u32 = 0x4286;
+const EBML_READ_VERSION: u32 = 0x42F7;
+const EBML_MAX_ID_LENGTH: u32 = 0x42F2;
+const EBML_MAX_SIZE_LENGTH: u32 = 0x42F3;
+const DOC_TYPE: u32 = 0x4282;
+const DOC_TYPE_VERSION: u32 = 0x4287;
+const DOC_TYPE_READ_VERSION: u32 = 0x4285;
+const SEGMENT: u32 = 0x18538067;
+const INFO: u32 = 0x1549A966;
+const TIMESTAMP_SCALE: u32 = 0x2AD7B1;
+const DURATION: u32 = 0x4489;
+const MUXING_APP: u32 = 0x4D80;
+const WRITING_APP: u32 = 0x5741;
+const TRACKS: u32 = 0x1654AE6B;
+const TRACK_ENTRY: u32 = 0xAE;
+const TRACK_NUMBER: u32 = 0xD7;
+const TRACK_UID: u32 = 0x73C5;
+const TRACK_TYPE: u32 = 0x83;
+const CODEC_ID: u32 = 0x86;
+const CODEC_PRIVATE: u32 = 0x63A2;
+const CODEC_DELAY: u32 = 0x56AA;
+const SEEK_PRE_ROLL: u32 = 0x56BB;
+const AUDIO: u32 = 0xE1;
+const SAMPLING_FREQUENCY: u32 = 0xB5;
+const CHANNELS: u32 = 0x9F;
+const TAGS: u32 = 0x1254C367;
+const TAG: u32 = 0x7373;
+const TARGETS: u32 = 0x63C0;
+const TARGET_TYPE_VALUE: u32 = 0x68CA;
+const SIMPLE_TAG: u32 = 0x67C8;
+const TAG_NAME: u32 = 0x45A3;
+const TAG_STRING: u32 = 0x4487;
+const CHAPTERS: u32 = 0x1043A770;
+const EDITION_ENTRY: u32 = 0x45B9;
+const CHAPTER_ATOM: u32 = 0xB6;
+const CHAPTER_UID: u32 = 0x73C4;
+const CHAPTER_TIME_START: u32 = 0x91;
+const CHAPTER_DISPLAY: u32 = 0x80;
+const CHAP_STRING: u32 = 0x85;
+const CLUSTER: u32 = 0x1F43B675;
+const CLUSTER_TIMESTAMP: u32 = 0xE7;
+const SIMPLE_BLOCK: u32 = 0xA3;
+
+/// 元素长度为“未知”（8 字节 vint 全 1）
+const UNKNOWN_SIZE: u64 = (1 << 56) - 1;
+
+/// Opus 解码器 seek 后需要预解码的时长（纳秒），见 Matroska Opus 映射
+const OPUS_SEEK_PRE_ROLL_NS: u64 = 80_000_000;
+
+/// 每个 Cluster 的帧数（1 秒）
+const FRAMES_PER_CLUSTER: usize = 1000 / OPUS_FRAME_MS;
+
+/// Tags 中专辑级标签的 TargetTypeValue
+const TARGET_ALBUM: u64 = 50;
+
+fn write_id(buf: &mut Vec<u8>, id: u32) {
+    let bytes = id.to_be_bytes();
+    let skip = bytes.iter().take_while(|&&b| b == 0).count();
+    buf.extend_from_slice(&bytes[skip..]);
+}
+
+/// 写入 EBML 变长整数（长度字段）
+fn write_size(buf: &mut Vec<u8>, size: u64) {
+    let len = (1..=8).find(|&len| size < (1 << (7 * len)) - 1).unwrap_or(8);
+    write_size_fixed(buf, size, len);
+}
+
+/// 以固定字节数写入长度字段（回写时需保持长度不变）
+fn write_size_fixed(buf: &mut Vec<u8>, size: u64, len: usize) {
+    let marked = size | (1 << (7 * len));
+    buf.extend_from_slice(&marked.to_be_bytes()[8 - len..]);
+}
+
+fn element(buf: &mut Vec<u8>, id: u32, body: &[u8]) {
+    write_id(buf, id);
+    write_size(buf, body.len() as u64);
+    buf.extend_from_slice(body);
+}
+
+fn uint_element(buf: &mut Vec<u8>, id: u32, value: u64) {
+    let bytes = value.to_be_bytes();
+    let skip = bytes.iter().take_while(|&&b| b == 0).count().min(7);
+    element(buf, id, &bytes[skip..]);
+}
+
+fn float_element(buf: &mut Vec<u8>, id: u32, value: f64) {
+    element(buf, id, &value.to_be_bytes());
+}
+
+fn string_element(buf: &mut Vec<u8>, id: u32, value: &str) {
+    element(buf, id, value.as_bytes());
+}
+
+fn master(buf: &mut Vec<u8>, id: u32, build: impl FnOnce(&mut Vec<u8>)) {
+    let mut body = Vec::new();
+    build(&mut body);
+    element(buf, id, &body);
+}
+
+/// 流式 WebM Opus 编码器
+pub(super) struct WebmStreamEncoder {
+    frames: OpusFrames,
+    sample_rate: u32,
+    channels: u8,
+    metadata: AudioMetadata,
+    /// 已输出的头部长度，尚未输出时为 None
+    header_len: Option<usize>,
+    /// 当前 Cluster 中的 SimpleBlock
+    cluster: Vec<u8>,
+    cluster_frames: usize,
+    /// 已编码的帧数
+    frame_count: u64,
+    /// 输入的样本帧数（用于计算时长）
+    input_frames: u64,
+    /// 已输出的 Cluster 字节数
+    cluster_len: u64,
+}
+
+impl WebmStreamEncoder {
+    /// `sample_rate` 必须为 Opus 支持的采样率（8/12/16/24/48 kHz），`channels` 为 1 或 2
+    pub(super) fn new(
+        sample_rate: u32,
+        channels: u8,
+        bitrate: u32,
+        metadata: AudioMetadata,
+    ) -> Result<Self, TranscodeError> {
+        Ok(Self {
+            frames: OpusFrames::new(sample_rate, channels, bitrate)?,
+            sample_rate,
+            channels,
+            metadata,
+            header_len: None,
+            cluster: Vec::new(),
+            cluster_frames: 0,
+            frame_count: 0,
+            input_frames: 0,
+            cluster_len: 0,
+        })
+    }
+
+    /// EBML 头部到 Segment 的元数据部分（不含 Cluster），`cluster_len` 为 None 时 Segment 长度未知
+    fn header(&self, cluster_len: Option<u64>, duration_ms: f64) -> Vec<u8> {
+        let mut buf = Vec::new();
+        master(&mut buf, EBML, |b| {
+            uint_element(b, EBML_VERSION, 1);
+            uint_element(b, EBML_READ_VERSION, 1);
+            uint_element(b, EBML_MAX_ID_LENGTH, 4);
+            uint_element(b, EBML_MAX_SIZE_LENGTH, 8);
+            string_element(b, DOC_TYPE, "webm");
+            uint_element(b, DOC_TYPE_VERSION, 4);
+            uint_element(b, DOC_TYPE_READ_VERSION, 2);
+        });
+
+        let mut segment = Vec::new();
+        master(&mut segment, INFO, |b| {
+            uint_element(b, TIMESTAMP_SCALE, 1_000_000); // 毫秒
+            float_element(b, DURATION, duration_ms);
+            string_element(b, MUXING_APP, "rovel");
+            string_element(b, WRITING_APP, "rovel");
+        });
+        master(&mut segment, TRACKS, |b| {
+            master(b, TRACK_ENTRY, |b| {
+                uint_element(b, TRACK_NUMBER, 1);
+                uint_element(b, TRACK_UID, 1);
+                uint_element(b, TRACK_TYPE, 2); // audio
+                string_element(b, CODEC_ID, "A_OPUS");
+                element(b, CODEC_PRIVATE, &self.frames.head());
+                uint_element(b, CODEC_DELAY, self.frames.pre_skip_ns());
+                uint_element(b, SEEK_PRE_ROLL, OPUS_SEEK_PRE_ROLL_NS);
+                master(b, AUDIO, |b| {
+                    float_element(b, SAMPLING_FREQUENCY, 48000.0);
+                    uint_element(b, CHANNELS, self.channels as u64);
+                });
+            });
+        });
+        self.write_tags(&mut segment);
+        self.write_chapters(&mut segment);
+
+        write_id(&mut buf, SEGMENT);
+        let segment_len = cluster_len.map_or(UNKNOWN_SIZE, |len| len + segment.len() as u64);
+        write_size_fixed(&mut buf, segment_len, 8);
+        buf.extend(segment);
+        buf
+    }
+
+    fn write_tags(&self, buf: &mut Vec<u8>) {
+        let tags: Vec<(&str, String)> = [
+            ("TITLE", self.metadata.title.clone()),
+            ("ALBUM", self.metadata.album.clone()),
+            ("ARTIST", self.metadata.artist.clone()),
+            ("PART_NUMBER", self.metadata.track_number.map(|n| n.to_string())),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| Some((name, value?)))
+        .collect();
+        if tags.is_empty() {
+            return;
+        }
+
+        master(buf, TAGS, |b| {
+            master(b, TAG, |b| {
+                master(b, TARGETS, |b| uint_element(b, TARGET_TYPE_VALUE, TARGET_ALBUM));
+                for (name, value) in &tags {
+                    master(b, SIMPLE_TAG, |b| {
+                        string_element(b, TAG_NAME, name);
+                        string_element(b, TAG_STRING, value);
+                    });
+                }
+            });
+        });
+    }
+
+    fn write_chapters(&self, buf: &mut Vec<u8>) {
+        if self.metadata.chapters.is_empty() {
+            return;
+        }
+        master(buf, CHAPTERS, |b| {
+            master(b, EDITION_ENTRY, |b| {
+                for (i, chapter) in self.metadata.chapters.iter().enumerate() {
+                    master(b, CHAPTER_ATOM, |b| {
+                        uint_element(b, CHAPTER_UID, i as u64 + 1);
+                        uint_element(b, CHAPTER_TIME_START, chapter.start_ms * 1_000_000);
+                        master(b, CHAPTER_DISPLAY, |b| string_element(b, CHAP_STRING, &chapter.title));
+                    });
+                }
+            });
+        });
+    }
+
+    fn duration_ms(&self) -> f64 {
+        self.input_frames as f64 * 1000.0 / self.sample_rate as f64
+    }
+
+    /// 首次输出时写入头部（长度未知、时长为 0）
+    fn take_header(&mut self) -> Vec<u8> {
+        if self.header_len.is_some() {
+            return Vec::new();
+        }
+        let header = self.header(None, 0.0);
+        self.header_len = Some(header.len());
+        header
+    }
+
+    fn add_packets(&mut self, packets: Vec<Vec<u8>>, output: &mut Vec<u8>) {
+        for packet in packets {
+            if self.cluster_frames == FRAMES_PER_CLUSTER {
+                self.flush_cluster(output);
+            }
+            let offset = (self.cluster_frames * OPUS_FRAME_MS) as i16;
+            let mut block = Vec::with_capacity(packet.len() + 4);
+            write_size(&mut block, 1); // track number
+            block.extend_from_slice(&offset.to_be_bytes());
+            block.push(0x80); // keyframe
+            block.extend(packet);
+            element(&mut self.cluster, SIMPLE_BLOCK, &block);
+            self.cluster_frames += 1;
+            self.frame_count += 1;
+        }
+    }
+
+    fn flush_cluster(&mut self, output: &mut Vec<u8>) {
+        if self.cluster_frames == 0 {
+            return;
+        }
+        let start_ms = (self.frame_count - self.cluster_frames as u64) * OPUS_FRAME_MS as u64;
+        let mut cluster = Vec::new();
+        uint_element(&mut cluster, CLUSTER_TIMESTAMP, start_ms);
+        cluster.append(&mut self.cluster);
+
+        let before = output.len();
+        element(output, CLUSTER, &cluster);
+        self.cluster_len += (output.len() - before) as u64;
+        self.cluster_frames = 0;
+    }
+}
+
+impl PcmEncoder for WebmStreamEncoder {
+    fn push(&mut self, samples: &[f32]) -> Result<Vec<u8>, TranscodeError> {
+        let mut output = self.take_header();
+        self.input_frames += (samples.len() / self.channels.max(1) as usize) as u64;
+        let packets = self.frames.push(samples)?;
+        self.add_packets(packets, &mut output);
+        Ok(output)
+    }
+
+    fn finish(&mut self) -> Result<Vec<u8>, TranscodeError> {
+        let mut output = self.take_header();
+        let packets = self.frames.finish()?;
+        self.add_packets(packets, &mut output);
+        self.flush_cluster(&mut output);
+        Ok(output)
+    }
+
+    fn final_header(&self) -> Option<Vec<u8>> {
+        let header = self.header(Some(self.cluster_len), self.duration_ms());
+        debug_assert_eq!(Some(header.len()), self.header_len);
+        Some(header)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::stream::encode_all;
+    use super::*;
+    use crate::application::ports::ChapterMark;
+
+    #[test]
+    fn test_ebml_size_encoding() {
+        let size = |value: u64| {
+            let mut buf = Vec::new();
+            write_size(&mut buf, value);
+            buf
+        };
+        assert_eq!(size(0), vec![0x80]);
+        assert_eq!(size(126), vec![0xFE]);
+        // 全 1 为保留值，127 需要两个字节
+        assert_eq!(size(127), vec![0x40, 0x7F]);
+        assert_eq!(size(16382), vec![0x7F, 0xFE]);
+
+        let mut buf = Vec::new();
+        write_size_fixed(&mut buf, UNKNOWN_SIZE, 8);
+        assert_eq!(buf, vec![0x01, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
+    }
+
+    #[test]
+    fn test_webm_output() {
+        let samples: Vec<f32> = (0..24000 * 3)
+            .map(|i| (i as f32 / 24000.0 * 440.0 * std::f32::consts::TAU).sin() * 0.5)
+            .collect();
+        let metadata = AudioMetadata {
+            title: Some("第一章".to_string()),
+            chapters: vec![ChapterMark { start_ms: 0, title: "第一章".to_string() }],
+            ..Default::default()
+        };
+
+        let mut encoder = WebmStreamEncoder::new(24000, 1, 32000, metadata.clone()).unwrap();
+        let whole = encode_all(&mut encoder, &samples).unwrap();
+        assert_eq!(&whole[..4], &EBML.to_be_bytes());
+        let find = |needle: &[u8]| whole.windows(needle.len()).any(|w| w == needle);
+        assert!(find(b"webm"));
+        assert!(find(b"A_OPUS"));
+        assert!(find(b"OpusHead"));
+        assert!(find("第一章".as_bytes()));
+        // 3 秒音频 + 刷新帧：4 个 Cluster
+        let clusters = whole.windows(4).filter(|w| *w == CLUSTER.to_be_bytes()).count();
+        assert_eq!(clusters, 4);
+        // 回写后的时长与 Segment 长度
+        assert!(find(&3000f64.to_be_bytes()));
+        let segment_start = whole.windows(4).position(|w| w == SEGMENT.to_be_bytes()).unwrap() + 12;
+        let mut expected = Vec::new();
+        write_size_fixed(&mut expected, (whole.len() - segment_start) as u64, 8);
+        assert_eq!(&whole[segment_start - 8..segment_start], expected.as_slice());
+
+        // 分块输出与一次性编码一致
+        let mut encoder = WebmStreamEncoder::new(24000, 1, 32000, metadata).unwrap();
+        let mut streamed = Vec::new();
+        for chunk in samples.chunks(999) {
+            streamed.extend(encoder.push(chunk).unwrap());
+        }
+        streamed.extend(encoder.finish().unwrap());
+        let header = encoder.final_header().unwrap();
+        streamed[..header.len()].copy_from_slice(&header);
+        assert_eq!(streamed, whole);
+    }
+}
@@ -1,6 +1,6 @@
 //! Fake TTS Client - 用于测试的 TTS 客户端
 //!
-//! 始终返回固定的音频文件，不实际调用 TTS 服务
+//! 始终返回固定的音频文件（未配置时为静音），不实际调用 TTS 服务
 
 use async_trait::async_trait;
 use std::path::PathBuf;
@@ -10,8 +10,8 @@ use crate::application::ports::{InferRequest, InferResponse, TtsEnginePort, TtsE
 /// Fake TTS Client 配置
 #[derive(Debug, Clone)]
 pub struct FakeTtsClientConfig {
-    /// 固定返回的音频文件路径
-    pub audio_file_path: PathBuf,
+    /// 固定返回的音频文件路径（None 时按 duration_ms 生成静音 WAV）
+    pub audio_file_path: Option<PathBuf>,
     /// 固定返回的音频时长（毫秒）
     pub duration_ms: u64,
     /// 采样率
@@ -21,7 +21,7 @@ pub struct FakeTtsClientConfig {
 impl Default for FakeTtsClientConfig {
     fn default() -> Self {
         Self {
-            audio_file_path: PathBuf::from("/home/github/rovel/Speaker_1.wav"),
+            audio_file_path: None,
             duration_ms: 5000,
             sample_rate: 22050,
         }
@@ -40,9 +40,12 @@ pub struct FakeTtsClient {
 impl FakeTtsClient {
     /// 创建新的 FakeTtsClient
     pub fn new(config: FakeTtsClientConfig) -> Result<Self, std::io::Error> {
-        let audio_data = std::fs::read(&config.audio_file_path)?;
+        let audio_data = match &config.audio_file_path {
+            Some(path) => std::fs::read(path)?,
+            None => silent_wav(config.duration_ms, config.sample_rate),
+        };
         tracing::info!(
-            path = %config.audio_file_path.display(),
+            path = ?config.audio_file_path,
             duration_ms = config.duration_ms,
             "FakeTtsClient initialized"
         );
@@ -55,6 +58,29 @@ impl FakeTtsClient {
     }
 }
 
+/// 生成指定时长的静音 WAV（16 位单声道 PCM）
+fn silent_wav(duration_ms: u64, sample_rate: u32) -> Vec<u8> {
+    let num_samples = (sample_rate as u64 * duration_ms / 1000) as usize;
+    let data_size = num_samples * 2;
+
+    let mut wav = Vec::with_capacity(44 + data_size);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_size as u32).to_le_bytes());
+    wav.extend_from_slice(b"WAVE");
+    wav.extend_from_slice(b"fmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // 单声道
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    wav.extend_from_slice(&(sample_rate * 2).to_le_bytes()); // byte rate
+    wav.extend_from_slice(&2u16.to_le_bytes()); // block align
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&(data_size as u32).to_le_bytes());
+    wav.resize(44 + data_size, 0);
+    wav
+}
+
 #[async_trait]
 impl TtsEnginePort for FakeTtsClient {
     async fn infer(&self, request: InferRequest) -> Result<InferResponse, TtsError> {
@@ -80,3 +106,17 @@ impl TtsEnginePort for FakeTtsClient {
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_to_silence() {
+        let client = FakeTtsClient::with_defaults().unwrap();
+        let wav = &client.audio_data;
+        assert_eq!(&wav[0..4], b"RIFF");
+        // 5 秒 22050Hz 16 位单声道
+        assert_eq!(wav.len(), 44 + 22050 * 5 * 2);
+    }
+}
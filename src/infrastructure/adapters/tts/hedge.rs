@@ -0,0 +1,178 @@
+//! Hedged TTS Engine - 对冲请求
+//!
+//! 对延迟敏感的请求（当前播放片段），主后端在 `delay` 内未响应时，
+//! 向备用后端发送同一请求，采用先成功返回的结果并取消另一个请求，
+//! 以掩盖繁忙 GPU 服务器的 p99 延迟尖刺。
+//!
+//! 非延迟敏感的请求只发送到主后端。
+
+use async_trait::async_trait;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::application::ports::{
+    InferRequest, InferResponse, InferStream, TtsEnginePort, TtsError,
+};
+
+/// 对冲 TTS 引擎
+pub struct HedgedTtsEngine {
+    primary: Arc<dyn TtsEnginePort>,
+    secondary: Arc<dyn TtsEnginePort>,
+    delay: Duration,
+}
+
+impl HedgedTtsEngine {
+    pub fn new(
+        primary: Arc<dyn TtsEnginePort>,
+        secondary: Arc<dyn TtsEnginePort>,
+        delay: Duration,
+    ) -> Self {
+        Self {
+            primary,
+            secondary,
+            delay,
+        }
+    }
+}
+
+/// 主请求超过 `delay` 未完成时启动备用请求，返回先成功的结果
+///
+/// 一方失败时等待另一方；返回时未完成的请求随 future 一起被丢弃（取消）
+async fn hedge<T, P, S>(delay: Duration, primary: P, secondary: S) -> Result<T, TtsError>
+where
+    P: Future<Output = Result<T, TtsError>>,
+    S: Future<Output = Result<T, TtsError>>,
+{
+    tokio::pin!(primary);
+    tokio::select! {
+        result = &mut primary => return result,
+        _ = tokio::time::sleep(delay) => {}
+    }
+
+    tracing::debug!(delay_ms = delay.as_millis() as u64, "Primary TTS backend slow, sending hedged request");
+    tokio::pin!(secondary);
+    tokio::select! {
+        result = &mut primary => match result {
+            Ok(value) => Ok(value),
+            Err(e) => {
+                tracing::debug!(error = %e, "Primary TTS backend failed, waiting for hedged request");
+                secondary.await
+            }
+        },
+        result = &mut secondary => match result {
+            Ok(value) => {
+                tracing::debug!("Hedged TTS request won");
+                Ok(value)
+            }
+            Err(e) => {
+                tracing::debug!(error = %e, "Hedged TTS request failed, waiting for primary");
+                primary.await
+            }
+        },
+    }
+}
+
+#[async_trait]
+impl TtsEnginePort for HedgedTtsEngine {
+    async fn infer(&self, request: InferRequest) -> Result<InferResponse, TtsError> {
+        if !request.latency_sensitive {
+            return self.primary.infer(request).await;
+        }
+        hedge(
+            self.delay,
+            self.primary.infer(request.clone()),
+            self.secondary.infer(request),
+        )
+        .await
+    }
+
+    /// 以开始返回音频的时间对冲
+    async fn infer_stream(&self, request: InferRequest) -> Result<InferStream, TtsError> {
+        if !request.latency_sensitive {
+            return self.primary.infer_stream(request).await;
+        }
+        hedge(
+            self.delay,
+            self.primary.infer_stream(request.clone()),
+            self.secondary.infer_stream(request),
+        )
+        .await
+    }
+
+    async fn infer_batch(&self, requests: Vec<InferRequest>) -> Result<Vec<InferResponse>, TtsError> {
+        self.primary.infer_batch(requests).await
+    }
+
+    async fn health_check(&self) -> bool {
+        self.primary.health_check().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::MarkupType;
+    use crate::domain::voice::TtsConfig;
+
+    /// 延迟指定时间后返回名称的测试引擎
+    struct SlowEngine(&'static str, Duration);
+
+    #[async_trait]
+    impl TtsEnginePort for SlowEngine {
+        async fn infer(&self, _request: InferRequest) -> Result<InferResponse, TtsError> {
+            tokio::time::sleep(self.1).await;
+            Ok(InferResponse {
+                session_id: self.0.to_string(),
+                audio_data: Vec::new(),
+                duration_ms: None,
+                sample_rate: None,
+            })
+        }
+    }
+
+    fn request(latency_sensitive: bool) -> InferRequest {
+        InferRequest {
+            text: "你好".to_string(),
+            voice_ref: "http://localhost/voice.wav".to_string(),
+            voice_ref_path: None,
+            voice_id: "voice".to_string(),
+            prompt_text: None,
+            engine: None,
+            tts_config: TtsConfig::default(),
+            params: Default::default(),
+            seed: None,
+            latency_sensitive,
+            markup: MarkupType::PlainText,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hedged_request_wins_when_primary_slow() {
+        let engine = HedgedTtsEngine::new(
+            Arc::new(SlowEngine("primary", Duration::from_secs(5))),
+            Arc::new(SlowEngine("secondary", Duration::from_millis(10))),
+            Duration::from_millis(20),
+        );
+
+        let response = engine.infer(request(true)).await.unwrap();
+        assert_eq!(response.session_id, "secondary");
+    }
+
+    #[tokio::test]
+    async fn test_no_hedge_when_primary_fast_or_not_latency_sensitive() {
+        let engine = HedgedTtsEngine::new(
+            Arc::new(SlowEngine("primary", Duration::from_millis(10))),
+            Arc::new(SlowEngine("secondary", Duration::ZERO)),
+            Duration::from_millis(200),
+        );
+        assert_eq!(engine.infer(request(true)).await.unwrap().session_id, "primary");
+
+        let engine = HedgedTtsEngine::new(
+            Arc::new(SlowEngine("primary", Duration::from_millis(50))),
+            Arc::new(SlowEngine("secondary", Duration::ZERO)),
+            Duration::ZERO,
+        );
+        assert_eq!(engine.infer(request(false)).await.unwrap().session_id, "primary");
+    }
+}
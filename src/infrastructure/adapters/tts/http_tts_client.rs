@@ -4,23 +4,147 @@
 //!
 //! 外部 TTS API:
 //! POST http://localhost:8000/api/tts/infer
-//! Request: {"text": "...", "voice_ref": "http://..."}  (JSON)
+//! Request: {"text": "...", "voice_ref": "http://...", "prompt_text": "...", "speed": 1.0,
+//!           "emotion": "happy", "energy": 1.0, "pitch": 0, "volume": 0.8, "temperature": 0.7,
+//!           "top_p": 0.9, "seed": 42}
+//!          (JSON, prompt_text/emotion/energy/volume/temperature/top_p/seed 可省略)
 //! Response: audio/wav binary, metadata in headers
+//!
+//! 文本为 SSML 时请求体附带 `"markup": "ssml"`
+//!
+//! 流式推理时请求体附带 `"stream": true`，服务端以 chunked 编码边合成边返回音频
+//!
+//! 配置 `api_key` 时所有请求附带认证：默认 `Authorization: Bearer <key>`，
+//! 设置 `api_key_header` 时改为以该 header 发送原始 key（如 `X-API-Key`）
+//!
+//! 参考音频传递方式（`reference_mode`）:
+//! - url: 仅发送 voice_ref 下载地址，由 TTS 服务回调 Rovel 下载（默认）
+//! - base64: 请求体附带 `"voice_audio": "<base64>"`，适用于 TTS 服务无法访问 Rovel 的场景（如 NAT 后）
+//! - multipart: multipart/form-data 上传，`request` 字段为上述 JSON，`voice_audio` 字段为音频文件
+//!   （批量推理不支持 multipart，使用 base64）
+//!
+//! 批量推理（需服务端支持，`batch = true`）:
+//! POST http://localhost:8000/api/tts/infer_batch
+//! Request: {"requests": [<同 infer 请求体>, ...]}
+//! Response: {"results": [{"session_id": "...", "audio": "<base64 WAV>",
+//!            "duration_ms": 1234, "sample_rate": 22050}, ...]}（与请求一一对应）
 
 use async_trait::async_trait;
-use reqwest::Client;
-use serde::Serialize;
+use base64::Engine;
+use futures_util::stream::{self, StreamExt};
+use reqwest::header::HeaderMap;
+use reqwest::multipart::{Form, Part};
+use reqwest::{Client, RequestBuilder, Response};
+use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
-use crate::application::ports::{InferRequest, InferResponse, TtsEnginePort, TtsError};
+use crate::application::ports::{
+    InferRequest, InferResponse, InferStream, MarkupType, TtsEnginePort, TtsError,
+};
 
 /// TTS 推理请求体 (JSON)
 #[derive(Debug, Serialize)]
 struct TtsHttpRequest {
     /// 要合成的文本
     text: String,
+    /// 文本标记类型（"ssml"，纯文本时省略）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    markup: Option<&'static str>,
     /// 参考音频的 URL 或路径（TTS 服务自行下载/读取并缓存）
     voice_ref: String,
+    /// Base64 编码的参考音频（base64 上传模式，其他模式省略）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    voice_audio: Option<String>,
+    /// 参考音频对应的文本（未设置时省略）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prompt_text: Option<String>,
+    /// 语速
+    speed: f32,
+    /// 情感（未设置时省略）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    emotion: Option<String>,
+    /// 能量/音量强度（未设置时省略）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    energy: Option<f32>,
+    /// 音调（半音）
+    pitch: i8,
+    /// 音量（默认 1.0 时省略）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    volume: Option<f32>,
+    /// 采样温度（未设置时使用服务默认值）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    /// 核采样阈值（未设置时使用服务默认值）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    /// 随机种子（未设置时省略）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+    /// 是否以 chunked 响应流式返回音频（false 时省略）
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
+}
+
+/// 批量推理请求体 (JSON)
+#[derive(Debug, Serialize)]
+struct TtsHttpBatchRequest {
+    requests: Vec<TtsHttpRequest>,
+}
+
+/// 批量推理响应体 (JSON)
+#[derive(Debug, Deserialize)]
+struct TtsHttpBatchResponse {
+    results: Vec<TtsHttpBatchResult>,
+}
+
+/// 批量推理单个结果
+#[derive(Debug, Deserialize)]
+struct TtsHttpBatchResult {
+    #[serde(default)]
+    session_id: Option<String>,
+    /// Base64 编码的音频
+    audio: String,
+    #[serde(default)]
+    duration_ms: Option<u64>,
+    #[serde(default)]
+    sample_rate: Option<u32>,
+}
+
+impl TtsHttpRequest {
+    fn new(request: &InferRequest, stream: bool) -> Self {
+        Self {
+            text: request.text.clone(),
+            markup: match request.markup {
+                MarkupType::PlainText => None,
+                MarkupType::Ssml => Some("ssml"),
+            },
+            voice_ref: request.voice_ref.clone(),
+            voice_audio: None,
+            prompt_text: request.prompt_text.clone(),
+            speed: request.params.speed.unwrap_or(request.tts_config.speed),
+            emotion: request.params.emotion.clone(),
+            energy: request.params.energy,
+            pitch: request.tts_config.pitch,
+            volume: (request.tts_config.volume != 1.0).then_some(request.tts_config.volume),
+            temperature: request.tts_config.temperature,
+            top_p: request.tts_config.top_p,
+            seed: request.seed,
+            stream,
+        }
+    }
+}
+
+/// 参考音频传递方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferenceMode {
+    /// TTS 服务通过 voice_ref URL 下载
+    #[default]
+    Url,
+    /// 以 Base64 嵌入 JSON 请求体
+    Base64,
+    /// multipart/form-data 上传
+    Multipart,
 }
 
 /// HTTP TTS 客户端配置
@@ -32,6 +156,14 @@ pub struct HttpTtsClientConfig {
     pub timeout_secs: u64,
     /// 重试次数
     pub max_retries: u32,
+    /// 服务端是否支持批量推理接口
+    pub batch: bool,
+    /// 参考音频传递方式
+    pub reference_mode: ReferenceMode,
+    /// TTS 服务 API Key（None 表示不认证）
+    pub api_key: Option<String>,
+    /// 发送 API Key 的 header 名（None 时使用 Authorization: Bearer）
+    pub api_key_header: Option<String>,
 }
 
 impl Default for HttpTtsClientConfig {
@@ -40,6 +172,10 @@ impl Default for HttpTtsClientConfig {
             base_url: "http://localhost:8000".to_string(),
             timeout_secs: 120,
             max_retries: 0,
+            batch: false,
+            reference_mode: ReferenceMode::Url,
+            api_key: None,
+            api_key_header: None,
         }
     }
 }
@@ -56,6 +192,11 @@ impl HttpTtsClientConfig {
         self.timeout_secs = secs;
         self
     }
+
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
 }
 
 /// HTTP TTS 客户端
@@ -87,31 +228,91 @@ impl HttpTtsClient {
         format!("{}/api/tts/infer", self.config.base_url)
     }
 
+    /// 获取批量推理 URL
+    fn infer_batch_url(&self) -> String {
+        format!("{}/api/tts/infer_batch", self.config.base_url)
+    }
+
     /// 获取健康检查 URL
     fn health_url(&self) -> String {
         format!("{}/health", self.config.base_url)
     }
-}
 
-#[async_trait]
-impl TtsEnginePort for HttpTtsClient {
-    async fn infer(&self, request: InferRequest) -> Result<InferResponse, TtsError> {
-        let http_request = TtsHttpRequest {
-            text: request.text.clone(),
-            voice_ref: request.voice_ref.clone(),
-        };
+    /// 读取参考音频（url 模式返回 None）
+    async fn read_reference(&self, request: &InferRequest) -> Result<Option<Vec<u8>>, TtsError> {
+        if self.config.reference_mode == ReferenceMode::Url {
+            return Ok(None);
+        }
+        let path = request
+            .voice_ref_path
+            .as_ref()
+            .ok_or_else(|| TtsError::VoiceNotFound(format!("No reference audio file for voice {}", request.voice_id)))?;
+        let audio = tokio::fs::read(path).await.map_err(|e| {
+            TtsError::VoiceNotFound(format!("Failed to read reference audio {}: {}", path.display(), e))
+        })?;
+        Ok(Some(audio))
+    }
+
+    /// 构建推理请求体，base64 模式下嵌入参考音频
+    async fn build_request(&self, request: &InferRequest, stream: bool) -> Result<TtsHttpRequest, TtsError> {
+        let mut http_request = TtsHttpRequest::new(request, stream);
+        if let Some(audio) = self.read_reference(request).await? {
+            http_request.voice_audio = Some(base64::engine::general_purpose::STANDARD.encode(audio));
+        }
+        Ok(http_request)
+    }
 
+    /// 发送推理请求，返回成功的 HTTP 响应（音频尚未读取）
+    async fn send_infer(&self, request: &InferRequest, stream: bool) -> Result<Response, TtsError> {
         tracing::debug!(
             url = %self.infer_url(),
-            text_len = http_request.text.len(),
-            voice_ref = %http_request.voice_ref,
+            text_len = request.text.len(),
+            voice_ref = %request.voice_ref,
+            reference_mode = ?self.config.reference_mode,
+            stream,
             "Sending TTS infer request"
         );
 
+        if self.config.reference_mode != ReferenceMode::Multipart {
+            let http_request = self.build_request(request, stream).await?;
+            return self.post_json(self.infer_url(), &http_request).await;
+        }
+
+        let audio = self.read_reference(request).await?.unwrap_or_default();
+        let http_request = TtsHttpRequest::new(request, stream);
+        let json = serde_json::to_string(&http_request)
+            .map_err(|e| TtsError::ServiceError(format!("Failed to encode request: {}", e)))?;
+        let file_name = request
+            .voice_ref_path
+            .as_ref()
+            .and_then(|p| p.file_name())
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| format!("{}.wav", request.voice_id));
+        let form = Form::new()
+            .text("request", json)
+            .part("voice_audio", Part::bytes(audio).file_name(file_name));
+
+        self.send(self.client.post(self.infer_url()).multipart(form)).await
+    }
+
+    /// POST JSON 请求，非 2xx 响应转换为 ServiceError
+    async fn post_json<T: Serialize>(&self, url: String, body: &T) -> Result<Response, TtsError> {
+        self.send(self.client.post(url).json(body)).await
+    }
+
+    /// 附加 API Key 认证 header
+    fn authorize(&self, builder: RequestBuilder) -> RequestBuilder {
+        match (&self.config.api_key, &self.config.api_key_header) {
+            (Some(key), Some(header)) => builder.header(header.as_str(), key),
+            (Some(key), None) => builder.bearer_auth(key),
+            (None, _) => builder,
+        }
+    }
+
+    /// 发送请求，非 2xx 响应转换为 ServiceError
+    async fn send(&self, builder: RequestBuilder) -> Result<Response, TtsError> {
         let response = self
-            .client
-            .post(&self.infer_url())
-            .json(&http_request)
+            .authorize(builder)
             .send()
             .await
             .map_err(|e| {
@@ -133,21 +334,33 @@ impl TtsEnginePort for HttpTtsClient {
             )));
         }
 
-        // 从 headers 提取元数据
-        let headers = response.headers();
-        let session_id = headers
-            .get("X-TTS-Session-Id")
-            .and_then(|v| v.to_str().ok())
-            .unwrap_or("unknown")
-            .to_string();
-        let duration_ms = headers
-            .get("X-TTS-Duration-Ms")
-            .and_then(|v| v.to_str().ok())
-            .and_then(|v| v.parse().ok());
-        let sample_rate = headers
-            .get("X-TTS-Sample-Rate")
-            .and_then(|v| v.to_str().ok())
-            .and_then(|v| v.parse().ok());
+        Ok(response)
+    }
+}
+
+/// 从 headers 提取的推理元数据
+struct ResponseMetadata {
+    session_id: String,
+    duration_ms: Option<u64>,
+    sample_rate: Option<u32>,
+}
+
+impl ResponseMetadata {
+    fn from_headers(headers: &HeaderMap) -> Self {
+        let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+        Self {
+            session_id: header("X-TTS-Session-Id").unwrap_or("unknown").to_string(),
+            duration_ms: header("X-TTS-Duration-Ms").and_then(|v| v.parse().ok()),
+            sample_rate: header("X-TTS-Sample-Rate").and_then(|v| v.parse().ok()),
+        }
+    }
+}
+
+#[async_trait]
+impl TtsEnginePort for HttpTtsClient {
+    async fn infer(&self, request: InferRequest) -> Result<InferResponse, TtsError> {
+        let response = self.send_infer(&request, false).await?;
+        let metadata = ResponseMetadata::from_headers(response.headers());
 
         // 直接获取音频字节
         let audio_data = response
@@ -157,25 +370,108 @@ impl TtsEnginePort for HttpTtsClient {
             .to_vec();
 
         tracing::info!(
-            session_id = %session_id,
-            duration_ms = ?duration_ms,
-            sample_rate = ?sample_rate,
+            session_id = %metadata.session_id,
+            duration_ms = ?metadata.duration_ms,
+            sample_rate = ?metadata.sample_rate,
             audio_size = audio_data.len(),
             "TTS inference completed"
         );
 
         Ok(InferResponse {
-            session_id,
+            session_id: metadata.session_id,
             audio_data,
-            duration_ms,
-            sample_rate,
+            duration_ms: metadata.duration_ms,
+            sample_rate: metadata.sample_rate,
         })
     }
 
+    async fn infer_stream(&self, request: InferRequest) -> Result<InferStream, TtsError> {
+        let response = self.send_infer(&request, true).await?;
+        let metadata = ResponseMetadata::from_headers(response.headers());
+
+        tracing::debug!(
+            session_id = %metadata.session_id,
+            sample_rate = ?metadata.sample_rate,
+            "TTS streaming inference started"
+        );
+
+        // 逐块读取 chunked 响应
+        let chunks = stream::unfold(Some(response), |response| async move {
+            let mut response = response?;
+            match response.chunk().await {
+                Ok(Some(chunk)) => Some((Ok(chunk.to_vec()), Some(response))),
+                Ok(None) => None,
+                Err(e) => Some((
+                    Err(TtsError::InvalidResponse(format!("Failed to read audio: {}", e))),
+                    None,
+                )),
+            }
+        });
+
+        Ok(InferStream {
+            session_id: metadata.session_id,
+            sample_rate: metadata.sample_rate,
+            chunks: chunks.boxed(),
+        })
+    }
+
+    async fn infer_batch(&self, requests: Vec<InferRequest>) -> Result<Vec<InferResponse>, TtsError> {
+        if !self.config.batch {
+            let mut responses = Vec::with_capacity(requests.len());
+            for request in requests {
+                responses.push(self.infer(request).await?);
+            }
+            return Ok(responses);
+        }
+
+        let mut body = TtsHttpBatchRequest {
+            requests: Vec::with_capacity(requests.len()),
+        };
+        for request in &requests {
+            body.requests.push(self.build_request(request, false).await?);
+        }
+        tracing::debug!(
+            url = %self.infer_batch_url(),
+            batch_size = body.requests.len(),
+            "Sending TTS batch infer request"
+        );
+
+        let response = self.post_json(self.infer_batch_url(), &body).await?;
+        let batch: TtsHttpBatchResponse = response
+            .json()
+            .await
+            .map_err(|e| TtsError::InvalidResponse(format!("Invalid batch response: {}", e)))?;
+        if batch.results.len() != requests.len() {
+            return Err(TtsError::InvalidResponse(format!(
+                "Batch response has {} results for {} requests",
+                batch.results.len(),
+                requests.len()
+            )));
+        }
+
+        let responses = batch
+            .results
+            .into_iter()
+            .map(|result| {
+                let audio_data = base64::engine::general_purpose::STANDARD
+                    .decode(result.audio.as_bytes())
+                    .map_err(|e| TtsError::InvalidResponse(format!("Invalid audio encoding: {}", e)))?;
+                Ok(InferResponse {
+                    session_id: result.session_id.unwrap_or_else(|| "unknown".to_string()),
+                    audio_data,
+                    duration_ms: result.duration_ms,
+                    sample_rate: result.sample_rate,
+                })
+            })
+            .collect::<Result<Vec<_>, TtsError>>()?;
+
+        tracing::info!(batch_size = responses.len(), "TTS batch inference completed");
+        Ok(responses)
+    }
+
     async fn health_check(&self) -> bool {
         match self
-            .client
-            .get(&self.health_url())
+            .authorize(self.client.get(self.health_url()))
             .timeout(Duration::from_secs(5))
             .send()
             .await
@@ -203,4 +499,112 @@ mod tests {
         assert_eq!(config.base_url, "http://example.com:9000");
         assert_eq!(config.timeout_secs, 60);
     }
+
+    #[test]
+    fn test_api_key_header() {
+        let headers = |config: HttpTtsClientConfig| {
+            let client = HttpTtsClient::new(config).unwrap();
+            client
+                .authorize(client.client.get(client.health_url()))
+                .build()
+                .unwrap()
+                .headers()
+                .clone()
+        };
+
+        assert!(headers(HttpTtsClientConfig::default()).is_empty());
+        assert_eq!(
+            headers(HttpTtsClientConfig::default().with_api_key("secret"))["authorization"],
+            "Bearer secret"
+        );
+        let config = HttpTtsClientConfig {
+            api_key_header: Some("X-API-Key".to_string()),
+            ..HttpTtsClientConfig::default().with_api_key("secret")
+        };
+        assert_eq!(headers(config)["x-api-key"], "secret");
+    }
+
+    #[test]
+    fn test_request_body_omits_unset_params() {
+        let body = TtsHttpRequest {
+            text: "你好".to_string(),
+            markup: None,
+            voice_ref: "http://localhost/voice.wav".to_string(),
+            voice_audio: None,
+            prompt_text: None,
+            speed: 1.2,
+            emotion: None,
+            energy: None,
+            pitch: -2,
+            volume: None,
+            temperature: None,
+            top_p: Some(0.9),
+            seed: Some(42),
+            stream: false,
+        };
+        let json = serde_json::to_value(&body).unwrap();
+        assert_eq!(json["pitch"], -2);
+        assert_eq!(json["seed"], 42);
+        assert!(json.get("temperature").is_none());
+        assert!(json.get("volume").is_none());
+        assert!(json.get("prompt_text").is_none());
+        assert!(json.get("top_p").is_some());
+        assert!(json.get("stream").is_none());
+        assert!(json.get("markup").is_none());
+        assert!(json.get("emotion").is_none());
+        assert!(json.get("voice_audio").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_base64_mode_embeds_reference_audio() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("voice.wav");
+        std::fs::write(&path, b"RIFF").unwrap();
+
+        let client = HttpTtsClient::new(HttpTtsClientConfig {
+            reference_mode: ReferenceMode::Base64,
+            ..Default::default()
+        })
+        .unwrap();
+        let mut request = InferRequest {
+            text: "你好".to_string(),
+            voice_ref: "http://localhost/voice.wav".to_string(),
+            voice_ref_path: Some(path),
+            voice_id: "voice".to_string(),
+            prompt_text: None,
+            engine: None,
+            tts_config: Default::default(),
+            params: Default::default(),
+            seed: None,
+            latency_sensitive: false,
+            markup: MarkupType::PlainText,
+        };
+
+        let body = client.build_request(&request, false).await.unwrap();
+        assert_eq!(body.voice_audio.as_deref(), Some("UklGRg=="));
+
+        // 参考音频缺失时不视为后端故障
+        request.voice_ref_path = None;
+        assert!(matches!(
+            client.build_request(&request, false).await,
+            Err(TtsError::VoiceNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_batch_response_parse() {
+        let batch: TtsHttpBatchResponse = serde_json::from_str(
+            r#"{"results": [{"session_id": "a", "audio": "UklGRg==", "sample_rate": 22050}, {"audio": ""}]}"#,
+        )
+        .unwrap();
+        assert_eq!(batch.results.len(), 2);
+        assert_eq!(batch.results[0].sample_rate, Some(22050));
+        assert_eq!(
+            base64::engine::general_purpose::STANDARD
+                .decode(batch.results[0].audio.as_bytes())
+                .unwrap(),
+            b"RIFF"
+        );
+        assert!(batch.results[1].session_id.is_none());
+    }
 }
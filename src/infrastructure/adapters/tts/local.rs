@@ -0,0 +1,418 @@
+//! Local TTS Engine - 本地 TTS 引擎（`local-tts` feature）
+//!
+//! 在 Rovel 所在机器上直接合成语音，小规模部署无需单独的 TTS 服务。
+//!
+//! 模型通过 [`LocalTtsModel`] 抽象接入，推理在阻塞线程池中执行，
+//! 输出的 PCM 样本封装为 16 位 WAV 返回。内置实现:
+//! - [`PiperModel`]: Piper（VITS）ONNX 模型，通过 onnxruntime 在进程内推理
+//!   （运行时动态加载 onnxruntime 库，编译时无需下载）
+//!
+//! 本地模型不支持声音克隆，voice_ref 被忽略；speed 映射为模型的 length_scale，
+//! emotion / energy 被忽略。
+
+use async_trait::async_trait;
+use ort::session::Session;
+use ort::value::Tensor;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tokio::sync::Semaphore;
+
+use crate::application::ports::{InferRequest, InferResponse, TtsEnginePort, TtsError};
+use crate::domain::voice::TtsConfig;
+
+/// 本地 TTS 模型
+pub trait LocalTtsModel: Send + Sync {
+    /// 输出采样率
+    fn sample_rate(&self) -> u32;
+
+    /// 合成单声道 16 位 PCM 样本（阻塞调用）
+    fn synthesize(&self, text: &str, config: &TtsConfig) -> Result<Vec<i16>, TtsError>;
+
+    /// 模型是否可用
+    fn is_ready(&self) -> bool {
+        true
+    }
+}
+
+/// 本地 TTS 引擎
+pub struct LocalTtsEngine {
+    model: Arc<dyn LocalTtsModel>,
+    /// 限制并发推理数（本地模型通常占满 CPU）
+    permits: Semaphore,
+}
+
+impl LocalTtsEngine {
+    pub fn new(model: Arc<dyn LocalTtsModel>, max_concurrent: usize) -> Self {
+        Self {
+            model,
+            permits: Semaphore::new(max_concurrent.max(1)),
+        }
+    }
+}
+
+#[async_trait]
+impl TtsEnginePort for LocalTtsEngine {
+    async fn infer(&self, request: InferRequest) -> Result<InferResponse, TtsError> {
+        let _permit = self
+            .permits
+            .acquire()
+            .await
+            .map_err(|e| TtsError::ServiceError(e.to_string()))?;
+
+        let model = self.model.clone();
+        let text = request.text.clone();
+        let mut config = request.tts_config.clone();
+        config.speed = request.params.speed.unwrap_or(config.speed);
+        let samples = tokio::task::spawn_blocking(move || model.synthesize(&text, &config))
+            .await
+            .map_err(|e| TtsError::ServiceError(format!("Local TTS task failed: {}", e)))??;
+
+        let sample_rate = self.model.sample_rate();
+        let duration_ms = samples.len() as u64 * 1000 / sample_rate.max(1) as u64;
+        tracing::debug!(
+            voice_id = %request.voice_id,
+            text_len = request.text.len(),
+            duration_ms,
+            "Local TTS inference completed"
+        );
+
+        Ok(InferResponse {
+            session_id: format!("local-{}", uuid::Uuid::new_v4()),
+            audio_data: pcm16_wav(&samples, sample_rate),
+            duration_ms: Some(duration_ms),
+            sample_rate: Some(sample_rate),
+        })
+    }
+
+    async fn health_check(&self) -> bool {
+        self.model.is_ready()
+    }
+}
+
+/// 将单声道 16 位 PCM 封装为 WAV
+fn pcm16_wav(samples: &[i16], sample_rate: u32) -> Vec<u8> {
+    let data_size = samples.len() * 2;
+    let mut wav = Vec::with_capacity(44 + data_size);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_size as u32).to_le_bytes());
+    wav.extend_from_slice(b"WAVE");
+    wav.extend_from_slice(b"fmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // 单声道
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    wav.extend_from_slice(&(sample_rate * 2).to_le_bytes()); // byte rate
+    wav.extend_from_slice(&2u16.to_le_bytes()); // block align
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&(data_size as u32).to_le_bytes());
+    for sample in samples {
+        wav.extend_from_slice(&sample.to_le_bytes());
+    }
+    wav
+}
+
+/// Piper 模型配置文件（`<model>.onnx.json`）中用到的字段
+#[derive(Debug, Deserialize)]
+struct PiperModelConfig {
+    audio: PiperAudioConfig,
+    /// 音素类型：`espeak`（需 espeak-ng 音素化）或 `text`（直接以字符为音素）
+    #[serde(default = "default_phoneme_type")]
+    phoneme_type: String,
+    phoneme_id_map: HashMap<String, Vec<i64>>,
+    #[serde(default)]
+    inference: PiperInferenceConfig,
+    #[serde(default)]
+    num_speakers: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct PiperAudioConfig {
+    sample_rate: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+struct PiperInferenceConfig {
+    noise_scale: f32,
+    length_scale: f32,
+    noise_w: f32,
+}
+
+impl Default for PiperInferenceConfig {
+    fn default() -> Self {
+        Self {
+            noise_scale: 0.667,
+            length_scale: 1.0,
+            noise_w: 0.8,
+        }
+    }
+}
+
+fn default_phoneme_type() -> String {
+    "espeak".to_string()
+}
+
+/// 音素序列的填充、起始、结束符
+const PAD: &str = "_";
+const BOS: &str = "^";
+const EOS: &str = "$";
+
+/// 加载 onnxruntime 动态库，未指定路径时按 `ORT_DYLIB_PATH` 或系统库搜索路径查找
+///
+/// 须在创建 [`PiperModel`] 之前调用
+pub fn init_onnxruntime(library: Option<&Path>) -> Result<(), TtsError> {
+    let builder = match library {
+        Some(path) => ort::init_from(path.display().to_string()),
+        None => ort::init(),
+    };
+    builder
+        .with_name("rovel")
+        .commit()
+        .map(|_| ())
+        .map_err(|e| TtsError::ServiceError(format!("Failed to initialize onnxruntime: {}", e)))
+}
+
+/// Piper（VITS）ONNX 模型，通过 onnxruntime 在进程内推理
+///
+/// 仅支持 `phoneme_type = "text"` 的模型（以小写字符为音素）；espeak 音素的模型
+/// 需要 espeak-ng 音素化，加载时拒绝。onnxruntime 会话不支持并发运行，
+/// 推理在会话锁内串行执行，单次推理的并行度由 onnxruntime 线程池决定
+pub struct PiperModel {
+    session: Mutex<Session>,
+    config: PiperModelConfig,
+    model_path: PathBuf,
+    /// 多说话人模型需要 `sid` 输入，固定使用 0 号说话人
+    multi_speaker: bool,
+}
+
+impl PiperModel {
+    /// 加载模型，配置从同目录的 `<model>.onnx.json` 读取
+    pub fn new(model_path: impl Into<PathBuf>) -> Result<Self, TtsError> {
+        let model_path = model_path.into();
+        if !model_path.exists() {
+            return Err(TtsError::ServiceError(format!(
+                "Piper model not found: {}",
+                model_path.display()
+            )));
+        }
+        let config_path = PathBuf::from(format!("{}.json", model_path.display()));
+        let config = load_model_config(&config_path)?;
+
+        let session = Session::builder()
+            .and_then(|builder| builder.commit_from_file(&model_path))
+            .map_err(|e| {
+                TtsError::ServiceError(format!(
+                    "Failed to load Piper model {}: {}",
+                    model_path.display(),
+                    e
+                ))
+            })?;
+        let multi_speaker =
+            config.num_speakers > 1 || session.inputs.iter().any(|input| input.name == "sid");
+
+        Ok(Self {
+            session: Mutex::new(session),
+            config,
+            model_path,
+            multi_speaker,
+        })
+    }
+
+    pub fn model_path(&self) -> &Path {
+        &self.model_path
+    }
+}
+
+/// 读取并校验模型配置
+fn load_model_config(config_path: &Path) -> Result<PiperModelConfig, TtsError> {
+    let config: PiperModelConfig = std::fs::read(config_path)
+        .map_err(|e| e.to_string())
+        .and_then(|data| serde_json::from_slice(&data).map_err(|e| e.to_string()))
+        .map_err(|e| {
+            TtsError::ServiceError(format!(
+                "Failed to load Piper model config {}: {}",
+                config_path.display(),
+                e
+            ))
+        })?;
+    if config.phoneme_type != "text" {
+        return Err(TtsError::ServiceError(format!(
+            "Piper model uses {} phonemes, only text-phoneme models can run in-process",
+            config.phoneme_type
+        )));
+    }
+    for symbol in [PAD, BOS, EOS] {
+        if !config.phoneme_id_map.contains_key(symbol) {
+            return Err(TtsError::ServiceError(format!(
+                "Piper model config {} has no id for '{}'",
+                config_path.display(),
+                symbol
+            )));
+        }
+    }
+    Ok(config)
+}
+
+/// 文本转换为音素 id 序列：`^ _ p1 _ p2 _ ... $`，模型不认识的字符跳过
+fn phoneme_ids(map: &HashMap<String, Vec<i64>>, text: &str) -> Vec<i64> {
+    let pad = &map[PAD];
+    let mut ids = map[BOS].clone();
+    ids.extend(pad);
+    let mut buf = [0u8; 4];
+    for c in text.chars().flat_map(char::to_lowercase) {
+        if let Some(phoneme) = map.get(c.encode_utf8(&mut buf) as &str) {
+            ids.extend(phoneme);
+            ids.extend(pad);
+        }
+    }
+    ids.extend(&map[EOS]);
+    ids
+}
+
+/// 浮点音频转换为 16 位 PCM（按峰值归一化，与 piper 一致）
+fn float_to_pcm16(audio: &[f32]) -> Vec<i16> {
+    let peak = audio.iter().fold(0.01f32, |peak, s| peak.max(s.abs()));
+    let scale = i16::MAX as f32 / peak;
+    audio
+        .iter()
+        .map(|s| (s * scale).clamp(i16::MIN as f32, i16::MAX as f32) as i16)
+        .collect()
+}
+
+impl LocalTtsModel for PiperModel {
+    fn sample_rate(&self) -> u32 {
+        self.config.audio.sample_rate
+    }
+
+    fn synthesize(&self, text: &str, config: &TtsConfig) -> Result<Vec<i16>, TtsError> {
+        let ids = phoneme_ids(&self.config.phoneme_id_map, text);
+        let len = ids.len();
+        let inference = &self.config.inference;
+        // length_scale 越大语速越慢
+        let scales = vec![
+            inference.noise_scale,
+            inference.length_scale / config.speed.max(0.1),
+            inference.noise_w,
+        ];
+
+        let infer_error = |e: ort::Error| TtsError::ServiceError(format!("Piper inference failed: {}", e));
+        let mut inputs = ort::inputs![
+            "input" => Tensor::from_array(([1usize, len], ids)).map_err(infer_error)?,
+            "input_lengths" => Tensor::from_array(([1usize], vec![len as i64])).map_err(infer_error)?,
+            "scales" => Tensor::from_array(([3usize], scales)).map_err(infer_error)?,
+        ];
+        if self.multi_speaker {
+            inputs.push((
+                "sid".into(),
+                Tensor::from_array(([1usize], vec![0i64])).map_err(infer_error)?.into(),
+            ));
+        }
+
+        let mut session = self
+            .session
+            .lock()
+            .map_err(|_| TtsError::ServiceError("Piper session poisoned".to_string()))?;
+        let outputs = session.run(inputs).map_err(infer_error)?;
+        let (_, audio) = outputs[0].try_extract_tensor::<f32>().map_err(infer_error)?;
+        Ok(float_to_pcm16(audio))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::MarkupType;
+
+    /// 每个字符输出 100 个样本
+    struct TestModel;
+
+    impl LocalTtsModel for TestModel {
+        fn sample_rate(&self) -> u32 {
+            1000
+        }
+
+        fn synthesize(&self, text: &str, _config: &TtsConfig) -> Result<Vec<i16>, TtsError> {
+            Ok(vec![1; text.chars().count() * 100])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_local_engine_returns_wav() {
+        let engine = LocalTtsEngine::new(Arc::new(TestModel), 1);
+        let response = engine
+            .infer(InferRequest {
+                text: "你好".to_string(),
+                voice_ref: String::new(),
+                voice_ref_path: None,
+                voice_id: "voice".to_string(),
+                prompt_text: None,
+                engine: None,
+                tts_config: TtsConfig::default(),
+                params: Default::default(),
+                seed: None,
+                latency_sensitive: false,
+                markup: MarkupType::PlainText,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(&response.audio_data[0..4], b"RIFF");
+        assert_eq!(response.audio_data.len(), 44 + 200 * 2);
+        assert_eq!(response.duration_ms, Some(200));
+        assert_eq!(response.sample_rate, Some(1000));
+    }
+
+    fn id_map() -> HashMap<String, Vec<i64>> {
+        [("_", 0), ("^", 1), ("$", 2), ("a", 3), ("b", 4), (" ", 5)]
+            .into_iter()
+            .map(|(symbol, id)| (symbol.to_string(), vec![id]))
+            .collect()
+    }
+
+    #[test]
+    fn test_phoneme_ids() {
+        // 大写转小写，模型不认识的字符跳过
+        assert_eq!(phoneme_ids(&id_map(), "Ab c"), vec![1, 0, 3, 0, 4, 0, 5, 0, 2]);
+        assert_eq!(phoneme_ids(&id_map(), ""), vec![1, 0, 2]);
+    }
+
+    #[test]
+    fn test_float_to_pcm16_normalizes_peak() {
+        assert_eq!(float_to_pcm16(&[0.5, -0.25, 0.0]), vec![i16::MAX, -16383, 0]);
+        // 近乎静音时不放大噪声
+        assert_eq!(float_to_pcm16(&[0.001]), vec![3276]);
+    }
+
+    #[test]
+    fn test_model_config_validation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("voice.onnx.json");
+        let write = |config: serde_json::Value| std::fs::write(&path, config.to_string()).unwrap();
+
+        write(serde_json::json!({
+            "audio": {"sample_rate": 22050},
+            "phoneme_type": "text",
+            "phoneme_id_map": {"_": [0], "^": [1], "$": [2]},
+        }));
+        let config = load_model_config(&path).unwrap();
+        assert_eq!(config.audio.sample_rate, 22050);
+        assert_eq!(config.inference.length_scale, 1.0);
+
+        // espeak 音素的模型无法在进程内音素化
+        write(serde_json::json!({
+            "audio": {"sample_rate": 22050},
+            "phoneme_id_map": {"_": [0], "^": [1], "$": [2]},
+        }));
+        assert!(load_model_config(&path).is_err());
+
+        write(serde_json::json!({
+            "audio": {"sample_rate": 22050},
+            "phoneme_type": "text",
+            "phoneme_id_map": {"a": [3]},
+        }));
+        assert!(load_model_config(&path).is_err());
+    }
+}
@@ -0,0 +1,330 @@
+//! TTS Metrics - TTS 请求指标
+//!
+//! `MeteredTtsEngine` 包装引擎，按引擎、操作（infer / infer_stream / infer_batch）记录：
+//! - 请求延迟直方图（流式推理为建立连接、开始返回音频的耗时）
+//! - 按 TtsError 类型分类的错误计数
+//! - 进行中请求数（流式推理持续到音频流结束）
+//!
+//! `TtsMetrics::render_prometheus` 以 Prometheus 文本格式输出（/api/admin/metrics）。
+
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use crate::application::ports::{
+    InferRequest, InferResponse, InferStream, TtsEnginePort, TtsError,
+};
+
+/// 延迟直方图的桶上界（秒）
+const LATENCY_BUCKETS: [f64; 10] = [0.25, 0.5, 1.0, 2.0, 5.0, 10.0, 20.0, 30.0, 60.0, 120.0];
+
+/// 推理操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Operation {
+    Infer,
+    InferStream,
+    InferBatch,
+}
+
+impl Operation {
+    const ALL: [Operation; 3] = [Self::Infer, Self::InferStream, Self::InferBatch];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Infer => "infer",
+            Self::InferStream => "infer_stream",
+            Self::InferBatch => "infer_batch",
+        }
+    }
+}
+
+/// 错误类型标签
+fn error_kind(error: &TtsError) -> &'static str {
+    match error {
+        TtsError::NetworkError(_) => "network",
+        TtsError::Timeout => "timeout",
+        TtsError::ServiceError(_) => "service",
+        TtsError::InvalidResponse(_) => "invalid_response",
+        TtsError::VoiceNotFound(_) => "voice_not_found",
+    }
+}
+
+const ERROR_KINDS: [&str; 5] = ["network", "timeout", "service", "invalid_response", "voice_not_found"];
+
+/// 单个操作的统计
+#[derive(Default)]
+struct OperationMetrics {
+    /// 各桶计数（非累积），最后一个为 +Inf
+    buckets: [AtomicU64; LATENCY_BUCKETS.len() + 1],
+    count: AtomicU64,
+    /// 延迟总和（微秒）
+    sum_micros: AtomicU64,
+    /// 各错误类型计数，顺序同 ERROR_KINDS
+    errors: [AtomicU64; ERROR_KINDS.len()],
+    inflight: AtomicI64,
+}
+
+impl OperationMetrics {
+    fn observe<T>(&self, started: Instant, result: &Result<T, TtsError>) {
+        let elapsed = started.elapsed();
+        let secs = elapsed.as_secs_f64();
+        let bucket = LATENCY_BUCKETS
+            .iter()
+            .position(|&bound| secs <= bound)
+            .unwrap_or(LATENCY_BUCKETS.len());
+        self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_micros
+            .fetch_add(elapsed.as_micros() as u64, Ordering::Relaxed);
+
+        if let Err(e) = result {
+            let kind = error_kind(e);
+            let index = ERROR_KINDS.iter().position(|&k| k == kind).unwrap();
+            self.errors[index].fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// 进行中请求计数守卫
+struct InflightGuard(Arc<EngineMetrics>, Operation);
+
+impl InflightGuard {
+    fn new(metrics: &Arc<EngineMetrics>, operation: Operation) -> Self {
+        metrics.get(operation).inflight.fetch_add(1, Ordering::Relaxed);
+        Self(metrics.clone(), operation)
+    }
+}
+
+impl Drop for InflightGuard {
+    fn drop(&mut self) {
+        self.0.get(self.1).inflight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// 单个引擎的指标
+#[derive(Default)]
+pub struct EngineMetrics {
+    infer: OperationMetrics,
+    infer_stream: OperationMetrics,
+    infer_batch: OperationMetrics,
+}
+
+impl EngineMetrics {
+    fn get(&self, operation: Operation) -> &OperationMetrics {
+        match operation {
+            Operation::Infer => &self.infer,
+            Operation::InferStream => &self.infer_stream,
+            Operation::InferBatch => &self.infer_batch,
+        }
+    }
+}
+
+/// TTS 指标注册表（按引擎名称）
+#[derive(Default)]
+pub struct TtsMetrics {
+    engines: Mutex<BTreeMap<String, Arc<EngineMetrics>>>,
+}
+
+impl TtsMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 获取（或创建）引擎的指标
+    pub fn engine(&self, name: &str) -> Arc<EngineMetrics> {
+        self.engines
+            .lock()
+            .unwrap()
+            .entry(name.to_string())
+            .or_default()
+            .clone()
+    }
+
+    /// 以 Prometheus 文本格式输出
+    pub fn render_prometheus(&self) -> String {
+        let engines = self.engines.lock().unwrap().clone();
+        let mut out = String::new();
+
+        out.push_str("# HELP rovel_tts_request_duration_seconds TTS request latency\n");
+        out.push_str("# TYPE rovel_tts_request_duration_seconds histogram\n");
+        for (engine, metrics) in &engines {
+            for operation in Operation::ALL {
+                let m = metrics.get(operation);
+                let labels = format!("engine=\"{}\",operation=\"{}\"", engine, operation.as_str());
+                let mut cumulative = 0;
+                for (i, bound) in LATENCY_BUCKETS.iter().enumerate() {
+                    cumulative += m.buckets[i].load(Ordering::Relaxed);
+                    let _ = writeln!(
+                        out,
+                        "rovel_tts_request_duration_seconds_bucket{{{},le=\"{}\"}} {}",
+                        labels, bound, cumulative
+                    );
+                }
+                let count = m.count.load(Ordering::Relaxed);
+                let _ = writeln!(
+                    out,
+                    "rovel_tts_request_duration_seconds_bucket{{{},le=\"+Inf\"}} {}",
+                    labels, count
+                );
+                let _ = writeln!(
+                    out,
+                    "rovel_tts_request_duration_seconds_sum{{{}}} {}",
+                    labels,
+                    m.sum_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0
+                );
+                let _ = writeln!(out, "rovel_tts_request_duration_seconds_count{{{}}} {}", labels, count);
+            }
+        }
+
+        out.push_str("# HELP rovel_tts_errors_total TTS request errors by error type\n");
+        out.push_str("# TYPE rovel_tts_errors_total counter\n");
+        for (engine, metrics) in &engines {
+            for operation in Operation::ALL {
+                let m = metrics.get(operation);
+                for (i, kind) in ERROR_KINDS.iter().enumerate() {
+                    let _ = writeln!(
+                        out,
+                        "rovel_tts_errors_total{{engine=\"{}\",operation=\"{}\",kind=\"{}\"}} {}",
+                        engine,
+                        operation.as_str(),
+                        kind,
+                        m.errors[i].load(Ordering::Relaxed)
+                    );
+                }
+            }
+        }
+
+        out.push_str("# HELP rovel_tts_inflight_requests TTS requests in progress\n");
+        out.push_str("# TYPE rovel_tts_inflight_requests gauge\n");
+        for (engine, metrics) in &engines {
+            for operation in Operation::ALL {
+                let _ = writeln!(
+                    out,
+                    "rovel_tts_inflight_requests{{engine=\"{}\",operation=\"{}\"}} {}",
+                    engine,
+                    operation.as_str(),
+                    metrics.get(operation).inflight.load(Ordering::Relaxed)
+                );
+            }
+        }
+
+        out
+    }
+}
+
+/// 记录指标的引擎包装
+pub struct MeteredTtsEngine {
+    inner: Arc<dyn TtsEnginePort>,
+    metrics: Arc<EngineMetrics>,
+}
+
+impl MeteredTtsEngine {
+    pub fn new(inner: Arc<dyn TtsEnginePort>, metrics: Arc<EngineMetrics>) -> Self {
+        Self { inner, metrics }
+    }
+}
+
+#[async_trait]
+impl TtsEnginePort for MeteredTtsEngine {
+    async fn infer(&self, request: InferRequest) -> Result<InferResponse, TtsError> {
+        let _guard = InflightGuard::new(&self.metrics, Operation::Infer);
+        let started = Instant::now();
+        let result = self.inner.infer(request).await;
+        self.metrics.infer.observe(started, &result);
+        result
+    }
+
+    async fn infer_stream(&self, request: InferRequest) -> Result<InferStream, TtsError> {
+        let guard = InflightGuard::new(&self.metrics, Operation::InferStream);
+        let started = Instant::now();
+        let result = self.inner.infer_stream(request).await;
+        self.metrics.infer_stream.observe(started, &result);
+        let stream = result?;
+        Ok(InferStream {
+            chunks: stream
+                .chunks
+                .map(move |chunk| {
+                    let _ = &guard;
+                    chunk
+                })
+                .boxed(),
+            ..stream
+        })
+    }
+
+    async fn infer_batch(&self, requests: Vec<InferRequest>) -> Result<Vec<InferResponse>, TtsError> {
+        let _guard = InflightGuard::new(&self.metrics, Operation::InferBatch);
+        let started = Instant::now();
+        let result = self.inner.infer_batch(requests).await;
+        self.metrics.infer_batch.observe(started, &result);
+        result
+    }
+
+    async fn health_check(&self) -> bool {
+        self.inner.health_check().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::MarkupType;
+
+    /// 按文本返回成功或超时的测试引擎
+    struct TestEngine;
+
+    #[async_trait]
+    impl TtsEnginePort for TestEngine {
+        async fn infer(&self, request: InferRequest) -> Result<InferResponse, TtsError> {
+            if request.text == "timeout" {
+                return Err(TtsError::Timeout);
+            }
+            Ok(InferResponse {
+                session_id: String::new(),
+                audio_data: Vec::new(),
+                duration_ms: None,
+                sample_rate: None,
+            })
+        }
+    }
+
+    fn request(text: &str) -> InferRequest {
+        InferRequest {
+            text: text.to_string(),
+            voice_ref: "http://localhost/voice.wav".to_string(),
+            voice_ref_path: None,
+            voice_id: "voice".to_string(),
+            prompt_text: None,
+            engine: None,
+            tts_config: Default::default(),
+            params: Default::default(),
+            seed: None,
+            latency_sensitive: false,
+            markup: MarkupType::PlainText,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_records_latency_and_errors() {
+        let metrics = TtsMetrics::new();
+        let engine = MeteredTtsEngine::new(Arc::new(TestEngine), metrics.engine("default"));
+
+        engine.infer(request("你好")).await.unwrap();
+        assert!(engine.infer(request("timeout")).await.is_err());
+
+        let text = metrics.render_prometheus();
+        assert!(text.contains(
+            "rovel_tts_request_duration_seconds_count{engine=\"default\",operation=\"infer\"} 2"
+        ));
+        assert!(text.contains(
+            "rovel_tts_errors_total{engine=\"default\",operation=\"infer\",kind=\"timeout\"} 1"
+        ));
+        assert!(text.contains(
+            "rovel_tts_inflight_requests{engine=\"default\",operation=\"infer\"} 0"
+        ));
+    }
+}
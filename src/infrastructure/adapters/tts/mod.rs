@@ -1,7 +1,23 @@
-//! TTS Adapter - HTTP TTS 客户端实现
+//! TTS Adapter - HTTP TTS 客户端实现、多后端负载均衡、对冲请求、SSML 生成、请求指标、限流及多引擎注册表
 
 mod fake_tts_client;
+mod hedge;
 mod http_tts_client;
+#[cfg(feature = "local-tts")]
+mod local;
+mod metrics;
+mod pool;
+mod rate_limit;
+mod registry;
+mod ssml;
 
 pub use fake_tts_client::{FakeTtsClient, FakeTtsClientConfig};
+pub use hedge::HedgedTtsEngine;
 pub use http_tts_client::*;
+#[cfg(feature = "local-tts")]
+pub use local::{init_onnxruntime, LocalTtsEngine, LocalTtsModel, PiperModel};
+pub use metrics::{EngineMetrics, MeteredTtsEngine, TtsMetrics};
+pub use pool::{BackendStatus, BalanceStrategy, TtsBackendPool, TtsBackendPoolConfig};
+pub use rate_limit::{RateLimitConfig, RateLimitedTtsEngine};
+pub use registry::{TtsEngineRegistry, DEFAULT_TTS_ENGINE};
+pub use ssml::SsmlTtsEngine;
@@ -0,0 +1,384 @@
+//! TTS Backend Pool - 多后端负载均衡
+//!
+//! 同一 TTS 引擎配置多个服务地址时，将 infer 请求分发到多个后端：
+//! - round_robin: 轮询
+//! - least_inflight: 选择当前进行中请求最少的后端
+//!
+//! 后端连续失败达到阈值后进入冷却期，冷却期内不参与调度；
+//! 冷却结束后重新参与调度，成功一次即恢复健康。
+//! 单次请求失败时自动切换到其他后端重试。
+
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::application::ports::{
+    InferRequest, InferResponse, InferStream, TtsEnginePort, TtsError,
+};
+
+/// 负载均衡策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BalanceStrategy {
+    /// 轮询
+    #[default]
+    RoundRobin,
+    /// 最少进行中请求
+    LeastInflight,
+}
+
+/// 后端池配置
+#[derive(Debug, Clone)]
+pub struct TtsBackendPoolConfig {
+    pub strategy: BalanceStrategy,
+    /// 连续失败多少次后标记为不健康
+    pub failure_threshold: u32,
+    /// 不健康后端的冷却时间
+    pub cooldown: Duration,
+}
+
+impl Default for TtsBackendPoolConfig {
+    fn default() -> Self {
+        Self {
+            strategy: BalanceStrategy::RoundRobin,
+            failure_threshold: 3,
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+/// 单个后端及其运行状态
+struct Backend {
+    name: String,
+    engine: Arc<dyn TtsEnginePort>,
+    inflight: Arc<AtomicUsize>,
+    consecutive_failures: AtomicU32,
+    /// 冷却截止时间（None 表示健康）
+    unhealthy_until: Mutex<Option<Instant>>,
+}
+
+impl Backend {
+    fn is_available(&self, now: Instant) -> bool {
+        match *self.unhealthy_until.lock().unwrap() {
+            Some(until) => now >= until,
+            None => true,
+        }
+    }
+
+    fn record_success(&self) {
+        self.consecutive_failures.store(0, Ordering::Relaxed);
+        *self.unhealthy_until.lock().unwrap() = None;
+    }
+
+    fn record_failure(&self, config: &TtsBackendPoolConfig) {
+        let failures = self.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        if failures >= config.failure_threshold {
+            *self.unhealthy_until.lock().unwrap() = Some(Instant::now() + config.cooldown);
+            tracing::warn!(
+                backend = %self.name,
+                failures,
+                cooldown_secs = config.cooldown.as_secs(),
+                "TTS backend marked unhealthy"
+            );
+        }
+    }
+}
+
+/// 进行中请求计数守卫
+struct InflightGuard(Arc<AtomicUsize>);
+
+impl InflightGuard {
+    fn new(counter: &Arc<AtomicUsize>) -> Self {
+        counter.fetch_add(1, Ordering::Relaxed);
+        Self(counter.clone())
+    }
+}
+
+impl Drop for InflightGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// 后端状态快照
+#[derive(Debug, Clone)]
+pub struct BackendStatus {
+    pub name: String,
+    pub inflight: usize,
+    pub consecutive_failures: u32,
+    pub healthy: bool,
+}
+
+/// TTS 后端池
+pub struct TtsBackendPool {
+    backends: Vec<Backend>,
+    config: TtsBackendPoolConfig,
+    next: AtomicUsize,
+}
+
+impl TtsBackendPool {
+    /// 创建后端池，`backends` 为 (名称, 引擎) 列表
+    pub fn new(
+        backends: Vec<(String, Arc<dyn TtsEnginePort>)>,
+        config: TtsBackendPoolConfig,
+    ) -> Self {
+        let backends = backends
+            .into_iter()
+            .map(|(name, engine)| Backend {
+                name,
+                engine,
+                inflight: Arc::new(AtomicUsize::new(0)),
+                consecutive_failures: AtomicU32::new(0),
+                unhealthy_until: Mutex::new(None),
+            })
+            .collect();
+        Self {
+            backends,
+            config,
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    /// 各后端状态
+    pub fn status(&self) -> Vec<BackendStatus> {
+        let now = Instant::now();
+        self.backends
+            .iter()
+            .map(|b| BackendStatus {
+                name: b.name.clone(),
+                inflight: b.inflight.load(Ordering::Relaxed),
+                consecutive_failures: b.consecutive_failures.load(Ordering::Relaxed),
+                healthy: b.is_available(now),
+            })
+            .collect()
+    }
+
+    /// 选择后端（排除本次请求已尝试过的）；所有后端都不健康时仍在未尝试的后端中选择
+    fn select(&self, tried: &[usize]) -> Option<usize> {
+        let now = Instant::now();
+        let untried: Vec<usize> = (0..self.backends.len())
+            .filter(|i| !tried.contains(i))
+            .collect();
+        if untried.is_empty() {
+            return None;
+        }
+        let healthy: Vec<usize> = untried
+            .iter()
+            .copied()
+            .filter(|&i| self.backends[i].is_available(now))
+            .collect();
+        let candidates = if healthy.is_empty() { untried } else { healthy };
+
+        let chosen = match self.config.strategy {
+            BalanceStrategy::RoundRobin => {
+                let n = self.next.fetch_add(1, Ordering::Relaxed);
+                candidates[n % candidates.len()]
+            }
+            BalanceStrategy::LeastInflight => *candidates
+                .iter()
+                .min_by_key(|&&i| self.backends[i].inflight.load(Ordering::Relaxed))
+                .unwrap(),
+        };
+        Some(chosen)
+    }
+}
+
+/// 是否为后端自身的故障（计入健康统计并切换后端重试）
+fn is_backend_failure(error: &TtsError) -> bool {
+    !matches!(error, TtsError::VoiceNotFound(_))
+}
+
+impl TtsBackendPool {
+    /// 选择后端执行请求，后端故障时切换到其他后端重试
+    async fn dispatch<T, F, Fut>(&self, request: InferRequest, call: F) -> Result<T, TtsError>
+    where
+        F: Fn(Arc<dyn TtsEnginePort>, InferRequest, InflightGuard) -> Fut,
+        Fut: Future<Output = Result<T, TtsError>>,
+    {
+        let mut tried = Vec::new();
+        let mut last_error = None;
+
+        while let Some(index) = self.select(&tried) {
+            tried.push(index);
+            let backend = &self.backends[index];
+            let guard = InflightGuard::new(&backend.inflight);
+
+            match call(backend.engine.clone(), request.clone(), guard).await {
+                Ok(result) => {
+                    backend.record_success();
+                    return Ok(result);
+                }
+                Err(e) if is_backend_failure(&e) => {
+                    tracing::warn!(backend = %backend.name, error = %e, "TTS backend request failed");
+                    backend.record_failure(&self.config);
+                    last_error = Some(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(last_error
+            .unwrap_or_else(|| TtsError::ServiceError("No TTS backend configured".to_string())))
+    }
+}
+
+#[async_trait]
+impl TtsEnginePort for TtsBackendPool {
+    async fn infer(&self, request: InferRequest) -> Result<InferResponse, TtsError> {
+        self.dispatch(request, |engine, request, _guard| async move {
+            engine.infer(request).await
+        })
+        .await
+    }
+
+    /// 整批发送到同一后端，失败时整批切换后端
+    async fn infer_batch(&self, requests: Vec<InferRequest>) -> Result<Vec<InferResponse>, TtsError> {
+        let Some(first) = requests.first().cloned() else {
+            return Ok(Vec::new());
+        };
+        self.dispatch(first, |engine, _request, _guard| {
+            let requests = requests.clone();
+            async move { engine.infer_batch(requests).await }
+        })
+        .await
+    }
+
+    /// 流式推理只在建立连接前切换后端；进行中计数持续到音频流结束
+    async fn infer_stream(&self, request: InferRequest) -> Result<InferStream, TtsError> {
+        self.dispatch(request, |engine, request, guard| async move {
+            let stream = engine.infer_stream(request).await?;
+            Ok(InferStream {
+                chunks: stream
+                    .chunks
+                    .map(move |chunk| {
+                        let _ = &guard;
+                        chunk
+                    })
+                    .boxed(),
+                ..stream
+            })
+        })
+        .await
+    }
+
+    /// 任一后端可用即视为可用
+    async fn health_check(&self) -> bool {
+        for backend in &self.backends {
+            if backend.engine.health_check().await {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::MarkupType;
+    use crate::domain::voice::TtsConfig;
+    use std::sync::atomic::AtomicBool;
+
+    /// 返回后端名称作为 session_id 的测试引擎
+    struct TestBackend {
+        name: &'static str,
+        failing: AtomicBool,
+    }
+
+    impl TestBackend {
+        fn new(name: &'static str, failing: bool) -> Arc<Self> {
+            Arc::new(Self {
+                name,
+                failing: AtomicBool::new(failing),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl TtsEnginePort for TestBackend {
+        async fn infer(&self, _request: InferRequest) -> Result<InferResponse, TtsError> {
+            if self.failing.load(Ordering::Relaxed) {
+                return Err(TtsError::NetworkError("down".to_string()));
+            }
+            Ok(InferResponse {
+                session_id: self.name.to_string(),
+                audio_data: Vec::new(),
+                duration_ms: None,
+                sample_rate: None,
+            })
+        }
+    }
+
+    fn request() -> InferRequest {
+        InferRequest {
+            text: "你好".to_string(),
+            voice_ref: "http://localhost/voice.wav".to_string(),
+            voice_ref_path: None,
+            voice_id: "voice".to_string(),
+            prompt_text: None,
+            engine: None,
+            tts_config: TtsConfig::default(),
+            params: Default::default(),
+            seed: None,
+            latency_sensitive: false,
+            markup: MarkupType::PlainText,
+        }
+    }
+
+    fn pool(backends: &[Arc<TestBackend>], config: TtsBackendPoolConfig) -> TtsBackendPool {
+        TtsBackendPool::new(
+            backends
+                .iter()
+                .map(|b| (b.name.to_string(), b.clone() as Arc<dyn TtsEnginePort>))
+                .collect(),
+            config,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_round_robin() {
+        let pool = pool(
+            &[TestBackend::new("a", false), TestBackend::new("b", false)],
+            TtsBackendPoolConfig::default(),
+        );
+
+        let mut served = Vec::new();
+        for _ in 0..4 {
+            served.push(pool.infer(request()).await.unwrap().session_id);
+        }
+        assert_eq!(served, vec!["a", "b", "a", "b"]);
+    }
+
+    #[tokio::test]
+    async fn test_failover_and_health_tracking() {
+        let a = TestBackend::new("a", true);
+        let b = TestBackend::new("b", false);
+        let pool = pool(
+            &[a.clone(), b.clone()],
+            TtsBackendPoolConfig {
+                failure_threshold: 1,
+                cooldown: Duration::from_secs(60),
+                ..Default::default()
+            },
+        );
+
+        // a 失败后切换到 b，并将 a 标记为不健康
+        assert_eq!(pool.infer(request()).await.unwrap().session_id, "b");
+        let status = pool.status();
+        assert!(!status[0].healthy);
+        assert!(status[1].healthy);
+
+        // 冷却期内只调度到 b
+        for _ in 0..3 {
+            assert_eq!(pool.infer(request()).await.unwrap().session_id, "b");
+        }
+
+        // 全部失败时返回最后一个错误
+        b.failing.store(true, Ordering::Relaxed);
+        assert!(pool.infer(request()).await.is_err());
+    }
+}
@@ -0,0 +1,147 @@
+//! Rate Limited TTS Engine - TTS 请求限流
+//!
+//! 按引擎限制每分钟请求数（令牌桶，允许不超过一分钟配额的突发）和同时进行的请求数，
+//! 保护有严格配额的共享/云端 TTS 服务。超出限制的请求在发送前等待，
+//! Worker 取消任务时等待随之结束。批量推理计为一次请求；流式推理持续到音频流结束。
+
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time::Instant;
+
+use crate::application::ports::{
+    InferRequest, InferResponse, InferStream, TtsEnginePort, TtsError,
+};
+
+/// 限流配置（0 表示不限制）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimitConfig {
+    /// 每分钟最多请求数
+    pub requests_per_minute: u32,
+    /// 同时进行的最多请求数
+    pub max_concurrent: usize,
+}
+
+impl RateLimitConfig {
+    pub fn is_unlimited(&self) -> bool {
+        self.requests_per_minute == 0 && self.max_concurrent == 0
+    }
+}
+
+/// 令牌桶
+struct TokenBucket {
+    capacity: f64,
+    /// 每秒补充的令牌数
+    rate: f64,
+    state: Mutex<(f64, Instant)>,
+}
+
+impl TokenBucket {
+    fn new(requests_per_minute: u32) -> Self {
+        let capacity = requests_per_minute as f64;
+        Self {
+            capacity,
+            rate: capacity / 60.0,
+            state: Mutex::new((capacity, Instant::now())),
+        }
+    }
+
+    /// 取出一个令牌，不足时返回需要等待的时间
+    fn try_take(&self) -> Result<(), Duration> {
+        let mut state = self.state.lock().unwrap();
+        let (tokens, last) = *state;
+        let now = Instant::now();
+        let tokens = (tokens + now.duration_since(last).as_secs_f64() * self.rate).min(self.capacity);
+        if tokens >= 1.0 {
+            *state = (tokens - 1.0, now);
+            Ok(())
+        } else {
+            *state = (tokens, now);
+            Err(Duration::from_secs_f64((1.0 - tokens) / self.rate))
+        }
+    }
+
+    async fn take(&self) {
+        while let Err(wait) = self.try_take() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// 限流 TTS 引擎
+pub struct RateLimitedTtsEngine {
+    inner: Arc<dyn TtsEnginePort>,
+    bucket: Option<TokenBucket>,
+    permits: Option<Arc<Semaphore>>,
+}
+
+impl RateLimitedTtsEngine {
+    pub fn new(inner: Arc<dyn TtsEnginePort>, config: RateLimitConfig) -> Self {
+        Self {
+            inner,
+            bucket: (config.requests_per_minute > 0).then(|| TokenBucket::new(config.requests_per_minute)),
+            permits: (config.max_concurrent > 0).then(|| Arc::new(Semaphore::new(config.max_concurrent))),
+        }
+    }
+
+    /// 等待并发槽位和请求配额
+    async fn acquire(&self) -> Option<OwnedSemaphorePermit> {
+        let permit = match &self.permits {
+            Some(permits) => permits.clone().acquire_owned().await.ok(),
+            None => None,
+        };
+        if let Some(bucket) = &self.bucket {
+            bucket.take().await;
+        }
+        permit
+    }
+}
+
+#[async_trait]
+impl TtsEnginePort for RateLimitedTtsEngine {
+    async fn infer(&self, request: InferRequest) -> Result<InferResponse, TtsError> {
+        let _permit = self.acquire().await;
+        self.inner.infer(request).await
+    }
+
+    async fn infer_stream(&self, request: InferRequest) -> Result<InferStream, TtsError> {
+        let permit = self.acquire().await;
+        let stream = self.inner.infer_stream(request).await?;
+        Ok(InferStream {
+            chunks: stream
+                .chunks
+                .map(move |chunk| {
+                    let _ = &permit;
+                    chunk
+                })
+                .boxed(),
+            ..stream
+        })
+    }
+
+    async fn infer_batch(&self, requests: Vec<InferRequest>) -> Result<Vec<InferResponse>, TtsError> {
+        let _permit = self.acquire().await;
+        self.inner.infer_batch(requests).await
+    }
+
+    async fn health_check(&self) -> bool {
+        self.inner.health_check().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requests_per_minute() {
+        let bucket = TokenBucket::new(2);
+        assert!(bucket.try_take().is_ok());
+        assert!(bucket.try_take().is_ok());
+        // 突发用完后按每 30 秒一个令牌补充
+        let wait = bucket.try_take().unwrap_err();
+        assert!(wait > Duration::from_secs(29) && wait <= Duration::from_secs(30));
+    }
+}
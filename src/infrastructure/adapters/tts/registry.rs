@@ -0,0 +1,149 @@
+//! TTS Engine Registry - 多 TTS 引擎注册表
+//!
+//! 持有多个命名的 TtsEnginePort 实现，按 InferRequest.engine 分发请求，
+//! 使同一部署中的不同音色可以由不同的 TTS 服务合成。
+
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::application::ports::{
+    InferRequest, InferResponse, InferStream, TtsEnginePort, TtsError,
+};
+
+/// 默认引擎名称（对应 `[tts]` 顶层配置）
+pub const DEFAULT_TTS_ENGINE: &str = "default";
+
+/// TTS 引擎注册表
+pub struct TtsEngineRegistry {
+    engines: BTreeMap<String, Arc<dyn TtsEnginePort>>,
+}
+
+impl TtsEngineRegistry {
+    /// 以默认引擎创建注册表
+    pub fn new(default_engine: Arc<dyn TtsEnginePort>) -> Self {
+        let mut engines = BTreeMap::new();
+        engines.insert(DEFAULT_TTS_ENGINE.to_string(), default_engine);
+        Self { engines }
+    }
+
+    /// 注册命名引擎（同名覆盖）
+    pub fn with_engine(mut self, name: impl Into<String>, engine: Arc<dyn TtsEnginePort>) -> Self {
+        self.engines.insert(name.into(), engine);
+        self
+    }
+
+    /// 获取引擎，None 表示默认引擎
+    pub fn get(&self, name: Option<&str>) -> Option<Arc<dyn TtsEnginePort>> {
+        self.engines
+            .get(name.unwrap_or(DEFAULT_TTS_ENGINE))
+            .cloned()
+    }
+
+    /// 是否已注册该引擎
+    pub fn contains(&self, name: &str) -> bool {
+        self.engines.contains_key(name)
+    }
+
+    /// 所有引擎名称（按名称排序）
+    pub fn names(&self) -> Vec<String> {
+        self.engines.keys().cloned().collect()
+    }
+
+    /// 按请求的 engine 字段选择引擎
+    fn engine_for(&self, request: &InferRequest) -> Result<Arc<dyn TtsEnginePort>, TtsError> {
+        self.get(request.engine.as_deref()).ok_or_else(|| {
+            TtsError::ServiceError(format!(
+                "Unknown TTS engine: {}",
+                request.engine.as_deref().unwrap_or(DEFAULT_TTS_ENGINE)
+            ))
+        })
+    }
+}
+
+#[async_trait]
+impl TtsEnginePort for TtsEngineRegistry {
+    async fn infer(&self, request: InferRequest) -> Result<InferResponse, TtsError> {
+        self.engine_for(&request)?.infer(request).await
+    }
+
+    async fn infer_stream(&self, request: InferRequest) -> Result<InferStream, TtsError> {
+        self.engine_for(&request)?.infer_stream(request).await
+    }
+
+    /// 同一引擎的请求整批转发，混合引擎时逐个推理
+    async fn infer_batch(&self, requests: Vec<InferRequest>) -> Result<Vec<InferResponse>, TtsError> {
+        let Some(first) = requests.first() else {
+            return Ok(Vec::new());
+        };
+        if requests.iter().all(|r| r.engine == first.engine) {
+            return self.engine_for(first)?.infer_batch(requests).await;
+        }
+
+        let mut responses = Vec::with_capacity(requests.len());
+        for request in requests {
+            responses.push(self.infer(request).await?);
+        }
+        Ok(responses)
+    }
+
+    /// 默认引擎是否可用
+    async fn health_check(&self) -> bool {
+        match self.get(None) {
+            Some(engine) => engine.health_check().await,
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::MarkupType;
+    use crate::domain::voice::TtsConfig;
+
+    /// 返回固定 session_id 的测试引擎
+    struct NamedEngine(&'static str);
+
+    #[async_trait]
+    impl TtsEnginePort for NamedEngine {
+        async fn infer(&self, _request: InferRequest) -> Result<InferResponse, TtsError> {
+            Ok(InferResponse {
+                session_id: self.0.to_string(),
+                audio_data: Vec::new(),
+                duration_ms: None,
+                sample_rate: None,
+            })
+        }
+    }
+
+    fn request(engine: Option<&str>) -> InferRequest {
+        InferRequest {
+            text: "你好".to_string(),
+            voice_ref: "http://localhost/voice.wav".to_string(),
+            voice_ref_path: None,
+            voice_id: "voice".to_string(),
+            prompt_text: None,
+            engine: engine.map(str::to_string),
+            tts_config: TtsConfig::default(),
+            params: Default::default(),
+            seed: None,
+            latency_sensitive: false,
+            markup: MarkupType::PlainText,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_registry_routes_by_engine() {
+        let registry = TtsEngineRegistry::new(Arc::new(NamedEngine("default")))
+            .with_engine("cosyvoice", Arc::new(NamedEngine("cosyvoice")));
+
+        assert_eq!(registry.infer(request(None)).await.unwrap().session_id, "default");
+        assert_eq!(
+            registry.infer(request(Some("cosyvoice"))).await.unwrap().session_id,
+            "cosyvoice"
+        );
+        assert!(registry.infer(request(Some("missing"))).await.is_err());
+        assert_eq!(registry.names(), vec!["cosyvoice", "default"]);
+    }
+}
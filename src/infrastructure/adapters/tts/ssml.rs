@@ -0,0 +1,52 @@
+//! SSML TTS Engine - 为支持 SSML 的引擎生成韵律标记
+//!
+//! 包装内部引擎，将纯文本请求转换为 SSML（段落停顿、对话强调）后转发，
+//! 并将请求标记为 `MarkupType::Ssml`。已是 SSML 的请求原样转发。
+
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::application::ports::{
+    InferRequest, InferResponse, InferStream, MarkupType, TtsEnginePort, TtsError,
+};
+use crate::domain::novel::{to_ssml, SsmlOptions};
+
+/// SSML 生成引擎包装
+pub struct SsmlTtsEngine {
+    inner: Arc<dyn TtsEnginePort>,
+    options: SsmlOptions,
+}
+
+impl SsmlTtsEngine {
+    pub fn new(inner: Arc<dyn TtsEnginePort>, options: SsmlOptions) -> Self {
+        Self { inner, options }
+    }
+
+    fn convert(&self, mut request: InferRequest) -> InferRequest {
+        if request.markup == MarkupType::PlainText {
+            request.text = to_ssml(&request.text, &self.options);
+            request.markup = MarkupType::Ssml;
+        }
+        request
+    }
+}
+
+#[async_trait]
+impl TtsEnginePort for SsmlTtsEngine {
+    async fn infer(&self, request: InferRequest) -> Result<InferResponse, TtsError> {
+        self.inner.infer(self.convert(request)).await
+    }
+
+    async fn infer_stream(&self, request: InferRequest) -> Result<InferStream, TtsError> {
+        self.inner.infer_stream(self.convert(request)).await
+    }
+
+    async fn infer_batch(&self, requests: Vec<InferRequest>) -> Result<Vec<InferResponse>, TtsError> {
+        let requests = requests.into_iter().map(|r| self.convert(r)).collect();
+        self.inner.infer_batch(requests).await
+    }
+
+    async fn health_check(&self) -> bool {
+        self.inner.health_check().await
+    }
+}
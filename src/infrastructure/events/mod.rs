@@ -2,4 +2,4 @@
 
 mod publisher;
 
-pub use publisher::{EventPublisher, WsEvent};
+pub use publisher::{EventPublisher, GlobalEvent, WsEvent};
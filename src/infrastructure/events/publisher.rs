@@ -2,7 +2,8 @@
 //!
 //! WebSocket 事件推送实现
 
-use crate::application::ports::TaskState;
+use crate::application::access::can_access;
+use crate::application::ports::{InferenceTask, PregenerationJob, TaskState};
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -23,12 +24,43 @@ pub enum WsEvent {
         duration_ms: Option<u64>,
         #[serde(skip_serializing_if = "Option::is_none")]
         error: Option<String>,
+        /// 提交任务的 HTTP 请求 ID
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        request_id: Option<String>,
+    },
+    /// 任务排队中：前面还有 `position` 个任务，`eta_ms` 为按最近平均推理耗时估算的完成时间
+    TaskQueued {
+        session_id: String,
+        task_id: String,
+        segment_index: u32,
+        position: usize,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        eta_ms: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        request_id: Option<String>,
     },
     /// 会话关闭
     SessionClosed {
         session_id: String,
         reason: String,
     },
+    /// 会话被其他设备接管（收到后当前连接随即关闭）
+    SessionTransferred {
+        session_id: String,
+    },
+    /// 自动前进模式：当前片段之后的片段已就绪
+    NextSegmentReady {
+        session_id: String,
+        segment_index: u32,
+        /// 可直接播放的音频地址（相对路径）
+        audio_url: String,
+    },
+    /// 切换音色的过渡结束：新音色的当前片段已就绪，之后不再返回旧音色的音频
+    VoiceHandoffCompleted {
+        session_id: String,
+        voice_id: Uuid,
+        segment_index: u32,
+    },
     /// Novel 处理完成
     NovelReady {
         novel_id: Uuid,
@@ -53,18 +85,92 @@ pub enum WsEvent {
         novel_id: Uuid,
         error: String,
     },
+    /// Voice 更新完成
+    VoiceUpdated {
+        voice_id: Uuid,
+    },
     /// Voice 删除完成
     VoiceDeleted {
         voice_id: Uuid,
     },
+    /// TTS 引擎健康检查失败（不可用）
+    TtsEngineDown {
+        engine: String,
+        consecutive_failures: u32,
+    },
+    /// TTS 引擎恢复可用
+    TtsEngineUp {
+        engine: String,
+    },
+    /// 有声书导出进度
+    ExportProgress {
+        job_id: String,
+        novel_id: Uuid,
+        state: String,
+        completed_segments: usize,
+        total_segments: usize,
+    },
+    /// 有声书导出完成
+    ExportReady {
+        job_id: String,
+        novel_id: Uuid,
+        download_url: String,
+    },
+    /// 有声书导出失败
+    ExportFailed {
+        job_id: String,
+        novel_id: Uuid,
+        error: String,
+    },
+    /// 整书预生成进度（含暂停、完成、取消等状态变化）
+    PregenerationProgress {
+        job_id: String,
+        novel_id: Uuid,
+        state: String,
+        completed_segments: usize,
+        failed_segments: usize,
+        total_segments: usize,
+    },
+    /// 一轮 GC 完成（有清理内容时发送）
+    GcCompleted {
+        /// 关闭的空闲会话数
+        closed_sessions: u64,
+        /// 清理的会话存储（记录或目录）数
+        cleaned_sessions: u64,
+        deleted_files: u64,
+        freed_bytes: u64,
+    },
+    /// 小说的音频缓存达到配额（之后写入时淘汰该小说自己的旧片段）
+    NovelCacheQuotaReached {
+        novel_id: Uuid,
+        used_bytes: u64,
+        quota_bytes: u64,
+    },
+}
+
+/// 全局事件及其所属用户
+///
+/// 启用用户账号时，全局 WebSocket 只转发当前用户有权访问的事件；
+/// `owner` 为 None（TTS 引擎状态、GC、无所属用户的资源）时所有连接可见
+#[derive(Debug, Clone)]
+pub struct GlobalEvent {
+    pub owner: Option<Uuid>,
+    pub event: WsEvent,
+}
+
+impl GlobalEvent {
+    /// 用户能否收到该事件（与读取资源的归属规则一致）
+    pub fn visible_to(&self, user_id: Option<Uuid>) -> bool {
+        can_access(self.owner, user_id)
+    }
 }
 
 /// 事件发布器
 pub struct EventPublisher {
     /// session_id -> broadcast sender (for session-specific events)
     session_channels: DashMap<String, broadcast::Sender<WsEvent>>,
-    /// Global broadcast channel for novel events (NovelReady/NovelFailed)
-    global_channel: broadcast::Sender<WsEvent>,
+    /// Global broadcast channel for novel/voice/job events
+    global_channel: broadcast::Sender<GlobalEvent>,
 }
 
 impl EventPublisher {
@@ -81,10 +187,16 @@ impl EventPublisher {
     }
 
     /// 订阅全局事件（NovelReady/NovelFailed）
-    pub fn subscribe_global(&self) -> broadcast::Receiver<WsEvent> {
+    pub fn subscribe_global(&self) -> broadcast::Receiver<GlobalEvent> {
         self.global_channel.subscribe()
     }
 
+    fn send_global(&self, owner: Option<Uuid>, event: WsEvent) -> Result<usize, broadcast::error::SendError<()>> {
+        self.global_channel
+            .send(GlobalEvent { owner, event })
+            .map_err(|_| broadcast::error::SendError(()))
+    }
+
     /// 注册会话的事件通道
     pub fn register_session(&self, session_id: &str) -> broadcast::Receiver<WsEvent> {
         if let Some(sender) = self.session_channels.get(session_id) {
@@ -101,102 +213,126 @@ impl EventPublisher {
         self.session_channels.remove(session_id);
     }
 
+    /// 连接断开时释放会话通道；通道已被替换（会话被其他设备接管后重新注册）时保留
+    pub fn release_session(&self, session_id: &str, channel: &broadcast::Receiver<WsEvent>) {
+        self.session_channels
+            .remove_if(session_id, |_, sender| sender.subscribe().same_channel(channel));
+    }
+
+    /// 会话被其他设备接管：通知并断开当前所有连接，之后的连接注册新的通道
+    pub fn transfer_session(&self, session_id: &str) {
+        self.publish_to_session(
+            session_id,
+            WsEvent::SessionTransferred {
+                session_id: session_id.to_string(),
+            },
+        );
+        self.session_channels.remove(session_id);
+    }
+
     /// 获取会话的事件接收器
     pub fn subscribe(&self, session_id: &str) -> Option<broadcast::Receiver<WsEvent>> {
         self.session_channels.get(session_id).map(|s| s.subscribe())
     }
 
-    /// 发布任务开始推理事件
-    pub fn publish_task_inferring(&self, task_id: &str, session_id: &str, segment_index: u32) {
+    /// 发布任务排队位置事件
+    pub fn publish_task_queued(&self, task: &InferenceTask, position: usize, eta_ms: Option<u64>) {
         self.publish_to_session(
-            session_id,
-            WsEvent::TaskStateChanged {
-                session_id: session_id.to_string(),
-                task_id: task_id.to_string(),
-                segment_index,
-                state: TaskState::Inferring.as_str().to_string(),
-                duration_ms: None,
-                error: None,
+            &task.session_id,
+            WsEvent::TaskQueued {
+                session_id: task.session_id.clone(),
+                task_id: task.task_id.clone(),
+                segment_index: task.segment_index,
+                position,
+                eta_ms,
+                request_id: task.request_id.clone(),
             },
         );
     }
 
+    /// 发布任务开始推理事件
+    pub fn publish_task_inferring(&self, task: &InferenceTask) {
+        self.publish_task_state(task, TaskState::Inferring.as_str(), None, None);
+    }
+
+    /// 发布任务开始输出音频事件（流式推理，可通过 /api/audio 边合成边播放）
+    pub fn publish_task_streaming(&self, task: &InferenceTask) {
+        self.publish_task_state(task, "streaming", None, None);
+    }
+
     /// 发布任务完成事件
-    pub fn publish_task_ready(&self, task_id: &str, session_id: &str, segment_index: u32) {
+    pub fn publish_task_ready(&self, task: &InferenceTask) {
+        self.publish_task_state(task, TaskState::Ready.as_str(), None, None);
+    }
+
+    /// 发布任务完成事件（带时长）
+    pub fn publish_task_ready_with_duration(&self, task: &InferenceTask, duration_ms: u64) {
+        self.publish_task_state(task, TaskState::Ready.as_str(), Some(duration_ms), None);
+    }
+
+    /// 发布任务失败事件
+    pub fn publish_task_failed(&self, task: &InferenceTask, error: &str) {
+        self.publish_task_state(task, TaskState::Failed.as_str(), None, Some(error.to_string()));
+    }
+
+    fn publish_task_state(&self, task: &InferenceTask, state: &str, duration_ms: Option<u64>, error: Option<String>) {
         self.publish_to_session(
-            session_id,
+            &task.session_id,
             WsEvent::TaskStateChanged {
-                session_id: session_id.to_string(),
-                task_id: task_id.to_string(),
-                segment_index,
-                state: TaskState::Ready.as_str().to_string(),
-                duration_ms: None,
-                error: None,
+                session_id: task.session_id.clone(),
+                task_id: task.task_id.clone(),
+                segment_index: task.segment_index,
+                state: state.to_string(),
+                duration_ms,
+                error,
+                request_id: task.request_id.clone(),
             },
         );
     }
 
-    /// 发布任务完成事件（带时长）
-    pub fn publish_task_ready_with_duration(
-        &self,
-        task_id: &str,
-        session_id: &str,
-        segment_index: u32,
-        duration_ms: u64,
-    ) {
+    /// 发布会话关闭事件
+    pub fn publish_session_closed(&self, session_id: &str, reason: &str) {
         self.publish_to_session(
             session_id,
-            WsEvent::TaskStateChanged {
+            WsEvent::SessionClosed {
                 session_id: session_id.to_string(),
-                task_id: task_id.to_string(),
-                segment_index,
-                state: TaskState::Ready.as_str().to_string(),
-                duration_ms: Some(duration_ms),
-                error: None,
+                reason: reason.to_string(),
             },
         );
     }
 
-    /// 发布任务失败事件
-    pub fn publish_task_failed(
-        &self,
-        task_id: &str,
-        session_id: &str,
-        segment_index: u32,
-        error: &str,
-    ) {
+    /// 发布下一片段就绪事件（自动前进模式）
+    pub fn publish_next_segment_ready(&self, session_id: &str, segment_index: u32, audio_url: &str) {
         self.publish_to_session(
             session_id,
-            WsEvent::TaskStateChanged {
+            WsEvent::NextSegmentReady {
                 session_id: session_id.to_string(),
-                task_id: task_id.to_string(),
                 segment_index,
-                state: TaskState::Failed.as_str().to_string(),
-                duration_ms: None,
-                error: Some(error.to_string()),
+                audio_url: audio_url.to_string(),
             },
         );
     }
 
-    /// 发布会话关闭事件
-    pub fn publish_session_closed(&self, session_id: &str, reason: &str) {
+    /// 发布切换音色过渡结束事件（发送给会话）
+    pub fn publish_voice_handoff_completed(&self, session_id: &str, voice_id: Uuid, segment_index: u32) {
         self.publish_to_session(
             session_id,
-            WsEvent::SessionClosed {
+            WsEvent::VoiceHandoffCompleted {
                 session_id: session_id.to_string(),
-                reason: reason.to_string(),
+                voice_id,
+                segment_index,
             },
         );
     }
 
     /// 发布 Novel 处理完成事件（全局广播）
-    pub fn publish_novel_ready(&self, novel_id: Uuid, title: &str, total_segments: usize) {
+    pub fn publish_novel_ready(&self, novel_id: Uuid, title: &str, total_segments: usize, owner: Option<Uuid>) {
         let event = WsEvent::NovelReady {
             novel_id,
             title: title.to_string(),
             total_segments,
         };
-        if let Err(e) = self.global_channel.send(event) {
+        if let Err(e) = self.send_global(owner, event) {
             tracing::debug!(
                 novel_id = %novel_id,
                 error = %e,
@@ -206,12 +342,12 @@ impl EventPublisher {
     }
 
     /// 发布 Novel 处理失败事件（全局广播）
-    pub fn publish_novel_failed(&self, novel_id: Uuid, error: &str) {
+    pub fn publish_novel_failed(&self, novel_id: Uuid, error: &str, owner: Option<Uuid>) {
         let event = WsEvent::NovelFailed {
             novel_id,
             error: error.to_string(),
         };
-        if let Err(e) = self.global_channel.send(event) {
+        if let Err(e) = self.send_global(owner, event) {
             tracing::debug!(
                 novel_id = %novel_id,
                 error = %e,
@@ -221,9 +357,9 @@ impl EventPublisher {
     }
 
     /// 发布 Novel 删除中事件（全局广播）
-    pub fn publish_novel_deleting(&self, novel_id: Uuid) {
+    pub fn publish_novel_deleting(&self, novel_id: Uuid, owner: Option<Uuid>) {
         let event = WsEvent::NovelDeleting { novel_id };
-        if let Err(e) = self.global_channel.send(event) {
+        if let Err(e) = self.send_global(owner, event) {
             tracing::debug!(
                 novel_id = %novel_id,
                 error = %e,
@@ -233,9 +369,9 @@ impl EventPublisher {
     }
 
     /// 发布 Novel 删除完成事件（全局广播）
-    pub fn publish_novel_deleted(&self, novel_id: Uuid) {
+    pub fn publish_novel_deleted(&self, novel_id: Uuid, owner: Option<Uuid>) {
         let event = WsEvent::NovelDeleted { novel_id };
-        if let Err(e) = self.global_channel.send(event) {
+        if let Err(e) = self.send_global(owner, event) {
             tracing::debug!(
                 novel_id = %novel_id,
                 error = %e,
@@ -245,12 +381,12 @@ impl EventPublisher {
     }
 
     /// 发布 Novel 删除失败事件（全局广播）
-    pub fn publish_novel_delete_failed(&self, novel_id: Uuid, error: &str) {
+    pub fn publish_novel_delete_failed(&self, novel_id: Uuid, error: &str, owner: Option<Uuid>) {
         let event = WsEvent::NovelDeleteFailed {
             novel_id,
             error: error.to_string(),
         };
-        if let Err(e) = self.global_channel.send(event) {
+        if let Err(e) = self.send_global(owner, event) {
             tracing::debug!(
                 novel_id = %novel_id,
                 error = %e,
@@ -259,10 +395,22 @@ impl EventPublisher {
         }
     }
 
+    /// 发布 Voice 更新完成事件（全局广播）
+    pub fn publish_voice_updated(&self, voice_id: Uuid, owner: Option<Uuid>) {
+        let event = WsEvent::VoiceUpdated { voice_id };
+        if let Err(e) = self.send_global(owner, event) {
+            tracing::debug!(
+                voice_id = %voice_id,
+                error = %e,
+                "Failed to publish VoiceUpdated event (no receivers)"
+            );
+        }
+    }
+
     /// 发布 Voice 删除完成事件（全局广播）
-    pub fn publish_voice_deleted(&self, voice_id: Uuid) {
+    pub fn publish_voice_deleted(&self, voice_id: Uuid, owner: Option<Uuid>) {
         let event = WsEvent::VoiceDeleted { voice_id };
-        if let Err(e) = self.global_channel.send(event) {
+        if let Err(e) = self.send_global(owner, event) {
             tracing::debug!(
                 voice_id = %voice_id,
                 error = %e,
@@ -271,6 +419,153 @@ impl EventPublisher {
         }
     }
 
+    /// 发布 TTS 引擎不可用事件（全局广播）
+    pub fn publish_tts_engine_down(&self, engine: &str, consecutive_failures: u32) {
+        let event = WsEvent::TtsEngineDown {
+            engine: engine.to_string(),
+            consecutive_failures,
+        };
+        if let Err(e) = self.send_global(None, event) {
+            tracing::debug!(
+                engine = %engine,
+                error = %e,
+                "Failed to publish TtsEngineDown event (no receivers)"
+            );
+        }
+    }
+
+    /// 发布 TTS 引擎恢复事件（全局广播）
+    pub fn publish_tts_engine_up(&self, engine: &str) {
+        let event = WsEvent::TtsEngineUp {
+            engine: engine.to_string(),
+        };
+        if let Err(e) = self.send_global(None, event) {
+            tracing::debug!(
+                engine = %engine,
+                error = %e,
+                "Failed to publish TtsEngineUp event (no receivers)"
+            );
+        }
+    }
+
+    /// 发布有声书导出进度事件（全局广播）
+    pub fn publish_export_progress(
+        &self,
+        job_id: &str,
+        novel_id: Uuid,
+        state: &str,
+        completed_segments: usize,
+        total_segments: usize,
+        owner: Option<Uuid>,
+    ) {
+        let event = WsEvent::ExportProgress {
+            job_id: job_id.to_string(),
+            novel_id,
+            state: state.to_string(),
+            completed_segments,
+            total_segments,
+        };
+        if let Err(e) = self.send_global(owner, event) {
+            tracing::debug!(
+                job_id = %job_id,
+                error = %e,
+                "Failed to publish ExportProgress event (no receivers)"
+            );
+        }
+    }
+
+    /// 发布有声书导出完成事件（全局广播）
+    pub fn publish_export_ready(&self, job_id: &str, novel_id: Uuid, download_url: &str, owner: Option<Uuid>) {
+        let event = WsEvent::ExportReady {
+            job_id: job_id.to_string(),
+            novel_id,
+            download_url: download_url.to_string(),
+        };
+        if let Err(e) = self.send_global(owner, event) {
+            tracing::debug!(
+                job_id = %job_id,
+                error = %e,
+                "Failed to publish ExportReady event (no receivers)"
+            );
+        }
+    }
+
+    /// 发布有声书导出失败事件（全局广播）
+    pub fn publish_export_failed(&self, job_id: &str, novel_id: Uuid, error: &str, owner: Option<Uuid>) {
+        let event = WsEvent::ExportFailed {
+            job_id: job_id.to_string(),
+            novel_id,
+            error: error.to_string(),
+        };
+        if let Err(e) = self.send_global(owner, event) {
+            tracing::debug!(
+                job_id = %job_id,
+                error = %e,
+                "Failed to publish ExportFailed event (no receivers)"
+            );
+        }
+    }
+
+    /// 发布整书预生成进度事件（全局广播）
+    pub fn publish_pregeneration_progress(&self, job: &PregenerationJob) {
+        let event = WsEvent::PregenerationProgress {
+            job_id: job.job_id.clone(),
+            novel_id: job.novel_id,
+            state: job.state.as_str().to_string(),
+            completed_segments: job.completed_segments,
+            failed_segments: job.failed_segments,
+            total_segments: job.total_segments,
+        };
+        if let Err(e) = self.send_global(job.user_id, event) {
+            tracing::debug!(
+                job_id = %job.job_id,
+                error = %e,
+                "Failed to publish PregenerationProgress event (no receivers)"
+            );
+        }
+    }
+
+    /// 发布 GC 完成摘要事件（全局广播）
+    pub fn publish_gc_completed(
+        &self,
+        closed_sessions: u64,
+        cleaned_sessions: u64,
+        deleted_files: u64,
+        freed_bytes: u64,
+    ) {
+        let event = WsEvent::GcCompleted {
+            closed_sessions,
+            cleaned_sessions,
+            deleted_files,
+            freed_bytes,
+        };
+        if let Err(e) = self.send_global(None, event) {
+            tracing::debug!(error = %e, "Failed to publish GcCompleted event (no receivers)");
+        }
+    }
+
+    /// 发布小说缓存达到配额事件（全局广播）
+    pub fn publish_novel_cache_quota_reached(
+        &self,
+        novel_id: Uuid,
+        used_bytes: u64,
+        quota_bytes: u64,
+        owner: Option<Uuid>,
+    ) {
+        let event = WsEvent::NovelCacheQuotaReached {
+            novel_id,
+            used_bytes,
+            quota_bytes,
+        };
+        if let Err(e) = self.send_global(owner, event) {
+            tracing::debug!(
+                novel_id = %novel_id,
+                error = %e,
+                "Failed to publish NovelCacheQuotaReached event (no receivers)"
+            );
+        }
+    }
+
     /// 发布事件到指定会话
     fn publish_to_session(&self, session_id: &str, event: WsEvent) {
         if let Some(sender) = self.session_channels.get(session_id) {
@@ -290,3 +585,46 @@ impl Default for EventPublisher {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_transfer_session_closes_old_connections() {
+        let publisher = EventPublisher::new();
+        let mut old_rx = publisher.register_session("session-1");
+        let old_channel = old_rx.resubscribe();
+
+        publisher.transfer_session("session-1");
+        assert!(matches!(old_rx.recv().await, Ok(WsEvent::SessionTransferred { .. })));
+        assert!(matches!(old_rx.recv().await, Err(broadcast::error::RecvError::Closed)));
+
+        // 新设备注册后，原连接断开时不应移除新通道
+        let new_rx = publisher.register_session("session-1");
+        publisher.release_session("session-1", &old_channel);
+        assert!(publisher.subscribe("session-1").is_some());
+
+        publisher.release_session("session-1", &new_rx);
+        assert!(publisher.subscribe("session-1").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_global_events_scoped_to_owner() {
+        let publisher = EventPublisher::new();
+        let mut rx = publisher.subscribe_global();
+        let alice = Uuid::new_v4();
+        let bob = Uuid::new_v4();
+
+        publisher.publish_voice_deleted(Uuid::new_v4(), Some(alice));
+        let event = rx.recv().await.unwrap();
+        assert!(event.visible_to(Some(alice)));
+        assert!(!event.visible_to(Some(bob)));
+        // 未启用账号时不过滤
+        assert!(event.visible_to(None));
+
+        publisher.publish_tts_engine_up("default");
+        let event = rx.recv().await.unwrap();
+        assert!(event.visible_to(Some(bob)));
+    }
+}
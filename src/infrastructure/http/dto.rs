@@ -3,6 +3,8 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::application::{Page, DEFAULT_PAGE_LIMIT};
+
 // ============================================================================
 // 统一响应结构
 // ============================================================================
@@ -51,6 +53,38 @@ impl ApiResponse<Empty> {
     }
 }
 
+/// 分页列表响应
+#[derive(Debug, Serialize)]
+pub struct PageResponse<T: Serialize> {
+    pub items: Vec<T>,
+    /// 符合条件的总数
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+    /// 下一页的 offset（已是最后一页时为 null）
+    pub next_offset: Option<usize>,
+}
+
+impl<T: Serialize> PageResponse<T> {
+    /// 由应用层分页结果转换
+    pub fn from_page<U>(page: Page<U>, f: impl FnMut(U) -> T) -> Self {
+        let next_offset = page.next_offset();
+        let page = page.map(f);
+        Self {
+            items: page.items,
+            total: page.total,
+            offset: page.offset,
+            limit: page.limit,
+            next_offset,
+        }
+    }
+}
+
+/// 分页参数 limit 的默认值（查询参数结构体直接包含 offset / limit 字段，查询字符串不支持 flatten 数字字段）
+pub fn default_page_limit() -> usize {
+    DEFAULT_PAGE_LIMIT
+}
+
 // ============================================================================
 // Novel DTOs
 // ============================================================================
@@ -1,7 +1,7 @@
 //! HTTP Error Handling - V2 架构
 
 use axum::{
-    http::StatusCode,
+    http::{header, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
@@ -12,7 +12,7 @@ use serde::Serialize;
 pub struct ErrorResponse {
     pub errno: i32,
     pub error: String,
-    pub data: Option<()>,
+    pub data: Option<serde_json::Value>,
 }
 
 impl ErrorResponse {
@@ -23,13 +23,22 @@ impl ErrorResponse {
             data: None,
         }
     }
+
+    /// 附带错误详情（供客户端处理）
+    pub fn with_data(mut self, data: serde_json::Value) -> Self {
+        self.data = Some(data);
+        self
+    }
 }
 
 /// 错误码定义
 pub mod errno {
     pub const BAD_REQUEST: i32 = 400;
+    pub const UNAUTHORIZED: i32 = 401;
+    pub const FORBIDDEN: i32 = 403;
     pub const NOT_FOUND: i32 = 404;
     pub const CONFLICT: i32 = 409;
+    pub const TOO_MANY_REQUESTS: i32 = 429;
     pub const INTERNAL_ERROR: i32 = 500;
     pub const SERVICE_UNAVAILABLE: i32 = 503;
 }
@@ -41,9 +50,22 @@ pub enum ApiError {
     BadRequest(String),
     Internal(String),
     Conflict(String),
+    /// 资源冲突，data 中携带冲突详情
+    ConflictWithData(String, serde_json::Value),
+    /// 超出数量限制，data 中携带限制详情；与其他错误不同，HTTP 状态码同样为 429
+    TooManyRequests(String, serde_json::Value),
     ServiceUnavailable(String),
+    /// 未登录或令牌无效；HTTP 状态码同样为 401，客户端据此跳转登录
+    Unauthorized(String),
+    /// 已登录但不允许该操作（如注册已关闭）
+    Forbidden(String),
+    /// 推理队列已满，HTTP 状态码为 429 并带 Retry-After（秒）
+    QueueFull(String, serde_json::Value, u64),
 }
 
+/// 推理队列已满时建议客户端重试的间隔（秒）
+const QUEUE_FULL_RETRY_AFTER_SECS: u64 = 5;
+
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
         let (status, response) = match &self {
@@ -75,6 +97,20 @@ impl IntoResponse for ApiError {
                     ErrorResponse::new(errno::CONFLICT, msg.clone()),
                 )
             }
+            ApiError::ConflictWithData(msg, data) => {
+                tracing::warn!(errno = errno::CONFLICT, error = %msg, "Resource conflict");
+                (
+                    StatusCode::OK,
+                    ErrorResponse::new(errno::CONFLICT, msg.clone()).with_data(data.clone()),
+                )
+            }
+            ApiError::TooManyRequests(msg, data) => {
+                tracing::warn!(errno = errno::TOO_MANY_REQUESTS, error = %msg, "Limit exceeded");
+                (
+                    StatusCode::TOO_MANY_REQUESTS,
+                    ErrorResponse::new(errno::TOO_MANY_REQUESTS, msg.clone()).with_data(data.clone()),
+                )
+            }
             ApiError::ServiceUnavailable(msg) => {
                 tracing::error!(errno = errno::SERVICE_UNAVAILABLE, error = %msg, "Service unavailable");
                 (
@@ -82,6 +118,31 @@ impl IntoResponse for ApiError {
                     ErrorResponse::new(errno::SERVICE_UNAVAILABLE, msg.clone()),
                 )
             }
+            ApiError::Unauthorized(msg) => {
+                tracing::warn!(errno = errno::UNAUTHORIZED, error = %msg, "Unauthorized");
+                (
+                    StatusCode::UNAUTHORIZED,
+                    ErrorResponse::new(errno::UNAUTHORIZED, msg.clone()),
+                )
+            }
+            ApiError::Forbidden(msg) => {
+                tracing::warn!(errno = errno::FORBIDDEN, error = %msg, "Forbidden");
+                (
+                    StatusCode::OK,
+                    ErrorResponse::new(errno::FORBIDDEN, msg.clone()),
+                )
+            }
+            ApiError::QueueFull(msg, data, retry_after) => {
+                tracing::warn!(errno = errno::TOO_MANY_REQUESTS, error = %msg, "Task queue full");
+                let response =
+                    ErrorResponse::new(errno::TOO_MANY_REQUESTS, msg.clone()).with_data(data.clone());
+                return (
+                    StatusCode::TOO_MANY_REQUESTS,
+                    [(header::RETRY_AFTER, retry_after.to_string())],
+                    Json(response),
+                )
+                    .into_response();
+            }
         };
 
         (status, Json(response)).into_response()
@@ -109,6 +170,38 @@ impl From<crate::application::ApplicationError> for ApiError {
                 ApiError::BadRequest(msg)
             }
             crate::application::ApplicationError::InvalidState(msg) => ApiError::BadRequest(msg),
+            crate::application::ApplicationError::Conflict(msg) => ApiError::Conflict(msg),
+            crate::application::ApplicationError::InUse {
+                resource_type,
+                id,
+                session_ids,
+            } => ApiError::ConflictWithData(
+                format!(
+                    "{} {} is in use by sessions: {}",
+                    resource_type,
+                    id,
+                    session_ids.join(", ")
+                ),
+                serde_json::json!({ "session_ids": session_ids }),
+            ),
+            crate::application::ApplicationError::LimitExceeded {
+                scope,
+                limit,
+                current,
+            } => ApiError::TooManyRequests(
+                format!(
+                    "Too many {}: limit is {}, currently {}",
+                    scope, limit, current
+                ),
+                serde_json::json!({ "scope": scope, "limit": limit, "current": current }),
+            ),
+            crate::application::ApplicationError::Unauthorized(msg) => ApiError::Unauthorized(msg),
+            crate::application::ApplicationError::Forbidden(msg) => ApiError::Forbidden(msg),
+            crate::application::ApplicationError::QueueFull { depth, capacity } => ApiError::QueueFull(
+                format!("Task queue is full ({}/{}), retry later", depth, capacity),
+                serde_json::json!({ "queue_depth": depth, "capacity": capacity }),
+                QUEUE_FULL_RETRY_AFTER_SECS,
+            ),
             crate::application::ApplicationError::RepositoryError(msg) => ApiError::Internal(msg),
             crate::application::ApplicationError::ExternalServiceError(msg) => {
                 ApiError::ServiceUnavailable(msg)
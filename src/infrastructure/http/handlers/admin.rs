@@ -0,0 +1,605 @@
+//! Admin HTTP Handlers - V2 架构
+
+use axum::{
+    body::{Body, Bytes},
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures_util::{stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::ports::{AudioCachePort, CacheError, SessionState, TaskEventKind, TaskPriority, TaskState};
+use crate::application::{
+    GetCacheStats, GetTtsEngineStatus, ListSessions, ListTaskEventsCommand, ListTasksCommand, PageRequest,
+    PurgeAudioCache, ReconcileStorage, RetryTaskCommand,
+};
+use crate::infrastructure::adapters::{CacheArchiveEntry, CacheArchiveReader, CacheArchiveWriter};
+use crate::infrastructure::http::dto::{default_page_limit, ApiResponse, PageResponse};
+use crate::infrastructure::http::error::ApiError;
+use crate::infrastructure::http::state::AppState;
+
+use super::session::{ListSessionsRequest, SessionSummaryDto};
+
+// ============================================================================
+// DTOs
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+pub struct TtsEngineStatusDto {
+    pub engine: String,
+    /// null 表示尚未完成首次健康检查
+    pub healthy: Option<bool>,
+    pub consecutive_failures: u32,
+    pub last_checked_at: Option<String>,
+    pub last_changed_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PurgeAudioCacheRequest {
+    #[serde(default)]
+    pub novel_id: Option<Uuid>,
+    #[serde(default)]
+    pub voice_id: Option<Uuid>,
+    /// 清除旧版本 cache key 的条目
+    #[serde(default)]
+    pub outdated: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PurgeAudioCacheResponseDto {
+    /// 删除的缓存条目数
+    pub removed: usize,
+}
+
+fn default_top_novels() -> usize {
+    10
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CacheStatsParams {
+    /// 返回缓存占用最大的前 N 部小说
+    #[serde(default = "default_top_novels")]
+    pub top: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NovelCacheUsageDto {
+    pub novel_id: Uuid,
+    /// null 表示小说已删除
+    pub title: Option<String>,
+    pub entries: usize,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CacheStatsDto {
+    pub total_entries: usize,
+    pub total_size_bytes: u64,
+    pub max_size_bytes: u64,
+    pub hit_count: u64,
+    pub miss_count: u64,
+    /// null 表示尚无读取
+    pub hit_rate: Option<f64>,
+    pub eviction_count: u64,
+    pub expired_count: u64,
+    /// 磁盘实际占用，null 表示后端不报告（文件缓存）
+    pub disk_size_bytes: Option<u64>,
+    /// 磁盘占用 / 条目总大小
+    pub space_amplification: Option<f64>,
+    pub top_novels: Vec<NovelCacheUsageDto>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReconcileStorageResponseDto {
+    /// 删除的无记录会话音频文件数
+    pub orphan_audio_files: u64,
+    pub freed_bytes: u64,
+    /// 删除的文件已丢失的音频段落记录数
+    pub missing_audio_records: usize,
+    /// 删除的属于已删除小说的缓存条目数
+    pub orphan_cache_entries: usize,
+    /// 缓存内部清理的孤立数据项数
+    pub cache_inconsistencies: usize,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ImportAudioCacheResponseDto {
+    /// 新写入的缓存条目数
+    pub imported: usize,
+    /// 本地已存在而跳过的条目数（片段引用仍会合并）
+    pub skipped: usize,
+}
+
+fn default_task_limit() -> usize {
+    100
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListTasksParams {
+    /// pending / inferring / ready / failed / cancelled
+    #[serde(default)]
+    pub state: Option<String>,
+    #[serde(default)]
+    pub session_id: Option<String>,
+    #[serde(default)]
+    pub offset: usize,
+    #[serde(default = "default_page_limit")]
+    pub limit: usize,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListTaskEventsParams {
+    #[serde(default)]
+    pub session_id: Option<String>,
+    #[serde(default)]
+    pub novel_id: Option<Uuid>,
+    #[serde(default)]
+    pub segment_index: Option<u32>,
+    #[serde(default)]
+    pub task_id: Option<String>,
+    /// submitted / started / retried / failed / completed
+    #[serde(default)]
+    pub event: Option<String>,
+    /// 提交任务的 HTTP 请求 ID（X-Request-Id）
+    #[serde(default)]
+    pub request_id: Option<String>,
+    #[serde(default = "default_task_limit")]
+    pub limit: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TaskEventDto {
+    pub task_id: String,
+    pub session_id: String,
+    pub novel_id: Uuid,
+    pub voice_id: Uuid,
+    pub segment_index: u32,
+    pub event: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TaskDetailDto {
+    pub task_id: String,
+    pub session_id: String,
+    pub novel_id: Uuid,
+    pub voice_id: Uuid,
+    pub segment_index: u32,
+    pub state: String,
+    pub priority: TaskPriority,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    pub created_at: String,
+    pub completed_at: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WorkerStatusDto {
+    /// 出队循环是否在运行（关闭服务时为 false）
+    pub running: bool,
+    pub max_concurrent: usize,
+    /// 正在推理的任务组数（批量推理算一组）
+    pub active: usize,
+    pub batch_size: usize,
+    pub streaming: bool,
+    /// 进行中的不同推理数（相同内容的任务合并为一次推理）
+    pub inflight_inferences: usize,
+    /// 等待进行中推理结果的重复任务数
+    pub joined_tasks: usize,
+    /// 最近每个片段的平均推理耗时，null 表示尚无记录
+    pub average_inference_ms: Option<u64>,
+    /// 推理队列中的任务数
+    pub queue_depth: usize,
+    pub pending_tasks: usize,
+    pub inferring_tasks: usize,
+    pub failed_tasks: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GcResponseDto {
+    /// 关闭的空闲会话数
+    pub closed_sessions: u64,
+    /// 清理了音频存储的会话数
+    pub cleaned_sessions: u64,
+    pub deleted_files: u64,
+    pub freed_bytes: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RetryTaskResponseDto {
+    pub task_id: String,
+    pub session_id: String,
+    pub segment_index: u32,
+    pub state: String,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// 获取所有 TTS 引擎的健康状态
+pub async fn get_tts_health(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<ApiResponse<Vec<TtsEngineStatusDto>>>, ApiError> {
+    let query = GetTtsEngineStatus {
+        engines: state.tts_engines.names(),
+    };
+    let result = state.get_tts_engine_status_handler.handle(query).await?;
+
+    Ok(Json(ApiResponse::success(
+        result
+            .into_iter()
+            .map(|s| TtsEngineStatusDto {
+                engine: s.engine,
+                healthy: s.healthy,
+                consecutive_failures: s.consecutive_failures,
+                last_checked_at: s.last_checked_at,
+                last_changed_at: s.last_changed_at,
+            })
+            .collect(),
+    )))
+}
+
+/// TTS 请求指标（Prometheus 文本格式）
+pub async fn get_metrics(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.tts_metrics.render_prometheus(),
+    )
+}
+
+/// 按小说/音色/旧版本清除音频缓存
+pub async fn purge_audio_cache(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<PurgeAudioCacheRequest>,
+) -> Result<Json<ApiResponse<PurgeAudioCacheResponseDto>>, ApiError> {
+    let command = PurgeAudioCache {
+        novel_id: req.novel_id,
+        voice_id: req.voice_id,
+        outdated: req.outdated,
+    };
+    let result = state.purge_audio_cache_handler.handle(command).await?;
+
+    Ok(Json(ApiResponse::success(PurgeAudioCacheResponseDto {
+        removed: result.removed,
+    })))
+}
+
+/// 核对音频存储、缓存与数据库记录，清理双方的孤立数据
+pub async fn reconcile_storage(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<ApiResponse<ReconcileStorageResponseDto>>, ApiError> {
+    let result = state.reconcile_storage_handler.handle(ReconcileStorage).await?;
+
+    Ok(Json(ApiResponse::success(ReconcileStorageResponseDto {
+        orphan_audio_files: result.orphan_audio_files,
+        freed_bytes: result.freed_bytes,
+        missing_audio_records: result.missing_audio_records,
+        orphan_cache_entries: result.orphan_cache_entries,
+        cache_inconsistencies: result.cache_inconsistencies,
+    })))
+}
+
+/// 列出推理任务（任务保留到会话关闭，失败的任务附带错误原因；按创建时间从新到旧分页）
+pub async fn list_tasks(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<ListTasksParams>,
+) -> Result<Json<ApiResponse<PageResponse<TaskDetailDto>>>, ApiError> {
+    let task_state = match params.state.as_deref() {
+        Some(s) => Some(
+            TaskState::from_str(s).ok_or_else(|| ApiError::BadRequest(format!("Invalid task state: {}", s)))?,
+        ),
+        None => None,
+    };
+    let cmd = ListTasksCommand {
+        state: task_state,
+        session_id: params.session_id,
+        page: PageRequest::new(params.offset, params.limit),
+    };
+    let result = state.list_tasks_handler.handle(cmd)?;
+
+    Ok(Json(ApiResponse::success(PageResponse::from_page(result, |t| TaskDetailDto {
+        task_id: t.task_id,
+        session_id: t.session_id,
+        novel_id: t.novel_id,
+        voice_id: t.voice_id,
+        segment_index: t.segment_index,
+        state: t.state.as_str().to_string(),
+        priority: t.priority,
+        error: t.error,
+        request_id: t.request_id,
+        created_at: t.created_at.to_rfc3339(),
+        completed_at: t.completed_at.map(|at| at.to_rfc3339()),
+    }))))
+}
+
+/// 查询任务审计日志（按会话/小说/片段/请求 ID 过滤，最新的在前）
+pub async fn list_task_events(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<ListTaskEventsParams>,
+) -> Result<Json<ApiResponse<Vec<TaskEventDto>>>, ApiError> {
+    let event = match params.event.as_deref() {
+        Some(s) => Some(
+            TaskEventKind::from_str(s).ok_or_else(|| ApiError::BadRequest(format!("Invalid task event: {}", s)))?,
+        ),
+        None => None,
+    };
+    let cmd = ListTaskEventsCommand {
+        session_id: params.session_id,
+        novel_id: params.novel_id,
+        segment_index: params.segment_index,
+        task_id: params.task_id,
+        event,
+        request_id: params.request_id,
+        limit: params.limit,
+    };
+    let result = state.list_task_events_handler.handle(cmd).await?;
+
+    Ok(Json(ApiResponse::success(
+        result
+            .events
+            .into_iter()
+            .map(|e| TaskEventDto {
+                task_id: e.task_id,
+                session_id: e.session_id,
+                novel_id: e.novel_id,
+                voice_id: e.voice_id,
+                segment_index: e.segment_index,
+                event: e.event.as_str(),
+                error: e.error,
+                duration_ms: e.duration_ms,
+                request_id: e.request_id,
+                created_at: e.created_at.to_rfc3339(),
+            })
+            .collect(),
+    )))
+}
+
+/// 失败的任务重新排队（沿用原任务 ID，状态变化通过会话 WS 推送）
+pub async fn retry_task(
+    State(state): State<Arc<AppState>>,
+    Path(task_id): Path<String>,
+) -> Result<Json<ApiResponse<RetryTaskResponseDto>>, ApiError> {
+    let result = state.retry_task_handler.handle(RetryTaskCommand { task_id })?;
+
+    Ok(Json(ApiResponse::success(RetryTaskResponseDto {
+        task_id: result.task_id,
+        session_id: result.session_id,
+        segment_index: result.segment_index,
+        state: result.state.as_str().to_string(),
+    })))
+}
+
+/// 推理 Worker 状态（并发、队列深度、各状态任务数、平均推理耗时）
+pub async fn get_worker_status(
+    State(state): State<Arc<AppState>>,
+) -> Json<ApiResponse<WorkerStatusDto>> {
+    let worker = state.worker.status();
+    let tasks = state.task_manager.list_tasks(None);
+    let count = |task_state: TaskState| tasks.iter().filter(|task| task.state == task_state).count();
+
+    Json(ApiResponse::success(WorkerStatusDto {
+        running: worker.running,
+        max_concurrent: worker.max_concurrent,
+        active: worker.active,
+        batch_size: worker.batch_size,
+        streaming: worker.streaming,
+        inflight_inferences: worker.inflight_inferences,
+        joined_tasks: worker.joined_tasks,
+        average_inference_ms: worker.average_inference_ms,
+        queue_depth: state.task_manager.queue_depth(),
+        pending_tasks: count(TaskState::Pending),
+        inferring_tasks: count(TaskState::Inferring),
+        failed_tasks: count(TaskState::Failed),
+    }))
+}
+
+/// 列出所有用户的会话（可按 novel_id / state 过滤，按最近活动时间从新到旧分页）
+pub async fn list_all_sessions(
+    State(state): State<Arc<AppState>>,
+    Query(req): Query<ListSessionsRequest>,
+) -> Result<Json<ApiResponse<PageResponse<SessionSummaryDto>>>, ApiError> {
+    let session_state = req
+        .state
+        .map(|s| {
+            SessionState::from_str(&s)
+                .ok_or_else(|| ApiError::BadRequest(format!("Invalid session state: {}", s)))
+        })
+        .transpose()?;
+    let query = ListSessions {
+        novel_id: req.novel_id,
+        state: session_state,
+        user_id: None,
+        page: PageRequest::new(req.offset, req.limit),
+    };
+
+    let sessions = state.list_sessions_handler.handle(query)?;
+    Ok(Json(ApiResponse::success(PageResponse::from_page(
+        sessions,
+        SessionSummaryDto::from,
+    ))))
+}
+
+/// 立即执行一轮 GC（与定时 GC 相同的步骤，未启用自动 GC 时同样可用）
+pub async fn run_gc(State(state): State<Arc<AppState>>) -> Json<ApiResponse<GcResponseDto>> {
+    let summary = state.gc.run_once().await;
+
+    Json(ApiResponse::success(GcResponseDto {
+        closed_sessions: summary.closed_sessions,
+        cleaned_sessions: summary.storage.cleaned_sessions,
+        deleted_files: summary.storage.deleted_files,
+        freed_bytes: summary.storage.freed_bytes,
+    }))
+}
+
+/// 当前生效的配置（配置文件与环境变量合并后的结果，密钥显示为 ***）
+pub async fn get_config(State(state): State<Arc<AppState>>) -> Json<ApiResponse<serde_json::Value>> {
+    Json(ApiResponse::success(state.config.as_ref().clone()))
+}
+
+/// 音频缓存统计（计数自服务启动起累计）
+pub async fn get_cache_stats(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<CacheStatsParams>,
+) -> Result<Json<ApiResponse<CacheStatsDto>>, ApiError> {
+    let query = GetCacheStats { top_novels: params.top };
+    let view = state.get_cache_stats_handler.handle(query).await?;
+
+    Ok(Json(ApiResponse::success(CacheStatsDto {
+        total_entries: view.stats.total_entries,
+        total_size_bytes: view.stats.total_size_bytes,
+        max_size_bytes: view.stats.max_size_bytes,
+        hit_count: view.stats.hit_count,
+        miss_count: view.stats.miss_count,
+        hit_rate: view.hit_rate,
+        eviction_count: view.stats.eviction_count,
+        expired_count: view.stats.expired_count,
+        disk_size_bytes: view.stats.disk_size_bytes,
+        space_amplification: view.space_amplification,
+        top_novels: view
+            .top_novels
+            .into_iter()
+            .map(|n| NovelCacheUsageDto {
+                novel_id: n.novel_id,
+                title: n.title,
+                entries: n.entries,
+                size_bytes: n.size_bytes,
+            })
+            .collect(),
+    })))
+}
+
+/// 导出音频缓存（流式 tar，保留缓存 key、片段引用和波形峰值）
+///
+/// 导出期间被删除或读取失败的条目跳过，不中断导出
+pub async fn export_audio_cache(State(state): State<Arc<AppState>>) -> Result<Response, ApiError> {
+    let keys = state
+        .audio_cache
+        .keys()
+        .await
+        .map_err(|e| ApiError::Internal(e.to_string()))?;
+    let writer = CacheArchiveWriter::new();
+    let manifest = writer
+        .begin()
+        .map_err(|e| ApiError::Internal(format!("Failed to build cache archive: {}", e)))?;
+
+    tracing::info!(entries = keys.len(), "Audio cache export started");
+
+    let cache = state.audio_cache.clone();
+    let entries = stream::unfold(Some((writer, keys.into_iter(), cache)), |export| async move {
+        let (mut writer, mut keys, cache) = export?;
+        loop {
+            let Some(cache_key) = keys.next() else {
+                tracing::info!("Audio cache export finished");
+                return Some((Bytes::from(writer.end()), None));
+            };
+            match encode_cache_entry(cache.as_ref(), &mut writer, &cache_key).await {
+                Ok(Some(block)) => return Some((Bytes::from(block), Some((writer, keys, cache)))),
+                Ok(None) => continue,
+                Err(e) => tracing::warn!(cache_key = %cache_key, error = %e, "Failed to export cache entry"),
+            }
+        }
+    });
+    let body = stream::once(async move { Bytes::from(manifest) })
+        .chain(entries)
+        .map(Ok::<_, std::io::Error>);
+
+    let filename = format!("rovel-cache-{}.tar", chrono::Utc::now().format("%Y%m%d%H%M%S"));
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/x-tar")
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", filename),
+        )
+        .body(Body::from_stream(body))
+        .unwrap())
+}
+
+/// 编码单个缓存条目，条目已不存在时返回 None
+async fn encode_cache_entry(
+    cache: &dyn AudioCachePort,
+    writer: &mut CacheArchiveWriter,
+    cache_key: &str,
+) -> Result<Option<Vec<u8>>, String> {
+    let Some(entry) = cache.peek(cache_key).await.map_err(|e| e.to_string())? else {
+        return Ok(None);
+    };
+    let references = cache.references(cache_key).await.map_err(|e| e.to_string())?;
+    let peaks = cache.get_peaks(cache_key).await.map_err(|e| e.to_string())?;
+
+    let archive_entry = CacheArchiveEntry::new(cache_key, &entry, references, peaks);
+    writer
+        .encode(&archive_entry, &entry.audio_data)
+        .map(Some)
+        .map_err(|e| e.to_string())
+}
+
+/// 导入音频缓存（请求体为导出的 tar，边接收边写入）
+///
+/// 本地已存在的条目不覆盖，只合并片段引用
+pub async fn import_audio_cache(
+    State(state): State<Arc<AppState>>,
+    body: Body,
+) -> Result<Json<ApiResponse<ImportAudioCacheResponseDto>>, ApiError> {
+    let mut reader = CacheArchiveReader::new();
+    let mut result = ImportAudioCacheResponseDto::default();
+
+    let mut chunks = body.into_data_stream();
+    while let Some(chunk) = chunks.next().await {
+        let chunk = chunk.map_err(|e| ApiError::BadRequest(format!("Failed to read archive: {}", e)))?;
+        let entries = reader
+            .push(&chunk)
+            .map_err(|e| ApiError::BadRequest(format!("Invalid cache archive: {}", e)))?;
+        for (entry, audio_data) in entries {
+            let imported = import_cache_entry(state.audio_cache.as_ref(), entry, audio_data)
+                .await
+                .map_err(|e| ApiError::Internal(e.to_string()))?;
+            if imported {
+                result.imported += 1;
+            } else {
+                result.skipped += 1;
+            }
+        }
+    }
+    reader
+        .finish()
+        .map_err(|e| ApiError::BadRequest(format!("Invalid cache archive: {}", e)))?;
+
+    tracing::info!(imported = result.imported, skipped = result.skipped, "Audio cache imported");
+
+    Ok(Json(ApiResponse::success(result)))
+}
+
+/// 写入单个缓存条目及其片段引用和波形峰值，条目已存在时返回 false
+async fn import_cache_entry(
+    cache: &dyn AudioCachePort,
+    entry: CacheArchiveEntry,
+    audio_data: Vec<u8>,
+) -> Result<bool, CacheError> {
+    let imported = !cache.exists(&entry.cache_key).await?;
+    if imported {
+        cache.put(&entry.cache_key, audio_data, entry.metadata()).await?;
+    }
+    for reference in &entry.references {
+        cache
+            .link(&entry.cache_key, reference.novel_id, reference.segment_index, reference.voice_id)
+            .await?;
+    }
+    if let Some(peaks) = &entry.peaks {
+        cache.put_peaks(&entry.cache_key, peaks).await?;
+    }
+    Ok(imported)
+}
@@ -2,42 +2,234 @@
 
 use axum::{
     body::Body,
-    extract::State,
-    http::{header, StatusCode},
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
     response::Response,
-    Json,
+    Extension, Json,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::application::GetAudioQuery;
+use crate::application::ports::AudioFormat;
+use crate::application::{AudioData, ExportChapterAudio, GetAudioPeaks, GetAudioQuery};
+use crate::infrastructure::http::dto::ApiResponse;
 use crate::infrastructure::http::error::ApiError;
+use crate::infrastructure::http::middleware::{current_user_id, CurrentUser};
 use crate::infrastructure::http::state::AppState;
 
+use super::range::ranged_response;
+
 #[derive(Debug, Deserialize)]
 pub struct GetAudioRequest {
     pub novel_id: Uuid,
     pub segment_index: u32,
     pub voice_id: Uuid,
+    #[serde(default)]
+    pub session_id: Option<String>,
+}
+
+/// 输出参数（`?format=opus|mp3|wav&speed=1.5`）
+#[derive(Debug, Deserialize)]
+pub struct AudioFormatParams {
+    #[serde(default)]
+    pub format: Option<AudioFormat>,
+    /// 服务端变速播放速度（0.5 - 2.0，保持音高），省略时使用会话设置
+    #[serde(default)]
+    pub speed: Option<f32>,
+}
+
+/// 从 Accept 头选择第一个可识别的音频格式
+fn accepted_format(headers: &HeaderMap) -> Option<AudioFormat> {
+    headers
+        .get(header::ACCEPT)?
+        .to_str()
+        .ok()?
+        .split(',')
+        .filter_map(|item| item.split(';').next())
+        .find_map(AudioFormat::from_mime)
+}
+
+/// GET 获取音频的查询参数
+#[derive(Debug, Deserialize)]
+pub struct SegmentAudioParams {
+    #[serde(default)]
+    pub session_id: Option<String>,
+    #[serde(default)]
+    pub format: Option<AudioFormat>,
+    #[serde(default)]
+    pub speed: Option<f32>,
+}
+
+/// 查询片段音频并构造响应
+///
+/// 已合成的音频支持单区间 Range 请求（206），合成中的音频以 chunked 输出，不支持 Range
+async fn segment_audio_response(
+    state: &AppState,
+    query: GetAudioQuery,
+    headers: &HeaderMap,
+) -> Result<Response, ApiError> {
+    let result = state.get_audio_handler.handle(query).await?;
+
+    let response = Response::builder().header(header::CONTENT_TYPE, result.content_type);
+    Ok(match result.audio {
+        AudioData::Complete(audio_data) => ranged_response(response, audio_data, headers),
+        // 合成中：chunked 输出，合成完成时结束
+        AudioData::Streaming(stream) => response
+            .status(StatusCode::OK)
+            .body(Body::from_stream(stream))
+            .unwrap(),
+    })
 }
 
+/// 获取音频：格式由 `?format=` 指定，未指定时按 Accept 头协商，均未指定时返回缓存格式；
+/// `?speed=` 在服务端做保持音高的变速（合成中的音频始终原速输出）
 pub async fn get_audio(
     State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    Query(params): Query<AudioFormatParams>,
+    headers: HeaderMap,
     Json(req): Json<GetAudioRequest>,
 ) -> Result<Response, ApiError> {
+    state.access.check_novel(req.novel_id, current_user_id(user)).await?;
     let query = GetAudioQuery {
         novel_id: req.novel_id,
         segment_index: req.segment_index,
         voice_id: req.voice_id,
+        session_id: req.session_id,
+        format: params.format.or_else(|| accepted_format(&headers)),
+        playback_speed: params.speed,
     };
 
-    let result = state.get_audio_handler.handle(query).await?;
+    segment_audio_response(&state, query, &headers).await
+}
+
+/// GET 获取音频（可直接作为 `<audio>` 的 src，支持拖动进度和断点续传）
+pub async fn get_segment_audio(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    Path((novel_id, voice_id, segment_index)): Path<(Uuid, Uuid, u32)>,
+    Query(params): Query<SegmentAudioParams>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    state.access.check_novel(novel_id, current_user_id(user)).await?;
+    let query = GetAudioQuery {
+        novel_id,
+        segment_index,
+        voice_id,
+        session_id: params.session_id,
+        format: params.format.or_else(|| accepted_format(&headers)),
+        playback_speed: params.speed,
+    };
+
+    segment_audio_response(&state, query, &headers).await
+}
+
+#[derive(Debug, Serialize)]
+pub struct AudioPeaksResponse {
+    pub peaks: Vec<f32>,
+    pub duration_ms: u64,
+}
+
+/// 获取片段波形峰值（用于绘制进度条波形）
+pub async fn get_audio_peaks(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    Json(req): Json<GetAudioRequest>,
+) -> Result<Json<ApiResponse<AudioPeaksResponse>>, ApiError> {
+    state.access.check_novel(req.novel_id, current_user_id(user)).await?;
+    let query = GetAudioPeaks {
+        novel_id: req.novel_id,
+        segment_index: req.segment_index,
+        voice_id: req.voice_id,
+        session_id: req.session_id,
+    };
+    let result = state.get_audio_peaks_handler.handle(query).await?;
+
+    Ok(Json(ApiResponse::success(AudioPeaksResponse {
+        peaks: result.peaks,
+        duration_ms: result.duration_ms,
+    })))
+}
+
+fn default_trim_silence() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportChapterAudioRequest {
+    pub novel_id: Uuid,
+    /// 章节编号（见 /api/novel/chapters）
+    pub chapter: usize,
+    pub voice_id: Uuid,
+    #[serde(default)]
+    pub session_id: Option<String>,
+    #[serde(default)]
+    pub format: AudioFormat,
+    #[serde(default = "default_trim_silence")]
+    pub trim_silence: bool,
+    /// 省略时使用会话的衔接提示
+    #[serde(default)]
+    pub gap_ms: Option<u32>,
+    #[serde(default)]
+    pub crossfade_ms: Option<u32>,
+}
+
+/// 导出章节音频（拼接已缓存的片段，作为附件下载）
+///
+/// 响应头 X-Exported-Segments / X-Missing-Segments 为已拼接和尚未合成的片段数
+pub async fn export_chapter_audio(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    Json(req): Json<ExportChapterAudioRequest>,
+) -> Result<Response, ApiError> {
+    state.access.check_novel(req.novel_id, current_user_id(user)).await?;
+    let cmd = ExportChapterAudio {
+        novel_id: req.novel_id,
+        chapter: req.chapter,
+        voice_id: req.voice_id,
+        session_id: req.session_id,
+        format: req.format,
+        trim_silence: req.trim_silence,
+        gap_ms: req.gap_ms,
+        crossfade_ms: req.crossfade_ms,
+    };
+
+    let result = state.export_chapter_audio_handler.handle(cmd).await?;
 
     Ok(Response::builder()
         .status(StatusCode::OK)
-        .header(header::CONTENT_TYPE, result.content_type)
+        .header(header::CONTENT_TYPE, result.format.content_type())
         .header(header::CONTENT_LENGTH, result.audio_data.len())
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!(
+                "attachment; filename=\"{}-chapter-{}.{}\"",
+                req.novel_id, req.chapter, result.format
+            ),
+        )
+        .header("X-Exported-Segments", result.exported_segments)
+        .header("X-Missing-Segments", result.missing_segments.len())
         .body(Body::from(result.audio_data))
         .unwrap())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accepted_format() {
+        let accept = |value: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::ACCEPT, value.parse().unwrap());
+            accepted_format(&headers)
+        };
+
+        assert_eq!(accept("audio/ogg"), Some(AudioFormat::Opus));
+        assert_eq!(accept("text/html, audio/mpeg;q=0.9, */*"), Some(AudioFormat::Mp3));
+        assert_eq!(accept("audio/x-wav"), Some(AudioFormat::Wav));
+        assert_eq!(accept("*/*"), None);
+        assert_eq!(accepted_format(&HeaderMap::new()), None);
+    }
+}
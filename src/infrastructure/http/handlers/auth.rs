@@ -0,0 +1,79 @@
+//! Auth HTTP Handlers - 注册与登录
+
+use axum::{extract::State, Json};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::{AuthTokenResponse, LoginUser, RegisterUser};
+use crate::infrastructure::http::dto::ApiResponse;
+use crate::infrastructure::http::error::ApiError;
+use crate::infrastructure::http::state::AppState;
+
+// ============================================================================
+// DTOs
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct CredentialsRequest {
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuthTokenResponseDto {
+    pub user_id: Uuid,
+    pub username: String,
+    /// 登录令牌（`Authorization: Bearer <token>` 或 `?access_token=<token>`）
+    pub token: String,
+    pub expires_at: String,
+}
+
+impl From<AuthTokenResponse> for AuthTokenResponseDto {
+    fn from(response: AuthTokenResponse) -> Self {
+        Self {
+            user_id: response.user_id,
+            username: response.username,
+            token: response.token,
+            expires_at: response.expires_at.to_rfc3339(),
+        }
+    }
+}
+
+fn accounts_disabled() -> ApiError {
+    ApiError::BadRequest("User accounts are not enabled".to_string())
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// 注册用户并返回登录令牌
+pub async fn register(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<CredentialsRequest>,
+) -> Result<Json<ApiResponse<AuthTokenResponseDto>>, ApiError> {
+    let handler = state.register_user_handler.as_ref().ok_or_else(accounts_disabled)?;
+    let command = RegisterUser {
+        username: req.username,
+        password: req.password,
+    };
+
+    let result = handler.handle(command).await?;
+    Ok(Json(ApiResponse::success(result.into())))
+}
+
+/// 登录并返回登录令牌
+pub async fn login(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<CredentialsRequest>,
+) -> Result<Json<ApiResponse<AuthTokenResponseDto>>, ApiError> {
+    let handler = state.login_handler.as_ref().ok_or_else(accounts_disabled)?;
+    let command = LoginUser {
+        username: req.username,
+        password: req.password,
+    };
+
+    let result = handler.handle(command).await?;
+    Ok(Json(ApiResponse::success(result.into())))
+}
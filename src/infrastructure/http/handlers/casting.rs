@@ -0,0 +1,110 @@
+//! Voice Casting HTTP Handlers - V2 架构
+
+use axum::{extract::State, Extension, Json};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::{GetVoiceCasting, UpdateVoiceCasting};
+use crate::infrastructure::http::dto::{ApiResponse, Empty};
+use crate::infrastructure::http::error::ApiError;
+use crate::infrastructure::http::middleware::{current_user_id, CurrentUser};
+use crate::infrastructure::http::state::AppState;
+
+// ============================================================================
+// DTOs
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct GetVoiceCastingRequest {
+    pub novel_id: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateVoiceCastingRequest {
+    pub novel_id: Uuid,
+    pub character_name: String,
+    /// 为 null 时删除该角色的配音
+    #[serde(default)]
+    pub voice_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VoiceCastingResponse {
+    pub novel_id: Uuid,
+    pub castings: Vec<VoiceCastingDto>,
+    pub detected_characters: Vec<DetectedCharacterDto>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VoiceCastingDto {
+    pub character_name: String,
+    pub voice_id: Uuid,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DetectedCharacterDto {
+    pub name: String,
+    pub dialogue_count: usize,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// 获取小说的角色配音表及识别出的角色
+pub async fn get_voice_casting(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    Json(req): Json<GetVoiceCastingRequest>,
+) -> Result<Json<ApiResponse<VoiceCastingResponse>>, ApiError> {
+    state.access.check_novel(req.novel_id, current_user_id(user)).await?;
+    let query = GetVoiceCasting {
+        novel_id: req.novel_id,
+    };
+    let result = state.get_voice_casting_handler.handle(query).await?;
+
+    Ok(Json(ApiResponse::success(VoiceCastingResponse {
+        novel_id: req.novel_id,
+        castings: result
+            .castings
+            .into_iter()
+            .map(|c| VoiceCastingDto {
+                character_name: c.character_name,
+                voice_id: c.voice_id,
+                updated_at: c.updated_at,
+            })
+            .collect(),
+        detected_characters: result
+            .detected_characters
+            .into_iter()
+            .map(|c| DetectedCharacterDto {
+                name: c.name,
+                dialogue_count: c.dialogue_count,
+            })
+            .collect(),
+    })))
+}
+
+/// 设置或删除角色配音
+pub async fn update_voice_casting(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    Json(req): Json<UpdateVoiceCastingRequest>,
+) -> Result<Json<ApiResponse<Empty>>, ApiError> {
+    let user_id = current_user_id(user);
+    state.access.check_novel_mut(req.novel_id, user_id).await?;
+    if let Some(voice_id) = req.voice_id {
+        state.access.check_voice(voice_id, user_id).await?;
+    }
+    let command = UpdateVoiceCasting {
+        novel_id: req.novel_id,
+        character_name: req.character_name,
+        voice_id: req.voice_id,
+    };
+
+    state.update_voice_casting_handler.handle(command).await?;
+
+    Ok(Json(ApiResponse::ok()))
+}
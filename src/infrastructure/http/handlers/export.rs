@@ -0,0 +1,168 @@
+//! Export HTTP Handlers - 有声书导出
+
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderMap},
+    response::Response,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::Instrument;
+use uuid::Uuid;
+
+use crate::application::commands::handlers::export_download_url;
+use crate::application::ports::{AudioFormat, ExportJob, ExportJobState, PlaybackHints};
+use crate::application::{current_request_id, with_request_id, ExportAudiobook, GetExportJob};
+use crate::domain::voice::SynthesisParams;
+use crate::infrastructure::http::dto::ApiResponse;
+use crate::infrastructure::http::error::ApiError;
+use crate::infrastructure::http::middleware::{current_user_id, CurrentUser};
+use crate::infrastructure::http::state::AppState;
+
+use super::range::ranged_file_response;
+
+// ============================================================================
+// DTOs
+// ============================================================================
+
+fn default_export_format() -> AudioFormat {
+    // 仅 Opus/WebM 输出可写入章节标记
+    AudioFormat::Opus
+}
+
+fn default_trim_silence() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportAudiobookRequest {
+    pub novel_id: Uuid,
+    pub voice_id: Uuid,
+    #[serde(default)]
+    pub params: SynthesisParams,
+    #[serde(default = "default_export_format")]
+    pub format: AudioFormat,
+    #[serde(default = "default_trim_silence")]
+    pub trim_silence: bool,
+    /// 片段衔接：gap_ms / crossfade_ms
+    #[serde(default, flatten)]
+    pub hints: PlaybackHints,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportJobResponse {
+    pub job_id: String,
+    pub novel_id: Uuid,
+    pub voice_id: Uuid,
+    pub format: AudioFormat,
+    pub state: ExportJobState,
+    pub completed_segments: usize,
+    pub total_segments: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub download_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub created_at: String,
+}
+
+impl From<ExportJob> for ExportJobResponse {
+    fn from(job: ExportJob) -> Self {
+        Self {
+            download_url: (job.state == ExportJobState::Ready).then(|| export_download_url(&job.job_id)),
+            job_id: job.job_id,
+            novel_id: job.novel_id,
+            voice_id: job.voice_id,
+            format: job.format,
+            state: job.state,
+            completed_segments: job.completed_segments,
+            total_segments: job.total_segments,
+            error: job.error_message,
+            created_at: job.created_at.to_rfc3339(),
+        }
+    }
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// 开始导出有声书（后台执行，进度通过全局 WS 事件通知）
+pub async fn export_audiobook(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    Json(req): Json<ExportAudiobookRequest>,
+) -> Result<Json<ApiResponse<ExportJobResponse>>, ApiError> {
+    let user_id = current_user_id(user);
+    state.access.check_novel(req.novel_id, user_id).await?;
+    state.access.check_voice(req.voice_id, user_id).await?;
+    let cmd = ExportAudiobook {
+        novel_id: req.novel_id,
+        voice_id: req.voice_id,
+        params: req.params,
+        format: req.format,
+        trim_silence: req.trim_silence,
+        hints: req.hints,
+        user_id,
+    };
+
+    let job = state.export_audiobook_handler.handle(&cmd).await?;
+
+    let state_clone = state.clone();
+    let job_id = job.job_id.clone();
+    // 后台任务沿用本次请求的 ID，期间提交的推理任务可追溯到发起请求
+    let request_id = current_request_id();
+    tokio::spawn(
+        with_request_id(request_id, async move {
+            state_clone.export_audiobook_handler.run(job_id, cmd).await;
+        })
+        .in_current_span(),
+    );
+
+    Ok(Json(ApiResponse::success(ExportJobResponse::from(job))))
+}
+
+/// 查询导出任务状态
+pub async fn get_export_job(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    Path(job_id): Path<String>,
+) -> Result<Json<ApiResponse<ExportJobResponse>>, ApiError> {
+    state.access.check_export_job(&job_id, current_user_id(user))?;
+    let job = state.get_export_job_handler.handle(GetExportJob { job_id })?;
+    Ok(Json(ApiResponse::success(ExportJobResponse::from(job))))
+}
+
+/// 下载导出文件
+pub async fn download_export(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    Path(job_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    state.access.check_export_job(&job_id, current_user_id(user))?;
+    let job = state.get_export_job_handler.handle(GetExportJob { job_id })?;
+    let path = match (job.state, &job.file_path) {
+        (ExportJobState::Ready, Some(path)) => path,
+        _ => {
+            return Err(ApiError::Conflict(format!(
+                "Export is not ready: {}",
+                job.state.as_str()
+            )))
+        }
+    };
+
+    let file = tokio::fs::File::open(path)
+        .await
+        .map_err(|e| ApiError::Internal(format!("Failed to open export file: {}", e)))?;
+
+    let builder = Response::builder()
+        .header(header::CONTENT_TYPE, job.format.content_type())
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}.{}\"", job.novel_id, job.format),
+        );
+    ranged_file_response(builder, file, &headers)
+        .await
+        .map_err(|e| ApiError::Internal(format!("Failed to read export file: {}", e)))
+}
@@ -1,12 +1,15 @@
 //! Inference Handlers - V2 架构
 
-use axum::{extract::State, Json};
+use axum::{extract::State, Extension, Json};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::application::{QueryTaskStatusCommand, SubmitInferCommand};
+use crate::domain::voice::SynthesisParams;
 use crate::infrastructure::http::dto::ApiResponse;
 use crate::infrastructure::http::error::ApiError;
+use crate::infrastructure::http::middleware::{current_user_id, CurrentUser};
 use crate::infrastructure::http::state::AppState;
 
 // ============================================================================
@@ -17,6 +20,9 @@ use crate::infrastructure::http::state::AppState;
 pub struct SubmitInferRequest {
     pub session_id: String,
     pub segment_indices: Vec<u32>,
+    /// 片段级参数覆盖（片段序号 -> 参数），与会话参数合并
+    #[serde(default)]
+    pub segment_params: HashMap<u32, SynthesisParams>,
 }
 
 #[derive(Debug, Serialize)]
@@ -29,15 +35,20 @@ pub struct TaskInfoDto {
 #[derive(Debug, Serialize)]
 pub struct SubmitInferResponseDto {
     pub tasks: Vec<TaskInfoDto>,
+    /// 排队等待推理的任务数
+    pub queue_depth: usize,
 }
 
 pub async fn submit_infer(
     State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
     Json(req): Json<SubmitInferRequest>,
 ) -> Result<Json<ApiResponse<SubmitInferResponseDto>>, ApiError> {
+    state.access.check_session(&req.session_id, current_user_id(user))?;
     let cmd = SubmitInferCommand {
         session_id: req.session_id,
         segment_indices: req.segment_indices,
+        segment_params: req.segment_params,
     };
 
     let result = state.submit_infer_handler.handle(cmd).await?;
@@ -52,6 +63,7 @@ pub async fn submit_infer(
                 state: t.state.as_str().to_string(),
             })
             .collect(),
+        queue_depth: result.queue_depth,
     })))
 }
 
@@ -80,10 +92,12 @@ pub struct QueryTaskStatusResponseDto {
 
 pub async fn query_task_status(
     State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
     Json(req): Json<QueryTaskStatusRequest>,
 ) -> Result<Json<ApiResponse<QueryTaskStatusResponseDto>>, ApiError> {
     let cmd = QueryTaskStatusCommand {
         task_ids: req.task_ids,
+        user_id: current_user_id(user),
     };
 
     let result = state.query_task_status_handler.handle(cmd);
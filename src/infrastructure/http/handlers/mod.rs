@@ -2,18 +2,34 @@
 //!
 //! V2 架构 - 基于 ARCHITECTURE.md 设计
 
+mod admin;
 mod audio;
+mod auth;
+mod casting;
+mod export;
 mod infer;
 mod novel;
 mod ping;
+mod pregenerate;
+mod range;
 mod session;
+mod settings;
+mod stats;
+mod upload;
 mod voice;
 mod websocket;
 
+pub use admin::*;
 pub use audio::*;
+pub use auth::*;
+pub use casting::*;
+pub use export::*;
 pub use infer::*;
 pub use novel::*;
 pub use ping::*;
+pub use pregenerate::*;
 pub use session::*;
+pub use settings::*;
+pub use stats::*;
 pub use voice::*;
 pub use websocket::*;
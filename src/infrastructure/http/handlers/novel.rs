@@ -1,8 +1,8 @@
 //! Novel HTTP Handlers - V2 架构
 
 use axum::{
-    extract::{Multipart, State},
-    Json,
+    extract::{Multipart, Path, Query, State},
+    Extension, Json,
 };
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -11,12 +11,19 @@ use tokio::fs;
 use uuid::Uuid;
 
 use crate::application::{
-    CreateNovelFromText, DeleteNovel, GetNovel, GetNovelSegments, ListNovels, ProcessNovelSegments,
+    ApplicationError, CreateNovelFromText, DeleteNovel, GetNovel, GetNovelChapters, GetNovelSegments, ListNovels, PageRequest,
+    PreviewSegments, ProcessNovelSegments,
 };
-use crate::infrastructure::http::dto::ApiResponse;
+use crate::infrastructure::http::dto::{default_page_limit, ApiResponse, PageResponse};
 use crate::infrastructure::http::error::ApiError;
+use crate::infrastructure::http::middleware::{current_user_id, CurrentUser};
 use crate::infrastructure::http::state::AppState;
 
+use super::upload::{stream_to_temp, UploadedFile, MAX_UPLOAD_BYTES};
+
+/// 小说原文目录
+const NOVELS_DIR: &str = "data/novels";
+
 // ============================================================================
 // DTOs
 // ============================================================================
@@ -43,6 +50,21 @@ pub struct GetNovelRequest {
     pub id: Uuid,
 }
 
+#[derive(Debug, Serialize)]
+pub struct ChapterDto {
+    pub number: usize,
+    pub title: String,
+    pub start_segment_index: usize,
+    /// 结束片段索引（不包含）
+    pub end_segment_index: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChaptersResponse {
+    pub novel_id: Uuid,
+    pub chapters: Vec<ChapterDto>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct DeleteNovelRequest {
     pub id: Uuid,
@@ -51,14 +73,30 @@ pub struct DeleteNovelRequest {
 #[derive(Debug, Deserialize)]
 pub struct GetNovelSegmentsRequest {
     pub novel_id: Uuid,
-    #[serde(default)]
-    pub start: usize,
-    #[serde(default = "default_limit")]
+    /// 起始片段索引（兼容旧参数名 start）
+    #[serde(default, alias = "start")]
+    pub offset: usize,
+    #[serde(default = "default_page_limit")]
+    pub limit: usize,
+}
+
+/// GET /api/novels/{id}/segments 的查询参数
+#[derive(Debug, Deserialize)]
+pub struct NovelSegmentsParams {
+    /// 起始片段索引（兼容旧参数名 start）
+    #[serde(default, alias = "start")]
+    pub offset: usize,
+    #[serde(default = "default_page_limit")]
     pub limit: usize,
 }
 
-fn default_limit() -> usize {
-    100
+/// GET /api/novel/list 的查询参数
+#[derive(Debug, Deserialize)]
+pub struct ListNovelsParams {
+    #[serde(default)]
+    pub offset: usize,
+    #[serde(default = "default_page_limit")]
+    pub limit: usize,
 }
 
 #[derive(Debug, Serialize)]
@@ -68,9 +106,22 @@ pub struct SegmentResponse {
     pub char_count: usize,
 }
 
+/// 分段预览请求
+#[derive(Debug, Deserialize)]
+pub struct SegmentPreviewRequest {
+    pub text: String,
+    #[serde(default)]
+    pub config: SegmentConfigRequest,
+}
+
+/// 分段配置（未提供的字段使用默认值）
+#[derive(Debug, Default, Deserialize)]
+pub struct SegmentConfigRequest {
+    pub min_chars: Option<usize>,
+}
+
 #[derive(Debug, Serialize)]
-pub struct SegmentsResponse {
-    pub novel_id: Uuid,
+pub struct SegmentPreviewResponse {
     pub total: usize,
     pub segments: Vec<SegmentResponse>,
 }
@@ -87,12 +138,15 @@ pub struct DeleteNovelResponse {
 // ============================================================================
 
 /// 上传小说 TXT 文件（异步处理，立即返回，完成后通过 WS 通知）
+///
+/// 文件流式写入小说目录下的临时文件，创建记录后移动为 `{novel_id}.txt`，分段时再读取
 pub async fn upload_novel(
     State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
     mut multipart: Multipart,
 ) -> Result<Json<ApiResponse<NovelUploadResponse>>, ApiError> {
     let mut title: Option<String> = None;
-    let mut content: Option<String> = None;
+    let mut upload: Option<UploadedFile> = None;
     let mut filename: Option<String> = None;
 
     while let Some(field) = multipart.next_field().await.map_err(|e| {
@@ -126,30 +180,13 @@ pub async fn upload_novel(
                     ));
                 }
 
-                let bytes = field
-                    .bytes()
-                    .await
-                    .map_err(|e| ApiError::BadRequest(format!("Failed to read file: {}", e)))?;
-
-                // 验证文件大小（最大 100MB）
-                const MAX_SIZE: usize = 100 * 1024 * 1024;
-                if bytes.len() > MAX_SIZE {
-                    return Err(ApiError::BadRequest(format!(
-                        "File too large. Maximum size is {} MB",
-                        MAX_SIZE / 1024 / 1024
-                    )));
-                }
-
-                content = Some(
-                    String::from_utf8(bytes.to_vec())
-                        .map_err(|_| ApiError::BadRequest("File must be valid UTF-8 text".to_string()))?,
-                );
+                upload = Some(stream_to_temp(field, &PathBuf::from(NOVELS_DIR), MAX_UPLOAD_BYTES, true).await?);
             }
             _ => {}
         }
     }
 
-    let content = content.ok_or_else(|| ApiError::BadRequest("File is required".to_string()))?;
+    let upload = upload.ok_or_else(|| ApiError::BadRequest("File is required".to_string()))?;
 
     let title = title.unwrap_or_else(|| {
         filename
@@ -164,43 +201,48 @@ pub async fn upload_novel(
     });
 
     // Step 1: 创建 processing 状态的记录，立即返回 ID
+    let user_id = current_user_id(user);
     let command = CreateNovelFromText {
         title: title.clone(),
-        text: content.clone(),
+        user_id,
     };
 
     let result = state.create_novel_handler.handle(command).await?;
     let novel_id = result.id;
     let novel_title = result.title.clone();
 
+    let (size, md5) = (upload.size, upload.md5.clone());
+    let file_path = PathBuf::from(NOVELS_DIR).join(format!("{}.txt", novel_id));
+    if let Err(e) = upload.persist(&file_path).await {
+        if let Err(e) = state.novel_repo.delete(novel_id).await {
+            tracing::warn!(novel_id = %novel_id, error = %e, "Failed to remove novel record");
+        }
+        return Err(e);
+    }
+
     tracing::info!(
         novel_id = %novel_id,
         title = %novel_title,
+        size,
+        md5 = %md5,
         "Novel created (processing)"
     );
 
-    // Step 2: 异步处理分段 + 保存文件 + WS 通知
+    // Step 2: 异步读取原文分段 + WS 通知
     let state_clone = state.clone();
-    let content_clone = content.clone();
     tokio::spawn(async move {
-        let process_command = ProcessNovelSegments {
-            novel_id,
-            text: content_clone.clone(),
+        let result = match fs::read_to_string(&file_path).await {
+            Ok(text) => {
+                state_clone
+                    .process_novel_handler
+                    .handle(ProcessNovelSegments { novel_id, text })
+                    .await
+            }
+            Err(e) => Err(ApplicationError::internal(format!("Failed to read novel file: {}", e))),
         };
 
-        match state_clone.process_novel_handler.handle(process_command).await {
+        match result {
             Ok(process_result) => {
-                // 保存原始文件
-                let novels_dir = PathBuf::from("data/novels");
-                if let Err(e) = fs::create_dir_all(&novels_dir).await {
-                    tracing::warn!("Failed to create novels directory: {}", e);
-                } else {
-                    let file_path = novels_dir.join(format!("{}.txt", novel_id));
-                    if let Err(e) = fs::write(&file_path, &content_clone).await {
-                        tracing::warn!("Failed to save novel file: {}", e);
-                    }
-                }
-
                 tracing::info!(
                     novel_id = %novel_id,
                     title = %process_result.title,
@@ -213,6 +255,7 @@ pub async fn upload_novel(
                     novel_id,
                     &process_result.title,
                     process_result.total_segments,
+                    user_id,
                 );
             }
             Err(e) => {
@@ -226,6 +269,7 @@ pub async fn upload_novel(
                 state_clone.event_publisher.publish_novel_failed(
                     novel_id,
                     &e.to_string(),
+                    user_id,
                 );
             }
         }
@@ -239,31 +283,34 @@ pub async fn upload_novel(
     })))
 }
 
-/// 获取小说列表
+/// 获取小说列表（?offset=&limit=，按创建时间从新到旧）
 pub async fn list_novels(
     State(state): State<Arc<AppState>>,
-) -> Result<Json<ApiResponse<Vec<NovelResponse>>>, ApiError> {
-    let result = state.list_novels_handler.handle(ListNovels).await?;
-
-    let responses: Vec<NovelResponse> = result
-        .into_iter()
-        .map(|n| NovelResponse {
-            id: n.id,
-            title: n.title,
-            total_segments: n.total_segments,
-            status: n.status,
-            created_at: n.created_at,
-        })
-        .collect();
-
-    Ok(Json(ApiResponse::success(responses)))
+    user: Option<Extension<CurrentUser>>,
+    Query(params): Query<ListNovelsParams>,
+) -> Result<Json<ApiResponse<PageResponse<NovelResponse>>>, ApiError> {
+    let query = ListNovels {
+        user_id: current_user_id(user),
+        page: PageRequest::new(params.offset, params.limit),
+    };
+    let result = state.list_novels_handler.handle(query).await?;
+
+    Ok(Json(ApiResponse::success(PageResponse::from_page(result, |n| NovelResponse {
+        id: n.id,
+        title: n.title,
+        total_segments: n.total_segments,
+        status: n.status,
+        created_at: n.created_at,
+    }))))
 }
 
 /// 获取小说详情
 pub async fn get_novel(
     State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
     Json(req): Json<GetNovelRequest>,
 ) -> Result<Json<ApiResponse<NovelResponse>>, ApiError> {
+    state.access.check_novel(req.id, current_user_id(user)).await?;
     let query = GetNovel { novel_id: req.id };
 
     let result = state.get_novel_handler.handle(query).await?;
@@ -277,19 +324,88 @@ pub async fn get_novel(
     })))
 }
 
+/// 获取小说详情（GET /api/novels/{id}）
+pub async fn get_novel_by_id(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<NovelResponse>>, ApiError> {
+    get_novel(State(state), user, Json(GetNovelRequest { id })).await
+}
+
+/// 获取小说章节（按标题片段识别）
+pub async fn get_novel_chapters(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    Json(req): Json<GetNovelRequest>,
+) -> Result<Json<ApiResponse<ChaptersResponse>>, ApiError> {
+    state.access.check_novel(req.id, current_user_id(user)).await?;
+    let query = GetNovelChapters { novel_id: req.id };
+
+    let result = state.get_novel_chapters_handler.handle(query).await?;
+
+    Ok(Json(ApiResponse::success(ChaptersResponse {
+        novel_id: req.id,
+        chapters: result
+            .into_iter()
+            .map(|c| ChapterDto {
+                number: c.number,
+                title: c.title,
+                start_segment_index: c.start_segment_index,
+                end_segment_index: c.end_segment_index,
+            })
+            .collect(),
+    })))
+}
+
 /// 获取小说段落
 pub async fn get_novel_segments(
     State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
     Json(req): Json<GetNovelSegmentsRequest>,
-) -> Result<Json<ApiResponse<SegmentsResponse>>, ApiError> {
+) -> Result<Json<ApiResponse<PageResponse<SegmentResponse>>>, ApiError> {
+    state.access.check_novel(req.novel_id, current_user_id(user)).await?;
     let query = GetNovelSegments {
         novel_id: req.novel_id,
-        start_index: Some(req.start),
-        limit: Some(req.limit),
+        page: PageRequest::new(req.offset, req.limit),
     };
 
     let result = state.get_novel_segments_handler.handle(query).await?;
 
+    Ok(Json(ApiResponse::success(PageResponse::from_page(result, |s| SegmentResponse {
+        index: s.index,
+        content: s.content,
+        char_count: s.char_count,
+    }))))
+}
+
+/// 获取小说段落（GET /api/novels/{id}/segments?offset=&limit=）
+pub async fn get_novel_segments_by_id(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    Path(novel_id): Path<Uuid>,
+    Query(params): Query<NovelSegmentsParams>,
+) -> Result<Json<ApiResponse<PageResponse<SegmentResponse>>>, ApiError> {
+    let req = GetNovelSegmentsRequest {
+        novel_id,
+        offset: params.offset,
+        limit: params.limit,
+    };
+    get_novel_segments(State(state), user, Json(req)).await
+}
+
+/// 分段预览（不写入数据库，用于调整 min_chars）
+pub async fn preview_segments(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<SegmentPreviewRequest>,
+) -> Result<Json<ApiResponse<SegmentPreviewResponse>>, ApiError> {
+    let query = PreviewSegments {
+        text: req.text,
+        min_chars: req.config.min_chars,
+    };
+
+    let result = state.preview_segments_handler.handle(query)?;
+
     let segments: Vec<SegmentResponse> = result
         .into_iter()
         .map(|s| SegmentResponse {
@@ -299,8 +415,7 @@ pub async fn get_novel_segments(
         })
         .collect();
 
-    Ok(Json(ApiResponse::success(SegmentsResponse {
-        novel_id: req.novel_id,
+    Ok(Json(ApiResponse::success(SegmentPreviewResponse {
         total: segments.len(),
         segments,
     })))
@@ -309,22 +424,19 @@ pub async fn get_novel_segments(
 /// 删除小说（异步处理，立即返回，完成后通过 WS 通知）
 pub async fn delete_novel(
     State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
     Json(req): Json<DeleteNovelRequest>,
 ) -> Result<Json<ApiResponse<DeleteNovelResponse>>, ApiError> {
     let novel_id = req.id;
 
-    // 先检查小说是否存在
-    let novel = state
-        .novel_repo
-        .find_by_id(novel_id)
-        .await
-        .map_err(|e| ApiError::Internal(e.to_string()))?
-        .ok_or_else(|| ApiError::NotFound(format!("Novel {} not found", novel_id)))?;
+    // 先检查小说是否存在且当前用户可以删除
+    let novel = state.access.check_novel_mut(novel_id, current_user_id(user)).await?;
 
     tracing::info!(novel_id = %novel_id, title = %novel.title, "Novel deleting");
 
     // 立即发送 deleting 事件
-    state.event_publisher.publish_novel_deleting(novel_id);
+    let owner = novel.user_id;
+    state.event_publisher.publish_novel_deleting(novel_id, owner);
 
     // 异步执行删除
     let state_clone = state.clone();
@@ -334,7 +446,7 @@ pub async fn delete_novel(
         match state_clone.delete_novel_handler.handle(command).await {
             Ok(_) => {
                 // 删除本地文件
-                let file_path = std::path::PathBuf::from("data/novels").join(format!("{}.txt", novel_id));
+                let file_path = PathBuf::from(NOVELS_DIR).join(format!("{}.txt", novel_id));
                 if file_path.exists() {
                     if let Err(e) = tokio::fs::remove_file(&file_path).await {
                         tracing::warn!("Failed to delete novel file: {}", e);
@@ -342,11 +454,11 @@ pub async fn delete_novel(
                 }
 
                 tracing::info!(novel_id = %novel_id, "Novel deleted");
-                state_clone.event_publisher.publish_novel_deleted(novel_id);
+                state_clone.event_publisher.publish_novel_deleted(novel_id, owner);
             }
             Err(e) => {
                 tracing::error!(novel_id = %novel_id, error = %e, "Novel delete failed");
-                state_clone.event_publisher.publish_novel_delete_failed(novel_id, &e.to_string());
+                state_clone.event_publisher.publish_novel_delete_failed(novel_id, &e.to_string(), owner);
             }
         }
     });
@@ -357,3 +469,22 @@ pub async fn delete_novel(
         status: "deleting".to_string(),
     })))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_novel_segments_params() {
+        let params = |uri: &str| Query::<NovelSegmentsParams>::try_from_uri(&uri.parse().unwrap());
+
+        let Query(defaults) = params("/api/novels/1/segments").unwrap();
+        assert_eq!((defaults.offset, defaults.limit), (0, default_page_limit()));
+        let Query(explicit) = params("/api/novels/1/segments?offset=20&limit=10").unwrap();
+        assert_eq!((explicit.offset, explicit.limit), (20, 10));
+        // 兼容旧参数名
+        let Query(legacy) = params("/api/novels/1/segments?start=5").unwrap();
+        assert_eq!(legacy.offset, 5);
+        assert!(params("/api/novels/1/segments?offset=-1").is_err());
+    }
+}
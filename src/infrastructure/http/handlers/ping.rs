@@ -2,8 +2,12 @@
 //!
 //! Health check endpoint similar to OpenSubsonic ping
 
-use axum::Json;
+use axum::{extract::State, http::StatusCode, Json};
 use serde::Serialize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use crate::infrastructure::http::state::AppState;
 
 /// Ping 响应
 #[derive(Serialize)]
@@ -19,3 +23,20 @@ pub async fn ping() -> Json<PingResponse> {
         version: env!("CARGO_PKG_VERSION"),
     })
 }
+
+/// Ready 响应
+#[derive(Serialize)]
+pub struct ReadyResponse {
+    pub ready: bool,
+}
+
+/// Ready endpoint - 就绪检查（TTS 预热完成前返回 503）
+pub async fn ready(State(state): State<Arc<AppState>>) -> (StatusCode, Json<ReadyResponse>) {
+    let ready = state.ready.load(Ordering::Acquire);
+    let status = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(ReadyResponse { ready }))
+}
@@ -0,0 +1,128 @@
+//! Pregenerate HTTP Handlers - 整书预生成
+
+use axum::{
+    extract::{Path, State},
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::Instrument;
+use uuid::Uuid;
+
+use crate::application::ports::{PregenerationJob, PregenerationJobState};
+use crate::application::{
+    current_request_id, with_request_id, ControlPregeneration, GetPregenerationJob, PregenerateNovel,
+    PregenerationAction,
+};
+use crate::domain::voice::SynthesisParams;
+use crate::infrastructure::http::dto::ApiResponse;
+use crate::infrastructure::http::error::ApiError;
+use crate::infrastructure::http::middleware::{current_user_id, CurrentUser};
+use crate::infrastructure::http::state::AppState;
+
+// ============================================================================
+// DTOs
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct PregenerateNovelRequest {
+    pub novel_id: Uuid,
+    pub voice_id: Uuid,
+    #[serde(default)]
+    pub params: SynthesisParams,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PregenerationJobResponse {
+    pub job_id: String,
+    pub novel_id: Uuid,
+    pub voice_id: Uuid,
+    pub state: PregenerationJobState,
+    pub completed_segments: usize,
+    pub failed_segments: usize,
+    pub total_segments: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub created_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<String>,
+}
+
+impl From<PregenerationJob> for PregenerationJobResponse {
+    fn from(job: PregenerationJob) -> Self {
+        Self {
+            job_id: job.job_id,
+            novel_id: job.novel_id,
+            voice_id: job.voice_id,
+            state: job.state,
+            completed_segments: job.completed_segments,
+            failed_segments: job.failed_segments,
+            total_segments: job.total_segments,
+            error: job.error_message,
+            created_at: job.created_at.to_rfc3339(),
+            completed_at: job.completed_at.map(|t| t.to_rfc3339()),
+        }
+    }
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// 开始整书预生成（后台执行，进度通过全局 WS 事件通知）
+pub async fn pregenerate_novel(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    Json(req): Json<PregenerateNovelRequest>,
+) -> Result<Json<ApiResponse<PregenerationJobResponse>>, ApiError> {
+    let user_id = current_user_id(user);
+    state.access.check_novel(req.novel_id, user_id).await?;
+    state.access.check_voice(req.voice_id, user_id).await?;
+    let cmd = PregenerateNovel {
+        novel_id: req.novel_id,
+        voice_id: req.voice_id,
+        params: req.params,
+        user_id,
+    };
+
+    let job = state.pregenerate_novel_handler.handle(&cmd).await?;
+
+    let state_clone = state.clone();
+    let job_id = job.job_id.clone();
+    // 后台任务沿用本次请求的 ID，期间提交的推理任务可追溯到发起请求
+    let request_id = current_request_id();
+    tokio::spawn(
+        with_request_id(request_id, async move {
+            state_clone.pregenerate_novel_handler.run(job_id, cmd).await;
+        })
+        .in_current_span(),
+    );
+
+    Ok(Json(ApiResponse::success(PregenerationJobResponse::from(job))))
+}
+
+/// 查询预生成任务状态
+pub async fn get_pregeneration_job(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    Path(job_id): Path<String>,
+) -> Result<Json<ApiResponse<PregenerationJobResponse>>, ApiError> {
+    state.access.check_pregeneration_job(&job_id, current_user_id(user))?;
+    let job = state
+        .get_pregeneration_job_handler
+        .handle(GetPregenerationJob { job_id })?;
+    Ok(Json(ApiResponse::success(PregenerationJobResponse::from(job))))
+}
+
+/// 暂停 / 恢复 / 取消预生成任务
+pub async fn control_pregeneration(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    Path((job_id, action)): Path<(String, PregenerationAction)>,
+) -> Result<Json<ApiResponse<PregenerationJobResponse>>, ApiError> {
+    state.access.check_pregeneration_job(&job_id, current_user_id(user))?;
+    let job = state
+        .control_pregeneration_handler
+        .handle(ControlPregeneration { job_id, action })?;
+    Ok(Json(ApiResponse::success(PregenerationJobResponse::from(job))))
+}
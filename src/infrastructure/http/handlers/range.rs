@@ -0,0 +1,222 @@
+//! HTTP Range 请求
+//!
+//! 仅支持单区间（`bytes=start-end` / `bytes=start-` / `bytes=-suffix`），
+//! 多区间或格式无法识别时按完整内容返回。
+
+use axum::{
+    body::{Body, Bytes},
+    http::{header, response::Builder, HeaderMap, StatusCode},
+    response::Response,
+};
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tokio_util::io::ReaderStream;
+
+/// Range 请求头解析结果
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum ByteRange {
+    /// 单个可满足的区间（闭区间）
+    Satisfiable { start: u64, end: u64 },
+    /// 区间超出文件范围（416）
+    Unsatisfiable,
+    /// 格式无法识别或多区间请求，按完整文件返回
+    Ignored,
+}
+
+/// 解析 `Range: bytes=start-end` / `bytes=start-` / `bytes=-suffix`
+pub(super) fn parse_byte_range(value: &str, file_size: u64) -> ByteRange {
+    let Some(spec) = value.trim().strip_prefix("bytes=") else {
+        return ByteRange::Ignored;
+    };
+    if spec.contains(',') {
+        return ByteRange::Ignored;
+    }
+    let Some((start, end)) = spec.trim().split_once('-') else {
+        return ByteRange::Ignored;
+    };
+    let (start, end) = (start.trim(), end.trim());
+
+    let (start, end) = if start.is_empty() {
+        // 后缀区间：最后 N 个字节
+        let Ok(suffix) = end.parse::<u64>() else {
+            return ByteRange::Ignored;
+        };
+        if suffix == 0 || file_size == 0 {
+            return ByteRange::Unsatisfiable;
+        }
+        (file_size.saturating_sub(suffix), file_size - 1)
+    } else {
+        let Ok(start) = start.parse::<u64>() else {
+            return ByteRange::Ignored;
+        };
+        let end = if end.is_empty() {
+            file_size.saturating_sub(1)
+        } else {
+            match end.parse::<u64>() {
+                Ok(end) if end >= start => end.min(file_size.saturating_sub(1)),
+                _ => return ByteRange::Ignored,
+            }
+        };
+        if start >= file_size {
+            return ByteRange::Unsatisfiable;
+        }
+        (start, end)
+    };
+
+    ByteRange::Satisfiable { start, end }
+}
+
+/// 读取请求头中的 Range
+pub(super) fn requested_range(headers: &HeaderMap, size: u64) -> Option<ByteRange> {
+    headers
+        .get(header::RANGE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| parse_byte_range(v, size))
+}
+
+/// 按 Range 请求返回内存中的完整内容或其中一段（200 / 206 / 416），区间直接切片不复制
+pub(super) fn ranged_response(builder: Builder, data: Bytes, headers: &HeaderMap) -> Response {
+    let size = data.len() as u64;
+    let builder = builder.header(header::ACCEPT_RANGES, "bytes");
+
+    match requested_range(headers, size) {
+        None | Some(ByteRange::Ignored) => builder
+            .status(StatusCode::OK)
+            .header(header::CONTENT_LENGTH, size)
+            .body(Body::from(data))
+            .unwrap(),
+        Some(ByteRange::Unsatisfiable) => builder
+            .status(StatusCode::RANGE_NOT_SATISFIABLE)
+            .header(header::CONTENT_RANGE, format!("bytes */{}", size))
+            .body(Body::empty())
+            .unwrap(),
+        Some(ByteRange::Satisfiable { start, end }) => {
+            let part = data.slice(start as usize..=end as usize);
+            builder
+                .status(StatusCode::PARTIAL_CONTENT)
+                .header(header::CONTENT_LENGTH, part.len())
+                .header(header::CONTENT_RANGE, format!("bytes {}-{}/{}", start, end, size))
+                .body(Body::from(part))
+                .unwrap()
+        }
+    }
+}
+
+/// 按 Range 请求流式返回文件的完整内容或其中一段（200 / 206 / 416），不整体读入内存
+pub(super) async fn ranged_file_response(
+    builder: Builder,
+    mut file: tokio::fs::File,
+    headers: &HeaderMap,
+) -> std::io::Result<Response> {
+    let size = file.metadata().await?.len();
+    let builder = builder.header(header::ACCEPT_RANGES, "bytes");
+
+    Ok(match requested_range(headers, size) {
+        None | Some(ByteRange::Ignored) => builder
+            .status(StatusCode::OK)
+            .header(header::CONTENT_LENGTH, size)
+            .body(Body::from_stream(ReaderStream::new(file)))
+            .unwrap(),
+        Some(ByteRange::Unsatisfiable) => builder
+            .status(StatusCode::RANGE_NOT_SATISFIABLE)
+            .header(header::CONTENT_RANGE, format!("bytes */{}", size))
+            .body(Body::empty())
+            .unwrap(),
+        Some(ByteRange::Satisfiable { start, end }) => {
+            file.seek(std::io::SeekFrom::Start(start)).await?;
+            let length = end - start + 1;
+            builder
+                .status(StatusCode::PARTIAL_CONTENT)
+                .header(header::CONTENT_LENGTH, length)
+                .header(header::CONTENT_RANGE, format!("bytes {}-{}/{}", start, end, size))
+                .body(Body::from_stream(ReaderStream::new(file.take(length))))
+                .unwrap()
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_byte_range() {
+        assert_eq!(
+            parse_byte_range("bytes=0-99", 1000),
+            ByteRange::Satisfiable { start: 0, end: 99 }
+        );
+        assert_eq!(
+            parse_byte_range("bytes=500-", 1000),
+            ByteRange::Satisfiable { start: 500, end: 999 }
+        );
+        assert_eq!(
+            parse_byte_range("bytes=-200", 1000),
+            ByteRange::Satisfiable { start: 800, end: 999 }
+        );
+        assert_eq!(
+            parse_byte_range("bytes=900-2000", 1000),
+            ByteRange::Satisfiable { start: 900, end: 999 }
+        );
+        assert_eq!(parse_byte_range("bytes=1000-", 1000), ByteRange::Unsatisfiable);
+        assert_eq!(parse_byte_range("bytes=0-1,5-9", 1000), ByteRange::Ignored);
+        assert_eq!(parse_byte_range("items=0-1", 1000), ByteRange::Ignored);
+    }
+
+    #[tokio::test]
+    async fn test_ranged_response() {
+        let respond = |range: Option<&str>| {
+            let mut headers = HeaderMap::new();
+            if let Some(range) = range {
+                headers.insert(header::RANGE, range.parse().unwrap());
+            }
+            ranged_response(Response::builder(), (0u8..100).collect(), &headers)
+        };
+
+        let full = respond(None);
+        assert_eq!(full.status(), StatusCode::OK);
+        assert_eq!(full.headers()[header::ACCEPT_RANGES], "bytes");
+        assert_eq!(full.headers()[header::CONTENT_LENGTH], "100");
+
+        let partial = respond(Some("bytes=10-19"));
+        assert_eq!(partial.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(partial.headers()[header::CONTENT_RANGE], "bytes 10-19/100");
+        let body = axum::body::to_bytes(partial.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body.as_ref(), (10u8..20).collect::<Vec<_>>().as_slice());
+
+        let unsatisfiable = respond(Some("bytes=100-"));
+        assert_eq!(unsatisfiable.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+        assert_eq!(unsatisfiable.headers()[header::CONTENT_RANGE], "bytes */100");
+    }
+
+    #[tokio::test]
+    async fn test_ranged_file_response() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.bin");
+        std::fs::write(&path, (0u8..100).collect::<Vec<_>>()).unwrap();
+        let respond = |range: Option<&'static str>| {
+            let path = path.clone();
+            async move {
+                let mut headers = HeaderMap::new();
+                if let Some(range) = range {
+                    headers.insert(header::RANGE, range.parse().unwrap());
+                }
+                let file = tokio::fs::File::open(path).await.unwrap();
+                ranged_file_response(Response::builder(), file, &headers).await.unwrap()
+            }
+        };
+
+        let full = respond(None).await;
+        assert_eq!(full.status(), StatusCode::OK);
+        assert_eq!(full.headers()[header::CONTENT_LENGTH], "100");
+        let body = axum::body::to_bytes(full.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body.len(), 100);
+
+        let partial = respond(Some("bytes=-10")).await;
+        assert_eq!(partial.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(partial.headers()[header::CONTENT_RANGE], "bytes 90-99/100");
+        let body = axum::body::to_bytes(partial.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body.as_ref(), (90u8..100).collect::<Vec<_>>().as_slice());
+
+        let unsatisfiable = respond(Some("bytes=200-")).await;
+        assert_eq!(unsatisfiable.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+    }
+}
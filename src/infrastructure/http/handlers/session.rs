@@ -1,15 +1,25 @@
 //! Session Handlers - V2 架构
 
-use axum::{extract::State, Json};
+use axum::{
+    extract::{Path, Query, State},
+    Extension, Json,
+};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::application::{
-    ChangeVoiceCommand, CloseSessionCommand, PlayCommand, SeekCommand,
+    ChangeVoiceCommand, ClaimSessionCommand, CloseSessionCommand, GetSessionSegments, GetSessionSnapshot,
+    HeartbeatCommand, ListActiveSessions, ListSessions, NextSegmentCommand, PageRequest, PlayCommand, PlayResponse,
+    PrefetchSegmentsCommand, PrevSegmentCommand, ReportPositionCommand, RestoreSessionCommand, SeekCommand,
+    SessionView, StepSegmentResponse, UpdateWindowCommand,
 };
-use crate::infrastructure::http::dto::ApiResponse;
+use crate::application::ports::{PlaybackHints, SessionState, WindowConfig};
+use crate::domain::voice::SynthesisParams;
+use crate::infrastructure::http::dto::{default_page_limit, ApiResponse, PageResponse};
+use crate::infrastructure::http::handlers::TaskInfoDto;
 use crate::infrastructure::http::error::ApiError;
+use crate::infrastructure::http::middleware::{current_user_id, ClientIp, CurrentUser};
 use crate::infrastructure::http::state::AppState;
 
 // ============================================================================
@@ -19,9 +29,54 @@ use crate::infrastructure::http::state::AppState;
 #[derive(Debug, Deserialize)]
 pub struct PlayRequest {
     pub novel_id: Uuid,
-    pub voice_id: Uuid,
+    /// 省略时使用默认音色设置
+    #[serde(default)]
+    pub voice_id: Option<Uuid>,
+    /// 省略时新会话从小说的阅读进度继续（没有进度为 0），复用的会话保持已保存的进度
+    #[serde(default)]
+    pub start_index: Option<u32>,
+    /// 复用同一小说、音色和合成参数的活动会话（客户端重连时避免重复预取）
+    #[serde(default)]
+    pub reuse: bool,
+    /// 会话级合成参数（语速/情感/能量）
+    #[serde(default)]
+    pub params: SynthesisParams,
+    /// 服务端变速播放速度（0.5 - 2.0，保持音高），省略为原速
+    #[serde(default)]
+    pub playback_speed: Option<f32>,
+    /// 片段衔接提示（gap_ms / crossfade_ms），章节导出未指定时使用
+    #[serde(default)]
+    pub playback_hints: PlaybackHints,
+    /// 预取窗口（before / after），省略的一侧使用配置默认值
     #[serde(default)]
-    pub start_index: u32,
+    pub window: WindowRequest,
+    /// 自动前进模式：向后预取到窗口上限，下一片段就绪时通过会话 WS 推送 NextSegmentReady；
+    /// 省略时新会话关闭，复用的会话保持原设置
+    #[serde(default)]
+    pub auto_advance: Option<bool>,
+}
+
+/// 预取窗口请求
+#[derive(Debug, Default, Deserialize)]
+pub struct WindowRequest {
+    pub before: Option<usize>,
+    pub after: Option<usize>,
+}
+
+/// 预取窗口
+#[derive(Debug, Serialize)]
+pub struct WindowDto {
+    pub before: usize,
+    pub after: usize,
+}
+
+impl From<WindowConfig> for WindowDto {
+    fn from(window: WindowConfig) -> Self {
+        Self {
+            before: window.before,
+            after: window.after,
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -30,28 +85,118 @@ pub struct PlayResponseDto {
     pub novel_id: Uuid,
     pub voice_id: Uuid,
     pub current_index: u32,
+    pub offset_ms: u32,
+    pub window: WindowDto,
+    pub reused: bool,
+    /// 起始位置来自已保存的进度（未指定 start_index）
+    pub resumed: bool,
+    pub auto_advance: bool,
 }
 
 pub async fn play(
     State(state): State<Arc<AppState>>,
+    client_ip: Option<Extension<ClientIp>>,
+    user: Option<Extension<CurrentUser>>,
     Json(req): Json<PlayRequest>,
 ) -> Result<Json<ApiResponse<PlayResponseDto>>, ApiError> {
     let cmd = PlayCommand {
         novel_id: req.novel_id,
         voice_id: req.voice_id,
         start_index: req.start_index,
+        reuse: req.reuse,
+        params: req.params,
+        playback_speed: req.playback_speed,
+        playback_hints: req.playback_hints,
+        window_before: req.window.before,
+        window_after: req.window.after,
+        offset_ms: None,
+        auto_advance: req.auto_advance,
+        client: client_ip.map(|Extension(ClientIp(ip))| ip),
+        user_id: current_user_id(user),
     };
 
     let result = state.play_handler.handle(cmd).await?;
 
-    Ok(Json(ApiResponse::success(PlayResponseDto {
-        session_id: result.session_id,
-        novel_id: result.novel_id,
-        voice_id: result.voice_id,
-        current_index: result.current_index,
+    Ok(Json(ApiResponse::success(result.into())))
+}
+
+impl From<PlayResponse> for PlayResponseDto {
+    fn from(result: PlayResponse) -> Self {
+        Self {
+            session_id: result.session_id,
+            novel_id: result.novel_id,
+            voice_id: result.voice_id,
+            current_index: result.current_index,
+            offset_ms: result.offset_ms,
+            window: result.window.into(),
+            reused: result.reused,
+            resumed: result.resumed,
+            auto_advance: result.auto_advance,
+        }
+    }
+}
+
+// ============================================================================
+// Snapshot / Restore
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+pub struct SessionSnapshotDto {
+    /// 可分享的快照令牌（POST /api/session/restore 恢复）
+    pub token: String,
+    pub novel_id: Uuid,
+    pub voice_id: Uuid,
+    pub current_index: u32,
+    pub offset_ms: u32,
+    pub window: WindowDto,
+    pub playback_speed: Option<f32>,
+}
+
+/// 获取会话快照
+pub async fn get_session_snapshot(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    Path(session_id): Path<String>,
+) -> Result<Json<ApiResponse<SessionSnapshotDto>>, ApiError> {
+    state.access.check_session(&session_id, current_user_id(user))?;
+    let snapshot = state
+        .get_session_snapshot_handler
+        .handle(GetSessionSnapshot { session_id })?;
+
+    Ok(Json(ApiResponse::success(SessionSnapshotDto {
+        token: snapshot.encode(),
+        novel_id: snapshot.novel_id,
+        voice_id: snapshot.voice_id,
+        current_index: snapshot.current_index,
+        offset_ms: snapshot.offset_ms,
+        window: WindowConfig::new(snapshot.window_before, snapshot.window_after).into(),
+        playback_speed: snapshot.playback_speed,
     })))
 }
 
+#[derive(Debug, Deserialize)]
+pub struct RestoreSessionRequest {
+    pub token: String,
+}
+
+/// 从快照令牌恢复为新会话
+pub async fn restore_session(
+    State(state): State<Arc<AppState>>,
+    client_ip: Option<Extension<ClientIp>>,
+    user: Option<Extension<CurrentUser>>,
+    Json(req): Json<RestoreSessionRequest>,
+) -> Result<Json<ApiResponse<PlayResponseDto>>, ApiError> {
+    let cmd = RestoreSessionCommand {
+        token: req.token,
+        client: client_ip.map(|Extension(ClientIp(ip))| ip),
+        user_id: current_user_id(user),
+    };
+
+    let result = state.play_handler.handle_restore(cmd).await?;
+
+    Ok(Json(ApiResponse::success(result.into())))
+}
+
 // ============================================================================
 // Seek
 // ============================================================================
@@ -71,8 +216,10 @@ pub struct SeekResponseDto {
 
 pub async fn seek(
     State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
     Json(req): Json<SeekRequest>,
 ) -> Result<Json<ApiResponse<SeekResponseDto>>, ApiError> {
+    state.access.check_session(&req.session_id, current_user_id(user))?;
     let cmd = SeekCommand {
         session_id: req.session_id,
         segment_index: req.segment_index,
@@ -87,6 +234,278 @@ pub async fn seek(
     })))
 }
 
+// ============================================================================
+// Next / Prev Segment
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct StepSegmentRequest {
+    pub session_id: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StepSegmentResponseDto {
+    pub session_id: String,
+    pub current_index: u32,
+    /// 已在第一个/最后一个片段时为 false
+    pub moved: bool,
+    pub total_segments: usize,
+    pub content: String,
+    pub char_count: usize,
+    /// ready / pending / inferring / failed / missing
+    pub audio_status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<String>,
+}
+
+impl From<StepSegmentResponse> for StepSegmentResponseDto {
+    fn from(result: StepSegmentResponse) -> Self {
+        Self {
+            session_id: result.session_id,
+            current_index: result.current_index,
+            moved: result.moved,
+            total_segments: result.total_segments,
+            content: result.content,
+            char_count: result.char_count,
+            audio_status: result.status.readiness.as_str().to_string(),
+            task_id: result.status.task_id,
+        }
+    }
+}
+
+/// 前进到下一个片段
+pub async fn next_segment(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    Json(req): Json<StepSegmentRequest>,
+) -> Result<Json<ApiResponse<StepSegmentResponseDto>>, ApiError> {
+    state.access.check_session(&req.session_id, current_user_id(user))?;
+    let cmd = NextSegmentCommand {
+        session_id: req.session_id,
+    };
+
+    let result = state.step_segment_handler.handle_next(cmd).await?;
+    Ok(Json(ApiResponse::success(result.into())))
+}
+
+/// 回到上一个片段
+pub async fn prev_segment(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    Json(req): Json<StepSegmentRequest>,
+) -> Result<Json<ApiResponse<StepSegmentResponseDto>>, ApiError> {
+    state.access.check_session(&req.session_id, current_user_id(user))?;
+    let cmd = PrevSegmentCommand {
+        session_id: req.session_id,
+    };
+
+    let result = state.step_segment_handler.handle_prev(cmd).await?;
+    Ok(Json(ApiResponse::success(result.into())))
+}
+
+// ============================================================================
+// Position
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct ReportPositionRequest {
+    pub segment_index: u32,
+    /// 片段内偏移（毫秒）
+    #[serde(default)]
+    pub offset_ms: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReportPositionResponseDto {
+    pub session_id: String,
+    pub current_index: u32,
+    pub offset_ms: u32,
+}
+
+pub async fn report_position(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    Path(session_id): Path<String>,
+    Json(req): Json<ReportPositionRequest>,
+) -> Result<Json<ApiResponse<ReportPositionResponseDto>>, ApiError> {
+    state.access.check_session(&session_id, current_user_id(user))?;
+    let cmd = ReportPositionCommand {
+        session_id,
+        segment_index: req.segment_index,
+        offset_ms: req.offset_ms,
+    };
+
+    let result = state.report_position_handler.handle(cmd).await?;
+
+    Ok(Json(ApiResponse::success(ReportPositionResponseDto {
+        session_id: result.session_id,
+        current_index: result.current_index,
+        offset_ms: result.offset_ms,
+    })))
+}
+
+// ============================================================================
+// Heartbeat
+// ============================================================================
+
+/// 预取窗口的就绪概览
+#[derive(Debug, Serialize)]
+pub struct WindowReadinessDto {
+    /// 窗口范围（闭区间）
+    pub start: u32,
+    pub end: u32,
+    pub ready: usize,
+    pub pending: usize,
+    pub inferring: usize,
+    pub failed: usize,
+    pub missing: usize,
+    /// 从当前片段起连续就绪的片段数
+    pub ready_ahead: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HeartbeatResponseDto {
+    pub session_id: String,
+    pub current_index: u32,
+    pub offset_ms: u32,
+    pub total_segments: usize,
+    /// 小说没有片段时为 null
+    pub window: Option<WindowReadinessDto>,
+}
+
+pub async fn heartbeat(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    Path(session_id): Path<String>,
+) -> Result<Json<ApiResponse<HeartbeatResponseDto>>, ApiError> {
+    state.access.check_session(&session_id, current_user_id(user))?;
+    let result = state
+        .heartbeat_handler
+        .handle(HeartbeatCommand { session_id })
+        .await?;
+
+    let readiness = result.readiness;
+    Ok(Json(ApiResponse::success(HeartbeatResponseDto {
+        session_id: result.session_id,
+        current_index: result.current_index,
+        offset_ms: result.offset_ms,
+        total_segments: result.total_segments,
+        window: result.window.map(|(start, end)| WindowReadinessDto {
+            start,
+            end,
+            ready: readiness.ready,
+            pending: readiness.pending,
+            inferring: readiness.inferring,
+            failed: readiness.failed,
+            missing: readiness.missing,
+            ready_ahead: readiness.ready_ahead,
+        }),
+    })))
+}
+
+// ============================================================================
+// Batch Prefetch
+// ============================================================================
+
+/// 批量预取请求：片段列表和范围可同时指定
+#[derive(Debug, Deserialize)]
+pub struct PrefetchSegmentsRequest {
+    #[serde(default)]
+    pub segment_indices: Vec<u32>,
+    /// 范围起点（与 end 同时指定，闭区间）
+    #[serde(default)]
+    pub start: Option<u32>,
+    #[serde(default)]
+    pub end: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PrefetchSegmentsResponseDto {
+    pub session_id: String,
+    /// 新提交推理的片段数
+    pub submitted: usize,
+    /// 已缓存的片段数
+    pub cached: usize,
+    /// 已在排队或推理中的片段数
+    pub queued: usize,
+    /// 排队等待推理的任务数
+    pub queue_depth: usize,
+}
+
+pub async fn prefetch_segments(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    Path(session_id): Path<String>,
+    Json(req): Json<PrefetchSegmentsRequest>,
+) -> Result<Json<ApiResponse<PrefetchSegmentsResponseDto>>, ApiError> {
+    state.access.check_session(&session_id, current_user_id(user))?;
+    let range = match (req.start, req.end) {
+        (Some(start), Some(end)) => Some((start, end)),
+        (None, None) => None,
+        _ => {
+            return Err(ApiError::BadRequest(
+                "start and end must be specified together".to_string(),
+            ))
+        }
+    };
+    let cmd = PrefetchSegmentsCommand {
+        session_id: session_id.clone(),
+        segment_indices: req.segment_indices,
+        range,
+    };
+
+    let result = state.prefetch_segments_handler.handle(cmd).await?;
+
+    Ok(Json(ApiResponse::success(PrefetchSegmentsResponseDto {
+        session_id,
+        submitted: result.submitted,
+        cached: result.cached,
+        queued: result.queued,
+        queue_depth: result.queue_depth,
+    })))
+}
+
+// ============================================================================
+// Window
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateWindowRequest {
+    pub session_id: String,
+    /// 省略时保持不变
+    #[serde(default)]
+    pub before: Option<usize>,
+    /// 省略时保持不变
+    #[serde(default)]
+    pub after: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UpdateWindowResponseDto {
+    pub session_id: String,
+    pub window: WindowDto,
+}
+
+pub async fn update_window(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    Json(req): Json<UpdateWindowRequest>,
+) -> Result<Json<ApiResponse<UpdateWindowResponseDto>>, ApiError> {
+    state.access.check_session(&req.session_id, current_user_id(user))?;
+    let cmd = UpdateWindowCommand {
+        session_id: req.session_id,
+        before: req.before,
+        after: req.after,
+    };
+
+    let result = state.update_window_handler.handle(cmd).await?;
+
+    Ok(Json(ApiResponse::success(UpdateWindowResponseDto {
+        session_id: result.session_id,
+        window: result.window.into(),
+    })))
+}
+
 // ============================================================================
 // Change Voice
 // ============================================================================
@@ -95,6 +514,12 @@ pub async fn seek(
 pub struct ChangeVoiceRequest {
     pub session_id: String,
     pub voice_id: Uuid,
+    /// 省略时保留当前会话参数
+    #[serde(default)]
+    pub params: Option<SynthesisParams>,
+    /// 新音色的音频就绪前继续返回旧音色已就绪的片段，结束时通过会话 WS 推送 VoiceHandoffCompleted
+    #[serde(default)]
+    pub handoff: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -102,15 +527,23 @@ pub struct ChangeVoiceResponseDto {
     pub session_id: String,
     pub voice_id: Uuid,
     pub cancelled_tasks: usize,
+    /// 过渡中的旧音色
+    pub handoff_voice_id: Option<Uuid>,
 }
 
 pub async fn change_voice(
     State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
     Json(req): Json<ChangeVoiceRequest>,
 ) -> Result<Json<ApiResponse<ChangeVoiceResponseDto>>, ApiError> {
+    let user_id = current_user_id(user);
+    state.access.check_session(&req.session_id, user_id)?;
+    state.access.check_voice(req.voice_id, user_id).await?;
     let cmd = ChangeVoiceCommand {
         session_id: req.session_id,
         voice_id: req.voice_id,
+        params: req.params,
+        handoff: req.handoff,
     };
 
     let result = state.change_voice_handler.handle(cmd).await?;
@@ -119,6 +552,64 @@ pub async fn change_voice(
         session_id: result.session_id,
         voice_id: result.voice_id,
         cancelled_tasks: result.cancelled_count,
+        handoff_voice_id: result.handoff_voice_id,
+    })))
+}
+
+// ============================================================================
+// Claim Session
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct ClaimSessionRequest {
+    pub session_id: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClaimSessionResponseDto {
+    pub session_id: String,
+    pub novel_id: Uuid,
+    pub voice_id: Uuid,
+    pub current_index: u32,
+    pub offset_ms: u32,
+    pub params: SynthesisParams,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub playback_speed: Option<f32>,
+    pub window: WindowDto,
+    /// 排队或推理中的任务，完成事件通过新的会话 WebSocket 推送
+    pub tasks: Vec<TaskInfoDto>,
+}
+
+pub async fn claim_session(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    Json(req): Json<ClaimSessionRequest>,
+) -> Result<Json<ApiResponse<ClaimSessionResponseDto>>, ApiError> {
+    state.access.check_session(&req.session_id, current_user_id(user))?;
+    let cmd = ClaimSessionCommand {
+        session_id: req.session_id,
+    };
+
+    let result = state.claim_session_handler.handle(cmd).await?;
+
+    Ok(Json(ApiResponse::success(ClaimSessionResponseDto {
+        session_id: result.session_id,
+        novel_id: result.novel_id,
+        voice_id: result.voice_id,
+        current_index: result.current_index,
+        offset_ms: result.offset_ms,
+        params: result.params,
+        playback_speed: result.playback_speed,
+        window: result.window.into(),
+        tasks: result
+            .tasks
+            .into_iter()
+            .map(|t| TaskInfoDto {
+                task_id: t.task_id,
+                segment_index: t.segment_index,
+                state: t.state.as_str().to_string(),
+            })
+            .collect(),
     })))
 }
 
@@ -138,8 +629,10 @@ pub struct CloseSessionResponseDto {
 
 pub async fn close_session(
     State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
     Json(req): Json<CloseSessionRequest>,
 ) -> Result<Json<ApiResponse<CloseSessionResponseDto>>, ApiError> {
+    state.access.check_session(&req.session_id, current_user_id(user))?;
     let cmd = CloseSessionCommand {
         session_id: req.session_id,
     };
@@ -150,3 +643,174 @@ pub async fn close_session(
         session_id: result.session_id,
     })))
 }
+
+// ============================================================================
+// List / Inspect Sessions
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct ListSessionsRequest {
+    pub novel_id: Option<Uuid>,
+    /// idle / playing
+    pub state: Option<String>,
+    #[serde(default)]
+    pub offset: usize,
+    #[serde(default = "default_page_limit")]
+    pub limit: usize,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListActiveSessionsRequest {
+    pub novel_id: Option<Uuid>,
+    #[serde(default)]
+    pub offset: usize,
+    #[serde(default = "default_page_limit")]
+    pub limit: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SessionSummaryDto {
+    pub session_id: String,
+    pub novel_id: Uuid,
+    pub voice_id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<Uuid>,
+    pub current_index: u32,
+    pub offset_ms: u32,
+    pub state: String,
+    pub background: bool,
+    pub auto_advance: bool,
+    pub window: WindowDto,
+    pub pending_tasks: usize,
+    pub inferring_tasks: usize,
+    pub created_at: String,
+    pub last_activity: String,
+}
+
+impl From<SessionView> for SessionSummaryDto {
+    fn from(view: SessionView) -> Self {
+        Self {
+            session_id: view.id,
+            novel_id: view.novel_id,
+            voice_id: view.voice_id,
+            user_id: view.user_id,
+            current_index: view.current_index,
+            offset_ms: view.offset_ms,
+            state: view.state.as_str().to_string(),
+            background: view.background,
+            auto_advance: view.auto_advance,
+            window: view.window.into(),
+            pending_tasks: view.pending_tasks,
+            inferring_tasks: view.inferring_tasks,
+            created_at: view.created_at.to_rfc3339(),
+            last_activity: view.last_activity.to_rfc3339(),
+        }
+    }
+}
+
+/// 列出会话（可按 novel_id / state 过滤，?offset=&limit= 分页）
+pub async fn list_sessions(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    Query(req): Query<ListSessionsRequest>,
+) -> Result<Json<ApiResponse<PageResponse<SessionSummaryDto>>>, ApiError> {
+    let session_state = req
+        .state
+        .map(|s| {
+            SessionState::from_str(&s)
+                .ok_or_else(|| ApiError::BadRequest(format!("Invalid session state: {}", s)))
+        })
+        .transpose()?;
+    let query = ListSessions {
+        novel_id: req.novel_id,
+        state: session_state,
+        user_id: current_user_id(user),
+        page: PageRequest::new(req.offset, req.limit),
+    };
+
+    let sessions = state.list_sessions_handler.handle(query)?;
+    Ok(Json(ApiResponse::success(PageResponse::from_page(
+        sessions,
+        SessionSummaryDto::from,
+    ))))
+}
+
+/// 列出活动会话（最近有活动或有进行中的推理任务）
+pub async fn list_active_sessions(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    Query(req): Query<ListActiveSessionsRequest>,
+) -> Result<Json<ApiResponse<PageResponse<SessionSummaryDto>>>, ApiError> {
+    let query = ListActiveSessions {
+        novel_id: req.novel_id,
+        user_id: current_user_id(user),
+        page: PageRequest::new(req.offset, req.limit),
+    };
+
+    let sessions = state.list_active_sessions_handler.handle(query)?;
+    Ok(Json(ApiResponse::success(PageResponse::from_page(
+        sessions,
+        SessionSummaryDto::from,
+    ))))
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct SessionSegmentsRequest {
+    /// 省略时使用会话的预取窗口
+    pub start: Option<u32>,
+    pub end: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SessionSegmentDto {
+    pub index: u32,
+    /// ready / pending / inferring / failed / missing
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_message: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SessionSegmentsResponseDto {
+    pub session_id: String,
+    pub current_index: u32,
+    pub start: u32,
+    pub end: u32,
+    pub segments: Vec<SessionSegmentDto>,
+}
+
+/// 获取会话片段的就绪状态
+pub async fn get_session_segments(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    Path(session_id): Path<String>,
+    Query(req): Query<SessionSegmentsRequest>,
+) -> Result<Json<ApiResponse<SessionSegmentsResponseDto>>, ApiError> {
+    state.access.check_session(&session_id, current_user_id(user))?;
+    let query = GetSessionSegments {
+        session_id,
+        start: req.start,
+        end: req.end,
+    };
+
+    let result = state.get_session_segments_handler.handle(query).await?;
+
+    Ok(Json(ApiResponse::success(SessionSegmentsResponseDto {
+        session_id: result.session_id,
+        current_index: result.current_index,
+        start: result.start,
+        end: result.end,
+        segments: result
+            .segments
+            .into_iter()
+            .map(|s| SessionSegmentDto {
+                index: s.index,
+                status: s.readiness.as_str().to_string(),
+                task_id: s.task_id,
+                error_message: s.error_message,
+            })
+            .collect(),
+    })))
+}
@@ -0,0 +1,57 @@
+//! Settings HTTP Handlers - V2 架构
+
+use axum::{extract::State, Extension, Json};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::application::{GetSettings, UpdateSetting};
+use crate::infrastructure::http::dto::{ApiResponse, Empty};
+use crate::infrastructure::http::error::ApiError;
+use crate::infrastructure::http::middleware::{current_user_id, CurrentUser};
+use crate::infrastructure::http::state::AppState;
+
+// ============================================================================
+// DTOs
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateSettingRequest {
+    pub key: String,
+    /// 为 null 时删除该设置
+    #[serde(default)]
+    pub value: Option<String>,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// 获取当前用户的所有设置
+pub async fn get_settings(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+) -> Result<Json<ApiResponse<BTreeMap<String, String>>>, ApiError> {
+    let query = GetSettings {
+        user_id: current_user_id(user),
+    };
+    let result = state.get_settings_handler.handle(query).await?;
+    Ok(Json(ApiResponse::success(result)))
+}
+
+/// 写入或删除当前用户的设置
+pub async fn update_setting(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    Json(req): Json<UpdateSettingRequest>,
+) -> Result<Json<ApiResponse<Empty>>, ApiError> {
+    let command = UpdateSetting {
+        key: req.key,
+        value: req.value,
+        user_id: current_user_id(user),
+    };
+
+    state.update_setting_handler.handle(command).await?;
+
+    Ok(Json(ApiResponse::ok()))
+}
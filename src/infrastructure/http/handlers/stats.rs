@@ -0,0 +1,109 @@
+//! Stats HTTP Handlers - 收听统计
+
+use axum::{
+    extract::{Query, State},
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::{GetListeningStats, ListeningStatsView};
+use crate::infrastructure::http::dto::ApiResponse;
+use crate::infrastructure::http::error::ApiError;
+use crate::infrastructure::http::middleware::{current_user_id, CurrentUser};
+use crate::infrastructure::http::state::AppState;
+
+/// 未指定时统计最近 7 天
+const DEFAULT_LISTENING_STATS_DAYS: u32 = 7;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ListeningStatsRequest {
+    pub novel_id: Option<Uuid>,
+    /// 最近多少天（含今天，1 - 366）
+    pub days: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DailyListeningDto {
+    /// YYYY-MM-DD（服务器本地日期）
+    pub day: String,
+    pub listened_ms: u64,
+    pub segments: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NovelListeningDto {
+    pub novel_id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub listened_ms: u64,
+    pub segments: u32,
+    pub max_index: u32,
+    pub total_segments: usize,
+    /// 0.0 - 1.0
+    pub completion: f32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListeningStatsResponse {
+    pub from: String,
+    pub to: String,
+    pub listened_ms: u64,
+    pub segments: u32,
+    pub days: Vec<DailyListeningDto>,
+    pub novels: Vec<NovelListeningDto>,
+}
+
+impl From<ListeningStatsView> for ListeningStatsResponse {
+    fn from(view: ListeningStatsView) -> Self {
+        Self {
+            from: view.from.to_string(),
+            to: view.to.to_string(),
+            listened_ms: view.listened_ms,
+            segments: view.segments,
+            days: view
+                .days
+                .into_iter()
+                .map(|d| DailyListeningDto {
+                    day: d.day.to_string(),
+                    listened_ms: d.listened_ms,
+                    segments: d.segments,
+                })
+                .collect(),
+            novels: view
+                .novels
+                .into_iter()
+                .map(|n| NovelListeningDto {
+                    novel_id: n.novel_id,
+                    title: n.title,
+                    listened_ms: n.listened_ms,
+                    segments: n.segments,
+                    max_index: n.max_index,
+                    total_segments: n.total_segments,
+                    completion: n.completion,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// 获取收听统计（按天汇总及各小说完成度）
+pub async fn get_listening_stats(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    Query(req): Query<ListeningStatsRequest>,
+) -> Result<Json<ApiResponse<ListeningStatsResponse>>, ApiError> {
+    let user_id = current_user_id(user);
+    if let Some(novel_id) = req.novel_id {
+        state.access.check_novel(novel_id, user_id).await?;
+    }
+    let query = GetListeningStats {
+        user_id,
+        novel_id: req.novel_id,
+        days: req.days.unwrap_or(DEFAULT_LISTENING_STATS_DAYS),
+    };
+
+    let result = state.get_listening_stats_handler.handle(query).await?;
+    Ok(Json(ApiResponse::success(result.into())))
+}
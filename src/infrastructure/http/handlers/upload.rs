@@ -0,0 +1,199 @@
+//! Multipart 文件流式落盘
+//!
+//! 上传的文件按块写入目标目录下的临时文件，同时计算 MD5（可选校验 UTF-8），
+//! 读取完成后再移动到最终位置，并发的大文件上传不会在内存中缓冲完整内容。
+//! 读取失败、超出大小限制或未移动到最终位置时临时文件自动删除
+
+use axum::extract::multipart::Field;
+use std::path::Path;
+use tempfile::TempPath;
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+
+use crate::infrastructure::http::error::ApiError;
+
+/// 单个上传文件的最大大小（与请求体大小限制一致）
+pub(super) const MAX_UPLOAD_BYTES: u64 = 100 * 1024 * 1024;
+
+/// 已写入临时文件的上传内容
+pub(super) struct UploadedFile {
+    path: TempPath,
+    /// 文件大小（字节）
+    pub size: u64,
+    /// 内容的 MD5（十六进制）
+    pub md5: String,
+}
+
+impl UploadedFile {
+    /// 临时文件路径
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 移动到最终位置（临时文件与目标在同一目录，重命名即可）
+    pub async fn persist(self, dest: &Path) -> Result<(), ApiError> {
+        fs::rename(&self.path, dest)
+            .await
+            .map_err(|e| ApiError::Internal(format!("Failed to save uploaded file: {}", e)))?;
+        // 已移走，不再删除临时路径
+        let _ = self.path.keep();
+        Ok(())
+    }
+}
+
+/// 在 `dir` 下创建临时文件
+async fn create_temp(dir: &Path) -> Result<(fs::File, TempPath), ApiError> {
+    fs::create_dir_all(dir)
+        .await
+        .map_err(|e| ApiError::Internal(format!("Failed to create upload directory: {}", e)))?;
+    let mut builder = tempfile::Builder::new();
+    builder.prefix(".upload-");
+    // 临时文件默认仅所有者可读写，与直接写入的文件保持一致
+    #[cfg(unix)]
+    builder.permissions(std::os::unix::fs::PermissionsExt::from_mode(0o644));
+    let (file, path) = builder
+        .tempfile_in(dir)
+        .map_err(|e| ApiError::Internal(format!("Failed to create temp file: {}", e)))?
+        .into_parts();
+    Ok((fs::File::from_std(file), path))
+}
+
+/// 将已在内存中的内容（如音色包中的参考音频）写入 `dir` 下的临时文件
+pub(super) async fn write_temp(dir: &Path, content: &[u8]) -> Result<UploadedFile, ApiError> {
+    let (mut file, path) = create_temp(dir).await?;
+    let write_error = |e: std::io::Error| ApiError::Internal(format!("Failed to write file: {}", e));
+    file.write_all(content).await.map_err(write_error)?;
+    file.flush().await.map_err(write_error)?;
+
+    Ok(UploadedFile {
+        path,
+        size: content.len() as u64,
+        md5: format!("{:x}", md5::compute(content)),
+    })
+}
+
+/// 将 multipart 文件字段流式写入 `dir` 下的临时文件
+///
+/// 超过 `max_size` 字节时返回 BadRequest；`require_utf8` 为 true 时内容必须是合法的 UTF-8
+pub(super) async fn stream_to_temp(
+    mut field: Field<'_>,
+    dir: &Path,
+    max_size: u64,
+    require_utf8: bool,
+) -> Result<UploadedFile, ApiError> {
+    let (mut file, path) = create_temp(dir).await?;
+    let write_error = |e: std::io::Error| ApiError::Internal(format!("Failed to write uploaded file: {}", e));
+
+    let mut md5 = md5::Context::new();
+    let mut utf8 = Utf8Validator::default();
+    let mut size = 0u64;
+    while let Some(chunk) = field
+        .chunk()
+        .await
+        .map_err(|e| ApiError::BadRequest(format!("Failed to read file: {}", e)))?
+    {
+        size += chunk.len() as u64;
+        if size > max_size {
+            return Err(ApiError::BadRequest(format!(
+                "File too large. Maximum size is {} MB",
+                max_size / 1024 / 1024
+            )));
+        }
+        if require_utf8 && !utf8.feed(&chunk) {
+            return Err(invalid_utf8());
+        }
+        md5.consume(&chunk);
+        file.write_all(&chunk).await.map_err(write_error)?;
+    }
+    if require_utf8 && !utf8.finish() {
+        return Err(invalid_utf8());
+    }
+    file.flush().await.map_err(write_error)?;
+
+    Ok(UploadedFile {
+        path,
+        size,
+        md5: format!("{:x}", md5.compute()),
+    })
+}
+
+fn invalid_utf8() -> ApiError {
+    ApiError::BadRequest("File must be valid UTF-8 text".to_string())
+}
+
+/// 分块校验 UTF-8（多字节字符可能被拆在两个块中）
+#[derive(Default)]
+struct Utf8Validator {
+    /// 上一块末尾不完整的字符
+    pending: Vec<u8>,
+}
+
+impl Utf8Validator {
+    /// 校验下一块，遇到非法字节时返回 false
+    fn feed(&mut self, chunk: &[u8]) -> bool {
+        let joined;
+        let bytes = if self.pending.is_empty() {
+            chunk
+        } else {
+            joined = [self.pending.as_slice(), chunk].concat();
+            joined.as_slice()
+        };
+        match std::str::from_utf8(bytes) {
+            Ok(_) => {
+                self.pending.clear();
+                true
+            }
+            // 末尾字符不完整，留给下一块
+            Err(e) if e.error_len().is_none() => {
+                self.pending = bytes[e.valid_up_to()..].to_vec();
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// 所有块都已校验：不能留下不完整的字符
+    fn finish(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate(chunks: &[&[u8]]) -> bool {
+        let mut validator = Utf8Validator::default();
+        chunks.iter().all(|chunk| validator.feed(chunk)) && validator.finish()
+    }
+
+    #[test]
+    fn test_utf8_validation_across_chunks() {
+        let text = "第一章 你好".as_bytes();
+        assert!(validate(&[text]));
+        // 在多字节字符中间拆分
+        assert!(validate(&[&text[..4], &text[4..8], &text[8..]]));
+        assert!(validate(&[&text[..1], &text[1..2], &text[2..]]));
+
+        // 非法字节、以不完整字符结尾
+        assert!(!validate(&[b"abc", &[0xff], b"def"]));
+        assert!(!validate(&[&text[..text.len() - 1]]));
+    }
+
+    #[tokio::test]
+    async fn test_unpersisted_upload_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        let upload = |content: &'static [u8]| write_temp(dir.path(), content);
+
+        let dest = dir.path().join("voice.wav");
+        std::fs::write(&dest, b"old").unwrap();
+        // 未移动到最终位置即丢弃：临时文件删除，目标文件不变
+        drop(upload(b"rejected").await.unwrap());
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+        assert_eq!(std::fs::read(&dest).unwrap(), b"old");
+
+        upload(b"new").await.unwrap().persist(&dest).await.unwrap();
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+        assert_eq!(std::fs::read(&dest).unwrap(), b"new");
+    }
+}
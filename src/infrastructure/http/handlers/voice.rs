@@ -2,24 +2,36 @@
 
 use axum::{
     body::Body,
-    extract::{Multipart, Path, State},
-    http::{header, StatusCode},
+    extract::{Multipart, Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
     response::Response,
-    Json,
+    Extension, Json,
 };
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::sync::Arc;
+use tempfile::TempPath;
 use tokio::fs;
-use tokio_util::io::ReaderStream;
+use tokio::io::AsyncReadExt;
 use uuid::Uuid;
 
-use crate::application::{CreateVoice, DeleteVoice, GetVoice, ListVoices};
-use crate::infrastructure::http::dto::{ApiResponse, Empty};
+use crate::application::{
+    can_access, CreateVoice, DeleteVoice, GetVoice, ImportVoice, ListVoiceTags, ListVoices,
+    NormalizeVoiceAudio, RepositoryError, UpdateVoice, SETTING_DEFAULT_VOICE_ID,
+};
+use crate::domain::voice::{TtsConfig, VoiceTags};
+use crate::infrastructure::adapters::{
+    ArchiveError, VoiceBundle, VoiceBundleManifest, VoiceBundleReader, VOICE_BUNDLE_VERSION,
+};
+use crate::infrastructure::http::dto::ApiResponse;
 use crate::infrastructure::http::error::ApiError;
+use crate::infrastructure::http::middleware::{current_user_id, CurrentUser};
 use crate::infrastructure::http::state::AppState;
 
+use super::range::ranged_file_response;
+use super::upload::{stream_to_temp, write_temp, UploadedFile, MAX_UPLOAD_BYTES};
+
 // ============================================================================
 // DTOs
 // ============================================================================
@@ -29,9 +41,58 @@ pub struct VoiceResponse {
     pub id: Uuid,
     pub name: String,
     pub description: Option<String>,
+    pub prompt_text: Option<String>,
+    /// TTS 引擎名称（None 表示默认引擎）
+    pub engine: Option<String>,
+    pub tts_params: TtsParamsResponse,
+    pub tags: VoiceTags,
     pub created_at: String,
 }
 
+/// 音色 TTS 参数
+#[derive(Debug, Serialize)]
+pub struct TtsParamsResponse {
+    pub speed: f32,
+    pub pitch: i8,
+    pub volume: f32,
+    pub temperature: Option<f32>,
+    pub top_p: Option<f32>,
+}
+
+impl From<TtsConfig> for TtsParamsResponse {
+    fn from(config: TtsConfig) -> Self {
+        Self {
+            speed: config.speed,
+            pitch: config.pitch,
+            volume: config.volume,
+            temperature: config.temperature,
+            top_p: config.top_p,
+        }
+    }
+}
+
+/// 音色列表过滤参数（query string）
+#[derive(Debug, Default, Deserialize)]
+pub struct ListVoicesRequest {
+    pub gender: Option<String>,
+    pub language: Option<String>,
+    pub style: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VoiceTagsResponse {
+    pub genders: Vec<String>,
+    pub languages: Vec<String>,
+    pub styles: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportVoiceResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub replaced: bool,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct GetVoiceRequest {
     pub id: Uuid,
@@ -40,6 +101,180 @@ pub struct GetVoiceRequest {
 #[derive(Debug, Deserialize)]
 pub struct DeleteVoiceRequest {
     pub id: Uuid,
+    /// 音色正被会话使用时，关闭这些会话后强制删除
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DeleteVoiceResponseDto {
+    /// 因强制删除而关闭的会话
+    pub closed_sessions: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VoiceAudioQuery {
+    /// 参考音频下载链接签名（启用账号时必填）
+    pub sig: Option<String>,
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+const VOICES_DIR: &str = "data/voices";
+
+/// 允许的参考音频格式
+const VALID_AUDIO_EXTS: [&str; 4] = ["wav", "mp3", "flac", "ogg"];
+
+/// 读取 multipart 音频文件字段（流式写入音色目录下的临时文件），返回 (临时文件, 小写扩展名)
+async fn read_audio_field(
+    field: axum::extract::multipart::Field<'_>,
+) -> Result<(UploadedFile, String), ApiError> {
+    let audio_ext = field.file_name().and_then(|f| {
+        PathBuf::from(f)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|s| s.to_lowercase())
+    });
+
+    // 验证音频格式
+    let audio_ext = audio_ext
+        .filter(|e| VALID_AUDIO_EXTS.contains(&e.as_str()))
+        .ok_or_else(|| {
+            ApiError::BadRequest("Only WAV, MP3, FLAC, OGG audio files are allowed".to_string())
+        })?;
+
+    let upload = stream_to_temp(field, &PathBuf::from(VOICES_DIR), MAX_UPLOAD_BYTES, false).await?;
+
+    Ok((upload, audio_ext))
+}
+
+/// 解析 multipart 中的 TTS 参数字段（speed/pitch/volume/temperature/top_p）
+///
+/// 在 `config` 上原地覆盖；temperature/top_p 传空字符串表示恢复服务默认值
+fn apply_tts_param(config: &mut TtsConfig, name: &str, value: &str) -> Result<(), ApiError> {
+    let value = value.trim();
+    let invalid = |e: &dyn std::fmt::Display| ApiError::BadRequest(format!("Invalid {}: {}", name, e));
+    match name {
+        "speed" => config.speed = value.parse().map_err(|e| invalid(&e))?,
+        "pitch" => config.pitch = value.parse().map_err(|e| invalid(&e))?,
+        "volume" => config.volume = value.parse().map_err(|e| invalid(&e))?,
+        "temperature" if value.is_empty() => config.temperature = None,
+        "temperature" => config.temperature = Some(value.parse().map_err(|e| invalid(&e))?),
+        "top_p" if value.is_empty() => config.top_p = None,
+        "top_p" => config.top_p = Some(value.parse().map_err(|e| invalid(&e))?),
+        _ => {}
+    }
+    Ok(())
+}
+
+/// 解析 multipart 中的标签字段（gender/language/style），空字符串表示清除
+fn apply_tag(tags: &mut VoiceTags, name: &str, value: &str) {
+    let value = VoiceTags::normalize_value(value);
+    match name {
+        "gender" => tags.gender = value,
+        "language" => tags.language = value,
+        "style" => tags.style = value,
+        _ => {}
+    }
+}
+
+/// 归一化参考音频（失败时保留原始音频，仅记录告警）
+/// 用户的默认音色为 `voice_id` 时清除该设置
+async fn clear_default_voice(state: &AppState, user_id: Uuid, voice_id: Uuid) -> Result<(), RepositoryError> {
+    let current = state.settings_repo.get(Some(user_id), SETTING_DEFAULT_VOICE_ID).await?;
+    if current.is_some_and(|value| value == voice_id.to_string()) {
+        state.settings_repo.delete(Some(user_id), SETTING_DEFAULT_VOICE_ID).await?;
+    }
+    Ok(())
+}
+
+async fn normalize_voice_audio(state: &AppState, voice_id: Uuid) {
+    let command = NormalizeVoiceAudio { voice_id };
+    if let Err(e) = state.normalize_voice_audio_handler.handle(command).await {
+        tracing::warn!(
+            voice_id = %voice_id,
+            error = %e,
+            "Reference audio normalization failed, using original file"
+        );
+    }
+}
+
+/// 校验音色指定的 TTS 引擎已注册（空字符串表示默认引擎）
+fn validate_engine(state: &AppState, engine: Option<&str>) -> Result<(), ApiError> {
+    match engine.map(str::trim).filter(|e| !e.is_empty()) {
+        Some(name) if !state.tts_engines.contains(name) => Err(ApiError::BadRequest(format!(
+            "Unknown TTS engine: {} (available: {})",
+            name,
+            state.tts_engines.names().join(", ")
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// 音色参考音频的保存路径
+fn voice_audio_path(voice_id: Uuid, ext: &str) -> PathBuf {
+    PathBuf::from(VOICES_DIR).join(format!("{}.{}", voice_id, ext))
+}
+
+/// 将上传的参考音频移动到音色目录（覆盖同名文件）
+async fn persist_voice_audio(voice_id: Uuid, ext: &str, upload: UploadedFile) -> Result<PathBuf, ApiError> {
+    let audio_path = voice_audio_path(voice_id, ext);
+    tracing::debug!(voice_id = %voice_id, size = upload.size, md5 = %upload.md5, "Voice audio uploaded");
+    upload.persist(&audio_path).await?;
+    Ok(audio_path)
+}
+
+/// 替换已有音色的参考音频：新文件先移动到最终位置，被覆盖的同名原文件暂存为备份，
+/// 数据库更新失败时 [`rollback`](Self::rollback) 恢复原文件，成功后丢弃即删除备份
+struct ReplacedVoiceAudio {
+    path: PathBuf,
+    backup: Option<TempPath>,
+}
+
+impl ReplacedVoiceAudio {
+    /// `path` 与上传的临时文件在同一目录
+    async fn install(path: PathBuf, upload: UploadedFile) -> Result<Self, ApiError> {
+        let dir = path.parent().unwrap_or(std::path::Path::new("."));
+        let backup = if fs::try_exists(&path).await.unwrap_or(false) {
+            let backup = tempfile::Builder::new()
+                .prefix(".backup-")
+                .tempfile_in(dir)
+                .map_err(|e| ApiError::Internal(format!("Failed to create temp file: {}", e)))?
+                .into_temp_path();
+            fs::rename(&path, &backup)
+                .await
+                .map_err(|e| ApiError::Internal(format!("Failed to back up voice audio: {}", e)))?;
+            Some(backup)
+        } else {
+            None
+        };
+
+        let replaced = Self { path, backup };
+        if let Err(e) = upload.persist(&replaced.path).await {
+            replaced.rollback().await;
+            return Err(e);
+        }
+        Ok(replaced)
+    }
+
+    /// 删除新文件并恢复原文件
+    async fn rollback(self) {
+        let _ = fs::remove_file(&self.path).await;
+        if let Some(backup) = self.backup {
+            match fs::rename(&backup, &self.path).await {
+                Ok(()) => {
+                    let _ = backup.keep();
+                }
+                Err(e) => tracing::warn!(
+                    path = %self.path.display(),
+                    error = %e,
+                    "Failed to restore replaced voice audio"
+                ),
+            }
+        }
+    }
 }
 
 // ============================================================================
@@ -49,12 +284,16 @@ pub struct DeleteVoiceRequest {
 /// 上传音色
 pub async fn upload_voice(
     State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
     mut multipart: Multipart,
 ) -> Result<Json<ApiResponse<VoiceResponse>>, ApiError> {
     let mut name: Option<String> = None;
     let mut description: Option<String> = None;
-    let mut audio_data: Option<Vec<u8>> = None;
-    let mut audio_ext: Option<String> = None;
+    let mut prompt_text: Option<String> = None;
+    let mut engine: Option<String> = None;
+    let mut audio: Option<(UploadedFile, String)> = None;
+    let mut tts_config = TtsConfig::default();
+    let mut tags = VoiceTags::default();
 
     while let Some(field) = multipart.next_field().await.map_err(|e| {
         ApiError::BadRequest(format!("Failed to read multipart field: {}", e))
@@ -78,65 +317,69 @@ pub async fn upload_voice(
                         .map_err(|e| ApiError::BadRequest(format!("Failed to read description: {}", e)))?,
                 );
             }
-            "file" => {
-                let filename = field.file_name().map(|s| s.to_string());
-                audio_ext = filename.as_ref().and_then(|f| {
-                    PathBuf::from(f)
-                        .extension()
-                        .and_then(|e| e.to_str())
-                        .map(|s| s.to_lowercase())
-                });
-
-                // 验证音频格式
-                let valid_exts = ["wav", "mp3", "flac", "ogg"];
-                if !audio_ext
-                    .as_ref()
-                    .map(|e| valid_exts.contains(&e.as_str()))
-                    .unwrap_or(false)
-                {
-                    return Err(ApiError::BadRequest(
-                        "Only WAV, MP3, FLAC, OGG audio files are allowed".to_string(),
-                    ));
-                }
-
-                audio_data = Some(
+            "prompt_text" => {
+                prompt_text = Some(
                     field
-                        .bytes()
+                        .text()
                         .await
-                        .map_err(|e| ApiError::BadRequest(format!("Failed to read file: {}", e)))?
-                        .to_vec(),
+                        .map_err(|e| ApiError::BadRequest(format!("Failed to read prompt_text: {}", e)))?,
                 );
             }
+            "engine" => {
+                engine = Some(
+                    field
+                        .text()
+                        .await
+                        .map_err(|e| ApiError::BadRequest(format!("Failed to read engine: {}", e)))?,
+                );
+            }
+            "speed" | "pitch" | "volume" | "temperature" | "top_p" => {
+                let value = field.text().await.map_err(|e| {
+                    ApiError::BadRequest(format!("Failed to read {}: {}", field_name, e))
+                })?;
+                apply_tts_param(&mut tts_config, &field_name, &value)?;
+            }
+            "gender" | "language" | "style" => {
+                let value = field.text().await.map_err(|e| {
+                    ApiError::BadRequest(format!("Failed to read {}: {}", field_name, e))
+                })?;
+                apply_tag(&mut tags, &field_name, &value);
+            }
+            "file" => {
+                audio = Some(read_audio_field(field).await?);
+            }
             _ => {}
         }
     }
 
     let name = name.ok_or_else(|| ApiError::BadRequest("Name is required".to_string()))?;
-    let audio_data =
-        audio_data.ok_or_else(|| ApiError::BadRequest("Audio file is required".to_string()))?;
-    let audio_ext = audio_ext.unwrap_or_else(|| "wav".to_string());
+    let (upload, audio_ext) =
+        audio.ok_or_else(|| ApiError::BadRequest("Audio file is required".to_string()))?;
+    tts_config
+        .validate()
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    validate_engine(&state, engine.as_deref())?;
 
     // 保存音频文件
     let voice_id = Uuid::new_v4();
-    let voices_dir = PathBuf::from("data/voices");
-    fs::create_dir_all(&voices_dir)
-        .await
-        .map_err(|e| ApiError::Internal(format!("Failed to create voices directory: {}", e)))?;
-
-    let audio_path = voices_dir.join(format!("{}.{}", voice_id, audio_ext));
-    fs::write(&audio_path, &audio_data)
-        .await
-        .map_err(|e| ApiError::Internal(format!("Failed to save audio file: {}", e)))?;
+    let audio_path = persist_voice_audio(voice_id, &audio_ext, upload).await?;
 
     // 创建音色
     let command = CreateVoice {
         name: name.clone(),
         reference_audio_path: audio_path.clone(),
         description: description.clone(),
+        prompt_text,
+        engine,
+        tts_config,
+        tags,
+        user_id: current_user_id(user),
     };
 
     let result = state.create_voice_handler.handle(command).await?;
 
+    normalize_voice_audio(&state, result.id).await;
+
     tracing::info!(
         voice_id = %result.id,
         name = %result.name,
@@ -147,15 +390,204 @@ pub async fn upload_voice(
         id: result.id,
         name: result.name,
         description: result.description,
+        prompt_text: result.prompt_text,
+        engine: result.engine,
+        tts_params: result.tts_config.into(),
+        tags: result.tags,
         created_at: Utc::now().to_rfc3339(),
     })))
 }
 
-/// 获取音色列表
+/// 更新音色（名称/描述/参考文本/参考音频/TTS 参数/标签），保留原 voice_id
+///
+/// multipart 字段：id（必填）、name、description、prompt_text、engine、file、speed/pitch/volume/temperature/top_p、
+/// gender/language/style（均可选）
+pub async fn update_voice(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    mut multipart: Multipart,
+) -> Result<Json<ApiResponse<VoiceResponse>>, ApiError> {
+    let mut voice_id: Option<Uuid> = None;
+    let mut name: Option<String> = None;
+    let mut description: Option<String> = None;
+    let mut prompt_text: Option<String> = None;
+    let mut engine: Option<String> = None;
+    let mut audio: Option<(UploadedFile, String)> = None;
+    let mut tts_params: Vec<(String, String)> = Vec::new();
+    let mut tag_fields: Vec<(String, String)> = Vec::new();
+
+    while let Some(field) = multipart.next_field().await.map_err(|e| {
+        ApiError::BadRequest(format!("Failed to read multipart field: {}", e))
+    })? {
+        let field_name = field.name().unwrap_or_default().to_string();
+
+        match field_name.as_str() {
+            "id" => {
+                let text = field
+                    .text()
+                    .await
+                    .map_err(|e| ApiError::BadRequest(format!("Failed to read id: {}", e)))?;
+                voice_id = Some(
+                    Uuid::parse_str(text.trim())
+                        .map_err(|e| ApiError::BadRequest(format!("Invalid voice id: {}", e)))?,
+                );
+            }
+            "name" => {
+                name = Some(
+                    field
+                        .text()
+                        .await
+                        .map_err(|e| ApiError::BadRequest(format!("Failed to read name: {}", e)))?,
+                );
+            }
+            "description" => {
+                description = Some(
+                    field
+                        .text()
+                        .await
+                        .map_err(|e| ApiError::BadRequest(format!("Failed to read description: {}", e)))?,
+                );
+            }
+            "prompt_text" => {
+                prompt_text = Some(
+                    field
+                        .text()
+                        .await
+                        .map_err(|e| ApiError::BadRequest(format!("Failed to read prompt_text: {}", e)))?,
+                );
+            }
+            "engine" => {
+                engine = Some(
+                    field
+                        .text()
+                        .await
+                        .map_err(|e| ApiError::BadRequest(format!("Failed to read engine: {}", e)))?,
+                );
+            }
+            "speed" | "pitch" | "volume" | "temperature" | "top_p" => {
+                let value = field.text().await.map_err(|e| {
+                    ApiError::BadRequest(format!("Failed to read {}: {}", field_name, e))
+                })?;
+                tts_params.push((field_name, value));
+            }
+            "gender" | "language" | "style" => {
+                let value = field.text().await.map_err(|e| {
+                    ApiError::BadRequest(format!("Failed to read {}: {}", field_name, e))
+                })?;
+                tag_fields.push((field_name, value));
+            }
+            "file" => {
+                audio = Some(read_audio_field(field).await?);
+            }
+            _ => {}
+        }
+    }
+
+    let voice_id = voice_id.ok_or_else(|| ApiError::BadRequest("Id is required".to_string()))?;
+    validate_engine(&state, engine.as_deref())?;
+
+    // 先确认音色存在且当前用户可以修改，避免为不存在的音色落盘文件
+    let voice = state.access.check_voice_mut(voice_id, current_user_id(user)).await?;
+
+    // 在现有参数上合并本次提交的字段
+    let tts_config = if tts_params.is_empty() {
+        None
+    } else {
+        let mut config = voice.tts_config;
+        for (name, value) in &tts_params {
+            apply_tts_param(&mut config, name, value)?;
+        }
+        config
+            .validate()
+            .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+        Some(config)
+    };
+
+    let tags = if tag_fields.is_empty() {
+        None
+    } else {
+        let mut tags = voice.tags;
+        for (name, value) in &tag_fields {
+            apply_tag(&mut tags, name, value);
+        }
+        Some(tags)
+    };
+
+    // 先将新参考音频移动到最终位置，数据库记录始终指向已存在的文件；
+    // 更新失败时删除新文件并恢复原音频
+    let replaced = match audio {
+        Some((upload, ext)) => {
+            tracing::debug!(voice_id = %voice_id, size = upload.size, md5 = %upload.md5, "Voice audio uploaded");
+            Some(ReplacedVoiceAudio::install(voice_audio_path(voice_id, &ext), upload).await?)
+        }
+        None => None,
+    };
+    let reference_audio_path = replaced.as_ref().map(|r| r.path.clone());
+
+    let command = UpdateVoice {
+        voice_id,
+        name,
+        description,
+        prompt_text,
+        engine,
+        reference_audio_path,
+        tts_config,
+        tags,
+    };
+
+    let result = match state.update_voice_handler.handle(command).await {
+        Ok(result) => result,
+        Err(e) => {
+            if let Some(replaced) = replaced {
+                replaced.rollback().await;
+            }
+            return Err(e.into());
+        }
+    };
+    // 更新已提交，丢弃被覆盖原文件的备份
+    drop(replaced);
+
+    // 清理被替换的旧音频文件（先清理再重新归一化，避免删掉新生成的文件）
+    for old_path in &result.stale_files {
+        if old_path.exists() {
+            if let Err(e) = fs::remove_file(old_path).await {
+                tracing::warn!("Failed to delete replaced voice audio file: {}", e);
+            }
+        }
+    }
+
+    if result.audio_replaced {
+        normalize_voice_audio(&state, voice_id).await;
+    }
+
+    // 广播事件通知其他客户端
+    state.event_publisher.publish_voice_updated(voice_id, voice.user_id);
+
+    Ok(Json(ApiResponse::success(VoiceResponse {
+        id: result.id,
+        name: result.name,
+        description: result.description,
+        prompt_text: result.prompt_text,
+        engine: result.engine,
+        tts_params: result.tts_config.into(),
+        tags: result.tags,
+        created_at: result.created_at,
+    })))
+}
+
+/// 获取音色列表（可按 gender/language/style 过滤）
 pub async fn list_voices(
     State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    Query(req): Query<ListVoicesRequest>,
 ) -> Result<Json<ApiResponse<Vec<VoiceResponse>>>, ApiError> {
-    let result = state.list_voices_handler.handle(ListVoices).await?;
+    let query = ListVoices {
+        gender: req.gender,
+        language: req.language,
+        style: req.style,
+        user_id: current_user_id(user),
+    };
+    let result = state.list_voices_handler.handle(query).await?;
 
     let responses: Vec<VoiceResponse> = result
         .into_iter()
@@ -163,6 +595,10 @@ pub async fn list_voices(
             id: v.id,
             name: v.name,
             description: v.description,
+            prompt_text: v.prompt_text,
+            engine: v.engine,
+            tts_params: v.tts_config.into(),
+            tags: v.tags,
             created_at: v.created_at,
         })
         .collect();
@@ -173,8 +609,10 @@ pub async fn list_voices(
 /// 获取音色详情
 pub async fn get_voice(
     State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
     Json(req): Json<GetVoiceRequest>,
 ) -> Result<Json<ApiResponse<VoiceResponse>>, ApiError> {
+    state.access.check_voice(req.id, current_user_id(user)).await?;
     let query = GetVoice { voice_id: req.id };
 
     let result = state.get_voice_handler.handle(query).await?;
@@ -183,51 +621,310 @@ pub async fn get_voice(
         id: result.id,
         name: result.name,
         description: result.description,
+        prompt_text: result.prompt_text,
+        engine: result.engine,
+        tts_params: result.tts_config.into(),
+        tags: result.tags,
         created_at: result.created_at,
     })))
 }
 
+/// 获取音色详情（GET /api/voices/{id}）
+pub async fn get_voice_by_id(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<VoiceResponse>>, ApiError> {
+    get_voice(State(state), user, Json(GetVoiceRequest { id })).await
+}
+
+/// 获取已使用的音色标签（用于分类浏览）
+pub async fn list_voice_tags(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+) -> Result<Json<ApiResponse<VoiceTagsResponse>>, ApiError> {
+    let query = ListVoiceTags {
+        user_id: current_user_id(user),
+    };
+    let result = state.list_voice_tags_handler.handle(query).await?;
+
+    Ok(Json(ApiResponse::success(VoiceTagsResponse {
+        genders: result.genders,
+        languages: result.languages,
+        styles: result.styles,
+    })))
+}
+
 /// 删除音色（同步，完成后广播 WS 事件）
 pub async fn delete_voice(
     State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
     Json(req): Json<DeleteVoiceRequest>,
-) -> Result<Json<ApiResponse<Empty>>, ApiError> {
+) -> Result<Json<ApiResponse<DeleteVoiceResponseDto>>, ApiError> {
     let voice_id = req.id;
 
-    // 获取音色信息
-    let voice = state
-        .voice_repo
-        .find_by_id(voice_id)
-        .await
-        .map_err(|e| ApiError::Internal(e.to_string()))?
-        .ok_or_else(|| ApiError::NotFound(format!("Voice {} not found", voice_id)))?;
+    // 获取音色信息（共享音色只有管理员可以删除）
+    let voice = state.access.check_voice_mut(voice_id, current_user_id(user)).await?;
 
-    let audio_path = voice.reference_audio_path.clone();
+    let audio_paths: Vec<PathBuf> = std::iter::once(voice.reference_audio_path.clone())
+        .chain(voice.processed_audio_path.clone())
+        .collect();
 
     // 删除数据库记录
-    let command = DeleteVoice { voice_id };
-    state.delete_voice_handler.handle(command).await?;
+    // 音色被会话使用时返回 409（附带会话 ID），force 时先关闭这些会话
+    let command = DeleteVoice {
+        voice_id,
+        force: req.force,
+    };
+    let result = state.delete_voice_handler.handle(command).await?;
 
-    // 删除音频文件
-    if audio_path.exists() {
-        if let Err(e) = tokio::fs::remove_file(&audio_path).await {
-            tracing::warn!("Failed to delete voice audio file: {}", e);
+    // 删除音频文件（原始 + 归一化）
+    for audio_path in &audio_paths {
+        if audio_path.exists() {
+            if let Err(e) = tokio::fs::remove_file(audio_path).await {
+                tracing::warn!("Failed to delete voice audio file: {}", e);
+            }
         }
     }
 
+    // 清除以被删除音色为默认音色的设置：用户的音色只有本人能设为默认音色，
+    // 共享音色（由管理员删除）则清除所有用户的设置
+    let cleared = match voice.user_id {
+        Some(owner) => clear_default_voice(&state, owner, voice_id).await,
+        None => state
+            .settings_repo
+            .delete_by_value(SETTING_DEFAULT_VOICE_ID, &voice_id.to_string())
+            .await
+            .map(|_| ()),
+    };
+    if let Err(e) = cleared {
+        tracing::warn!("Failed to clear default voice setting: {}", e);
+    }
+
     tracing::info!(voice_id = %voice_id, "Voice deleted");
 
     // 广播事件通知其他客户端
-    state.event_publisher.publish_voice_deleted(voice_id);
+    state.event_publisher.publish_voice_deleted(voice_id, voice.user_id);
+
+    Ok(Json(ApiResponse::success(DeleteVoiceResponseDto {
+        closed_sessions: result.closed_sessions,
+    })))
+}
+
+/// 导出音色包（元数据 + 原始参考音频，tar 格式）
+pub async fn export_voice(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    Path(voice_id): Path<Uuid>,
+) -> Result<Response, ApiError> {
+    let voice = state.access.check_voice(voice_id, current_user_id(user)).await?;
+
+    let audio_data = fs::read(&voice.reference_audio_path)
+        .await
+        .map_err(|e| ApiError::Internal(format!("Failed to read reference audio: {}", e)))?;
+    let audio_ext = voice
+        .reference_audio_path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("wav")
+        .to_lowercase();
+
+    let bundle = VoiceBundle {
+        manifest: VoiceBundleManifest {
+            version: VOICE_BUNDLE_VERSION,
+            id: voice.id,
+            name: voice.name,
+            description: voice.description,
+            prompt_text: voice.prompt_text,
+            engine: voice.engine,
+            tts_config: voice.tts_config,
+            tags: voice.tags,
+            created_at: voice.created_at,
+            audio_file: format!("reference.{}", audio_ext),
+        },
+        audio_data,
+    };
+    let archive = bundle
+        .to_tar()
+        .map_err(|e| ApiError::Internal(format!("Failed to build voice bundle: {}", e)))?;
+
+    tracing::info!(voice_id = %voice_id, size = archive.len(), "Voice exported");
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/x-tar")
+        .header(header::CONTENT_LENGTH, archive.len())
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}.voice.tar\"", voice_id),
+        )
+        .body(Body::from(archive))
+        .unwrap())
+}
+
+/// 导入音色包，保留原 voice_id
+///
+/// multipart 字段：file（音色包，必填）、overwrite（"true" 时覆盖已存在的音色）
+pub async fn import_voice(
+    State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
+    mut multipart: Multipart,
+) -> Result<Json<ApiResponse<ImportVoiceResponse>>, ApiError> {
+    let mut bundle_file: Option<UploadedFile> = None;
+    let mut overwrite = false;
+
+    while let Some(field) = multipart.next_field().await.map_err(|e| {
+        ApiError::BadRequest(format!("Failed to read multipart field: {}", e))
+    })? {
+        let field_name = field.name().unwrap_or_default().to_string();
+
+        match field_name.as_str() {
+            "file" => {
+                bundle_file =
+                    Some(stream_to_temp(field, &PathBuf::from(VOICES_DIR), MAX_UPLOAD_BYTES, false).await?);
+            }
+            "overwrite" => {
+                let value = field
+                    .text()
+                    .await
+                    .map_err(|e| ApiError::BadRequest(format!("Failed to read overwrite: {}", e)))?;
+                overwrite = matches!(value.trim(), "true" | "1");
+            }
+            _ => {}
+        }
+    }
+
+    let bundle_file =
+        bundle_file.ok_or_else(|| ApiError::BadRequest("Bundle file is required".to_string()))?;
+    let bundle = read_voice_bundle(&bundle_file).await?;
+    drop(bundle_file);
+
+    let audio_ext = bundle
+        .audio_ext()
+        .filter(|e| VALID_AUDIO_EXTS.contains(&e.as_str()))
+        .ok_or_else(|| {
+            ApiError::BadRequest("Only WAV, MP3, FLAC, OGG audio files are allowed".to_string())
+        })?;
+
+    bundle
+        .manifest
+        .tts_config
+        .validate()
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    validate_engine(&state, bundle.manifest.engine.as_deref())?;
+
+    let voice_id = bundle.manifest.id;
+
+    // 写文件前先检查冲突，避免覆盖已存在音色（或其他用户的音色）的参考音频
+    let user_id = current_user_id(user);
+    let existing = state
+        .voice_repo
+        .find_by_id(voice_id)
+        .await
+        .map_err(|e| ApiError::Internal(e.to_string()))?;
+    // 覆盖时保留原音色的所属用户
+    let owner = existing.as_ref().map_or(user_id, |v| v.user_id);
+    if let Some(existing) = existing {
+        if !overwrite {
+            return Err(ApiError::Conflict(format!("Voice already exists: {}", voice_id)));
+        }
+        if !can_access(existing.user_id, user_id) {
+            return Err(ApiError::Forbidden(format!("Voice belongs to another user: {}", voice_id)));
+        }
+        // 覆盖共享音色需要管理员
+        state.access.check_modify(existing.user_id, user_id).await?;
+    }
+
+    // 与更新音色相同：先写入临时文件再移动到最终位置，导入失败时恢复原音频
+    let upload = write_temp(std::path::Path::new(VOICES_DIR), &bundle.audio_data).await?;
+    let replaced = ReplacedVoiceAudio::install(voice_audio_path(voice_id, &audio_ext), upload).await?;
+
+    let manifest = bundle.manifest;
+    let command = ImportVoice {
+        voice_id,
+        name: manifest.name,
+        description: manifest.description,
+        prompt_text: manifest.prompt_text,
+        engine: manifest.engine,
+        reference_audio_path: replaced.path.clone(),
+        tts_config: manifest.tts_config,
+        tags: manifest.tags,
+        created_at: manifest.created_at,
+        overwrite,
+        user_id,
+    };
+
+    let result = match state.import_voice_handler.handle(command).await {
+        Ok(result) => result,
+        Err(e) => {
+            replaced.rollback().await;
+            return Err(e.into());
+        }
+    };
+    // 导入已提交，丢弃被覆盖原文件的备份
+    drop(replaced);
+
+    for old_path in &result.stale_files {
+        if old_path.exists() {
+            if let Err(e) = fs::remove_file(old_path).await {
+                tracing::warn!("Failed to delete replaced voice audio file: {}", e);
+            }
+        }
+    }
+
+    normalize_voice_audio(&state, voice_id).await;
+
+    if result.replaced {
+        state.event_publisher.publish_voice_updated(voice_id, owner);
+    }
+
+    Ok(Json(ApiResponse::success(ImportVoiceResponse {
+        id: result.id,
+        name: result.name,
+        replaced: result.replaced,
+    })))
+}
+
+/// 从上传到磁盘的音色包逐块读取
+async fn read_voice_bundle(bundle_file: &UploadedFile) -> Result<VoiceBundle, ApiError> {
+    let read_error = |e: std::io::Error| ApiError::Internal(format!("Failed to read voice bundle: {}", e));
+    let invalid = |e: ArchiveError| ApiError::BadRequest(format!("Invalid voice bundle: {}", e));
+
+    let mut file = fs::File::open(bundle_file.path()).await.map_err(read_error)?;
+    let mut reader = VoiceBundleReader::new();
+    let mut chunk = vec![0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut chunk).await.map_err(read_error)?;
+        if n == 0 {
+            break;
+        }
+        reader.push(&chunk[..n]).map_err(invalid)?;
+    }
+    reader.finish().map_err(invalid)
+}
 
-    Ok(Json(ApiResponse::ok()))
+/// 列出可用的 TTS 引擎
+pub async fn list_tts_engines(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<ApiResponse<Vec<String>>>, ApiError> {
+    Ok(Json(ApiResponse::success(state.tts_engines.names())))
 }
 
-/// 下载音色参考音频（供外部 TTS 服务使用）
+/// 下载音色参考音频（供外部 TTS 服务使用），支持单区间 Range 请求
+///
+/// 该接口不需要登录；启用账号时需附带 Worker 生成的签名（`?sig=`），否则按音色不存在处理
 pub async fn download_voice_audio(
     State(state): State<Arc<AppState>>,
     Path(voice_id): Path<Uuid>,
+    Query(query): Query<VoiceAudioQuery>,
+    headers: HeaderMap,
 ) -> Result<Response, ApiError> {
+    if let Some(signer) = &state.voice_audio_signer {
+        if !query.sig.is_some_and(|sig| signer.verify(voice_id, &sig)) {
+            return Err(ApiError::NotFound(format!("Voice not found: {}", voice_id)));
+        }
+    }
+
     // 直接从 repository 查询以获取 reference_audio_path
     let voice = state
         .voice_repo
@@ -236,8 +933,12 @@ pub async fn download_voice_audio(
         .map_err(|e| ApiError::Internal(format!("Database error: {}", e)))?
         .ok_or_else(|| ApiError::NotFound(format!("Voice not found: {}", voice_id)))?;
 
-    // 获取音频文件路径
-    let audio_path = &voice.reference_audio_path;
+    // 获取音频文件路径（优先使用归一化后的版本）
+    let audio_path = voice
+        .processed_audio_path
+        .as_ref()
+        .filter(|p| p.exists())
+        .unwrap_or(&voice.reference_audio_path);
     if !audio_path.exists() {
         return Err(ApiError::NotFound(format!(
             "Voice audio file not found: {}",
@@ -250,13 +951,6 @@ pub async fn download_voice_audio(
         .await
         .map_err(|e| ApiError::Internal(format!("Failed to open audio file: {}", e)))?;
 
-    // 获取文件大小
-    let metadata = file
-        .metadata()
-        .await
-        .map_err(|e| ApiError::Internal(format!("Failed to get file metadata: {}", e)))?;
-    let file_size = metadata.len();
-
     // 检测 Content-Type
     let content_type = match audio_path.extension().and_then(|e| e.to_str()) {
         Some("wav") => "audio/wav",
@@ -266,14 +960,8 @@ pub async fn download_voice_audio(
         _ => "application/octet-stream",
     };
 
-    // 流式返回文件内容
-    let stream = ReaderStream::new(file);
-    let body = Body::from_stream(stream);
-
-    Ok(Response::builder()
-        .status(StatusCode::OK)
+    let builder = Response::builder()
         .header(header::CONTENT_TYPE, content_type)
-        .header(header::CONTENT_LENGTH, file_size)
         .header(
             header::CONTENT_DISPOSITION,
             format!(
@@ -284,7 +972,72 @@ pub async fn download_voice_audio(
                     .and_then(|e| e.to_str())
                     .unwrap_or("wav")
             ),
-        )
-        .body(body)
-        .unwrap())
+        );
+
+    ranged_file_response(builder, file, &headers)
+        .await
+        .map_err(|e| ApiError::Internal(format!("Failed to read audio file: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_replaced_voice_audio_rollback() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("voice.wav");
+        std::fs::write(&path, b"old").unwrap();
+        let count = || std::fs::read_dir(dir.path()).unwrap().count();
+
+        // 更新失败：恢复原文件，不留下临时文件和备份
+        let upload = write_temp(dir.path(), b"new").await.unwrap();
+        let replaced = ReplacedVoiceAudio::install(path.clone(), upload).await.unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"new");
+        replaced.rollback().await;
+        assert_eq!(std::fs::read(&path).unwrap(), b"old");
+        assert_eq!(count(), 1);
+
+        // 更新成功：新文件保留，备份删除
+        let upload = write_temp(dir.path(), b"new").await.unwrap();
+        drop(ReplacedVoiceAudio::install(path.clone(), upload).await.unwrap());
+        assert_eq!(std::fs::read(&path).unwrap(), b"new");
+        assert_eq!(count(), 1);
+
+        // 新扩展名：回滚时删除新文件
+        let new_path = dir.path().join("voice.flac");
+        let upload = write_temp(dir.path(), b"flac").await.unwrap();
+        ReplacedVoiceAudio::install(new_path.clone(), upload).await.unwrap().rollback().await;
+        assert!(!new_path.exists());
+        assert_eq!(count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_read_voice_bundle_from_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let bundle = VoiceBundle {
+            manifest: VoiceBundleManifest {
+                version: VOICE_BUNDLE_VERSION,
+                id: Uuid::new_v4(),
+                name: "旁白".to_string(),
+                description: None,
+                prompt_text: None,
+                engine: None,
+                tts_config: TtsConfig::default(),
+                tags: VoiceTags::default(),
+                created_at: Utc::now(),
+                audio_file: "reference.wav".to_string(),
+            },
+            // 跨越多个读取块
+            audio_data: vec![7; 200 * 1024],
+        };
+
+        let upload = write_temp(dir.path(), &bundle.to_tar().unwrap()).await.unwrap();
+        let restored = read_voice_bundle(&upload).await.unwrap();
+        assert_eq!(restored.manifest.id, bundle.manifest.id);
+        assert_eq!(restored.audio_data, bundle.audio_data);
+
+        let upload = write_temp(dir.path(), b"not a tar").await.unwrap();
+        assert!(matches!(read_voice_bundle(&upload).await, Err(ApiError::BadRequest(_))));
+    }
 }
@@ -6,28 +6,36 @@ use axum::{
         Path, State,
     },
     response::IntoResponse,
+    Extension,
 };
 use futures_util::{SinkExt, StreamExt};
 use std::sync::Arc;
+use uuid::Uuid;
 
+use crate::infrastructure::http::error::ApiError;
+use crate::infrastructure::http::middleware::{current_user_id, CurrentUser};
 use crate::infrastructure::http::state::AppState;
 use crate::infrastructure::events::WsEvent;
 
-/// Session WebSocket 连接处理（用于 task 状态通知）
+/// Session WebSocket 连接处理（用于 task 状态通知），只能连接当前用户的会话
 pub async fn websocket_handler(
     ws: WebSocketUpgrade,
     Path(session_id): Path<String>,
     State(state): State<Arc<AppState>>,
-) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| handle_session_socket(socket, session_id, state))
+    user: Option<Extension<CurrentUser>>,
+) -> Result<impl IntoResponse, ApiError> {
+    state.access.check_session(&session_id, current_user_id(user))?;
+    Ok(ws.on_upgrade(move |socket| handle_session_socket(socket, session_id, state)))
 }
 
-/// 全局 WebSocket 连接处理（用于 novel 事件通知）
+/// 全局 WebSocket 连接处理（用于 novel 事件通知），只转发当前用户有权访问的事件
 pub async fn global_websocket_handler(
     ws: WebSocketUpgrade,
     State(state): State<Arc<AppState>>,
+    user: Option<Extension<CurrentUser>>,
 ) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| handle_global_socket(socket, state))
+    let user_id = current_user_id(user);
+    ws.on_upgrade(move |socket| handle_global_socket(socket, state, user_id))
 }
 
 async fn handle_session_socket(socket: WebSocket, session_id: String, state: Arc<AppState>) {
@@ -40,8 +48,9 @@ async fn handle_session_socket(socket: WebSocket, session_id: String, state: Arc
         return;
     }
 
-    // 注册事件接收器
+    // 注册事件接收器（保留一份用于断开时识别通道）
     let mut event_rx = state.event_publisher.register_session(&session_id);
+    let channel = event_rx.resubscribe();
 
     tracing::info!(session_id = %session_id, "WebSocket connected");
 
@@ -70,6 +79,8 @@ async fn handle_session_socket(socket: WebSocket, session_id: String, state: Arc
                 break;
             }
         }
+        // 通道关闭（会话被其他设备接管）时断开连接
+        let _ = sender.close().await;
     });
 
     // 接收客户端消息（心跳）
@@ -104,12 +115,12 @@ async fn handle_session_socket(socket: WebSocket, session_id: String, state: Arc
     }
 
     // 清理
-    state.event_publisher.unregister_session(&session_id_for_cleanup);
+    state.event_publisher.release_session(&session_id_for_cleanup, &channel);
     tracing::info!(session_id = %session_id_for_cleanup, "WebSocket disconnected");
 }
 
-/// 处理全局 WebSocket（用于接收 NovelReady/NovelFailed 事件）
-async fn handle_global_socket(socket: WebSocket, state: Arc<AppState>) {
+/// 处理全局 WebSocket（用于接收 Novel/Voice 事件、TTS 引擎健康状态及导出进度）
+async fn handle_global_socket(socket: WebSocket, state: Arc<AppState>, user_id: Option<Uuid>) {
     let (mut sender, mut receiver) = socket.split();
 
     // 订阅全局事件
@@ -119,15 +130,29 @@ async fn handle_global_socket(socket: WebSocket, state: Arc<AppState>) {
 
     // 事件转发任务
     let forward_task = tokio::spawn(async move {
-        while let Ok(event) = event_rx.recv().await {
-            // 转发全局事件（Novel 和 Voice 相关）
+        while let Ok(global) = event_rx.recv().await {
+            // 其他用户的资源和任务事件不转发
+            if !global.visible_to(user_id) {
+                continue;
+            }
+            let event = global.event;
+            // 转发全局事件（Novel、Voice、TTS 引擎状态、有声书导出、整书预生成、GC 及缓存配额）
             match &event {
                 WsEvent::NovelReady { .. }
                 | WsEvent::NovelFailed { .. }
                 | WsEvent::NovelDeleting { .. }
                 | WsEvent::NovelDeleted { .. }
                 | WsEvent::NovelDeleteFailed { .. }
-                | WsEvent::VoiceDeleted { .. } => {
+                | WsEvent::VoiceUpdated { .. }
+                | WsEvent::VoiceDeleted { .. }
+                | WsEvent::TtsEngineDown { .. }
+                | WsEvent::TtsEngineUp { .. }
+                | WsEvent::ExportProgress { .. }
+                | WsEvent::ExportReady { .. }
+                | WsEvent::ExportFailed { .. }
+                | WsEvent::PregenerationProgress { .. }
+                | WsEvent::GcCompleted { .. }
+                | WsEvent::NovelCacheQuotaReached { .. } => {
                     let msg = match serde_json::to_string(&event) {
                         Ok(json) => Message::Text(json),
                         Err(e) => {
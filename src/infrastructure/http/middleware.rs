@@ -1,12 +1,221 @@
 //! HTTP Middleware
 //!
-//! HTTP 状态码错误日志中间件
+//! HTTP 状态码错误日志中间件、客户端 IP 识别中间件、登录令牌校验中间件、管理接口校验中间件、请求 ID 中间件
+
+use std::net::SocketAddr;
+use std::sync::Arc;
 
 use axum::{
-    extract::Request,
+    extract::{ConnectInfo, Query, Request, State},
+    http::{header::AUTHORIZATION, HeaderName, HeaderValue},
     middleware::Next,
-    response::Response,
+    response::{IntoResponse, Response},
+    Extension,
 };
+use serde::Deserialize;
+use tracing::Instrument;
+use uuid::Uuid;
+
+use super::error::ApiError;
+use crate::application::ports::{AuthTokenPort, UserRepositoryPort};
+use crate::application::with_request_id;
+
+/// 请求 ID 的请求头/响应头
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// 客户端传入的请求 ID 最大长度
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// 不需要登录的接口（健康检查和登录/注册本身）
+const PUBLIC_API_PATHS: &[&str] = &["/api/ping", "/api/ready", "/api/auth/login", "/api/auth/register"];
+
+/// 不需要登录的接口前缀：TTS 服务按 URL 下载参考音频，不携带令牌（由链接签名校验）
+const PUBLIC_API_PREFIXES: &[&str] = &["/api/voice/audio/"];
+
+/// 管理接口前缀
+const ADMIN_API_PREFIX: &str = "/api/admin/";
+
+/// 管理令牌的请求头
+pub const ADMIN_TOKEN_HEADER: HeaderName = HeaderName::from_static("x-admin-token");
+
+/// 请求的客户端 IP（由 [`client_ip_middleware`] 写入请求扩展）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientIp(pub String);
+
+/// 客户端 IP 识别中间件
+///
+/// 取连接的对端地址；`trust_forwarded_for` 开启时优先使用 X-Forwarded-For 中的第一个地址。
+/// 两者都取不到（如测试中直接调用 Router）时不写入扩展
+pub async fn client_ip_middleware(
+    State(trust_forwarded_for): State<bool>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let forwarded = trust_forwarded_for
+        .then(|| request.headers().get("x-forwarded-for"))
+        .flatten()
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(',').next())
+        .map(str::trim)
+        .filter(|ip| !ip.is_empty())
+        .map(str::to_string);
+    let client_ip = forwarded.or_else(|| {
+        request
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip().to_string())
+    });
+    if let Some(ip) = client_ip {
+        request.extensions_mut().insert(ClientIp(ip));
+    }
+    next.run(request).await
+}
+
+/// 查询参数中的登录令牌
+#[derive(Debug, Deserialize)]
+struct AccessTokenQuery {
+    access_token: Option<String>,
+}
+
+/// 当前登录用户（由 [`auth_middleware`] 写入请求扩展，未启用账号时不存在）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CurrentUser(pub Uuid);
+
+/// 取出当前登录用户的 ID（未启用账号时为 None）
+pub fn current_user_id(user: Option<Extension<CurrentUser>>) -> Option<Uuid> {
+    user.map(|Extension(CurrentUser(user_id))| user_id)
+}
+
+/// 登录令牌校验中间件
+///
+/// 未启用账号（`tokens` 为 None）时直接放行。否则 /api 和 /ws 下除公开接口外的请求
+/// 都需要令牌：优先取 `Authorization: Bearer`，其次取 `?access_token=`（WebSocket 和 `<audio>`
+/// 无法设置请求头）；缺少或无效时返回 401，静态文件不受影响
+pub async fn auth_middleware(
+    State(tokens): State<Option<Arc<dyn AuthTokenPort>>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let Some(tokens) = tokens else {
+        return next.run(request).await;
+    };
+    let path = request.uri().path();
+    // 携带管理令牌的管理接口请求由 admin_middleware 校验
+    let admin_token = path.starts_with(ADMIN_API_PREFIX) && request.headers().contains_key(&ADMIN_TOKEN_HEADER);
+    let protected = (path.starts_with("/api/") || path.starts_with("/ws/"))
+        && !admin_token
+        && !PUBLIC_API_PATHS.contains(&path)
+        && !PUBLIC_API_PREFIXES.iter().any(|prefix| path.starts_with(prefix));
+    if !protected {
+        return next.run(request).await;
+    }
+
+    let bearer = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim)
+        .map(str::to_string);
+    let token = bearer.or_else(|| {
+        Query::<AccessTokenQuery>::try_from_uri(request.uri())
+            .ok()
+            .and_then(|Query(query)| query.access_token)
+    });
+    let Some(token) = token.filter(|token| !token.is_empty()) else {
+        return ApiError::Unauthorized("Missing access token".to_string()).into_response();
+    };
+
+    match tokens.verify(&token) {
+        Ok(user_id) => {
+            request.extensions_mut().insert(CurrentUser(user_id));
+            next.run(request).await
+        }
+        Err(e) => ApiError::Unauthorized(e.to_string()).into_response(),
+    }
+}
+
+/// 管理接口访问校验
+#[derive(Clone, Default)]
+pub struct AdminGuard {
+    /// 管理令牌（X-Admin-Token），None 表示不接受令牌
+    pub token: Option<Arc<str>>,
+    /// 启用账号时用于确认当前用户是否为管理员，未启用账号时为 None
+    pub users: Option<Arc<dyn UserRepositoryPort>>,
+}
+
+/// 管理接口校验中间件（需在 [`auth_middleware`] 之后执行）
+///
+/// /api/admin 下的请求：携带 X-Admin-Token 时必须与配置的管理令牌一致；否则启用账号时
+/// 当前用户必须是管理员（非管理员返回 403）。既未设置管理令牌也未启用账号时管理接口关闭，
+/// 一律返回 403
+pub async fn admin_middleware(State(guard): State<AdminGuard>, request: Request, next: Next) -> Response {
+    if !request.uri().path().starts_with(ADMIN_API_PREFIX) {
+        return next.run(request).await;
+    }
+
+    if let Some(presented) = request.headers().get(&ADMIN_TOKEN_HEADER) {
+        return match guard.token.as_deref() {
+            Some(token) if constant_time_eq(presented.as_bytes(), token.as_bytes()) => next.run(request).await,
+            _ => ApiError::Unauthorized("Invalid admin token".to_string()).into_response(),
+        };
+    }
+
+    let current_user = request.extensions().get::<CurrentUser>().copied();
+    match (guard.users.as_ref(), current_user) {
+        (Some(users), Some(CurrentUser(user_id))) => match users.find_by_id(user_id).await {
+            Ok(Some(user)) if user.is_admin => next.run(request).await,
+            Ok(_) => ApiError::Forbidden("Admin privileges required".to_string()).into_response(),
+            Err(e) => ApiError::from(e).into_response(),
+        },
+        (Some(_), None) => ApiError::Unauthorized("Missing access token".to_string()).into_response(),
+        (None, _) if guard.token.is_some() => {
+            ApiError::Unauthorized("Missing admin token".to_string()).into_response()
+        }
+        (None, _) => ApiError::Forbidden(
+            "Admin API is disabled: set auth.admin_token or enable user accounts".to_string(),
+        )
+        .into_response(),
+    }
+}
+
+/// 比较令牌时耗时与内容无关，避免逐字节猜测
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// 请求 ID 中间件
+///
+/// 沿用客户端（或反向代理）传入的 X-Request-Id，缺少或格式不合法时生成新的 UUID。
+/// 请求处理期间的日志都在带 request_id 的 span 中，期间提交的推理任务记录该 ID
+/// （见 [`crate::application::request_context`]），响应头返回同一个 ID
+pub async fn request_id_middleware(request: Request, next: Next) -> Response {
+    let request_id = request
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| is_valid_request_id(value))
+        .map(str::to_string)
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    let span = tracing::info_span!("request", request_id = %request_id);
+    let mut response = with_request_id(Some(request_id.clone()), next.run(request))
+        .instrument(span)
+        .await;
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+/// 请求 ID 只接受有限长度的可打印字符，避免日志注入
+fn is_valid_request_id(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= MAX_REQUEST_ID_LEN
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b':'))
+}
 
 /// HTTP 状态码错误日志中间件
 ///
@@ -104,4 +313,240 @@ mod tests {
         let response = app.oneshot(request).await.unwrap();
         assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
     }
+
+    async fn client_ip_handler(client_ip: Option<axum::Extension<ClientIp>>) -> String {
+        client_ip.map(|axum::Extension(ClientIp(ip))| ip).unwrap_or_default()
+    }
+
+    async fn client_ip_of(trust_forwarded_for: bool, forwarded_for: &str) -> String {
+        let app = Router::new()
+            .route("/ip", get(client_ip_handler))
+            .layer(axum::middleware::from_fn_with_state(
+                trust_forwarded_for,
+                client_ip_middleware,
+            ));
+        let mut request = HttpRequest::builder()
+            .uri("/ip")
+            .header("x-forwarded-for", forwarded_for)
+            .body(Body::empty())
+            .unwrap();
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from(([10, 0, 0, 1], 40000))));
+
+        let response = app.oneshot(request).await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_client_ip_from_forwarded_for_only_when_trusted() {
+        assert_eq!(client_ip_of(false, "203.0.113.7").await, "10.0.0.1");
+        assert_eq!(client_ip_of(true, "203.0.113.7, 10.0.0.2").await, "203.0.113.7");
+        assert_eq!(client_ip_of(true, "").await, "10.0.0.1");
+    }
+
+    struct FixedTokens(Uuid);
+
+    impl AuthTokenPort for FixedTokens {
+        fn issue(&self, _: Uuid) -> Result<crate::application::ports::IssuedToken, crate::application::ports::AuthError> {
+            unimplemented!()
+        }
+
+        fn verify(&self, token: &str) -> Result<Uuid, crate::application::ports::AuthError> {
+            match token {
+                "good" => Ok(self.0),
+                _ => Err(crate::application::ports::AuthError::InvalidToken("bad signature".to_string())),
+            }
+        }
+    }
+
+    async fn current_user_handler(user: Option<axum::Extension<CurrentUser>>) -> String {
+        user.map(|axum::Extension(CurrentUser(id))| id.to_string()).unwrap_or_default()
+    }
+
+    async fn auth_request(tokens: Option<Arc<dyn AuthTokenPort>>, uri: &str, bearer: Option<&str>) -> (StatusCode, String) {
+        let app = Router::new()
+            .route("/api/ping", get(current_user_handler))
+            .route("/api/novel/list", get(current_user_handler))
+            .route("/api/voice/audio/:voice_id", get(current_user_handler))
+            .route("/index.html", get(current_user_handler))
+            .layer(axum::middleware::from_fn_with_state(tokens, auth_middleware));
+        let mut request = HttpRequest::builder().uri(uri);
+        if let Some(bearer) = bearer {
+            request = request.header("authorization", format!("Bearer {}", bearer));
+        }
+
+        let response = app.oneshot(request.body(Body::empty()).unwrap()).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    async fn request_id_of(header: Option<&str>) -> (String, String) {
+        let app = Router::new()
+            .route(
+                "/request-id",
+                get(|| async { crate::application::current_request_id().unwrap_or_default() }),
+            )
+            .layer(axum::middleware::from_fn(request_id_middleware));
+        let mut request = HttpRequest::builder().uri("/request-id");
+        if let Some(header) = header {
+            request = request.header("x-request-id", header);
+        }
+
+        let response = app.oneshot(request.body(Body::empty()).unwrap()).await.unwrap();
+        let response_header = response.headers()["x-request-id"].to_str().unwrap().to_string();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (String::from_utf8(body.to_vec()).unwrap(), response_header)
+    }
+
+    #[tokio::test]
+    async fn test_request_id_middleware() {
+        // 沿用客户端传入的 ID
+        let (seen, returned) = request_id_of(Some("client-42")).await;
+        assert_eq!(seen, "client-42");
+        assert_eq!(returned, "client-42");
+
+        // 缺少或不合法时生成新 ID
+        for header in [None, Some("bad id with spaces"), Some("")] {
+            let (seen, returned) = request_id_of(header).await;
+            assert!(Uuid::parse_str(&seen).is_ok());
+            assert_eq!(seen, returned);
+        }
+        let too_long = "a".repeat(MAX_REQUEST_ID_LEN + 1);
+        assert_ne!(request_id_of(Some(&too_long)).await.0, too_long);
+    }
+
+    #[tokio::test]
+    async fn test_auth_middleware() {
+        let user_id = Uuid::new_v4();
+        let tokens: Arc<dyn AuthTokenPort> = Arc::new(FixedTokens(user_id));
+
+        // 未启用账号时放行
+        assert_eq!(auth_request(None, "/api/novel/list", None).await, (StatusCode::OK, String::new()));
+
+        // 令牌来自请求头或查询参数
+        let expected = (StatusCode::OK, user_id.to_string());
+        assert_eq!(auth_request(Some(tokens.clone()), "/api/novel/list", Some("good")).await, expected);
+        assert_eq!(
+            auth_request(Some(tokens.clone()), "/api/novel/list?access_token=good", None).await,
+            expected
+        );
+
+        // 缺少或无效的令牌
+        assert_eq!(auth_request(Some(tokens.clone()), "/api/novel/list", None).await.0, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            auth_request(Some(tokens.clone()), "/api/novel/list", Some("bad")).await.0,
+            StatusCode::UNAUTHORIZED
+        );
+
+        // 健康检查、参考音频下载和静态文件不需要令牌
+        assert_eq!(auth_request(Some(tokens.clone()), "/api/ping", None).await.0, StatusCode::OK);
+        let voice_audio = format!("/api/voice/audio/{}", Uuid::new_v4());
+        assert_eq!(auth_request(Some(tokens.clone()), &voice_audio, None).await.0, StatusCode::OK);
+        assert_eq!(auth_request(Some(tokens), "/index.html", None).await.0, StatusCode::OK);
+    }
+
+    /// 只有 `admin` 是管理员
+    struct FixedUsers {
+        admin: Uuid,
+    }
+
+    #[async_trait::async_trait]
+    impl UserRepositoryPort for FixedUsers {
+        async fn create(
+            &self,
+            _: &crate::application::ports::UserRecord,
+            _: bool,
+        ) -> Result<Option<bool>, crate::application::RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn find_by_id(
+            &self,
+            id: Uuid,
+        ) -> Result<Option<crate::application::ports::UserRecord>, crate::application::RepositoryError> {
+            Ok(Some(crate::application::ports::UserRecord {
+                id,
+                username: id.to_string(),
+                password_hash: String::new(),
+                is_admin: id == self.admin,
+                created_at: chrono::Utc::now(),
+            }))
+        }
+
+        async fn find_by_username(
+            &self,
+            _: &str,
+        ) -> Result<Option<crate::application::ports::UserRecord>, crate::application::RepositoryError> {
+            unimplemented!()
+        }
+    }
+
+    async fn admin_request(
+        tokens: Option<Arc<dyn AuthTokenPort>>,
+        guard: AdminGuard,
+        bearer: Option<&str>,
+        admin_token: Option<&str>,
+    ) -> (StatusCode, String) {
+        let app = Router::new()
+            .route("/api/admin/worker", get(|| async { "ok" }))
+            .layer(axum::middleware::from_fn_with_state(guard, admin_middleware))
+            .layer(axum::middleware::from_fn_with_state(tokens, auth_middleware));
+        let mut request = HttpRequest::builder().uri("/api/admin/worker");
+        if let Some(bearer) = bearer {
+            request = request.header("authorization", format!("Bearer {}", bearer));
+        }
+        if let Some(admin_token) = admin_token {
+            request = request.header("x-admin-token", admin_token);
+        }
+
+        let response = app.oneshot(request.body(Body::empty()).unwrap()).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_admin_middleware() {
+        let ok = (StatusCode::OK, "ok".to_string());
+        let with_token = || AdminGuard {
+            token: Some(Arc::from("secret")),
+            users: None,
+        };
+
+        // 未启用账号、未设置管理令牌时管理接口关闭（携带任意令牌也不放行）
+        let (status, body) = admin_request(None, AdminGuard::default(), None, None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains("403"));
+        assert_eq!(
+            admin_request(None, AdminGuard::default(), None, Some("secret")).await.0,
+            StatusCode::UNAUTHORIZED
+        );
+
+        // 设置管理令牌后必须携带且一致
+        assert_eq!(admin_request(None, with_token(), None, Some("secret")).await, ok);
+        assert_eq!(admin_request(None, with_token(), None, None).await.0, StatusCode::UNAUTHORIZED);
+        assert_eq!(admin_request(None, with_token(), None, Some("wrong")).await.0, StatusCode::UNAUTHORIZED);
+
+        // 启用账号：管理员用户或管理令牌可访问，普通用户返回 errno 403
+        let admin = Uuid::new_v4();
+        let users: Arc<dyn UserRepositoryPort> = Arc::new(FixedUsers { admin });
+        let guard = AdminGuard {
+            token: Some(Arc::from("secret")),
+            users: Some(users),
+        };
+        let admin_tokens: Arc<dyn AuthTokenPort> = Arc::new(FixedTokens(admin));
+        let user_tokens: Arc<dyn AuthTokenPort> = Arc::new(FixedTokens(Uuid::new_v4()));
+        assert_eq!(admin_request(Some(admin_tokens.clone()), guard.clone(), Some("good"), None).await, ok);
+        assert_eq!(admin_request(Some(user_tokens.clone()), guard.clone(), None, Some("secret")).await, ok);
+        let (status, body) = admin_request(Some(user_tokens.clone()), guard.clone(), Some("good"), None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains("403"));
+        assert_eq!(
+            admin_request(Some(admin_tokens), guard, None, None).await.0,
+            StatusCode::UNAUTHORIZED
+        );
+    }
 }
@@ -12,5 +12,5 @@ pub mod state;
 
 pub use error::ApiError;
 pub use routes::create_routes;
-pub use server::{HttpServer, ServerConfig};
-pub use state::AppState;
+pub use server::{HttpServer, ServerConfig, TlsFiles};
+pub use state::{AdminServices, AppState, AuthServices};
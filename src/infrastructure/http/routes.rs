@@ -3,24 +3,90 @@
 //! API 路由定义 - 基于 ARCHITECTURE.md V2 设计
 //!
 //! API Endpoints:
+//! - /api/ping              GET   健康检查
+//! - /api/ready             GET   就绪检查（启用 TTS 预热时预热完成前返回 503）
+//! - /api/auth/register     POST  注册用户并返回登录令牌（启用账号时；第一个用户接管已有小说）
+//! - /api/auth/login        POST  登录并返回登录令牌
 //! - /api/novel/upload      POST  上传小说（异步处理，通过 WS 通知完成）
 //! - /api/novel/delete      POST  删除小说
-//! - /api/novel/get         POST  获取小说详情
-//! - /api/novel/list        GET   列出所有小说
-//! - /api/novel/segments    POST  获取小说片段
+//! - /api/novel/get         POST  获取小说详情（兼容旧客户端，请使用 GET /api/novels/{id}）
+//! - /api/novel/list        GET   列出小说（?offset=&limit=，分页）
+//! - /api/novel/segments    POST  获取小说片段（兼容旧客户端，请使用 GET /api/novels/{id}/segments）
+//! - /api/novel/segment-preview POST 分段预览（不持久化）
+//! - /api/novel/chapters    POST  获取章节（按标题片段识别）
+//! - /api/novel/chapter/export POST 导出章节音频（拼接已缓存片段，附件下载）
+//! - /api/novel/casting/get POST 获取角色配音表（含识别出的角色）
+//! - /api/novel/casting/update POST 设置/删除角色配音
+//! - /api/novels/{id}       GET   获取小说详情
+//! - /api/novels/{id}/segments GET 获取小说片段（?offset=&limit=，分页；offset 兼容旧参数名 start）
 //! - /api/voice/upload      POST  上传音色
-//! - /api/voice/delete      POST  删除音色
-//! - /api/voice/get         POST  获取音色详情
-//! - /api/voice/list        GET   列出所有音色
-//! - /api/session/play      POST  开始播放（创建会话）
+//! - /api/voice/update      POST  更新音色（名称/描述/参考音频/TTS 参数）
+//! - /api/voice/delete      POST  删除音色（被会话使用时返回 409，force 强制删除）
+//! - /api/voice/get         POST  获取音色详情（兼容旧客户端，请使用 GET /api/voices/{id}）
+//! - /api/voice/list        GET   列出音色（?gender=&language=&style= 过滤）
+//! - /api/voice/tags        GET   列出已使用的音色标签
+//! - /api/voice/engines     GET   列出可用的 TTS 引擎
+//! - /api/voice/export/{id} GET   导出音色包（tar）
+//! - /api/voice/import      POST  导入音色包（保留原 UUID）
+//! - /api/voices/{id}       GET   获取音色详情
+//! - /api/session/play      POST  开始播放（创建会话；reuse=true 复用同一小说/音色/参数的会话；auto_advance=true 自动前进；超出会话数上限返回 429）
 //! - /api/session/seek      POST  跳转位置
-//! - /api/session/change_voice POST 切换音色
+//! - /api/session/next      POST  前进到下一个片段（返回片段文本和音频状态）
+//! - /api/session/prev      POST  回到上一个片段（同上）
+//! - /api/session/list      GET   列出会话（?novel_id=&state=idle|playing&offset=&limit=，分页）
+//! - /api/session/active    GET   列出活动会话（?novel_id=&offset=&limit=，分页）
+//! - /api/session/{id}/segments GET 片段就绪状态（?start=&end=，默认为预取窗口）
+//! - /api/session/{id}/position POST 上报播放进度（片段索引 + 片段内偏移，随会话持久化）
+//! - /api/session/{id}/heartbeat POST 保持会话活动（无 WebSocket 的客户端），返回预取窗口就绪概览
+//! - /api/session/{id}/prefetch POST 批量预取指定片段（segment_indices 或 start/end，数量受 prefetch.max_batch 限制）
+//! - /api/session/{id}/snapshot GET 会话快照（小说/音色/位置/窗口/速度编码为可分享的令牌）
+//! - /api/session/restore   POST  从快照令牌恢复为新会话（可在另一实例上恢复）
+//! - /api/session/window    POST  调整会话预取窗口（before/after，不超过配置上限）
+//! - /api/session/change_voice POST 切换音色（可选过渡：新音色就绪前继续返回旧音色的片段）
+//! - /api/session/claim     POST  在当前设备接管会话（返回进度和进行中的任务，原设备的会话 WS 被断开）
 //! - /api/session/close     POST  关闭会话
+//! - /api/settings/get      GET   获取所有设置
+//! - /api/settings/update   POST  写入/删除设置（如 default_voice_id）
 //! - /api/infer/submit      POST  提交推理任务
 //! - /api/infer/status      POST  查询任务状态
-//! - /api/audio             POST  获取音频
+//! - /api/admin/*           管理接口：需管理令牌（X-Admin-Token）或管理员用户的登录令牌，未启用账号且未设置令牌时开放
+//! - /api/admin/tts/health  GET   TTS 引擎健康状态
+//! - /api/admin/metrics     GET   TTS 请求指标（Prometheus 文本格式）
+//! - /api/admin/cache/purge POST  按小说/音色/旧版本清除音频缓存
+//! - /api/admin/cache/stats GET   音频缓存统计（容量、命中率、淘汰数、占用最大的小说）
+//! - /api/admin/cache/export GET  导出音频缓存（流式 tar，迁移服务器时使用）
+//! - /api/admin/cache/import POST 导入音频缓存（请求体为导出的 tar，不受上传大小限制，已有条目跳过）
+//! - /api/admin/storage/reconcile POST 核对音频文件、缓存与数据库记录，清理孤立数据（启动时也会执行一次）
+//! - /api/admin/tasks      GET   列出推理任务（?state=failed 查看失败任务及错误原因；?offset=&limit= 分页）
+//! - /api/admin/tasks/{id}/retry POST 失败的任务重新排队
+//! - /api/admin/tasks/events GET 任务审计日志（?session_id=&novel_id=&segment_index=&task_id=&event=&request_id=&limit=）
+//! - /api/admin/worker     GET   推理 Worker 状态（并发、队列深度、各状态任务数、平均推理耗时）
+//! - /api/admin/sessions   GET   列出所有用户的会话（?novel_id=&state=&offset=&limit=，分页）
+//! - /api/admin/gc         POST  立即执行一轮 GC，返回清理摘要
+//! - /api/admin/config     GET   当前生效的配置（密钥脱敏）
+//! - /api/export/audiobook  POST  导出整部有声书（后台任务，进度通过 /ws/events 通知）
+//! - /api/export/{id}       GET   查询导出任务状态
+//! - /api/export/{id}/download GET 下载导出文件
+//! - /api/pregenerate       POST  整书预生成（后台低优先级合成，进度通过 /ws/events 通知）
+//! - /api/pregenerate/{id}  GET   查询预生成任务状态
+//! - /api/pregenerate/{id}/{action} POST 暂停（pause）/恢复（resume）/取消（cancel）预生成任务
+//! - /api/stats/listening   GET   收听统计（?novel_id=&days=，按天汇总及各小说完成度）
+//! - /api/audio             POST  获取音频（流式推理时返回合成中的音频；?format= 或 Accept 按需转码，?speed= 变速）
+//! - /api/audio/{novel_id}/{voice_id}/{index} GET 获取音频（同上，?session_id=；支持 Range）
+//! - /api/audio/peaks       POST  获取片段波形峰值
 //! - /ws/session/{id}       WS    Session WebSocket（task 状态事件）
 //! - /ws/events             WS    全局 WebSocket（novel 事件）
+//!
+//! 启用账号后，除健康检查和登录/注册外的接口都需要令牌（`Authorization: Bearer` 或 `?access_token=`），
+//! 小说、音色和会话只对所属用户可见；没有所属用户的共享资源（如预置音色）只有管理员可以修改或删除。
+//! 供 TTS 服务下载参考音频的 /api/voice/audio/{id} 不需要令牌，但需附带 Worker 生成的签名（`?sig=`）；
+//! /ws/events 只推送当前用户有权访问的事件
+//!
+//! 分页列表返回 `{items, total, offset, limit, next_offset}`：limit 默认 100、最大 1000，
+//! 已是最后一页时 next_offset 为 null
+//!
+//! 所有接口的响应都带 `X-Request-Id`（沿用请求头中的值或新生成），
+//! 期间提交的推理任务、任务审计日志和 task 状态事件都附带该 ID
 
 use axum::{
     routing::{get, post},
@@ -43,11 +109,32 @@ pub fn create_routes() -> Router<Arc<AppState>> {
 fn api_routes() -> Router<Arc<AppState>> {
     Router::new()
         .route("/ping", get(handlers::ping))
+        .route("/ready", get(handlers::ready))
+        .nest("/auth", auth_routes())
         .nest("/novel", novel_routes())
+        .nest("/novels", novels_routes())
         .nest("/voice", voice_routes())
+        .nest("/voices", voices_routes())
         .nest("/session", session_routes())
+        .nest("/settings", settings_routes())
         .nest("/infer", infer_routes())
         .route("/audio", post(handlers::get_audio))
+        .route("/audio/peaks", post(handlers::get_audio_peaks))
+        .route(
+            "/audio/:novel_id/:voice_id/:segment_index",
+            get(handlers::get_segment_audio),
+        )
+        .nest("/export", export_routes())
+        .nest("/pregenerate", pregenerate_routes())
+        .nest("/stats", stats_routes())
+        .nest("/admin", admin_routes())
+}
+
+/// Auth 路由
+fn auth_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/register", post(handlers::register))
+        .route("/login", post(handlers::login))
 }
 
 /// Novel 路由
@@ -58,30 +145,111 @@ fn novel_routes() -> Router<Arc<AppState>> {
         .route("/get", post(handlers::get_novel))
         .route("/list", get(handlers::list_novels))
         .route("/segments", post(handlers::get_novel_segments))
+        .route("/segment-preview", post(handlers::preview_segments))
+        .route("/chapters", post(handlers::get_novel_chapters))
+        .route("/chapter/export", post(handlers::export_chapter_audio))
+        .route("/casting/get", post(handlers::get_voice_casting))
+        .route("/casting/update", post(handlers::update_voice_casting))
+}
+
+/// Novel 资源路由（GET 读取，便于浏览器、curl 和缓存代理访问）
+fn novels_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/:novel_id", get(handlers::get_novel_by_id))
+        .route("/:novel_id/segments", get(handlers::get_novel_segments_by_id))
 }
 
 /// Voice 路由
 fn voice_routes() -> Router<Arc<AppState>> {
     Router::new()
         .route("/upload", post(handlers::upload_voice))
+        .route("/update", post(handlers::update_voice))
         .route("/delete", post(handlers::delete_voice))
         .route("/get", post(handlers::get_voice))
         .route("/list", get(handlers::list_voices))
+        .route("/tags", get(handlers::list_voice_tags))
+        .route("/engines", get(handlers::list_tts_engines))
+        .route("/export/:voice_id", get(handlers::export_voice))
+        .route("/import", post(handlers::import_voice))
         .route("/audio/:voice_id", get(handlers::download_voice_audio))
 }
 
+/// Voice 资源路由
+fn voices_routes() -> Router<Arc<AppState>> {
+    Router::new().route("/:voice_id", get(handlers::get_voice_by_id))
+}
+
 /// Session 路由
 fn session_routes() -> Router<Arc<AppState>> {
     Router::new()
         .route("/play", post(handlers::play))
         .route("/seek", post(handlers::seek))
+        .route("/next", post(handlers::next_segment))
+        .route("/prev", post(handlers::prev_segment))
+        .route("/list", get(handlers::list_sessions))
+        .route("/active", get(handlers::list_active_sessions))
+        .route("/:session_id/segments", get(handlers::get_session_segments))
+        .route("/:session_id/position", post(handlers::report_position))
+        .route("/:session_id/heartbeat", post(handlers::heartbeat))
+        .route("/:session_id/prefetch", post(handlers::prefetch_segments))
+        .route("/:session_id/snapshot", get(handlers::get_session_snapshot))
+        .route("/restore", post(handlers::restore_session))
+        .route("/window", post(handlers::update_window))
         .route("/change_voice", post(handlers::change_voice))
+        .route("/claim", post(handlers::claim_session))
         .route("/close", post(handlers::close_session))
 }
 
+/// Settings 路由
+fn settings_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/get", get(handlers::get_settings))
+        .route("/update", post(handlers::update_setting))
+}
+
 /// Infer 路由
 fn infer_routes() -> Router<Arc<AppState>> {
     Router::new()
         .route("/submit", post(handlers::submit_infer))
         .route("/status", post(handlers::query_task_status))
 }
+
+/// Export 路由
+fn export_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/audiobook", post(handlers::export_audiobook))
+        .route("/:job_id", get(handlers::get_export_job))
+        .route("/:job_id/download", get(handlers::download_export))
+}
+
+/// 整书预生成路由
+fn pregenerate_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/", post(handlers::pregenerate_novel))
+        .route("/:job_id", get(handlers::get_pregeneration_job))
+        .route("/:job_id/:action", post(handlers::control_pregeneration))
+}
+
+/// 统计路由
+fn stats_routes() -> Router<Arc<AppState>> {
+    Router::new().route("/listening", get(handlers::get_listening_stats))
+}
+
+/// Admin 路由
+fn admin_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/tts/health", get(handlers::get_tts_health))
+        .route("/metrics", get(handlers::get_metrics))
+        .route("/cache/purge", post(handlers::purge_audio_cache))
+        .route("/cache/stats", get(handlers::get_cache_stats))
+        .route("/cache/export", get(handlers::export_audio_cache))
+        .route("/cache/import", post(handlers::import_audio_cache))
+        .route("/storage/reconcile", post(handlers::reconcile_storage))
+        .route("/tasks", get(handlers::list_tasks))
+        .route("/tasks/events", get(handlers::list_task_events))
+        .route("/tasks/:task_id/retry", post(handlers::retry_task))
+        .route("/worker", get(handlers::get_worker_status))
+        .route("/sessions", get(handlers::list_all_sessions))
+        .route("/gc", post(handlers::run_gc))
+        .route("/config", get(handlers::get_config))
+}
@@ -1,21 +1,31 @@
 //! HTTP Server
 //!
-//! Axum HTTP 服务器启动和配置
+//! Axum HTTP 服务器启动和配置（可选 HTTPS，基于 rustls）
 
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use axum::Router;
 use axum::extract::DefaultBodyLimit;
 use axum::middleware;
+use axum_server::tls_rustls::RustlsConfig;
+use http::{Extensions, HeaderMap, StatusCode, Version};
 use tokio::net::TcpListener;
-use tower_http::cors::{Any, CorsLayer};
+use tower_http::compression::predicate::{And, Predicate, SizeAbove};
+use tower_http::compression::CompressionLayer;
+use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, Any, CorsLayer, ExposeHeaders};
 use tower_http::services::{ServeDir, ServeFile};
 use tower_http::trace::TraceLayer;
-use http::header::{AUTHORIZATION, CONTENT_TYPE};
+use http::header::{ACCEPT_RANGES, CONTENT_DISPOSITION, CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE, RETRY_AFTER};
+use http::{HeaderName, HeaderValue, Method};
 use tracing::info;
 
-use super::middleware::error_logging_middleware;
+use super::middleware::{
+    admin_middleware, auth_middleware, client_ip_middleware, error_logging_middleware, request_id_middleware, REQUEST_ID_HEADER,
+};
+use crate::config::{CompressionConfig, CorsConfig};
 use super::routes::create_routes;
 use super::state::AppState;
 
@@ -26,6 +36,25 @@ pub struct ServerConfig {
     pub port: u16,
     /// 静态文件配置
     pub static_files: Option<StaticFilesConfig>,
+    /// 使用 X-Forwarded-For 中的第一个地址作为客户端 IP
+    pub trust_forwarded_for: bool,
+    /// 跨域配置
+    pub cors: CorsConfig,
+    /// HTTPS 配置（None 时使用 HTTP）
+    pub tls: Option<TlsFiles>,
+    /// 响应压缩配置
+    pub compression: CompressionConfig,
+}
+
+/// HTTPS 证书配置
+#[derive(Debug, Clone)]
+pub struct TlsFiles {
+    /// 证书链文件（PEM）
+    pub cert_path: PathBuf,
+    /// 私钥文件（PEM）
+    pub key_path: PathBuf,
+    /// 重新读取证书文件的间隔（证书续期后生效），None 表示不重新读取
+    pub reload_interval: Option<Duration>,
 }
 
 /// 静态文件服务配置
@@ -43,6 +72,10 @@ impl Default for ServerConfig {
             host: "0.0.0.0".to_string(),
             port: 5060,
             static_files: None,
+            trust_forwarded_for: false,
+            cors: CorsConfig::default(),
+            tls: None,
+            compression: CompressionConfig::default(),
         }
     }
 }
@@ -53,6 +86,10 @@ impl ServerConfig {
             host: host.into(),
             port,
             static_files: None,
+            trust_forwarded_for: false,
+            cors: CorsConfig::default(),
+            tls: None,
+            compression: CompressionConfig::default(),
         }
     }
 
@@ -61,11 +98,121 @@ impl ServerConfig {
         self
     }
 
+    pub fn with_trust_forwarded_for(mut self, trust_forwarded_for: bool) -> Self {
+        self.trust_forwarded_for = trust_forwarded_for;
+        self
+    }
+
+    pub fn with_cors(mut self, cors: CorsConfig) -> Self {
+        self.cors = cors;
+        self
+    }
+
+    pub fn with_compression(mut self, compression: CompressionConfig) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    pub fn with_tls(mut self, cert_path: PathBuf, key_path: PathBuf, reload_interval: Option<Duration>) -> Self {
+        self.tls = Some(TlsFiles {
+            cert_path,
+            key_path,
+            reload_interval,
+        });
+        self
+    }
+
     pub fn addr(&self) -> String {
         format!("{}:{}", self.host, self.port)
     }
 }
 
+/// 携带凭据时浏览器不接受 "*"，改为逐个列出前端需要读取的响应头
+const EXPOSED_HEADERS: [HeaderName; 6] = [
+    REQUEST_ID_HEADER,
+    CONTENT_RANGE,
+    ACCEPT_RANGES,
+    CONTENT_LENGTH,
+    CONTENT_DISPOSITION,
+    RETRY_AFTER,
+];
+
+/// 按配置构建 CORS 层（配置项已在加载时校验，无法解析的项忽略）
+///
+/// 携带凭据时请求方法和请求头的 "*" 改为回显预检请求中的值
+fn cors_layer(config: &CorsConfig) -> CorsLayer {
+    let origins = if config.any_origin() {
+        AllowOrigin::from(Any)
+    } else {
+        AllowOrigin::list(
+            config
+                .allowed_origins
+                .iter()
+                .filter_map(|origin| HeaderValue::from_str(origin).ok()),
+        )
+    };
+    let methods = match (config.any_method(), config.allow_credentials) {
+        (true, false) => AllowMethods::from(Any),
+        (true, true) => AllowMethods::mirror_request(),
+        (false, _) => AllowMethods::list(
+            config
+                .allowed_methods
+                .iter()
+                .filter_map(|method| Method::from_bytes(method.as_bytes()).ok()),
+        ),
+    };
+    let headers = match (config.any_header(), config.allow_credentials) {
+        (true, false) => AllowHeaders::from(Any),
+        (true, true) => AllowHeaders::mirror_request(),
+        (false, _) => AllowHeaders::list(
+            config
+                .allowed_headers
+                .iter()
+                .filter_map(|header| HeaderName::from_bytes(header.as_bytes()).ok()),
+        ),
+    };
+    let exposed = if config.allow_credentials {
+        ExposeHeaders::list(EXPOSED_HEADERS)
+    } else {
+        ExposeHeaders::from(Any)
+    };
+
+    CorsLayer::new()
+        .allow_origin(origins)
+        .allow_methods(methods)
+        .allow_headers(headers)
+        .allow_credentials(config.allow_credentials)
+        .expose_headers(exposed)
+        .max_age(std::time::Duration::from_secs(config.max_age_secs))
+}
+
+/// 可压缩的响应类型：JSON 和文本（含网页资源）；音频、图片等已压缩的内容不再压缩
+fn is_compressible(_: StatusCode, _: Version, headers: &HeaderMap, _: &Extensions) -> bool {
+    let Some(content_type) = headers.get(CONTENT_TYPE).and_then(|value| value.to_str().ok()) else {
+        return false;
+    };
+    let mime = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+    mime.starts_with("text/")
+        || mime == "application/json"
+        || mime == "application/javascript"
+        || mime == "application/xml"
+        || mime == "image/svg+xml"
+        || mime.ends_with("+json")
+}
+
+/// 响应压缩条件：超过最小大小且为可压缩的类型
+type CompressionPredicate = And<SizeAbove, fn(StatusCode, Version, &HeaderMap, &Extensions) -> bool>;
+
+/// 根据配置构建响应压缩层
+fn compression_layer(config: &CompressionConfig) -> CompressionLayer<CompressionPredicate> {
+    let predicate = SizeAbove::new(config.min_size_bytes)
+        .and(is_compressible as fn(StatusCode, Version, &HeaderMap, &Extensions) -> bool);
+    CompressionLayer::new()
+        .gzip(config.gzip)
+        .br(config.brotli)
+        .compress_when(predicate)
+}
+
 /// HTTP 服务器
 pub struct HttpServer {
     config: ServerConfig,
@@ -88,19 +235,26 @@ impl HttpServer {
 
     /// 构建 Router
     fn build_router(&self) -> Router {
-        // CORS 配置 - 允许所有来源的跨域请求
-        let cors = CorsLayer::new()
-            .allow_origin(Any)
-            .allow_methods(Any)
-            .allow_headers([AUTHORIZATION, CONTENT_TYPE])
-            .expose_headers(Any)
-            .max_age(std::time::Duration::from_secs(3600));
+        let cors = cors_layer(&self.config.cors);
 
         // 构建 API 路由，设置请求体大小限制为 100MB（用于文件上传）
         let mut router = create_routes()
             .layer(DefaultBodyLimit::max(100 * 1024 * 1024))
+            .layer(middleware::from_fn_with_state(
+                self.state.admin_guard.clone(),
+                admin_middleware,
+            ))
+            .layer(middleware::from_fn_with_state(
+                self.state.auth_tokens.clone(),
+                auth_middleware,
+            ))
             .layer(middleware::from_fn(error_logging_middleware))
+            .layer(middleware::from_fn_with_state(
+                self.config.trust_forwarded_for,
+                client_ip_middleware,
+            ))
             .layer(TraceLayer::new_for_http())
+            .layer(middleware::from_fn(request_id_middleware))
             .layer(cors)
             .with_state(self.state.clone());
 
@@ -129,20 +283,17 @@ impl HttpServer {
             }
         }
 
+        // 压缩放在最外层，静态文件（JS/CSS）也一并压缩
+        if self.config.compression.enabled {
+            router = router.layer(compression_layer(&self.config.compression));
+        }
+
         router
     }
 
     /// 启动服务器
     pub async fn run(self) -> Result<(), std::io::Error> {
-        let router = self.build_router();
-        let addr = self.config.addr();
-
-        info!("Starting HTTP server on {}", addr);
-
-        let listener = TcpListener::bind(&addr).await?;
-        axum::serve(listener, router).await?;
-
-        Ok(())
+        self.run_with_shutdown(std::future::pending()).await
     }
 
     /// 启动服务器（带优雅关闭）
@@ -152,14 +303,116 @@ impl HttpServer {
     {
         let router = self.build_router();
         let addr = self.config.addr();
+        let listener = TcpListener::bind(&addr).await?;
 
-        info!("Starting HTTP server on {} (with graceful shutdown)", addr);
+        let Some(tls) = self.config.tls.clone() else {
+            info!("Starting HTTP server on {} (with graceful shutdown)", addr);
+            axum::serve(listener, router.into_make_service_with_connect_info::<SocketAddr>())
+                .with_graceful_shutdown(shutdown_signal)
+                .await?;
+            return Ok(());
+        };
 
-        let listener = TcpListener::bind(&addr).await?;
-        axum::serve(listener, router)
-            .with_graceful_shutdown(shutdown_signal)
-            .await?;
+        let rustls_config = load_rustls_config(&tls).await?;
+        if let Some(interval) = tls.reload_interval {
+            tokio::spawn(reload_certificates(rustls_config.clone(), tls.clone(), interval));
+        }
+
+        info!("Starting HTTPS server on {} (with graceful shutdown)", addr);
+        let handle = axum_server::Handle::new();
+        let shutdown_handle = handle.clone();
+        tokio::spawn(async move {
+            shutdown_signal.await;
+            shutdown_handle.graceful_shutdown(None);
+        });
+        axum_server::from_tcp_rustls(listener.into_std()?, rustls_config)
+            .handle(handle)
+            .serve(router.into_make_service_with_connect_info::<SocketAddr>())
+            .await
+    }
+}
+
+/// 读取证书和私钥
+async fn load_rustls_config(tls: &TlsFiles) -> Result<RustlsConfig, std::io::Error> {
+    // 进程内只使用 ring 作为 rustls 的加密实现；已安装时忽略
+    let _ = rustls::crypto::ring::default_provider().install_default();
+
+    RustlsConfig::from_pem_file(&tls.cert_path, &tls.key_path)
+        .await
+        .map_err(|e| {
+            std::io::Error::new(
+                e.kind(),
+                format!(
+                    "Failed to load TLS certificate {} / key {}: {}",
+                    tls.cert_path.display(),
+                    tls.key_path.display(),
+                    e
+                ),
+            )
+        })
+}
+
+/// 定期重新读取证书文件（外部 ACME 客户端续期后生效）；读取失败时继续使用当前证书
+async fn reload_certificates(config: RustlsConfig, tls: TlsFiles, interval: Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.tick().await;
+    loop {
+        ticker.tick().await;
+        match config.reload_from_pem_file(&tls.cert_path, &tls.key_path).await {
+            Ok(()) => tracing::debug!(cert = %tls.cert_path.display(), "TLS certificate reloaded"),
+            Err(e) => tracing::warn!(
+                cert = %tls.cert_path.display(),
+                error = %e,
+                "Failed to reload TLS certificate, keeping the current one"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request, routing::get};
+    use http::header::{ACCEPT_ENCODING, CONTENT_ENCODING};
+    use tower::util::ServiceExt;
+
+    fn test_router(config: &CompressionConfig) -> Router {
+        let json = "[".to_string() + &"{\"index\":0},".repeat(200) + "{}]";
+        Router::new()
+            .route("/json", get(move || async move { ([(CONTENT_TYPE, "application/json")], json) }))
+            .route("/small", get(|| async { ([(CONTENT_TYPE, "application/json")], "{}") }))
+            .route("/audio", get(|| async { ([(CONTENT_TYPE, "audio/wav")], vec![0u8; 4096]) }))
+            .layer(compression_layer(config))
+    }
+
+    async fn content_encoding(router: &Router, uri: &str, accept_encoding: &str) -> Option<String> {
+        let request = Request::builder()
+            .uri(uri)
+            .header(ACCEPT_ENCODING, accept_encoding)
+            .body(Body::empty())
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        response
+            .headers()
+            .get(CONTENT_ENCODING)
+            .map(|value| value.to_str().unwrap().to_string())
+    }
+
+    #[tokio::test]
+    async fn test_compression_layer() {
+        let router = test_router(&CompressionConfig::default());
+        assert_eq!(content_encoding(&router, "/json", "gzip").await.as_deref(), Some("gzip"));
+        assert_eq!(content_encoding(&router, "/json", "br").await.as_deref(), Some("br"));
+        assert_eq!(content_encoding(&router, "/json", "identity").await, None);
+        // 小响应和音频不压缩
+        assert_eq!(content_encoding(&router, "/small", "gzip").await, None);
+        assert_eq!(content_encoding(&router, "/audio", "gzip, br").await, None);
 
-        Ok(())
+        let gzip_only = test_router(&CompressionConfig {
+            brotli: false,
+            ..Default::default()
+        });
+        assert_eq!(content_encoding(&gzip_only, "/json", "br").await, None);
+        assert_eq!(content_encoding(&gzip_only, "/json", "br, gzip").await.as_deref(), Some("gzip"));
     }
 }
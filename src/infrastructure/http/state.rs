@@ -3,21 +3,59 @@
 //! V2 架构 - 基于 ARCHITECTURE.md 设计
 //! 包含所有 Command/Query Handlers 的应用状态
 
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use crate::application::{
     // Command handlers
-    ChangeVoiceHandler, CloseSessionHandler, CreateNovelFromTextHandler, CreateVoiceHandler,
-    DeleteNovelHandler, DeleteVoiceHandler, PlayHandler, ProcessNovelSegmentsHandler,
-    QueryTaskStatusHandler, SeekHandler, SubmitInferHandler,
+    ChangeVoiceHandler, ClaimSessionHandler, CloseSessionHandler, ControlPregenerationHandler,
+    CreateNovelFromTextHandler, CreateVoiceHandler,
+    DeleteNovelHandler, DeleteVoiceHandler, ExportAudiobookHandler, ExportChapterAudioHandler,
+    ImportVoiceHandler, ListTaskEventsHandler, ListTasksHandler, LoginHandler, NormalizeVoiceAudioHandler,
+    PlayHandler, RegisterUserHandler, PregenerateNovelHandler,
+    ProcessNovelSegmentsHandler, PurgeAudioCacheHandler, QueryTaskStatusHandler,
+    ReconcileStorageHandler, ReportPositionHandler, RetryTaskHandler, SeekHandler, StepSegmentHandler, HeartbeatHandler, PrefetchSegmentsHandler, SubmitInferHandler, UpdateSettingHandler, UpdateVoiceCastingHandler, UpdateVoiceHandler, UpdateWindowHandler,
     // Query handlers
-    GetAudioHandler, GetNovelHandler, GetNovelSegmentsHandler, GetVoiceHandler,
-    ListNovelsHandler, ListVoicesHandler,
+    GetAudioHandler, GetAudioPeaksHandler, GetCacheStatsHandler, GetExportJobHandler, GetListeningStatsHandler, GetNovelChaptersHandler, GetNovelHandler, GetNovelSegmentsHandler, GetPregenerationJobHandler, GetSessionSegmentsHandler, GetSessionSnapshotHandler, GetSettingsHandler,
+    GetTtsEngineStatusHandler, GetVoiceCastingHandler, GetVoiceHandler,
+    ListActiveSessionsHandler, ListNovelsHandler, ListSessionsHandler, ListVoiceTagsHandler, ListVoicesHandler, PreviewSegmentsHandler,
     // Ports
-    AudioCachePort, NovelRepositoryPort, SessionManagerPort, TaskManagerPort, TtsEnginePort,
+    AudioCachePort, AudioSegmentRepositoryPort, ListeningStatsRepositoryPort, ListeningTracker, ReadingProgressRepositoryPort, NovelRepositoryPort, PartialAudioPort, SessionManagerPort, SettingsRepositoryPort,
+    TaskEventRepositoryPort, TaskManagerPort, TtsEngineStatusRepositoryPort, VoiceCastingRepositoryPort, VoiceCastingResolver,
     VoiceRepositoryPort,
+    AccessPolicy, AudioTagResolver, AutoAdvanceNotifier, PrefetchCoordinator, PrefetchSettings, PregenerationSchedule, SegmentStatusResolver, SessionLimits,
 };
+use crate::application::ports::{
+    AudioStoragePort, AuthTokenPort, PasswordHasherPort, UserRepositoryPort, AudioTranscoderPort, ExportJobManagerPort, PregenerationJobManagerPort, ReferenceNormalizeConfig,
+    SessionRepositoryPort, TranscodeConfig,
+};
+use crate::infrastructure::adapters::{TtsEngineRegistry, TtsMetrics, VoiceAudioSigner};
 use crate::infrastructure::events::EventPublisher;
+use crate::infrastructure::http::middleware::AdminGuard;
+use crate::infrastructure::worker::{GcService, WorkerMonitor};
+
+/// 用户账号服务（启用账号时提供）
+pub struct AuthServices {
+    pub user_repo: Arc<dyn UserRepositoryPort>,
+    pub password_hasher: Arc<dyn PasswordHasherPort>,
+    pub tokens: Arc<dyn AuthTokenPort>,
+    /// 参考音频下载链接签名（与 Worker 使用同一密钥）
+    pub voice_audio_signer: VoiceAudioSigner,
+    /// 是否允许注册（第一个用户总是可以注册）
+    pub allow_registration: bool,
+}
+
+/// 管理接口依赖
+pub struct AdminServices {
+    /// 管理令牌（X-Admin-Token），None 表示不接受令牌
+    pub token: Option<String>,
+    pub worker: WorkerMonitor,
+    /// 未启用自动 GC 时同样可以手动触发
+    pub gc: Arc<GcService>,
+    /// 脱敏后的运行配置
+    pub config: serde_json::Value,
+}
 
 /// 应用状态
 ///
@@ -28,74 +66,258 @@ pub struct AppState {
     pub task_manager: Arc<dyn TaskManagerPort>,
     pub novel_repo: Arc<dyn NovelRepositoryPort>,
     pub voice_repo: Arc<dyn VoiceRepositoryPort>,
+    pub settings_repo: Arc<dyn SettingsRepositoryPort>,
+    pub voice_casting_repo: Arc<dyn VoiceCastingRepositoryPort>,
     pub audio_cache: Arc<dyn AudioCachePort>,
-    pub tts_engine: Arc<dyn TtsEnginePort>,
+    pub partial_audio: Arc<dyn PartialAudioPort>,
+    pub tts_engines: Arc<TtsEngineRegistry>,
+    pub tts_metrics: Arc<TtsMetrics>,
     pub event_publisher: Arc<EventPublisher>,
+    /// 预取协调器（空闲会话的预取由后台任务暂停）
+    pub prefetch: Arc<PrefetchCoordinator>,
+    /// 服务是否就绪（启用 TTS 预热时在预热完成后置为 true）
+    pub ready: Arc<AtomicBool>,
+    /// 登录令牌校验（未启用账号时为 None）
+    pub auth_tokens: Option<Arc<dyn AuthTokenPort>>,
+    /// 启用账号时校验参考音频下载链接的签名
+    pub voice_audio_signer: Option<VoiceAudioSigner>,
+    /// 小说、音色和会话的归属校验
+    pub access: AccessPolicy,
+    /// 管理接口访问校验
+    pub admin_guard: AdminGuard,
+    /// 推理 Worker 状态
+    pub worker: WorkerMonitor,
+    pub gc: Arc<GcService>,
+    /// 脱敏后的运行配置
+    pub config: Arc<serde_json::Value>,
 
     // ========== Command Handlers ==========
     pub create_novel_handler: CreateNovelFromTextHandler,
     pub process_novel_handler: ProcessNovelSegmentsHandler,
     pub delete_novel_handler: DeleteNovelHandler,
     pub create_voice_handler: CreateVoiceHandler,
+    pub update_voice_handler: UpdateVoiceHandler,
+    pub normalize_voice_audio_handler: NormalizeVoiceAudioHandler,
+    pub import_voice_handler: ImportVoiceHandler,
     pub delete_voice_handler: DeleteVoiceHandler,
     pub play_handler: PlayHandler,
     pub seek_handler: SeekHandler,
+    pub step_segment_handler: StepSegmentHandler,
+    pub report_position_handler: ReportPositionHandler,
+    pub heartbeat_handler: HeartbeatHandler,
+    pub prefetch_segments_handler: PrefetchSegmentsHandler,
+    pub update_window_handler: UpdateWindowHandler,
     pub change_voice_handler: ChangeVoiceHandler,
+    pub claim_session_handler: ClaimSessionHandler,
     pub close_session_handler: CloseSessionHandler,
     pub submit_infer_handler: SubmitInferHandler,
     pub query_task_status_handler: QueryTaskStatusHandler,
+    pub list_tasks_handler: ListTasksHandler,
+    pub list_task_events_handler: ListTaskEventsHandler,
+    pub retry_task_handler: RetryTaskHandler,
+    pub update_setting_handler: UpdateSettingHandler,
+    pub update_voice_casting_handler: UpdateVoiceCastingHandler,
+    pub export_chapter_audio_handler: ExportChapterAudioHandler,
+    pub export_audiobook_handler: ExportAudiobookHandler,
+    pub purge_audio_cache_handler: PurgeAudioCacheHandler,
+    pub reconcile_storage_handler: ReconcileStorageHandler,
+    pub pregenerate_novel_handler: PregenerateNovelHandler,
+    pub control_pregeneration_handler: ControlPregenerationHandler,
+    /// 未启用账号时为 None
+    pub register_user_handler: Option<RegisterUserHandler>,
+    /// 未启用账号时为 None
+    pub login_handler: Option<LoginHandler>,
 
     // ========== Query Handlers ==========
     pub get_novel_handler: GetNovelHandler,
     pub list_novels_handler: ListNovelsHandler,
     pub get_novel_segments_handler: GetNovelSegmentsHandler,
+    pub get_novel_chapters_handler: GetNovelChaptersHandler,
+    pub preview_segments_handler: PreviewSegmentsHandler,
     pub get_voice_handler: GetVoiceHandler,
     pub list_voices_handler: ListVoicesHandler,
+    pub list_voice_tags_handler: ListVoiceTagsHandler,
     pub get_audio_handler: GetAudioHandler,
+    pub get_audio_peaks_handler: GetAudioPeaksHandler,
+    pub get_settings_handler: GetSettingsHandler,
+    pub get_voice_casting_handler: GetVoiceCastingHandler,
+    pub get_tts_engine_status_handler: GetTtsEngineStatusHandler,
+    pub get_listening_stats_handler: GetListeningStatsHandler,
+    pub get_cache_stats_handler: GetCacheStatsHandler,
+    pub get_export_job_handler: GetExportJobHandler,
+    pub get_pregeneration_job_handler: GetPregenerationJobHandler,
+    pub list_sessions_handler: ListSessionsHandler,
+    pub list_active_sessions_handler: ListActiveSessionsHandler,
+    pub get_session_segments_handler: GetSessionSegmentsHandler,
+    pub get_session_snapshot_handler: GetSessionSnapshotHandler,
 }
 
 impl AppState {
     /// 创建应用状态
+    ///
+    /// `reference_normalize` 为 None 时上传的参考音频不做归一化；
+    /// `audio_output` 为音频接口按需转码及导出的参数；
+    /// `exports_dir` 为有声书导出文件目录；
+    /// `pregeneration_jobs` 记录整书预生成任务；
+    /// `voice_casting` 需与 InferWorker 使用同一配置，保证缓存 key 一致；
+    /// `prefetch_settings` 为预取开关及会话窗口的默认值和上限；
+    /// `pregeneration_schedule` 为允许整书预生成的时段；
+    /// `auth` 为 None 时不启用用户账号；
+    /// `admin` 为管理接口的访问校验及查询对象；
+    /// `ready` 由启动流程在可以处理请求后置为 true
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         session_manager: Arc<dyn SessionManagerPort>,
         task_manager: Arc<dyn TaskManagerPort>,
         novel_repo: Arc<dyn NovelRepositoryPort>,
         voice_repo: Arc<dyn VoiceRepositoryPort>,
+        settings_repo: Arc<dyn SettingsRepositoryPort>,
+        voice_casting_repo: Arc<dyn VoiceCastingRepositoryPort>,
+        tts_engine_status_repo: Arc<dyn TtsEngineStatusRepositoryPort>,
+        listening_stats_repo: Arc<dyn ListeningStatsRepositoryPort>,
+        reading_progress_repo: Arc<dyn ReadingProgressRepositoryPort>,
+        task_event_repo: Arc<dyn TaskEventRepositoryPort>,
         audio_cache: Arc<dyn AudioCachePort>,
-        tts_engine: Arc<dyn TtsEnginePort>,
+        partial_audio: Arc<dyn PartialAudioPort>,
+        audio_storage: Arc<dyn AudioStoragePort>,
+        session_repo: Arc<dyn SessionRepositoryPort>,
+        audio_segment_repo: Arc<dyn AudioSegmentRepositoryPort>,
+        tts_engines: Arc<TtsEngineRegistry>,
+        tts_metrics: Arc<TtsMetrics>,
         event_publisher: Arc<EventPublisher>,
+        audio_transcoder: Arc<dyn AudioTranscoderPort>,
+        reference_normalize: Option<ReferenceNormalizeConfig>,
+        audio_output: TranscodeConfig,
+        export_jobs: Arc<dyn ExportJobManagerPort>,
+        exports_dir: PathBuf,
+        pregeneration_jobs: Arc<dyn PregenerationJobManagerPort>,
+        voice_casting: VoiceCastingResolver,
+        prefetch_settings: PrefetchSettings,
+        session_limits: SessionLimits,
+        pregeneration_schedule: PregenerationSchedule,
+        auth: Option<AuthServices>,
+        admin: AdminServices,
+        ready: Arc<AtomicBool>,
     ) -> Self {
+        let prefetch = Arc::new(PrefetchCoordinator::new(
+            session_manager.clone(),
+            task_manager.clone(),
+            novel_repo.clone(),
+            audio_cache.clone(),
+            voice_casting.clone(),
+            event_publisher.clone(),
+            prefetch_settings,
+        ));
+        let segment_status =
+            SegmentStatusResolver::new(task_manager.clone(), audio_cache.clone(), voice_casting.clone());
+
         Self {
             // Ports
             session_manager: session_manager.clone(),
             task_manager: task_manager.clone(),
             novel_repo: novel_repo.clone(),
             voice_repo: voice_repo.clone(),
+            settings_repo: settings_repo.clone(),
+            voice_casting_repo: voice_casting_repo.clone(),
             audio_cache: audio_cache.clone(),
-            tts_engine: tts_engine.clone(),
+            partial_audio: partial_audio.clone(),
+            tts_engines: tts_engines.clone(),
+            tts_metrics,
             event_publisher: event_publisher.clone(),
+            prefetch: prefetch.clone(),
+            ready,
+            auth_tokens: auth.as_ref().map(|auth| auth.tokens.clone()),
+            voice_audio_signer: auth.as_ref().map(|auth| auth.voice_audio_signer.clone()),
+            access: AccessPolicy::new(
+                novel_repo.clone(),
+                voice_repo.clone(),
+                session_manager.clone(),
+                export_jobs.clone(),
+                pregeneration_jobs.clone(),
+                auth.as_ref().map(|auth| auth.user_repo.clone()),
+            ),
+            admin_guard: AdminGuard {
+                token: admin.token.map(Arc::from),
+                users: auth.as_ref().map(|auth| auth.user_repo.clone()),
+            },
+            worker: admin.worker,
+            gc: admin.gc,
+            config: Arc::new(admin.config),
 
             // Command handlers
             create_novel_handler: CreateNovelFromTextHandler::new(novel_repo.clone()),
             process_novel_handler: ProcessNovelSegmentsHandler::new(novel_repo.clone()),
-            delete_novel_handler: DeleteNovelHandler::new(novel_repo.clone()),
+            delete_novel_handler: DeleteNovelHandler::new(novel_repo.clone(), audio_cache.clone()),
             create_voice_handler: CreateVoiceHandler::new(voice_repo.clone()),
-            delete_voice_handler: DeleteVoiceHandler::new(voice_repo.clone()),
+            update_voice_handler: UpdateVoiceHandler::new(voice_repo.clone(), audio_cache.clone()),
+            normalize_voice_audio_handler: NormalizeVoiceAudioHandler::new(
+                voice_repo.clone(),
+                audio_transcoder.clone(),
+                reference_normalize,
+            ),
+            import_voice_handler: ImportVoiceHandler::new(voice_repo.clone(), audio_cache.clone()),
+            delete_voice_handler: DeleteVoiceHandler::new(
+                voice_repo.clone(),
+                session_manager.clone(),
+                task_manager.clone(),
+                audio_cache.clone(),
+                event_publisher.clone(),
+            ),
             play_handler: PlayHandler::new(
                 session_manager.clone(),
                 task_manager.clone(),
                 novel_repo.clone(),
                 voice_repo.clone(),
+                settings_repo.clone(),
+                reading_progress_repo.clone(),
+                prefetch.clone(),
+                session_limits,
+            ),
+            seek_handler: SeekHandler::new(
+                session_manager.clone(),
+                task_manager.clone(),
+                prefetch.clone(),
+            ),
+            step_segment_handler: StepSegmentHandler::new(
+                session_manager.clone(),
+                novel_repo.clone(),
+                prefetch.clone(),
+                segment_status.clone(),
+            ),
+            report_position_handler: ReportPositionHandler::new(
+                session_manager.clone(),
+                reading_progress_repo.clone(),
+                prefetch.clone(),
+                Arc::new(ListeningTracker::new(listening_stats_repo.clone())),
+                Arc::new(AutoAdvanceNotifier::new(
+                    novel_repo.clone(),
+                    segment_status.clone(),
+                    event_publisher.clone(),
+                )),
             ),
-            seek_handler: SeekHandler::new(session_manager.clone(), task_manager.clone()),
+            prefetch_segments_handler: PrefetchSegmentsHandler::new(prefetch.clone()),
+            heartbeat_handler: HeartbeatHandler::new(
+                session_manager.clone(),
+                novel_repo.clone(),
+                prefetch.clone(),
+                segment_status.clone(),
+            ),
+            update_window_handler: UpdateWindowHandler::new(session_manager.clone(), prefetch.clone()),
             change_voice_handler: ChangeVoiceHandler::new(
                 session_manager.clone(),
                 task_manager.clone(),
                 voice_repo.clone(),
+                prefetch.clone(),
+            ),
+            claim_session_handler: ClaimSessionHandler::new(
+                session_manager.clone(),
+                task_manager.clone(),
+                event_publisher.clone(),
             ),
             close_session_handler: CloseSessionHandler::new(
                 session_manager.clone(),
                 task_manager.clone(),
+                audio_cache.clone(),
                 event_publisher.clone(),
             ),
             submit_infer_handler: SubmitInferHandler::new(
@@ -103,16 +325,128 @@ impl AppState {
                 task_manager.clone(),
                 novel_repo.clone(),
                 audio_cache.clone(),
+                voice_casting.clone(),
+            ),
+            query_task_status_handler: QueryTaskStatusHandler::new(task_manager.clone(), session_manager.clone()),
+            list_tasks_handler: ListTasksHandler::new(task_manager.clone()),
+            list_task_events_handler: ListTaskEventsHandler::new(task_event_repo),
+            retry_task_handler: RetryTaskHandler::new(session_manager.clone(), task_manager.clone()),
+            update_setting_handler: UpdateSettingHandler::new(
+                settings_repo.clone(),
+                voice_repo.clone(),
+            ),
+            update_voice_casting_handler: UpdateVoiceCastingHandler::new(
+                voice_casting_repo.clone(),
+                novel_repo.clone(),
+                voice_repo.clone(),
+            ),
+            export_chapter_audio_handler: ExportChapterAudioHandler::new(
+                novel_repo.clone(),
+                session_manager.clone(),
+                audio_cache.clone(),
+                audio_transcoder.clone(),
+                voice_casting.clone(),
+                audio_output.clone(),
+            ),
+            export_audiobook_handler: ExportAudiobookHandler::new(
+                novel_repo.clone(),
+                voice_repo.clone(),
+                session_manager.clone(),
+                task_manager.clone(),
+                audio_cache.clone(),
+                audio_transcoder.clone(),
+                export_jobs.clone(),
+                event_publisher.clone(),
+                voice_casting.clone(),
+                audio_output.clone(),
+                exports_dir,
+            ),
+            purge_audio_cache_handler: PurgeAudioCacheHandler::new(audio_cache.clone()),
+            reconcile_storage_handler: ReconcileStorageHandler::new(
+                audio_cache.clone(),
+                audio_storage,
+                session_repo,
+                audio_segment_repo,
+                novel_repo.clone(),
+            ),
+            pregenerate_novel_handler: PregenerateNovelHandler::new(
+                novel_repo.clone(),
+                voice_repo.clone(),
+                session_manager.clone(),
+                task_manager.clone(),
+                audio_cache.clone(),
+                pregeneration_jobs.clone(),
+                event_publisher.clone(),
+                voice_casting.clone(),
+                pregeneration_schedule,
             ),
-            query_task_status_handler: QueryTaskStatusHandler::new(task_manager.clone()),
+            control_pregeneration_handler: ControlPregenerationHandler::new(
+                pregeneration_jobs.clone(),
+                event_publisher.clone(),
+            ),
+            register_user_handler: auth.as_ref().map(|auth| {
+                RegisterUserHandler::new(
+                    auth.user_repo.clone(),
+                    novel_repo.clone(),
+                    auth.password_hasher.clone(),
+                    auth.tokens.clone(),
+                    auth.allow_registration,
+                )
+            }),
+            login_handler: auth.as_ref().map(|auth| {
+                LoginHandler::new(auth.user_repo.clone(), auth.password_hasher.clone(), auth.tokens.clone())
+            }),
 
             // Query handlers
             get_novel_handler: GetNovelHandler::new(novel_repo.clone()),
             list_novels_handler: ListNovelsHandler::new(novel_repo.clone()),
             get_novel_segments_handler: GetNovelSegmentsHandler::new(novel_repo.clone()),
+            get_novel_chapters_handler: GetNovelChaptersHandler::new(novel_repo.clone()),
+            preview_segments_handler: PreviewSegmentsHandler::new(),
             get_voice_handler: GetVoiceHandler::new(voice_repo.clone()),
             list_voices_handler: ListVoicesHandler::new(voice_repo.clone()),
-            get_audio_handler: GetAudioHandler::new(audio_cache.clone(), novel_repo.clone()),
+            list_voice_tags_handler: ListVoiceTagsHandler::new(voice_repo.clone()),
+            get_audio_handler: GetAudioHandler::new(
+                audio_cache.clone(),
+                partial_audio,
+                novel_repo.clone(),
+                session_manager.clone(),
+                voice_casting.clone(),
+                audio_transcoder.clone(),
+                AudioTagResolver::new(novel_repo.clone(), voice_repo.clone()),
+                audio_output,
+            ),
+            get_audio_peaks_handler: GetAudioPeaksHandler::new(
+                audio_cache.clone(),
+                novel_repo.clone(),
+                session_manager.clone(),
+                voice_casting.clone(),
+                audio_transcoder,
+            ),
+            get_settings_handler: GetSettingsHandler::new(settings_repo.clone()),
+            get_voice_casting_handler: GetVoiceCastingHandler::new(
+                voice_casting_repo.clone(),
+                novel_repo.clone(),
+            ),
+            get_tts_engine_status_handler: GetTtsEngineStatusHandler::new(tts_engine_status_repo),
+            get_listening_stats_handler: GetListeningStatsHandler::new(
+                listening_stats_repo,
+                novel_repo.clone(),
+            ),
+            get_cache_stats_handler: GetCacheStatsHandler::new(audio_cache.clone(), novel_repo.clone()),
+            get_export_job_handler: GetExportJobHandler::new(export_jobs),
+            get_pregeneration_job_handler: GetPregenerationJobHandler::new(pregeneration_jobs),
+            list_sessions_handler: ListSessionsHandler::new(session_manager.clone(), task_manager.clone()),
+            list_active_sessions_handler: ListActiveSessionsHandler::new(
+                session_manager.clone(),
+                task_manager.clone(),
+            ),
+            get_session_segments_handler: GetSessionSegmentsHandler::new(
+                session_manager.clone(),
+                novel_repo.clone(),
+                segment_status,
+            ),
+            get_session_snapshot_handler: GetSessionSnapshotHandler::new(session_manager.clone()),
         }
     }
 }
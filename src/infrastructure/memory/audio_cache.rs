@@ -0,0 +1,320 @@
+//! Two-Tier Audio Cache - 内存 LRU + 持久化缓存
+//!
+//! 播放时片段会被反复读取（预取、拖动、重播），持久化缓存每次 get 都要读盘并回写访问时间；
+//! 在其前面加一层按字节限额的内存 LRU，当前播放位置附近的热点片段直接从内存返回：
+//! - 读取：先查内存，未命中再读下层并放入内存
+//! - 写入：写穿到下层，同时放入内存
+//! - 删除：两层同时删除
+//!
+//! 内存命中不回写下层的访问时间，条目从内存淘汰后再次读取时才更新
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::Utc;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::application::ports::{
+    cache_key_version, AudioCachePort, CacheEntry, CacheError, CacheMetadata, CacheStats, NovelCacheUsage,
+    SegmentReference, CACHE_KEY_VERSION,
+};
+
+/// 内存 LRU
+#[derive(Default)]
+struct MemoryLru {
+    /// cache_key -> (条目, 访问序号)
+    entries: HashMap<String, (CacheEntry, u64)>,
+    /// 访问序号 -> cache_key（最小的为最久未访问）
+    order: BTreeMap<u64, String>,
+    size_bytes: u64,
+    tick: u64,
+}
+
+impl MemoryLru {
+    fn get(&mut self, cache_key: &str) -> Option<CacheEntry> {
+        self.tick += 1;
+        let tick = self.tick;
+        let (entry, last) = self.entries.get_mut(cache_key)?;
+        self.order.remove(last);
+        self.order.insert(tick, cache_key.to_string());
+        *last = tick;
+        entry.last_accessed = Utc::now().timestamp();
+        Some(entry.clone())
+    }
+
+    fn insert(&mut self, cache_key: &str, entry: CacheEntry, max_size_bytes: u64) {
+        let size = entry.audio_data.len() as u64;
+        self.remove(cache_key);
+        if size > max_size_bytes {
+            return;
+        }
+
+        while self.size_bytes + size > max_size_bytes {
+            let Some((_, oldest)) = self.order.pop_first() else {
+                break;
+            };
+            if let Some((evicted, _)) = self.entries.remove(&oldest) {
+                self.size_bytes -= evicted.audio_data.len() as u64;
+            }
+        }
+
+        self.tick += 1;
+        self.order.insert(self.tick, cache_key.to_string());
+        self.entries.insert(cache_key.to_string(), (entry, self.tick));
+        self.size_bytes += size;
+    }
+
+    fn remove(&mut self, cache_key: &str) {
+        if let Some((entry, tick)) = self.entries.remove(cache_key) {
+            self.order.remove(&tick);
+            self.size_bytes -= entry.audio_data.len() as u64;
+        }
+    }
+
+    fn retain(&mut self, keep: impl Fn(&str, &CacheEntry) -> bool) {
+        let removed: Vec<String> = self
+            .entries
+            .iter()
+            .filter(|(key, (entry, _))| !keep(key, entry))
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in removed {
+            self.remove(&key);
+        }
+    }
+}
+
+/// 两级音频缓存
+pub struct TieredAudioCache {
+    inner: Arc<dyn AudioCachePort>,
+    memory: Mutex<MemoryLru>,
+    max_memory_bytes: u64,
+    memory_hits: AtomicU64,
+}
+
+impl TieredAudioCache {
+    /// `max_memory_bytes` 为内存层容量（按音频字节计）
+    pub fn new(inner: Arc<dyn AudioCachePort>, max_memory_bytes: u64) -> Self {
+        tracing::info!(max_memory_bytes, "TieredAudioCache initialized");
+        Self {
+            inner,
+            memory: Mutex::new(MemoryLru::default()),
+            max_memory_bytes,
+            memory_hits: AtomicU64::new(0),
+        }
+    }
+
+    fn memory(&self) -> std::sync::MutexGuard<'_, MemoryLru> {
+        self.memory.lock().unwrap()
+    }
+
+    /// 删除内存层中下层已不存在的条目（内存层条目较少，逐个确认）
+    async fn prune_memory(&self) -> Result<(), CacheError> {
+        let keys: Vec<String> = self.memory().entries.keys().cloned().collect();
+        for key in keys {
+            if !self.inner.exists(&key).await? {
+                self.memory().remove(&key);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AudioCachePort for TieredAudioCache {
+    async fn put(
+        &self,
+        cache_key: &str,
+        audio_data: Vec<u8>,
+        metadata: CacheMetadata,
+    ) -> Result<(), CacheError> {
+        self.inner.put(cache_key, audio_data.clone(), metadata.clone()).await?;
+
+        let now = Utc::now().timestamp();
+        let entry = CacheEntry {
+            size_bytes: audio_data.len() as u64,
+            audio_data: Bytes::from(audio_data),
+            metadata,
+            last_accessed: now,
+            created_at: now,
+        };
+        self.memory().insert(cache_key, entry, self.max_memory_bytes);
+        Ok(())
+    }
+
+    async fn get(&self, cache_key: &str) -> Result<Option<Bytes>, CacheError> {
+        Ok(self.get_entry(cache_key).await?.map(|entry| entry.audio_data))
+    }
+
+    async fn get_entry(&self, cache_key: &str) -> Result<Option<CacheEntry>, CacheError> {
+        if let Some(entry) = self.memory().get(cache_key) {
+            self.memory_hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(Some(entry));
+        }
+
+        let entry = self.inner.get_entry(cache_key).await?;
+        if let Some(entry) = &entry {
+            self.memory().insert(cache_key, entry.clone(), self.max_memory_bytes);
+        }
+        Ok(entry)
+    }
+
+    async fn lookup(
+        &self,
+        novel_id: Uuid,
+        segment_index: u32,
+        voice_id: Uuid,
+    ) -> Result<Option<String>, CacheError> {
+        self.inner.lookup(novel_id, segment_index, voice_id).await
+    }
+
+    async fn exists(&self, cache_key: &str) -> Result<bool, CacheError> {
+        if self.memory().entries.contains_key(cache_key) {
+            return Ok(true);
+        }
+        self.inner.exists(cache_key).await
+    }
+
+    async fn link(
+        &self,
+        cache_key: &str,
+        novel_id: Uuid,
+        segment_index: u32,
+        voice_id: Uuid,
+    ) -> Result<bool, CacheError> {
+        self.inner.link(cache_key, novel_id, segment_index, voice_id).await
+    }
+
+    async fn put_peaks(&self, cache_key: &str, peaks: &[f32]) -> Result<(), CacheError> {
+        self.inner.put_peaks(cache_key, peaks).await
+    }
+
+    async fn get_peaks(&self, cache_key: &str) -> Result<Option<Vec<f32>>, CacheError> {
+        self.inner.get_peaks(cache_key).await
+    }
+
+    async fn remove(&self, cache_key: &str) -> Result<(), CacheError> {
+        self.memory().remove(cache_key);
+        self.inner.remove(cache_key).await
+    }
+
+    async fn remove_expired(&self) -> Result<usize, CacheError> {
+        let removed = self.inner.remove_expired().await?;
+        if removed > 0 {
+            self.prune_memory().await?;
+        }
+        Ok(removed)
+    }
+
+    async fn remove_by_novel(&self, novel_id: Uuid) -> Result<usize, CacheError> {
+        // 条目可能被多部小说共享，元数据中的小说不一定是删除的这部，以下层结果为准
+        let removed = self.inner.remove_by_novel(novel_id).await?;
+        if removed > 0 {
+            self.prune_memory().await?;
+        }
+        Ok(removed)
+    }
+
+    async fn remove_by_voice(&self, voice_id: Uuid) -> Result<usize, CacheError> {
+        self.memory().retain(|_, entry| entry.metadata.voice_id != voice_id);
+        self.inner.remove_by_voice(voice_id).await
+    }
+
+    fn pin(&self, session_id: &str, cache_keys: Vec<String>) {
+        self.inner.pin(session_id, cache_keys);
+    }
+
+    fn unpin(&self, session_id: &str) {
+        self.inner.unpin(session_id);
+    }
+
+    async fn remove_orphans(&self) -> Result<usize, CacheError> {
+        // 音频文件丢失的条目会被下层删除
+        let removed = self.inner.remove_orphans().await?;
+        if removed > 0 {
+            self.prune_memory().await?;
+        }
+        Ok(removed)
+    }
+
+    async fn flush(&self) -> Result<(), CacheError> {
+        self.inner.flush().await
+    }
+
+    async fn remove_outdated(&self) -> Result<usize, CacheError> {
+        self.memory().retain(|key, _| cache_key_version(key) >= CACHE_KEY_VERSION);
+        self.inner.remove_outdated().await
+    }
+
+    async fn stats(&self) -> CacheStats {
+        let mut stats = self.inner.stats().await;
+        stats.hit_count += self.memory_hits.load(Ordering::Relaxed);
+        stats
+    }
+
+    async fn usage_by_novel(&self) -> Result<Vec<NovelCacheUsage>, CacheError> {
+        self.inner.usage_by_novel().await
+    }
+
+    async fn keys(&self) -> Result<Vec<String>, CacheError> {
+        self.inner.keys().await
+    }
+
+    async fn peek(&self, cache_key: &str) -> Result<Option<CacheEntry>, CacheError> {
+        self.inner.peek(cache_key).await
+    }
+
+    async fn references(&self, cache_key: &str) -> Result<Vec<SegmentReference>, CacheError> {
+        self.inner.references(cache_key).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::AudioFormat;
+    use crate::infrastructure::persistence::sled::SledAudioCache;
+    use tempfile::tempdir;
+
+    fn metadata(novel_id: Uuid, segment_index: u32) -> CacheMetadata {
+        CacheMetadata {
+            novel_id,
+            segment_index,
+            voice_id: Uuid::new_v4(),
+            content_hash: "test_hash".to_string(),
+            duration_ms: 1000,
+            sample_rate: Some(22050),
+            format: AudioFormat::Wav,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_memory_tier() {
+        let dir = tempdir().unwrap();
+        let sled = Arc::new(SledAudioCache::open(dir.path().join("test.sled"), 1024 * 1024).unwrap());
+        let cache = TieredAudioCache::new(sled.clone(), 8);
+        let novel_id = Uuid::new_v4();
+
+        cache.put("a", vec![1; 4], metadata(novel_id, 0)).await.unwrap();
+        cache.put("b", vec![2; 4], metadata(novel_id, 1)).await.unwrap();
+
+        // 内存命中不读下层
+        assert_eq!(cache.get("a").await.unwrap(), Some(Bytes::from(vec![1; 4])));
+        assert_eq!(sled.stats().await.hit_count, 0);
+
+        // 超出容量时淘汰最久未访问的 b，下层仍保留
+        cache.put("c", vec![3; 4], metadata(novel_id, 2)).await.unwrap();
+        assert!(!cache.memory().entries.contains_key("b"));
+        assert_eq!(cache.get("b").await.unwrap(), Some(Bytes::from(vec![2; 4])));
+        assert_eq!(sled.stats().await.hit_count, 1);
+        assert_eq!(cache.stats().await.hit_count, 2);
+
+        // 两层同时删除
+        cache.remove_by_novel(novel_id).await.unwrap();
+        assert!(cache.memory().entries.is_empty());
+        assert_eq!(cache.memory().size_bytes, 0);
+        assert_eq!(cache.get("a").await.unwrap(), None);
+    }
+}
@@ -0,0 +1,304 @@
+//! Audited Task Manager - 记录任务生命周期的任务管理器
+//!
+//! 包装另一个任务管理器，把提交、开始推理、重试、失败（附错误）和完成（附推理耗时）
+//! 追加到 TaskEventRepository，可按会话、小说或片段查询，排查某些片段反复失败的原因。
+//! 写入由后台任务批量完成，不阻塞提交任务和 Worker；日志只保留最近一段时间
+
+use chrono::Utc;
+use dashmap::DashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, oneshot};
+
+use crate::application::ports::{
+    InferenceTask, RepositoryError, TaskError, TaskEventKind, TaskEventRecord,
+    TaskEventRepositoryPort, TaskManagerPort, TaskPriority, TaskState,
+};
+
+/// 审计日志的保留时长
+const TASK_EVENT_RETENTION: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// 待写入的审计日志
+enum EventWrite {
+    Append(TaskEventRecord),
+    /// 之前的写入全部完成后通知
+    Flush(oneshot::Sender<()>),
+}
+
+/// 审计任务管理器
+pub struct AuditedTaskManager {
+    tasks: Arc<dyn TaskManagerPort>,
+    task_event_repo: Arc<dyn TaskEventRepositoryPort>,
+    /// 推理中的任务 -> 开始时间（用于计算耗时）
+    started: DashMap<String, Instant>,
+    writes: mpsc::UnboundedSender<EventWrite>,
+}
+
+impl AuditedTaskManager {
+    /// 创建管理器并启动写入任务（需在 Tokio 运行时中调用）
+    pub fn new(tasks: Arc<dyn TaskManagerPort>, task_event_repo: Arc<dyn TaskEventRepositoryPort>) -> Self {
+        let (writes, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(run_writer(task_event_repo.clone(), receiver));
+        Self {
+            tasks,
+            task_event_repo,
+            started: DashMap::new(),
+            writes,
+        }
+    }
+
+    /// 删除超过保留时长的日志，返回删除数
+    pub async fn prune(&self) -> Result<u64, RepositoryError> {
+        let before = Utc::now()
+            - chrono::Duration::from_std(TASK_EVENT_RETENTION).expect("retention fits chrono::Duration");
+        self.task_event_repo.delete_before(before).await
+    }
+
+    /// 等待已记录的日志全部写入（关闭服务前调用）
+    pub async fn flush(&self) {
+        let (done, wait) = oneshot::channel();
+        if self.writes.send(EventWrite::Flush(done)).is_ok() {
+            let _ = wait.await;
+        }
+    }
+
+    fn record(&self, task: &InferenceTask, event: TaskEventKind, error: Option<String>, duration: Option<Duration>) {
+        let record = TaskEventRecord {
+            task_id: task.task_id.clone(),
+            session_id: task.session_id.clone(),
+            novel_id: task.novel_id,
+            voice_id: task.voice_id,
+            segment_index: task.segment_index,
+            event,
+            error,
+            duration_ms: duration.map(|d| d.as_millis() as u64),
+            request_id: task.request_id.clone(),
+            created_at: Utc::now(),
+        };
+        if self.writes.send(EventWrite::Append(record)).is_err() {
+            tracing::warn!("Task event writer stopped, event not recorded");
+        }
+    }
+
+    fn record_by_id(&self, task_id: &str, event: TaskEventKind, error: Option<String>, duration: Option<Duration>) {
+        if let Some(task) = self.tasks.get_task(task_id) {
+            self.record(&task, event, error, duration);
+        }
+    }
+
+    /// 任务结束后取出推理耗时
+    fn elapsed(&self, task_id: &str) -> Option<Duration> {
+        self.started.remove(task_id).map(|(_, started)| started.elapsed())
+    }
+}
+
+impl TaskManagerPort for AuditedTaskManager {
+    fn submit(&self, tasks: Vec<InferenceTask>) -> Result<Vec<String>, TaskError> {
+        let submitted = tasks.clone();
+        let task_ids = self.tasks.submit(tasks)?;
+        for task in &submitted {
+            self.record(task, TaskEventKind::Submitted, None, None);
+        }
+        Ok(task_ids)
+    }
+
+    fn cancel_pending(&self, session_id: &str) -> usize {
+        self.tasks.cancel_pending(session_id)
+    }
+
+    fn cancel_all(&self, session_id: &str) -> usize {
+        self.tasks.cancel_all(session_id)
+    }
+
+    fn cancel_outside(&self, session_id: &str, start: u32, end: u32) -> usize {
+        self.tasks.cancel_outside(session_id, start, end)
+    }
+
+    fn cancel_window(&self, session_id: &str) -> usize {
+        self.tasks.cancel_window(session_id)
+    }
+
+    fn prioritize(&self, task_id: &str, priority: TaskPriority) -> bool {
+        self.tasks.prioritize(task_id, priority)
+    }
+
+    fn is_cancelled(&self, task_id: &str) -> bool {
+        self.tasks.is_cancelled(task_id)
+    }
+
+    fn get_state(&self, task_id: &str) -> Option<TaskState> {
+        self.tasks.get_state(task_id)
+    }
+
+    fn set_state(&self, task_id: &str, state: TaskState) -> Result<(), TaskError> {
+        self.tasks.set_state(task_id, state)?;
+        match state {
+            TaskState::Inferring => {
+                self.started.insert(task_id.to_string(), Instant::now());
+                self.record_by_id(task_id, TaskEventKind::Started, None, None);
+            }
+            TaskState::Ready => {
+                let elapsed = self.elapsed(task_id);
+                self.record_by_id(task_id, TaskEventKind::Completed, None, elapsed);
+            }
+            TaskState::Failed => {
+                let elapsed = self.elapsed(task_id);
+                self.record_by_id(task_id, TaskEventKind::Failed, None, elapsed);
+            }
+            TaskState::Cancelled => {
+                self.started.remove(task_id);
+            }
+            TaskState::Pending => {}
+        }
+        Ok(())
+    }
+
+    fn set_failed(&self, task_id: &str, error: String) -> Result<(), TaskError> {
+        self.tasks.set_failed(task_id, error.clone())?;
+        let elapsed = self.elapsed(task_id);
+        self.record_by_id(task_id, TaskEventKind::Failed, Some(error), elapsed);
+        Ok(())
+    }
+
+    fn get_task(&self, task_id: &str) -> Option<InferenceTask> {
+        self.tasks.get_task(task_id)
+    }
+
+    fn get_tasks_by_session(&self, session_id: &str) -> Vec<InferenceTask> {
+        self.tasks.get_tasks_by_session(session_id)
+    }
+
+    fn list_tasks(&self, state: Option<TaskState>) -> Vec<InferenceTask> {
+        self.tasks.list_tasks(state)
+    }
+
+    fn retry(&self, task_id: &str, priority: TaskPriority) -> Result<(), TaskError> {
+        self.tasks.retry(task_id, priority)?;
+        self.record_by_id(task_id, TaskEventKind::Retried, None, None);
+        Ok(())
+    }
+
+    fn cleanup_session(&self, session_id: &str) {
+        for task in self.tasks.get_tasks_by_session(session_id) {
+            self.started.remove(&task.task_id);
+        }
+        self.tasks.cleanup_session(session_id);
+    }
+
+    fn queue_depth(&self) -> usize {
+        self.tasks.queue_depth()
+    }
+}
+
+/// 按记录顺序批量追加
+async fn run_writer(
+    task_event_repo: Arc<dyn TaskEventRepositoryPort>,
+    mut receiver: mpsc::UnboundedReceiver<EventWrite>,
+) {
+    while let Some(write) = receiver.recv().await {
+        let mut batch = vec![write];
+        while let Ok(write) = receiver.try_recv() {
+            batch.push(write);
+        }
+
+        let mut events = Vec::new();
+        let mut flushes = Vec::new();
+        for write in batch {
+            match write {
+                EventWrite::Append(record) => events.push(record),
+                EventWrite::Flush(done) => flushes.push(done),
+            }
+        }
+        if !events.is_empty() {
+            if let Err(e) = task_event_repo.append(&events).await {
+                tracing::warn!(count = events.len(), error = %e, "Failed to record task events");
+            }
+        }
+        for done in flushes {
+            let _ = done.send(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::TaskEventFilter;
+    use crate::infrastructure::memory::{InMemoryTaskManager, TaskQueue};
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+    use uuid::Uuid;
+
+    #[derive(Default)]
+    struct MemoryTaskEventRepo {
+        events: Mutex<Vec<TaskEventRecord>>,
+    }
+
+    #[async_trait]
+    impl TaskEventRepositoryPort for MemoryTaskEventRepo {
+        async fn append(&self, events: &[TaskEventRecord]) -> Result<(), RepositoryError> {
+            self.events.lock().unwrap().extend_from_slice(events);
+            Ok(())
+        }
+
+        async fn find(&self, filter: &TaskEventFilter) -> Result<Vec<TaskEventRecord>, RepositoryError> {
+            Ok(self
+                .events
+                .lock()
+                .unwrap()
+                .iter()
+                .rev()
+                .filter(|event| filter.task_id.as_ref().is_none_or(|id| &event.task_id == id))
+                .take(filter.limit)
+                .cloned()
+                .collect())
+        }
+
+        async fn delete_before(&self, _: chrono::DateTime<Utc>) -> Result<u64, RepositoryError> {
+            Ok(0)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lifecycle_events() {
+        let repo = Arc::new(MemoryTaskEventRepo::default());
+        let inner = Arc::new(InMemoryTaskManager::new(Arc::new(TaskQueue::new(10))));
+        let manager = AuditedTaskManager::new(inner, repo.clone());
+
+        let task = crate::application::with_request_id(Some("req-1".to_string()), async {
+            InferenceTask::new("s1".to_string(), Uuid::new_v4(), Uuid::new_v4(), 3, "Content".to_string())
+        })
+        .await;
+        let task_id = manager.submit(vec![task]).unwrap().remove(0);
+        manager.set_state(&task_id, TaskState::Inferring).unwrap();
+        manager.set_failed(&task_id, "TTS error: timeout".to_string()).unwrap();
+        manager.retry(&task_id, TaskPriority::Current).unwrap();
+        manager.set_state(&task_id, TaskState::Inferring).unwrap();
+        manager.set_state(&task_id, TaskState::Ready).unwrap();
+        manager.flush().await;
+
+        let filter = TaskEventFilter {
+            task_id: Some(task_id),
+            limit: 10,
+            ..Default::default()
+        };
+        let events = repo.find(&filter).await.unwrap();
+        let kinds: Vec<TaskEventKind> = events.iter().rev().map(|event| event.event).collect();
+        assert_eq!(
+            kinds,
+            [
+                TaskEventKind::Submitted,
+                TaskEventKind::Started,
+                TaskEventKind::Failed,
+                TaskEventKind::Retried,
+                TaskEventKind::Started,
+                TaskEventKind::Completed,
+            ]
+        );
+        assert!(events.iter().all(|event| event.segment_index == 3));
+        assert!(events.iter().all(|event| event.request_id.as_deref() == Some("req-1")));
+        assert_eq!(events[3].error.as_deref(), Some("TTS error: timeout"));
+        assert!(events[3].duration_ms.is_some());
+        assert!(events[0].duration_ms.is_some());
+    }
+}
@@ -0,0 +1,50 @@
+//! Cache Pins - 会话窗口内的缓存条目固定
+//!
+//! 缓存已满时 LRU 可能恰好淘汰即将播放的片段；提交推理时按会话记录窗口内的 cache key，
+//! 音频缓存淘汰时跳过这些条目，会话关闭时解除
+
+use std::collections::{HashMap, HashSet};
+use std::sync::RwLock;
+
+/// 按会话记录的固定 cache key
+#[derive(Default)]
+pub struct CachePins {
+    /// session_id -> 窗口内的 cache key
+    sessions: RwLock<HashMap<String, HashSet<String>>>,
+}
+
+impl CachePins {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 替换会话固定的 cache key
+    pub fn set(&self, session_id: &str, cache_keys: Vec<String>) {
+        let mut sessions = self.sessions.write().unwrap();
+        if cache_keys.is_empty() {
+            sessions.remove(session_id);
+        } else {
+            sessions.insert(session_id.to_string(), cache_keys.into_iter().collect());
+        }
+    }
+
+    /// 解除会话固定的 cache key
+    pub fn clear(&self, session_id: &str) {
+        self.sessions.write().unwrap().remove(session_id);
+    }
+
+    /// 是否被任一会话固定
+    pub fn contains(&self, cache_key: &str) -> bool {
+        self.sessions
+            .read()
+            .unwrap()
+            .values()
+            .any(|keys| keys.contains(cache_key))
+    }
+
+    /// 被固定的 cache key 数（多个会话固定同一条目时分别计入）
+    pub fn pinned_count(&self) -> usize {
+        self.sessions.read().unwrap().values().map(HashSet::len).sum()
+    }
+}
+
@@ -0,0 +1,60 @@
+//! In-Memory Export Job Manager Implementation
+
+use chrono::Utc;
+use dashmap::DashMap;
+use std::path::PathBuf;
+
+use crate::application::ports::{AudioFormat, ExportJob, ExportJobManagerPort, ExportJobState};
+
+/// 内存导出任务管理器
+#[derive(Default)]
+pub struct InMemoryExportJobManager {
+    /// job_id -> ExportJob
+    jobs: DashMap<String, ExportJob>,
+}
+
+impl InMemoryExportJobManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ExportJobManagerPort for InMemoryExportJobManager {
+    fn create(&self, job: ExportJob) {
+        self.jobs.insert(job.job_id.clone(), job);
+    }
+
+    fn get(&self, job_id: &str) -> Option<ExportJob> {
+        self.jobs.get(job_id).map(|j| j.clone())
+    }
+
+    fn update_progress(&self, job_id: &str, completed_segments: usize) {
+        if let Some(mut job) = self.jobs.get_mut(job_id) {
+            job.completed_segments = completed_segments;
+        }
+    }
+
+    fn set_state(&self, job_id: &str, state: ExportJobState) {
+        if let Some(mut job) = self.jobs.get_mut(job_id) {
+            job.state = state;
+        }
+    }
+
+    fn set_ready(&self, job_id: &str, file_path: PathBuf, format: AudioFormat) {
+        if let Some(mut job) = self.jobs.get_mut(job_id) {
+            job.state = ExportJobState::Ready;
+            job.format = format;
+            job.completed_segments = job.total_segments;
+            job.file_path = Some(file_path);
+            job.completed_at = Some(Utc::now());
+        }
+    }
+
+    fn set_failed(&self, job_id: &str, error: String) {
+        if let Some(mut job) = self.jobs.get_mut(job_id) {
+            job.state = ExportJobState::Failed;
+            job.error_message = Some(error);
+            job.completed_at = Some(Utc::now());
+        }
+    }
+}
@@ -1,9 +1,35 @@
 //! Memory Layer - In-Memory State Management
 //!
 //! 实现 SessionManager 和 TaskManager，管理播放会话和推理任务的内存状态
+//! （PersistentSessionManager 和 PersistentTaskManager 将会话和未完成的任务写穿到数据库，重启后恢复；
+//! AuditedTaskManager 记录任务生命周期的审计日志）；
+//! PartialAudio 缓冲流式推理中已合成的音频；ExportJobManager 记录有声书导出任务；
+//! PregenerationJobManager 记录整书预生成任务；TieredAudioCache 在持久化音频缓存前加一层内存 LRU；
+//! CachePins 记录会话窗口内不参与淘汰的缓存条目；NovelQuotas 限制单部小说的缓存占用；
+//! TaskQueue 按优先级向 Worker 分发推理任务
 
+mod audio_cache;
+mod audited_task_manager;
+mod cache_pins;
+mod export_jobs;
+mod novel_quotas;
+mod partial_audio;
+mod persistent_session_manager;
+mod persistent_task_manager;
+mod pregeneration_jobs;
 mod session_manager;
 mod task_manager;
+mod task_queue;
 
+pub use audio_cache::TieredAudioCache;
+pub use audited_task_manager::AuditedTaskManager;
+pub use cache_pins::CachePins;
+pub use export_jobs::InMemoryExportJobManager;
+pub use novel_quotas::{NovelQuotaReached, NovelQuotas};
+pub use partial_audio::InMemoryPartialAudio;
+pub use persistent_session_manager::PersistentSessionManager;
+pub use persistent_task_manager::PersistentTaskManager;
+pub use pregeneration_jobs::InMemoryPregenerationJobManager;
 pub use session_manager::InMemorySessionManager;
 pub use task_manager::InMemoryTaskManager;
+pub use task_queue::{PushRejection, TaskQueue};
@@ -0,0 +1,102 @@
+//! Novel Quotas - 单部小说的音频缓存配额
+//!
+//! 共享实例上一部超长小说可能把其他小说的缓存全部挤出；配置配额后，小说写入缓存超出配额时
+//! 先淘汰该小说自己最久未访问的条目，而不是参与全局 LRU。条目计入首次写入它的小说
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+/// 小说达到缓存配额
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NovelQuotaReached {
+    pub novel_id: Uuid,
+    pub used_bytes: u64,
+    pub quota_bytes: u64,
+}
+
+/// 单部小说的缓存配额（0 表示不限制）
+#[derive(Debug)]
+pub struct NovelQuotas {
+    /// 未单独指定的小说使用的配额
+    default_bytes: u64,
+    /// 单独指定的配额
+    overrides: HashMap<Uuid, u64>,
+    /// 已通知达到配额的小说（每部只通知一次，缓存被清除后重新计）
+    notified: Mutex<HashSet<Uuid>>,
+    sender: broadcast::Sender<NovelQuotaReached>,
+}
+
+impl Default for NovelQuotas {
+    fn default() -> Self {
+        Self::new(0, HashMap::new())
+    }
+}
+
+impl NovelQuotas {
+    pub fn new(default_bytes: u64, overrides: HashMap<Uuid, u64>) -> Self {
+        let (sender, _) = broadcast::channel(16);
+        Self {
+            default_bytes,
+            overrides,
+            notified: Mutex::new(HashSet::new()),
+            sender,
+        }
+    }
+
+    /// 小说的配额，不限制时为 None
+    pub fn limit(&self, novel_id: Uuid) -> Option<u64> {
+        let bytes = self.overrides.get(&novel_id).copied().unwrap_or(self.default_bytes);
+        (bytes > 0).then_some(bytes)
+    }
+
+    /// 订阅达到配额的通知
+    pub fn subscribe(&self) -> broadcast::Receiver<NovelQuotaReached> {
+        self.sender.subscribe()
+    }
+
+    /// 记录小说达到配额，首次达到时发送通知
+    pub fn reached(&self, novel_id: Uuid, used_bytes: u64, quota_bytes: u64) {
+        if !self.notified.lock().unwrap().insert(novel_id) {
+            return;
+        }
+        tracing::info!(%novel_id, used_bytes, quota_bytes, "Novel reached audio cache quota");
+        let _ = self.sender.send(NovelQuotaReached {
+            novel_id,
+            used_bytes,
+            quota_bytes,
+        });
+    }
+
+    /// 小说的缓存被清除后，再次达到配额时重新通知
+    pub fn reset(&self, novel_id: Uuid) {
+        self.notified.lock().unwrap().remove(&novel_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limit_and_notify_once() {
+        let special = Uuid::new_v4();
+        let quotas = NovelQuotas::new(100, HashMap::from([(special, 0)]));
+        let mut events = quotas.subscribe();
+        let novel_id = Uuid::new_v4();
+
+        assert_eq!(quotas.limit(novel_id), Some(100));
+        assert_eq!(quotas.limit(special), None);
+
+        quotas.reached(novel_id, 120, 100);
+        quotas.reached(novel_id, 130, 100);
+        assert_eq!(events.try_recv().unwrap().used_bytes, 120);
+        assert!(events.try_recv().is_err());
+
+        quotas.reset(novel_id);
+        quotas.reached(novel_id, 110, 100);
+        assert_eq!(events.try_recv().unwrap().used_bytes, 110);
+    }
+}
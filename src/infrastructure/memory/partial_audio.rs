@@ -0,0 +1,177 @@
+//! In-Memory Partial Audio Buffer Implementation
+
+use dashmap::DashMap;
+use futures_util::stream::{self, StreamExt};
+use std::sync::{Arc, Mutex};
+use tokio::sync::watch;
+
+use crate::application::ports::{CacheError, PartialAudioPort, PartialAudioStream};
+
+/// 缓冲状态
+#[derive(Debug, Clone, PartialEq)]
+enum BufferStatus {
+    Streaming,
+    Finished,
+    Failed(String),
+}
+
+#[derive(Debug)]
+struct BufferState {
+    data: Vec<u8>,
+    status: BufferStatus,
+}
+
+/// 单个合成中音频的缓冲
+///
+/// 订阅方持有 Arc，缓冲从表中移除后仍可读完剩余数据
+struct PartialBuffer {
+    state: Mutex<BufferState>,
+    /// 每次写入/结束后通知订阅方
+    notify: watch::Sender<()>,
+}
+
+impl PartialBuffer {
+    fn new() -> Self {
+        Self {
+            state: Mutex::new(BufferState {
+                data: Vec::new(),
+                status: BufferStatus::Streaming,
+            }),
+            notify: watch::Sender::new(()),
+        }
+    }
+
+    fn update(&self, f: impl FnOnce(&mut BufferState)) {
+        f(&mut self.state.lock().unwrap());
+        self.notify.send_replace(());
+    }
+}
+
+/// 内存合成中音频缓冲
+pub struct InMemoryPartialAudio {
+    buffers: DashMap<String, Arc<PartialBuffer>>,
+}
+
+impl InMemoryPartialAudio {
+    pub fn new() -> Self {
+        Self {
+            buffers: DashMap::new(),
+        }
+    }
+
+    pub fn arc(self) -> Arc<Self> {
+        Arc::new(self)
+    }
+}
+
+impl Default for InMemoryPartialAudio {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialAudioPort for InMemoryPartialAudio {
+    fn begin(&self, cache_key: &str) {
+        let previous = self
+            .buffers
+            .insert(cache_key.to_string(), Arc::new(PartialBuffer::new()));
+        if let Some(previous) = previous {
+            previous.update(|s| s.status = BufferStatus::Failed("Synthesis restarted".to_string()));
+        }
+    }
+
+    fn append(&self, cache_key: &str, chunk: &[u8]) {
+        if let Some(buffer) = self.buffers.get(cache_key) {
+            buffer.update(|s| s.data.extend_from_slice(chunk));
+        }
+    }
+
+    fn finish(&self, cache_key: &str) {
+        if let Some((_, buffer)) = self.buffers.remove(cache_key) {
+            buffer.update(|s| s.status = BufferStatus::Finished);
+        }
+    }
+
+    fn abort(&self, cache_key: &str, error: &str) {
+        if let Some((_, buffer)) = self.buffers.remove(cache_key) {
+            buffer.update(|s| s.status = BufferStatus::Failed(error.to_string()));
+        }
+    }
+
+    fn subscribe(&self, cache_key: &str) -> Option<PartialAudioStream> {
+        let buffer = self.buffers.get(cache_key)?.clone();
+        let receiver = buffer.notify.subscribe();
+
+        let stream = stream::unfold(
+            (buffer, receiver, 0usize, false),
+            |(buffer, mut receiver, offset, done)| async move {
+                if done {
+                    return None;
+                }
+                loop {
+                    // 先标记已读再检查状态，避免错过检查后到达的通知
+                    receiver.borrow_and_update();
+                    let (chunk, status) = {
+                        let state = buffer.state.lock().unwrap();
+                        (state.data[offset..].to_vec(), state.status.clone())
+                    };
+
+                    if !chunk.is_empty() {
+                        let next_offset = offset + chunk.len();
+                        return Some((Ok(chunk), (buffer, receiver, next_offset, false)));
+                    }
+                    match status {
+                        BufferStatus::Finished => return None,
+                        BufferStatus::Failed(error) => {
+                            return Some((
+                                Err(CacheError::NotFound(format!("Synthesis failed: {}", error))),
+                                (buffer, receiver, offset, true),
+                            ));
+                        }
+                        BufferStatus::Streaming => {
+                            if receiver.changed().await.is_err() {
+                                return None;
+                            }
+                        }
+                    }
+                }
+            },
+        );
+
+        Some(stream.boxed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_subscriber_receives_buffered_and_new_chunks() {
+        let partial = InMemoryPartialAudio::new();
+        assert!(partial.subscribe("key").is_none());
+
+        partial.begin("key");
+        partial.append("key", &[1, 2]);
+        let mut stream = partial.subscribe("key").unwrap();
+        assert_eq!(stream.next().await.unwrap().unwrap(), vec![1, 2]);
+
+        partial.append("key", &[3]);
+        assert_eq!(stream.next().await.unwrap().unwrap(), vec![3]);
+
+        partial.finish("key");
+        assert!(stream.next().await.is_none());
+        assert!(partial.subscribe("key").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_abort_ends_stream_with_error() {
+        let partial = InMemoryPartialAudio::new();
+        partial.begin("key");
+        let mut stream = partial.subscribe("key").unwrap();
+
+        partial.abort("key", "TTS error");
+        assert!(stream.next().await.unwrap().is_err());
+        assert!(stream.next().await.is_none());
+    }
+}
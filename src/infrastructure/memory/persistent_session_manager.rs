@@ -0,0 +1,372 @@
+//! Persistent Session Manager - 写穿到数据库的会话管理器
+//!
+//! 会话状态仍以内存为准，每次变更后由后台任务按顺序写入 SessionRepository；
+//! 启动时从数据库恢复会话，服务重启后客户端可以用原来的 session_id 重连继续播放
+
+use chrono::Utc;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{mpsc, oneshot};
+use uuid::Uuid;
+
+use super::InMemorySessionManager;
+use crate::application::ports::{
+    RepositoryError, Session, SessionError, SessionManagerPort, SessionRecord,
+    SessionRepositoryPort, SessionState, VoiceHandoff, WindowConfig,
+};
+use crate::domain::voice::SynthesisParams;
+
+/// 待写入数据库的会话变更
+enum SessionWrite {
+    Create(Session),
+    Update(Session),
+    Delete(String),
+    /// 之前的写入全部完成后通知
+    Flush(oneshot::Sender<()>),
+}
+
+impl SessionWrite {
+    fn session_id(&self) -> Option<&str> {
+        match self {
+            SessionWrite::Create(session) | SessionWrite::Update(session) => Some(&session.id),
+            SessionWrite::Delete(id) => Some(id),
+            SessionWrite::Flush(_) => None,
+        }
+    }
+}
+
+/// 写穿会话管理器
+pub struct PersistentSessionManager {
+    sessions: InMemorySessionManager,
+    session_repo: Arc<dyn SessionRepositoryPort>,
+    writes: mpsc::UnboundedSender<SessionWrite>,
+}
+
+impl PersistentSessionManager {
+    /// 创建管理器并启动写入任务（需在 Tokio 运行时中调用）
+    pub fn new(session_repo: Arc<dyn SessionRepositoryPort>) -> Self {
+        let (writes, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(run_writer(session_repo.clone(), receiver));
+        Self {
+            sessions: InMemorySessionManager::new(),
+            session_repo,
+            writes,
+        }
+    }
+
+    /// 从数据库恢复会话，返回恢复数
+    pub async fn restore(&self) -> Result<usize, RepositoryError> {
+        let records = self.session_repo.find_all().await?;
+        let mut restored = 0;
+        for record in records {
+            if record.state == SessionState::Finished {
+                continue;
+            }
+            if self.sessions.create(session_from_record(record)).is_ok() {
+                restored += 1;
+            }
+        }
+        Ok(restored)
+    }
+
+    /// 等待已提交的写入全部完成（关闭服务前调用）
+    pub async fn flush(&self) {
+        let (done, wait) = oneshot::channel();
+        if self.writes.send(SessionWrite::Flush(done)).is_ok() {
+            let _ = wait.await;
+        }
+    }
+
+    fn enqueue(&self, write: SessionWrite) {
+        if self.writes.send(write).is_err() {
+            tracing::warn!("Session writer stopped, change not persisted");
+        }
+    }
+
+    /// 内存中的会话变更成功后写入其最新状态
+    fn persist(&self, id: &str) {
+        if let Ok(session) = self.sessions.get(id) {
+            self.enqueue(SessionWrite::Update(session));
+        }
+    }
+}
+
+impl SessionManagerPort for PersistentSessionManager {
+    fn create(&self, session: Session) -> Result<String, SessionError> {
+        let snapshot = session.clone();
+        let session_id = self.sessions.create(session)?;
+        self.enqueue(SessionWrite::Create(snapshot));
+        Ok(session_id)
+    }
+
+    fn get(&self, id: &str) -> Result<Session, SessionError> {
+        self.sessions.get(id)
+    }
+
+    fn update_index(&self, id: &str, index: u32) -> Result<(), SessionError> {
+        self.sessions.update_index(id, index)?;
+        self.persist(id);
+        Ok(())
+    }
+
+    fn step_index(&self, id: &str, delta: i32, last_index: u32) -> Result<u32, SessionError> {
+        let index = self.sessions.step_index(id, delta, last_index)?;
+        self.persist(id);
+        Ok(index)
+    }
+
+    fn update_position(&self, id: &str, index: u32, offset_ms: u32) -> Result<(), SessionError> {
+        self.sessions.update_position(id, index, offset_ms)?;
+        self.persist(id);
+        Ok(())
+    }
+
+    fn update_voice(&self, id: &str, voice_id: Uuid) -> Result<(), SessionError> {
+        self.sessions.update_voice(id, voice_id)?;
+        self.persist(id);
+        Ok(())
+    }
+
+    fn update_window(&self, id: &str, window: WindowConfig) -> Result<(), SessionError> {
+        self.sessions.update_window(id, window)?;
+        self.persist(id);
+        Ok(())
+    }
+
+    fn set_auto_advance(&self, id: &str, enabled: bool) -> Result<(), SessionError> {
+        self.sessions.set_auto_advance(id, enabled)?;
+        self.persist(id);
+        Ok(())
+    }
+
+    fn set_voice_handoff(&self, id: &str, handoff: Option<VoiceHandoff>) -> Result<(), SessionError> {
+        // 过渡状态只在内存中，重启后按新音色播放
+        self.sessions.set_voice_handoff(id, handoff)
+    }
+
+    fn update_params(&self, id: &str, params: SynthesisParams) -> Result<(), SessionError> {
+        self.sessions.update_params(id, params)?;
+        self.persist(id);
+        Ok(())
+    }
+
+    fn set_segment_params(
+        &self,
+        id: &str,
+        segment_index: u32,
+        params: SynthesisParams,
+    ) -> Result<(), SessionError> {
+        self.sessions.set_segment_params(id, segment_index, params)?;
+        self.persist(id);
+        Ok(())
+    }
+
+    fn is_valid(&self, id: &str) -> bool {
+        self.sessions.is_valid(id)
+    }
+
+    fn close(&self, id: &str) -> Result<(), SessionError> {
+        self.sessions.close(id)?;
+        self.enqueue(SessionWrite::Delete(id.to_string()));
+        Ok(())
+    }
+
+    fn touch(&self, id: &str) {
+        self.sessions.touch(id);
+        self.persist(id);
+    }
+
+    fn get_expired_sessions(&self, idle_timeout_secs: u64) -> Vec<String> {
+        self.sessions.get_expired_sessions(idle_timeout_secs)
+    }
+
+    fn list_all(&self) -> Vec<String> {
+        self.sessions.list_all()
+    }
+}
+
+/// 按提交顺序写入数据库；积压的写入中同一会话只保留最后一次更新
+async fn run_writer(
+    session_repo: Arc<dyn SessionRepositoryPort>,
+    mut receiver: mpsc::UnboundedReceiver<SessionWrite>,
+) {
+    while let Some(write) = receiver.recv().await {
+        let mut batch = vec![write];
+        while let Ok(write) = receiver.try_recv() {
+            batch.push(write);
+        }
+
+        let mut last_write: HashMap<String, usize> = HashMap::new();
+        for (i, write) in batch.iter().enumerate() {
+            if let Some(id) = write.session_id() {
+                last_write.insert(id.to_string(), i);
+            }
+        }
+
+        for (i, write) in batch.into_iter().enumerate() {
+            if let SessionWrite::Update(session) = &write {
+                if last_write.get(&session.id) != Some(&i) {
+                    continue;
+                }
+            }
+            apply(session_repo.as_ref(), write).await;
+        }
+    }
+}
+
+async fn apply(session_repo: &dyn SessionRepositoryPort, write: SessionWrite) {
+    let result = match &write {
+        SessionWrite::Create(session) => match session_to_record(session) {
+            Some(record) => session_repo.save(&record).await,
+            None => Ok(()),
+        },
+        SessionWrite::Update(session) => match session_to_record(session) {
+            Some(record) => session_repo.update(&record).await,
+            None => Ok(()),
+        },
+        SessionWrite::Delete(id) => match Uuid::parse_str(id) {
+            Ok(id) => session_repo.delete(id).await,
+            Err(_) => Ok(()),
+        },
+        SessionWrite::Flush(_) => Ok(()),
+    };
+
+    if let Err(e) = result {
+        tracing::warn!(session_id = ?write.session_id(), error = %e, "Failed to persist session");
+    }
+    if let SessionWrite::Flush(done) = write {
+        let _ = done.send(());
+    }
+}
+
+/// 后台会话和 ID 不是 UUID 的会话不持久化
+fn session_to_record(session: &Session) -> Option<SessionRecord> {
+    if session.background {
+        return None;
+    }
+    let id = Uuid::parse_str(&session.id).ok()?;
+    Some(SessionRecord {
+        id,
+        novel_id: session.novel_id,
+        voice_id: session.voice_id,
+        current_index: session.current_index as usize,
+        offset_ms: session.offset_ms,
+        state: SessionState::Playing,
+        window_config: session.window,
+        params: session.params.clone(),
+        segment_params: session.segment_params.clone(),
+        playback_speed: session.playback_speed,
+        playback_hints: session.playback_hints,
+        client: session.client.clone(),
+        auto_advance: session.auto_advance,
+        user_id: session.user_id,
+        created_at: session.created_at,
+        updated_at: Utc::now(),
+        last_accessed_at: session.last_activity,
+    })
+}
+
+fn session_from_record(record: SessionRecord) -> Session {
+    Session {
+        id: record.id.to_string(),
+        novel_id: record.novel_id,
+        voice_id: record.voice_id,
+        current_index: record.current_index as u32,
+        offset_ms: record.offset_ms,
+        params: record.params,
+        segment_params: record.segment_params,
+        playback_speed: record.playback_speed,
+        playback_hints: record.playback_hints,
+        window: record.window_config,
+        background: false,
+        client: record.client,
+        user_id: record.user_id,
+        auto_advance: record.auto_advance,
+        voice_handoff: None,
+        created_at: record.created_at,
+        last_activity: record.last_accessed_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MemorySessionRepo {
+        records: Mutex<HashMap<Uuid, SessionRecord>>,
+    }
+
+    #[async_trait]
+    impl SessionRepositoryPort for MemorySessionRepo {
+        async fn save(&self, session: &SessionRecord) -> Result<(), RepositoryError> {
+            self.records.lock().unwrap().insert(session.id, session.clone());
+            Ok(())
+        }
+
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<SessionRecord>, RepositoryError> {
+            Ok(self.records.lock().unwrap().get(&id).cloned())
+        }
+
+        async fn find_all(&self) -> Result<Vec<SessionRecord>, RepositoryError> {
+            Ok(self.records.lock().unwrap().values().cloned().collect())
+        }
+
+        async fn update(&self, session: &SessionRecord) -> Result<(), RepositoryError> {
+            if let Some(record) = self.records.lock().unwrap().get_mut(&session.id) {
+                *record = session.clone();
+            }
+            Ok(())
+        }
+
+        async fn delete(&self, id: Uuid) -> Result<(), RepositoryError> {
+            self.records.lock().unwrap().remove(&id);
+            Ok(())
+        }
+
+        async fn find_active(&self) -> Result<Vec<SessionRecord>, RepositoryError> {
+            self.find_all().await
+        }
+
+        async fn find_expired(&self, _: u64) -> Result<Vec<SessionRecord>, RepositoryError> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sessions_survive_restart() {
+        let repo = Arc::new(MemorySessionRepo::default());
+        let manager = PersistentSessionManager::new(repo.clone());
+
+        let kept = Session::new(Uuid::new_v4(), Uuid::new_v4(), 0);
+        let kept_id = manager.create(kept).unwrap();
+        let closed_id = manager.create(Session::new(Uuid::new_v4(), Uuid::new_v4(), 0)).unwrap();
+        let voice_id = Uuid::new_v4();
+        manager.update_position(&kept_id, 7, 1200).unwrap();
+        manager.update_voice(&kept_id, voice_id).unwrap();
+        manager
+            .set_segment_params(
+                &kept_id,
+                3,
+                SynthesisParams { speed: Some(1.5), ..Default::default() },
+            )
+            .unwrap();
+        manager.update_window(&kept_id, WindowConfig::new(1, 10)).unwrap();
+        manager.set_auto_advance(&kept_id, true).unwrap();
+        manager.close(&closed_id).unwrap();
+        manager.flush().await;
+
+        // 模拟重启：新的管理器从数据库恢复
+        let restarted = PersistentSessionManager::new(repo);
+        assert_eq!(restarted.restore().await.unwrap(), 1);
+        assert!(!restarted.is_valid(&closed_id));
+        let session = restarted.get(&kept_id).unwrap();
+        assert_eq!((session.current_index, session.offset_ms), (7, 1200));
+        assert_eq!(session.voice_id, voice_id);
+        assert_eq!(session.params_for(3).speed, Some(1.5));
+        assert_eq!(session.window, WindowConfig::new(1, 10));
+        assert!(session.auto_advance);
+    }
+}
@@ -0,0 +1,394 @@
+//! Persistent Task Manager - 写穿到数据库的任务管理器
+//!
+//! 任务状态仍以内存为准：提交、调整优先级和重试后写入任务，任务完成、失败或取消后删除，
+//! 由后台任务按顺序写入 QueuedTaskRepository。进程崩溃或重启后，启动时（恢复会话之后）
+//! 把所属会话仍有效的任务按原任务 ID 和优先级重新排队，推理中被中断的任务重新推理。
+//! 导出、预生成等后台会话不持久化，其任务也不写入
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{mpsc, oneshot};
+
+use super::InMemoryTaskManager;
+use crate::application::ports::{
+    InferenceTask, QueuedTaskRecord, QueuedTaskRepositoryPort, RepositoryError,
+    SessionManagerPort, TaskError, TaskManagerPort, TaskPriority, TaskState,
+};
+
+/// 待写入数据库的任务变更
+enum TaskWrite {
+    Save(QueuedTaskRecord),
+    Delete(String),
+    /// 之前的写入全部完成后通知
+    Flush(oneshot::Sender<()>),
+}
+
+impl TaskWrite {
+    fn task_id(&self) -> Option<&str> {
+        match self {
+            TaskWrite::Save(record) => Some(&record.task_id),
+            TaskWrite::Delete(task_id) => Some(task_id),
+            TaskWrite::Flush(_) => None,
+        }
+    }
+}
+
+/// 写穿任务管理器
+pub struct PersistentTaskManager {
+    tasks: InMemoryTaskManager,
+    session_manager: Arc<dyn SessionManagerPort>,
+    queued_task_repo: Arc<dyn QueuedTaskRepositoryPort>,
+    writes: mpsc::UnboundedSender<TaskWrite>,
+}
+
+impl PersistentTaskManager {
+    /// 创建管理器并启动写入任务（需在 Tokio 运行时中调用）
+    pub fn new(
+        tasks: InMemoryTaskManager,
+        session_manager: Arc<dyn SessionManagerPort>,
+        queued_task_repo: Arc<dyn QueuedTaskRepositoryPort>,
+    ) -> Self {
+        let (writes, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(run_writer(queued_task_repo.clone(), receiver));
+        Self {
+            tasks,
+            session_manager,
+            queued_task_repo,
+            writes,
+        }
+    }
+
+    /// 重新提交上次运行未完成的任务（需在恢复会话之后调用），返回恢复数；
+    /// 所属会话已不存在的任务删除
+    pub async fn restore(&self) -> Result<usize, RepositoryError> {
+        let mut tasks = Vec::new();
+        for record in self.queued_task_repo.find_all().await? {
+            if self.is_persistent(&record.session_id) {
+                tasks.push(task_from_record(record));
+            } else {
+                self.enqueue(TaskWrite::Delete(record.task_id));
+            }
+        }
+
+        let restored = tasks.len();
+        if !tasks.is_empty() {
+            self.tasks
+                .submit(tasks)
+                .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        }
+        Ok(restored)
+    }
+
+    /// 等待已提交的写入全部完成（关闭服务前调用）
+    pub async fn flush(&self) {
+        let (done, wait) = oneshot::channel();
+        if self.writes.send(TaskWrite::Flush(done)).is_ok() {
+            let _ = wait.await;
+        }
+    }
+
+    fn enqueue(&self, write: TaskWrite) {
+        if self.writes.send(write).is_err() {
+            tracing::warn!("Task writer stopped, change not persisted");
+        }
+    }
+
+    /// 后台会话的任务不持久化
+    fn is_persistent(&self, session_id: &str) -> bool {
+        self.session_manager
+            .get(session_id)
+            .map(|session| !session.background)
+            .unwrap_or(false)
+    }
+
+    /// 内存中的任务变更成功后写入其最新状态
+    fn persist(&self, task_id: &str) {
+        if let Some(task) = self.tasks.get_task(task_id) {
+            if self.is_persistent(&task.session_id) {
+                self.enqueue(TaskWrite::Save(task_to_record(task)));
+            }
+        }
+    }
+
+    /// 取消后删除会话中已结束的任务
+    fn cancelled(&self, session_id: &str, cancel: impl FnOnce() -> usize) -> usize {
+        let active: Vec<String> = self
+            .tasks
+            .get_tasks_by_session(session_id)
+            .into_iter()
+            .filter(|task| matches!(task.state, TaskState::Pending | TaskState::Inferring))
+            .map(|task| task.task_id)
+            .collect();
+        let cancelled_count = cancel();
+        if cancelled_count > 0 {
+            for task_id in active {
+                if self.tasks.get_state(&task_id) == Some(TaskState::Cancelled) {
+                    self.enqueue(TaskWrite::Delete(task_id));
+                }
+            }
+        }
+        cancelled_count
+    }
+}
+
+impl TaskManagerPort for PersistentTaskManager {
+    fn submit(&self, tasks: Vec<InferenceTask>) -> Result<Vec<String>, TaskError> {
+        let task_ids = self.tasks.submit(tasks)?;
+        for task_id in &task_ids {
+            self.persist(task_id);
+        }
+        Ok(task_ids)
+    }
+
+    fn cancel_pending(&self, session_id: &str) -> usize {
+        self.cancelled(session_id, || self.tasks.cancel_pending(session_id))
+    }
+
+    fn cancel_all(&self, session_id: &str) -> usize {
+        self.cancelled(session_id, || self.tasks.cancel_all(session_id))
+    }
+
+    fn cancel_outside(&self, session_id: &str, start: u32, end: u32) -> usize {
+        self.cancelled(session_id, || self.tasks.cancel_outside(session_id, start, end))
+    }
+
+    fn cancel_window(&self, session_id: &str) -> usize {
+        self.cancelled(session_id, || self.tasks.cancel_window(session_id))
+    }
+
+    fn prioritize(&self, task_id: &str, priority: TaskPriority) -> bool {
+        let prioritized = self.tasks.prioritize(task_id, priority);
+        if prioritized {
+            self.persist(task_id);
+        }
+        prioritized
+    }
+
+    fn is_cancelled(&self, task_id: &str) -> bool {
+        self.tasks.is_cancelled(task_id)
+    }
+
+    fn get_state(&self, task_id: &str) -> Option<TaskState> {
+        self.tasks.get_state(task_id)
+    }
+
+    fn set_state(&self, task_id: &str, state: TaskState) -> Result<(), TaskError> {
+        self.tasks.set_state(task_id, state)?;
+        if matches!(state, TaskState::Ready | TaskState::Failed | TaskState::Cancelled) {
+            self.enqueue(TaskWrite::Delete(task_id.to_string()));
+        }
+        Ok(())
+    }
+
+    fn set_failed(&self, task_id: &str, error: String) -> Result<(), TaskError> {
+        self.tasks.set_failed(task_id, error)?;
+        self.enqueue(TaskWrite::Delete(task_id.to_string()));
+        Ok(())
+    }
+
+    fn get_task(&self, task_id: &str) -> Option<InferenceTask> {
+        self.tasks.get_task(task_id)
+    }
+
+    fn get_tasks_by_session(&self, session_id: &str) -> Vec<InferenceTask> {
+        self.tasks.get_tasks_by_session(session_id)
+    }
+
+    fn list_tasks(&self, state: Option<TaskState>) -> Vec<InferenceTask> {
+        self.tasks.list_tasks(state)
+    }
+
+    fn retry(&self, task_id: &str, priority: TaskPriority) -> Result<(), TaskError> {
+        self.tasks.retry(task_id, priority)?;
+        self.persist(task_id);
+        Ok(())
+    }
+
+    fn cleanup_session(&self, session_id: &str) {
+        for task in self.tasks.get_tasks_by_session(session_id) {
+            if matches!(task.state, TaskState::Pending | TaskState::Inferring) {
+                self.enqueue(TaskWrite::Delete(task.task_id));
+            }
+        }
+        self.tasks.cleanup_session(session_id);
+    }
+
+    fn queue_depth(&self) -> usize {
+        self.tasks.queue_depth()
+    }
+}
+
+/// 按提交顺序写入数据库；积压的写入中同一任务只保留最后一次
+async fn run_writer(
+    queued_task_repo: Arc<dyn QueuedTaskRepositoryPort>,
+    mut receiver: mpsc::UnboundedReceiver<TaskWrite>,
+) {
+    while let Some(write) = receiver.recv().await {
+        let mut batch = vec![write];
+        while let Ok(write) = receiver.try_recv() {
+            batch.push(write);
+        }
+
+        let mut last_write: HashMap<String, usize> = HashMap::new();
+        for (i, write) in batch.iter().enumerate() {
+            if let Some(task_id) = write.task_id() {
+                last_write.insert(task_id.to_string(), i);
+            }
+        }
+
+        let mut saves = Vec::new();
+        let mut deletes = Vec::new();
+        let mut flushes = Vec::new();
+        for (i, write) in batch.into_iter().enumerate() {
+            if write.task_id().is_some_and(|task_id| last_write.get(task_id) != Some(&i)) {
+                continue;
+            }
+            match write {
+                TaskWrite::Save(record) => saves.push(record),
+                TaskWrite::Delete(task_id) => deletes.push(task_id),
+                TaskWrite::Flush(done) => flushes.push(done),
+            }
+        }
+
+        // 每个任务只剩一次写入，先后顺序不影响结果
+        if !saves.is_empty() {
+            if let Err(e) = queued_task_repo.save_all(&saves).await {
+                tracing::warn!(count = saves.len(), error = %e, "Failed to persist tasks");
+            }
+        }
+        if !deletes.is_empty() {
+            if let Err(e) = queued_task_repo.delete(&deletes).await {
+                tracing::warn!(count = deletes.len(), error = %e, "Failed to delete persisted tasks");
+            }
+        }
+        for done in flushes {
+            let _ = done.send(());
+        }
+    }
+}
+
+fn task_to_record(task: InferenceTask) -> QueuedTaskRecord {
+    QueuedTaskRecord {
+        task_id: task.task_id,
+        session_id: task.session_id,
+        novel_id: task.novel_id,
+        voice_id: task.voice_id,
+        segment_index: task.segment_index,
+        segment_content: task.segment_content,
+        params: task.params,
+        explicit: task.explicit,
+        priority: task.priority,
+        created_at: task.created_at,
+    }
+}
+
+fn task_from_record(record: QueuedTaskRecord) -> InferenceTask {
+    let mut task = InferenceTask::new(
+        record.session_id,
+        record.novel_id,
+        record.voice_id,
+        record.segment_index,
+        record.segment_content,
+    )
+    .with_params(record.params)
+    .with_priority(record.priority);
+    if record.explicit {
+        task = task.as_explicit();
+    }
+    task.task_id = record.task_id;
+    task.created_at = record.created_at;
+    task
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::Session;
+    use crate::infrastructure::memory::{InMemorySessionManager, TaskQueue};
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+    use uuid::Uuid;
+
+    #[derive(Default)]
+    struct MemoryQueuedTaskRepo {
+        records: Mutex<HashMap<String, QueuedTaskRecord>>,
+    }
+
+    #[async_trait]
+    impl QueuedTaskRepositoryPort for MemoryQueuedTaskRepo {
+        async fn save_all(&self, tasks: &[QueuedTaskRecord]) -> Result<(), RepositoryError> {
+            let mut records = self.records.lock().unwrap();
+            for task in tasks {
+                records.insert(task.task_id.clone(), task.clone());
+            }
+            Ok(())
+        }
+
+        async fn find_all(&self) -> Result<Vec<QueuedTaskRecord>, RepositoryError> {
+            Ok(self.records.lock().unwrap().values().cloned().collect())
+        }
+
+        async fn delete(&self, task_ids: &[String]) -> Result<(), RepositoryError> {
+            let mut records = self.records.lock().unwrap();
+            for task_id in task_ids {
+                records.remove(task_id);
+            }
+            Ok(())
+        }
+
+        async fn delete_all(&self) -> Result<(), RepositoryError> {
+            self.records.lock().unwrap().clear();
+            Ok(())
+        }
+    }
+
+    fn task(session_id: &str, segment_index: u32) -> InferenceTask {
+        InferenceTask::new(
+            session_id.to_string(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            segment_index,
+            format!("segment {}", segment_index),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_incomplete_tasks_survive_restart() {
+        let repo = Arc::new(MemoryQueuedTaskRepo::default());
+        let sessions = Arc::new(InMemorySessionManager::new());
+        let session_id = sessions.create(Session::new(Uuid::new_v4(), Uuid::new_v4(), 0)).unwrap();
+        let background_id = sessions
+            .create(Session::new(Uuid::new_v4(), Uuid::new_v4(), 0).as_background())
+            .unwrap();
+
+        let manager = PersistentTaskManager::new(
+            InMemoryTaskManager::new(Arc::new(TaskQueue::new(10))),
+            sessions.clone(),
+            repo.clone(),
+        );
+        let ids = manager
+            .submit(vec![task(&session_id, 0), task(&session_id, 1), task(&session_id, 2)])
+            .unwrap();
+        manager.submit(vec![task(&background_id, 0)]).unwrap();
+        manager.set_state(&ids[0], TaskState::Inferring).unwrap();
+        manager.set_state(&ids[1], TaskState::Ready).unwrap();
+        manager.prioritize(&ids[2], TaskPriority::Current);
+        manager.flush().await;
+
+        // 模拟崩溃后重启：推理中的和排队中的任务重新排队
+        let queue = Arc::new(TaskQueue::new(10));
+        let restarted = PersistentTaskManager::new(
+            InMemoryTaskManager::new(queue.clone()),
+            sessions,
+            repo.clone(),
+        );
+        assert_eq!(restarted.restore().await.unwrap(), 2);
+        assert_eq!(queue.try_pop().as_deref(), Some(ids[2].as_str()));
+        assert_eq!(queue.try_pop().as_deref(), Some(ids[0].as_str()));
+        assert_eq!(restarted.get_state(&ids[0]), Some(TaskState::Pending));
+
+        restarted.cancel_all(&session_id);
+        restarted.flush().await;
+        assert!(repo.records.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,53 @@
+//! In-Memory Pregeneration Job Manager Implementation
+
+use chrono::Utc;
+use dashmap::DashMap;
+
+use crate::application::ports::{PregenerationJob, PregenerationJobManagerPort, PregenerationJobState};
+
+/// 内存预生成任务管理器
+#[derive(Default)]
+pub struct InMemoryPregenerationJobManager {
+    /// job_id -> PregenerationJob
+    jobs: DashMap<String, PregenerationJob>,
+}
+
+impl InMemoryPregenerationJobManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl PregenerationJobManagerPort for InMemoryPregenerationJobManager {
+    fn create(&self, job: PregenerationJob) {
+        self.jobs.insert(job.job_id.clone(), job);
+    }
+
+    fn get(&self, job_id: &str) -> Option<PregenerationJob> {
+        self.jobs.get(job_id).map(|j| j.clone())
+    }
+
+    fn update_progress(&self, job_id: &str, completed_segments: usize, failed_segments: usize) {
+        if let Some(mut job) = self.jobs.get_mut(job_id) {
+            job.completed_segments = completed_segments;
+            job.failed_segments = failed_segments;
+        }
+    }
+
+    fn set_state(&self, job_id: &str, state: PregenerationJobState) {
+        if let Some(mut job) = self.jobs.get_mut(job_id) {
+            job.state = state;
+            if state.is_finished() {
+                job.completed_at = Some(Utc::now());
+            }
+        }
+    }
+
+    fn set_failed(&self, job_id: &str, error: String) {
+        if let Some(mut job) = self.jobs.get_mut(job_id) {
+            job.state = PregenerationJobState::Failed;
+            job.error_message = Some(error);
+            job.completed_at = Some(Utc::now());
+        }
+    }
+}
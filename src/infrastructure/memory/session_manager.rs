@@ -5,7 +5,10 @@ use dashmap::DashMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::application::ports::{Session, SessionError, SessionManagerPort};
+use crate::application::ports::{
+    Session, SessionError, SessionManagerPort, VoiceHandoff, WindowConfig,
+};
+use crate::domain::voice::SynthesisParams;
 
 /// 内存会话管理器
 pub struct InMemorySessionManager {
@@ -54,11 +57,45 @@ impl SessionManagerPort for InMemorySessionManager {
             .get_mut(id)
             .ok_or_else(|| SessionError::NotFound(id.to_string()))?;
         session.current_index = index;
+        session.offset_ms = 0;
         session.last_activity = Utc::now();
         tracing::debug!(session_id = %id, index = index, "Session index updated");
         Ok(())
     }
 
+    fn step_index(&self, id: &str, delta: i32, last_index: u32) -> Result<u32, SessionError> {
+        let mut session = self
+            .sessions
+            .get_mut(id)
+            .ok_or_else(|| SessionError::NotFound(id.to_string()))?;
+        if session.current_index > last_index {
+            return Err(SessionError::InvalidOperation(format!(
+                "Current index {} is out of range (last index: {})",
+                session.current_index, last_index
+            )));
+        }
+        let index = session
+            .current_index
+            .saturating_add_signed(delta)
+            .min(last_index);
+        session.current_index = index;
+        session.offset_ms = 0;
+        session.last_activity = Utc::now();
+        tracing::debug!(session_id = %id, index = index, "Session index stepped");
+        Ok(index)
+    }
+
+    fn update_position(&self, id: &str, index: u32, offset_ms: u32) -> Result<(), SessionError> {
+        let mut session = self
+            .sessions
+            .get_mut(id)
+            .ok_or_else(|| SessionError::NotFound(id.to_string()))?;
+        session.current_index = index;
+        session.offset_ms = offset_ms;
+        session.last_activity = Utc::now();
+        Ok(())
+    }
+
     fn update_voice(&self, id: &str, voice_id: Uuid) -> Result<(), SessionError> {
         let mut session = self
             .sessions
@@ -70,6 +107,72 @@ impl SessionManagerPort for InMemorySessionManager {
         Ok(())
     }
 
+    fn update_window(&self, id: &str, window: WindowConfig) -> Result<(), SessionError> {
+        let mut session = self
+            .sessions
+            .get_mut(id)
+            .ok_or_else(|| SessionError::NotFound(id.to_string()))?;
+        tracing::debug!(session_id = %id, window = ?window, "Session window updated");
+        session.window = window;
+        session.last_activity = Utc::now();
+        Ok(())
+    }
+
+    fn set_auto_advance(&self, id: &str, enabled: bool) -> Result<(), SessionError> {
+        let mut session = self
+            .sessions
+            .get_mut(id)
+            .ok_or_else(|| SessionError::NotFound(id.to_string()))?;
+        tracing::debug!(session_id = %id, enabled = enabled, "Session auto-advance updated");
+        session.auto_advance = enabled;
+        session.last_activity = Utc::now();
+        Ok(())
+    }
+
+    fn set_voice_handoff(&self, id: &str, handoff: Option<VoiceHandoff>) -> Result<(), SessionError> {
+        let mut session = self
+            .sessions
+            .get_mut(id)
+            .ok_or_else(|| SessionError::NotFound(id.to_string()))?;
+        tracing::debug!(
+            session_id = %id,
+            previous_voice_id = ?handoff.as_ref().map(|h| h.voice_id),
+            "Session voice handoff updated"
+        );
+        session.voice_handoff = handoff;
+        Ok(())
+    }
+
+    fn update_params(&self, id: &str, params: SynthesisParams) -> Result<(), SessionError> {
+        let mut session = self
+            .sessions
+            .get_mut(id)
+            .ok_or_else(|| SessionError::NotFound(id.to_string()))?;
+        tracing::debug!(session_id = %id, params = ?params, "Session params updated");
+        session.params = params;
+        session.last_activity = Utc::now();
+        Ok(())
+    }
+
+    fn set_segment_params(
+        &self,
+        id: &str,
+        segment_index: u32,
+        params: SynthesisParams,
+    ) -> Result<(), SessionError> {
+        let mut session = self
+            .sessions
+            .get_mut(id)
+            .ok_or_else(|| SessionError::NotFound(id.to_string()))?;
+        if params.is_default() {
+            session.segment_params.remove(&segment_index);
+        } else {
+            session.segment_params.insert(segment_index, params);
+        }
+        session.last_activity = Utc::now();
+        Ok(())
+    }
+
     fn is_valid(&self, id: &str) -> bool {
         self.sessions.contains_key(id)
     }
@@ -136,6 +239,57 @@ mod tests {
         let session = manager.get(&session_id).unwrap();
         assert_eq!(session.current_index, 10);
 
+        // Position: 上报进度记录偏移，跳转后偏移归零
+        manager.update_position(&session_id, 11, 2500).unwrap();
+        let session = manager.get(&session_id).unwrap();
+        assert_eq!((session.current_index, session.offset_ms), (11, 2500));
+        manager.update_index(&session_id, 10).unwrap();
+        assert_eq!(manager.get(&session_id).unwrap().offset_ms, 0);
+
+        // Step: 前后移动，限制在片段范围内
+        assert_eq!(manager.step_index(&session_id, 1, 10).unwrap(), 10);
+        assert_eq!(manager.step_index(&session_id, -1, 10).unwrap(), 9);
+        manager.update_index(&session_id, 0).unwrap();
+        assert_eq!(manager.step_index(&session_id, -1, 10).unwrap(), 0);
+        manager.update_index(&session_id, 10).unwrap();
+        // 当前索引超出范围：拒绝，索引不变
+        assert!(matches!(
+            manager.step_index(&session_id, -1, 5),
+            Err(SessionError::InvalidOperation(_))
+        ));
+        assert_eq!(manager.get(&session_id).unwrap().current_index, 10);
+
+        // Params: 片段覆盖叠加在会话参数之上
+        manager
+            .update_params(&session_id, SynthesisParams { speed: Some(1.2), ..Default::default() })
+            .unwrap();
+        manager
+            .set_segment_params(
+                &session_id,
+                3,
+                SynthesisParams { emotion: Some("sad".to_string()), ..Default::default() },
+            )
+            .unwrap();
+        let session = manager.get(&session_id).unwrap();
+        assert_eq!(session.params_for(3).speed, Some(1.2));
+        assert_eq!(session.params_for(3).emotion.as_deref(), Some("sad"));
+        assert!(session.params_for(4).emotion.is_none());
+
+        // Voice handoff: 旧音色沿用片段覆盖，结束后清除
+        let old_voice_id = session.voice_id;
+        manager
+            .set_voice_handoff(
+                &session_id,
+                Some(VoiceHandoff { voice_id: old_voice_id, params: SynthesisParams::default() }),
+            )
+            .unwrap();
+        manager.update_voice(&session_id, Uuid::new_v4()).unwrap();
+        let (voice_id, params) = manager.get(&session_id).unwrap().handoff_for(3).unwrap();
+        assert_eq!(voice_id, old_voice_id);
+        assert_eq!((params.speed, params.emotion.as_deref()), (None, Some("sad")));
+        manager.set_voice_handoff(&session_id, None).unwrap();
+        assert!(manager.get(&session_id).unwrap().handoff_for(3).is_none());
+
         // Is valid
         assert!(manager.is_valid(&session_id));
 
@@ -4,9 +4,9 @@ use chrono::Utc;
 use dashmap::DashMap;
 use std::collections::HashSet;
 use std::sync::Arc;
-use tokio::sync::mpsc;
 
-use crate::application::ports::{InferenceTask, TaskError, TaskManagerPort, TaskState};
+use super::{PushRejection, TaskQueue};
+use crate::application::ports::{InferenceTask, TaskError, TaskManagerPort, TaskPriority, TaskState};
 
 /// 内存任务管理器
 pub struct InMemoryTaskManager {
@@ -14,67 +14,106 @@ pub struct InMemoryTaskManager {
     tasks: DashMap<String, InferenceTask>,
     /// session_id -> Set<task_id>
     session_tasks: DashMap<String, HashSet<String>>,
-    /// 任务队列发送端
-    queue_sender: mpsc::Sender<String>,
+    /// 任务队列（Worker 按优先级消费）
+    queue: Arc<TaskQueue>,
 }
 
 impl InMemoryTaskManager {
-    pub fn new(queue_sender: mpsc::Sender<String>) -> Self {
+    pub fn new(queue: Arc<TaskQueue>) -> Self {
         Self {
             tasks: DashMap::new(),
             session_tasks: DashMap::new(),
-            queue_sender,
+            queue,
         }
     }
 
     pub fn arc(self) -> Arc<Self> {
         Arc::new(self)
     }
-}
-
-impl TaskManagerPort for InMemoryTaskManager {
-    fn submit(&self, tasks: Vec<InferenceTask>) -> Result<Vec<String>, TaskError> {
-        let mut task_ids = Vec::with_capacity(tasks.len());
-
-        for task in tasks {
-            let task_id = task.task_id.clone();
-            let session_id = task.session_id.clone();
-
-            // 存储任务
-            self.tasks.insert(task_id.clone(), task);
 
-            // 关联到会话
-            self.session_tasks
-                .entry(session_id.clone())
-                .or_insert_with(HashSet::new)
-                .insert(task_id.clone());
-
-            // 发送到队列
-            if let Err(e) = self.queue_sender.try_send(task_id.clone()) {
-                tracing::warn!(task_id = %task_id, error = %e, "Failed to enqueue task");
-            }
-
-            task_ids.push(task_id);
+    fn queue_full(&self) -> TaskError {
+        TaskError::QueueFull {
+            depth: self.queue.len(),
+            capacity: self.queue.capacity(),
         }
-
-        tracing::debug!(count = task_ids.len(), "Tasks submitted");
-        Ok(task_ids)
     }
 
-    fn cancel_pending(&self, session_id: &str) -> usize {
+    /// 取消会话中满足条件的 pending 任务，`abort_inferring` 时同时中止推理中的任务
+    fn cancel_where(
+        &self,
+        session_id: &str,
+        abort_inferring: bool,
+        predicate: impl Fn(&InferenceTask) -> bool,
+    ) -> usize {
         let mut cancelled_count = 0;
 
         if let Some(task_ids) = self.session_tasks.get(session_id) {
             for task_id in task_ids.iter() {
                 if let Some(mut task) = self.tasks.get_mut(task_id) {
-                    if task.state == TaskState::Pending {
+                    let cancellable = task.state == TaskState::Pending
+                        || (abort_inferring && task.state == TaskState::Inferring);
+                    if cancellable && predicate(&task) {
                         task.state = TaskState::Cancelled;
                         task.completed_at = Some(Utc::now());
+                        task.cancel_token.cancel();
+                        // 取消的任务移出队列，不再占用容量
+                        self.queue.remove(task_id);
                         cancelled_count += 1;
                     }
                 }
             }
         }
+        cancelled_count
+    }
+}
+
+impl TaskManagerPort for InMemoryTaskManager {
+    fn submit(&self, tasks: Vec<InferenceTask>) -> Result<Vec<String>, TaskError> {
+        let entries: Vec<(String, String, TaskPriority)> = tasks
+            .iter()
+            .map(|task| (task.task_id.clone(), task.session_id.clone(), task.priority))
+            .collect();
+
+        // 先存储任务并关联到会话，Worker 出队时即可取到
+        for task in tasks {
+            self.session_tasks
+                .entry(task.session_id.clone())
+                .or_default()
+                .insert(task.task_id.clone());
+            self.tasks.insert(task.task_id.clone(), task);
+        }
+
+        // 按优先级整批入队（同优先级各会话轮流出队）
+        let refs: Vec<(&str, &str, TaskPriority)> = entries
+            .iter()
+            .map(|(task_id, session_id, priority)| (task_id.as_str(), session_id.as_str(), *priority))
+            .collect();
+        match self.queue.push_all(&refs) {
+            Ok(()) => {}
+            Err(PushRejection::Full) => {
+                for (task_id, session_id, _) in &entries {
+                    self.tasks.remove(task_id);
+                    if let Some(mut task_ids) = self.session_tasks.get_mut(session_id) {
+                        task_ids.remove(task_id);
+                    }
+                }
+                let error = self.queue_full();
+                tracing::warn!(count = entries.len(), error = %error, "Tasks rejected");
+                return Err(error);
+            }
+            // 关闭服务时保留任务状态，由持久化的任务管理器在下次启动时恢复
+            Err(PushRejection::Closed) => {
+                tracing::warn!(count = entries.len(), "Failed to enqueue tasks: queue is closed");
+            }
+        }
+
+        let task_ids: Vec<String> = entries.into_iter().map(|(task_id, _, _)| task_id).collect();
+        tracing::debug!(count = task_ids.len(), "Tasks submitted");
+        Ok(task_ids)
+    }
+
+    fn cancel_pending(&self, session_id: &str) -> usize {
+        let cancelled_count = self.cancel_where(session_id, false, |_| true);
 
         tracing::debug!(
             session_id = %session_id,
@@ -84,6 +123,58 @@ impl TaskManagerPort for InMemoryTaskManager {
         cancelled_count
     }
 
+    fn cancel_all(&self, session_id: &str) -> usize {
+        let cancelled_count = self.cancel_where(session_id, true, |_| true);
+
+        tracing::debug!(
+            session_id = %session_id,
+            cancelled_count = cancelled_count,
+            "Session tasks cancelled"
+        );
+        cancelled_count
+    }
+
+    fn cancel_outside(&self, session_id: &str, start: u32, end: u32) -> usize {
+        let cancelled_count = self.cancel_where(session_id, true, |task| {
+            !task.explicit && !(start..=end).contains(&task.segment_index)
+        });
+
+        tracing::debug!(
+            session_id = %session_id,
+            start,
+            end,
+            cancelled_count,
+            "Tasks outside window cancelled"
+        );
+        cancelled_count
+    }
+
+    fn cancel_window(&self, session_id: &str) -> usize {
+        let cancelled_count = self.cancel_where(session_id, true, |task| !task.explicit);
+
+        tracing::debug!(
+            session_id = %session_id,
+            cancelled_count,
+            "Window tasks cancelled"
+        );
+        cancelled_count
+    }
+
+    fn prioritize(&self, task_id: &str, priority: TaskPriority) -> bool {
+        let Some(mut task) = self.tasks.get_mut(task_id) else {
+            return false;
+        };
+        if task.state != TaskState::Pending || task.priority >= priority {
+            return false;
+        }
+        task.priority = priority;
+        let session_id = task.session_id.clone();
+        drop(task);
+
+        tracing::debug!(task_id = %task_id, priority = ?priority, "Task reprioritized");
+        self.queue.push(task_id, &session_id, priority)
+    }
+
     fn is_cancelled(&self, task_id: &str) -> bool {
         self.tasks
             .get(task_id)
@@ -145,14 +236,62 @@ impl TaskManagerPort for InMemoryTaskManager {
             .unwrap_or_default()
     }
 
+    fn list_tasks(&self, state: Option<TaskState>) -> Vec<InferenceTask> {
+        self.tasks
+            .iter()
+            .filter(|task| state.is_none_or(|state| task.state == state))
+            .map(|task| task.clone())
+            .collect()
+    }
+
+    fn retry(&self, task_id: &str, priority: TaskPriority) -> Result<(), TaskError> {
+        let mut task = self
+            .tasks
+            .get_mut(task_id)
+            .ok_or_else(|| TaskError::NotFound(task_id.to_string()))?;
+        if task.state != TaskState::Failed {
+            return Err(TaskError::InvalidStateTransition(format!(
+                "{} -> pending",
+                task.state.as_str()
+            )));
+        }
+        let previous = (task.priority, task.error_message.take(), task.completed_at.take());
+        task.state = TaskState::Pending;
+        task.priority = priority;
+        let session_id = task.session_id.clone();
+        drop(task);
+
+        match self.queue.push_all(&[(task_id, &session_id, priority)]) {
+            Ok(()) => {}
+            Err(PushRejection::Full) => {
+                // 队列已满时保持失败状态，客户端稍后重试
+                if let Some(mut task) = self.tasks.get_mut(task_id) {
+                    task.state = TaskState::Failed;
+                    (task.priority, task.error_message, task.completed_at) = previous;
+                }
+                return Err(self.queue_full());
+            }
+            Err(PushRejection::Closed) => {
+                tracing::warn!(task_id = %task_id, "Failed to enqueue task: queue is closed");
+            }
+        }
+        tracing::debug!(task_id = %task_id, "Failed task requeued");
+        Ok(())
+    }
+
     fn cleanup_session(&self, session_id: &str) {
         if let Some((_, task_ids)) = self.session_tasks.remove(session_id) {
             for task_id in task_ids {
+                self.queue.remove(&task_id);
                 self.tasks.remove(&task_id);
             }
             tracing::debug!(session_id = %session_id, "Session tasks cleaned up");
         }
     }
+
+    fn queue_depth(&self) -> usize {
+        self.queue.len()
+    }
 }
 
 #[cfg(test)]
@@ -162,8 +301,8 @@ mod tests {
 
     #[tokio::test]
     async fn test_task_lifecycle() {
-        let (tx, mut rx) = mpsc::channel(100);
-        let manager = InMemoryTaskManager::new(tx);
+        let queue = Arc::new(TaskQueue::new(100));
+        let manager = InMemoryTaskManager::new(queue.clone());
 
         let task = InferenceTask::new(
             "session-1".to_string(),
@@ -181,8 +320,8 @@ mod tests {
         assert_eq!(task_ids.len(), 1);
 
         // Check queue
-        let queued_id = rx.try_recv();
-        assert!(queued_id.is_ok());
+        let queued_id = queue.try_pop();
+        assert!(queued_id.is_some());
         assert_eq!(queued_id.unwrap(), task_id);
 
         // Get state
@@ -201,8 +340,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_cancel_pending() {
-        let (tx, _rx) = mpsc::channel(100);
-        let manager = InMemoryTaskManager::new(tx);
+        let manager = InMemoryTaskManager::new(Arc::new(TaskQueue::new(100)));
 
         // Submit multiple tasks
         let tasks: Vec<InferenceTask> = (0..5)
@@ -228,4 +366,200 @@ mod tests {
             assert_eq!(task.state, TaskState::Cancelled);
         }
     }
+
+    #[tokio::test]
+    async fn test_cancel_pending_outside_window() {
+        let manager = InMemoryTaskManager::new(Arc::new(TaskQueue::new(100)));
+
+        let tasks: Vec<InferenceTask> = (0..6)
+            .map(|i| {
+                InferenceTask::new(
+                    "session-1".to_string(),
+                    Uuid::new_v4(),
+                    Uuid::new_v4(),
+                    i,
+                    format!("Content {}", i),
+                )
+            })
+            .collect();
+        manager.submit(tasks).unwrap();
+
+        assert_eq!(manager.cancel_outside("session-1", 2, 4), 3);
+        for task in manager.get_tasks_by_session("session-1") {
+            let expected = if (2..=4).contains(&task.segment_index) {
+                TaskState::Pending
+            } else {
+                TaskState::Cancelled
+            };
+            assert_eq!(task.state, expected);
+        }
+
+        // 显式预取的任务不随窗口移动取消
+        let explicit = InferenceTask::new(
+            "session-1".to_string(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            40,
+            "Content 40".to_string(),
+        )
+        .as_explicit();
+        manager.submit(vec![explicit]).unwrap();
+        assert_eq!(manager.cancel_outside("session-1", 2, 4), 0);
+
+        // 会话空闲时取消整个窗口，显式预取的任务保留
+        assert_eq!(manager.cancel_window("session-1"), 3);
+        let pending = manager.list_tasks(Some(TaskState::Pending));
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].segment_index, 40);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_aborts_inferring_task() {
+        let manager = InMemoryTaskManager::new(Arc::new(TaskQueue::new(100)));
+
+        let tasks: Vec<InferenceTask> = (0..3)
+            .map(|i| {
+                InferenceTask::new(
+                    "session-1".to_string(),
+                    Uuid::new_v4(),
+                    Uuid::new_v4(),
+                    i,
+                    format!("Content {}", i),
+                )
+            })
+            .collect();
+        let task_ids = manager.submit(tasks).unwrap();
+        for task_id in &task_ids {
+            manager.set_state(task_id, TaskState::Inferring).unwrap();
+        }
+        // Worker 持有的是任务的克隆
+        let in_flight: Vec<InferenceTask> =
+            task_ids.iter().map(|id| manager.get_task(id).unwrap()).collect();
+
+        // 推理中的任务只在移出窗口或会话关闭时中止
+        assert_eq!(manager.cancel_pending("session-1"), 0);
+        assert_eq!(manager.cancel_outside("session-1", 1, 2), 1);
+        assert!(in_flight[0].cancel_token.is_cancelled());
+        assert!(!in_flight[1].cancel_token.is_cancelled());
+
+        assert_eq!(manager.cancel_all("session-1"), 2);
+        assert!(in_flight.iter().all(|task| task.cancel_token.is_cancelled()));
+        assert!(task_ids.iter().all(|id| manager.is_cancelled(id)));
+    }
+
+    #[tokio::test]
+    async fn test_prioritize_pending_task() {
+        let queue = Arc::new(TaskQueue::new(100));
+        let manager = InMemoryTaskManager::new(queue.clone());
+
+        let tasks: Vec<InferenceTask> = (0..3)
+            .map(|i| {
+                InferenceTask::new(
+                    "session-1".to_string(),
+                    Uuid::new_v4(),
+                    Uuid::new_v4(),
+                    i,
+                    format!("Content {}", i),
+                )
+            })
+            .collect();
+        let task_ids = manager.submit(tasks).unwrap();
+
+        // 跳转到片段 2：已排队的任务提升为当前片段，先于之前提交的任务出队
+        assert!(manager.prioritize(&task_ids[2], TaskPriority::Current));
+        assert!(!manager.prioritize(&task_ids[2], TaskPriority::Prefetch));
+        assert_eq!(queue.try_pop().as_ref(), Some(&task_ids[2]));
+        assert_eq!(queue.try_pop().as_ref(), Some(&task_ids[0]));
+
+        // 推理中的任务不再调整
+        manager.set_state(&task_ids[1], TaskState::Inferring).unwrap();
+        assert!(!manager.prioritize(&task_ids[1], TaskPriority::Current));
+    }
+
+    #[tokio::test]
+    async fn test_retry_failed_task() {
+        let queue = Arc::new(TaskQueue::new(100));
+        let manager = InMemoryTaskManager::new(queue.clone());
+
+        let task = InferenceTask::new(
+            "session-1".to_string(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            7,
+            "Content 7".to_string(),
+        );
+        let task_id = manager.submit(vec![task]).unwrap().remove(0);
+        assert_eq!(queue.try_pop().as_ref(), Some(&task_id));
+
+        // 只有失败的任务可以重试
+        assert!(manager.retry(&task_id, TaskPriority::Current).is_err());
+        manager.set_failed(&task_id, "TTS error: timeout".to_string()).unwrap();
+        let failed = manager.list_tasks(Some(TaskState::Failed));
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].error_message.as_deref(), Some("TTS error: timeout"));
+
+        manager.retry(&task_id, TaskPriority::Current).unwrap();
+        let task = manager.get_task(&task_id).unwrap();
+        assert_eq!((task.state, task.priority), (TaskState::Pending, TaskPriority::Current));
+        assert!(task.error_message.is_none());
+        assert!(manager.list_tasks(Some(TaskState::Failed)).is_empty());
+        assert_eq!(queue.try_pop().as_ref(), Some(&task_id));
+    }
+
+    #[tokio::test]
+    async fn test_submit_queue_full() {
+        let queue = Arc::new(TaskQueue::new(3));
+        let manager = InMemoryTaskManager::new(queue.clone());
+        let tasks = |count: u32| -> Vec<InferenceTask> {
+            (0..count)
+                .map(|i| {
+                    InferenceTask::new(
+                        "session-1".to_string(),
+                        Uuid::new_v4(),
+                        Uuid::new_v4(),
+                        i,
+                        format!("Content {}", i),
+                    )
+                })
+                .collect()
+        };
+
+        manager.submit(tasks(2)).unwrap();
+        assert_eq!(manager.queue_depth(), 2);
+
+        // 放不下的整批拒绝，不留下未入队的任务
+        let error = manager.submit(tasks(2)).unwrap_err();
+        assert!(matches!(error, TaskError::QueueFull { depth: 2, capacity: 3 }));
+        assert_eq!(manager.get_tasks_by_session("session-1").len(), 2);
+        assert_eq!(manager.queue_depth(), 2);
+
+        manager.submit(tasks(1)).unwrap();
+        assert_eq!(manager.queue_depth(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_tasks_free_queue_capacity() {
+        let manager = InMemoryTaskManager::new(Arc::new(TaskQueue::new(2)));
+        let task = |i: u32| {
+            InferenceTask::new(
+                "session-1".to_string(),
+                Uuid::new_v4(),
+                Uuid::new_v4(),
+                i,
+                format!("Content {}", i),
+            )
+        };
+
+        manager.submit(vec![task(0), task(1)]).unwrap();
+        assert!(matches!(
+            manager.submit(vec![task(2)]),
+            Err(TaskError::QueueFull { depth: 2, capacity: 2 })
+        ));
+
+        // 取消后队列腾出空间，新的任务可以提交
+        assert_eq!(manager.cancel_pending("session-1"), 2);
+        assert_eq!(manager.queue_depth(), 0);
+        manager.submit(vec![task(2), task(3)]).unwrap();
+        assert_eq!(manager.queue_depth(), 2);
+    }
 }
@@ -0,0 +1,302 @@
+//! Task Queue - 按优先级出队的推理任务队列
+//!
+//! 会话正在播放的片段优先于预取片段，预取片段优先于导出/预生成等后台任务；
+//! 跳转到很远的位置时新的当前片段无需排在已提交的预取和后台任务之后。
+//! 同优先级内各会话轮流出队（按会话分配虚拟轮次），一个会话的大量预取不会让其他会话等待，
+//! 同一会话内先进先出。提升已排队任务的优先级时重新入队，旧的条目出队时跳过。
+//! 关闭服务时关闭队列：不再入队和出队，剩余任务由任务管理器中的状态持久化。
+//! 队列容量有限，批量入队要么全部入队要么全部拒绝，提交方据此向客户端返回队列已满
+//!
+//! 队列只在进程内共享。多个实例共享 Redis 队列时（`queue.backend = "redis"`），任务管理器
+//! 提交到另一个队列（发件队列）再写入 Redis，Worker 消费的队列只保存本实例从 Redis 取出的任务
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::sync::Mutex;
+use tokio::sync::Notify;
+
+use crate::application::ports::TaskPriority;
+
+/// 队列条目
+#[derive(Debug, PartialEq, Eq)]
+struct QueueEntry {
+    priority: TaskPriority,
+    /// 虚拟轮次：会话在该优先级上的第几个任务（从入队时的当前轮次起算）
+    round: u64,
+    /// 入队序号（同轮次先进先出）
+    seq: u64,
+    task_id: String,
+    session_id: String,
+}
+
+impl Ord for QueueEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.round.cmp(&self.round))
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+impl PartialOrd for QueueEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Default)]
+struct QueueState {
+    heap: BinaryHeap<QueueEntry>,
+    /// task_id -> 最新入队的优先级（与之不同的条目已过时）
+    queued: HashMap<String, TaskPriority>,
+    /// 各优先级最近出队的轮次
+    current_round: HashMap<TaskPriority, u64>,
+    /// (会话, 优先级) -> 该会话排队中最后一个任务的轮次
+    session_rounds: HashMap<(String, TaskPriority), u64>,
+    next_seq: u64,
+    closed: bool,
+}
+
+/// 批量入队被拒绝的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushRejection {
+    /// 剩余容量不足以容纳整批任务
+    Full,
+    /// 队列已关闭（服务正在关闭）
+    Closed,
+}
+
+/// 推理任务队列（多生产者、单消费者）
+pub struct TaskQueue {
+    state: Mutex<QueueState>,
+    notify: Notify,
+    capacity: usize,
+}
+
+impl TaskQueue {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            state: Mutex::new(QueueState::default()),
+            notify: Notify::new(),
+            capacity,
+        }
+    }
+
+    /// 入队；已在队列中时按新的优先级重新排队，队列已满或已关闭时返回 false
+    pub fn push(&self, task_id: &str, session_id: &str, priority: TaskPriority) -> bool {
+        self.push_all(&[(task_id, session_id, priority)]).is_ok()
+    }
+
+    /// 批量入队（task_id, session_id, 优先级）：剩余容量不足或队列已关闭时整批拒绝
+    pub fn push_all(&self, entries: &[(&str, &str, TaskPriority)]) -> Result<(), PushRejection> {
+        let mut state = self.state.lock().unwrap();
+        if state.closed {
+            return Err(PushRejection::Closed);
+        }
+        let added = entries
+            .iter()
+            .filter(|(task_id, _, _)| !state.queued.contains_key(*task_id))
+            .count();
+        if state.queued.len() + added > self.capacity {
+            return Err(PushRejection::Full);
+        }
+
+        for &(task_id, session_id, priority) in entries {
+            Self::push_entry(&mut state, task_id, session_id, priority);
+        }
+        drop(state);
+        self.notify.notify_one();
+        Ok(())
+    }
+
+    fn push_entry(state: &mut QueueState, task_id: &str, session_id: &str, priority: TaskPriority) {
+        let current_round = state.current_round.get(&priority).copied().unwrap_or(0);
+        let key = (session_id.to_string(), priority);
+        let round = state
+            .session_rounds
+            .get(&key)
+            .copied()
+            .unwrap_or(0)
+            .max(current_round)
+            + 1;
+        state.session_rounds.insert(key, round);
+
+        let seq = state.next_seq;
+        state.next_seq += 1;
+        state.queued.insert(task_id.to_string(), priority);
+        state.heap.push(QueueEntry {
+            priority,
+            round,
+            seq,
+            task_id: task_id.to_string(),
+            session_id: session_id.to_string(),
+        });
+    }
+
+    /// 取出优先级最高的任务，队列为空或已关闭时返回 None
+    pub fn try_pop(&self) -> Option<String> {
+        let mut state = self.state.lock().unwrap();
+        if state.closed {
+            return None;
+        }
+        while let Some(entry) = state.heap.pop() {
+            state.current_round.insert(entry.priority, entry.round);
+            // 会话在该优先级上已没有更靠后的任务
+            let key = (entry.session_id, entry.priority);
+            if state.session_rounds.get(&key) == Some(&entry.round) {
+                state.session_rounds.remove(&key);
+            }
+            if state.queued.get(&entry.task_id) == Some(&entry.priority) {
+                state.queued.remove(&entry.task_id);
+                return Some(entry.task_id);
+            }
+        }
+        None
+    }
+
+    /// 等待并取出优先级最高的任务，队列关闭后返回 None
+    pub async fn pop(&self) -> Option<String> {
+        loop {
+            if let Some(task_id) = self.try_pop() {
+                return Some(task_id);
+            }
+            if self.is_closed() {
+                return None;
+            }
+            self.notify.notified().await;
+        }
+    }
+
+    /// 移出排队中的任务（任务取消后），不再占用容量；返回是否在队列中
+    ///
+    /// 堆中的条目出队时跳过
+    pub fn remove(&self, task_id: &str) -> bool {
+        self.state.lock().unwrap().queued.remove(task_id).is_some()
+    }
+
+    /// 关闭队列：之后的入队被拒绝，等待中的出队返回 None
+    pub fn close(&self) {
+        self.state.lock().unwrap().closed = true;
+        self.notify.notify_one();
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.state.lock().unwrap().closed
+    }
+
+    /// 按出队顺序列出排在最前的任务（之后没有新任务入队时）
+    pub fn peek(&self, limit: usize) -> Vec<String> {
+        let state = self.state.lock().unwrap();
+        let mut entries: Vec<&QueueEntry> = state
+            .heap
+            .iter()
+            .filter(|entry| state.queued.get(&entry.task_id) == Some(&entry.priority))
+            .collect();
+        entries.sort_unstable_by(|a, b| b.cmp(a));
+        entries
+            .into_iter()
+            .take(limit)
+            .map(|entry| entry.task_id.clone())
+            .collect()
+    }
+
+    /// 队列容量
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// 排队中的任务数
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().queued.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_priority_order() {
+        let queue = TaskQueue::new(10);
+        queue.push("export-1", "s1", TaskPriority::Background);
+        queue.push("prefetch-1", "s1", TaskPriority::Prefetch);
+        queue.push("prefetch-2", "s1", TaskPriority::Prefetch);
+        queue.push("current", "s1", TaskPriority::Current);
+        // 已排队的预取任务成为当前片段：提升后只出队一次
+        queue.push("prefetch-2", "s1", TaskPriority::Current);
+        assert_eq!(queue.len(), 4);
+
+        assert_eq!(queue.peek(2), ["current", "prefetch-2"]);
+        let order: Vec<String> = std::iter::from_fn(|| queue.try_pop()).collect();
+        assert_eq!(order, ["current", "prefetch-2", "prefetch-1", "export-1"]);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_sessions_take_turns() {
+        let queue = TaskQueue::new(20);
+        for i in 0..4 {
+            queue.push(&format!("a{}", i), "a", TaskPriority::Prefetch);
+        }
+        assert_eq!(queue.try_pop().as_deref(), Some("a0"));
+
+        // 后到的会话不必等待前一个会话的全部预取
+        queue.push("b0", "b", TaskPriority::Prefetch);
+        queue.push("b1", "b", TaskPriority::Prefetch);
+        let order: Vec<String> = std::iter::from_fn(|| queue.try_pop()).collect();
+        assert_eq!(order, ["a1", "b0", "a2", "b1", "a3"]);
+    }
+
+    #[test]
+    fn test_capacity() {
+        let queue = TaskQueue::new(1);
+        assert!(queue.push("a", "s1", TaskPriority::Prefetch));
+        assert!(!queue.push("b", "s1", TaskPriority::Current));
+        assert!(queue.push("a", "s1", TaskPriority::Current));
+        assert_eq!(queue.try_pop().as_deref(), Some("a"));
+        assert!(queue.push("b", "s1", TaskPriority::Current));
+
+        // 批量入队整批拒绝，不会只入队一部分
+        let queue = TaskQueue::new(2);
+        assert!(queue.push("a", "s1", TaskPriority::Prefetch));
+        let batch = [("b", "s1", TaskPriority::Prefetch), ("c", "s1", TaskPriority::Prefetch)];
+        assert_eq!(queue.push_all(&batch), Err(PushRejection::Full));
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue.push_all(&batch[..1]), Ok(()));
+    }
+
+    #[test]
+    fn test_remove() {
+        let queue = TaskQueue::new(2);
+        assert!(queue.push("a", "s1", TaskPriority::Prefetch));
+        assert!(queue.push("b", "s1", TaskPriority::Prefetch));
+        assert!(queue.remove("a"));
+        assert!(!queue.remove("a"));
+
+        // 移出的任务不占用容量，也不再出队
+        assert_eq!(queue.len(), 1);
+        assert!(queue.push("c", "s1", TaskPriority::Prefetch));
+        assert_eq!(queue.peek(3), ["b", "c"]);
+        let order: Vec<String> = std::iter::from_fn(|| queue.try_pop()).collect();
+        assert_eq!(order, ["b", "c"]);
+    }
+
+    #[tokio::test]
+    async fn test_close() {
+        let queue = std::sync::Arc::new(TaskQueue::new(10));
+        let consumer = tokio::spawn({
+            let queue = queue.clone();
+            async move { queue.pop().await }
+        });
+        tokio::task::yield_now().await;
+        queue.close();
+        assert_eq!(consumer.await.unwrap(), None);
+
+        assert!(!queue.push("a", "s1", TaskPriority::Current));
+        assert!(queue.try_pop().is_none());
+    }
+}
@@ -7,9 +7,11 @@ pub mod events;
 pub mod http;
 pub mod memory;
 pub mod persistence;
+pub mod presets;
 pub mod worker;
 
 pub use events::EventPublisher;
 pub use memory::{InMemorySessionManager, InMemoryTaskManager};
 pub use persistence::sled::SledAudioCache;
-pub use worker::{InferWorker, InferWorkerConfig};
+pub use presets::VoicePresetSeeder;
+pub use worker::{InferWorker, InferWorkerConfig, TtsHealthMonitor};
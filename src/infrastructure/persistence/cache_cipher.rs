@@ -0,0 +1,155 @@
+//! Cache Cipher - 音频缓存静态加密
+//!
+//! 以 AES-256-GCM 加密写入磁盘的缓存音频（缓存放在共享盘或云盘上时使用）。
+//! 加密数据格式：`RVE1` + 12 字节随机 nonce + 密文（含认证标签）；
+//! 不以该魔数开头的数据视为未加密，启用加密前写入的条目仍可读取
+
+use std::fmt;
+use std::path::Path;
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::Engine;
+use bytes::Bytes;
+
+use crate::application::ports::CacheError;
+
+/// 加密数据魔数
+const CIPHER_MAGIC: &[u8; 4] = b"RVE1";
+
+/// AES-GCM nonce 长度
+const NONCE_LEN: usize = 12;
+
+/// 密钥长度（AES-256）
+pub const CACHE_KEY_LEN: usize = 32;
+
+fn encryption_error(e: impl fmt::Display) -> CacheError {
+    CacheError::EncryptionError(e.to_string())
+}
+
+/// 缓存音频加密器
+pub struct CacheCipher {
+    cipher: Aes256Gcm,
+}
+
+impl fmt::Debug for CacheCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CacheCipher").finish_non_exhaustive()
+    }
+}
+
+impl CacheCipher {
+    /// 使用 32 字节密钥创建
+    pub fn new(key: &[u8]) -> Result<Self, CacheError> {
+        if key.len() != CACHE_KEY_LEN {
+            return Err(CacheError::EncryptionError(format!(
+                "Key must be {} bytes, got {}",
+                CACHE_KEY_LEN,
+                key.len()
+            )));
+        }
+        let cipher = Aes256Gcm::new_from_slice(key).map_err(encryption_error)?;
+        Ok(Self { cipher })
+    }
+
+    /// 从配置加载密钥，均未配置时返回 None
+    ///
+    /// `key` 为 base64 编码的密钥；`key_file` 为密钥文件，内容为 32 字节原始密钥或其 base64 编码
+    pub fn load(key: Option<&str>, key_file: Option<&Path>) -> Result<Option<Self>, CacheError> {
+        let key = match (key, key_file) {
+            (Some(key), _) => decode_key(key.as_bytes())?,
+            (None, Some(path)) => {
+                let content = std::fs::read(path)
+                    .map_err(|e| CacheError::IoError(format!("{}: {}", path.display(), e)))?;
+                if content.len() == CACHE_KEY_LEN {
+                    content
+                } else {
+                    decode_key(&content)?
+                }
+            }
+            (None, None) => return Ok(None),
+        };
+        Self::new(&key).map(Some)
+    }
+
+    /// 加密数据（每次使用随机 nonce）
+    pub fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, CacheError> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self.cipher.encrypt(&nonce, data).map_err(encryption_error)?;
+
+        let mut encrypted = Vec::with_capacity(CIPHER_MAGIC.len() + NONCE_LEN + ciphertext.len());
+        encrypted.extend_from_slice(CIPHER_MAGIC);
+        encrypted.extend_from_slice(&nonce);
+        encrypted.extend_from_slice(&ciphertext);
+        Ok(encrypted)
+    }
+
+    /// 解密数据（数据被篡改或密钥不符时失败）
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, CacheError> {
+        let body = &data[CIPHER_MAGIC.len()..];
+        if body.len() < NONCE_LEN {
+            return Err(CacheError::EncryptionError("Encrypted data truncated".to_string()));
+        }
+        let (nonce, ciphertext) = body.split_at(NONCE_LEN);
+        self.cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| CacheError::EncryptionError("Failed to decrypt cached audio (wrong key?)".to_string()))
+    }
+}
+
+fn decode_key(encoded: &[u8]) -> Result<Vec<u8>, CacheError> {
+    base64::engine::general_purpose::STANDARD
+        .decode(encoded.trim_ascii())
+        .map_err(encryption_error)
+}
+
+/// 还原存储的音频数据：加密的数据解密，未加密的原样返回（不复制）
+pub fn decrypt_audio(cipher: Option<&CacheCipher>, data: Bytes) -> Result<Bytes, CacheError> {
+    if !data.starts_with(CIPHER_MAGIC) {
+        return Ok(data);
+    }
+    match cipher {
+        Some(cipher) => cipher.decrypt(&data).map(Bytes::from),
+        None => Err(CacheError::EncryptionError(
+            "Cached audio is encrypted but no key is configured".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_roundtrip() {
+        let cipher = CacheCipher::new(&[7; CACHE_KEY_LEN]).unwrap();
+        let encrypted = cipher.encrypt(b"RIFF audio").unwrap();
+        assert!(!encrypted.windows(5).any(|w| w == b"audio"));
+
+        let decrypted = decrypt_audio(Some(&cipher), Bytes::from(encrypted.clone())).unwrap();
+        assert_eq!(decrypted, Bytes::from_static(b"RIFF audio"));
+
+        // 未加密的数据原样返回，加密的数据缺少或用错密钥时失败
+        let plain = decrypt_audio(Some(&cipher), Bytes::from_static(b"RIFF")).unwrap();
+        assert_eq!(plain, Bytes::from_static(b"RIFF"));
+        assert!(decrypt_audio(None, Bytes::from(encrypted.clone())).is_err());
+        let other = CacheCipher::new(&[8; CACHE_KEY_LEN]).unwrap();
+        assert!(decrypt_audio(Some(&other), Bytes::from(encrypted)).is_err());
+    }
+
+    #[test]
+    fn test_load_key() {
+        let encoded = base64::engine::general_purpose::STANDARD.encode([1u8; CACHE_KEY_LEN]);
+        assert!(CacheCipher::load(None, None).unwrap().is_none());
+        assert!(CacheCipher::load(Some(&encoded), None).unwrap().is_some());
+        assert!(CacheCipher::load(Some("c2hvcnQ="), None).is_err());
+
+        let dir = tempfile::tempdir().unwrap();
+        let raw = dir.path().join("raw.key");
+        std::fs::write(&raw, [2u8; CACHE_KEY_LEN]).unwrap();
+        assert!(CacheCipher::load(None, Some(&raw)).unwrap().is_some());
+        let text = dir.path().join("text.key");
+        std::fs::write(&text, format!("{}\n", encoded)).unwrap();
+        assert!(CacheCipher::load(None, Some(&text)).unwrap().is_some());
+    }
+}
@@ -0,0 +1,852 @@
+//! File-based Audio Cache Implementation
+//!
+//! 每条音频存为独立文件，索引（元数据、访问时间、片段引用、波形峰值）存于同目录的 SQLite：
+//!
+//! ```text
+//! {root_dir}/
+//!   index.db
+//!   3f/3f2a..._{voice_id}.wav
+//! ```
+//!
+//! 目录可直接备份、rsync 增量同步，文件可直接试听排查（启用加密时除外）；淘汰与过期语义与 SledAudioCache 相同
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::Utc;
+use sqlx::FromRow;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use uuid::Uuid;
+
+use crate::application::ports::{
+    cache_key_version, AudioCachePort, AudioFormat, CacheEntry, CacheError, CacheMetadata, CacheStats, NovelCacheUsage,
+    SegmentReference, CACHE_KEY_VERSION,
+};
+use crate::infrastructure::memory::{CachePins, NovelQuotas};
+use crate::infrastructure::persistence::{decrypt_audio, CacheCipher};
+use crate::infrastructure::persistence::sqlite::{create_pool, DatabaseConfig, DbPool};
+
+/// 文件缓存配置
+#[derive(Debug, Clone)]
+pub struct FileCacheConfig {
+    /// 缓存根目录（音频文件与 index.db）
+    pub root_dir: PathBuf,
+    /// 最大缓存大小（字节）
+    pub max_size_bytes: u64,
+    /// 条目自最后访问起的保留时长（秒），None 表示只按大小淘汰
+    pub ttl_secs: Option<u64>,
+    /// 单部小说的缓存配额（条目计入写入它的小说）
+    pub quotas: Arc<NovelQuotas>,
+    /// 音频文件加密器，None 表示不加密（已加密的文件需配置密钥才能读取）
+    pub cipher: Option<Arc<CacheCipher>>,
+}
+
+/// 没有索引的文件在最后修改后保留的时长，避免删除正在写入的文件
+const ORPHAN_FILE_GRACE: Duration = Duration::from_secs(600);
+
+fn db_error(e: sqlx::Error) -> CacheError {
+    CacheError::DatabaseError(e.to_string())
+}
+
+fn io_error(e: std::io::Error) -> CacheError {
+    CacheError::IoError(e.to_string())
+}
+
+/// 缓存 key 对应的相对路径（按内容哈希前两位分目录，`:` 替换为 `_`）
+fn relative_path(cache_key: &str, format: AudioFormat) -> PathBuf {
+    let shard: String = cache_key.chars().take(2).collect();
+    PathBuf::from(shard).join(format!("{}.{}", cache_key.replace(':', "_"), format))
+}
+
+#[derive(FromRow)]
+struct CacheEntryRow {
+    cache_key: String,
+    file_path: String,
+    size_bytes: i64,
+    duration_ms: i64,
+    content_hash: String,
+    novel_id: String,
+    segment_index: i64,
+    voice_id: String,
+    sample_rate: Option<i64>,
+    format: String,
+    last_accessed: i64,
+    created_at: i64,
+}
+
+impl CacheEntryRow {
+    fn metadata(&self) -> Result<CacheMetadata, CacheError> {
+        let parse_uuid = |s: &str| {
+            Uuid::parse_str(s).map_err(|e| CacheError::SerializationError(e.to_string()))
+        };
+        Ok(CacheMetadata {
+            novel_id: parse_uuid(&self.novel_id)?,
+            segment_index: self.segment_index as u32,
+            voice_id: parse_uuid(&self.voice_id)?,
+            content_hash: self.content_hash.clone(),
+            duration_ms: self.duration_ms as u64,
+            sample_rate: self.sample_rate.map(|rate| rate as u32),
+            format: self
+                .format
+                .parse()
+                .map_err(|e: crate::application::ports::TranscodeError| {
+                    CacheError::SerializationError(e.to_string())
+                })?,
+        })
+    }
+}
+
+const ENTRY_COLUMNS: &str = "cache_key, file_path, size_bytes, duration_ms, content_hash, novel_id, \
+     segment_index, voice_id, sample_rate, format, last_accessed, created_at";
+
+/// 文件音频缓存
+pub struct FileAudioCache {
+    root_dir: PathBuf,
+    pool: DbPool,
+    max_size_bytes: u64,
+    ttl_secs: Option<u64>,
+    current_size: AtomicU64,
+    hit_count: AtomicU64,
+    miss_count: AtomicU64,
+    eviction_count: AtomicU64,
+    expired_count: AtomicU64,
+    pins: CachePins,
+    quotas: Arc<NovelQuotas>,
+    cipher: Option<Arc<CacheCipher>>,
+}
+
+impl FileAudioCache {
+    /// 打开（或创建）文件缓存
+    pub async fn new(config: &FileCacheConfig) -> Result<Self, CacheError> {
+        tokio::fs::create_dir_all(&config.root_dir).await.map_err(io_error)?;
+        let pool = create_pool(&DatabaseConfig::new(config.root_dir.join("index.db")))
+            .await
+            .map_err(db_error)?;
+        Self::create_schema(&pool).await.map_err(db_error)?;
+
+        let (current_size,): (i64,) = sqlx::query_as("SELECT COALESCE(SUM(size_bytes), 0) FROM cache_entries")
+            .fetch_one(&pool)
+            .await
+            .map_err(db_error)?;
+
+        tracing::info!(
+            root_dir = %config.root_dir.display(),
+            max_size_bytes = config.max_size_bytes,
+            current_size = current_size,
+            ttl_secs = ?config.ttl_secs,
+            encrypt = config.cipher.is_some(),
+            "FileAudioCache initialized"
+        );
+
+        Ok(Self {
+            root_dir: config.root_dir.clone(),
+            pool,
+            max_size_bytes: config.max_size_bytes,
+            ttl_secs: config.ttl_secs,
+            current_size: AtomicU64::new(current_size as u64),
+            hit_count: AtomicU64::new(0),
+            miss_count: AtomicU64::new(0),
+            eviction_count: AtomicU64::new(0),
+            expired_count: AtomicU64::new(0),
+            pins: CachePins::new(),
+            quotas: config.quotas.clone(),
+            cipher: config.cipher.clone(),
+        })
+    }
+
+    /// 使用指定目录创建（便捷方法）
+    pub async fn open<P: AsRef<Path>>(root_dir: P, max_size_bytes: u64) -> Result<Self, CacheError> {
+        let config = FileCacheConfig {
+            root_dir: root_dir.as_ref().to_path_buf(),
+            max_size_bytes,
+            ttl_secs: None,
+            quotas: Arc::new(NovelQuotas::default()),
+            cipher: None,
+        };
+        Self::new(&config).await
+    }
+
+    async fn create_schema(pool: &DbPool) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS cache_entries (
+                cache_key TEXT PRIMARY KEY,
+                file_path TEXT NOT NULL,
+                size_bytes INTEGER NOT NULL,
+                duration_ms INTEGER NOT NULL,
+                content_hash TEXT NOT NULL,
+                novel_id TEXT NOT NULL,
+                segment_index INTEGER NOT NULL,
+                voice_id TEXT NOT NULL,
+                sample_rate INTEGER,
+                format TEXT NOT NULL,
+                last_accessed INTEGER NOT NULL,
+                created_at INTEGER NOT NULL
+            )
+            "#,
+        )
+        .execute(pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS cache_peaks (
+                cache_key TEXT PRIMARY KEY,
+                peaks BLOB NOT NULL
+            )
+            "#,
+        )
+        .execute(pool)
+        .await?;
+
+        // 片段对条目的引用（内容相同的片段共享同一条目）
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS cache_refs (
+                novel_id TEXT NOT NULL,
+                segment_index INTEGER NOT NULL,
+                voice_id TEXT NOT NULL,
+                cache_key TEXT NOT NULL,
+                PRIMARY KEY (novel_id, segment_index, voice_id)
+            )
+            "#,
+        )
+        .execute(pool)
+        .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_cache_refs_cache_key ON cache_refs(cache_key)")
+            .execute(pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_cache_entries_voice_id ON cache_entries(voice_id)")
+            .execute(pool)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_cache_entries_novel_id ON cache_entries(novel_id)")
+            .execute(pool)
+            .await?;
+
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_cache_entries_last_accessed ON cache_entries(last_accessed)",
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_row(&self, cache_key: &str) -> Result<Option<CacheEntryRow>, CacheError> {
+        sqlx::query_as(&format!("SELECT {} FROM cache_entries WHERE cache_key = ?", ENTRY_COLUMNS))
+            .bind(cache_key)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(db_error)
+    }
+
+    /// 删除条目的索引、引用、波形峰值和音频文件
+    async fn remove_row(&self, row: &CacheEntryRow) -> Result<(), CacheError> {
+        self.remove_audio(row).await?;
+        for table in ["cache_refs", "cache_peaks"] {
+            sqlx::query(&format!("DELETE FROM {} WHERE cache_key = ?", table))
+                .bind(&row.cache_key)
+                .execute(&self.pool)
+                .await
+                .map_err(db_error)?;
+        }
+        Ok(())
+    }
+
+    /// 只删除条目的索引和音频文件（覆盖写入时保留引用和波形峰值）
+    async fn remove_audio(&self, row: &CacheEntryRow) -> Result<(), CacheError> {
+        let result = sqlx::query("DELETE FROM cache_entries WHERE cache_key = ?")
+            .bind(&row.cache_key)
+            .execute(&self.pool)
+            .await
+            .map_err(db_error)?;
+
+        // 并发删除同一条目时只扣减一次
+        if result.rows_affected() > 0 {
+            self.current_size.fetch_sub(row.size_bytes as u64, Ordering::Relaxed);
+        }
+        if let Err(e) = tokio::fs::remove_file(self.root_dir.join(&row.file_path)).await {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                tracing::warn!(path = %row.file_path, error = %e, "Failed to remove cached audio file");
+            }
+        }
+        Ok(())
+    }
+
+    /// 记录片段对条目的引用（片段原先引用的条目被替换）
+    async fn add_reference(
+        &self,
+        cache_key: &str,
+        novel_id: Uuid,
+        segment_index: u32,
+        voice_id: Uuid,
+    ) -> Result<(), CacheError> {
+        sqlx::query(
+            "INSERT OR REPLACE INTO cache_refs (novel_id, segment_index, voice_id, cache_key) \
+             VALUES (?, ?, ?, ?)",
+        )
+        .bind(novel_id.to_string())
+        .bind(segment_index as i64)
+        .bind(voice_id.to_string())
+        .bind(cache_key)
+        .execute(&self.pool)
+        .await
+        .map_err(db_error)?;
+        Ok(())
+    }
+
+    /// 删除满足条件（SQL WHERE 子句）的所有条目，返回删除数
+    async fn remove_where(&self, condition: &str, value: &str) -> Result<usize, CacheError> {
+        let rows: Vec<CacheEntryRow> =
+            sqlx::query_as(&format!("SELECT {} FROM cache_entries WHERE {}", ENTRY_COLUMNS, condition))
+                .bind(value)
+                .fetch_all(&self.pool)
+                .await
+                .map_err(db_error)?;
+
+        for row in &rows {
+            self.remove_row(row).await?;
+        }
+        Ok(rows.len())
+    }
+
+    /// 缓存目录下的所有音频文件（分片目录中的文件，含未完成写入的 `.partial`）
+    async fn audio_files(&self) -> Result<Vec<PathBuf>, CacheError> {
+        let mut files = Vec::new();
+        let mut shards = tokio::fs::read_dir(&self.root_dir).await.map_err(io_error)?;
+        while let Some(shard) = shards.next_entry().await.map_err(io_error)? {
+            if !shard.file_type().await.map_err(io_error)?.is_dir() {
+                continue;
+            }
+            let mut entries = tokio::fs::read_dir(shard.path()).await.map_err(io_error)?;
+            while let Some(entry) = entries.next_entry().await.map_err(io_error)? {
+                files.push(entry.path());
+            }
+        }
+        Ok(files)
+    }
+
+    /// 淘汰最久未访问且未被固定的条目（指定 `novel_id` 时只淘汰该小说写入的条目），
+    /// 没有可淘汰的条目时返回 false
+    async fn evict_lru(&self, novel_id: Option<Uuid>) -> Result<bool, CacheError> {
+        // 最久未访问的若干条中至少有一条未被固定（如果存在）
+        let filter = if novel_id.is_some() { "WHERE novel_id = ?" } else { "" };
+        let sql = format!(
+            "SELECT {} FROM cache_entries {} ORDER BY last_accessed LIMIT ?",
+            ENTRY_COLUMNS, filter
+        );
+        let mut query = sqlx::query_as(&sql);
+        if let Some(novel_id) = novel_id {
+            query = query.bind(novel_id.to_string());
+        }
+        let oldest: Vec<CacheEntryRow> = query
+            .bind(self.pins.pinned_count() as i64 + 1)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(db_error)?;
+
+        let Some(row) = oldest.into_iter().find(|row| !self.pins.contains(&row.cache_key)) else {
+            return Ok(false);
+        };
+        self.remove_row(&row).await?;
+        self.eviction_count.fetch_add(1, Ordering::Relaxed);
+        tracing::debug!(key = %row.cache_key, size_bytes = row.size_bytes, "LRU evicted cache entry");
+        Ok(true)
+    }
+
+    /// 小说写入的条目总大小
+    async fn novel_usage(&self, novel_id: Uuid) -> Result<u64, CacheError> {
+        let (used,): (i64,) =
+            sqlx::query_as("SELECT COALESCE(SUM(size_bytes), 0) FROM cache_entries WHERE novel_id = ?")
+                .bind(novel_id.to_string())
+                .fetch_one(&self.pool)
+                .await
+                .map_err(db_error)?;
+        Ok(used as u64)
+    }
+
+    /// 写入前按小说配额淘汰该小说自己的条目（其余条目均被固定时暂时超出配额）
+    async fn enforce_quota(&self, novel_id: Uuid, size: u64) -> Result<(), CacheError> {
+        let Some(quota) = self.quotas.limit(novel_id) else {
+            return Ok(());
+        };
+        let used = self.novel_usage(novel_id).await?;
+        if used + size <= quota {
+            return Ok(());
+        }
+
+        self.quotas.reached(novel_id, used, quota);
+        while self.novel_usage(novel_id).await? + size > quota {
+            if !self.evict_lru(Some(novel_id)).await? {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AudioCachePort for FileAudioCache {
+    async fn put(
+        &self,
+        cache_key: &str,
+        audio_data: Vec<u8>,
+        metadata: CacheMetadata,
+    ) -> Result<(), CacheError> {
+        // 覆盖相同内容的已有条目时先删除旧文件（保留引用）
+        if let Some(row) = self.find_row(cache_key).await? {
+            self.remove_audio(&row).await?;
+        }
+
+        let audio_data = match &self.cipher {
+            Some(cipher) => cipher.encrypt(&audio_data)?,
+            None => audio_data,
+        };
+        let size = audio_data.len() as u64;
+        self.enforce_quota(metadata.novel_id, size).await?;
+        while self.current_size.load(Ordering::Relaxed) + size > self.max_size_bytes {
+            if !self.evict_lru(None).await? {
+                break;
+            }
+        }
+
+        let file_path = relative_path(cache_key, metadata.format);
+        let absolute = self.root_dir.join(&file_path);
+        if let Some(parent) = absolute.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(io_error)?;
+        }
+        // 先写临时文件再改名，避免读到写了一半的文件
+        let partial = absolute.with_extension("partial");
+        tokio::fs::write(&partial, &audio_data).await.map_err(io_error)?;
+        tokio::fs::rename(&partial, &absolute).await.map_err(io_error)?;
+
+        let now = Utc::now().timestamp();
+        sqlx::query(&format!(
+            "INSERT OR REPLACE INTO cache_entries ({}) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            ENTRY_COLUMNS
+        ))
+        .bind(cache_key)
+        .bind(file_path.to_string_lossy().as_ref())
+        .bind(size as i64)
+        .bind(metadata.duration_ms as i64)
+        .bind(&metadata.content_hash)
+        .bind(metadata.novel_id.to_string())
+        .bind(metadata.segment_index as i64)
+        .bind(metadata.voice_id.to_string())
+        .bind(metadata.sample_rate.map(|rate| rate as i64))
+        .bind(metadata.format.to_string())
+        .bind(now)
+        .bind(now)
+        .execute(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        self.current_size.fetch_add(size, Ordering::Relaxed);
+        self.add_reference(cache_key, metadata.novel_id, metadata.segment_index, metadata.voice_id)
+            .await?;
+
+        tracing::debug!(
+            cache_key = %cache_key,
+            size_bytes = size,
+            "Audio cached"
+        );
+
+        Ok(())
+    }
+
+    async fn get(&self, cache_key: &str) -> Result<Option<Bytes>, CacheError> {
+        Ok(self.get_entry(cache_key).await?.map(|entry| entry.audio_data))
+    }
+
+    async fn get_entry(&self, cache_key: &str) -> Result<Option<CacheEntry>, CacheError> {
+        let Some(row) = self.find_row(cache_key).await? else {
+            self.miss_count.fetch_add(1, Ordering::Relaxed);
+            return Ok(None);
+        };
+
+        let audio_data = match tokio::fs::read(self.root_dir.join(&row.file_path)).await {
+            Ok(data) => data,
+            // 文件被手动删除时清理索引，视为未命中
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                tracing::warn!(cache_key = %cache_key, path = %row.file_path, "Cached audio file missing");
+                self.remove_row(&row).await?;
+                self.miss_count.fetch_add(1, Ordering::Relaxed);
+                return Ok(None);
+            }
+            Err(e) => return Err(io_error(e)),
+        };
+
+        // 更新 last_accessed (LRU touch)
+        let now = Utc::now().timestamp();
+        sqlx::query("UPDATE cache_entries SET last_accessed = ? WHERE cache_key = ?")
+            .bind(now)
+            .bind(cache_key)
+            .execute(&self.pool)
+            .await
+            .map_err(db_error)?;
+
+        self.hit_count.fetch_add(1, Ordering::Relaxed);
+        Ok(Some(CacheEntry {
+            metadata: row.metadata()?,
+            audio_data: decrypt_audio(self.cipher.as_deref(), Bytes::from(audio_data))?,
+            size_bytes: row.size_bytes as u64,
+            last_accessed: now,
+            created_at: row.created_at,
+        }))
+    }
+
+    async fn lookup(
+        &self,
+        novel_id: Uuid,
+        segment_index: u32,
+        voice_id: Uuid,
+    ) -> Result<Option<String>, CacheError> {
+        let row: Option<(String,)> = sqlx::query_as(
+            "SELECT cache_key FROM cache_refs WHERE novel_id = ? AND segment_index = ? AND voice_id = ?",
+        )
+        .bind(novel_id.to_string())
+        .bind(segment_index as i64)
+        .bind(voice_id.to_string())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(db_error)?;
+        Ok(row.map(|(cache_key,)| cache_key))
+    }
+
+    async fn exists(&self, cache_key: &str) -> Result<bool, CacheError> {
+        let row: Option<(i64,)> = sqlx::query_as("SELECT 1 FROM cache_entries WHERE cache_key = ?")
+            .bind(cache_key)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(db_error)?;
+        Ok(row.is_some())
+    }
+
+    async fn link(
+        &self,
+        cache_key: &str,
+        novel_id: Uuid,
+        segment_index: u32,
+        voice_id: Uuid,
+    ) -> Result<bool, CacheError> {
+        if !self.exists(cache_key).await? {
+            return Ok(false);
+        }
+        self.add_reference(cache_key, novel_id, segment_index, voice_id).await?;
+        Ok(true)
+    }
+
+    async fn put_peaks(&self, cache_key: &str, peaks: &[f32]) -> Result<(), CacheError> {
+        let bytes: Vec<u8> = peaks.iter().flat_map(|p| p.to_le_bytes()).collect();
+        sqlx::query("INSERT OR REPLACE INTO cache_peaks (cache_key, peaks) VALUES (?, ?)")
+            .bind(cache_key)
+            .bind(bytes)
+            .execute(&self.pool)
+            .await
+            .map_err(db_error)?;
+        Ok(())
+    }
+
+    async fn get_peaks(&self, cache_key: &str) -> Result<Option<Vec<f32>>, CacheError> {
+        let row: Option<(Vec<u8>,)> = sqlx::query_as("SELECT peaks FROM cache_peaks WHERE cache_key = ?")
+            .bind(cache_key)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(db_error)?;
+        Ok(row.map(|(bytes,)| {
+            bytes
+                .chunks_exact(4)
+                .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect()
+        }))
+    }
+
+    async fn remove(&self, cache_key: &str) -> Result<(), CacheError> {
+        match self.find_row(cache_key).await? {
+            Some(row) => self.remove_row(&row).await,
+            None => {
+                sqlx::query("DELETE FROM cache_peaks WHERE cache_key = ?")
+                    .bind(cache_key)
+                    .execute(&self.pool)
+                    .await
+                    .map_err(db_error)?;
+                Ok(())
+            }
+        }
+    }
+
+    async fn remove_expired(&self) -> Result<usize, CacheError> {
+        let Some(ttl_secs) = self.ttl_secs else {
+            return Ok(0);
+        };
+        let cutoff = Utc::now().timestamp() - ttl_secs as i64;
+        let removed = self
+            .remove_where("last_accessed < CAST(? AS INTEGER)", &cutoff.to_string())
+            .await?;
+        self.expired_count.fetch_add(removed as u64, Ordering::Relaxed);
+        if removed > 0 {
+            tracing::info!(removed, ttl_secs, "Expired cache entries removed");
+        }
+        Ok(removed)
+    }
+
+    async fn remove_by_novel(&self, novel_id: Uuid) -> Result<usize, CacheError> {
+        // 只删除不被其他小说引用的条目，其余条目仅解除该小说的引用
+        self.quotas.reset(novel_id);
+        let novel_id = novel_id.to_string();
+        let removed = self
+            .remove_where(
+                "(novel_id = ?1 OR cache_key IN (SELECT cache_key FROM cache_refs WHERE novel_id = ?1)) \
+                 AND cache_key NOT IN (SELECT cache_key FROM cache_refs WHERE novel_id != ?1)",
+                &novel_id,
+            )
+            .await?;
+        sqlx::query("DELETE FROM cache_refs WHERE novel_id = ?")
+            .bind(&novel_id)
+            .execute(&self.pool)
+            .await
+            .map_err(db_error)?;
+        Ok(removed)
+    }
+
+    async fn remove_by_voice(&self, voice_id: Uuid) -> Result<usize, CacheError> {
+        self.remove_where("voice_id = ?", &voice_id.to_string()).await
+    }
+
+    fn pin(&self, session_id: &str, cache_keys: Vec<String>) {
+        self.pins.set(session_id, cache_keys);
+    }
+
+    fn unpin(&self, session_id: &str) {
+        self.pins.clear(session_id);
+    }
+
+    async fn remove_orphans(&self) -> Result<usize, CacheError> {
+        let mut removed = 0;
+
+        // 引用和波形峰值指向不存在的条目
+        for table in ["cache_refs", "cache_peaks"] {
+            let result = sqlx::query(&format!(
+                "DELETE FROM {} WHERE cache_key NOT IN (SELECT cache_key FROM cache_entries)",
+                table
+            ))
+            .execute(&self.pool)
+            .await
+            .map_err(db_error)?;
+            removed += result.rows_affected() as usize;
+        }
+
+        // 音频文件已丢失的索引
+        let rows: Vec<CacheEntryRow> = sqlx::query_as(&format!("SELECT {} FROM cache_entries", ENTRY_COLUMNS))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(db_error)?;
+        let mut indexed = HashSet::new();
+        for row in rows {
+            if tokio::fs::try_exists(self.root_dir.join(&row.file_path)).await.unwrap_or(true) {
+                indexed.insert(self.root_dir.join(&row.file_path));
+            } else {
+                self.remove_row(&row).await?;
+                removed += 1;
+            }
+        }
+
+        // 没有索引的音频文件（跳过最近修改的，可能正在写入）
+        let cutoff = SystemTime::now() - ORPHAN_FILE_GRACE;
+        for path in self.audio_files().await? {
+            if indexed.contains(&path) {
+                continue;
+            }
+            let modified = tokio::fs::metadata(&path).await.and_then(|metadata| metadata.modified());
+            if modified.map_or(true, |modified| modified > cutoff) {
+                continue;
+            }
+            tokio::fs::remove_file(&path).await.map_err(io_error)?;
+            removed += 1;
+        }
+
+        if removed > 0 {
+            tracing::info!(removed, "Orphaned cache data removed");
+        }
+        Ok(removed)
+    }
+
+    async fn flush(&self) -> Result<(), CacheError> {
+        // 音频文件写入后改名即完成，索引由 SQLite 逐条提交
+        Ok(())
+    }
+
+    async fn remove_outdated(&self) -> Result<usize, CacheError> {
+        let rows: Vec<CacheEntryRow> = sqlx::query_as(&format!("SELECT {} FROM cache_entries", ENTRY_COLUMNS))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(db_error)?;
+
+        let mut removed = 0;
+        for row in rows.iter().filter(|row| cache_key_version(&row.cache_key) < CACHE_KEY_VERSION) {
+            self.remove_row(row).await?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    async fn stats(&self) -> CacheStats {
+        let total_entries: i64 = sqlx::query_as::<_, (i64,)>("SELECT COUNT(*) FROM cache_entries")
+            .fetch_one(&self.pool)
+            .await
+            .map(|(count,)| count)
+            .unwrap_or(0);
+
+        CacheStats {
+            total_entries: total_entries as usize,
+            total_size_bytes: self.current_size.load(Ordering::Relaxed),
+            max_size_bytes: self.max_size_bytes,
+            hit_count: self.hit_count.load(Ordering::Relaxed),
+            miss_count: self.miss_count.load(Ordering::Relaxed),
+            eviction_count: self.eviction_count.load(Ordering::Relaxed),
+            expired_count: self.expired_count.load(Ordering::Relaxed),
+            disk_size_bytes: None,
+        }
+    }
+
+    async fn usage_by_novel(&self) -> Result<Vec<NovelCacheUsage>, CacheError> {
+        // 按片段引用统计，多部小说共享的条目分别计入
+        let rows: Vec<(String, i64, i64)> = sqlx::query_as(
+            "SELECT novel_id, COUNT(*), SUM(size_bytes) FROM ( \
+                 SELECT DISTINCT r.novel_id, e.cache_key, e.size_bytes \
+                 FROM cache_refs r JOIN cache_entries e ON e.cache_key = r.cache_key \
+             ) GROUP BY novel_id ORDER BY SUM(size_bytes) DESC",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(novel_id, entries, size_bytes)| {
+                Some(NovelCacheUsage {
+                    novel_id: Uuid::parse_str(&novel_id).ok()?,
+                    entries: entries as usize,
+                    size_bytes: size_bytes as u64,
+                })
+            })
+            .collect())
+    }
+
+    async fn keys(&self) -> Result<Vec<String>, CacheError> {
+        let rows: Vec<(String,)> = sqlx::query_as("SELECT cache_key FROM cache_entries ORDER BY created_at")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(db_error)?;
+        Ok(rows.into_iter().map(|(cache_key,)| cache_key).collect())
+    }
+
+    async fn peek(&self, cache_key: &str) -> Result<Option<CacheEntry>, CacheError> {
+        let Some(row) = self.find_row(cache_key).await? else {
+            return Ok(None);
+        };
+
+        let audio_data = match tokio::fs::read(self.root_dir.join(&row.file_path)).await {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(io_error(e)),
+        };
+        Ok(Some(CacheEntry {
+            metadata: row.metadata()?,
+            audio_data: decrypt_audio(self.cipher.as_deref(), Bytes::from(audio_data))?,
+            size_bytes: row.size_bytes as u64,
+            last_accessed: row.last_accessed,
+            created_at: row.created_at,
+        }))
+    }
+
+    async fn references(&self, cache_key: &str) -> Result<Vec<SegmentReference>, CacheError> {
+        let rows: Vec<(String, i64, String)> = sqlx::query_as(
+            "SELECT novel_id, segment_index, voice_id FROM cache_refs WHERE cache_key = ? \
+             ORDER BY novel_id, segment_index",
+        )
+        .bind(cache_key)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(novel_id, segment_index, voice_id)| {
+                Some(SegmentReference {
+                    novel_id: Uuid::parse_str(&novel_id).ok()?,
+                    segment_index: segment_index as u32,
+                    voice_id: Uuid::parse_str(&voice_id).ok()?,
+                })
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn metadata(novel_id: Uuid, segment_index: u32, voice_id: Uuid) -> CacheMetadata {
+        CacheMetadata {
+            novel_id,
+            segment_index,
+            voice_id,
+            content_hash: "test_hash".to_string(),
+            duration_ms: 1000,
+            sample_rate: Some(22050),
+            format: AudioFormat::Wav,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_file_cache_put_get_evict() {
+        let dir = tempdir().unwrap();
+        let cache = FileAudioCache::open(dir.path(), 8).await.unwrap();
+        let (novel_id, voice_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let key = format!("abcdef:{}", voice_id);
+
+        cache.put(&key, vec![1; 4], metadata(novel_id, 0, voice_id)).await.unwrap();
+        cache.put_peaks(&key, &[0.25, 1.0]).await.unwrap();
+
+        // 音频为独立文件
+        let path = dir.path().join("ab").join(format!("abcdef_{}.wav", voice_id));
+        assert_eq!(std::fs::read(&path).unwrap(), vec![1; 4]);
+
+        let entry = cache.get_entry(&key).await.unwrap().unwrap();
+        assert_eq!(entry.audio_data, vec![1; 4]);
+        assert_eq!(entry.metadata.voice_id, voice_id);
+        assert_eq!(cache.lookup(novel_id, 0, voice_id).await.unwrap(), Some(key.clone()));
+        assert_eq!(cache.get_peaks(&key).await.unwrap(), Some(vec![0.25, 1.0]));
+
+        // 重新打开后索引与大小保留
+        drop(cache);
+        let cache = FileAudioCache::open(dir.path(), 8).await.unwrap();
+        assert_eq!(cache.stats().await.total_size_bytes, 4);
+
+        // 超出容量时淘汰最久未访问的条目（文件与峰值一并删除）
+        cache.put("other", vec![2; 6], metadata(novel_id, 1, voice_id)).await.unwrap();
+        assert!(!cache.exists(&key).await.unwrap());
+        assert!(!path.exists());
+        assert_eq!(cache.get_peaks(&key).await.unwrap(), None);
+        assert_eq!(cache.stats().await.eviction_count, 1);
+
+        // 另一部小说引用相同内容，删除原小说时保留
+        let reuploaded = Uuid::new_v4();
+        assert!(cache.link("other", reuploaded, 0, voice_id).await.unwrap());
+        assert!(!cache.link(&key, reuploaded, 1, voice_id).await.unwrap());
+        assert_eq!(cache.remove_by_novel(novel_id).await.unwrap(), 0);
+        assert_eq!(cache.lookup(novel_id, 1, voice_id).await.unwrap(), None);
+        assert_eq!(cache.lookup(reuploaded, 0, voice_id).await.unwrap(), Some("other".to_string()));
+
+        assert_eq!(cache.remove_by_novel(reuploaded).await.unwrap(), 1);
+        assert_eq!(cache.stats().await.total_size_bytes, 0);
+    }
+}
@@ -0,0 +1,5 @@
+//! File Persistence - 文件系统存储实现
+
+mod audio_cache;
+
+pub use audio_cache::*;
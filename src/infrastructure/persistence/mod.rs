@@ -1,8 +1,12 @@
 //! Persistence Layer - 数据持久化
 //!
-//! SQLite 和 Sled 存储实现
+//! SQLite、Sled 和文件系统存储实现；CacheCipher 为两种音频缓存共用的静态加密
 
+mod cache_cipher;
+pub mod file;
 pub mod sled;
 pub mod sqlite;
 
+pub use self::cache_cipher::{decrypt_audio, CacheCipher, CACHE_KEY_LEN};
+pub use self::file::FileAudioCache;
 pub use self::sled::SledAudioCache;
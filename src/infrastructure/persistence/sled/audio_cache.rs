@@ -1,17 +1,29 @@
 //! Sled-based LRU Audio Cache Implementation
+//!
+//! 存储布局：
+//! - `cache:{cache_key}` - 音频与元数据（cache_key 由内容哈希生成，内容相同的片段共享）
+//! - `mapping:{novel_id}:{segment_index}:{voice_id}` - 片段当前引用的 cache_key
+//! - `refs:{cache_key}|{novel_id}:{segment_index}:{voice_id}` - 反向引用，用于引用计数
+//! - `peaks:{cache_key}` - 波形峰值
+//! - `meta:{counter}` - 命中/未命中/淘汰/过期计数（大端 u64），跨重启累计
 
 use async_trait::async_trait;
+use bytes::Bytes;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
-use sled::Db;
+use sled::{Db, IVec};
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
 use crate::application::ports::{
-    AudioCachePort, CacheError, CacheMetadata, CacheStats,
+    cache_key_version, AudioCachePort, AudioFormat, CacheEntry, CacheError, CacheMetadata, CacheStats, NovelCacheUsage,
+    SegmentReference, CACHE_KEY_VERSION,
 };
+use crate::infrastructure::memory::{CachePins, NovelQuotas};
+use crate::infrastructure::persistence::{decrypt_audio, CacheCipher};
 
 /// Sled 缓存配置
 #[derive(Debug, Clone)]
@@ -20,6 +32,14 @@ pub struct SledCacheConfig {
     pub db_path: String,
     /// 最大缓存大小（字节）
     pub max_size_bytes: u64,
+    /// 是否以 zstd 压缩音频数据
+    pub compress: bool,
+    /// 条目自最后访问起的保留时长（秒），None 表示只按大小淘汰
+    pub ttl_secs: Option<u64>,
+    /// 单部小说的缓存配额
+    pub quotas: Arc<NovelQuotas>,
+    /// 音频加密器，None 表示不加密（已加密的条目需配置密钥才能读取）
+    pub cipher: Option<Arc<CacheCipher>>,
 }
 
 impl Default for SledCacheConfig {
@@ -27,14 +47,74 @@ impl Default for SledCacheConfig {
         Self {
             db_path: "data/cache.sled".to_string(),
             max_size_bytes: 10 * 1024 * 1024 * 1024, // 10GB
+            compress: false,
+            ttl_secs: None,
+            quotas: Arc::new(NovelQuotas::default()),
+            cipher: None,
         }
     }
 }
 
+/// zstd 帧魔数（音频容器均不以此开头，据此区分压缩与未压缩的数据）
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+/// zstd 压缩级别
+const ZSTD_LEVEL: i32 = 3;
+
+/// 压缩音频数据，压缩后不更小时保持原样
+fn compress_audio(data: Vec<u8>) -> Vec<u8> {
+    match zstd::bulk::compress(&data, ZSTD_LEVEL) {
+        Ok(compressed) if compressed.len() < data.len() => compressed,
+        _ => data,
+    }
+}
+
+/// 还原音频数据（未压缩的原样返回，不复制）
+fn decompress_audio(data: Bytes) -> Result<Bytes, CacheError> {
+    if !data.starts_with(&ZSTD_MAGIC) {
+        return Ok(data);
+    }
+    zstd::stream::decode_all(data.as_ref())
+        .map(Bytes::from)
+        .map_err(|e| CacheError::SerializationError(e.to_string()))
+}
+
+/// 持久化的统计计数器键
+const META_HIT_COUNT: &str = "meta:hit_count";
+const META_MISS_COUNT: &str = "meta:miss_count";
+const META_EVICTION_COUNT: &str = "meta:eviction_count";
+const META_EXPIRED_COUNT: &str = "meta:expired_count";
+
+/// bincode 编码中音频数据的起始偏移（`audio_data` 为首个字段，前缀 u64 长度）
+const AUDIO_OFFSET: usize = 8;
+
+/// 引用编码后条目中的音频数据（不复制）
+fn stored_audio(encoded: IVec, len: usize) -> Bytes {
+    Bytes::from_owner(encoded).slice(AUDIO_OFFSET..AUDIO_OFFSET + len)
+}
+
 /// 内部缓存条目
+///
+/// 读取时以 `InternalCacheEntry<&[u8]>` 借用音频数据，避免解码时复制
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct InternalCacheEntry {
-    audio_data: Vec<u8>,
+struct InternalCacheEntry<A = Vec<u8>> {
+    audio_data: A,
+    size_bytes: u64,
+    duration_ms: u64,
+    content_hash: String,
+    novel_id: String,
+    segment_index: u32,
+    voice_id: String,
+    last_accessed: i64,
+    created_at: i64,
+    sample_rate: Option<u32>,
+    format: AudioFormat,
+}
+
+/// 加入 `format` 字段之前的条目布局（音频均为 WAV），启动时原地迁移为当前布局
+#[derive(Deserialize)]
+struct LegacyCacheEntry<A> {
+    audio_data: A,
     size_bytes: u64,
     duration_ms: u64,
     content_hash: String,
@@ -46,13 +126,64 @@ struct InternalCacheEntry {
     sample_rate: Option<u32>,
 }
 
+impl<A> From<LegacyCacheEntry<A>> for InternalCacheEntry<A> {
+    fn from(legacy: LegacyCacheEntry<A>) -> Self {
+        Self {
+            audio_data: legacy.audio_data,
+            size_bytes: legacy.size_bytes,
+            duration_ms: legacy.duration_ms,
+            content_hash: legacy.content_hash,
+            novel_id: legacy.novel_id,
+            segment_index: legacy.segment_index,
+            voice_id: legacy.voice_id,
+            last_accessed: legacy.last_accessed,
+            created_at: legacy.created_at,
+            sample_rate: legacy.sample_rate,
+            format: AudioFormat::Wav,
+        }
+    }
+}
+
+impl<A> InternalCacheEntry<A> {
+    /// 转换为缓存条目，`audio_data` 为还原（解密、解压）后的音频数据
+    fn into_cache_entry(self, audio_data: Bytes) -> Result<CacheEntry, CacheError> {
+        let parse_uuid = |s: &str| {
+            Uuid::parse_str(s).map_err(|e| CacheError::SerializationError(e.to_string()))
+        };
+        Ok(CacheEntry {
+            metadata: CacheMetadata {
+                novel_id: parse_uuid(&self.novel_id)?,
+                segment_index: self.segment_index,
+                voice_id: parse_uuid(&self.voice_id)?,
+                content_hash: self.content_hash,
+                duration_ms: self.duration_ms,
+                sample_rate: self.sample_rate,
+                format: self.format,
+            },
+            audio_data,
+            size_bytes: self.size_bytes,
+            last_accessed: self.last_accessed,
+            created_at: self.created_at,
+        })
+    }
+}
+
 /// Sled 音频缓存
 pub struct SledAudioCache {
     db: Db,
     max_size_bytes: u64,
+    compress: bool,
+    ttl_secs: Option<u64>,
     current_size: AtomicU64,
     hit_count: AtomicU64,
     miss_count: AtomicU64,
+    eviction_count: AtomicU64,
+    expired_count: AtomicU64,
+    pins: CachePins,
+    quotas: Arc<NovelQuotas>,
+    cipher: Option<Arc<CacheCipher>>,
+    /// novel_id -> 该小说写入的条目总大小（配额统计）
+    novel_usage: Mutex<HashMap<String, u64>>,
 }
 
 impl SledAudioCache {
@@ -62,21 +193,38 @@ impl SledAudioCache {
             .map_err(|e| CacheError::DatabaseError(e.to_string()))?;
 
         // 计算当前缓存大小
-        let current_size = Self::calculate_total_size(&db)?;
+        let (current_size, novel_usage) = Self::calculate_total_size(&db)?;
+        let hit_count = Self::load_counter(&db, META_HIT_COUNT)?;
+        let miss_count = Self::load_counter(&db, META_MISS_COUNT)?;
+        let eviction_count = Self::load_counter(&db, META_EVICTION_COUNT)?;
+        let expired_count = Self::load_counter(&db, META_EXPIRED_COUNT)?;
 
         tracing::info!(
             db_path = %config.db_path,
             max_size_bytes = config.max_size_bytes,
             current_size = current_size,
+            compress = config.compress,
+            encrypt = config.cipher.is_some(),
+            ttl_secs = ?config.ttl_secs,
+            hit_count,
+            miss_count,
             "SledAudioCache initialized"
         );
 
         Ok(Self {
             db,
             max_size_bytes: config.max_size_bytes,
+            compress: config.compress,
+            ttl_secs: config.ttl_secs,
             current_size: AtomicU64::new(current_size),
-            hit_count: AtomicU64::new(0),
-            miss_count: AtomicU64::new(0),
+            hit_count: AtomicU64::new(hit_count),
+            miss_count: AtomicU64::new(miss_count),
+            eviction_count: AtomicU64::new(eviction_count),
+            expired_count: AtomicU64::new(expired_count),
+            pins: CachePins::new(),
+            quotas: config.quotas.clone(),
+            cipher: config.cipher.clone(),
+            novel_usage: Mutex::new(novel_usage),
         })
     }
 
@@ -85,6 +233,10 @@ impl SledAudioCache {
         let config = SledCacheConfig {
             db_path: path.as_ref().to_string_lossy().to_string(),
             max_size_bytes,
+            compress: false,
+            ttl_secs: None,
+            quotas: Arc::new(NovelQuotas::default()),
+            cipher: None,
         };
         Self::new(&config)
     }
@@ -93,25 +245,108 @@ impl SledAudioCache {
         Arc::new(self)
     }
 
-    /// 计算数据库中所有条目的总大小
-    fn calculate_total_size(db: &Db) -> Result<u64, CacheError> {
+    /// 还原存储的音频数据（先解密再解压，未加密、未压缩时不复制）
+    fn decode_audio(&self, stored: Bytes) -> Result<Bytes, CacheError> {
+        decompress_audio(decrypt_audio(self.cipher.as_deref(), stored)?)
+    }
+
+    /// 读取持久化的计数器（不存在或格式不符时从 0 开始）
+    fn load_counter(db: &Db, key: &str) -> Result<u64, CacheError> {
+        let value = db
+            .get(key)
+            .map_err(|e| CacheError::DatabaseError(e.to_string()))?;
+        Ok(value
+            .and_then(|value| <[u8; 8]>::try_from(value.as_ref()).ok())
+            .map(u64::from_be_bytes)
+            .unwrap_or(0))
+    }
+
+    /// 累加计数器并写回 sled，使统计跨重启累计
+    fn bump_counter(&self, counter: &AtomicU64, key: &str, n: u64) {
+        counter.fetch_add(n, Ordering::Relaxed);
+        let result = self.db.update_and_fetch(key, |old| {
+            let old = old
+                .and_then(|old| <[u8; 8]>::try_from(old).ok())
+                .map(u64::from_be_bytes)
+                .unwrap_or(0);
+            Some((old + n).to_be_bytes().to_vec())
+        });
+        if let Err(e) = result {
+            tracing::warn!(key, error = %e, "Failed to persist cache counter");
+        }
+    }
+
+    /// 计算数据库中所有条目的总大小及各小说写入的条目大小
+    ///
+    /// 缺少 `format` 字段的旧版本条目按 WAV 原地迁移为当前布局，两种布局都无法解析的
+    /// 条目才删除（之后按未命中重新合成）；没有反向引用的旧版本条目按其映射补建引用
+    fn calculate_total_size(db: &Db) -> Result<(u64, HashMap<String, u64>), CacheError> {
         let mut total = 0u64;
+        let mut novel_usage: HashMap<String, u64> = HashMap::new();
+        let mut stale = Vec::new();
+        let mut migrated = 0usize;
         for item in db.scan_prefix("cache:") {
-            let (_, value) = item.map_err(|e| CacheError::DatabaseError(e.to_string()))?;
-            if let Ok(entry) = bincode::deserialize::<InternalCacheEntry>(&value) {
-                total += entry.size_bytes;
+            let (key, value) = item.map_err(|e| CacheError::DatabaseError(e.to_string()))?;
+            let decoded = match bincode::deserialize::<InternalCacheEntry<&[u8]>>(&value) {
+                Ok(entry) => Some(entry),
+                Err(_) => match bincode::deserialize::<LegacyCacheEntry<&[u8]>>(&value) {
+                    Ok(legacy) => {
+                        let entry = InternalCacheEntry::from(legacy);
+                        let encoded = bincode::serialize(&entry)
+                            .map_err(|e| CacheError::SerializationError(e.to_string()))?;
+                        db.insert(&key, encoded)
+                            .map_err(|e| CacheError::DatabaseError(e.to_string()))?;
+                        migrated += 1;
+                        Some(entry)
+                    }
+                    Err(_) => None,
+                },
+            };
+            match decoded {
+                Some(entry) => {
+                    total += entry.size_bytes;
+                    *novel_usage.entry(entry.novel_id.to_string()).or_default() += entry.size_bytes;
+                    let cache_key = &key[b"cache:".len()..];
+                    let segment = format!("{}:{}:{}", entry.novel_id, entry.segment_index, entry.voice_id);
+                    let mapped = db
+                        .get(format!("mapping:{}", segment))
+                        .map_err(|e| CacheError::DatabaseError(e.to_string()))?;
+                    if mapped.as_deref() == Some(cache_key) {
+                        let refs_key = [b"refs:", cache_key, b"|", segment.as_bytes()].concat();
+                        db.insert(refs_key, &[])
+                            .map_err(|e| CacheError::DatabaseError(e.to_string()))?;
+                    }
+                }
+                None => stale.push(key),
+            }
+        }
+        if migrated > 0 {
+            tracing::info!(count = migrated, "Migrated cache entries written before the format field");
+        }
+        if !stale.is_empty() {
+            tracing::warn!(count = stale.len(), "Removing unreadable cache entries");
+            for key in stale {
+                db.remove(key)
+                    .map_err(|e| CacheError::DatabaseError(e.to_string()))?;
             }
         }
-        Ok(total)
+        Ok((total, novel_usage))
     }
 
-    /// LRU 淘汰
-    fn evict_lru(&self) -> Result<(), CacheError> {
+    /// LRU 淘汰（跳过固定的条目，指定 `novel_id` 时只淘汰该小说写入的条目），
+    /// 没有可淘汰的条目时返回 false
+    fn evict_lru(&self, novel_id: Option<&str>) -> Result<bool, CacheError> {
         let mut oldest: Option<(String, InternalCacheEntry)> = None;
 
         for item in self.db.scan_prefix("cache:") {
             let (key, value) = item.map_err(|e| CacheError::DatabaseError(e.to_string()))?;
+            if self.pins.contains(&String::from_utf8_lossy(&key[b"cache:".len()..])) {
+                continue;
+            }
             if let Ok(entry) = bincode::deserialize::<InternalCacheEntry>(&value) {
+                if novel_id.is_some_and(|novel_id| entry.novel_id != novel_id) {
+                    continue;
+                }
                 let is_older = oldest
                     .as_ref()
                     .map(|(_, e)| entry.last_accessed < e.last_accessed)
@@ -125,20 +360,9 @@ impl SledAudioCache {
             }
         }
 
-        if let Some((key, entry)) = oldest {
-            // 删除缓存条目
-            self.db
-                .remove(&key)
-                .map_err(|e| CacheError::DatabaseError(e.to_string()))?;
-
-            // 删除映射
-            let mapping_key = format!(
-                "mapping:{}:{}:{}",
-                entry.novel_id, entry.segment_index, entry.voice_id
-            );
-            let _ = self.db.remove(&mapping_key);
-
-            self.current_size.fetch_sub(entry.size_bytes, Ordering::Relaxed);
+        if let Some((key, entry)) = &oldest {
+            self.remove_entry(key, entry)?;
+            self.bump_counter(&self.eviction_count, META_EVICTION_COUNT, 1);
             tracing::debug!(
                 key = %key,
                 size_bytes = entry.size_bytes,
@@ -146,16 +370,131 @@ impl SledAudioCache {
             );
         }
 
+        Ok(oldest.is_some())
+    }
+
+    /// 删除缓存条目及引用它的所有映射和波形峰值（`key` 含 `cache:` 前缀）
+    fn remove_entry(&self, key: &str, entry: &InternalCacheEntry) -> Result<(), CacheError> {
+        self.db
+            .remove(key)
+            .map_err(|e| CacheError::DatabaseError(e.to_string()))?;
+
+        let cache_key = key.strip_prefix("cache:").unwrap_or(key);
+        for segment in self.segments_referencing(cache_key)? {
+            let _ = self.db.remove(format!("refs:{}|{}", cache_key, segment));
+            self.remove_mapping_to(&segment, cache_key)?;
+        }
+        let segment = format!("{}:{}:{}", entry.novel_id, entry.segment_index, entry.voice_id);
+        self.remove_mapping_to(&segment, cache_key)?;
+        let _ = self.db.remove(format!("peaks:{}", cache_key));
+
+        self.current_size.fetch_sub(entry.size_bytes, Ordering::Relaxed);
+        self.release_usage(&entry.novel_id, entry.size_bytes);
+        Ok(())
+    }
+
+    /// 小说写入的条目总大小
+    fn novel_usage(&self, novel_id: &str) -> u64 {
+        self.novel_usage.lock().unwrap().get(novel_id).copied().unwrap_or(0)
+    }
+
+    /// 扣减小说写入的条目大小（条目删除或被覆盖时）
+    fn release_usage(&self, novel_id: &str, size: u64) {
+        let mut usage = self.novel_usage.lock().unwrap();
+        if let Some(used) = usage.get_mut(novel_id) {
+            *used = used.saturating_sub(size);
+            if *used == 0 {
+                usage.remove(novel_id);
+            }
+        }
+    }
+
+    /// 写入前按小说配额淘汰该小说自己的条目（其余条目均被固定时暂时超出配额）
+    fn enforce_quota(&self, novel_id: Uuid, size: u64) -> Result<(), CacheError> {
+        let Some(quota) = self.quotas.limit(novel_id) else {
+            return Ok(());
+        };
+        let novel_key = novel_id.to_string();
+        let used = self.novel_usage(&novel_key);
+        if used + size <= quota {
+            return Ok(());
+        }
+
+        self.quotas.reached(novel_id, used, quota);
+        while self.novel_usage(&novel_key) + size > quota {
+            if !self.evict_lru(Some(&novel_key))? {
+                tracing::debug!(%novel_id, "No evictable cache entry, exceeding novel quota");
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// 记录片段（`novel_id:segment_index:voice_id`）对缓存条目的引用，片段原先引用其他条目时解除
+    fn add_reference(&self, segment: &str, cache_key: &str) -> Result<(), CacheError> {
+        let previous = self
+            .db
+            .insert(format!("mapping:{}", segment), cache_key.as_bytes())
+            .map_err(|e| CacheError::DatabaseError(e.to_string()))?;
+        if let Some(previous) = previous.filter(|previous| previous.as_ref() != cache_key.as_bytes()) {
+            let previous = String::from_utf8_lossy(&previous);
+            let _ = self.db.remove(format!("refs:{}|{}", previous, segment));
+        }
+        self.db
+            .insert(format!("refs:{}|{}", cache_key, segment), &[])
+            .map_err(|e| CacheError::DatabaseError(e.to_string()))?;
         Ok(())
     }
 
-    /// 刷新数据库
-    pub fn flush(&self) -> Result<(), CacheError> {
+    /// 引用缓存条目的所有片段
+    fn segments_referencing(&self, cache_key: &str) -> Result<Vec<String>, CacheError> {
+        let prefix = format!("refs:{}|", cache_key);
         self.db
-            .flush()
+            .scan_prefix(&prefix)
+            .keys()
+            .map(|key| {
+                let key = key.map_err(|e| CacheError::DatabaseError(e.to_string()))?;
+                Ok(String::from_utf8_lossy(&key[prefix.len()..]).into_owned())
+            })
+            .collect()
+    }
+
+    /// 片段映射仍指向该条目时删除映射
+    fn remove_mapping_to(&self, segment: &str, cache_key: &str) -> Result<(), CacheError> {
+        let mapping_key = format!("mapping:{}", segment);
+        let mapped = self
+            .db
+            .get(&mapping_key)
             .map_err(|e| CacheError::DatabaseError(e.to_string()))?;
+        if mapped.as_deref() == Some(cache_key.as_bytes()) {
+            let _ = self.db.remove(&mapping_key);
+        }
         Ok(())
     }
+
+    /// 删除满足条件的所有条目（条件参数为不含前缀的 cache_key 和条目），返回删除数
+    fn remove_matching(
+        &self,
+        predicate: impl Fn(&str, &InternalCacheEntry) -> bool,
+    ) -> Result<usize, CacheError> {
+        let mut matched = Vec::new();
+        for item in self.db.scan_prefix("cache:") {
+            let (key, value) = item.map_err(|e| CacheError::DatabaseError(e.to_string()))?;
+            if let Ok(entry) = bincode::deserialize::<InternalCacheEntry>(&value) {
+                let key = String::from_utf8(key.to_vec())
+                    .map_err(|e| CacheError::SerializationError(e.to_string()))?;
+                if predicate(&key["cache:".len()..], &entry) {
+                    matched.push((key, entry));
+                }
+            }
+        }
+
+        for (key, entry) in &matched {
+            self.remove_entry(key, entry)?;
+        }
+        Ok(matched.len())
+    }
+
 }
 
 #[async_trait]
@@ -166,11 +505,41 @@ impl AudioCachePort for SledAudioCache {
         audio_data: Vec<u8>,
         metadata: CacheMetadata,
     ) -> Result<(), CacheError> {
+        let original_size = audio_data.len();
+        let audio_data = if self.compress {
+            compress_audio(audio_data)
+        } else {
+            audio_data
+        };
+        // 压缩后再加密（密文无法压缩）
+        let audio_data = match &self.cipher {
+            Some(cipher) => cipher.encrypt(&audio_data)?,
+            None => audio_data,
+        };
         let size = audio_data.len() as u64;
 
-        // 淘汰以腾出空间
+        // 覆盖相同内容的已有条目：先移除旧音频（保留引用），避免重复计入大小
+        let key = format!("cache:{}", cache_key);
+        let existing = self
+            .db
+            .remove(&key)
+            .map_err(|e| CacheError::DatabaseError(e.to_string()))?;
+        if let Some(existing) = existing
+            .as_deref()
+            .and_then(|data| bincode::deserialize::<InternalCacheEntry<&[u8]>>(data).ok())
+        {
+            self.current_size.fetch_sub(existing.size_bytes, Ordering::Relaxed);
+            self.release_usage(&existing.novel_id, existing.size_bytes);
+        }
+
+        self.enforce_quota(metadata.novel_id, size)?;
+
+        // 淘汰以腾出空间（其余条目均被固定时暂时超出容量）
         while self.current_size.load(Ordering::Relaxed) + size > self.max_size_bytes {
-            self.evict_lru()?;
+            if !self.evict_lru(None)? {
+                tracing::debug!(cache_key = %cache_key, "No evictable cache entry, exceeding size limit");
+                break;
+            }
         }
 
         let entry = InternalCacheEntry {
@@ -184,6 +553,7 @@ impl AudioCachePort for SledAudioCache {
             last_accessed: Utc::now().timestamp(),
             created_at: Utc::now().timestamp(),
             sample_rate: metadata.sample_rate,
+            format: metadata.format,
         };
 
         let entry_bytes =
@@ -191,50 +561,63 @@ impl AudioCachePort for SledAudioCache {
 
         // 存储缓存条目
         self.db
-            .insert(format!("cache:{}", cache_key), entry_bytes)
+            .insert(&key, entry_bytes)
             .map_err(|e| CacheError::DatabaseError(e.to_string()))?;
 
         // 存储映射
-        let mapping_key = format!(
-            "mapping:{}:{}:{}",
+        let segment = format!(
+            "{}:{}:{}",
             metadata.novel_id, metadata.segment_index, metadata.voice_id
         );
-        self.db
-            .insert(mapping_key, cache_key.as_bytes())
-            .map_err(|e| CacheError::DatabaseError(e.to_string()))?;
+        self.add_reference(&segment, cache_key)?;
 
         self.current_size.fetch_add(size, Ordering::Relaxed);
+        *self
+            .novel_usage
+            .lock()
+            .unwrap()
+            .entry(metadata.novel_id.to_string())
+            .or_default() += size;
 
         tracing::debug!(
             cache_key = %cache_key,
             size_bytes = size,
+            original_size,
             "Audio cached"
         );
 
         Ok(())
     }
 
-    async fn get(&self, cache_key: &str) -> Result<Option<Vec<u8>>, CacheError> {
+    async fn get(&self, cache_key: &str) -> Result<Option<Bytes>, CacheError> {
+        Ok(self.get_entry(cache_key).await?.map(|entry| entry.audio_data))
+    }
+
+    async fn get_entry(&self, cache_key: &str) -> Result<Option<CacheEntry>, CacheError> {
         let key = format!("cache:{}", cache_key);
 
         match self.db.get(&key) {
             Ok(Some(data)) => {
-                let mut entry: InternalCacheEntry = bincode::deserialize(&data)
+                let mut entry: InternalCacheEntry<&[u8]> = bincode::deserialize(&data)
                     .map_err(|e| CacheError::SerializationError(e.to_string()))?;
 
                 // 更新 last_accessed (LRU touch)
                 entry.last_accessed = Utc::now().timestamp();
-                let entry_bytes = bincode::serialize(&entry)
-                    .map_err(|e| CacheError::SerializationError(e.to_string()))?;
+                let touched = IVec::from(
+                    bincode::serialize(&entry)
+                        .map_err(|e| CacheError::SerializationError(e.to_string()))?,
+                );
                 self.db
-                    .insert(&key, entry_bytes)
+                    .insert(&key, touched.clone())
                     .map_err(|e| CacheError::DatabaseError(e.to_string()))?;
 
-                self.hit_count.fetch_add(1, Ordering::Relaxed);
-                Ok(Some(entry.audio_data))
+                self.bump_counter(&self.hit_count, META_HIT_COUNT, 1);
+                // 音频直接引用写回的数据，不再复制
+                let audio_data = self.decode_audio(stored_audio(touched, entry.audio_data.len()))?;
+                entry.into_cache_entry(audio_data).map(Some)
             }
             Ok(None) => {
-                self.miss_count.fetch_add(1, Ordering::Relaxed);
+                self.bump_counter(&self.miss_count, META_MISS_COUNT, 1);
                 Ok(None)
             }
             Err(e) => Err(CacheError::DatabaseError(e.to_string())),
@@ -267,29 +650,165 @@ impl AudioCachePort for SledAudioCache {
             .map_err(|e| CacheError::DatabaseError(e.to_string()))
     }
 
-    async fn remove(&self, cache_key: &str) -> Result<(), CacheError> {
-        let key = format!("cache:{}", cache_key);
+    async fn link(
+        &self,
+        cache_key: &str,
+        novel_id: Uuid,
+        segment_index: u32,
+        voice_id: Uuid,
+    ) -> Result<bool, CacheError> {
+        if !self.exists(cache_key).await? {
+            return Ok(false);
+        }
+        let segment = format!("{}:{}:{}", novel_id, segment_index, voice_id);
+        self.add_reference(&segment, cache_key)?;
+        Ok(true)
+    }
 
-        if let Some(data) = self
-            .db
-            .remove(&key)
-            .map_err(|e| CacheError::DatabaseError(e.to_string()))?
-        {
-            if let Ok(entry) = bincode::deserialize::<InternalCacheEntry>(&data) {
-                // 删除映射
-                let mapping_key = format!(
-                    "mapping:{}:{}:{}",
-                    entry.novel_id, entry.segment_index, entry.voice_id
-                );
-                let _ = self.db.remove(&mapping_key);
+    async fn put_peaks(&self, cache_key: &str, peaks: &[f32]) -> Result<(), CacheError> {
+        let bytes =
+            bincode::serialize(peaks).map_err(|e| CacheError::SerializationError(e.to_string()))?;
+        self.db
+            .insert(format!("peaks:{}", cache_key), bytes)
+            .map_err(|e| CacheError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get_peaks(&self, cache_key: &str) -> Result<Option<Vec<f32>>, CacheError> {
+        match self.db.get(format!("peaks:{}", cache_key)) {
+            Ok(Some(data)) => bincode::deserialize(&data)
+                .map(Some)
+                .map_err(|e| CacheError::SerializationError(e.to_string())),
+            Ok(None) => Ok(None),
+            Err(e) => Err(CacheError::DatabaseError(e.to_string())),
+        }
+    }
+
+    async fn remove_expired(&self) -> Result<usize, CacheError> {
+        let Some(ttl_secs) = self.ttl_secs else {
+            return Ok(0);
+        };
+        let cutoff = Utc::now().timestamp() - ttl_secs as i64;
+        let removed = self.remove_matching(|_, entry| entry.last_accessed < cutoff)?;
+        if removed > 0 {
+            self.bump_counter(&self.expired_count, META_EXPIRED_COUNT, removed as u64);
+            tracing::info!(removed, ttl_secs, "Expired cache entries removed");
+        }
+        Ok(removed)
+    }
+
+    async fn remove_by_novel(&self, novel_id: Uuid) -> Result<usize, CacheError> {
+        // 先解除该小说所有片段的引用
+        let mut released = HashSet::new();
+        for item in self.db.scan_prefix(format!("mapping:{}:", novel_id)) {
+            let (key, value) = item.map_err(|e| CacheError::DatabaseError(e.to_string()))?;
+            let segment = String::from_utf8_lossy(&key["mapping:".len()..]).into_owned();
+            let cache_key = String::from_utf8_lossy(&value).into_owned();
+            let _ = self.db.remove(&key);
+            let _ = self.db.remove(format!("refs:{}|{}", cache_key, segment));
+            released.insert(cache_key);
+        }
+
+        self.quotas.reset(novel_id);
 
-                self.current_size.fetch_sub(entry.size_bytes, Ordering::Relaxed);
+        // 只删除不再被其他片段引用的条目
+        let novel_id = novel_id.to_string();
+        self.remove_matching(|cache_key, entry| {
+            (released.contains(cache_key) || entry.novel_id == novel_id)
+                && self
+                    .segments_referencing(cache_key)
+                    .map(|segments| segments.is_empty())
+                    .unwrap_or(false)
+        })
+    }
+
+    async fn remove_by_voice(&self, voice_id: Uuid) -> Result<usize, CacheError> {
+        let voice_id = voice_id.to_string();
+        self.remove_matching(|_, entry| entry.voice_id == voice_id)
+    }
+
+    fn pin(&self, session_id: &str, cache_keys: Vec<String>) {
+        self.pins.set(session_id, cache_keys);
+    }
+
+    fn unpin(&self, session_id: &str) {
+        self.pins.clear(session_id);
+    }
+
+    async fn remove_orphans(&self) -> Result<usize, CacheError> {
+        let entry_exists = |cache_key: &[u8]| {
+            self.db
+                .contains_key([&b"cache:"[..], cache_key].concat())
+                .map_err(|e| CacheError::DatabaseError(e.to_string()))
+        };
+
+        let mut orphans = Vec::new();
+        for item in self.db.scan_prefix("mapping:") {
+            let (key, value) = item.map_err(|e| CacheError::DatabaseError(e.to_string()))?;
+            if !entry_exists(&value[..])? {
+                orphans.push(key);
             }
         }
+        // refs:{cache_key}|{segment}
+        for key in self.db.scan_prefix("refs:").keys() {
+            let key = key.map_err(|e| CacheError::DatabaseError(e.to_string()))?;
+            let rest = &key[b"refs:".len()..];
+            let cache_key = rest.split(|&b| b == b'|').next().unwrap_or(rest);
+            if !entry_exists(cache_key)? {
+                orphans.push(key);
+            }
+        }
+        for key in self.db.scan_prefix("peaks:").keys() {
+            let key = key.map_err(|e| CacheError::DatabaseError(e.to_string()))?;
+            if !entry_exists(&key[b"peaks:".len()..])? {
+                orphans.push(key);
+            }
+        }
+
+        for key in &orphans {
+            self.db
+                .remove(key)
+                .map_err(|e| CacheError::DatabaseError(e.to_string()))?;
+        }
+        if !orphans.is_empty() {
+            tracing::info!(removed = orphans.len(), "Orphaned cache keys removed");
+        }
+        Ok(orphans.len())
+    }
 
+    async fn flush(&self) -> Result<(), CacheError> {
+        self.db
+            .flush_async()
+            .await
+            .map_err(|e| CacheError::DatabaseError(e.to_string()))?;
         Ok(())
     }
 
+    async fn remove_outdated(&self) -> Result<usize, CacheError> {
+        let removed = self.remove_matching(|cache_key, _| cache_key_version(cache_key) < CACHE_KEY_VERSION)?;
+        if removed > 0 {
+            tracing::info!(removed, version = CACHE_KEY_VERSION, "Outdated cache entries removed");
+        }
+        Ok(removed)
+    }
+
+    async fn remove(&self, cache_key: &str) -> Result<(), CacheError> {
+        let key = format!("cache:{}", cache_key);
+        let data = self
+            .db
+            .get(&key)
+            .map_err(|e| CacheError::DatabaseError(e.to_string()))?;
+
+        match data.and_then(|data| bincode::deserialize::<InternalCacheEntry>(&data).ok()) {
+            Some(entry) => self.remove_entry(&key, &entry),
+            None => {
+                let _ = self.db.remove(&key);
+                let _ = self.db.remove(format!("peaks:{}", cache_key));
+                Ok(())
+            }
+        }
+    }
+
     async fn stats(&self) -> CacheStats {
         let total_entries = self.db.scan_prefix("cache:").count();
 
@@ -299,7 +818,91 @@ impl AudioCachePort for SledAudioCache {
             max_size_bytes: self.max_size_bytes,
             hit_count: self.hit_count.load(Ordering::Relaxed),
             miss_count: self.miss_count.load(Ordering::Relaxed),
+            eviction_count: self.eviction_count.load(Ordering::Relaxed),
+            expired_count: self.expired_count.load(Ordering::Relaxed),
+            disk_size_bytes: self.db.size_on_disk().ok(),
+        }
+    }
+
+    async fn usage_by_novel(&self) -> Result<Vec<NovelCacheUsage>, CacheError> {
+        let mut sizes: HashMap<Vec<u8>, u64> = HashMap::new();
+        for item in self.db.scan_prefix("cache:") {
+            let (key, value) = item.map_err(|e| CacheError::DatabaseError(e.to_string()))?;
+            if let Ok(entry) = bincode::deserialize::<InternalCacheEntry<&[u8]>>(&value) {
+                sizes.insert(key["cache:".len()..].to_vec(), entry.size_bytes);
+            }
+        }
+
+        // 按片段引用统计，多部小说共享的条目分别计入
+        let mut usage: HashMap<String, (HashSet<Vec<u8>>, u64)> = HashMap::new();
+        for item in self.db.scan_prefix("mapping:") {
+            let (key, value) = item.map_err(|e| CacheError::DatabaseError(e.to_string()))?;
+            let Some(&size) = sizes.get(value.as_ref()) else {
+                continue;
+            };
+            let key = String::from_utf8_lossy(&key["mapping:".len()..]).into_owned();
+            let Some((novel_id, _)) = key.split_once(':') else {
+                continue;
+            };
+            let (entries, size_bytes) = usage.entry(novel_id.to_string()).or_default();
+            if entries.insert(value.to_vec()) {
+                *size_bytes += size;
+            }
         }
+
+        let mut usage: Vec<NovelCacheUsage> = usage
+            .into_iter()
+            .filter_map(|(novel_id, (entries, size_bytes))| {
+                Some(NovelCacheUsage {
+                    novel_id: Uuid::parse_str(&novel_id).ok()?,
+                    entries: entries.len(),
+                    size_bytes,
+                })
+            })
+            .collect();
+        usage.sort_by_key(|u| std::cmp::Reverse(u.size_bytes));
+        Ok(usage)
+    }
+
+    async fn keys(&self) -> Result<Vec<String>, CacheError> {
+        self.db
+            .scan_prefix("cache:")
+            .keys()
+            .map(|key| {
+                let key = key.map_err(|e| CacheError::DatabaseError(e.to_string()))?;
+                Ok(String::from_utf8_lossy(&key["cache:".len()..]).into_owned())
+            })
+            .collect()
+    }
+
+    async fn peek(&self, cache_key: &str) -> Result<Option<CacheEntry>, CacheError> {
+        let data = self
+            .db
+            .get(format!("cache:{}", cache_key))
+            .map_err(|e| CacheError::DatabaseError(e.to_string()))?;
+        let Some(data) = data else {
+            return Ok(None);
+        };
+        let entry: InternalCacheEntry<&[u8]> = bincode::deserialize(&data)
+            .map_err(|e| CacheError::SerializationError(e.to_string()))?;
+        let audio_data = self.decode_audio(stored_audio(data.clone(), entry.audio_data.len()))?;
+        entry.into_cache_entry(audio_data).map(Some)
+    }
+
+    async fn references(&self, cache_key: &str) -> Result<Vec<SegmentReference>, CacheError> {
+        // 片段格式: novel_id:segment_index:voice_id
+        Ok(self
+            .segments_referencing(cache_key)?
+            .iter()
+            .filter_map(|segment| {
+                let mut parts = segment.split(':');
+                Some(SegmentReference {
+                    novel_id: Uuid::parse_str(parts.next()?).ok()?,
+                    segment_index: parts.next()?.parse().ok()?,
+                    voice_id: Uuid::parse_str(parts.next()?).ok()?,
+                })
+            })
+            .collect())
     }
 }
 
@@ -314,6 +917,9 @@ mod tests {
         let config = SledCacheConfig {
             db_path: dir.path().join("test.sled").to_string_lossy().to_string(),
             max_size_bytes: 1024 * 1024,
+            compress: false,
+            ttl_secs: None,
+            ..Default::default()
         };
 
         let cache = SledAudioCache::new(&config).unwrap();
@@ -326,6 +932,7 @@ mod tests {
             content_hash: "test_hash".to_string(),
             duration_ms: 1000,
             sample_rate: Some(22050),
+            format: AudioFormat::Wav,
         };
 
         // Put
@@ -344,6 +951,65 @@ mod tests {
         let stats = cache.stats().await;
         assert_eq!(stats.total_entries, 1);
         assert_eq!(stats.hit_count, 1);
+
+        // 元数据记录实际存储格式
+        let entry = cache.get_entry("test_key").await.unwrap().unwrap();
+        assert_eq!(entry.metadata.format, AudioFormat::Wav);
+        assert_eq!(entry.metadata.sample_rate, Some(22050));
+    }
+
+    #[tokio::test]
+    async fn test_flush_and_disk_size() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("test.sled");
+        let metadata = CacheMetadata {
+            novel_id: Uuid::new_v4(),
+            segment_index: 0,
+            voice_id: Uuid::new_v4(),
+            content_hash: "test_hash".to_string(),
+            duration_ms: 1000,
+            sample_rate: None,
+            format: AudioFormat::Wav,
+        };
+        {
+            let cache = SledAudioCache::open(&path, 1024 * 1024).unwrap();
+            cache.put("test_key", vec![1, 2, 3], metadata).await.unwrap();
+            cache.flush().await.unwrap();
+
+            let stats = cache.stats().await;
+            assert!(stats.disk_size_bytes.is_some_and(|size| size > 0));
+        }
+
+        // 落盘后重新打开仍能读到
+        let cache = SledAudioCache::open(&path, 1024 * 1024).unwrap();
+        assert_eq!(cache.get("test_key").await.unwrap().unwrap(), vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_stored_audio_not_copied() {
+        let dir = tempdir().unwrap();
+        let cache = SledAudioCache::open(dir.path().join("test.sled"), 1024 * 1024).unwrap();
+        let audio_data: Vec<u8> = (0..=255).collect();
+        let metadata = CacheMetadata {
+            novel_id: Uuid::new_v4(),
+            segment_index: 0,
+            voice_id: Uuid::new_v4(),
+            content_hash: "test_hash".to_string(),
+            duration_ms: 1000,
+            sample_rate: None,
+            format: AudioFormat::Wav,
+        };
+        cache.put("test_key", audio_data.clone(), metadata).await.unwrap();
+
+        // 音频数据位于编码后条目的固定偏移处，直接引用存储的数据
+        let encoded = cache.db.get("cache:test_key").unwrap().unwrap();
+        let audio = stored_audio(encoded.clone(), audio_data.len());
+        assert_eq!(audio, audio_data);
+        assert_eq!(audio.as_ptr(), encoded[AUDIO_OFFSET..].as_ptr());
+
+        let entry = cache.get_entry("test_key").await.unwrap().unwrap();
+        assert_eq!(entry.audio_data, audio_data);
+        assert_eq!(entry.size_bytes, audio_data.len() as u64);
     }
 
     #[tokio::test]
@@ -352,6 +1018,9 @@ mod tests {
         let config = SledCacheConfig {
             db_path: dir.path().join("test.sled").to_string_lossy().to_string(),
             max_size_bytes: 1024 * 1024,
+            compress: false,
+            ttl_secs: None,
+            ..Default::default()
         };
 
         let cache = SledAudioCache::new(&config).unwrap();
@@ -365,6 +1034,7 @@ mod tests {
             content_hash: "test_hash".to_string(),
             duration_ms: 1000,
             sample_rate: Some(22050),
+            format: AudioFormat::Wav,
         };
 
         cache.put("my_cache_key", vec![1, 2, 3], metadata).await.unwrap();
@@ -374,4 +1044,460 @@ mod tests {
         assert!(result.is_some());
         assert_eq!(result.unwrap(), "my_cache_key");
     }
+
+    #[tokio::test]
+    async fn test_peaks_removed_with_entry() {
+        let dir = tempdir().unwrap();
+        let config = SledCacheConfig {
+            db_path: dir.path().join("test.sled").to_string_lossy().to_string(),
+            max_size_bytes: 1024 * 1024,
+            compress: false,
+            ttl_secs: None,
+            ..Default::default()
+        };
+
+        let cache = SledAudioCache::new(&config).unwrap();
+        let metadata = CacheMetadata {
+            novel_id: Uuid::new_v4(),
+            segment_index: 0,
+            voice_id: Uuid::new_v4(),
+            content_hash: "test_hash".to_string(),
+            duration_ms: 1000,
+            sample_rate: Some(22050),
+            format: AudioFormat::Wav,
+        };
+
+        cache.put("peaks_key", vec![1, 2, 3], metadata).await.unwrap();
+        assert_eq!(cache.get_peaks("peaks_key").await.unwrap(), None);
+
+        cache.put_peaks("peaks_key", &[0.0, 0.5, 1.0]).await.unwrap();
+        assert_eq!(cache.get_peaks("peaks_key").await.unwrap(), Some(vec![0.0, 0.5, 1.0]));
+        // 波形峰值不计入缓存条目数
+        assert_eq!(cache.stats().await.total_entries, 1);
+
+        cache.remove("peaks_key").await.unwrap();
+        assert_eq!(cache.get_peaks("peaks_key").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_remove_expired() {
+        let dir = tempdir().unwrap();
+        let config = SledCacheConfig {
+            db_path: dir.path().join("test.sled").to_string_lossy().to_string(),
+            max_size_bytes: 1024 * 1024,
+            compress: false,
+            ttl_secs: Some(30 * 86400),
+            ..Default::default()
+        };
+
+        let cache = SledAudioCache::new(&config).unwrap();
+        let metadata = |segment_index| CacheMetadata {
+            novel_id: Uuid::new_v4(),
+            segment_index,
+            voice_id: Uuid::new_v4(),
+            content_hash: "test_hash".to_string(),
+            duration_ms: 1000,
+            sample_rate: Some(22050),
+            format: AudioFormat::Wav,
+        };
+        cache.put("fresh", vec![1, 2, 3], metadata(0)).await.unwrap();
+        cache.put("stale", vec![4, 5], metadata(1)).await.unwrap();
+        cache.put_peaks("stale", &[0.5]).await.unwrap();
+
+        // 模拟 31 天前最后访问
+        let key = "cache:stale";
+        let mut entry: InternalCacheEntry =
+            bincode::deserialize(&cache.db.get(key).unwrap().unwrap()).unwrap();
+        entry.last_accessed -= 31 * 86400;
+        cache.db.insert(key, bincode::serialize(&entry).unwrap()).unwrap();
+
+        assert_eq!(cache.remove_expired().await.unwrap(), 1);
+        assert!(cache.exists("fresh").await.unwrap());
+        assert!(!cache.exists("stale").await.unwrap());
+        assert_eq!(cache.get_peaks("stale").await.unwrap(), None);
+        assert_eq!(cache.stats().await.total_size_bytes, 3);
+    }
+
+    #[tokio::test]
+    async fn test_remove_by_novel_and_voice() {
+        let dir = tempdir().unwrap();
+        let cache = SledAudioCache::open(dir.path().join("test.sled"), 1024 * 1024).unwrap();
+
+        let (novel_a, novel_b) = (Uuid::new_v4(), Uuid::new_v4());
+        let (voice_a, voice_b) = (Uuid::new_v4(), Uuid::new_v4());
+        let metadata = |novel_id, segment_index, voice_id| CacheMetadata {
+            novel_id,
+            segment_index,
+            voice_id,
+            content_hash: "test_hash".to_string(),
+            duration_ms: 1000,
+            sample_rate: Some(22050),
+            format: AudioFormat::Wav,
+        };
+        cache.put("a0", vec![1], metadata(novel_a, 0, voice_a)).await.unwrap();
+        cache.put("a1", vec![2], metadata(novel_a, 1, voice_b)).await.unwrap();
+        cache.put("b0", vec![3], metadata(novel_b, 0, voice_a)).await.unwrap();
+        cache.put("b1", vec![4, 5, 6], metadata(novel_b, 1, voice_b)).await.unwrap();
+
+        let usage = cache.usage_by_novel().await.unwrap();
+        assert_eq!(usage.len(), 2);
+        assert_eq!((usage[0].novel_id, usage[0].entries, usage[0].size_bytes), (novel_b, 2, 4));
+        assert_eq!((usage[1].novel_id, usage[1].entries, usage[1].size_bytes), (novel_a, 2, 2));
+
+        assert_eq!(cache.remove_by_novel(novel_a).await.unwrap(), 2);
+        assert_eq!(cache.lookup(novel_a, 0, voice_a).await.unwrap(), None);
+        assert!(cache.exists("b0").await.unwrap());
+
+        assert_eq!(cache.remove_by_voice(voice_b).await.unwrap(), 1);
+        assert!(!cache.exists("b1").await.unwrap());
+        assert!(cache.exists("b0").await.unwrap());
+        assert_eq!(cache.stats().await.total_entries, 1);
+    }
+
+    #[tokio::test]
+    async fn test_shared_entry_reference_counted() {
+        let dir = tempdir().unwrap();
+        let cache = SledAudioCache::open(dir.path().join("test.sled"), 1024 * 1024).unwrap();
+
+        let (original, reuploaded) = (Uuid::new_v4(), Uuid::new_v4());
+        let voice_id = Uuid::new_v4();
+        let metadata = CacheMetadata {
+            novel_id: original,
+            segment_index: 3,
+            voice_id,
+            content_hash: "test_hash".to_string(),
+            duration_ms: 1000,
+            sample_rate: Some(22050),
+            format: AudioFormat::Wav,
+        };
+        cache.put("shared", vec![1, 2, 3], metadata.clone()).await.unwrap();
+
+        // 重新上传的小说中相同内容的片段引用同一条目
+        assert!(cache.link("shared", reuploaded, 5, voice_id).await.unwrap());
+        assert!(!cache.link("missing", reuploaded, 6, voice_id).await.unwrap());
+        assert_eq!(cache.lookup(reuploaded, 5, voice_id).await.unwrap(), Some("shared".to_string()));
+        let usage = cache.usage_by_novel().await.unwrap();
+        assert_eq!(usage.len(), 2);
+        assert!(usage.iter().all(|u| u.entries == 1 && u.size_bytes == 3));
+        assert_eq!(cache.references("shared").await.unwrap().len(), 2);
+        assert_eq!(cache.keys().await.unwrap(), vec!["shared".to_string()]);
+
+        // peek 不计入命中统计
+        assert!(cache.peek("shared").await.unwrap().is_some());
+        assert_eq!(cache.stats().await.hit_count, 0);
+
+        // 覆盖写入不重复计入大小，也不丢失引用
+        cache.put("shared", vec![4, 5], metadata).await.unwrap();
+        assert_eq!(cache.stats().await.total_size_bytes, 2);
+
+        // 仍被引用时只解除原小说的引用
+        assert_eq!(cache.remove_by_novel(original).await.unwrap(), 0);
+        assert_eq!(cache.lookup(original, 3, voice_id).await.unwrap(), None);
+        assert_eq!(cache.get("shared").await.unwrap(), Some(Bytes::from(vec![4, 5])));
+
+        assert_eq!(cache.remove_by_novel(reuploaded).await.unwrap(), 1);
+        assert!(!cache.exists("shared").await.unwrap());
+        assert_eq!(cache.stats().await.total_size_bytes, 0);
+    }
+
+    #[tokio::test]
+    async fn test_remove_outdated() {
+        let dir = tempdir().unwrap();
+        let cache = SledAudioCache::open(dir.path().join("test.sled"), 1024 * 1024).unwrap();
+
+        let voice_id = Uuid::new_v4();
+        let metadata = |segment_index| CacheMetadata {
+            novel_id: Uuid::new_v4(),
+            segment_index,
+            voice_id,
+            content_hash: "test_hash".to_string(),
+            duration_ms: 1000,
+            sample_rate: Some(22050),
+            format: AudioFormat::Wav,
+        };
+        let voice = crate::application::ports::VoiceKey::new(voice_id, None, &Default::default());
+        let current = crate::application::ports::generate_cache_key("你好。", &voice);
+        let legacy = format!("{:x}:{}", md5::compute("你好。"), voice_id);
+        assert_eq!(cache_key_version(&current), CACHE_KEY_VERSION);
+        assert_eq!(cache_key_version(&legacy), 1);
+
+        cache.put(&current, vec![1, 2], metadata(0)).await.unwrap();
+        cache.put(&legacy, vec![3], metadata(1)).await.unwrap();
+
+        assert_eq!(cache.remove_outdated().await.unwrap(), 1);
+        assert!(cache.exists(&current).await.unwrap());
+        assert!(!cache.exists(&legacy).await.unwrap());
+        assert_eq!(cache.stats().await.total_size_bytes, 2);
+    }
+
+    #[tokio::test]
+    async fn test_novel_quota_evicts_own_entries() {
+        let dir = tempdir().unwrap();
+        let quotas = Arc::new(NovelQuotas::new(4, HashMap::new()));
+        let mut events = quotas.subscribe();
+        let config = SledCacheConfig {
+            db_path: dir.path().join("test.sled").to_string_lossy().to_string(),
+            max_size_bytes: 1024 * 1024,
+            quotas,
+            ..Default::default()
+        };
+        let cache = SledAudioCache::new(&config).unwrap();
+        let (novel_a, novel_b) = (Uuid::new_v4(), Uuid::new_v4());
+        let metadata = |novel_id, segment_index| CacheMetadata {
+            novel_id,
+            segment_index,
+            voice_id: Uuid::new_v4(),
+            content_hash: "test_hash".to_string(),
+            duration_ms: 1000,
+            sample_rate: Some(22050),
+            format: AudioFormat::Wav,
+        };
+
+        cache.put("b0", vec![1, 2], metadata(novel_b, 0)).await.unwrap();
+        cache.put("a0", vec![1, 2], metadata(novel_a, 0)).await.unwrap();
+        cache.put("a1", vec![1, 2], metadata(novel_a, 1)).await.unwrap();
+        assert!(events.try_recv().is_err());
+
+        // 超出配额时淘汰该小说最早的条目，而不是全局最早的 b0
+        cache.put("a2", vec![1, 2], metadata(novel_a, 2)).await.unwrap();
+        assert!(cache.exists("b0").await.unwrap());
+        assert!(!cache.exists("a0").await.unwrap());
+        assert!(cache.exists("a2").await.unwrap());
+
+        let event = events.try_recv().unwrap();
+        assert_eq!(event.novel_id, novel_a);
+        assert_eq!((event.used_bytes, event.quota_bytes), (4, 4));
+    }
+
+    #[tokio::test]
+    async fn test_remove_orphans() {
+        let dir = tempdir().unwrap();
+        let cache = SledAudioCache::open(dir.path().join("test.sled"), 1024 * 1024).unwrap();
+        let novel_id = Uuid::new_v4();
+        let voice_id = Uuid::new_v4();
+        let metadata = CacheMetadata {
+            novel_id,
+            segment_index: 0,
+            voice_id,
+            content_hash: "test_hash".to_string(),
+            duration_ms: 1000,
+            sample_rate: Some(22050),
+            format: AudioFormat::Wav,
+        };
+        cache.put("kept", vec![1, 2], metadata).await.unwrap();
+
+        // 模拟写入中途崩溃：只留下映射、引用和峰值
+        cache
+            .db
+            .insert(format!("mapping:{}:1:{}", novel_id, voice_id), "lost".as_bytes())
+            .unwrap();
+        cache.db.insert("refs:lost|segment", vec![]).unwrap();
+        cache.db.insert("peaks:lost", vec![]).unwrap();
+
+        assert_eq!(cache.remove_orphans().await.unwrap(), 3);
+        assert_eq!(cache.lookup(novel_id, 0, voice_id).await.unwrap(), Some("kept".to_string()));
+        assert_eq!(cache.lookup(novel_id, 1, voice_id).await.unwrap(), None);
+        assert_eq!(cache.remove_orphans().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_pinned_entries_not_evicted() {
+        let dir = tempdir().unwrap();
+        let cache = SledAudioCache::open(dir.path().join("test.sled"), 4).unwrap();
+        let metadata = |segment_index| CacheMetadata {
+            novel_id: Uuid::new_v4(),
+            segment_index,
+            voice_id: Uuid::new_v4(),
+            content_hash: "test_hash".to_string(),
+            duration_ms: 1000,
+            sample_rate: Some(22050),
+            format: AudioFormat::Wav,
+        };
+
+        // 固定最久未访问的 a，淘汰 b
+        cache.pin("session", vec!["a".to_string()]);
+        cache.put("a", vec![1, 2], metadata(0)).await.unwrap();
+        cache.put("b", vec![3, 4], metadata(1)).await.unwrap();
+        cache.put("c", vec![5, 6], metadata(2)).await.unwrap();
+        assert!(cache.exists("a").await.unwrap());
+        assert!(!cache.exists("b").await.unwrap());
+
+        // 其余条目均被固定时暂时超出容量
+        cache.pin("session", vec!["a".to_string(), "c".to_string()]);
+        cache.put("d", vec![7], metadata(3)).await.unwrap();
+        assert_eq!(cache.stats().await.total_size_bytes, 5);
+
+        cache.unpin("session");
+        cache.put("e", vec![8], metadata(4)).await.unwrap();
+        assert!(!cache.exists("a").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_counters_survive_reopen() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("test.sled");
+        let metadata = |segment_index| CacheMetadata {
+            novel_id: Uuid::new_v4(),
+            segment_index,
+            voice_id: Uuid::new_v4(),
+            content_hash: "test_hash".to_string(),
+            duration_ms: 1000,
+            sample_rate: Some(22050),
+            format: AudioFormat::Wav,
+        };
+
+        {
+            let cache = SledAudioCache::open(&path, 4).unwrap();
+            cache.put("a", vec![1, 2, 3], metadata(0)).await.unwrap();
+            cache.get("a").await.unwrap();
+            cache.get("missing").await.unwrap();
+            // 超出容量，淘汰 a
+            cache.put("b", vec![4, 5], metadata(1)).await.unwrap();
+            cache.flush().await.unwrap();
+        }
+
+        let cache = SledAudioCache::open(&path, 4).unwrap();
+        cache.get("b").await.unwrap();
+        let stats = cache.stats().await;
+        assert_eq!(stats.hit_count, 2);
+        assert_eq!(stats.miss_count, 1);
+        assert_eq!(stats.eviction_count, 1);
+        // 计数器键不计入缓存条目
+        assert_eq!(stats.total_entries, 1);
+    }
+
+    #[tokio::test]
+    async fn test_legacy_entries_migrated() {
+        #[derive(Serialize)]
+        struct Legacy<'a> {
+            audio_data: &'a [u8],
+            size_bytes: u64,
+            duration_ms: u64,
+            content_hash: &'a str,
+            novel_id: String,
+            segment_index: u32,
+            voice_id: String,
+            last_accessed: i64,
+            created_at: i64,
+            sample_rate: Option<u32>,
+        }
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("test.sled");
+        let (novel_id, voice_id) = (Uuid::new_v4(), Uuid::new_v4());
+        {
+            let db = sled::open(&path).unwrap();
+            let legacy = Legacy {
+                audio_data: b"RIFF....",
+                size_bytes: 8,
+                duration_ms: 1000,
+                content_hash: "test_hash",
+                novel_id: novel_id.to_string(),
+                segment_index: 0,
+                voice_id: voice_id.to_string(),
+                last_accessed: 1,
+                created_at: 1,
+                sample_rate: Some(22050),
+            };
+            db.insert("cache:old", bincode::serialize(&legacy).unwrap()).unwrap();
+            db.insert("cache:broken", &b"garbage"[..]).unwrap();
+            db.flush().unwrap();
+        }
+
+        let cache = SledAudioCache::open(&path, 1024).unwrap();
+        // 旧布局条目按 WAV 迁移保留，无法解析的条目删除
+        let entry = cache.get_entry("old").await.unwrap().unwrap();
+        assert_eq!(entry.metadata.format, AudioFormat::Wav);
+        assert_eq!(entry.metadata.novel_id, novel_id);
+        assert_eq!(entry.audio_data.as_ref(), b"RIFF....");
+        assert!(!cache.exists("broken").await.unwrap());
+        assert_eq!(cache.stats().await.total_size_bytes, 8);
+
+        // 迁移后以当前布局存储
+        let stored = cache.db.get("cache:old").unwrap().unwrap();
+        assert!(bincode::deserialize::<InternalCacheEntry>(&stored).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_compressed_put_get() {
+        let dir = tempdir().unwrap();
+        let config = SledCacheConfig {
+            db_path: dir.path().join("test.sled").to_string_lossy().to_string(),
+            max_size_bytes: 1024 * 1024,
+            compress: true,
+            ttl_secs: None,
+            ..Default::default()
+        };
+
+        let cache = SledAudioCache::new(&config).unwrap();
+        let metadata = CacheMetadata {
+            novel_id: Uuid::new_v4(),
+            segment_index: 0,
+            voice_id: Uuid::new_v4(),
+            content_hash: "test_hash".to_string(),
+            duration_ms: 1000,
+            sample_rate: Some(22050),
+            format: AudioFormat::Wav,
+        };
+
+        // 静音 WAV 可大幅压缩，读取时还原
+        let mut wav = b"RIFF".to_vec();
+        wav.resize(64 * 1024, 0);
+        cache.put("wav", wav.clone(), metadata.clone()).await.unwrap();
+        let entry = cache.get_entry("wav").await.unwrap().unwrap();
+        assert_eq!(entry.audio_data, wav);
+        assert!(entry.size_bytes < 1024);
+        assert_eq!(cache.stats().await.total_size_bytes, entry.size_bytes);
+
+        // 不可压缩的数据原样存储
+        let mut state = 0x9E37_79B9_7F4A_7C15u64;
+        let noise: Vec<u8> = (0..4096)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state >> 32) as u8
+            })
+            .collect();
+        cache.put("noise", noise.clone(), metadata).await.unwrap();
+        let entry = cache.get_entry("noise").await.unwrap().unwrap();
+        assert_eq!(entry.audio_data, noise);
+        assert_eq!(entry.size_bytes, noise.len() as u64);
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_audio() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.sled").to_string_lossy().to_string();
+        let config = SledCacheConfig {
+            db_path: db_path.clone(),
+            max_size_bytes: 1024 * 1024,
+            compress: true,
+            cipher: Some(Arc::new(CacheCipher::new(&[3; 32]).unwrap())),
+            ..Default::default()
+        };
+        let metadata = CacheMetadata {
+            novel_id: Uuid::new_v4(),
+            segment_index: 0,
+            voice_id: Uuid::new_v4(),
+            content_hash: "test_hash".to_string(),
+            duration_ms: 1000,
+            sample_rate: Some(22050),
+            format: AudioFormat::Wav,
+        };
+        let mut wav = b"RIFF".to_vec();
+        wav.resize(64 * 1024, 0);
+
+        {
+            let cache = SledAudioCache::new(&config).unwrap();
+            cache.put("wav", wav.clone(), metadata).await.unwrap();
+            let raw = cache.db.get("cache:wav").unwrap().unwrap();
+            assert!(!raw.windows(4).any(|w| w == b"RIFF"));
+            assert_eq!(cache.get("wav").await.unwrap(), Some(Bytes::from(wav.clone())));
+        }
+
+        // 未配置密钥时无法读取已加密的条目
+        let cache = SledAudioCache::open(&db_path, 1024 * 1024).unwrap();
+        assert!(cache.get("wav").await.is_err());
+    }
 }
@@ -2,6 +2,7 @@
 
 use sqlx::{sqlite::SqlitePoolOptions, Pool, Sqlite};
 use std::path::Path;
+use uuid::Uuid;
 
 /// 数据库配置
 #[derive(Debug, Clone)]
@@ -110,7 +111,18 @@ pub async fn run_migrations(pool: &DbPool) -> Result<(), sqlx::Error> {
             id TEXT PRIMARY KEY,
             name TEXT NOT NULL,
             reference_audio_path TEXT NOT NULL,
+            processed_audio_path TEXT,
             description TEXT,
+            prompt_text TEXT,
+            engine TEXT,
+            speed REAL NOT NULL DEFAULT 1.0,
+            pitch INTEGER NOT NULL DEFAULT 0,
+            volume REAL NOT NULL DEFAULT 1.0,
+            temperature REAL,
+            top_p REAL,
+            gender TEXT,
+            language TEXT,
+            style TEXT,
             created_at TEXT NOT NULL
         )
         "#,
@@ -118,6 +130,23 @@ pub async fn run_migrations(pool: &DbPool) -> Result<(), sqlx::Error> {
     .execute(pool)
     .await?;
 
+    // 兼容旧库: voices 表补充 TTS 参数列
+    add_column_if_missing(pool, "voices", "speed", "REAL NOT NULL DEFAULT 1.0").await?;
+    add_column_if_missing(pool, "voices", "pitch", "INTEGER NOT NULL DEFAULT 0").await?;
+    add_column_if_missing(pool, "voices", "temperature", "REAL").await?;
+    add_column_if_missing(pool, "voices", "top_p", "REAL").await?;
+    add_column_if_missing(pool, "voices", "volume", "REAL NOT NULL DEFAULT 1.0").await?;
+    add_column_if_missing(pool, "voices", "processed_audio_path", "TEXT").await?;
+    add_column_if_missing(pool, "voices", "gender", "TEXT").await?;
+    add_column_if_missing(pool, "voices", "language", "TEXT").await?;
+    add_column_if_missing(pool, "voices", "style", "TEXT").await?;
+    add_column_if_missing(pool, "voices", "prompt_text", "TEXT").await?;
+    add_column_if_missing(pool, "voices", "engine", "TEXT").await?;
+
+    // 所属用户（启用账号后创建的小说、音色和会话）
+    add_column_if_missing(pool, "novels", "user_id", "TEXT").await?;
+    add_column_if_missing(pool, "voices", "user_id", "TEXT").await?;
+
     // 创建 sessions 表
     sqlx::query(
         r#"
@@ -140,6 +169,17 @@ pub async fn run_migrations(pool: &DbPool) -> Result<(), sqlx::Error> {
     .execute(pool)
     .await?;
 
+    // 会话播放进度、合成参数和播放设置（参数为 JSON）
+    add_column_if_missing(pool, "sessions", "offset_ms", "INTEGER NOT NULL DEFAULT 0").await?;
+    add_column_if_missing(pool, "sessions", "params", "TEXT").await?;
+    add_column_if_missing(pool, "sessions", "segment_params", "TEXT").await?;
+    add_column_if_missing(pool, "sessions", "playback_speed", "REAL").await?;
+    add_column_if_missing(pool, "sessions", "gap_ms", "INTEGER NOT NULL DEFAULT 0").await?;
+    add_column_if_missing(pool, "sessions", "crossfade_ms", "INTEGER NOT NULL DEFAULT 0").await?;
+    add_column_if_missing(pool, "sessions", "client", "TEXT").await?;
+    add_column_if_missing(pool, "sessions", "auto_advance", "INTEGER NOT NULL DEFAULT 0").await?;
+    add_column_if_missing(pool, "sessions", "user_id", "TEXT").await?;
+
     // 创建 audio_segments 表
     sqlx::query(
         r#"
@@ -162,6 +202,159 @@ pub async fn run_migrations(pool: &DbPool) -> Result<(), sqlx::Error> {
     .execute(pool)
     .await?;
 
+    // 创建 settings 表（每个用户的 key/value 设置）
+    create_user_scoped_table(
+        pool,
+        "settings",
+        r#"
+        CREATE TABLE IF NOT EXISTS settings (
+            user_id TEXT NOT NULL DEFAULT '',
+            key TEXT NOT NULL,
+            value TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            PRIMARY KEY (user_id, key)
+        )
+        "#,
+        "key, value, updated_at",
+    )
+    .await?;
+
+    // 创建 novel_voice_casting 表（角色 -> 音色）
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS novel_voice_casting (
+            novel_id TEXT NOT NULL,
+            character_name TEXT NOT NULL,
+            voice_id TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            PRIMARY KEY (novel_id, character_name),
+            FOREIGN KEY (novel_id) REFERENCES novels(id) ON DELETE CASCADE,
+            FOREIGN KEY (voice_id) REFERENCES voices(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // 创建 tts_engine_status 表（TTS 引擎健康检查结果）
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS tts_engine_status (
+            engine TEXT PRIMARY KEY,
+            healthy INTEGER NOT NULL,
+            consecutive_failures INTEGER NOT NULL DEFAULT 0,
+            last_checked_at TEXT NOT NULL,
+            last_changed_at TEXT NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // 创建 listening_stats 表（每个用户的每日收听统计）
+    create_user_scoped_table(
+        pool,
+        "listening_stats",
+        r#"
+        CREATE TABLE IF NOT EXISTS listening_stats (
+            user_id TEXT NOT NULL DEFAULT '',
+            novel_id TEXT NOT NULL,
+            day TEXT NOT NULL,
+            segments INTEGER NOT NULL DEFAULT 0,
+            listened_ms INTEGER NOT NULL DEFAULT 0,
+            max_index INTEGER NOT NULL DEFAULT 0,
+            updated_at TEXT NOT NULL,
+            PRIMARY KEY (user_id, novel_id, day)
+        )
+        "#,
+        "novel_id, day, segments, listened_ms, max_index, updated_at",
+    )
+    .await?;
+
+    // 创建 queued_tasks 表（未完成的推理任务，重启后重新排队）
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS queued_tasks (
+            task_id TEXT PRIMARY KEY,
+            session_id TEXT NOT NULL,
+            novel_id TEXT NOT NULL,
+            voice_id TEXT NOT NULL,
+            segment_index INTEGER NOT NULL,
+            segment_content TEXT NOT NULL,
+            params TEXT,
+            explicit INTEGER NOT NULL DEFAULT 0,
+            priority TEXT NOT NULL DEFAULT 'prefetch',
+            created_at TEXT NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // 创建 task_events 表（推理任务生命周期的审计日志，只追加）
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS task_events (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            task_id TEXT NOT NULL,
+            session_id TEXT NOT NULL,
+            novel_id TEXT NOT NULL,
+            voice_id TEXT NOT NULL,
+            segment_index INTEGER NOT NULL,
+            event TEXT NOT NULL,
+            error TEXT,
+            duration_ms INTEGER,
+            created_at TEXT NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+    add_column_if_missing(pool, "task_events", "request_id", "TEXT").await?;
+
+    // 创建 reading_progress 表（每个用户在每部小说上最近的播放位置）
+    create_user_scoped_table(
+        pool,
+        "reading_progress",
+        r#"
+        CREATE TABLE IF NOT EXISTS reading_progress (
+            user_id TEXT NOT NULL DEFAULT '',
+            novel_id TEXT NOT NULL,
+            segment_index INTEGER NOT NULL DEFAULT 0,
+            offset_ms INTEGER NOT NULL DEFAULT 0,
+            updated_at TEXT NOT NULL,
+            PRIMARY KEY (user_id, novel_id)
+        )
+        "#,
+        "novel_id, segment_index, offset_ms, updated_at",
+    )
+    .await?;
+
+    // 创建 users 表（用户账号）
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS users (
+            id TEXT PRIMARY KEY,
+            username TEXT NOT NULL UNIQUE,
+            password_hash TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+    add_column_if_missing(pool, "users", "is_admin", "INTEGER NOT NULL DEFAULT 0").await?;
+    // 升级前注册的用户中没有管理员时，由最早注册的用户担任
+    sqlx::query(
+        r#"
+        UPDATE users SET is_admin = 1
+        WHERE id = (SELECT id FROM users ORDER BY created_at LIMIT 1)
+          AND NOT EXISTS (SELECT 1 FROM users WHERE is_admin = 1)
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
     // 创建索引
     sqlx::query(
         r#"
@@ -200,10 +393,113 @@ pub async fn run_migrations(pool: &DbPool) -> Result<(), sqlx::Error> {
     .execute(pool)
     .await?;
 
+    // 索引: task_events 按小说片段和会话查询
+    sqlx::query(
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_task_events_novel_segment
+        ON task_events(novel_id, segment_index)
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_task_events_session_id
+        ON task_events(session_id)
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
     tracing::info!("Database migrations completed");
     Ok(())
 }
 
+/// 按用户区分的表中 user_id 列的值（未启用账号时为空字符串，主键列不使用 NULL）
+pub fn user_key(user_id: Option<Uuid>) -> String {
+    user_id.map(|id| id.to_string()).unwrap_or_default()
+}
+
+/// 解析 user_id 列（空字符串为 None）
+pub fn parse_user_key(value: &str) -> Result<Option<Uuid>, uuid::Error> {
+    if value.is_empty() {
+        Ok(None)
+    } else {
+        Uuid::parse_str(value).map(Some)
+    }
+}
+
+/// 创建按用户区分的表（user_id 为主键的一部分）
+///
+/// 旧库中的同名表没有 user_id 列时在事务中重建：原有数据复制到新表，
+/// 归入未启用账号时的空用户（`columns` 为新旧表共有的列）
+async fn create_user_scoped_table(
+    pool: &DbPool,
+    table: &str,
+    create_sql: &str,
+    columns: &str,
+) -> Result<(), sqlx::Error> {
+    let legacy: Option<(String,)> = sqlx::query_as(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name = ?1 \
+         AND NOT EXISTS (SELECT 1 FROM pragma_table_info(?1) WHERE name = 'user_id')",
+    )
+    .bind(table)
+    .fetch_optional(pool)
+    .await?;
+
+    if legacy.is_none() {
+        sqlx::query(create_sql).execute(pool).await?;
+        return Ok(());
+    }
+
+    let mut tx = pool.begin().await?;
+    sqlx::query(&format!("ALTER TABLE {0} RENAME TO {0}_legacy", table))
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query(create_sql).execute(&mut *tx).await?;
+    sqlx::query(&format!(
+        "INSERT INTO {0} ({1}) SELECT {1} FROM {0}_legacy",
+        table, columns
+    ))
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query(&format!("DROP TABLE {}_legacy", table))
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    tracing::info!(table, "Migrated table to per-user rows");
+
+    Ok(())
+}
+
+/// 列不存在时执行 ALTER TABLE ADD COLUMN（SQLite 不支持 IF NOT EXISTS）
+async fn add_column_if_missing(
+    pool: &DbPool,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> Result<(), sqlx::Error> {
+    let exists: Option<(String,)> =
+        sqlx::query_as("SELECT name FROM pragma_table_info(?) WHERE name = ?")
+            .bind(table)
+            .bind(column)
+            .fetch_optional(pool)
+            .await?;
+
+    if exists.is_none() {
+        sqlx::query(&format!(
+            "ALTER TABLE {} ADD COLUMN {} {}",
+            table, column, definition
+        ))
+        .execute(pool)
+        .await?;
+        tracing::info!(table, column, "Added missing column");
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -214,4 +510,62 @@ mod tests {
         let pool = create_pool(&config).await.unwrap();
         run_migrations(&pool).await.unwrap();
     }
+
+    #[tokio::test]
+    async fn test_migrations_add_columns_to_legacy_voices() {
+        let config = DatabaseConfig::in_memory();
+        let pool = create_pool(&config).await.unwrap();
+        sqlx::query(
+            "CREATE TABLE voices (id TEXT PRIMARY KEY, name TEXT NOT NULL, \
+             reference_audio_path TEXT NOT NULL, description TEXT, created_at TEXT NOT NULL)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        run_migrations(&pool).await.unwrap();
+        // 重复执行应保持幂等
+        run_migrations(&pool).await.unwrap();
+
+        let (count,): (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM pragma_table_info('voices') \
+             WHERE name IN ('speed', 'pitch', 'temperature', 'top_p')",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(count, 4);
+    }
+
+    #[tokio::test]
+    async fn test_migrations_scope_legacy_progress_by_user() {
+        let config = DatabaseConfig::in_memory();
+        let pool = create_pool(&config).await.unwrap();
+        sqlx::query(
+            "CREATE TABLE reading_progress (novel_id TEXT PRIMARY KEY, segment_index INTEGER NOT NULL DEFAULT 0, \
+             offset_ms INTEGER NOT NULL DEFAULT 0, updated_at TEXT NOT NULL)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO reading_progress VALUES ('n1', 7, 300, '2026-01-01T00:00:00Z')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        run_migrations(&pool).await.unwrap();
+        run_migrations(&pool).await.unwrap();
+
+        // 旧数据保留在空用户下，其他用户可以有各自的进度
+        let (user_id, segment_index): (String, i64) =
+            sqlx::query_as("SELECT user_id, segment_index FROM reading_progress WHERE novel_id = 'n1'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!((user_id.as_str(), segment_index), ("", 7));
+        sqlx::query("INSERT INTO reading_progress VALUES ('user-b', 'n1', 1, 0, '2026-01-02T00:00:00Z')")
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
 }
@@ -0,0 +1,104 @@
+//! SQLite Listening Stats Repository
+
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use super::{parse_user_key, user_key, DbPool};
+use crate::application::ports::{
+    ListeningStatsRecord, ListeningStatsRepositoryPort, RepositoryError,
+};
+
+/// 日期列格式
+const DAY_FORMAT: &str = "%Y-%m-%d";
+
+/// SQLite Listening Stats Repository
+pub struct SqliteListeningStatsRepository {
+    pool: DbPool,
+}
+
+impl SqliteListeningStatsRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(FromRow)]
+struct ListeningStatsRow {
+    user_id: String,
+    novel_id: String,
+    day: String,
+    segments: i64,
+    listened_ms: i64,
+    max_index: i64,
+}
+
+impl TryFrom<ListeningStatsRow> for ListeningStatsRecord {
+    type Error = RepositoryError;
+
+    fn try_from(row: ListeningStatsRow) -> Result<Self, Self::Error> {
+        Ok(ListeningStatsRecord {
+            user_id: parse_user_key(&row.user_id)
+                .map_err(|e| RepositoryError::SerializationError(e.to_string()))?,
+            novel_id: Uuid::parse_str(&row.novel_id)
+                .map_err(|e| RepositoryError::SerializationError(e.to_string()))?,
+            day: NaiveDate::parse_from_str(&row.day, DAY_FORMAT)
+                .map_err(|e| RepositoryError::SerializationError(e.to_string()))?,
+            segments: row.segments.max(0) as u32,
+            listened_ms: row.listened_ms.max(0) as u64,
+            max_index: row.max_index.max(0) as u32,
+        })
+    }
+}
+
+#[async_trait]
+impl ListeningStatsRepositoryPort for SqliteListeningStatsRepository {
+    async fn add(&self, record: &ListeningStatsRecord) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO listening_stats (user_id, novel_id, day, segments, listened_ms, max_index, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(user_id, novel_id, day) DO UPDATE SET
+                segments = segments + excluded.segments,
+                listened_ms = listened_ms + excluded.listened_ms,
+                max_index = MAX(max_index, excluded.max_index),
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(user_key(record.user_id))
+        .bind(record.novel_id.to_string())
+        .bind(record.day.format(DAY_FORMAT).to_string())
+        .bind(record.segments as i64)
+        .bind(record.listened_ms as i64)
+        .bind(record.max_index as i64)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn find(
+        &self,
+        user_id: Option<Uuid>,
+        novel_id: Option<Uuid>,
+    ) -> Result<Vec<ListeningStatsRecord>, RepositoryError> {
+        let rows: Vec<ListeningStatsRow> = sqlx::query_as(
+            r#"
+            SELECT user_id, novel_id, day, segments, listened_ms, max_index
+            FROM listening_stats
+            WHERE user_id = ?1 AND (?2 IS NULL OR novel_id = ?2)
+            ORDER BY day, novel_id
+            "#,
+        )
+        .bind(user_key(user_id))
+        .bind(novel_id.map(|id| id.to_string()))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        rows.into_iter().map(ListeningStatsRecord::try_from).collect()
+    }
+}
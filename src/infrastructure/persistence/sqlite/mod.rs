@@ -5,9 +5,25 @@ mod novel_repo;
 mod voice_repo;
 mod session_repo;
 mod audio_segment_repo;
+mod settings_repo;
+mod voice_casting_repo;
+mod tts_engine_status_repo;
+mod listening_stats_repo;
+mod reading_progress_repo;
+mod queued_task_repo;
+mod task_event_repo;
+mod user_repo;
 
 pub use database::*;
 pub use novel_repo::*;
 pub use voice_repo::*;
 pub use session_repo::*;
 pub use audio_segment_repo::*;
+pub use settings_repo::*;
+pub use voice_casting_repo::*;
+pub use tts_engine_status_repo::*;
+pub use listening_stats_repo::*;
+pub use reading_progress_repo::*;
+pub use queued_task_repo::*;
+pub use task_event_repo::*;
+pub use user_repo::*;
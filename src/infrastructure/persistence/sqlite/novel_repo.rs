@@ -29,6 +29,7 @@ struct NovelRow {
     raw_text_path: String,
     total_segments: i64,
     status: String,
+    user_id: Option<String>,
     created_at: String,
     updated_at: String,
 }
@@ -44,6 +45,11 @@ impl TryFrom<NovelRow> for NovelRecord {
             raw_text_path: PathBuf::from(row.raw_text_path),
             total_segments: row.total_segments as usize,
             status: NovelStatus::from_str(&row.status).unwrap_or_default(),
+            user_id: row
+                .user_id
+                .map(|id| Uuid::parse_str(&id))
+                .transpose()
+                .map_err(|e| RepositoryError::SerializationError(e.to_string()))?,
             created_at: DateTime::parse_from_rfc3339(&row.created_at)
                 .map_err(|e| RepositoryError::SerializationError(e.to_string()))?
                 .with_timezone(&Utc),
@@ -84,8 +90,8 @@ impl NovelRepositoryPort for SqliteNovelRepository {
     async fn save(&self, novel: &NovelRecord) -> Result<(), RepositoryError> {
         sqlx::query(
             r#"
-            INSERT INTO novels (id, title, raw_text_path, total_segments, status, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO novels (id, title, raw_text_path, total_segments, status, user_id, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
             ON CONFLICT(id) DO UPDATE SET
                 title = excluded.title,
                 raw_text_path = excluded.raw_text_path,
@@ -99,6 +105,7 @@ impl NovelRepositoryPort for SqliteNovelRepository {
         .bind(novel.raw_text_path.to_string_lossy().to_string())
         .bind(novel.total_segments as i64)
         .bind(novel.status.as_str())
+        .bind(novel.user_id.map(|id| id.to_string()))
         .bind(novel.created_at.to_rfc3339())
         .bind(novel.updated_at.to_rfc3339())
         .execute(&self.pool)
@@ -110,7 +117,7 @@ impl NovelRepositoryPort for SqliteNovelRepository {
 
     async fn find_by_id(&self, id: Uuid) -> Result<Option<NovelRecord>, RepositoryError> {
         let row: Option<NovelRow> = sqlx::query_as(
-            "SELECT id, title, raw_text_path, total_segments, status, created_at, updated_at FROM novels WHERE id = ?",
+            "SELECT id, title, raw_text_path, total_segments, status, user_id, created_at, updated_at FROM novels WHERE id = ?",
         )
         .bind(id.to_string())
         .fetch_optional(&self.pool)
@@ -122,8 +129,33 @@ impl NovelRepositoryPort for SqliteNovelRepository {
 
     async fn find_all(&self) -> Result<Vec<NovelRecord>, RepositoryError> {
         let rows: Vec<NovelRow> = sqlx::query_as(
-            "SELECT id, title, raw_text_path, total_segments, status, created_at, updated_at FROM novels ORDER BY created_at DESC",
+            "SELECT id, title, raw_text_path, total_segments, status, user_id, created_at, updated_at FROM novels ORDER BY created_at DESC",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        rows.into_iter().map(NovelRecord::try_from).collect()
+    }
+
+    async fn find_page(
+        &self,
+        user_id: Option<Uuid>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<NovelRecord>, RepositoryError> {
+        let rows: Vec<NovelRow> = sqlx::query_as(
+            r#"
+            SELECT id, title, raw_text_path, total_segments, status, user_id, created_at, updated_at
+            FROM novels
+            WHERE (?1 IS NULL OR user_id IS NULL OR user_id = ?1)
+            ORDER BY created_at DESC
+            LIMIT ?2 OFFSET ?3
+            "#,
         )
+        .bind(user_id.map(|id| id.to_string()))
+        .bind(limit as i64)
+        .bind(offset as i64)
         .fetch_all(&self.pool)
         .await
         .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
@@ -131,6 +163,18 @@ impl NovelRepositoryPort for SqliteNovelRepository {
         rows.into_iter().map(NovelRecord::try_from).collect()
     }
 
+    async fn count(&self, user_id: Option<Uuid>) -> Result<u64, RepositoryError> {
+        let (count,): (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM novels WHERE (?1 IS NULL OR user_id IS NULL OR user_id = ?1)",
+        )
+        .bind(user_id.map(|id| id.to_string()))
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        Ok(count.max(0) as u64)
+    }
+
     async fn delete(&self, id: Uuid) -> Result<(), RepositoryError> {
         // 使用事务确保原子性
         let mut tx = self.pool.begin().await
@@ -159,6 +203,27 @@ impl NovelRepositoryPort for SqliteNovelRepository {
             .await
             .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
 
+        // 删除角色配音
+        sqlx::query("DELETE FROM novel_voice_casting WHERE novel_id = ?")
+            .bind(id.to_string())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        // 删除收听统计
+        sqlx::query("DELETE FROM listening_stats WHERE novel_id = ?")
+            .bind(id.to_string())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        // 删除阅读进度
+        sqlx::query("DELETE FROM reading_progress WHERE novel_id = ?")
+            .bind(id.to_string())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
         // 删除 novel
         sqlx::query("DELETE FROM novels WHERE id = ?")
             .bind(id.to_string())
@@ -247,6 +312,16 @@ impl NovelRepositoryPort for SqliteNovelRepository {
         rows.into_iter().map(TextSegmentRecord::try_from).collect()
     }
 
+    async fn count_segments(&self, novel_id: Uuid) -> Result<u64, RepositoryError> {
+        let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM text_segments WHERE novel_id = ?")
+            .bind(novel_id.to_string())
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        Ok(count.max(0) as u64)
+    }
+
     async fn find_segments_by_indices(
         &self,
         novel_id: Uuid,
@@ -302,6 +377,35 @@ impl NovelRepositoryPort for SqliteNovelRepository {
         Ok(())
     }
 
+    async fn assign_unowned(&self, user_id: Uuid) -> Result<u64, RepositoryError> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        // 这些小说未归属用户时记录的阅读进度和收听统计随小说一起归属
+        for table in ["reading_progress", "listening_stats"] {
+            sqlx::query(&format!(
+                "UPDATE {} SET user_id = ? WHERE user_id = '' \
+                 AND novel_id IN (SELECT id FROM novels WHERE user_id IS NULL)",
+                table
+            ))
+            .bind(user_id.to_string())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        }
+
+        let result = sqlx::query("UPDATE novels SET user_id = ? WHERE user_id IS NULL")
+            .bind(user_id.to_string())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        tx.commit().await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        Ok(result.rows_affected())
+    }
+
     async fn save_segments_batch(&self, segments: &[TextSegmentRecord]) -> Result<(), RepositoryError> {
         if segments.is_empty() {
             return Ok(());
@@ -0,0 +1,144 @@
+//! SQLite Queued Task Repository
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use super::DbPool;
+use crate::application::ports::{
+    QueuedTaskRecord, QueuedTaskRepositoryPort, RepositoryError, TaskPriority,
+};
+
+/// SQLite Queued Task Repository
+pub struct SqliteQueuedTaskRepository {
+    pool: DbPool,
+}
+
+impl SqliteQueuedTaskRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(FromRow)]
+struct QueuedTaskRow {
+    task_id: String,
+    session_id: String,
+    novel_id: String,
+    voice_id: String,
+    segment_index: i64,
+    segment_content: String,
+    params: Option<String>,
+    explicit: bool,
+    priority: String,
+    created_at: String,
+}
+
+impl TryFrom<QueuedTaskRow> for QueuedTaskRecord {
+    type Error = RepositoryError;
+
+    fn try_from(row: QueuedTaskRow) -> Result<Self, Self::Error> {
+        let parse_uuid = |value: &str| {
+            Uuid::parse_str(value).map_err(|e| RepositoryError::SerializationError(e.to_string()))
+        };
+        Ok(QueuedTaskRecord {
+            novel_id: parse_uuid(&row.novel_id)?,
+            voice_id: parse_uuid(&row.voice_id)?,
+            task_id: row.task_id,
+            session_id: row.session_id,
+            segment_index: row.segment_index.max(0) as u32,
+            segment_content: row.segment_content,
+            params: match row.params.as_deref() {
+                Some(params) => serde_json::from_str(params)
+                    .map_err(|e| RepositoryError::SerializationError(e.to_string()))?,
+                None => Default::default(),
+            },
+            explicit: row.explicit,
+            priority: TaskPriority::from_str(&row.priority).unwrap_or_default(),
+            created_at: DateTime::parse_from_rfc3339(&row.created_at)
+                .map_err(|e| RepositoryError::SerializationError(e.to_string()))?
+                .with_timezone(&Utc),
+        })
+    }
+}
+
+#[async_trait]
+impl QueuedTaskRepositoryPort for SqliteQueuedTaskRepository {
+    async fn save_all(&self, tasks: &[QueuedTaskRecord]) -> Result<(), RepositoryError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        for task in tasks {
+            let params = serde_json::to_string(&task.params)
+                .map_err(|e| RepositoryError::SerializationError(e.to_string()))?;
+            sqlx::query(
+                r#"
+                INSERT OR REPLACE INTO queued_tasks (task_id, session_id, novel_id, voice_id, segment_index, segment_content, params, explicit, priority, created_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&task.task_id)
+            .bind(&task.session_id)
+            .bind(task.novel_id.to_string())
+            .bind(task.voice_id.to_string())
+            .bind(task.segment_index as i64)
+            .bind(&task.segment_content)
+            .bind(params)
+            .bind(task.explicit)
+            .bind(task.priority.as_str())
+            .bind(task.created_at.to_rfc3339())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn find_all(&self) -> Result<Vec<QueuedTaskRecord>, RepositoryError> {
+        let rows: Vec<QueuedTaskRow> = sqlx::query_as(
+            "SELECT task_id, session_id, novel_id, voice_id, segment_index, segment_content, params, explicit, priority, created_at FROM queued_tasks ORDER BY created_at",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        rows.into_iter().map(QueuedTaskRecord::try_from).collect()
+    }
+
+    async fn delete(&self, task_ids: &[String]) -> Result<(), RepositoryError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        for task_id in task_ids {
+            sqlx::query("DELETE FROM queued_tasks WHERE task_id = ?")
+                .bind(task_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn delete_all(&self) -> Result<(), RepositoryError> {
+        sqlx::query("DELETE FROM queued_tasks")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+//! SQLite Reading Progress Repository
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use super::{parse_user_key, user_key, DbPool};
+use crate::application::ports::{
+    ReadingProgressRecord, ReadingProgressRepositoryPort, RepositoryError,
+};
+
+/// SQLite Reading Progress Repository
+pub struct SqliteReadingProgressRepository {
+    pool: DbPool,
+}
+
+impl SqliteReadingProgressRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(FromRow)]
+struct ReadingProgressRow {
+    user_id: String,
+    novel_id: String,
+    segment_index: i64,
+    offset_ms: i64,
+    updated_at: String,
+}
+
+impl TryFrom<ReadingProgressRow> for ReadingProgressRecord {
+    type Error = RepositoryError;
+
+    fn try_from(row: ReadingProgressRow) -> Result<Self, Self::Error> {
+        Ok(ReadingProgressRecord {
+            user_id: parse_user_key(&row.user_id)
+                .map_err(|e| RepositoryError::SerializationError(e.to_string()))?,
+            novel_id: Uuid::parse_str(&row.novel_id)
+                .map_err(|e| RepositoryError::SerializationError(e.to_string()))?,
+            segment_index: row.segment_index.max(0) as u32,
+            offset_ms: row.offset_ms.max(0) as u32,
+            updated_at: DateTime::parse_from_rfc3339(&row.updated_at)
+                .map_err(|e| RepositoryError::SerializationError(e.to_string()))?
+                .with_timezone(&Utc),
+        })
+    }
+}
+
+#[async_trait]
+impl ReadingProgressRepositoryPort for SqliteReadingProgressRepository {
+    async fn save(&self, record: &ReadingProgressRecord) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO reading_progress (user_id, novel_id, segment_index, offset_ms, updated_at)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT(user_id, novel_id) DO UPDATE SET
+                segment_index = excluded.segment_index,
+                offset_ms = excluded.offset_ms,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(user_key(record.user_id))
+        .bind(record.novel_id.to_string())
+        .bind(record.segment_index as i64)
+        .bind(record.offset_ms as i64)
+        .bind(record.updated_at.to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn find(
+        &self,
+        user_id: Option<Uuid>,
+        novel_id: Uuid,
+    ) -> Result<Option<ReadingProgressRecord>, RepositoryError> {
+        let row: Option<ReadingProgressRow> = sqlx::query_as(
+            "SELECT user_id, novel_id, segment_index, offset_ms, updated_at FROM reading_progress \
+             WHERE user_id = ? AND novel_id = ?",
+        )
+        .bind(user_key(user_id))
+        .bind(novel_id.to_string())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        row.map(ReadingProgressRecord::try_from).transpose()
+    }
+}
@@ -2,14 +2,19 @@
 
 use async_trait::async_trait;
 use chrono::{DateTime, Duration, Utc};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use sqlx::FromRow;
 use uuid::Uuid;
 
 use super::DbPool;
 use crate::application::ports::{
-    RepositoryError, SessionRecord, SessionRepositoryPort, SessionState, WindowConfig,
+    PlaybackHints, RepositoryError, SessionRecord, SessionRepositoryPort, SessionState,
+    WindowConfig,
 };
 
+const SESSION_COLUMNS: &str = "id, novel_id, voice_id, current_index, offset_ms, state, window_before, window_after, params, segment_params, playback_speed, gap_ms, crossfade_ms, client, auto_advance, user_id, created_at, updated_at, last_accessed_at";
+
 /// SQLite Session Repository
 pub struct SqliteSessionRepository {
     pool: DbPool,
@@ -27,9 +32,18 @@ struct SessionRow {
     novel_id: String,
     voice_id: String,
     current_index: i64,
+    offset_ms: i64,
     state: String,
     window_before: i64,
     window_after: i64,
+    params: Option<String>,
+    segment_params: Option<String>,
+    playback_speed: Option<f64>,
+    gap_ms: i64,
+    crossfade_ms: i64,
+    client: Option<String>,
+    auto_advance: bool,
+    user_id: Option<String>,
     created_at: String,
     updated_at: String,
     last_accessed_at: String,
@@ -47,8 +61,23 @@ impl TryFrom<SessionRow> for SessionRecord {
             voice_id: Uuid::parse_str(&row.voice_id)
                 .map_err(|e| RepositoryError::SerializationError(e.to_string()))?,
             current_index: row.current_index as usize,
+            offset_ms: row.offset_ms as u32,
             state: SessionState::from_str(&row.state).unwrap_or(SessionState::Idle),
             window_config: WindowConfig::new(row.window_before as usize, row.window_after as usize),
+            params: from_json(row.params.as_deref())?,
+            segment_params: from_json(row.segment_params.as_deref())?,
+            playback_speed: row.playback_speed.map(|speed| speed as f32),
+            playback_hints: PlaybackHints {
+                gap_ms: row.gap_ms as u32,
+                crossfade_ms: row.crossfade_ms as u32,
+            },
+            client: row.client,
+            auto_advance: row.auto_advance,
+            user_id: row
+                .user_id
+                .map(|id| Uuid::parse_str(&id))
+                .transpose()
+                .map_err(|e| RepositoryError::SerializationError(e.to_string()))?,
             created_at: DateTime::parse_from_rfc3339(&row.created_at)
                 .map_err(|e| RepositoryError::SerializationError(e.to_string()))?
                 .with_timezone(&Utc),
@@ -62,22 +91,44 @@ impl TryFrom<SessionRow> for SessionRecord {
     }
 }
 
+/// 解析 JSON 列（旧记录没有该列的值时使用默认值）
+fn from_json<T: DeserializeOwned + Default>(value: Option<&str>) -> Result<T, RepositoryError> {
+    match value {
+        Some(value) => serde_json::from_str(value)
+            .map_err(|e| RepositoryError::SerializationError(e.to_string())),
+        None => Ok(T::default()),
+    }
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, RepositoryError> {
+    serde_json::to_string(value).map_err(|e| RepositoryError::SerializationError(e.to_string()))
+}
+
 #[async_trait]
 impl SessionRepositoryPort for SqliteSessionRepository {
     async fn save(&self, session: &SessionRecord) -> Result<(), RepositoryError> {
         sqlx::query(
             r#"
-            INSERT INTO sessions (id, novel_id, voice_id, current_index, state, window_before, window_after, created_at, updated_at, last_accessed_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO sessions (id, novel_id, voice_id, current_index, offset_ms, state, window_before, window_after, params, segment_params, playback_speed, gap_ms, crossfade_ms, client, auto_advance, user_id, created_at, updated_at, last_accessed_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(session.id.to_string())
         .bind(session.novel_id.to_string())
         .bind(session.voice_id.to_string())
         .bind(session.current_index as i64)
+        .bind(session.offset_ms as i64)
         .bind(session.state.as_str())
         .bind(session.window_config.before as i64)
         .bind(session.window_config.after as i64)
+        .bind(to_json(&session.params)?)
+        .bind(to_json(&session.segment_params)?)
+        .bind(session.playback_speed.map(f64::from))
+        .bind(session.playback_hints.gap_ms as i64)
+        .bind(session.playback_hints.crossfade_ms as i64)
+        .bind(&session.client)
+        .bind(session.auto_advance)
+        .bind(session.user_id.map(|id| id.to_string()))
         .bind(session.created_at.to_rfc3339())
         .bind(session.updated_at.to_rfc3339())
         .bind(session.last_accessed_at.to_rfc3339())
@@ -90,7 +141,7 @@ impl SessionRepositoryPort for SqliteSessionRepository {
 
     async fn find_by_id(&self, id: Uuid) -> Result<Option<SessionRecord>, RepositoryError> {
         let row: Option<SessionRow> = sqlx::query_as(
-            "SELECT id, novel_id, voice_id, current_index, state, window_before, window_after, created_at, updated_at, last_accessed_at FROM sessions WHERE id = ?",
+            &format!("SELECT {} FROM sessions WHERE id = ?", SESSION_COLUMNS),
         )
         .bind(id.to_string())
         .fetch_optional(&self.pool)
@@ -102,7 +153,7 @@ impl SessionRepositoryPort for SqliteSessionRepository {
 
     async fn find_all(&self) -> Result<Vec<SessionRecord>, RepositoryError> {
         let rows: Vec<SessionRow> = sqlx::query_as(
-            "SELECT id, novel_id, voice_id, current_index, state, window_before, window_after, created_at, updated_at, last_accessed_at FROM sessions ORDER BY created_at DESC",
+            &format!("SELECT {} FROM sessions ORDER BY created_at DESC", SESSION_COLUMNS),
         )
         .fetch_all(&self.pool)
         .await
@@ -115,19 +166,35 @@ impl SessionRepositoryPort for SqliteSessionRepository {
         sqlx::query(
             r#"
             UPDATE sessions SET
+                voice_id = ?,
                 current_index = ?,
+                offset_ms = ?,
                 state = ?,
                 window_before = ?,
                 window_after = ?,
+                params = ?,
+                segment_params = ?,
+                playback_speed = ?,
+                gap_ms = ?,
+                crossfade_ms = ?,
+                auto_advance = ?,
                 updated_at = ?,
                 last_accessed_at = ?
             WHERE id = ?
             "#,
         )
+        .bind(session.voice_id.to_string())
         .bind(session.current_index as i64)
+        .bind(session.offset_ms as i64)
         .bind(session.state.as_str())
         .bind(session.window_config.before as i64)
         .bind(session.window_config.after as i64)
+        .bind(to_json(&session.params)?)
+        .bind(to_json(&session.segment_params)?)
+        .bind(session.playback_speed.map(f64::from))
+        .bind(session.playback_hints.gap_ms as i64)
+        .bind(session.playback_hints.crossfade_ms as i64)
+        .bind(session.auto_advance)
         .bind(session.updated_at.to_rfc3339())
         .bind(session.last_accessed_at.to_rfc3339())
         .bind(session.id.to_string())
@@ -150,7 +217,7 @@ impl SessionRepositoryPort for SqliteSessionRepository {
 
     async fn find_active(&self) -> Result<Vec<SessionRecord>, RepositoryError> {
         let rows: Vec<SessionRow> = sqlx::query_as(
-            "SELECT id, novel_id, voice_id, current_index, state, window_before, window_after, created_at, updated_at, last_accessed_at FROM sessions WHERE state != 'finished' ORDER BY last_accessed_at DESC",
+            &format!("SELECT {} FROM sessions WHERE state != 'finished' ORDER BY last_accessed_at DESC", SESSION_COLUMNS),
         )
         .fetch_all(&self.pool)
         .await
@@ -163,7 +230,7 @@ impl SessionRepositoryPort for SqliteSessionRepository {
         let expire_time = Utc::now() - Duration::seconds(expire_seconds as i64);
 
         let rows: Vec<SessionRow> = sqlx::query_as(
-            "SELECT id, novel_id, voice_id, current_index, state, window_before, window_after, created_at, updated_at, last_accessed_at FROM sessions WHERE last_accessed_at < ?",
+            &format!("SELECT {} FROM sessions WHERE last_accessed_at < ?", SESSION_COLUMNS),
         )
         .bind(expire_time.to_rfc3339())
         .fetch_all(&self.pool)
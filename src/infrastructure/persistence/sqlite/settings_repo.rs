@@ -0,0 +1,85 @@
+//! SQLite Settings Repository
+
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use super::{user_key, DbPool};
+use crate::application::ports::{RepositoryError, SettingsRepositoryPort};
+
+/// SQLite Settings Repository
+pub struct SqliteSettingsRepository {
+    pool: DbPool,
+}
+
+impl SqliteSettingsRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SettingsRepositoryPort for SqliteSettingsRepository {
+    async fn get(&self, user_id: Option<Uuid>, key: &str) -> Result<Option<String>, RepositoryError> {
+        let row: Option<(String,)> =
+            sqlx::query_as("SELECT value FROM settings WHERE user_id = ? AND key = ?")
+                .bind(user_key(user_id))
+                .bind(key)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        Ok(row.map(|(value,)| value))
+    }
+
+    async fn set(&self, user_id: Option<Uuid>, key: &str, value: &str) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO settings (user_id, key, value, updated_at)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT(user_id, key) DO UPDATE SET
+                value = excluded.value,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(user_key(user_id))
+        .bind(key)
+        .bind(value)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, user_id: Option<Uuid>, key: &str) -> Result<(), RepositoryError> {
+        sqlx::query("DELETE FROM settings WHERE user_id = ? AND key = ?")
+            .bind(user_key(user_id))
+            .bind(key)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn delete_by_value(&self, key: &str, value: &str) -> Result<u64, RepositoryError> {
+        let result = sqlx::query("DELETE FROM settings WHERE key = ? AND value = ?")
+            .bind(key)
+            .bind(value)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn find_all(&self, user_id: Option<Uuid>) -> Result<Vec<(String, String)>, RepositoryError> {
+        sqlx::query_as("SELECT key, value FROM settings WHERE user_id = ? ORDER BY key")
+            .bind(user_key(user_id))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))
+    }
+}
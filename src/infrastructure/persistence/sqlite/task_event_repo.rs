@@ -0,0 +1,138 @@
+//! SQLite Task Event Repository
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use super::DbPool;
+use crate::application::ports::{
+    RepositoryError, TaskEventFilter, TaskEventKind, TaskEventRecord, TaskEventRepositoryPort,
+};
+
+/// SQLite Task Event Repository
+pub struct SqliteTaskEventRepository {
+    pool: DbPool,
+}
+
+impl SqliteTaskEventRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(FromRow)]
+struct TaskEventRow {
+    task_id: String,
+    session_id: String,
+    novel_id: String,
+    voice_id: String,
+    segment_index: i64,
+    event: String,
+    error: Option<String>,
+    duration_ms: Option<i64>,
+    request_id: Option<String>,
+    created_at: String,
+}
+
+impl TryFrom<TaskEventRow> for TaskEventRecord {
+    type Error = RepositoryError;
+
+    fn try_from(row: TaskEventRow) -> Result<Self, Self::Error> {
+        let parse_uuid = |value: &str| {
+            Uuid::parse_str(value).map_err(|e| RepositoryError::SerializationError(e.to_string()))
+        };
+        Ok(TaskEventRecord {
+            novel_id: parse_uuid(&row.novel_id)?,
+            voice_id: parse_uuid(&row.voice_id)?,
+            task_id: row.task_id,
+            session_id: row.session_id,
+            segment_index: row.segment_index.max(0) as u32,
+            event: TaskEventKind::from_str(&row.event).ok_or_else(|| {
+                RepositoryError::SerializationError(format!("Unknown task event: {}", row.event))
+            })?,
+            error: row.error,
+            duration_ms: row.duration_ms.map(|ms| ms.max(0) as u64),
+            request_id: row.request_id,
+            created_at: DateTime::parse_from_rfc3339(&row.created_at)
+                .map_err(|e| RepositoryError::SerializationError(e.to_string()))?
+                .with_timezone(&Utc),
+        })
+    }
+}
+
+#[async_trait]
+impl TaskEventRepositoryPort for SqliteTaskEventRepository {
+    async fn append(&self, events: &[TaskEventRecord]) -> Result<(), RepositoryError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        for event in events {
+            sqlx::query(
+                r#"
+                INSERT INTO task_events (task_id, session_id, novel_id, voice_id, segment_index, event, error, duration_ms, request_id, created_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&event.task_id)
+            .bind(&event.session_id)
+            .bind(event.novel_id.to_string())
+            .bind(event.voice_id.to_string())
+            .bind(event.segment_index as i64)
+            .bind(event.event.as_str())
+            .bind(&event.error)
+            .bind(event.duration_ms.map(|ms| ms as i64))
+            .bind(&event.request_id)
+            .bind(event.created_at.to_rfc3339())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn find(&self, filter: &TaskEventFilter) -> Result<Vec<TaskEventRecord>, RepositoryError> {
+        let rows: Vec<TaskEventRow> = sqlx::query_as(
+            r#"
+            SELECT task_id, session_id, novel_id, voice_id, segment_index, event, error, duration_ms, request_id, created_at
+            FROM task_events
+            WHERE (?1 IS NULL OR session_id = ?1)
+              AND (?2 IS NULL OR novel_id = ?2)
+              AND (?3 IS NULL OR segment_index = ?3)
+              AND (?4 IS NULL OR task_id = ?4)
+              AND (?5 IS NULL OR event = ?5)
+              AND (?6 IS NULL OR request_id = ?6)
+            ORDER BY id DESC
+            LIMIT ?7
+            "#,
+        )
+        .bind(&filter.session_id)
+        .bind(filter.novel_id.map(|id| id.to_string()))
+        .bind(filter.segment_index.map(|index| index as i64))
+        .bind(&filter.task_id)
+        .bind(filter.event.map(|event| event.as_str()))
+        .bind(&filter.request_id)
+        .bind(filter.limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        rows.into_iter().map(TaskEventRecord::try_from).collect()
+    }
+
+    async fn delete_before(&self, before: DateTime<Utc>) -> Result<u64, RepositoryError> {
+        let result = sqlx::query("DELETE FROM task_events WHERE created_at < ?")
+            .bind(before.to_rfc3339())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,93 @@
+//! SQLite TTS Engine Status Repository
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+use super::DbPool;
+use crate::application::ports::{
+    RepositoryError, TtsEngineStatusRecord, TtsEngineStatusRepositoryPort,
+};
+
+/// SQLite TTS Engine Status Repository
+pub struct SqliteTtsEngineStatusRepository {
+    pool: DbPool,
+}
+
+impl SqliteTtsEngineStatusRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(FromRow)]
+struct TtsEngineStatusRow {
+    engine: String,
+    healthy: bool,
+    consecutive_failures: i64,
+    last_checked_at: String,
+    last_changed_at: String,
+}
+
+fn parse_time(value: &str) -> Result<DateTime<Utc>, RepositoryError> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|t| t.with_timezone(&Utc))
+        .map_err(|e| RepositoryError::SerializationError(e.to_string()))
+}
+
+impl TryFrom<TtsEngineStatusRow> for TtsEngineStatusRecord {
+    type Error = RepositoryError;
+
+    fn try_from(row: TtsEngineStatusRow) -> Result<Self, Self::Error> {
+        Ok(TtsEngineStatusRecord {
+            engine: row.engine,
+            healthy: row.healthy,
+            consecutive_failures: row.consecutive_failures.max(0) as u32,
+            last_checked_at: parse_time(&row.last_checked_at)?,
+            last_changed_at: parse_time(&row.last_changed_at)?,
+        })
+    }
+}
+
+#[async_trait]
+impl TtsEngineStatusRepositoryPort for SqliteTtsEngineStatusRepository {
+    async fn find_all(&self) -> Result<Vec<TtsEngineStatusRecord>, RepositoryError> {
+        let rows: Vec<TtsEngineStatusRow> = sqlx::query_as(
+            r#"
+            SELECT engine, healthy, consecutive_failures, last_checked_at, last_changed_at
+            FROM tts_engine_status
+            ORDER BY engine
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        rows.into_iter().map(TtsEngineStatusRecord::try_from).collect()
+    }
+
+    async fn save(&self, record: &TtsEngineStatusRecord) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO tts_engine_status
+                (engine, healthy, consecutive_failures, last_checked_at, last_changed_at)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT(engine) DO UPDATE SET
+                healthy = excluded.healthy,
+                consecutive_failures = excluded.consecutive_failures,
+                last_checked_at = excluded.last_checked_at,
+                last_changed_at = excluded.last_changed_at
+            "#,
+        )
+        .bind(&record.engine)
+        .bind(record.healthy)
+        .bind(record.consecutive_failures as i64)
+        .bind(record.last_checked_at.to_rfc3339())
+        .bind(record.last_changed_at.to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+}
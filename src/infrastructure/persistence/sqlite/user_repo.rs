@@ -0,0 +1,133 @@
+//! SQLite User Repository
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use super::DbPool;
+use crate::application::ports::{RepositoryError, UserRecord, UserRepositoryPort};
+
+/// SQLite User Repository
+pub struct SqliteUserRepository {
+    pool: DbPool,
+}
+
+impl SqliteUserRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(FromRow)]
+struct UserRow {
+    id: String,
+    username: String,
+    password_hash: String,
+    is_admin: bool,
+    created_at: String,
+}
+
+impl TryFrom<UserRow> for UserRecord {
+    type Error = RepositoryError;
+
+    fn try_from(row: UserRow) -> Result<Self, Self::Error> {
+        Ok(UserRecord {
+            id: Uuid::parse_str(&row.id)
+                .map_err(|e| RepositoryError::SerializationError(e.to_string()))?,
+            username: row.username,
+            password_hash: row.password_hash,
+            is_admin: row.is_admin,
+            created_at: DateTime::parse_from_rfc3339(&row.created_at)
+                .map_err(|e| RepositoryError::SerializationError(e.to_string()))?
+                .with_timezone(&Utc),
+        })
+    }
+}
+
+#[async_trait]
+impl UserRepositoryPort for SqliteUserRepository {
+    async fn create(&self, user: &UserRecord, first_only: bool) -> Result<Option<bool>, RepositoryError> {
+        // 写语句开始时即持有写锁，"是否已有用户"的判断和插入不会与其他注册交错
+        let row: Option<(bool,)> = sqlx::query_as(
+            r#"
+            INSERT INTO users (id, username, password_hash, is_admin, created_at)
+            SELECT ?1, ?2, ?3, NOT EXISTS (SELECT 1 FROM users), ?4
+            WHERE NOT ?5 OR NOT EXISTS (SELECT 1 FROM users)
+            RETURNING is_admin
+            "#,
+        )
+        .bind(user.id.to_string())
+        .bind(&user.username)
+        .bind(&user.password_hash)
+        .bind(user.created_at.to_rfc3339())
+        .bind(first_only)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| match e.as_database_error() {
+            Some(db_error) if db_error.is_unique_violation() => {
+                RepositoryError::Duplicate(format!("User {}", user.username))
+            }
+            _ => RepositoryError::DatabaseError(e.to_string()),
+        })?;
+
+        Ok(row.map(|(is_admin,)| is_admin))
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<UserRecord>, RepositoryError> {
+        let row: Option<UserRow> = sqlx::query_as(
+            "SELECT id, username, password_hash, is_admin, created_at FROM users WHERE id = ?",
+        )
+        .bind(id.to_string())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        row.map(UserRecord::try_from).transpose()
+    }
+
+    async fn find_by_username(&self, username: &str) -> Result<Option<UserRecord>, RepositoryError> {
+        let row: Option<UserRow> = sqlx::query_as(
+            "SELECT id, username, password_hash, is_admin, created_at FROM users WHERE username = ?",
+        )
+        .bind(username)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        row.map(UserRecord::try_from).transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::persistence::sqlite::{create_pool, run_migrations, DatabaseConfig};
+
+    fn user(username: &str) -> UserRecord {
+        UserRecord {
+            id: Uuid::new_v4(),
+            username: username.to_string(),
+            password_hash: String::new(),
+            is_admin: false,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_only_first_user_becomes_admin() {
+        let pool = create_pool(&DatabaseConfig::in_memory()).await.unwrap();
+        run_migrations(&pool).await.unwrap();
+        let repo = SqliteUserRepository::new(pool);
+
+        assert_eq!(repo.create(&user("alice"), true).await.unwrap(), Some(true));
+        // 已有用户：关闭注册时不插入，开放注册时为普通用户
+        assert_eq!(repo.create(&user("bob"), true).await.unwrap(), None);
+        assert!(repo.find_by_username("bob").await.unwrap().is_none());
+        assert_eq!(repo.create(&user("bob"), false).await.unwrap(), Some(false));
+        assert!(matches!(
+            repo.create(&user("bob"), false).await,
+            Err(RepositoryError::Duplicate(_))
+        ));
+    }
+}
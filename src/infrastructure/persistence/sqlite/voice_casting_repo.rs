@@ -0,0 +1,97 @@
+//! SQLite Voice Casting Repository
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use super::DbPool;
+use crate::application::ports::{RepositoryError, VoiceCastingRecord, VoiceCastingRepositoryPort};
+
+/// SQLite Voice Casting Repository
+pub struct SqliteVoiceCastingRepository {
+    pool: DbPool,
+}
+
+impl SqliteVoiceCastingRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(FromRow)]
+struct VoiceCastingRow {
+    novel_id: String,
+    character_name: String,
+    voice_id: String,
+    updated_at: String,
+}
+
+impl TryFrom<VoiceCastingRow> for VoiceCastingRecord {
+    type Error = RepositoryError;
+
+    fn try_from(row: VoiceCastingRow) -> Result<Self, Self::Error> {
+        Ok(VoiceCastingRecord {
+            novel_id: Uuid::parse_str(&row.novel_id)
+                .map_err(|e| RepositoryError::SerializationError(e.to_string()))?,
+            character_name: row.character_name,
+            voice_id: Uuid::parse_str(&row.voice_id)
+                .map_err(|e| RepositoryError::SerializationError(e.to_string()))?,
+            updated_at: DateTime::parse_from_rfc3339(&row.updated_at)
+                .map_err(|e| RepositoryError::SerializationError(e.to_string()))?
+                .with_timezone(&Utc),
+        })
+    }
+}
+
+#[async_trait]
+impl VoiceCastingRepositoryPort for SqliteVoiceCastingRepository {
+    async fn find_by_novel(&self, novel_id: Uuid) -> Result<Vec<VoiceCastingRecord>, RepositoryError> {
+        let rows: Vec<VoiceCastingRow> = sqlx::query_as(
+            r#"
+            SELECT novel_id, character_name, voice_id, updated_at
+            FROM novel_voice_casting
+            WHERE novel_id = ?
+            ORDER BY character_name
+            "#,
+        )
+        .bind(novel_id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        rows.into_iter().map(VoiceCastingRecord::try_from).collect()
+    }
+
+    async fn set(&self, novel_id: Uuid, character_name: &str, voice_id: Uuid) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO novel_voice_casting (novel_id, character_name, voice_id, updated_at)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT(novel_id, character_name) DO UPDATE SET
+                voice_id = excluded.voice_id,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(novel_id.to_string())
+        .bind(character_name)
+        .bind(voice_id.to_string())
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, novel_id: Uuid, character_name: &str) -> Result<(), RepositoryError> {
+        sqlx::query("DELETE FROM novel_voice_casting WHERE novel_id = ? AND character_name = ?")
+            .bind(novel_id.to_string())
+            .bind(character_name)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+}
@@ -8,6 +8,7 @@ use uuid::Uuid;
 
 use super::DbPool;
 use crate::application::ports::{RepositoryError, VoiceRecord, VoiceRepositoryPort};
+use crate::domain::voice::{TtsConfig, VoiceTags};
 
 /// SQLite Voice Repository
 pub struct SqliteVoiceRepository {
@@ -25,7 +26,19 @@ struct VoiceRow {
     id: String,
     name: String,
     reference_audio_path: String,
+    processed_audio_path: Option<String>,
     description: Option<String>,
+    prompt_text: Option<String>,
+    engine: Option<String>,
+    speed: f64,
+    pitch: i64,
+    volume: f64,
+    temperature: Option<f64>,
+    top_p: Option<f64>,
+    gender: Option<String>,
+    language: Option<String>,
+    style: Option<String>,
+    user_id: Option<String>,
     created_at: String,
 }
 
@@ -38,7 +51,27 @@ impl TryFrom<VoiceRow> for VoiceRecord {
                 .map_err(|e| RepositoryError::SerializationError(e.to_string()))?,
             name: row.name,
             reference_audio_path: PathBuf::from(row.reference_audio_path),
+            processed_audio_path: row.processed_audio_path.map(PathBuf::from),
             description: row.description,
+            prompt_text: row.prompt_text,
+            engine: row.engine,
+            tts_config: TtsConfig {
+                speed: row.speed as f32,
+                pitch: row.pitch as i8,
+                volume: row.volume as f32,
+                temperature: row.temperature.map(|t| t as f32),
+                top_p: row.top_p.map(|p| p as f32),
+            },
+            tags: VoiceTags {
+                gender: row.gender,
+                language: row.language,
+                style: row.style,
+            },
+            user_id: row
+                .user_id
+                .map(|id| Uuid::parse_str(&id))
+                .transpose()
+                .map_err(|e| RepositoryError::SerializationError(e.to_string()))?,
             created_at: DateTime::parse_from_rfc3339(&row.created_at)
                 .map_err(|e| RepositoryError::SerializationError(e.to_string()))?
                 .with_timezone(&Utc),
@@ -51,18 +84,46 @@ impl VoiceRepositoryPort for SqliteVoiceRepository {
     async fn save(&self, voice: &VoiceRecord) -> Result<(), RepositoryError> {
         sqlx::query(
             r#"
-            INSERT INTO voices (id, name, reference_audio_path, description, created_at)
-            VALUES (?, ?, ?, ?, ?)
+            INSERT INTO voices (id, name, reference_audio_path, processed_audio_path, description, prompt_text, engine, speed, pitch, volume, temperature, top_p, gender, language, style, user_id, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             ON CONFLICT(id) DO UPDATE SET
                 name = excluded.name,
                 reference_audio_path = excluded.reference_audio_path,
-                description = excluded.description
+                processed_audio_path = excluded.processed_audio_path,
+                description = excluded.description,
+                prompt_text = excluded.prompt_text,
+                engine = excluded.engine,
+                speed = excluded.speed,
+                pitch = excluded.pitch,
+                volume = excluded.volume,
+                temperature = excluded.temperature,
+                top_p = excluded.top_p,
+                gender = excluded.gender,
+                language = excluded.language,
+                style = excluded.style
             "#,
         )
         .bind(voice.id.to_string())
         .bind(&voice.name)
         .bind(voice.reference_audio_path.to_string_lossy().to_string())
+        .bind(
+            voice
+                .processed_audio_path
+                .as_ref()
+                .map(|p| p.to_string_lossy().to_string()),
+        )
         .bind(&voice.description)
+        .bind(&voice.prompt_text)
+        .bind(&voice.engine)
+        .bind(voice.tts_config.speed as f64)
+        .bind(voice.tts_config.pitch as i64)
+        .bind(voice.tts_config.volume as f64)
+        .bind(voice.tts_config.temperature.map(|t| t as f64))
+        .bind(voice.tts_config.top_p.map(|p| p as f64))
+        .bind(&voice.tags.gender)
+        .bind(&voice.tags.language)
+        .bind(&voice.tags.style)
+        .bind(voice.user_id.map(|id| id.to_string()))
         .bind(voice.created_at.to_rfc3339())
         .execute(&self.pool)
         .await
@@ -73,7 +134,7 @@ impl VoiceRepositoryPort for SqliteVoiceRepository {
 
     async fn find_by_id(&self, id: Uuid) -> Result<Option<VoiceRecord>, RepositoryError> {
         let row: Option<VoiceRow> = sqlx::query_as(
-            "SELECT id, name, reference_audio_path, description, created_at FROM voices WHERE id = ?",
+            "SELECT id, name, reference_audio_path, processed_audio_path, description, prompt_text, engine, speed, pitch, volume, temperature, top_p, gender, language, style, user_id, created_at FROM voices WHERE id = ?",
         )
         .bind(id.to_string())
         .fetch_optional(&self.pool)
@@ -85,7 +146,7 @@ impl VoiceRepositoryPort for SqliteVoiceRepository {
 
     async fn find_all(&self) -> Result<Vec<VoiceRecord>, RepositoryError> {
         let rows: Vec<VoiceRow> = sqlx::query_as(
-            "SELECT id, name, reference_audio_path, description, created_at FROM voices ORDER BY created_at DESC",
+            "SELECT id, name, reference_audio_path, processed_audio_path, description, prompt_text, engine, speed, pitch, volume, temperature, top_p, gender, language, style, user_id, created_at FROM voices ORDER BY created_at DESC",
         )
         .fetch_all(&self.pool)
         .await
@@ -95,12 +156,40 @@ impl VoiceRepositoryPort for SqliteVoiceRepository {
     }
 
     async fn delete(&self, id: Uuid) -> Result<(), RepositoryError> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        // 删除引用该音色的角色配音
+        sqlx::query("DELETE FROM novel_voice_casting WHERE voice_id = ?")
+            .bind(id.to_string())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        // 删除使用该音色的会话记录及其音频段落记录
+        sqlx::query(
+            "DELETE FROM audio_segments WHERE session_id IN (SELECT id FROM sessions WHERE voice_id = ?)"
+        )
+        .bind(id.to_string())
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        sqlx::query("DELETE FROM sessions WHERE voice_id = ?")
+            .bind(id.to_string())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
         sqlx::query("DELETE FROM voices WHERE id = ?")
             .bind(id.to_string())
-            .execute(&self.pool)
+            .execute(&mut *tx)
             .await
             .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
 
+        tx.commit().await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
         Ok(())
     }
 }
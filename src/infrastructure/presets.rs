@@ -0,0 +1,311 @@
+//! Voice Presets - 内置音色预置
+//!
+//! 启动时从预置目录注册音色，新部署无需手动上传即可使用。
+//!
+//! 目录结构：
+//! ```text
+//! data/presets/
+//! ├── presets.toml
+//! ├── narrator.wav
+//! └── girl.flac
+//! ```
+//!
+//! `presets.toml`：
+//! ```toml
+//! [[voice]]
+//! id = "6f1c1f5e-6a5e-4c36-9a8e-0d3f4a1b2c3d"   # 固定 ID，重复启动不会重复注册
+//! name = "旁白"
+//! file = "narrator.wav"
+//! description = "沉稳的男声"
+//! prompt_text = "参考音频对应的文本"
+//! engine = "cosyvoice"                          # 可选，[tts.engines] 中的引擎名
+//! speed = 1.0
+//! pitch = 0
+//! gender = "male"
+//! language = "zh"
+//! style = "narration"
+//! ```
+//!
+//! 已存在的音色（同 ID）会被跳过，不覆盖用户的修改。
+
+use chrono::Utc;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::application::ports::{
+    AudioCachePort, AudioTranscoderPort, ReferenceNormalizeConfig, VoiceRepositoryPort,
+};
+use crate::application::{
+    ApplicationError, ImportVoice, ImportVoiceHandler, NormalizeVoiceAudio,
+    NormalizeVoiceAudioHandler,
+};
+use crate::domain::voice::{TtsConfig, VoiceTags};
+
+/// 预置清单文件名
+pub const PRESET_MANIFEST_FILE: &str = "presets.toml";
+
+/// 支持的参考音频扩展名
+const PRESET_AUDIO_EXTS: &[&str] = &["wav", "mp3", "flac", "ogg"];
+
+/// 预置错误
+#[derive(Debug, Error)]
+pub enum PresetError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid preset manifest: {0}")]
+    InvalidManifest(String),
+
+    #[error("Invalid preset '{name}': {reason}")]
+    InvalidPreset { name: String, reason: String },
+
+    #[error(transparent)]
+    Application(#[from] ApplicationError),
+}
+
+/// 预置清单
+#[derive(Debug, Clone, Deserialize)]
+pub struct PresetManifest {
+    #[serde(default, rename = "voice")]
+    pub voices: Vec<VoicePreset>,
+}
+
+/// 单个预置音色
+#[derive(Debug, Clone, Deserialize)]
+pub struct VoicePreset {
+    pub id: Uuid,
+    pub name: String,
+    /// 参考音频文件（相对预置目录）
+    pub file: PathBuf,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub prompt_text: Option<String>,
+    /// TTS 引擎名称（不填使用默认引擎）
+    #[serde(default)]
+    pub engine: Option<String>,
+    #[serde(default)]
+    pub speed: Option<f32>,
+    #[serde(default)]
+    pub pitch: Option<i8>,
+    #[serde(default)]
+    pub volume: Option<f32>,
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    #[serde(default)]
+    pub top_p: Option<f32>,
+    #[serde(default)]
+    pub gender: Option<String>,
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub style: Option<String>,
+}
+
+impl VoicePreset {
+    fn tts_config(&self) -> TtsConfig {
+        let default = TtsConfig::default();
+        TtsConfig {
+            speed: self.speed.unwrap_or(default.speed),
+            pitch: self.pitch.unwrap_or(default.pitch),
+            volume: self.volume.unwrap_or(default.volume),
+            temperature: self.temperature,
+            top_p: self.top_p,
+        }
+    }
+
+    fn tags(&self) -> VoiceTags {
+        VoiceTags {
+            gender: self.gender.as_deref().and_then(VoiceTags::normalize_value),
+            language: self.language.as_deref().and_then(VoiceTags::normalize_value),
+            style: self.style.as_deref().and_then(VoiceTags::normalize_value),
+        }
+    }
+
+    /// 参考音频扩展名（小写），不支持的格式返回 None
+    fn audio_ext(&self) -> Option<String> {
+        self.file
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase())
+            .filter(|e| PRESET_AUDIO_EXTS.contains(&e.as_str()))
+    }
+}
+
+impl PresetManifest {
+    /// 解析清单内容
+    pub fn parse(content: &str) -> Result<Self, PresetError> {
+        toml::from_str(content).map_err(|e| PresetError::InvalidManifest(e.to_string()))
+    }
+}
+
+/// 预置结果
+#[derive(Debug, Default, Clone)]
+pub struct SeedReport {
+    /// 新注册的音色
+    pub created: Vec<Uuid>,
+    /// 已存在而跳过的音色
+    pub skipped: Vec<Uuid>,
+    /// 注册失败的音色（名称）
+    pub failed: Vec<String>,
+}
+
+/// 音色预置器
+pub struct VoicePresetSeeder {
+    voice_repo: Arc<dyn VoiceRepositoryPort>,
+    import_handler: ImportVoiceHandler,
+    normalize_handler: NormalizeVoiceAudioHandler,
+    /// 参考音频存储目录
+    voices_dir: PathBuf,
+}
+
+impl VoicePresetSeeder {
+    pub fn new(
+        voice_repo: Arc<dyn VoiceRepositoryPort>,
+        audio_cache: Arc<dyn AudioCachePort>,
+        audio_transcoder: Arc<dyn AudioTranscoderPort>,
+        reference_normalize: Option<ReferenceNormalizeConfig>,
+        voices_dir: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            import_handler: ImportVoiceHandler::new(voice_repo.clone(), audio_cache),
+            normalize_handler: NormalizeVoiceAudioHandler::new(
+                voice_repo.clone(),
+                audio_transcoder,
+                reference_normalize,
+            ),
+            voice_repo,
+            voices_dir: voices_dir.into(),
+        }
+    }
+
+    /// 从预置目录注册音色；目录或清单不存在时不做任何事
+    ///
+    /// 单个预置失败只记录日志，不影响其他预置
+    pub async fn seed(&self, presets_dir: &Path) -> Result<SeedReport, PresetError> {
+        let manifest_path = presets_dir.join(PRESET_MANIFEST_FILE);
+        if !manifest_path.exists() {
+            tracing::debug!(path = ?manifest_path, "No voice preset manifest, skipping seeding");
+            return Ok(SeedReport::default());
+        }
+
+        let content = tokio::fs::read_to_string(&manifest_path).await?;
+        let manifest = PresetManifest::parse(&content)?;
+
+        let mut report = SeedReport::default();
+        for preset in &manifest.voices {
+            match self.seed_one(presets_dir, preset).await {
+                Ok(true) => report.created.push(preset.id),
+                Ok(false) => report.skipped.push(preset.id),
+                Err(e) => {
+                    tracing::warn!(
+                        voice_id = %preset.id,
+                        name = %preset.name,
+                        error = %e,
+                        "Failed to seed voice preset"
+                    );
+                    report.failed.push(preset.name.clone());
+                }
+            }
+        }
+
+        tracing::info!(
+            created = report.created.len(),
+            skipped = report.skipped.len(),
+            failed = report.failed.len(),
+            "Voice presets seeded"
+        );
+
+        Ok(report)
+    }
+
+    /// 注册单个预置，返回是否新建
+    async fn seed_one(&self, presets_dir: &Path, preset: &VoicePreset) -> Result<bool, PresetError> {
+        if self.voice_repo.find_by_id(preset.id).await.map_err(ApplicationError::from)?.is_some() {
+            return Ok(false);
+        }
+
+        let invalid = |reason: &str| PresetError::InvalidPreset {
+            name: preset.name.clone(),
+            reason: reason.to_string(),
+        };
+        let ext = preset
+            .audio_ext()
+            .ok_or_else(|| invalid("only WAV, MP3, FLAC, OGG audio files are allowed"))?;
+        let tts_config = preset.tts_config();
+        tts_config.validate().map_err(invalid)?;
+
+        let audio = tokio::fs::read(presets_dir.join(&preset.file)).await?;
+        tokio::fs::create_dir_all(&self.voices_dir).await?;
+        let audio_path = self.voices_dir.join(format!("{}.{}", preset.id, ext));
+        tokio::fs::write(&audio_path, &audio).await?;
+
+        let command = ImportVoice {
+            voice_id: preset.id,
+            name: preset.name.clone(),
+            description: preset.description.clone(),
+            prompt_text: preset.prompt_text.clone(),
+            engine: preset.engine.clone(),
+            reference_audio_path: audio_path.clone(),
+            tts_config,
+            tags: preset.tags(),
+            created_at: Utc::now(),
+            overwrite: false,
+            // 预置音色对所有用户共享
+            user_id: None,
+        };
+        if let Err(e) = self.import_handler.handle(command).await {
+            let _ = tokio::fs::remove_file(&audio_path).await;
+            return Err(e.into());
+        }
+
+        // 归一化失败不影响使用原始音频
+        if let Err(e) = self
+            .normalize_handler
+            .handle(NormalizeVoiceAudio { voice_id: preset.id })
+            .await
+        {
+            tracing::warn!(voice_id = %preset.id, error = %e, "Failed to normalize preset audio");
+        }
+
+        tracing::info!(voice_id = %preset.id, name = %preset.name, "Voice preset registered");
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest = PresetManifest::parse(
+            r#"
+            [[voice]]
+            id = "6f1c1f5e-6a5e-4c36-9a8e-0d3f4a1b2c3d"
+            name = "旁白"
+            file = "narrator.WAV"
+            speed = 1.2
+            gender = " Male "
+
+            [[voice]]
+            id = "0b7e3c1a-2d4f-4e5a-8b9c-1d2e3f4a5b6c"
+            name = "少女"
+            file = "girl.aac"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(manifest.voices.len(), 2);
+        let narrator = &manifest.voices[0];
+        assert_eq!(narrator.audio_ext().as_deref(), Some("wav"));
+        assert_eq!(narrator.tts_config().speed, 1.2);
+        assert_eq!(narrator.tags().gender.as_deref(), Some("Male"));
+        assert_eq!(manifest.voices[1].audio_ext(), None);
+
+        assert!(PresetManifest::parse("[[voice]]\nname = \"missing id\"").is_err());
+    }
+}
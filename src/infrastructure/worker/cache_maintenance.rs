@@ -0,0 +1,70 @@
+//! Cache Maintenance - 音频缓存定期落盘与磁盘占用监控
+//!
+//! sled 在后台按自身节奏落盘，非正常退出时可能丢失最近写入的条目；这里按固定间隔主动落盘，
+//! 并检查磁盘占用相对条目总大小的放大率，放大过高（大量覆盖/删除后未回收）时记录警告
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::application::ports::AudioCachePort;
+
+/// 空间放大率超过该值时警告
+const AMPLIFICATION_WARN_THRESHOLD: f64 = 3.0;
+
+/// 放大率只在条目总大小达到该值后检查（缓存很小时日志和元数据占比高，没有意义）
+const AMPLIFICATION_MIN_BYTES: u64 = 64 * 1024 * 1024;
+
+/// 音频缓存维护后台任务
+pub struct CacheMaintenance {
+    interval: Duration,
+    audio_cache: Arc<dyn AudioCachePort>,
+}
+
+impl CacheMaintenance {
+    pub fn new(interval: Duration, audio_cache: Arc<dyn AudioCachePort>) -> Self {
+        Self {
+            interval,
+            audio_cache,
+        }
+    }
+
+    /// 启动维护循环
+    pub async fn run(self) {
+        tracing::info!(interval_secs = self.interval.as_secs(), "CacheMaintenance started");
+
+        let mut ticker = tokio::time::interval(self.interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut amplified = false;
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.audio_cache.flush().await {
+                tracing::warn!(error = %e, "Failed to flush audio cache");
+            }
+
+            let stats = self.audio_cache.stats().await;
+            let Some(disk_size_bytes) = stats.disk_size_bytes else {
+                continue;
+            };
+            let amplification = disk_size_bytes as f64 / stats.total_size_bytes.max(1) as f64;
+            tracing::debug!(
+                disk_size_bytes,
+                total_size_bytes = stats.total_size_bytes,
+                amplification,
+                "Audio cache disk usage"
+            );
+
+            // 只在越过阈值时记录，避免每次检查重复警告
+            let over = stats.total_size_bytes >= AMPLIFICATION_MIN_BYTES
+                && amplification > AMPLIFICATION_WARN_THRESHOLD;
+            if over && !amplified {
+                tracing::warn!(
+                    disk_size_bytes,
+                    total_size_bytes = stats.total_size_bytes,
+                    amplification,
+                    "Audio cache disk usage far exceeds cached data"
+                );
+            }
+            amplified = over;
+        }
+    }
+}
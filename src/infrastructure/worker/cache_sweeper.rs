@@ -0,0 +1,38 @@
+//! Cache Sweeper - 定期清理过期的音频缓存
+//!
+//! 大小淘汰只在写入时触发，缓存未满时被弃读小说的音频会一直保留；
+//! 按固定间隔删除超过保留时长未访问的条目，自动回收这部分空间
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::application::ports::AudioCachePort;
+
+/// 音频缓存过期清理后台任务
+pub struct CacheSweeper {
+    interval: Duration,
+    audio_cache: Arc<dyn AudioCachePort>,
+}
+
+impl CacheSweeper {
+    pub fn new(interval: Duration, audio_cache: Arc<dyn AudioCachePort>) -> Self {
+        Self {
+            interval,
+            audio_cache,
+        }
+    }
+
+    /// 启动清理循环
+    pub async fn run(self) {
+        tracing::info!(interval_secs = self.interval.as_secs(), "CacheSweeper started");
+
+        let mut ticker = tokio::time::interval(self.interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.audio_cache.remove_expired().await {
+                tracing::warn!(error = %e, "Failed to remove expired cache entries");
+            }
+        }
+    }
+}
@@ -0,0 +1,203 @@
+//! GC Service - 定期回收会话与会话音频存储
+//!
+//! 每轮依次执行：
+//! - 关闭超过过期时间无活动的内存会话（取消其待处理任务，见 SessionExpirySweeper）
+//! - 删除过期的持久化会话及其音频段落记录和文件
+//! - 删除活跃会话中播放窗口外、超过保留时间未访问的音频段落
+//! - 清理数据库中已无记录的过期会话目录，并按 LRU 将存储占用控制在上限内
+//!
+//! 有清理内容时在全局 WebSocket 广播 GcCompleted 摘要。管理接口可随时触发一轮（见 [`GcService::run_once`]）
+
+use chrono::{Duration as ChronoDuration, Utc};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::application::error::ApplicationError;
+use super::SessionExpirySweeper;
+use crate::application::ports::{
+    AudioSegmentRepositoryPort, AudioStoragePort, GcConfig, GcResult, SessionRecord,
+    SessionRepositoryPort,
+};
+use crate::infrastructure::events::EventPublisher;
+
+/// 一轮 GC 的结果
+#[derive(Debug, Clone, Default)]
+pub struct GcSummary {
+    /// 关闭的空闲内存会话数
+    pub closed_sessions: u64,
+    pub storage: GcResult,
+}
+
+impl GcSummary {
+    fn add(&mut self, result: GcResult) {
+        self.storage.deleted_files += result.deleted_files;
+        self.storage.freed_bytes += result.freed_bytes;
+        self.storage.cleaned_sessions += result.cleaned_sessions;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.closed_sessions == 0
+            && self.storage.deleted_files == 0
+            && self.storage.cleaned_sessions == 0
+    }
+}
+
+/// GC 后台任务
+pub struct GcService {
+    /// 同一时间只执行一轮（定时与手动触发互斥）
+    running: tokio::sync::Mutex<()>,
+    config: GcConfig,
+    session_expiry: Arc<SessionExpirySweeper>,
+    session_repo: Arc<dyn SessionRepositoryPort>,
+    audio_segment_repo: Arc<dyn AudioSegmentRepositoryPort>,
+    audio_storage: Arc<dyn AudioStoragePort>,
+    event_publisher: Arc<EventPublisher>,
+}
+
+impl GcService {
+    pub fn new(
+        config: GcConfig,
+        session_expiry: Arc<SessionExpirySweeper>,
+        session_repo: Arc<dyn SessionRepositoryPort>,
+        audio_segment_repo: Arc<dyn AudioSegmentRepositoryPort>,
+        audio_storage: Arc<dyn AudioStoragePort>,
+        event_publisher: Arc<EventPublisher>,
+    ) -> Self {
+        Self {
+            running: tokio::sync::Mutex::new(()),
+            config,
+            session_expiry,
+            session_repo,
+            audio_segment_repo,
+            audio_storage,
+            event_publisher,
+        }
+    }
+
+    /// 启动 GC 循环
+    pub async fn run(self: Arc<Self>) {
+        tracing::info!(
+            interval_secs = self.config.gc_interval_secs,
+            session_expire_secs = self.config.session_expire_secs,
+            max_storage_bytes = self.config.max_storage_bytes,
+            "GcService started"
+        );
+
+        let mut ticker = tokio::time::interval(Duration::from_secs(self.config.gc_interval_secs));
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            self.run_once().await;
+        }
+    }
+
+    /// 立即执行一轮 GC（已有一轮在执行时等待其结束后再执行），有清理内容时广播摘要
+    pub async fn run_once(&self) -> GcSummary {
+        let _running = self.running.lock().await;
+        let summary = self.collect().await;
+        if summary.is_empty() {
+            return summary;
+        }
+
+        tracing::info!(
+            closed_sessions = summary.closed_sessions,
+            cleaned_sessions = summary.storage.cleaned_sessions,
+            deleted_files = summary.storage.deleted_files,
+            freed_bytes = summary.storage.freed_bytes,
+            "GC completed"
+        );
+        self.event_publisher.publish_gc_completed(
+            summary.closed_sessions,
+            summary.storage.cleaned_sessions,
+            summary.storage.deleted_files,
+            summary.storage.freed_bytes,
+        );
+        summary
+    }
+
+    /// 执行一轮 GC（单个步骤失败只记录日志，不影响其他步骤）
+    async fn collect(&self) -> GcSummary {
+        let sweep = self.session_expiry.sweep().await;
+        let mut summary = GcSummary {
+            closed_sessions: sweep.closed_sessions,
+            ..Default::default()
+        };
+        summary.add(sweep.storage);
+
+        match self.session_repo.find_expired(self.config.session_expire_secs).await {
+            Ok(sessions) => {
+                for session in &sessions {
+                    match self.remove_session(session).await {
+                        Ok(result) => summary.add(result),
+                        Err(e) => tracing::warn!(session_id = %session.id, error = %e, "Failed to clean expired session"),
+                    }
+                }
+            }
+            Err(e) => tracing::warn!(error = %e, "Failed to find expired sessions"),
+        }
+
+        match self.session_repo.find_active().await {
+            Ok(sessions) => {
+                for session in &sessions {
+                    match self.evict_outside_window(session).await {
+                        Ok(result) => summary.add(result),
+                        Err(e) => tracing::warn!(session_id = %session.id, error = %e, "Failed to evict segments outside window"),
+                    }
+                }
+            }
+            Err(e) => tracing::warn!(error = %e, "Failed to find active sessions"),
+        }
+
+        match self.audio_storage.gc(&self.config).await {
+            Ok(result) => summary.add(result),
+            Err(e) => tracing::warn!(error = %e, "Failed to collect audio storage"),
+        }
+
+        summary
+    }
+
+    /// 删除过期会话的音频文件、音频段落记录和会话记录
+    async fn remove_session(&self, session: &SessionRecord) -> Result<GcResult, ApplicationError> {
+        let segments = self.audio_segment_repo.find_by_session(session.id).await?;
+        let freed_bytes = segments.iter().filter_map(|segment| segment.file_size).sum();
+
+        let deleted_files = self
+            .audio_storage
+            .delete_session_audio(session.id)
+            .await
+            .map_err(|e| ApplicationError::StorageError(e.to_string()))?;
+        self.audio_segment_repo.delete_by_session(session.id).await?;
+        self.session_repo.delete(session.id).await?;
+
+        Ok(GcResult {
+            deleted_files,
+            freed_bytes,
+            cleaned_sessions: 1,
+        })
+    }
+
+    /// 删除播放窗口外、超过保留时间未访问的音频段落
+    async fn evict_outside_window(&self, session: &SessionRecord) -> Result<GcResult, ApplicationError> {
+        let window_start = session.current_index.saturating_sub(session.window_config.before);
+        let window_end = session.current_index + session.window_config.after;
+        let cutoff = Utc::now() - ChronoDuration::seconds(self.config.window_evict_delay_secs as i64);
+
+        let mut result = GcResult::default();
+        let segments = self
+            .audio_segment_repo
+            .find_outside_window(session.id, window_start, window_end)
+            .await?;
+        for segment in segments.iter().filter(|segment| segment.last_accessed_at < cutoff) {
+            self.audio_storage
+                .delete_audio(session.id, segment.segment_index)
+                .await
+                .map_err(|e| ApplicationError::StorageError(e.to_string()))?;
+            self.audio_segment_repo.delete(segment.id).await?;
+            if segment.audio_path.is_some() {
+                result.deleted_files += 1;
+                result.freed_bytes += segment.file_size.unwrap_or(0);
+            }
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,40 @@
+//! Idle Prefetch Throttle - 暂停空闲会话的预取
+//!
+//! 按固定间隔检查超过 `prefetch.idle_pause_secs` 无活动的会话：取消其窗口内未完成的推理任务
+//! 并暂停预取，直到会话再次活动（位置上报时立即恢复，仅有 WS 活动时在下一次检查时恢复）
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::application::PrefetchCoordinator;
+
+/// 空闲会话预取节流
+pub struct IdlePrefetchThrottle {
+    prefetch: Arc<PrefetchCoordinator>,
+}
+
+impl IdlePrefetchThrottle {
+    pub fn new(prefetch: Arc<PrefetchCoordinator>) -> Self {
+        Self { prefetch }
+    }
+
+    /// 启动检查循环
+    pub async fn run(self, interval: Duration) {
+        tracing::info!(interval_secs = interval.as_secs(), "IdlePrefetchThrottle started");
+
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            let result = self.prefetch.throttle_idle().await;
+            if result.paused > 0 || result.resumed > 0 {
+                tracing::info!(
+                    paused = result.paused,
+                    resumed = result.resumed,
+                    cancelled_tasks = result.cancelled,
+                    "Idle session prefetch throttled"
+                );
+            }
+        }
+    }
+}
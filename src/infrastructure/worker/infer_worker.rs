@@ -1,18 +1,40 @@
 //! Inference Worker - Background TTS Task Processor
+//!
+//! 推理完成后按 AudioConfig 转码写入缓存；持久化会话的音频同时写入会话存储目录，
+//! 并更新音频段落记录（路径、大小、时长），供存储核对和会话清理使用
 
+use futures_util::future::join_all;
+use futures_util::StreamExt;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
-use tokio::sync::mpsc;
+use std::time::Instant;
+use tracing::Instrument;
+use uuid::Uuid;
 
 use crate::application::ports::{
-    generate_cache_key, AudioCachePort, CacheMetadata,
+    generate_cache_key_with_params, AudioCachePort, AudioInfo, AudioSegmentRecord, AudioSegmentRepositoryPort,
+    AudioSegmentState, AudioStoragePort, CacheMetadata,
     SessionManagerPort,
-    TaskManagerPort, TaskState,
-    InferRequest, TtsEnginePort,
+    InferenceTask, TaskManagerPort, TaskState,
+    InferRequest, InferResponse, MarkupType, TtsEnginePort, TtsError,
+    PartialAudioPort,
     VoiceRepositoryPort,
-    AudioTranscoderPort, TranscodeConfig,
+    AudioFormat, AudioMetadata, AudioTranscoderPort, WAVEFORM_POINTS,
 };
+use crate::application::{segment_audio_url, AudioTagResolver, VoiceCastingResolver};
 use crate::config::AudioConfig;
+use crate::infrastructure::adapters::{voice_audio_url, VoiceAudioSigner};
 use crate::infrastructure::events::EventPublisher;
+use crate::infrastructure::memory::TaskQueue;
+
+use super::inflight::{Claim, InflightInference};
+use super::rolling_average::RollingAverage;
+
+/// 推送排队位置的任务数（排在更后面的任务等到前移后再推送）
+const QUEUE_POSITION_LIMIT: usize = 20;
+
+/// 估算等待时间使用的最近推理次数
+const INFERENCE_TIME_WINDOW: usize = 20;
 
 /// Worker 配置
 #[derive(Debug, Clone)]
@@ -21,8 +43,22 @@ pub struct InferWorkerConfig {
     pub max_concurrent: usize,
     /// Rovel 服务的公开 Base URL（供 TTS 服务下载 voice reference）
     pub base_url: String,
+    /// 参考音频下载链接签名（启用账号时）
+    pub voice_audio_signer: Option<VoiceAudioSigner>,
     /// 音频配置
     pub audio: AudioConfig,
+    /// 流式推理：合成中的音频即可通过 /api/audio 播放
+    pub streaming: bool,
+    /// 批量推理的最大片段数（<= 1 表示不批量）
+    ///
+    /// 同一会话、同一音色的相邻片段合并为一次 infer_batch 请求
+    pub batch_size: usize,
+    /// 校验 TTS 返回的音频（WAV 头、时长与文本长度是否匹配）
+    pub validate_audio: bool,
+    /// 固定随机种子，随每个推理请求发送
+    pub seed: Option<u64>,
+    /// 持久化会话的音频同时写入会话存储目录并记录音频段落
+    pub store_session_audio: bool,
 }
 
 impl Default for InferWorkerConfig {
@@ -30,244 +66,790 @@ impl Default for InferWorkerConfig {
         Self {
             max_concurrent: 2,
             base_url: "http://localhost:5060".to_string(),
+            voice_audio_signer: None,
             audio: AudioConfig::default(),
+            streaming: false,
+            batch_size: 1,
+            validate_audio: true,
+            seed: None,
+            store_session_audio: true,
         }
     }
 }
 
 /// 推理 Worker
 ///
-/// 后台任务处理器，从队列消费任务并执行 TTS 推理
+/// 后台任务处理器，按优先级从队列消费任务并执行 TTS 推理；
+/// 每次出队后向排在前面的任务所属会话推送排队位置和预计完成时间（TaskQueued）
 pub struct InferWorker {
     config: InferWorkerConfig,
-    queue_receiver: mpsc::Receiver<String>,
+    queue: Arc<TaskQueue>,
+    context: Arc<WorkerContext>,
+    running: Arc<AtomicBool>,
+    /// 正在推理的任务组数（出队前占用的槽位不计入）
+    active: Arc<AtomicUsize>,
+}
+
+/// Worker 运行状态快照
+#[derive(Debug, Clone)]
+pub struct WorkerStatus {
+    /// 出队循环是否在运行（关闭时置为 false）
+    pub running: bool,
+    pub max_concurrent: usize,
+    /// 正在推理的任务组数（批量推理算一组）
+    pub active: usize,
+    pub batch_size: usize,
+    pub streaming: bool,
+    /// 进行中的不同推理数（按缓存键）
+    pub inflight_inferences: usize,
+    /// 加入进行中推理、等待其结果的任务数
+    pub joined_tasks: usize,
+    /// 最近每个片段的平均推理耗时（毫秒），尚无记录时为 None
+    pub average_inference_ms: Option<u64>,
+}
+
+/// Worker 状态查询句柄（Worker 启动后仍可使用）
+#[derive(Clone)]
+pub struct WorkerMonitor {
+    max_concurrent: usize,
+    batch_size: usize,
+    streaming: bool,
+    context: Arc<WorkerContext>,
+    running: Arc<AtomicBool>,
+    active: Arc<AtomicUsize>,
+}
+
+impl WorkerMonitor {
+    /// 当前状态
+    pub fn status(&self) -> WorkerStatus {
+        let (inflight_inferences, joined_tasks) = self.context.inflight.counts();
+        WorkerStatus {
+            running: self.running.load(Ordering::Acquire),
+            max_concurrent: self.max_concurrent,
+            active: self.active.load(Ordering::Acquire),
+            batch_size: self.batch_size,
+            streaming: self.streaming,
+            inflight_inferences,
+            joined_tasks,
+            average_inference_ms: self.context.inference_time.average(),
+        }
+    }
+}
+
+/// 任务处理依赖（所有并发任务共享）
+struct WorkerContext {
     task_manager: Arc<dyn TaskManagerPort>,
     session_manager: Arc<dyn SessionManagerPort>,
     tts_engine: Arc<dyn TtsEnginePort>,
     audio_cache: Arc<dyn AudioCachePort>,
+    partial_audio: Arc<dyn PartialAudioPort>,
     voice_repo: Arc<dyn VoiceRepositoryPort>,
     audio_transcoder: Arc<dyn AudioTranscoderPort>,
+    audio_segment_repo: Arc<dyn AudioSegmentRepositoryPort>,
+    audio_storage: Arc<dyn AudioStoragePort>,
     event_publisher: Arc<EventPublisher>,
+    voice_casting: VoiceCastingResolver,
+    audio_tags: AudioTagResolver,
+    /// 进行中的推理（按缓存键合并重复任务）
+    inflight: InflightInference,
+    /// 最近每个片段的推理耗时（毫秒）
+    inference_time: RollingAverage,
+    base_url: String,
+    voice_audio_signer: Option<VoiceAudioSigner>,
+    audio_config: AudioConfig,
+    streaming: bool,
+    validate_audio: bool,
+    seed: Option<u64>,
+    store_session_audio: bool,
+}
+
+/// 已通过检查、待推理的任务
+struct PreparedTask {
+    task: InferenceTask,
+    /// 实际使用的音色（角色配音解析后）
+    voice_id: Uuid,
+    cache_key: String,
+    request: InferRequest,
 }
 
 impl InferWorker {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         config: InferWorkerConfig,
-        queue_receiver: mpsc::Receiver<String>,
+        queue: Arc<TaskQueue>,
         task_manager: Arc<dyn TaskManagerPort>,
         session_manager: Arc<dyn SessionManagerPort>,
         tts_engine: Arc<dyn TtsEnginePort>,
         audio_cache: Arc<dyn AudioCachePort>,
+        partial_audio: Arc<dyn PartialAudioPort>,
         voice_repo: Arc<dyn VoiceRepositoryPort>,
         audio_transcoder: Arc<dyn AudioTranscoderPort>,
+        audio_segment_repo: Arc<dyn AudioSegmentRepositoryPort>,
+        audio_storage: Arc<dyn AudioStoragePort>,
         event_publisher: Arc<EventPublisher>,
+        voice_casting: VoiceCastingResolver,
+        audio_tags: AudioTagResolver,
     ) -> Self {
-        Self {
-            config,
-            queue_receiver,
+        let context = Arc::new(WorkerContext {
             task_manager,
             session_manager,
             tts_engine,
             audio_cache,
+            partial_audio,
             voice_repo,
             audio_transcoder,
+            audio_segment_repo,
+            audio_storage,
             event_publisher,
+            voice_casting,
+            audio_tags,
+            inflight: InflightInference::new(),
+            inference_time: RollingAverage::new(INFERENCE_TIME_WINDOW),
+            base_url: config.base_url.clone(),
+            voice_audio_signer: config.voice_audio_signer.clone(),
+            audio_config: config.audio.clone(),
+            streaming: config.streaming,
+            validate_audio: config.validate_audio,
+            seed: config.seed,
+            store_session_audio: config.store_session_audio,
+        });
+        Self {
+            running: Arc::new(AtomicBool::new(false)),
+            active: Arc::new(AtomicUsize::new(0)),
+            config,
+            queue,
+            context,
+        }
+    }
+
+    /// 状态查询句柄（供管理接口使用）
+    pub fn monitor(&self) -> WorkerMonitor {
+        WorkerMonitor {
+            max_concurrent: self.config.max_concurrent,
+            batch_size: self.config.batch_size,
+            streaming: self.config.streaming,
+            context: self.context.clone(),
+            running: self.running.clone(),
+            active: self.active.clone(),
         }
     }
 
     /// 启动 Worker
-    pub async fn run(mut self) {
+    pub async fn run(self) {
         tracing::info!(
             max_concurrent = self.config.max_concurrent,
             output_format = %self.config.audio.output_format,
             transcode_enabled = self.config.audio.transcode_enabled,
+            streaming = self.config.streaming,
+            batch_size = self.config.batch_size,
             "InferWorker started"
         );
 
         // 使用 semaphore 控制并发
         let semaphore = Arc::new(tokio::sync::Semaphore::new(self.config.max_concurrent));
+        self.running.store(true, Ordering::Release);
 
-        while let Some(task_id) = self.queue_receiver.recv().await {
-            let permit = semaphore.clone().acquire_owned().await;
-            if permit.is_err() {
+        loop {
+            // 先等到空闲的推理槽位再出队，等待期间提交的高优先级任务可以排到前面
+            let Ok(first_permit) = semaphore.clone().acquire_owned().await else {
                 tracing::error!("Failed to acquire semaphore permit");
-                continue;
+                break;
+            };
+            let mut first_permit = Some(first_permit);
+
+            // 批量模式下取出队列中已就绪的任务，按相邻片段分组；队列关闭后停止出队
+            let Some(task_id) = self.queue.pop().await else {
+                break;
+            };
+            let mut task_ids = vec![task_id];
+            while task_ids.len() < self.config.batch_size {
+                match self.queue.try_pop() {
+                    Some(task_id) => task_ids.push(task_id),
+                    None => break,
+                }
             }
-            let permit = permit.unwrap();
-
-            let task_manager = self.task_manager.clone();
-            let session_manager = self.session_manager.clone();
-            let tts_engine = self.tts_engine.clone();
-            let audio_cache = self.audio_cache.clone();
-            let voice_repo = self.voice_repo.clone();
-            let audio_transcoder = self.audio_transcoder.clone();
-            let event_publisher = self.event_publisher.clone();
-            let base_url = self.config.base_url.clone();
-            let audio_config = self.config.audio.clone();
-
-            tokio::spawn(async move {
-                let _permit = permit; // 持有 permit 直到任务完成
-
-                Self::process_task(
-                    &task_id,
-                    task_manager,
-                    session_manager,
-                    tts_engine,
-                    audio_cache,
-                    voice_repo,
-                    audio_transcoder,
-                    event_publisher,
-                    &base_url,
-                    &audio_config,
-                )
-                .await;
-            });
+            self.publish_queue_positions();
+
+            for group in self.group_adjacent(task_ids) {
+                let permit = match first_permit.take() {
+                    Some(permit) => Ok(permit),
+                    None => semaphore.clone().acquire_owned().await,
+                };
+                if permit.is_err() {
+                    tracing::error!("Failed to acquire semaphore permit");
+                    continue;
+                }
+                let permit = permit.unwrap();
+                let context = self.context.clone();
+                let span = context.task_span(&group);
+                let active = self.active.clone();
+                active.fetch_add(1, Ordering::AcqRel);
+
+                tokio::spawn(
+                    async move {
+                        let _permit = permit; // 持有 permit 直到任务完成
+
+                        if group.len() == 1 {
+                            context.process_task(&group[0]).await;
+                        } else {
+                            context.process_batch(&group).await;
+                        }
+                        active.fetch_sub(1, Ordering::AcqRel);
+                    }
+                    .instrument(span),
+                );
+            }
+        }
+
+        self.running.store(false, Ordering::Release);
+
+        // 等待进行中的推理完成并写入缓存
+        let in_flight = self.config.max_concurrent - semaphore.available_permits();
+        if in_flight > 0 {
+            tracing::info!(in_flight, "Waiting for in-flight inference to finish");
         }
+        let _ = semaphore.acquire_many(self.config.max_concurrent as u32).await;
 
         tracing::info!("InferWorker stopped");
     }
 
+    /// 推送排在前面的任务的排队位置，并按最近的平均推理耗时估算完成时间
+    fn publish_queue_positions(&self) {
+        let average_ms = self.context.inference_time.average();
+        let concurrency = self.config.max_concurrent.max(1);
+        for (position, task_id) in self.queue.peek(QUEUE_POSITION_LIMIT).into_iter().enumerate() {
+            let Some(task) = self.context.task_manager.get_task(&task_id) else {
+                continue;
+            };
+            let eta_ms = average_ms.map(|average_ms| average_ms * (position / concurrency + 1) as u64);
+            self.context.event_publisher.publish_task_queued(&task, position, eta_ms);
+        }
+    }
+
+    /// 将任务分组：同一会话、同一音色且片段索引连续的任务为一组
+    ///
+    /// 队列中各会话轮流出队，先按会话归拢（保持会话首次出现的顺序）再分组
+    fn group_adjacent(&self, task_ids: Vec<String>) -> Vec<Vec<String>> {
+        let mut tasks = task_ids
+            .into_iter()
+            .map(|id| {
+                let key = self
+                    .context
+                    .task_manager
+                    .get_task(&id)
+                    .map(|t| (t.session_id, t.voice_id, t.segment_index));
+                (id, key)
+            })
+            .collect::<Vec<_>>();
+        let sessions: Vec<Option<String>> =
+            tasks.iter().map(|(_, key)| key.as_ref().map(|k| k.0.clone())).collect();
+        tasks.sort_by_key(|(_, key)| {
+            let session = key.as_ref().map(|k| &k.0);
+            sessions.iter().position(|s| s.as_ref() == session)
+        });
+
+        let (task_ids, keys): (Vec<_>, Vec<_>) = tasks.into_iter().unzip();
+        group_by_adjacency(task_ids, &keys)
+    }
+}
+
+/// 按 (会话, 音色, 片段索引) 将相邻任务分组；查不到的任务单独成组
+fn group_by_adjacency(
+    task_ids: Vec<String>,
+    keys: &[Option<(String, Uuid, u32)>],
+) -> Vec<Vec<String>> {
+    let mut groups: Vec<Vec<String>> = Vec::new();
+    let mut previous: Option<&(String, Uuid, u32)> = None;
+
+    for (task_id, key) in task_ids.into_iter().zip(keys) {
+        let adjacent = match (previous, key) {
+            (Some((prev_session, prev_voice, prev_index)), Some((session, voice, index))) => {
+                prev_session == session && prev_voice == voice && prev_index + 1 == *index
+            }
+            _ => false,
+        };
+        match groups.last_mut() {
+            Some(group) if adjacent => group.push(task_id),
+            _ => groups.push(vec![task_id]),
+        }
+        previous = key.as_ref();
+    }
+
+    groups
+}
+
+/// 每个字符的最短朗读时长（毫秒），短于此视为截断或无效音频
+const MIN_MS_PER_CHAR: u64 = 20;
+/// 每个字符的最长朗读时长（毫秒），另加 MAX_EXTRA_MS 余量
+const MAX_MS_PER_CHAR: u64 = 1000;
+const MAX_EXTRA_MS: u64 = 10_000;
+
+/// 校验 TTS 返回的音频，返回不合格的原因
+///
+/// - 非空
+/// - 可解析的 WAV（RIFF 头，`audio_info` 由转码器提供）
+/// - 时长与文本长度大致匹配
+fn validate_tts_audio(
+    audio: &[u8],
+    text: &str,
+    audio_info: impl Fn(&[u8]) -> Result<AudioInfo, String>,
+) -> Result<(), String> {
+    if audio.is_empty() {
+        return Err("empty audio payload".to_string());
+    }
+    if audio.len() < 4 || &audio[0..4] != b"RIFF" {
+        let head = String::from_utf8_lossy(&audio[..audio.len().min(512)]).to_lowercase();
+        if head.trim_start().starts_with('<') || head.contains("<html") {
+            return Err("TTS service returned an HTML page instead of audio".to_string());
+        }
+        return Err("missing RIFF header".to_string());
+    }
+
+    let info = audio_info(audio)?;
+    if info.data_size == 0 || info.duration_ms == 0 {
+        return Err("audio contains no samples".to_string());
+    }
+
+    let chars = text.chars().filter(|c| c.is_alphanumeric()).count() as u64;
+    let min_ms = chars * MIN_MS_PER_CHAR;
+    let max_ms = chars * MAX_MS_PER_CHAR + MAX_EXTRA_MS;
+    if info.duration_ms < min_ms || info.duration_ms > max_ms {
+        return Err(format!(
+            "implausible duration {}ms for {} characters (expected {}-{}ms)",
+            info.duration_ms, chars, min_ms, max_ms
+        ));
+    }
+
+    Ok(())
+}
+
+/// 从音频数据计算实际时长（`audio_info` 由转码器提供），无法解析时使用 TTS 响应中的时长（缺失时为 0）
+fn measure_duration(response: &InferResponse, audio_info: impl Fn(&[u8]) -> Result<AudioInfo, String>) -> u64 {
+    audio_info(&response.audio_data)
+        .ok()
+        .map(|info| info.duration_ms)
+        .filter(|&duration_ms| duration_ms > 0)
+        .or(response.duration_ms)
+        .unwrap_or(0)
+}
+
+impl WorkerContext {
+    /// 推理日志的 span，附带任务 ID 和提交任务的请求 ID（批量推理取第一个带请求 ID 的任务）
+    fn task_span(&self, task_ids: &[String]) -> tracing::Span {
+        let request_id = task_ids
+            .iter()
+            .find_map(|task_id| self.task_manager.get_task(task_id).and_then(|task| task.request_id));
+        let span = tracing::info_span!(
+            "infer",
+            task_id = %task_ids[0],
+            tasks = task_ids.len(),
+            request_id = tracing::field::Empty,
+        );
+        if let Some(request_id) = request_id {
+            span.record("request_id", request_id.as_str());
+        }
+        span
+    }
+
     /// 处理单个任务
-    #[allow(clippy::too_many_arguments)]
-    async fn process_task(
-        task_id: &str,
-        task_manager: Arc<dyn TaskManagerPort>,
-        session_manager: Arc<dyn SessionManagerPort>,
-        tts_engine: Arc<dyn TtsEnginePort>,
-        audio_cache: Arc<dyn AudioCachePort>,
-        voice_repo: Arc<dyn VoiceRepositoryPort>,
-        audio_transcoder: Arc<dyn AudioTranscoderPort>,
-        event_publisher: Arc<EventPublisher>,
-        base_url: &str,
-        audio_config: &AudioConfig,
-    ) {
+    async fn process_task(&self, task_id: &str) {
+        let Some(prepared) = self.prepare(task_id).await else {
+            return;
+        };
+        self.infer_one(&prepared, self.streaming).await;
+    }
+
+    /// 推理单个任务；推理期间任务被取消（移出预取窗口/会话关闭）时丢弃请求，
+    /// 断开与 TTS 服务的连接，后端可以立即释放 GPU。
+    /// 有其他任务在等待同一推理时由其接替，重新发起请求
+    async fn infer_one(&self, prepared: &PreparedTask, streaming: bool) {
+        let mut promoted: Option<PreparedTask> = None;
+        loop {
+            let current = promoted.as_ref().unwrap_or(prepared);
+            let started = Instant::now();
+            let inference = async {
+                if streaming {
+                    self.infer_streaming(current).await
+                } else {
+                    self.tts_engine.infer(current.request.clone()).await
+                }
+            };
+            match current.task.cancel_token.run_until_cancelled(inference).await {
+                Some(result) => {
+                    if result.is_ok() {
+                        self.inference_time.record(started.elapsed().as_millis() as u64);
+                    }
+                    self.settle(current, result).await;
+                    return;
+                }
+                None => {
+                    if streaming {
+                        self.partial_audio.abort(&current.cache_key, "Task cancelled");
+                    }
+                    tracing::info!(
+                        task_id = %current.task.task_id,
+                        session_id = %current.task.session_id,
+                        segment_index = current.task.segment_index,
+                        "Inference aborted: task cancelled"
+                    );
+                    let next = self.promote(current);
+                    match next {
+                        Some(next) => promoted = Some(next),
+                        None => return,
+                    }
+                }
+            }
+        }
+    }
+
+    /// 等待中的任务是否仍需要结果（未取消且会话有效）
+    fn is_waiting(&self, task: &InferenceTask) -> bool {
+        !self.task_manager.is_cancelled(&task.task_id) && self.session_manager.is_valid(&task.session_id)
+    }
+
+    /// 发起推理的任务被取消后，由等待同一推理的任务接替
+    fn promote(&self, prepared: &PreparedTask) -> Option<PreparedTask> {
+        let task = self
+            .inflight
+            .promote(&prepared.cache_key, |task| self.is_waiting(task))?;
+        tracing::debug!(
+            task_id = %task.task_id,
+            session_id = %task.session_id,
+            segment_index = task.segment_index,
+            "Coalesced task takes over inference"
+        );
+        Some(PreparedTask {
+            task,
+            voice_id: prepared.voice_id,
+            cache_key: prepared.cache_key.clone(),
+            request: prepared.request.clone(),
+        })
+    }
+
+    /// 处理推理结果，并以同一结果完成合并到该推理的任务
+    async fn settle(&self, prepared: &PreparedTask, result: Result<InferResponse, TtsError>) {
+        let outcome = self.complete(prepared, result).await;
+        let waiting = self.inflight.finish(&prepared.cache_key);
+        // 合并的任务共用同一份缓存音频，需要写入会话存储时读取一次
+        let mut cached_audio = None;
+        for task in waiting.iter().filter(|task| self.is_waiting(task)) {
+            match &outcome {
+                Ok(duration_ms) => {
+                    if let Err(e) = self
+                        .audio_cache
+                        .link(&prepared.cache_key, task.novel_id, task.segment_index, prepared.voice_id)
+                        .await
+                    {
+                        tracing::warn!(task_id = %task.task_id, error = %e, "Failed to link cached audio");
+                    }
+                    if let Some(session_id) = self.stored_session(task) {
+                        if cached_audio.is_none() {
+                            cached_audio = self.audio_cache.get(&prepared.cache_key).await.ok().flatten();
+                        }
+                        if let Some(audio_data) = &cached_audio {
+                            self.store_session_audio(task, session_id, audio_data, *duration_ms).await;
+                        }
+                    }
+                    let _ = self.task_manager.set_state(&task.task_id, TaskState::Ready);
+                    self.publish_ready(task, *duration_ms);
+                }
+                Err(error) => {
+                    let _ = self.task_manager.set_failed(&task.task_id, error.clone());
+                    self.event_publisher.publish_task_failed(task, error);
+                }
+            }
+        }
+        if !waiting.is_empty() {
+            tracing::debug!(
+                task_id = %prepared.task.task_id,
+                coalesced = waiting.len(),
+                "Coalesced tasks completed"
+            );
+        }
+    }
+
+    /// 批量处理相邻任务
+    ///
+    /// 角色配音可能使组内片段解析到不同音色，此时按实际音色再拆分；
+    /// 批量请求失败时逐个重试，不支持批量的 TTS 服务也能正常工作
+    async fn process_batch(&self, task_ids: &[String]) {
+        let mut prepared = Vec::with_capacity(task_ids.len());
+        for task_id in task_ids {
+            if let Some(task) = self.prepare(task_id).await {
+                prepared.push(task);
+            }
+        }
+
+        let mut batches: Vec<Vec<PreparedTask>> = Vec::new();
+        for task in prepared {
+            match batches.last_mut() {
+                Some(batch) if batch[0].voice_id == task.voice_id => batch.push(task),
+                _ => batches.push(vec![task]),
+            }
+        }
+
+        for batch in batches {
+            if batch.len() == 1 {
+                self.infer_one(&batch[0], false).await;
+                continue;
+            }
+
+            // 组内任务全部取消时中止批量请求，等待其中片段的其他任务逐个接替
+            let requests = batch.iter().map(|t| t.request.clone()).collect();
+            let all_cancelled = join_all(batch.iter().map(|t| t.task.cancel_token.cancelled()));
+            let started = Instant::now();
+            let result = tokio::select! {
+                result = self.tts_engine.infer_batch(requests) => result,
+                _ = all_cancelled => {
+                    tracing::info!(segments = batch.len(), "Batch inference aborted: tasks cancelled");
+                    for task in &batch {
+                        if let Some(next) = self.promote(task) {
+                            self.infer_one(&next, false).await;
+                        }
+                    }
+                    continue;
+                }
+            };
+            match result {
+                Ok(responses) => {
+                    tracing::debug!(segments = batch.len(), "Batch inference completed");
+                    self.inference_time
+                        .record(started.elapsed().as_millis() as u64 / batch.len() as u64);
+                    for (task, response) in batch.iter().zip(responses) {
+                        self.settle(task, Ok(response)).await;
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        segments = batch.len(),
+                        error = %e,
+                        "Batch inference failed, retrying segments individually"
+                    );
+                    for task in &batch {
+                        self.infer_one(task, false).await;
+                    }
+                }
+            }
+        }
+    }
+
+    /// 推理前检查并构建请求；任务无需推理（已取消/会话失效/缓存命中/失败/
+    /// 加入了相同内容的推理）时返回 None
+    async fn prepare(&self, task_id: &str) -> Option<PreparedTask> {
+        let task_manager = &self.task_manager;
+        let event_publisher = &self.event_publisher;
+
         // 获取任务信息
         let task = match task_manager.get_task(task_id) {
             Some(t) => t,
             None => {
                 tracing::warn!(task_id = %task_id, "Task not found, skipping");
-                return;
+                return None;
             }
         };
 
         // Check 1: 任务是否已取消
         if task_manager.is_cancelled(task_id) {
             tracing::debug!(task_id = %task_id, "Task cancelled, skipping");
-            return;
+            return None;
         }
 
         // Check 2: 会话是否有效
-        if !session_manager.is_valid(&task.session_id) {
+        if !self.session_manager.is_valid(&task.session_id) {
             tracing::debug!(
                 task_id = %task_id,
                 session_id = %task.session_id,
                 "Session invalid, skipping"
             );
-            return;
+            return None;
+        }
+
+        // 启用说话人识别时，对话片段使用角色配音表中的音色
+        let voice = self
+            .voice_casting
+            .resolve_key(task.novel_id, task.segment_index, task.voice_id)
+            .await;
+        let voice_id = voice.voice_id;
+        if voice_id != task.voice_id {
+            tracing::debug!(
+                task_id = %task_id,
+                session_voice_id = %task.voice_id,
+                voice_id = %voice_id,
+                "Using cast voice for dialogue segment"
+            );
         }
 
         // 检查缓存是否已存在
-        let cache_key = generate_cache_key(&task.segment_content, &task.voice_id);
-        if let Ok(Some(_)) = audio_cache.get(&cache_key).await {
+        let cache_key = generate_cache_key_with_params(&task.segment_content, &voice, &task.params);
+        if let Ok(Some(entry)) = self.audio_cache.get_entry(&cache_key).await {
             tracing::debug!(task_id = %task_id, "Cache hit, marking as ready");
+            // 内容相同的片段共享缓存，记录本片段的引用
+            if let Err(e) = self
+                .audio_cache
+                .link(&cache_key, task.novel_id, task.segment_index, voice_id)
+                .await
+            {
+                tracing::warn!(task_id = %task_id, error = %e, "Failed to link cached audio");
+            }
             let _ = task_manager.set_state(task_id, TaskState::Ready);
-            event_publisher.publish_task_ready(
-                task_id,
-                &task.session_id,
-                task.segment_index,
-            );
-            return;
+            self.publish_ready(&task, entry.metadata.duration_ms);
+            return None;
         }
 
         // 标记为推理中
         if let Err(e) = task_manager.set_state(task_id, TaskState::Inferring) {
             tracing::error!(task_id = %task_id, error = %e, "Failed to update task state");
-            return;
+            return None;
         }
-        event_publisher.publish_task_inferring(task_id, &task.session_id, task.segment_index);
+        event_publisher.publish_task_inferring(&task);
 
         // 构建 voice reference 的下载 URL（TTS 服务通过此 URL 下载并缓存）
-        let voice_ref = match voice_repo.find_by_id(task.voice_id).await {
-            Ok(Some(_voice)) => {
+        // 同时附带本地路径，上传模式下由 TTS 客户端直接发送音频
+        let (voice_ref, voice_ref_path, prompt_text, engine, tts_config) = match self.voice_repo.find_by_id(voice_id).await {
+            Ok(Some(voice)) => {
+                // 优先使用归一化后的版本（与 /api/voice/audio 一致）
+                let voice_ref_path = voice
+                    .processed_audio_path
+                    .filter(|p| p.exists())
+                    .unwrap_or(voice.reference_audio_path);
                 // 构建下载 URL: {base_url}/api/voice/audio/{voice_id}
-                format!("{}/api/voice/audio/{}", base_url, task.voice_id)
+                (
+                    voice_audio_url(&self.base_url, voice_id, self.voice_audio_signer.as_ref()),
+                    Some(voice_ref_path),
+                    voice.prompt_text,
+                    voice.engine,
+                    voice.tts_config,
+                )
             }
             Ok(None) => {
-                tracing::error!(task_id = %task_id, voice_id = %task.voice_id, "Voice not found");
+                tracing::error!(task_id = %task_id, voice_id = %voice_id, "Voice not found");
                 let _ = task_manager.set_failed(task_id, "Voice not found".to_string());
-                event_publisher.publish_task_failed(
-                    task_id,
-                    &task.session_id,
-                    task.segment_index,
-                    "Voice not found",
-                );
-                return;
+                event_publisher.publish_task_failed(&task, "Voice not found");
+                return None;
             }
             Err(e) => {
                 tracing::error!(task_id = %task_id, error = %e, "Failed to find voice");
                 let _ = task_manager.set_failed(task_id, format!("Database error: {}", e));
-                event_publisher.publish_task_failed(
-                    task_id,
-                    &task.session_id,
-                    task.segment_index,
-                    &format!("Database error: {}", e),
-                );
-                return;
+                event_publisher.publish_task_failed(&task, &format!("Database error: {}", e));
+                return None;
             }
         };
 
-        // 执行 TTS 推理
+        // 当前播放片段延迟敏感，允许对冲到备用后端
+        let latency_sensitive = self
+            .session_manager
+            .get(&task.session_id)
+            .map(|session| session.current_index == task.segment_index)
+            .unwrap_or(false);
+
+        // TTS 推理请求
         let request = InferRequest {
             text: task.segment_content.clone(),
             voice_ref,
-            voice_id: task.voice_id.to_string(),
+            voice_ref_path,
+            voice_id: voice_id.to_string(),
+            prompt_text,
+            engine,
+            tts_config,
+            params: task.params.clone(),
+            seed: self.seed,
+            latency_sensitive,
+            markup: MarkupType::PlainText,
         };
 
-        let response = match tts_engine.infer(request).await {
+        // 相同内容已在推理时等待其结果，不再重复请求
+        if self.inflight.claim(&cache_key, &task) == Claim::Joined {
+            tracing::debug!(
+                task_id = %task_id,
+                session_id = %task.session_id,
+                segment_index = task.segment_index,
+                "Joined in-flight inference for identical segment"
+            );
+            return None;
+        }
+
+        Some(PreparedTask {
+            task,
+            voice_id,
+            cache_key,
+            request,
+        })
+    }
+
+    /// 处理推理结果：转码、写入缓存并更新任务状态，返回音频时长或失败原因
+    async fn complete(
+        &self,
+        prepared: &PreparedTask,
+        result: Result<InferResponse, TtsError>,
+    ) -> Result<u64, String> {
+        let task = &prepared.task;
+        let task_id = task.task_id.as_str();
+        let cache_key = &prepared.cache_key;
+        let task_manager = &self.task_manager;
+        let event_publisher = &self.event_publisher;
+        let audio_config = &self.audio_config;
+
+        let response = match result {
             Ok(resp) => resp,
             Err(e) => {
                 tracing::error!(task_id = %task_id, error = %e, "TTS inference failed");
-                let _ = task_manager.set_failed(task_id, format!("TTS error: {}", e));
-                event_publisher.publish_task_failed(
-                    task_id,
-                    &task.session_id,
-                    task.segment_index,
-                    &format!("TTS error: {}", e),
-                );
-                return;
+                let error = format!("TTS error: {}", e);
+                let _ = task_manager.set_failed(task_id, error.clone());
+                event_publisher.publish_task_failed(task, &error);
+                return Err(error);
             }
         };
 
-        // Check 3: 推理后再次检查会话是否有效
-        if !session_manager.is_valid(&task.session_id) {
+        // Check 3: 推理后再次检查会话是否有效（仍有其他任务等待该结果时照常写入缓存）
+        if !self.session_manager.is_valid(&task.session_id)
+            && !self.inflight.has_waiting(cache_key, |task| self.is_waiting(task))
+        {
             tracing::debug!(
                 task_id = %task_id,
                 session_id = %task.session_id,
                 "Session invalid after TTS, dropping result"
             );
-            return;
+            self.partial_audio.finish(cache_key);
+            return Err("Session closed".to_string());
+        }
+
+        // 校验音频：TTS 服务可能以 200 状态返回空内容或 HTML 错误页
+        if self.validate_audio {
+            if let Err(reason) = validate_tts_audio(
+                &response.audio_data,
+                &task.segment_content,
+                |data| self.audio_transcoder.get_audio_info(data).map_err(|e| e.to_string()),
+            ) {
+                tracing::error!(
+                    task_id = %task_id,
+                    tts_session_id = %response.session_id,
+                    audio_size = response.audio_data.len(),
+                    reason = %reason,
+                    "TTS returned invalid audio"
+                );
+                let error = format!("Invalid TTS audio: {}", reason);
+                self.partial_audio.abort(cache_key, &error);
+                let _ = task_manager.set_failed(task_id, error.clone());
+                event_publisher.publish_task_failed(task, &error);
+                return Err(error);
+            }
         }
 
-        // 转码音频（如果启用）
-        let (final_audio_data, final_duration_ms, final_sample_rate) =
+        // 转码音频（如果启用）：按配置的采样率/声道数/格式写入缓存
+        let (final_audio_data, final_duration_ms, final_sample_rate, final_format) =
             if audio_config.transcode_enabled {
-                let transcode_config = TranscodeConfig {
-                    format: audio_config.output_format,
-                    bitrate: Some(audio_config.bitrate),
-                    sample_rate: if audio_config.sample_rate > 0 {
-                        Some(audio_config.sample_rate)
-                    } else {
-                        None
-                    },
-                    channels: if audio_config.channels > 0 {
-                        Some(audio_config.channels)
-                    } else {
-                        None
-                    },
+                // 有损格式写入标签（小说、章节、片段序号、音色），WAV 不写
+                let transcode_config = audio_config.transcode_config();
+                let metadata = if transcode_config.format == AudioFormat::Wav {
+                    AudioMetadata::default()
+                } else {
+                    self.audio_tags
+                        .resolve(task.novel_id, task.segment_index, prepared.voice_id)
+                        .await
                 };
-
-                match audio_transcoder
-                    .transcode(&response.audio_data, &transcode_config)
+                match self
+                    .audio_transcoder
+                    .transcode_with_metadata(&response.audio_data, &transcode_config, &metadata)
                     .await
                 {
                     Ok(result) => {
@@ -278,7 +860,12 @@ impl InferWorker {
                             format = %result.format,
                             "Audio transcoded"
                         );
-                        (result.audio_data, result.duration_ms, Some(result.sample_rate))
+                        (
+                            result.audio_data,
+                            result.duration_ms,
+                            Some(result.sample_rate),
+                            result.format,
+                        )
                     }
                     Err(e) => {
                         tracing::warn!(
@@ -288,16 +875,18 @@ impl InferWorker {
                         );
                         (
                             response.audio_data.clone(),
-                            response.duration_ms.unwrap_or(0),
+                            self.measure_duration(&response),
                             response.sample_rate,
+                            AudioFormat::Wav,
                         )
                     }
                 }
             } else {
                 (
                     response.audio_data.clone(),
-                    response.duration_ms.unwrap_or(0),
+                    self.measure_duration(&response),
                     response.sample_rate,
+                    AudioFormat::Wav,
                 )
             };
 
@@ -305,34 +894,361 @@ impl InferWorker {
         let metadata = CacheMetadata {
             novel_id: task.novel_id,
             segment_index: task.segment_index,
-            voice_id: task.voice_id,
+            voice_id: prepared.voice_id,
             content_hash: cache_key.clone(),
             duration_ms: final_duration_ms,
             sample_rate: final_sample_rate,
+            format: final_format,
         };
 
-        if let Err(e) = audio_cache.put(&cache_key, final_audio_data, metadata).await {
+        let session_audio = self
+            .stored_session(task)
+            .map(|session_id| (session_id, final_audio_data.clone()));
+        let cache_result = self.audio_cache.put(cache_key, final_audio_data, metadata).await;
+        // 完整音频已写入缓存（或写入失败），结束合成中音频的订阅
+        self.partial_audio.finish(cache_key);
+        if let Err(e) = cache_result {
             tracing::error!(task_id = %task_id, error = %e, "Failed to cache audio");
-            let _ = task_manager.set_failed(task_id, format!("Cache error: {}", e));
-            event_publisher.publish_task_failed(
-                task_id,
-                &task.session_id,
-                task.segment_index,
-                &format!("Cache error: {}", e),
-            );
-            return;
+            let error = format!("Cache error: {}", e);
+            let _ = task_manager.set_failed(task_id, error.clone());
+            event_publisher.publish_task_failed(task, &error);
+            return Err(error);
+        }
+
+        // 从原始音频计算波形峰值（缓存的 Opus 无法解码）
+        match self.audio_transcoder.peaks(&response.audio_data, WAVEFORM_POINTS) {
+            Ok(peaks) => {
+                if let Err(e) = self.audio_cache.put_peaks(cache_key, &peaks).await {
+                    tracing::warn!(task_id = %task_id, error = %e, "Failed to cache waveform peaks");
+                }
+            }
+            Err(e) => tracing::warn!(task_id = %task_id, error = %e, "Failed to compute waveform peaks"),
+        }
+
+        if let Some((session_id, audio_data)) = session_audio {
+            self.store_session_audio(task, session_id, &audio_data, final_duration_ms).await;
         }
 
         // 标记为完成
         let _ = task_manager.set_state(task_id, TaskState::Ready);
-        event_publisher.publish_task_ready(task_id, &task.session_id, task.segment_index);
+        self.publish_ready(task, final_duration_ms);
 
         tracing::info!(
             task_id = %task_id,
             session_id = %task.session_id,
             segment_index = task.segment_index,
-            duration_ms = ?response.duration_ms,
+            duration_ms = final_duration_ms,
             "Task completed"
         );
+        Ok(final_duration_ms)
+    }
+
+    fn measure_duration(&self, response: &InferResponse) -> u64 {
+        measure_duration(response, |data| {
+            self.audio_transcoder.get_audio_info(data).map_err(|e| e.to_string())
+        })
+    }
+
+    /// 需要写入会话存储的会话 ID（已启用且为持久化会话；后台会话不持久化）
+    fn stored_session(&self, task: &InferenceTask) -> Option<Uuid> {
+        if !self.store_session_audio {
+            return None;
+        }
+        let session = self.session_manager.get(&task.session_id).ok()?;
+        if session.background {
+            return None;
+        }
+        Uuid::parse_str(&task.session_id).ok()
+    }
+
+    /// 写入会话音频文件并更新音频段落记录（按会话和片段序号覆盖）
+    ///
+    /// 失败只记录日志：音频已在缓存中，播放不受影响
+    async fn store_session_audio(&self, task: &InferenceTask, session_id: Uuid, audio_data: &[u8], duration_ms: u64) {
+        let segment_index = task.segment_index as usize;
+        let audio_path = match self.audio_storage.save_audio(session_id, segment_index, audio_data).await {
+            Ok(path) => path,
+            Err(e) => {
+                tracing::warn!(task_id = %task.task_id, error = %e, "Failed to store session audio");
+                return;
+            }
+        };
+
+        let now = chrono::Utc::now();
+        let segment = AudioSegmentRecord {
+            id: Uuid::new_v4(),
+            session_id,
+            segment_index,
+            audio_path: Some(audio_path),
+            duration_ms: Some(duration_ms as u32),
+            file_size: Some(audio_data.len() as u64),
+            state: AudioSegmentState::Ready,
+            error_message: None,
+            created_at: now,
+            last_accessed_at: now,
+        };
+        if let Err(e) = self.audio_segment_repo.save(&segment).await {
+            tracing::warn!(task_id = %task.task_id, error = %e, "Failed to record audio segment");
+        }
+    }
+
+    /// 发布任务完成事件，时长未知（0）时不附带时长
+    fn publish_ready(&self, task: &InferenceTask, duration_ms: u64) {
+        if duration_ms > 0 {
+            self.event_publisher.publish_task_ready_with_duration(task, duration_ms);
+        } else {
+            self.event_publisher.publish_task_ready(task);
+        }
+
+        // 自动前进模式：正在播放的片段之后的片段就绪
+        if let Ok(session) = self.session_manager.get(&task.session_id) {
+            if session.auto_advance && task.segment_index == session.current_index + 1 {
+                self.event_publisher.publish_next_segment_ready(
+                    &task.session_id,
+                    task.segment_index,
+                    &segment_audio_url(&session, task.segment_index),
+                );
+            }
+        }
+    }
+
+    /// 流式推理：边接收音频分块边写入合成中缓冲，返回拼接后的完整音频
+    async fn infer_streaming(&self, prepared: &PreparedTask) -> Result<InferResponse, TtsError> {
+        let task = &prepared.task;
+        let cache_key = prepared.cache_key.as_str();
+
+        let mut stream = self.tts_engine.infer_stream(prepared.request.clone()).await?;
+        self.partial_audio.begin(cache_key);
+
+        let mut audio_data = Vec::new();
+        while let Some(chunk) = stream.chunks.next().await {
+            let chunk = match chunk {
+                Ok(chunk) => chunk,
+                Err(e) => {
+                    self.partial_audio.abort(cache_key, &e.to_string());
+                    return Err(e);
+                }
+            };
+            if audio_data.is_empty() && !chunk.is_empty() {
+                tracing::debug!(task_id = %task.task_id, "First audio chunk received");
+                self.event_publisher.publish_task_streaming(task);
+            }
+            self.partial_audio.append(cache_key, &chunk);
+            audio_data.extend_from_slice(&chunk);
+        }
+
+        Ok(InferResponse {
+            session_id: stream.session_id,
+            audio_data,
+            duration_ms: None,
+            sample_rate: stream.sample_rate,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::{NovelRecord, NovelRepositoryPort, NovelStatus, Session, VoiceRecord};
+    use crate::domain::voice::{TtsConfig, VoiceTags};
+    use crate::infrastructure::adapters::storage::FileAudioStorage;
+    use crate::infrastructure::adapters::transcoder::WavTranscoder;
+    use crate::infrastructure::adapters::tts::FakeTtsClient;
+    use crate::infrastructure::memory::{InMemoryPartialAudio, PersistentSessionManager};
+    use crate::infrastructure::persistence::sqlite::{
+        create_pool, run_migrations, DatabaseConfig, SqliteAudioSegmentRepository, SqliteNovelRepository,
+        SqliteSessionRepository, SqliteVoiceCastingRepository, SqliteVoiceRepository,
+    };
+    use crate::infrastructure::{InMemoryTaskManager, SledAudioCache};
+    use chrono::Utc;
+    use std::path::PathBuf;
+
+    #[tokio::test]
+    async fn test_store_session_audio() {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = create_pool(&DatabaseConfig::in_memory()).await.unwrap();
+        run_migrations(&pool).await.unwrap();
+        let novel_repo = Arc::new(SqliteNovelRepository::new(pool.clone()));
+        let voice_repo = Arc::new(SqliteVoiceRepository::new(pool.clone()));
+        let audio_segment_repo = Arc::new(SqliteAudioSegmentRepository::new(pool.clone()));
+        let session_manager =
+            Arc::new(PersistentSessionManager::new(Arc::new(SqliteSessionRepository::new(pool.clone()))));
+        let queue = Arc::new(TaskQueue::new(10));
+        let task_manager = Arc::new(InMemoryTaskManager::new(queue.clone()));
+        let audio_storage = Arc::new(FileAudioStorage::new(dir.path().join("audio")).await.unwrap());
+        let worker = InferWorker::new(
+            InferWorkerConfig {
+                validate_audio: false,
+                ..Default::default()
+            },
+            queue,
+            task_manager.clone(),
+            session_manager.clone(),
+            Arc::new(FakeTtsClient::with_defaults().unwrap()),
+            Arc::new(SledAudioCache::open(dir.path().join("cache"), 10 * 1024 * 1024).unwrap()),
+            Arc::new(InMemoryPartialAudio::new()),
+            voice_repo.clone(),
+            Arc::new(WavTranscoder::new(false)),
+            audio_segment_repo.clone(),
+            audio_storage.clone(),
+            Arc::new(EventPublisher::new()),
+            VoiceCastingResolver::new(
+                novel_repo.clone(),
+                Arc::new(SqliteVoiceCastingRepository::new(pool)),
+                voice_repo.clone(),
+                false,
+            ),
+            AudioTagResolver::new(novel_repo.clone(), voice_repo.clone()),
+        );
+
+        let novel_id = Uuid::new_v4();
+        novel_repo
+            .save(&NovelRecord {
+                id: novel_id,
+                title: "测试".to_string(),
+                raw_text_path: PathBuf::from("novel.txt"),
+                total_segments: 2,
+                status: NovelStatus::Ready,
+                user_id: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            })
+            .await
+            .unwrap();
+        let voice_id = Uuid::new_v4();
+        voice_repo
+            .save(&VoiceRecord {
+                id: voice_id,
+                name: "旁白".to_string(),
+                reference_audio_path: PathBuf::from("voice.wav"),
+                processed_audio_path: None,
+                description: None,
+                prompt_text: None,
+                engine: None,
+                tts_config: TtsConfig::default(),
+                tags: VoiceTags::default(),
+                user_id: None,
+                created_at: Utc::now(),
+            })
+            .await
+            .unwrap();
+        let session_id = session_manager.create(Session::new(novel_id, voice_id, 0)).unwrap();
+        // 后台会话不持久化，音频只写入缓存
+        let background_id = session_manager
+            .create(Session::new(novel_id, voice_id, 0).as_background())
+            .unwrap();
+        session_manager.flush().await;
+
+        let task = |session_id: &str, segment_index| {
+            let content = format!("第{}段", segment_index);
+            InferenceTask::new(session_id.to_string(), novel_id, voice_id, segment_index, content)
+        };
+        let task_ids = task_manager
+            .submit(vec![task(&session_id, 0), task(&background_id, 1)])
+            .unwrap();
+        for task_id in &task_ids {
+            worker.context.process_task(task_id).await;
+            assert_eq!(task_manager.get_state(task_id), Some(TaskState::Ready));
+        }
+
+        let session_uuid = Uuid::parse_str(&session_id).unwrap();
+        let segments = audio_segment_repo.find_by_session(session_uuid).await.unwrap();
+        assert_eq!(segments.len(), 1);
+        let segment = &segments[0];
+        assert_eq!((segment.segment_index, segment.state), (0, AudioSegmentState::Ready));
+        let audio_path = segment.audio_path.as_ref().unwrap();
+        assert_eq!(std::fs::metadata(audio_path).unwrap().len(), segment.file_size.unwrap());
+        assert!(segment.duration_ms.is_some_and(|duration_ms| duration_ms > 0));
+
+        let background_uuid = Uuid::parse_str(&background_id).unwrap();
+        assert!(audio_segment_repo.find_by_session(background_uuid).await.unwrap().is_empty());
+        assert!(audio_storage.read_audio(background_uuid, 1).await.is_err());
+    }
+
+    #[test]
+    fn test_validate_tts_audio() {
+        let info = |duration_ms: u64| {
+            move |_: &[u8]| {
+                Ok(AudioInfo {
+                    duration_ms,
+                    sample_rate: 22050,
+                    channels: 1,
+                    bits_per_sample: 16,
+                    data_size: (duration_ms * 44) as usize,
+                })
+            }
+        };
+        let text = "今天天气很好。";
+
+        assert!(validate_tts_audio(b"RIFF....WAVE", text, info(1500)).is_ok());
+        assert!(validate_tts_audio(b"", text, info(1500)).unwrap_err().contains("empty"));
+        assert!(validate_tts_audio(b"<!DOCTYPE html><html>502</html>", text, info(1500))
+            .unwrap_err()
+            .contains("HTML"));
+        assert!(validate_tts_audio(b"{\"error\":1}", text, info(1500))
+            .unwrap_err()
+            .contains("RIFF"));
+        // 6 个字只返回 50ms / 返回 1 分钟
+        assert!(validate_tts_audio(b"RIFF....WAVE", text, info(50)).is_err());
+        assert!(validate_tts_audio(b"RIFF....WAVE", text, info(60_000)).is_err());
+    }
+
+    #[test]
+    fn test_measure_duration() {
+        let response = |duration_ms: Option<u64>| InferResponse {
+            session_id: "tts".to_string(),
+            audio_data: b"RIFF....WAVE".to_vec(),
+            duration_ms,
+            sample_rate: Some(22050),
+        };
+        let measured = |duration_ms: u64| {
+            move |_: &[u8]| {
+                Ok(AudioInfo {
+                    duration_ms,
+                    sample_rate: 22050,
+                    channels: 1,
+                    bits_per_sample: 16,
+                    data_size: (duration_ms * 44) as usize,
+                })
+            }
+        };
+        let unparsable = |_: &[u8]| Err("missing RIFF header".to_string());
+
+        // 以实际音频时长为准，而不是 TTS 服务报告的时长
+        assert_eq!(measure_duration(&response(Some(900)), measured(1234)), 1234);
+        // 无法解析或没有采样时回退到报告的时长
+        assert_eq!(measure_duration(&response(Some(900)), unparsable), 900);
+        assert_eq!(measure_duration(&response(Some(900)), measured(0)), 900);
+        assert_eq!(measure_duration(&response(None), unparsable), 0);
+    }
+
+    #[test]
+    fn test_group_by_adjacency() {
+        let voice = Uuid::new_v4();
+        let other_voice = Uuid::new_v4();
+        let key = |session: &str, voice: Uuid, index: u32| Some((session.to_string(), voice, index));
+
+        let ids: Vec<String> = (0..7).map(|i| format!("t{}", i)).collect();
+        let keys = vec![
+            key("s1", voice, 3),
+            key("s1", voice, 4),
+            key("s1", voice, 5),
+            key("s1", other_voice, 6), // 音色不同
+            key("s2", other_voice, 7), // 会话不同
+            None,                      // 任务已移除
+            key("s2", other_voice, 9), // 不相邻
+        ];
+
+        let groups = group_by_adjacency(ids, &keys);
+        assert_eq!(
+            groups,
+            vec![
+                vec!["t0", "t1", "t2"],
+                vec!["t3"],
+                vec!["t4"],
+                vec!["t5"],
+                vec!["t6"],
+            ]
+        );
     }
 }
@@ -0,0 +1,111 @@
+//! In-flight Inference - 合并相同内容的推理
+//!
+//! 按缓存键（文本、音色、合成参数）登记正在推理的任务：两个会话请求同一片段，
+//! 或跳转后重新提交的窗口与进行中的推理重复时，后到的任务加入已有的推理，
+//! 由一次 TTS 调用的结果一并标记完成。发起推理的任务被取消时，由仍在等待的任务接替
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::application::ports::InferenceTask;
+
+/// 登记结果
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum Claim {
+    /// 没有相同的推理，由该任务发起
+    Leader,
+    /// 已加入进行中的推理，等待其结果
+    Joined,
+}
+
+/// 进行中的推理：缓存键 -> 等待结果的其他任务
+#[derive(Default)]
+pub(super) struct InflightInference {
+    waiting: Mutex<HashMap<String, Vec<InferenceTask>>>,
+}
+
+impl InflightInference {
+    pub(super) fn new() -> Self {
+        Self::default()
+    }
+
+    /// 登记任务：相同缓存键的推理已在进行时加入等待
+    pub(super) fn claim(&self, cache_key: &str, task: &InferenceTask) -> Claim {
+        let mut waiting = self.waiting.lock().unwrap();
+        match waiting.get_mut(cache_key) {
+            Some(tasks) => {
+                tasks.push(task.clone());
+                Claim::Joined
+            }
+            None => {
+                waiting.insert(cache_key.to_string(), Vec::new());
+                Claim::Leader
+            }
+        }
+    }
+
+    /// 是否有仍在等待结果的任务
+    pub(super) fn has_waiting(&self, cache_key: &str, is_active: impl Fn(&InferenceTask) -> bool) -> bool {
+        self.waiting
+            .lock()
+            .unwrap()
+            .get(cache_key)
+            .is_some_and(|tasks| tasks.iter().any(is_active))
+    }
+
+    /// 发起推理的任务被取消：取出第一个仍在等待的任务接替（其余任务继续等待），
+    /// 没有时结束登记
+    pub(super) fn promote(
+        &self,
+        cache_key: &str,
+        is_active: impl Fn(&InferenceTask) -> bool,
+    ) -> Option<InferenceTask> {
+        let mut waiting = self.waiting.lock().unwrap();
+        let tasks = waiting.get_mut(cache_key)?;
+        tasks.retain(|task| is_active(task));
+        if tasks.is_empty() {
+            waiting.remove(cache_key);
+            return None;
+        }
+        Some(tasks.remove(0))
+    }
+
+    /// 进行中的推理数和加入等待的任务数
+    pub(super) fn counts(&self) -> (usize, usize) {
+        let waiting = self.waiting.lock().unwrap();
+        (waiting.len(), waiting.values().map(Vec::len).sum())
+    }
+
+    /// 推理结束：结束登记并取出等待结果的任务
+    pub(super) fn finish(&self, cache_key: &str) -> Vec<InferenceTask> {
+        self.waiting.lock().unwrap().remove(cache_key).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn task(session_id: &str) -> InferenceTask {
+        InferenceTask::new(session_id.to_string(), Uuid::new_v4(), Uuid::new_v4(), 0, "text".to_string())
+    }
+
+    #[test]
+    fn test_duplicates_join_and_take_over() {
+        let inflight = InflightInference::new();
+        let (a, b, c) = (task("a"), task("b"), task("c"));
+        assert_eq!(inflight.claim("key", &a), Claim::Leader);
+        assert_eq!(inflight.claim("key", &b), Claim::Joined);
+        assert_eq!(inflight.claim("key", &c), Claim::Joined);
+        assert_eq!(inflight.claim("other", &a), Claim::Leader);
+        assert_eq!(inflight.counts(), (2, 2));
+
+        // a 被取消、b 也已取消：由 c 接替，结束时没有其他等待的任务
+        let next = inflight.promote("key", |task| task.session_id != "b").unwrap();
+        assert_eq!(next.session_id, "c");
+        assert!(!inflight.has_waiting("key", |_| true));
+        assert!(inflight.finish("key").is_empty());
+        assert_eq!(inflight.claim("key", &a), Claim::Leader);
+    }
+}
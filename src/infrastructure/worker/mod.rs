@@ -1,7 +1,27 @@
 //! Worker Layer - Background Task Processing
 //!
-//! 实现 InferWorker，处理 TTS 推理任务
+//! 实现 InferWorker，处理 TTS 推理任务（相同内容的并发推理合并为一次）；TtsHealthMonitor 定期检查 TTS 引擎可用性；
+//! TtsWarmup 在启动时预热 TTS 引擎；CacheSweeper 定期清理过期的音频缓存；
+//! CacheMaintenance 定期将音频缓存落盘并监控磁盘占用；SessionExpirySweeper 关闭空闲会话；
+//! IdlePrefetchThrottle 暂停长时间无活动的会话的预取；
+//! GcService 定期回收空闲会话与会话音频存储（也可由管理接口手动触发）
 
+mod cache_maintenance;
+mod cache_sweeper;
+mod gc_service;
+mod idle_prefetch;
 mod infer_worker;
+mod inflight;
+mod rolling_average;
+mod session_expiry;
+mod tts_health_monitor;
+mod tts_warmup;
 
-pub use infer_worker::{InferWorker, InferWorkerConfig};
+pub use cache_maintenance::CacheMaintenance;
+pub use cache_sweeper::CacheSweeper;
+pub use gc_service::{GcService, GcSummary};
+pub use idle_prefetch::IdlePrefetchThrottle;
+pub use infer_worker::{InferWorker, InferWorkerConfig, WorkerMonitor, WorkerStatus};
+pub use session_expiry::{SessionExpirySweeper, SessionSweep};
+pub use tts_health_monitor::TtsHealthMonitor;
+pub use tts_warmup::{TtsWarmup, WarmupSummary};
@@ -0,0 +1,57 @@
+//! Rolling Average - 最近若干次推理耗时的平均值
+//!
+//! Worker 记录每个片段的推理耗时，按平均耗时估算排队任务的等待时间
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// 固定窗口的滑动平均
+pub(super) struct RollingAverage {
+    window: usize,
+    samples: Mutex<VecDeque<u64>>,
+}
+
+impl RollingAverage {
+    pub(super) fn new(window: usize) -> Self {
+        Self {
+            window: window.max(1),
+            samples: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// 记录一次耗时，超出窗口时丢弃最早的记录
+    pub(super) fn record(&self, value: u64) {
+        let mut samples = self.samples.lock().unwrap();
+        if samples.len() == self.window {
+            samples.pop_front();
+        }
+        samples.push_back(value);
+    }
+
+    /// 平均值，尚无记录时返回 None
+    pub(super) fn average(&self) -> Option<u64> {
+        let samples = self.samples.lock().unwrap();
+        if samples.is_empty() {
+            return None;
+        }
+        Some(samples.iter().sum::<u64>() / samples.len() as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rolling_window() {
+        let average = RollingAverage::new(3);
+        assert_eq!(average.average(), None);
+        for value in [1000, 2000, 3000] {
+            average.record(value);
+        }
+        assert_eq!(average.average(), Some(2000));
+        // 最早的记录移出窗口
+        average.record(7000);
+        assert_eq!(average.average(), Some(4000));
+    }
+}
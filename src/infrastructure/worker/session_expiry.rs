@@ -0,0 +1,238 @@
+//! Session Expiry - 关闭空闲会话
+//!
+//! 按较短的固定间隔检查超过 `gc.session_expire_secs` 无活动的会话：取消其待处理和推理中的任务、
+//! 解除缓存固定、通知 SessionClosed{reason:"expired"} 后关闭，并删除会话的音频文件和音频段落记录。
+//! GcService 每轮开始时同样执行一次
+
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::application::ports::{
+    AudioCachePort, AudioSegmentRepositoryPort, AudioStoragePort, GcResult, SessionManagerPort,
+    TaskManagerPort,
+};
+use crate::infrastructure::events::EventPublisher;
+
+/// 一次检查的结果
+#[derive(Debug, Clone, Default)]
+pub struct SessionSweep {
+    /// 关闭的会话数
+    pub closed_sessions: u64,
+    /// 删除的会话音频
+    pub storage: GcResult,
+}
+
+/// 空闲会话清理
+pub struct SessionExpirySweeper {
+    expire_secs: u64,
+    session_manager: Arc<dyn SessionManagerPort>,
+    task_manager: Arc<dyn TaskManagerPort>,
+    audio_cache: Arc<dyn AudioCachePort>,
+    audio_storage: Arc<dyn AudioStoragePort>,
+    audio_segment_repo: Arc<dyn AudioSegmentRepositoryPort>,
+    event_publisher: Arc<EventPublisher>,
+}
+
+impl SessionExpirySweeper {
+    pub fn new(
+        expire_secs: u64,
+        session_manager: Arc<dyn SessionManagerPort>,
+        task_manager: Arc<dyn TaskManagerPort>,
+        audio_cache: Arc<dyn AudioCachePort>,
+        audio_storage: Arc<dyn AudioStoragePort>,
+        audio_segment_repo: Arc<dyn AudioSegmentRepositoryPort>,
+        event_publisher: Arc<EventPublisher>,
+    ) -> Self {
+        Self {
+            expire_secs,
+            session_manager,
+            task_manager,
+            audio_cache,
+            audio_storage,
+            audio_segment_repo,
+            event_publisher,
+        }
+    }
+
+    /// 启动检查循环
+    pub async fn run(self: Arc<Self>, interval: Duration) {
+        tracing::info!(
+            interval_secs = interval.as_secs(),
+            session_expire_secs = self.expire_secs,
+            "SessionExpirySweeper started"
+        );
+
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            let sweep = self.sweep().await;
+            if sweep.closed_sessions > 0 {
+                tracing::info!(
+                    closed_sessions = sweep.closed_sessions,
+                    deleted_files = sweep.storage.deleted_files,
+                    freed_bytes = sweep.storage.freed_bytes,
+                    "Expired sessions closed"
+                );
+            }
+        }
+    }
+
+    /// 关闭所有过期会话并删除其音频
+    pub async fn sweep(&self) -> SessionSweep {
+        let mut sweep = SessionSweep::default();
+        for session_id in self.session_manager.get_expired_sessions(self.expire_secs) {
+            let cancelled = self.task_manager.cancel_all(&session_id);
+            self.task_manager.cleanup_session(&session_id);
+            self.audio_cache.unpin(&session_id);
+            self.event_publisher.publish_session_closed(&session_id, "expired");
+            if let Err(e) = self.session_manager.close(&session_id) {
+                tracing::warn!(session_id = %session_id, "Failed to close session: {}", e);
+            }
+            self.event_publisher.unregister_session(&session_id);
+            sweep.closed_sessions += 1;
+
+            if let Ok(id) = Uuid::parse_str(&session_id) {
+                let result = self.remove_audio(id).await;
+                sweep.storage.deleted_files += result.deleted_files;
+                sweep.storage.freed_bytes += result.freed_bytes;
+            }
+
+            tracing::info!(
+                session_id = %session_id,
+                cancelled_tasks = cancelled,
+                "Idle session closed"
+            );
+        }
+        sweep
+    }
+
+    /// 删除会话的音频文件和音频段落记录（失败只记录日志，残留由 GC 清理）
+    async fn remove_audio(&self, session_id: Uuid) -> GcResult {
+        let mut result = GcResult::default();
+        match self.audio_segment_repo.find_by_session(session_id).await {
+            Ok(segments) => {
+                result.freed_bytes = segments.iter().filter_map(|segment| segment.file_size).sum();
+            }
+            Err(e) => tracing::warn!(session_id = %session_id, error = %e, "Failed to find session audio segments"),
+        }
+        match self.audio_storage.delete_session_audio(session_id).await {
+            Ok(deleted_files) => result.deleted_files = deleted_files,
+            Err(e) => tracing::warn!(session_id = %session_id, error = %e, "Failed to delete session audio"),
+        }
+        if let Err(e) = self.audio_segment_repo.delete_by_session(session_id).await {
+            tracing::warn!(session_id = %session_id, error = %e, "Failed to delete session audio segments");
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::{
+        AudioSegmentRecord, AudioSegmentState, NovelRecord, NovelRepositoryPort, NovelStatus, Session,
+        VoiceRecord, VoiceRepositoryPort,
+    };
+    use crate::domain::voice::{TtsConfig, VoiceTags};
+    use crate::infrastructure::adapters::storage::FileAudioStorage;
+    use crate::infrastructure::memory::{PersistentSessionManager, TaskQueue};
+    use crate::infrastructure::persistence::sqlite::{
+        create_pool, run_migrations, DatabaseConfig, SqliteAudioSegmentRepository, SqliteNovelRepository,
+        SqliteSessionRepository, SqliteVoiceRepository,
+    };
+    use crate::infrastructure::{InMemoryTaskManager, SledAudioCache};
+    use chrono::Utc;
+    use std::path::PathBuf;
+
+    #[tokio::test]
+    async fn test_sweep_closes_expired_session_and_deletes_audio() {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = create_pool(&DatabaseConfig::in_memory()).await.unwrap();
+        run_migrations(&pool).await.unwrap();
+        let novel_repo = SqliteNovelRepository::new(pool.clone());
+        let voice_repo = SqliteVoiceRepository::new(pool.clone());
+        let audio_segment_repo = Arc::new(SqliteAudioSegmentRepository::new(pool.clone()));
+        let session_manager = Arc::new(PersistentSessionManager::new(Arc::new(SqliteSessionRepository::new(pool))));
+        let audio_storage = Arc::new(FileAudioStorage::new(dir.path().join("audio")).await.unwrap());
+        let sweeper = SessionExpirySweeper::new(
+            60,
+            session_manager.clone(),
+            Arc::new(InMemoryTaskManager::new(Arc::new(TaskQueue::new(10)))),
+            Arc::new(SledAudioCache::open(dir.path().join("cache"), 1024 * 1024).unwrap()),
+            audio_storage.clone(),
+            audio_segment_repo.clone(),
+            Arc::new(EventPublisher::new()),
+        );
+
+        let novel_id = Uuid::new_v4();
+        novel_repo
+            .save(&NovelRecord {
+                id: novel_id,
+                title: "测试".to_string(),
+                raw_text_path: PathBuf::from("novel.txt"),
+                total_segments: 1,
+                status: NovelStatus::Ready,
+                user_id: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            })
+            .await
+            .unwrap();
+        let voice_id = Uuid::new_v4();
+        voice_repo
+            .save(&VoiceRecord {
+                id: voice_id,
+                name: "旁白".to_string(),
+                reference_audio_path: PathBuf::from("voice.wav"),
+                processed_audio_path: None,
+                description: None,
+                prompt_text: None,
+                engine: None,
+                tts_config: TtsConfig::default(),
+                tags: VoiceTags::default(),
+                user_id: None,
+                created_at: Utc::now(),
+            })
+            .await
+            .unwrap();
+
+        let idle = Session {
+            last_activity: Utc::now() - chrono::Duration::seconds(120),
+            ..Session::new(novel_id, voice_id, 0)
+        };
+        let idle_id = session_manager.create(idle).unwrap();
+        let active_id = session_manager.create(Session::new(novel_id, voice_id, 0)).unwrap();
+        session_manager.flush().await;
+
+        let session_id = Uuid::parse_str(&idle_id).unwrap();
+        let audio_path = audio_storage.save_audio(session_id, 0, &[0; 100]).await.unwrap();
+        audio_segment_repo
+            .save(&AudioSegmentRecord {
+                id: Uuid::new_v4(),
+                session_id,
+                segment_index: 0,
+                audio_path: Some(audio_path.clone()),
+                duration_ms: Some(1000),
+                file_size: Some(100),
+                state: AudioSegmentState::Ready,
+                error_message: None,
+                created_at: Utc::now(),
+                last_accessed_at: Utc::now(),
+            })
+            .await
+            .unwrap();
+
+        let sweep = sweeper.sweep().await;
+        assert_eq!(sweep.closed_sessions, 1);
+        assert_eq!((sweep.storage.deleted_files, sweep.storage.freed_bytes), (1, 100));
+        assert!(session_manager.get(&idle_id).is_err());
+        assert!(session_manager.get(&active_id).is_ok());
+        assert!(!audio_path.exists());
+        assert!(audio_segment_repo.find_by_session(session_id).await.unwrap().is_empty());
+
+        // 已关闭的会话不再重复处理
+        assert_eq!(sweeper.sweep().await.closed_sessions, 0);
+    }
+}
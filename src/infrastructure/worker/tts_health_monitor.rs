@@ -0,0 +1,200 @@
+//! TTS Health Monitor - 定期检查 TTS 引擎可用性
+//!
+//! 按固定间隔对注册表中的所有引擎执行 health_check：
+//! - 结果持久化到 tts_engine_status 表（/api/admin/tts/health 查询）
+//! - 状态变化时在全局 WebSocket 广播 TtsEngineDown / TtsEngineUp，
+//!   前端可据此提示用户，而不是让推理请求静默挂起
+
+use chrono::Utc;
+use futures_util::future::join_all;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+use crate::application::ports::{TtsEngineStatusRecord, TtsEngineStatusRepositoryPort};
+use crate::infrastructure::adapters::TtsEngineRegistry;
+use crate::infrastructure::events::EventPublisher;
+
+/// TTS 健康检查后台任务
+pub struct TtsHealthMonitor {
+    interval: Duration,
+    engines: Arc<TtsEngineRegistry>,
+    status_repo: Arc<dyn TtsEngineStatusRepositoryPort>,
+    event_publisher: Arc<EventPublisher>,
+    /// 各引擎最近一次状态（首次检查时从数据库加载）
+    last_status: Mutex<Option<HashMap<String, TtsEngineStatusRecord>>>,
+}
+
+impl TtsHealthMonitor {
+    pub fn new(
+        interval: Duration,
+        engines: Arc<TtsEngineRegistry>,
+        status_repo: Arc<dyn TtsEngineStatusRepositoryPort>,
+        event_publisher: Arc<EventPublisher>,
+    ) -> Self {
+        Self {
+            interval,
+            engines,
+            status_repo,
+            event_publisher,
+            last_status: Mutex::new(None),
+        }
+    }
+
+    /// 启动健康检查循环
+    pub async fn run(self) {
+        tracing::info!(
+            interval_secs = self.interval.as_secs(),
+            engines = ?self.engines.names(),
+            "TtsHealthMonitor started"
+        );
+
+        let mut ticker = tokio::time::interval(self.interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            self.check_all().await;
+        }
+    }
+
+    /// 检查所有引擎一次
+    pub async fn check_all(&self) {
+        let names = self.engines.names();
+        let results = join_all(names.iter().map(|name| async move {
+            let healthy = match self.engines.get(Some(name)) {
+                Some(engine) => engine.health_check().await,
+                None => false,
+            };
+            (name.as_str(), healthy)
+        }))
+        .await;
+
+        let mut last_status = self.last_status.lock().await;
+        if last_status.is_none() {
+            let persisted = match self.status_repo.find_all().await {
+                Ok(records) => records,
+                Err(e) => {
+                    tracing::warn!(error = %e, "Failed to load TTS engine status");
+                    Vec::new()
+                }
+            };
+            *last_status = Some(
+                persisted
+                    .into_iter()
+                    .map(|record| (record.engine.clone(), record))
+                    .collect(),
+            );
+        }
+        let last_status = last_status.as_mut().unwrap();
+
+        let now = Utc::now();
+        for (name, healthy) in results {
+            let previous = last_status.get(name);
+            // 首次检查且无历史记录时视为之前健康，仅在不可用时发出事件
+            let was_healthy = previous.map(|r| r.healthy).unwrap_or(true);
+            let record = TtsEngineStatusRecord {
+                engine: name.to_string(),
+                healthy,
+                consecutive_failures: match (healthy, previous) {
+                    (true, _) => 0,
+                    (false, Some(prev)) => prev.consecutive_failures + 1,
+                    (false, None) => 1,
+                },
+                last_checked_at: now,
+                last_changed_at: match previous {
+                    Some(prev) if prev.healthy == healthy => prev.last_changed_at,
+                    _ => now,
+                },
+            };
+
+            if was_healthy && !healthy {
+                tracing::warn!(engine = %name, "TTS engine is down");
+                self.event_publisher
+                    .publish_tts_engine_down(name, record.consecutive_failures);
+            } else if !was_healthy && healthy {
+                tracing::info!(engine = %name, "TTS engine is up");
+                self.event_publisher.publish_tts_engine_up(name);
+            }
+
+            if let Err(e) = self.status_repo.save(&record).await {
+                tracing::warn!(engine = %name, error = %e, "Failed to persist TTS engine status");
+            }
+            last_status.insert(name.to_string(), record);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::{
+        InferRequest, InferResponse, RepositoryError, TtsEnginePort, TtsError,
+    };
+    use crate::infrastructure::events::WsEvent;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    struct ToggleEngine(AtomicBool);
+
+    #[async_trait]
+    impl TtsEnginePort for ToggleEngine {
+        async fn infer(&self, _request: InferRequest) -> Result<InferResponse, TtsError> {
+            Err(TtsError::ServiceError("not used".to_string()))
+        }
+
+        async fn health_check(&self) -> bool {
+            self.0.load(Ordering::Relaxed)
+        }
+    }
+
+    #[derive(Default)]
+    struct MemoryStatusRepo(std::sync::Mutex<HashMap<String, TtsEngineStatusRecord>>);
+
+    #[async_trait]
+    impl TtsEngineStatusRepositoryPort for MemoryStatusRepo {
+        async fn find_all(&self) -> Result<Vec<TtsEngineStatusRecord>, RepositoryError> {
+            Ok(self.0.lock().unwrap().values().cloned().collect())
+        }
+
+        async fn save(&self, record: &TtsEngineStatusRecord) -> Result<(), RepositoryError> {
+            self.0
+                .lock()
+                .unwrap()
+                .insert(record.engine.clone(), record.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_publishes_transitions_and_persists_status() {
+        let engine = Arc::new(ToggleEngine(AtomicBool::new(true)));
+        let repo = Arc::new(MemoryStatusRepo::default());
+        let publisher = Arc::new(EventPublisher::new());
+        let mut events = publisher.subscribe_global();
+        let monitor = TtsHealthMonitor::new(
+            Duration::from_secs(30),
+            Arc::new(TtsEngineRegistry::new(engine.clone())),
+            repo.clone(),
+            publisher,
+        );
+
+        // 健康：不发事件
+        monitor.check_all().await;
+        assert!(events.try_recv().is_err());
+
+        // 不可用：发 Down，连续失败计数递增
+        engine.0.store(false, Ordering::Relaxed);
+        monitor.check_all().await;
+        monitor.check_all().await;
+        assert!(matches!(events.try_recv().map(|e| e.event), Ok(WsEvent::TtsEngineDown { .. })));
+        assert!(events.try_recv().is_err());
+        assert_eq!(repo.0.lock().unwrap()["default"].consecutive_failures, 2);
+
+        // 恢复：发 Up
+        engine.0.store(true, Ordering::Relaxed);
+        monitor.check_all().await;
+        assert!(matches!(events.try_recv().map(|e| e.event), Ok(WsEvent::TtsEngineUp { .. })));
+        assert!(repo.0.lock().unwrap()["default"].healthy);
+    }
+}
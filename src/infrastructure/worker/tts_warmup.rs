@@ -0,0 +1,250 @@
+//! TTS Warmup - 启动预热
+//!
+//! 服务启动后对每个音色按其引擎执行一次短文本推理，让 TTS 服务提前加载模型
+//! 和参考音频，避免第一个真实片段承担冷启动耗时：
+//! - 同一引擎的音色依次预热，不同引擎并行
+//! - 单个请求失败只记录日志，不影响启动
+//! - 超过总超时后放弃剩余请求
+
+use futures_util::future::join_all;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::application::ports::{
+    InferRequest, MarkupType, TtsEnginePort, VoiceRecord, VoiceRepositoryPort,
+};
+use crate::infrastructure::adapters::{voice_audio_url, TtsEngineRegistry, VoiceAudioSigner, DEFAULT_TTS_ENGINE};
+
+/// 预热结果
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WarmupSummary {
+    pub succeeded: usize,
+    pub failed: usize,
+    /// 是否因超时提前结束
+    pub timed_out: bool,
+}
+
+/// TTS 预热任务
+pub struct TtsWarmup {
+    engines: Arc<TtsEngineRegistry>,
+    voice_repo: Arc<dyn VoiceRepositoryPort>,
+    /// 服务对外地址，用于构建参考音频下载 URL
+    base_url: String,
+    voice_audio_signer: Option<VoiceAudioSigner>,
+    text: String,
+    timeout: Duration,
+}
+
+impl TtsWarmup {
+    pub fn new(
+        engines: Arc<TtsEngineRegistry>,
+        voice_repo: Arc<dyn VoiceRepositoryPort>,
+        base_url: String,
+        voice_audio_signer: Option<VoiceAudioSigner>,
+        text: String,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            engines,
+            voice_repo,
+            base_url,
+            voice_audio_signer,
+            text,
+            timeout,
+        }
+    }
+
+    /// 预热所有音色
+    pub async fn run(&self) -> WarmupSummary {
+        let voices = match self.voice_repo.find_all().await {
+            Ok(voices) => voices,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to load voices for TTS warmup");
+                return WarmupSummary::default();
+            }
+        };
+
+        // 按引擎分组；音色引用了未注册的引擎时跳过
+        let mut by_engine: BTreeMap<String, Vec<VoiceRecord>> = BTreeMap::new();
+        for voice in voices {
+            let engine = voice.engine.clone().unwrap_or_else(|| DEFAULT_TTS_ENGINE.to_string());
+            if !self.engines.contains(&engine) {
+                tracing::warn!(voice_id = %voice.id, engine = %engine, "Skipping warmup for unknown TTS engine");
+                continue;
+            }
+            by_engine.entry(engine).or_default().push(voice);
+        }
+
+        let started = Instant::now();
+        let succeeded = AtomicUsize::new(0);
+        let failed = AtomicUsize::new(0);
+        let warm_all = join_all(by_engine.iter().map(|(engine, voices)| {
+            let (succeeded, failed) = (&succeeded, &failed);
+            async move {
+                for voice in voices {
+                    if self.warm_voice(engine, voice).await {
+                        succeeded.fetch_add(1, Ordering::Relaxed);
+                    } else {
+                        failed.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+            }
+        }));
+        let timed_out = tokio::time::timeout(self.timeout, warm_all).await.is_err();
+
+        let summary = WarmupSummary {
+            succeeded: succeeded.load(Ordering::Relaxed),
+            failed: failed.load(Ordering::Relaxed),
+            timed_out,
+        };
+        tracing::info!(
+            succeeded = summary.succeeded,
+            failed = summary.failed,
+            timed_out,
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            "TTS warmup finished"
+        );
+        summary
+    }
+
+    /// 对单个音色执行一次推理，返回是否成功
+    async fn warm_voice(&self, engine: &str, voice: &VoiceRecord) -> bool {
+        let voice_ref_path = voice
+            .processed_audio_path
+            .clone()
+            .filter(|p| p.exists())
+            .unwrap_or_else(|| voice.reference_audio_path.clone());
+        let request = InferRequest {
+            text: self.text.clone(),
+            voice_ref: voice_audio_url(&self.base_url, voice.id, self.voice_audio_signer.as_ref()),
+            voice_ref_path: Some(voice_ref_path),
+            voice_id: voice.id.to_string(),
+            prompt_text: voice.prompt_text.clone(),
+            engine: voice.engine.clone(),
+            tts_config: voice.tts_config.clone(),
+            params: Default::default(),
+            seed: None,
+            latency_sensitive: false,
+            markup: MarkupType::PlainText,
+        };
+
+        let started = Instant::now();
+        match self.engines.infer(request).await {
+            Ok(_) => {
+                tracing::debug!(
+                    engine,
+                    voice_id = %voice.id,
+                    elapsed_ms = started.elapsed().as_millis() as u64,
+                    "TTS warmup inference done"
+                );
+                true
+            }
+            Err(e) => {
+                tracing::warn!(engine, voice_id = %voice.id, error = %e, "TTS warmup inference failed");
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::{InferResponse, RepositoryError, TtsError};
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use std::path::PathBuf;
+    use std::sync::Mutex;
+    use uuid::Uuid;
+
+    /// 记录请求音色的测试引擎，`fail` 为 true 时总是失败
+    struct RecordingEngine {
+        voices: Mutex<Vec<String>>,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl TtsEnginePort for RecordingEngine {
+        async fn infer(&self, request: InferRequest) -> Result<InferResponse, TtsError> {
+            self.voices.lock().unwrap().push(request.voice_id);
+            if self.fail {
+                return Err(TtsError::ServiceError("model not loaded".to_string()));
+            }
+            Ok(InferResponse {
+                session_id: String::new(),
+                audio_data: Vec::new(),
+                duration_ms: None,
+                sample_rate: None,
+            })
+        }
+    }
+
+    struct StaticVoiceRepo(Vec<VoiceRecord>);
+
+    #[async_trait]
+    impl VoiceRepositoryPort for StaticVoiceRepo {
+        async fn save(&self, _voice: &VoiceRecord) -> Result<(), RepositoryError> {
+            Ok(())
+        }
+
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<VoiceRecord>, RepositoryError> {
+            Ok(self.0.iter().find(|v| v.id == id).cloned())
+        }
+
+        async fn find_all(&self) -> Result<Vec<VoiceRecord>, RepositoryError> {
+            Ok(self.0.clone())
+        }
+
+        async fn delete(&self, _id: Uuid) -> Result<(), RepositoryError> {
+            Ok(())
+        }
+    }
+
+    fn voice(engine: Option<&str>) -> VoiceRecord {
+        VoiceRecord {
+            id: Uuid::new_v4(),
+            name: "voice".to_string(),
+            reference_audio_path: PathBuf::from("voice.wav"),
+            processed_audio_path: None,
+            description: None,
+            prompt_text: None,
+            engine: engine.map(str::to_string),
+            tts_config: Default::default(),
+            tags: Default::default(),
+            user_id: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_warms_each_voice_on_its_engine() {
+        let default_engine = Arc::new(RecordingEngine { voices: Mutex::new(Vec::new()), fail: false });
+        let gpu_engine = Arc::new(RecordingEngine { voices: Mutex::new(Vec::new()), fail: true });
+        let registry = TtsEngineRegistry::new(default_engine.clone()).with_engine("gpu", gpu_engine.clone());
+
+        let voices = vec![voice(None), voice(None), voice(Some("gpu")), voice(Some("missing"))];
+        let gpu_voice_id = voices[2].id.to_string();
+        let warmup = TtsWarmup::new(
+            Arc::new(registry),
+            Arc::new(StaticVoiceRepo(voices)),
+            "http://localhost:5060".to_string(),
+            None,
+            "你好。".to_string(),
+            Duration::from_secs(5),
+        );
+
+        let summary = warmup.run().await;
+        assert_eq!(
+            summary,
+            WarmupSummary {
+                succeeded: 2,
+                failed: 1,
+                timed_out: false
+            }
+        );
+        assert_eq!(default_engine.voices.lock().unwrap().len(), 2);
+        assert_eq!(*gpu_engine.voices.lock().unwrap(), vec![gpu_voice_id]);
+    }
+}
@@ -5,21 +5,47 @@
 //! - Application: commands, queries, ports
 //! - Infrastructure: http, memory, worker, persistence, adapters, events
 
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
-use rovel::config::{load_config, print_config};
-use rovel::infrastructure::adapters::{HttpTtsClient, HttpTtsClientConfig, WavTranscoder};
-// use rovel::infrastructure::adapters::{FakeTtsClient, FakeTtsClientConfig};
+use rovel::application::ports::{
+    AudioCachePort, GcConfig, NovelRepositoryPort, SessionManagerPort, TaskManagerPort, TtsEnginePort,
+};
+use rovel::application::{AudioTagResolver, ReconcileStorage, ReconcileStorageHandler, VoiceCastingResolver};
+use rovel::config::{
+    load_config, print_config, AudioCacheBackend, LocalTtsConfig, QueueBackend, QueueConfig, TtsEngineKind,
+};
+use rovel::infrastructure::adapters::{
+    BalanceStrategy, FakeTtsClient, FakeTtsClientConfig, FileAudioStorage, HedgedTtsEngine, HttpTtsClient,
+    HttpTtsClientConfig, MeteredTtsEngine, RateLimitConfig, RateLimitedTtsEngine, SsmlTtsEngine,
+    TtsBackendPool, TtsBackendPoolConfig,
+    TtsEngineRegistry, TtsMetrics, WavTranscoder, DEFAULT_TTS_ENGINE, Argon2PasswordHasher, JwtTokenService, VoiceAudioSigner,
+};
+use rovel::domain::novel::SsmlOptions;
 use rovel::infrastructure::events::EventPublisher;
-use rovel::infrastructure::http::{AppState, HttpServer, ServerConfig};
-use rovel::infrastructure::memory::{InMemorySessionManager, InMemoryTaskManager};
+use rovel::infrastructure::http::{AdminServices, AppState, AuthServices, HttpServer, ServerConfig};
+use rovel::infrastructure::memory::{
+    AuditedTaskManager, InMemoryExportJobManager, InMemoryPartialAudio, InMemoryPregenerationJobManager,
+    InMemoryTaskManager, NovelQuotas, PersistentSessionManager, PersistentTaskManager, TaskQueue, TieredAudioCache,
+};
+use rovel::infrastructure::persistence::file::{FileAudioCache, FileCacheConfig};
+use rovel::infrastructure::persistence::CacheCipher;
 use rovel::infrastructure::persistence::sled::{SledAudioCache, SledCacheConfig};
 use rovel::infrastructure::persistence::sqlite::{
-    create_pool, run_migrations, DatabaseConfig,
-    SqliteNovelRepository, SqliteVoiceRepository,
+    create_pool, run_migrations, DatabaseConfig, SqliteAudioSegmentRepository, SqliteListeningStatsRepository,
+    SqliteQueuedTaskRepository, SqliteReadingProgressRepository, SqliteTaskEventRepository,
+    SqliteNovelRepository, SqliteSessionRepository, SqliteSettingsRepository, SqliteTtsEngineStatusRepository,
+    SqliteUserRepository, SqliteVoiceCastingRepository, SqliteVoiceRepository,
+};
+use rovel::infrastructure::presets::VoicePresetSeeder;
+use rovel::infrastructure::worker::{
+    CacheMaintenance, CacheSweeper, GcService, IdlePrefetchThrottle, InferWorker, InferWorkerConfig,
+    SessionExpirySweeper, TtsHealthMonitor, TtsWarmup,
 };
-use rovel::infrastructure::worker::{InferWorker, InferWorkerConfig};
-use tokio::sync::mpsc;
+use tokio::sync::broadcast;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -58,66 +84,439 @@ async fn main() -> anyhow::Result<()> {
     // 创建 Repository 适配器
     let novel_repo = Arc::new(SqliteNovelRepository::new(pool.clone()));
     let voice_repo = Arc::new(SqliteVoiceRepository::new(pool.clone()));
+    let settings_repo = Arc::new(SqliteSettingsRepository::new(pool.clone()));
+    let voice_casting_repo = Arc::new(SqliteVoiceCastingRepository::new(pool.clone()));
+    let tts_engine_status_repo = Arc::new(SqliteTtsEngineStatusRepository::new(pool.clone()));
+    let listening_stats_repo = Arc::new(SqliteListeningStatsRepository::new(pool.clone()));
+    let reading_progress_repo = Arc::new(SqliteReadingProgressRepository::new(pool.clone()));
+    let task_event_repo = Arc::new(SqliteTaskEventRepository::new(pool.clone()));
+    let audio_segment_repo = Arc::new(SqliteAudioSegmentRepository::new(pool.clone()));
+    let session_repo = Arc::new(SqliteSessionRepository::new(pool.clone()));
 
-    // 创建 HTTP TTS 引擎
-    let tts_config = HttpTtsClientConfig {
-        base_url: config.tts.url.clone(),
-        timeout_secs: config.tts.timeout_secs,
-        max_retries: config.tts.max_retries,
+    // 创建 TTS 引擎：http 引擎多个地址时使用后端池负载均衡，fake 引擎返回固定音频，
+    // local 引擎在进程内推理
+    let pool_config = TtsBackendPoolConfig {
+        strategy: config.tts.balance,
+        failure_threshold: config.tts.failure_threshold,
+        cooldown: Duration::from_secs(config.tts.cooldown_secs),
     };
-    let tts_engine = Arc::new(HttpTtsClient::new(tts_config)?);
-
-    // // 创建 Fake TTS 引擎（测试用，始终返回固定音频）
-    // let tts_config = FakeTtsClientConfig {
-    //     audio_file_path: std::path::PathBuf::from("/home/github/rovel/Speaker_1.wav"),
-    //     duration_ms: 5000,
-    //     sample_rate: 22050,
-    // };
-    // let tts_engine = Arc::new(FakeTtsClient::new(tts_config)?);;
+    let hedge_delay = Duration::from_millis(config.tts.hedge_delay_ms);
+    let fake_config = FakeTtsClientConfig {
+        audio_file_path: config.tts.fake.audio_path.clone(),
+        duration_ms: config.tts.fake.duration_ms,
+        sample_rate: config.tts.fake.sample_rate,
+    };
+    let build_engine = |kind: TtsEngineKind,
+                        urls: Vec<String>,
+                        strategy: BalanceStrategy,
+                        hedge_url: Option<String>,
+                        client_config: HttpTtsClientConfig|
+     -> anyhow::Result<Arc<dyn TtsEnginePort>> {
+        match kind {
+            TtsEngineKind::Http => {}
+            TtsEngineKind::Fake => return Ok(Arc::new(FakeTtsClient::new(fake_config.clone())?)),
+            TtsEngineKind::Local => return build_local_engine(&config.tts.local),
+        }
+        let mut backends: Vec<(String, Arc<dyn TtsEnginePort>)> = Vec::new();
+        for url in urls {
+            let client = HttpTtsClient::new(HttpTtsClientConfig {
+                base_url: url.clone(),
+                ..client_config.clone()
+            })?;
+            backends.push((url, Arc::new(client)));
+        }
+        let engine: Arc<dyn TtsEnginePort> = if backends.len() == 1 {
+            backends.pop().unwrap().1
+        } else {
+            Arc::new(TtsBackendPool::new(
+                backends,
+                TtsBackendPoolConfig {
+                    strategy,
+                    ..pool_config.clone()
+                },
+            ))
+        };
+        let Some(hedge_url) = hedge_url else {
+            return Ok(engine);
+        };
+        let secondary = HttpTtsClient::new(HttpTtsClientConfig {
+            base_url: hedge_url,
+            ..client_config
+        })?;
+        Ok(Arc::new(HedgedTtsEngine::new(engine, Arc::new(secondary), hedge_delay)))
+    };
+    // 支持 SSML 的引擎：推理文本先转换为 SSML；
+    // 每个命名引擎记录请求延迟、错误与进行中请求数，并按配置限流（等待时间不计入延迟）
+    let ssml_options = SsmlOptions {
+        paragraph_break_ms: config.tts.ssml_paragraph_break_ms,
+        ..Default::default()
+    };
+    let tts_metrics = Arc::new(TtsMetrics::new());
+    let decorate = |name: &str,
+                    engine: Arc<dyn TtsEnginePort>,
+                    ssml: bool,
+                    rate_limit: RateLimitConfig|
+     -> Arc<dyn TtsEnginePort> {
+        let engine: Arc<dyn TtsEnginePort> = if ssml {
+            Arc::new(SsmlTtsEngine::new(engine, ssml_options.clone()))
+        } else {
+            engine
+        };
+        let engine: Arc<dyn TtsEnginePort> = Arc::new(MeteredTtsEngine::new(engine, tts_metrics.engine(name)));
+        if rate_limit.is_unlimited() {
+            engine
+        } else {
+            Arc::new(RateLimitedTtsEngine::new(engine, rate_limit))
+        }
+    };
+    let mut tts_engines = TtsEngineRegistry::new(decorate(
+        DEFAULT_TTS_ENGINE,
+        build_engine(
+            config.tts.engine,
+            config.tts.backend_urls(),
+            config.tts.balance,
+            config.tts.hedge_url.clone(),
+            HttpTtsClientConfig {
+                base_url: config.tts.url.clone(),
+                timeout_secs: config.tts.timeout_secs,
+                max_retries: config.tts.max_retries,
+                batch: config.tts.batch,
+                reference_mode: config.tts.reference_mode,
+                api_key: config.tts.api_key.clone(),
+                api_key_header: config.tts.api_key_header.clone(),
+            },
+        )?,
+        config.tts.ssml,
+        config.tts.rate_limit(),
+    ));
+    for (name, engine) in &config.tts.engines {
+        tts_engines = tts_engines.with_engine(
+            name,
+            decorate(
+                name,
+                build_engine(
+                    engine.engine,
+                    engine.backend_urls(),
+                    engine.balance,
+                    engine.hedge_url.clone(),
+                    HttpTtsClientConfig {
+                        base_url: engine.url.clone(),
+                        timeout_secs: engine.timeout_secs,
+                        max_retries: engine.max_retries,
+                        batch: engine.batch,
+                        reference_mode: engine.reference_mode,
+                        api_key: engine.api_key.clone().or_else(|| config.tts.api_key.clone()),
+                        api_key_header: engine
+                            .api_key_header
+                            .clone()
+                            .or_else(|| config.tts.api_key_header.clone()),
+                    },
+                )?,
+                engine.ssml,
+                engine.rate_limit(),
+            ),
+        );
+    }
+    let tts_engines = Arc::new(tts_engines);
 
-    // 创建 Sled 音频缓存
-    let cache_config = SledCacheConfig {
-        db_path: format!("{}/cache.sled", config.storage.audio_dir.display()),
-        max_size_bytes: 10 * 1024 * 1024 * 1024, // 10GB
+    // 创建音频缓存
+    let cache_max_size_bytes = 10 * 1024 * 1024 * 1024; // 10GB
+    let cache_ttl_secs = (config.gc.cache_ttl_days > 0).then(|| config.gc.cache_ttl_days * 86400);
+    let cache_cipher = CacheCipher::load(
+        config.storage.cache_encryption_key.as_deref(),
+        config.storage.cache_encryption_key_file.as_deref(),
+    )?
+    .map(Arc::new);
+    let novel_quotas = Arc::new(NovelQuotas::new(
+        config.storage.novel_cache_quota_bytes,
+        config.storage.novel_cache_quotas.clone().into_iter().collect(),
+    ));
+    let persistent_cache: Arc<dyn AudioCachePort> = match config.storage.audio_cache_backend {
+        AudioCacheBackend::Sled => {
+            let cache_config = SledCacheConfig {
+                db_path: format!("{}/cache.sled", config.storage.audio_dir.display()),
+                max_size_bytes: cache_max_size_bytes,
+                compress: config.storage.compress_audio_cache,
+                ttl_secs: cache_ttl_secs,
+                quotas: novel_quotas.clone(),
+                cipher: cache_cipher.clone(),
+            };
+            Arc::new(SledAudioCache::new(&cache_config)?)
+        }
+        AudioCacheBackend::File => {
+            let cache_config = FileCacheConfig {
+                root_dir: config.storage.audio_dir.join("cache"),
+                max_size_bytes: cache_max_size_bytes,
+                ttl_secs: cache_ttl_secs,
+                quotas: novel_quotas.clone(),
+                cipher: cache_cipher,
+            };
+            Arc::new(FileAudioCache::new(&cache_config).await?)
+        }
     };
-    let audio_cache = Arc::new(SledAudioCache::new(&cache_config)?);
+    let audio_cache: Arc<dyn AudioCachePort> = if config.storage.memory_cache_bytes > 0 {
+        Arc::new(TieredAudioCache::new(persistent_cache, config.storage.memory_cache_bytes))
+    } else {
+        persistent_cache
+    };
+
+    // 流式推理中的音频缓冲（Worker 写入，音频接口读取）
+    let partial_audio = Arc::new(InMemoryPartialAudio::new());
 
     // 创建事件发布器
     let event_publisher = Arc::new(EventPublisher::new());
 
-    // 创建任务队列
-    let (task_tx, task_rx) = mpsc::channel(1000);
+    // 小说缓存达到配额时广播事件
+    let mut quota_events = novel_quotas.subscribe();
+    let quota_publisher = event_publisher.clone();
+    let quota_novels = novel_repo.clone();
+    tokio::spawn(async move {
+        loop {
+            match quota_events.recv().await {
+                Ok(event) => {
+                    // 只通知小说所属用户
+                    let owner = match quota_novels.find_by_id(event.novel_id).await {
+                        Ok(Some(novel)) => novel.user_id,
+                        _ => continue,
+                    };
+                    quota_publisher.publish_novel_cache_quota_reached(
+                        event.novel_id,
+                        event.used_bytes,
+                        event.quota_bytes,
+                        owner,
+                    )
+                }
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
+
+    // 创建任务队列（按优先级出队：当前片段 > 预取 > 导出/预生成，同优先级各会话轮流）
+    let task_queue = Arc::new(TaskQueue::new(config.queue.capacity));
+    // Redis 队列：本实例提交的任务经发件队列写入 Redis，Worker 消费本实例从 Redis 取出的任务
+    let outbox = match config.queue.backend {
+        QueueBackend::Memory => task_queue.clone(),
+        QueueBackend::Redis => Arc::new(TaskQueue::new(config.queue.capacity)),
+    };
+
+    // 创建 Session 和 Task 管理器（均写穿到数据库，恢复重启前的会话和任务）
+    let session_manager = Arc::new(PersistentSessionManager::new(session_repo.clone()));
+    match session_manager.restore().await {
+        Ok(restored) => tracing::info!(restored, "Sessions restored"),
+        Err(e) => tracing::warn!(error = %e, "Failed to restore sessions"),
+    }
+    let persistent_tasks = Arc::new(PersistentTaskManager::new(
+        InMemoryTaskManager::new(outbox.clone()),
+        session_manager.clone(),
+        Arc::new(SqliteQueuedTaskRepository::new(pool.clone())),
+    ));
+    // 重新排队上次运行（包括崩溃）未完成的推理任务
+    match persistent_tasks.restore().await {
+        Ok(restored) => tracing::info!(restored, "Queued tasks restored"),
+        Err(e) => tracing::warn!(error = %e, "Failed to restore queued tasks"),
+    }
+    let shared_queue = match config.queue.backend {
+        QueueBackend::Memory => None,
+        QueueBackend::Redis => Some(
+            build_shared_queue(
+                &config.queue,
+                persistent_tasks.clone(),
+                session_manager.clone(),
+                outbox,
+                task_queue.clone(),
+                event_publisher.clone(),
+            )
+            .await?,
+        ),
+    };
+    let queued_tasks: Arc<dyn TaskManagerPort> = match &shared_queue {
+        Some(shared) => shared.tasks.clone(),
+        None => persistent_tasks.clone(),
+    };
+    let worker_sessions: Arc<dyn SessionManagerPort> = match &shared_queue {
+        Some(shared) => shared.sessions.clone(),
+        None => session_manager.clone(),
+    };
+    // 记录任务生命周期（审计日志），清理超过保留时长的日志
+    let task_manager = Arc::new(AuditedTaskManager::new(queued_tasks, task_event_repo.clone()));
+    match task_manager.prune().await {
+        Ok(pruned) => tracing::debug!(pruned, "Old task events pruned"),
+        Err(e) => tracing::warn!(error = %e, "Failed to prune task events"),
+    }
+
+    // 创建音频转码器：写入缓存前是否转码由 Worker 按 audio.transcode_enabled 决定，
+    // 音频接口的按需转码始终可用
+    let audio_transcoder = Arc::new(
+        WavTranscoder::new(true)
+            .with_resample_quality(config.audio.resample_quality),
+    );
+
+    // 注册内置音色预置（失败不影响启动）
+    let preset_seeder = VoicePresetSeeder::new(
+        voice_repo.clone(),
+        audio_cache.clone(),
+        audio_transcoder.clone(),
+        config.audio.reference.normalize_config(),
+        config.storage.voices_dir.clone(),
+    );
+    if let Err(e) = preset_seeder.seed(&config.storage.presets_dir).await {
+        tracing::warn!(error = %e, "Failed to seed voice presets");
+    }
+
+    // 角色配音解析（Worker 与 HTTP 共用）
+    let voice_casting = VoiceCastingResolver::new(
+        novel_repo.clone(),
+        voice_casting_repo.clone(),
+        voice_repo.clone(),
+        config.tts.speaker_detection,
+    );
 
-    // 创建内存 Session 和 Task 管理器
-    let session_manager = Arc::new(InMemorySessionManager::new());
-    let task_manager = Arc::new(InMemoryTaskManager::new(task_tx));
+    let audio_storage = Arc::new(FileAudioStorage::new(&config.storage.audio_dir).await?);
 
-    // 创建音频转码器
-    let audio_transcoder = Arc::new(WavTranscoder::new(config.audio.transcode_enabled));
+    // 启用账号时参考音频下载链接需要签名（TTS 服务下载时不携带登录令牌）
+    let voice_audio_signer = match (config.auth.enabled, config.auth.jwt_secret.as_deref()) {
+        (true, Some(jwt_secret)) => Some(VoiceAudioSigner::new(jwt_secret)),
+        _ => None,
+    };
 
     // 创建 InferWorker
     let worker_config = InferWorkerConfig {
         max_concurrent: 2,
         base_url: config.server.public_base_url(),
+        voice_audio_signer: voice_audio_signer.clone(),
         audio: config.audio.clone(),
+        streaming: config.tts.streaming,
+        batch_size: config.tts.batch_size,
+        validate_audio: config.tts.validate_audio,
+        seed: config.tts.seed,
+        store_session_audio: config.storage.store_session_audio,
     };
     let worker = InferWorker::new(
         worker_config,
-        task_rx,
+        task_queue.clone(),
         task_manager.clone(),
-        session_manager.clone(),
-        tts_engine.clone(),
+        worker_sessions,
+        tts_engines.clone(),
         audio_cache.clone(),
+        partial_audio.clone(),
         voice_repo.clone(),
-        audio_transcoder,
+        audio_transcoder.clone(),
+        audio_segment_repo.clone(),
+        audio_storage.clone(),
         event_publisher.clone(),
+        voice_casting.clone(),
+        AudioTagResolver::new(novel_repo.clone(), voice_repo.clone()),
     );
 
     // 启动 Worker
-    tokio::spawn(worker.run());
+    let worker_monitor = worker.monitor();
+    let worker_handle = tokio::spawn(worker.run());
+
+    // 启动 TTS 健康检查
+    if config.tts.health_check_interval_secs > 0 {
+        let monitor = TtsHealthMonitor::new(
+            Duration::from_secs(config.tts.health_check_interval_secs),
+            tts_engines.clone(),
+            tts_engine_status_repo.clone(),
+            event_publisher.clone(),
+        );
+        tokio::spawn(monitor.run());
+    }
+
+    // 启动音频缓存定期落盘与磁盘占用监控
+    if config.storage.cache_flush_interval_secs > 0 {
+        let maintenance = CacheMaintenance::new(
+            Duration::from_secs(config.storage.cache_flush_interval_secs),
+            audio_cache.clone(),
+        );
+        tokio::spawn(maintenance.run());
+    }
+
+    // 启动音频缓存过期清理
+    if config.gc.enabled && config.gc.cache_ttl_days > 0 {
+        let sweeper = CacheSweeper::new(
+            Duration::from_secs(config.gc.interval_secs),
+            audio_cache.clone(),
+        );
+        tokio::spawn(sweeper.run());
+    }
+
+    // 启动时核对存储与数据库记录，清理上次崩溃留下的残留
+    let reconciler = ReconcileStorageHandler::new(
+        audio_cache.clone(),
+        audio_storage.clone(),
+        session_repo.clone(),
+        audio_segment_repo.clone(),
+        novel_repo.clone(),
+    );
+    tokio::spawn(async move {
+        if let Err(e) = reconciler.handle(ReconcileStorage).await {
+            tracing::warn!(error = %e, "Storage reconciliation failed");
+        }
+    });
+
+    // 启动空闲会话清理（不依赖 gc.enabled，GC 每轮同样执行一次）
+    let session_expiry = Arc::new(SessionExpirySweeper::new(
+        config.gc.session_expire_secs,
+        session_manager.clone(),
+        task_manager.clone(),
+        audio_cache.clone(),
+        audio_storage.clone(),
+        audio_segment_repo.clone(),
+        event_publisher.clone(),
+    ));
+    if config.gc.session_sweep_interval_secs > 0 {
+        tokio::spawn(
+            session_expiry
+                .clone()
+                .run(Duration::from_secs(config.gc.session_sweep_interval_secs)),
+        );
+    }
+
+    // 启动 GC：回收空闲会话与会话音频存储（未启用时仍可通过管理接口手动触发）
+    let gc_service = Arc::new(GcService::new(
+        GcConfig {
+            window_evict_delay_secs: config.gc.window_evict_delay_secs,
+            session_expire_secs: config.gc.session_expire_secs,
+            max_storage_bytes: config.gc.max_storage_bytes,
+            gc_interval_secs: config.gc.interval_secs,
+        },
+        session_expiry,
+        session_repo.clone(),
+        audio_segment_repo.clone(),
+        audio_storage.clone(),
+        event_publisher.clone(),
+    ));
+    if config.gc.enabled {
+        tokio::spawn(gc_service.clone().run());
+    }
+
+    // TTS 预热：在后台与 HTTP 服务同时进行（url 模式下 TTS 服务需从本服务下载参考音频），
+    // 预热完成前 /api/ready 返回 503
+    let ready = Arc::new(AtomicBool::new(!config.tts.warmup));
+    if config.tts.warmup {
+        let warmup = TtsWarmup::new(
+            tts_engines.clone(),
+            voice_repo.clone(),
+            config.server.public_base_url(),
+            voice_audio_signer.clone(),
+            config.tts.warmup_text.clone(),
+            Duration::from_secs(config.tts.warmup_timeout_secs),
+        );
+        let ready = ready.clone();
+        tokio::spawn(async move {
+            warmup.run().await;
+            ready.store(true, Ordering::Release);
+            tracing::info!("Service ready");
+        });
+    }
 
     // 创建 HTTP 服务器
-    let mut server_config = ServerConfig::new(&config.server.host, config.server.port);
+    let mut server_config = ServerConfig::new(&config.server.host, config.server.port)
+        .with_trust_forwarded_for(config.server.trust_forwarded_for)
+        .with_cors(config.server.cors.clone())
+        .with_compression(config.server.compression.clone());
     
     // 配置静态文件服务
     if config.server.static_files.enabled {
@@ -126,17 +525,93 @@ async fn main() -> anyhow::Result<()> {
             config.server.static_files.path.clone(),
         );
     }
+
+    // 配置 HTTPS（证书路径已在加载配置时校验）
+    if let (true, Some(cert_path), Some(key_path)) = (
+        config.server.tls.enabled,
+        &config.server.tls.cert_path,
+        &config.server.tls.key_path,
+    ) {
+        let reload_interval = (config.server.tls.reload_interval_secs > 0)
+            .then(|| Duration::from_secs(config.server.tls.reload_interval_secs));
+        server_config = server_config.with_tls(cert_path.clone(), key_path.clone(), reload_interval);
+    }
     
+    // 用户账号：启用时按用户隔离小说、音色和会话（密钥已在加载配置时校验）
+    let auth = match (config.auth.enabled, config.auth.jwt_secret.as_deref()) {
+        (true, Some(jwt_secret)) => Some(AuthServices {
+            user_repo: Arc::new(SqliteUserRepository::new(pool.clone())),
+            password_hasher: Arc::new(Argon2PasswordHasher::new()),
+            tokens: Arc::new(JwtTokenService::new(
+                jwt_secret,
+                Duration::from_secs(config.auth.token_ttl_secs),
+            )),
+            voice_audio_signer: VoiceAudioSigner::new(jwt_secret),
+            allow_registration: config.auth.allow_registration,
+        }),
+        _ => None,
+    };
+
+    // 管理接口：未启用账号且未设置管理令牌时关闭
+    if !config.auth.enabled && config.auth.admin_token.is_none() {
+        tracing::warn!("Admin API is disabled; set auth.admin_token or enable auth to use /api/admin");
+    }
+    let admin = AdminServices {
+        token: config.auth.admin_token.clone(),
+        worker: worker_monitor,
+        gc: gc_service,
+        config: serde_json::to_value(config.redacted()).expect("config is serializable"),
+    };
+
+    let shutdown_cache = audio_cache.clone();
+    let shutdown_sessions = session_manager.clone();
+    let shutdown_tasks = persistent_tasks.clone();
+    let shutdown_task_events = task_manager.clone();
     let state = AppState::new(
         session_manager,
         task_manager,
         novel_repo,
         voice_repo,
+        settings_repo,
+        voice_casting_repo,
+        tts_engine_status_repo,
+        listening_stats_repo,
+        reading_progress_repo,
+        task_event_repo,
         audio_cache,
-        tts_engine,
+        partial_audio,
+        audio_storage,
+        session_repo,
+        audio_segment_repo,
+        tts_engines,
+        tts_metrics,
         event_publisher,
+        audio_transcoder,
+        config.audio.reference.normalize_config(),
+        config.audio.transcode_config(),
+        Arc::new(InMemoryExportJobManager::new()),
+        config.storage.exports_dir.clone(),
+        Arc::new(InMemoryPregenerationJobManager::new()),
+        voice_casting,
+        config.prefetch.settings(),
+        config.sessions.limits(),
+        config.pregeneration.schedule(),
+        auth,
+        admin,
+        ready.clone(),
     );
 
+    // 暂停长时间无活动的会话的预取（与空闲会话清理使用同一检查间隔）
+    if config.prefetch.enabled
+        && config.prefetch.idle_pause_secs > 0
+        && config.gc.session_sweep_interval_secs > 0
+    {
+        tokio::spawn(
+            IdlePrefetchThrottle::new(state.prefetch.clone())
+                .run(Duration::from_secs(config.gc.session_sweep_interval_secs)),
+        );
+    }
+
     let server = HttpServer::new(server_config, state);
 
     tracing::info!("Starting HTTP server...");
@@ -151,7 +626,103 @@ async fn main() -> anyhow::Result<()> {
         })
         .await?;
 
+    // 停止出队，等待进行中的推理完成；剩余任务已写入数据库，下次启动时重新排队
+    task_queue.close();
+    let drain = Duration::from_secs(config.tts.shutdown_drain_secs);
+    if tokio::time::timeout(drain, worker_handle).await.is_err() {
+        tracing::warn!(drain_secs = drain.as_secs(), "In-flight inference did not finish before shutdown");
+    }
+    shutdown_tasks.flush().await;
+    shutdown_task_events.flush().await;
+    if let Some(shared) = shared_queue {
+        shared.flush.await;
+    }
+
+    // 写完尚未落库的会话变更，落盘缓冲中的缓存写入
+    shutdown_sessions.flush().await;
+    if let Err(e) = shutdown_cache.flush().await {
+        tracing::warn!(error = %e, "Failed to flush audio cache on shutdown");
+    }
+
     tracing::info!("Server shutdown complete");
 
     Ok(())
 }
+
+/// 共享推理队列（queue.backend = "redis"）
+struct SharedQueue {
+    /// 包装本实例任务管理器、经 Redis 分发任务的任务管理器
+    tasks: Arc<dyn TaskManagerPort>,
+    /// Worker 使用的会话管理器（其他实例任务所属的会话视为有效）
+    sessions: Arc<dyn SessionManagerPort>,
+    /// 关闭服务前等待 Redis 写入完成
+    flush: Pin<Box<dyn Future<Output = ()> + Send>>,
+}
+
+/// 连接 Redis 共享队列
+#[cfg(feature = "redis-queue")]
+async fn build_shared_queue(
+    config: &QueueConfig,
+    tasks: Arc<dyn TaskManagerPort>,
+    sessions: Arc<dyn SessionManagerPort>,
+    outbox: Arc<TaskQueue>,
+    inbox: Arc<TaskQueue>,
+    event_publisher: Arc<EventPublisher>,
+) -> anyhow::Result<SharedQueue> {
+    use rovel::infrastructure::adapters::{RedisQueueConfig, RedisTaskManager};
+
+    let queue_config = RedisQueueConfig {
+        url: config
+            .redis_url
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("queue.redis_url is not configured"))?,
+        key_prefix: config.key_prefix.clone(),
+        group: config.group.clone(),
+        capacity: config.capacity,
+        read_ahead: config.read_ahead,
+        claim_idle: Duration::from_secs(config.claim_idle_secs),
+    };
+    let manager = Arc::new(
+        RedisTaskManager::connect(queue_config, tasks, outbox, inbox, event_publisher)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to connect to Redis task queue: {}", e))?,
+    );
+    let sessions = Arc::new(manager.sessions(sessions));
+    Ok(SharedQueue {
+        tasks: manager.clone(),
+        sessions,
+        flush: Box::pin(async move { manager.flush().await }),
+    })
+}
+
+#[cfg(not(feature = "redis-queue"))]
+async fn build_shared_queue(
+    _config: &QueueConfig,
+    _tasks: Arc<dyn TaskManagerPort>,
+    _sessions: Arc<dyn SessionManagerPort>,
+    _outbox: Arc<TaskQueue>,
+    _inbox: Arc<TaskQueue>,
+    _event_publisher: Arc<EventPublisher>,
+) -> anyhow::Result<SharedQueue> {
+    anyhow::bail!("Redis task queue requires building with `--features redis-queue`")
+}
+
+/// 创建本地 TTS 引擎
+#[cfg(feature = "local-tts")]
+fn build_local_engine(config: &LocalTtsConfig) -> anyhow::Result<Arc<dyn TtsEnginePort>> {
+    use rovel::infrastructure::adapters::{init_onnxruntime, LocalTtsEngine, PiperModel};
+
+    let model_path = config
+        .model_path
+        .clone()
+        .ok_or_else(|| anyhow::anyhow!("tts.local.model_path is not configured"))?;
+    init_onnxruntime(config.onnxruntime_path.as_deref())?;
+    let model = PiperModel::new(model_path)?;
+    tracing::info!(model = %model.model_path().display(), "Local TTS engine initialized");
+    Ok(Arc::new(LocalTtsEngine::new(Arc::new(model), config.max_concurrent)))
+}
+
+#[cfg(not(feature = "local-tts"))]
+fn build_local_engine(_config: &LocalTtsConfig) -> anyhow::Result<Arc<dyn TtsEnginePort>> {
+    anyhow::bail!("Local TTS engine requires building with `--features local-tts`")
+}